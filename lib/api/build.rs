@@ -125,6 +125,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.default_timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("CreateCollection.default_search_params", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -132,6 +134,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.default_timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("UpdateCollection.default_search_params", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("CollectionConfig.params", ""),
@@ -141,7 +145,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("CollectionParams.vectors_config", ""),
             ("ChangeAliases.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
-            ("HnswConfigDiff.ef_construct", "custom(function = \"crate::grpc::validate::validate_u64_range_min_4\")"),
+            ("HnswConfigDiff.m", "custom(function = \"crate::grpc::validate::validate_u64_range_max_1024\")"),
+            ("HnswConfigDiff.ef_construct", "custom(function = \"crate::grpc::validate::validate_u64_range_min_4_max_10000\")"),
+            ("HnswConfigDiff.full_scan_threshold", "custom(function = \"crate::grpc::validate::validate_u64_range_min_10\")"),
+            ("HnswConfigDiff.payload_m", "custom(function = \"crate::grpc::validate::validate_u64_range_max_1024\")"),
             ("WalConfigDiff.wal_capacity_mb", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("OptimizersConfigDiff.deleted_threshold", "custom(function = \"crate::grpc::validate::validate_f64_range_1\")"),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom(function = \"crate::grpc::validate::validate_u64_range_min_100\")"),
@@ -161,6 +168,10 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollectionClusterSetupRequest.operation", ""),
             ("StrictModeConfig.max_query_limit", "range(min = 1)"),
             ("StrictModeConfig.max_timeout", "range(min = 1)"),
+            ("StrictModeConfig.max_distinct_values", "range(min = 1)"),
+            ("StrictModeConfig.max_filter_conditions", "range(min = 1)"),
+            ("StrictModeConfig.max_result_points", "range(min = 1)"),
+            ("StrictModeConfig.max_payload_bytes", "range(min = 1)"),
         ], &[
             "ListCollectionsRequest",
             "CollectionParamsDiff",
@@ -182,6 +193,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WaitForShardStateRequest.timeout", "range(min = 1)"),
             ("GetShardRecoveryPointRequest.collection_name", "length(min = 1, max = 255)"),
             ("UpdateShardCutoffPointRequest.collection_name", "length(min = 1, max = 255)"),
+            ("GetHashRingsRequest.collection_name", "length(min = 1, max = 255)"),
         ], &[])
         // Service: points.proto
         .validates(&[
@@ -218,6 +230,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("SearchParams.quantization", ""),
+            ("SearchParams.score_precision", "custom(function = \"crate::grpc::validate::validate_u32_range_max_9\")"),
             ("QuantizationSearchParams.oversampling", "custom(function = \"crate::grpc::validate::validate_f64_range_min_1\")"),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
@@ -314,6 +327,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("QueryBatchPointsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("FacetCountsInternal.collection_name", "length(min = 1, max = 255)"),
             ("FacetCountsInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("DistinctValuesInternal.collection_name", "length(min = 1, max = 255)"),
+            ("DistinctValuesInternal.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
         ], &[])
         // Service: raft_service.proto
         .validates(&[
@@ -18,12 +18,13 @@ use uuid::Uuid;
 
 use super::qdrant::raw_query::RawContextPair;
 use super::qdrant::{
-    raw_query, start_from, BinaryQuantization, BoolIndexParams, CompressionRatio,
-    DatetimeIndexParams, DatetimeRange, Direction, FacetHit, FacetHitInternal, FacetValue,
-    FacetValueInternal, FieldType, FloatIndexParams, GeoIndexParams, GeoLineString, GroupId,
-    KeywordIndexParams, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy,
-    OrderValue, Range, RawVector, RecommendStrategy, SearchPointGroups, SearchPoints,
-    ShardKeySelector, SparseIndices, StartFrom, UuidIndexParams, WithLookup,
+    raw_query, start_from, tie_break, ArrayMatch, BinaryQuantization, BoolIndexParams,
+    CompressionRatio, DatetimeIndexParams, DatetimeRange, Direction, FacetHit, FacetHitInternal,
+    FacetValue, FacetValueInternal, FieldType, FloatIndexParams, GeoIndexParams, GeoLineString,
+    GroupId, KeywordIndexParams, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy,
+    OrderValue, PayloadTieBreak, Range, RawVector, RecommendStrategy, SearchPointGroups,
+    SearchPoints, ShardKeySelector, SparseIndices, StartFrom, TieBreak, UuidIndexParams,
+    WithLookup,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
@@ -227,6 +228,7 @@ impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams
             index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
                 is_tenant: params.is_tenant,
                 on_disk: params.on_disk,
+                case_insensitive: params.case_insensitive,
             })),
         }
     }
@@ -385,6 +387,7 @@ impl TryFrom<KeywordIndexParams> for segment::data_types::index::KeywordIndexPar
             r#type: KeywordIndexType::Keyword,
             is_tenant: params.is_tenant,
             on_disk: params.on_disk,
+            case_insensitive: params.case_insensitive,
         })
     }
 }
@@ -501,32 +504,33 @@ impl TryFrom<IndexParams> for segment::types::PayloadSchemaParams {
     }
 }
 
+impl TryFrom<PayloadSchemaType> for segment::types::PayloadSchemaType {
+    type Error = Status;
+
+    fn try_from(schema_type: PayloadSchemaType) -> Result<Self, Self::Error> {
+        match schema_type {
+            PayloadSchemaType::Keyword => Ok(segment::types::PayloadSchemaType::Keyword),
+            PayloadSchemaType::Integer => Ok(segment::types::PayloadSchemaType::Integer),
+            PayloadSchemaType::Float => Ok(segment::types::PayloadSchemaType::Float),
+            PayloadSchemaType::Geo => Ok(segment::types::PayloadSchemaType::Geo),
+            PayloadSchemaType::Text => Ok(segment::types::PayloadSchemaType::Text),
+            PayloadSchemaType::Bool => Ok(segment::types::PayloadSchemaType::Bool),
+            PayloadSchemaType::Datetime => Ok(segment::types::PayloadSchemaType::Datetime),
+            PayloadSchemaType::Uuid => Ok(segment::types::PayloadSchemaType::Uuid),
+            PayloadSchemaType::UnknownType => Err(Status::invalid_argument(
+                "Malformed payload schema".to_string(),
+            )),
+        }
+    }
+}
+
 impl TryFrom<PayloadSchemaInfo> for segment::types::PayloadIndexInfo {
     type Error = Status;
 
     fn try_from(schema: PayloadSchemaInfo) -> Result<Self, Self::Error> {
-        let data_type = match PayloadSchemaType::try_from(schema.data_type) {
-            Err(_) => {
-                return Err(Status::invalid_argument(
-                    "Malformed payload schema".to_string(),
-                ));
-            }
-            Ok(data_type) => match data_type {
-                PayloadSchemaType::Keyword => segment::types::PayloadSchemaType::Keyword,
-                PayloadSchemaType::Integer => segment::types::PayloadSchemaType::Integer,
-                PayloadSchemaType::Float => segment::types::PayloadSchemaType::Float,
-                PayloadSchemaType::Geo => segment::types::PayloadSchemaType::Geo,
-                PayloadSchemaType::Text => segment::types::PayloadSchemaType::Text,
-                PayloadSchemaType::Bool => segment::types::PayloadSchemaType::Bool,
-                PayloadSchemaType::Datetime => segment::types::PayloadSchemaType::Datetime,
-                PayloadSchemaType::UnknownType => {
-                    return Err(Status::invalid_argument(
-                        "Malformed payload schema".to_string(),
-                    ));
-                }
-                PayloadSchemaType::Uuid => segment::types::PayloadSchemaType::Uuid,
-            },
-        };
+        let data_type = PayloadSchemaType::try_from(schema.data_type)
+            .map_err(|_| Status::invalid_argument("Malformed payload schema".to_string()))?
+            .try_into()?;
         let params = match schema.params {
             None => None,
             Some(PayloadIndexParams { index_params: None }) => None,
@@ -641,9 +645,10 @@ impl From<SearchParams> for segment::types::SearchParams {
     fn from(params: SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as usize),
-            exact: params.exact.unwrap_or(false),
+            exact: params.exact,
             quantization: params.quantization.map(|q| q.into()),
-            indexed_only: params.indexed_only.unwrap_or(false),
+            indexed_only: params.indexed_only,
+            score_precision: params.score_precision,
         }
     }
 }
@@ -652,9 +657,10 @@ impl From<segment::types::SearchParams> for SearchParams {
     fn from(params: segment::types::SearchParams) -> Self {
         Self {
             hnsw_ef: params.hnsw_ef.map(|x| x as u64),
-            exact: Some(params.exact),
+            exact: params.exact,
             quantization: params.quantization.map(|q| q.into()),
-            indexed_only: Some(params.indexed_only),
+            indexed_only: params.indexed_only,
+            score_precision: params.score_precision,
         }
     }
 }
@@ -1353,6 +1359,7 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             values_count,
             geo_polygon,
             datetime_range,
+            array_match,
         } = value;
 
         let geo_bounding_box =
@@ -1364,6 +1371,15 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
         let datetime_range = datetime_range
             .map(segment::types::RangeInterface::try_from)
             .transpose()?;
+        let array_match = array_match
+            .map(|array_match| {
+                ArrayMatch::try_from(array_match)
+                    .map(segment::types::ArrayMatch::from)
+                    .map_err(|_| {
+                        Status::invalid_argument(format!("Unknown array match: {array_match}"))
+                    })
+            })
+            .transpose()?;
 
         Ok(Self {
             key: json_path_from_proto(&key)?,
@@ -1373,10 +1389,29 @@ impl TryFrom<FieldCondition> for segment::types::FieldCondition {
             geo_radius,
             geo_polygon,
             values_count: values_count.map(Into::into),
+            array_match,
         })
     }
 }
 
+impl From<ArrayMatch> for segment::types::ArrayMatch {
+    fn from(value: ArrayMatch) -> Self {
+        match value {
+            ArrayMatch::Any => segment::types::ArrayMatch::Any,
+            ArrayMatch::All => segment::types::ArrayMatch::All,
+        }
+    }
+}
+
+impl From<segment::types::ArrayMatch> for ArrayMatch {
+    fn from(value: segment::types::ArrayMatch) -> Self {
+        match value {
+            segment::types::ArrayMatch::Any => ArrayMatch::Any,
+            segment::types::ArrayMatch::All => ArrayMatch::All,
+        }
+    }
+}
+
 impl From<segment::types::FieldCondition> for FieldCondition {
     fn from(value: segment::types::FieldCondition) -> Self {
         let segment::types::FieldCondition {
@@ -1387,6 +1422,7 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_radius,
             geo_polygon,
             values_count,
+            array_match,
         } = value;
 
         let (range, datetime_range) = match range {
@@ -1404,6 +1440,7 @@ impl From<segment::types::FieldCondition> for FieldCondition {
             geo_polygon: geo_polygon.map(Into::into),
             values_count: values_count.map(Into::into),
             datetime_range,
+            array_match: array_match.map(|array_match| ArrayMatch::from(array_match) as i32),
         }
     }
 }
@@ -1602,6 +1639,8 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::Integer(int) => int.into(),
                 MatchValue::Boolean(flag) => flag.into(),
                 MatchValue::Text(text) => segment::types::Match::Text(text.into()),
+                MatchValue::TextPrefix(prefix) => segment::types::Match::new_text_prefix(&prefix),
+                MatchValue::TextBoolean(text) => segment::types::Match::new_text_boolean(&text),
                 MatchValue::Keywords(kwds) => kwds.strings.into(),
                 MatchValue::Integers(ints) => ints.integers.into(),
                 MatchValue::ExceptIntegers(kwds) => {
@@ -1624,8 +1663,18 @@ impl From<segment::types::Match> for Match {
                 segment::types::ValueVariants::Integer(int) => MatchValue::Integer(int),
                 segment::types::ValueVariants::Bool(flag) => MatchValue::Boolean(flag),
             },
-            segment::types::Match::Text(segment::types::MatchText { text }) => {
-                MatchValue::Text(text)
+            segment::types::Match::Text(segment::types::MatchText {
+                text,
+                prefix,
+                boolean,
+            }) => {
+                if prefix {
+                    MatchValue::TextPrefix(text)
+                } else if boolean {
+                    MatchValue::TextBoolean(text)
+                } else {
+                    MatchValue::Text(text)
+                }
             }
             segment::types::Match::Any(any) => match any.any {
                 segment::types::AnyVariants::Strings(strings) => {
@@ -1671,6 +1720,59 @@ impl From<segment::data_types::order_by::Direction> for Direction {
         }
     }
 }
+
+fn direction_from_proto(
+    direction: i32,
+) -> Result<segment::data_types::order_by::Direction, Status> {
+    Direction::try_from(direction)
+        .map(segment::data_types::order_by::Direction::from)
+        .map_err(|_| Status::invalid_argument(format!("Unknown direction: {direction}")))
+}
+
+impl From<segment::utils::scored_point_ties::TieBreak> for TieBreak {
+    fn from(value: segment::utils::scored_point_ties::TieBreak) -> Self {
+        use segment::utils::scored_point_ties::TieBreak as SegmentTieBreak;
+
+        let tie_break = match value {
+            SegmentTieBreak::Id(direction) => {
+                tie_break::TieBreak::Id(Direction::from(direction) as i32)
+            }
+            SegmentTieBreak::Payload(payload_tie_break) => {
+                tie_break::TieBreak::Payload(PayloadTieBreak {
+                    key: payload_tie_break.key.to_string(),
+                    direction: Some(Direction::from(payload_tie_break.direction) as i32),
+                })
+            }
+        };
+        Self {
+            tie_break: Some(tie_break),
+        }
+    }
+}
+
+impl TryFrom<TieBreak> for segment::utils::scored_point_ties::TieBreak {
+    type Error = Status;
+
+    fn try_from(value: TieBreak) -> Result<Self, Self::Error> {
+        use segment::utils::scored_point_ties::PayloadTieBreak as SegmentPayloadTieBreak;
+
+        match value.tie_break {
+            Some(tie_break::TieBreak::Id(direction)) => {
+                Ok(Self::Id(direction_from_proto(direction)?))
+            }
+            Some(tie_break::TieBreak::Payload(PayloadTieBreak { key, direction })) => {
+                Ok(Self::Payload(SegmentPayloadTieBreak {
+                    key: json_path_from_proto(&key)?,
+                    direction: direction
+                        .map(direction_from_proto)
+                        .transpose()?
+                        .unwrap_or_default(),
+                }))
+            }
+            None => Err(Status::invalid_argument("Malformed TieBreak type")),
+        }
+    }
+}
 impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
     type Error = Status;
 
@@ -1709,6 +1811,7 @@ impl TryFrom<OrderBy> for segment::data_types::order_by::OrderBy {
             key: json_path_from_proto(&value.key)?,
             direction,
             start_from,
+            geo_distance_from: value.geo_distance_from.map(Into::into),
         })
     }
 }
@@ -1719,6 +1822,7 @@ impl From<segment::data_types::order_by::OrderBy> for OrderBy {
             key: value.key.to_string(),
             direction: value.direction.map(|d| Direction::from(d) as i32),
             start_from: value.start_from.map(|start_from| start_from.into()),
+            geo_distance_from: value.geo_distance_from.map(Into::into),
         }
     }
 }
@@ -1750,6 +1854,7 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            use_heuristic: hnsw_config.use_heuristic,
         }
     }
 }
@@ -1852,6 +1957,7 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            deduplicated: res.deduplicated,
         }
     }
 }
@@ -1863,6 +1969,7 @@ impl From<UpdateResult> for UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status,
             clock_tag: None,
+            deduplicated: res.deduplicated,
         }
     }
 }
@@ -215,6 +215,12 @@ pub struct OptimizerStatus {
     pub ok: bool,
     #[prost(string, tag = "2")]
     pub error: ::prost::alloc::string::String,
+    /// Name of the optimizer which failed, if the error originates from an optimizer
+    #[prost(string, optional, tag = "3")]
+    pub optimizer: ::core::option::Option<::prost::alloc::string::String>,
+    /// IDs of the segments which were being optimized when the error occurred
+    #[prost(uint64, repeated, tag = "4")]
+    pub segment_ids: ::prost::alloc::vec::Vec<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -223,16 +229,18 @@ pub struct OptimizerStatus {
 pub struct HnswConfigDiff {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
     #[prost(uint64, optional, tag = "1")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_max_1024"))]
     pub m: ::core::option::Option<u64>,
     /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build the index.
     #[prost(uint64, optional, tag = "2")]
-    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_4"))]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_4_max_10000"))]
     pub ef_construct: ::core::option::Option<u64>,
     /// Minimal size (in KiloBytes) of vectors for additional payload-based indexing.
     /// If the payload chunk is smaller than `full_scan_threshold` additional indexing won't be used -
     /// in this case full-scan search should be preferred by query planner and additional indexing is not required.
     /// Note: 1 Kb = 1 vector of size 256
     #[prost(uint64, optional, tag = "3")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_10"))]
     pub full_scan_threshold: ::core::option::Option<u64>,
     /// Number of parallel threads used for background index building.
     /// If 0 - automatically select from 8 to 16.
@@ -245,7 +253,12 @@ pub struct HnswConfigDiff {
     pub on_disk: ::core::option::Option<bool>,
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_max_1024"))]
     pub payload_m: ::core::option::Option<u64>,
+    /// Select neighbours with the heuristic, which prefers diverse directions over the nearest candidates.
+    /// If set to false, nodes are simply linked to their nearest neighbours. Default: true
+    #[prost(bool, optional, tag = "7")]
+    pub use_heuristic: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -274,6 +287,20 @@ pub struct WalConfigDiff {
     /// Number of segments to create in advance
     #[prost(uint64, optional, tag = "2")]
     pub wal_segments_ahead: ::core::option::Option<u64>,
+    /// When to fsync written operations to disk, default is OnFlush
+    #[prost(message, optional, tag = "3")]
+    pub fsync_policy: ::core::option::Option<WalFsyncPolicy>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WalFsyncPolicy {
+    /// When to fsync written operations to disk
+    #[prost(enumeration = "WalFsyncPolicyType", tag = "1")]
+    pub r#type: i32,
+    /// Minimal time between fsyncs in milliseconds, required for the Interval policy only
+    #[prost(uint64, optional, tag = "2")]
+    pub interval_ms: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -336,6 +363,10 @@ pub struct OptimizersConfigDiff {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    /// Minimal number of vectors in a segment, required to build a vector index for it.
+    /// Smaller segments keep the plain index, regardless of `indexing_threshold` and `memmap_threshold`.
+    #[prost(uint64, optional, tag = "9")]
+    pub indexing_min_vector_number: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -452,6 +483,114 @@ pub struct StrictModeConfig {
     pub search_allow_exact: ::core::option::Option<bool>,
     #[prost(float, optional, tag = "8")]
     pub search_max_oversampling: ::core::option::Option<f32>,
+    #[prost(uint32, optional, tag = "9")]
+    #[validate(range(min = 1))]
+    pub max_distinct_values: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag = "10")]
+    #[validate(range(min = 1))]
+    pub max_filter_conditions: ::core::option::Option<u32>,
+    #[prost(bool, optional, tag = "11")]
+    pub require_filter: ::core::option::Option<bool>,
+    #[prost(uint32, optional, tag = "12")]
+    #[validate(range(min = 1))]
+    pub max_result_points: ::core::option::Option<u32>,
+    #[prost(bool, optional, tag = "13")]
+    pub truncate_result_points: ::core::option::Option<bool>,
+    #[prost(uint32, optional, tag = "14")]
+    #[validate(range(min = 1))]
+    pub max_payload_bytes: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadSchemaConfig {
+    /// Allowed top level payload keys and the type of their values. Arrays of values of the declared type and `null` are accepted too
+    #[prost(map = "string, enumeration(PayloadSchemaType)", tag = "1")]
+    pub fields: ::std::collections::HashMap<::prost::alloc::string::String, i32>,
+    /// How to handle payloads not matching the schema
+    #[prost(enumeration = "PayloadSchemaEnforcement", tag = "2")]
+    pub enforcement: i32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QuantizationSearchParams {
+    /// If set to true, search will ignore quantized vector data
+    #[prost(bool, optional, tag = "1")]
+    pub ignore: ::core::option::Option<bool>,
+    /// If true, use original vectors to re-score top-k results. If ignored, qdrant decides automatically does rescore enabled or not.
+    #[prost(bool, optional, tag = "2")]
+    pub rescore: ::core::option::Option<bool>,
+    /// Oversampling factor for quantization.
+    ///
+    /// Defines how many extra vectors should be pre-selected using quantized index,
+    /// and then re-scored using original vectors.
+    ///
+    /// For example, if `oversampling` is 2.4 and `limit` is 100, then 240 vectors will be pre-selected using quantized index,
+    /// and then top-100 will be returned after re-scoring.
+    #[prost(double, optional, tag = "3")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_min_1"))]
+    pub oversampling: ::core::option::Option<f64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchParams {
+    /// Params relevant to HNSW index. Size of the beam in a beam-search.
+    /// Larger the value - more accurate the result, more time required for search.
+    #[prost(uint64, optional, tag = "1")]
+    pub hnsw_ef: ::core::option::Option<u64>,
+    /// Search without approximation. If set to true, search may run long but with exact results.
+    #[prost(bool, optional, tag = "2")]
+    pub exact: ::core::option::Option<bool>,
+    /// If set to true, search will ignore quantized vector data
+    #[prost(message, optional, tag = "3")]
+    #[validate(nested)]
+    pub quantization: ::core::option::Option<QuantizationSearchParams>,
+    /// If enabled, the engine will only perform search among indexed or small segments.
+    /// Using this option prevents slow searches in case of delayed index, but does not
+    /// guarantee that all uploaded vectors will be included in search results
+    #[prost(bool, optional, tag = "4")]
+    pub indexed_only: ::core::option::Option<bool>,
+    /// If set, scores of the results are rounded to this number of decimal places.
+    /// Scores which differ only slightly become equal, the order of the results is preserved.
+    #[prost(uint32, optional, tag = "5")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u32_range_max_9"))]
+    pub score_precision: ::core::option::Option<u32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadTieBreak {
+    /// Payload key to order by, only the first value of arrays is used
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Direction of ordering. Default: ascending
+    #[prost(enumeration = "Direction", optional, tag = "2")]
+    pub direction: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TieBreak {
+    #[prost(oneof = "tie_break::TieBreak", tags = "1, 2")]
+    pub tie_break: ::core::option::Option<tie_break::TieBreak>,
+}
+/// Nested message and enum types in `TieBreak`.
+pub mod tie_break {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum TieBreak {
+        /// Points with equal scores are ordered by their id
+        #[prost(enumeration = "super::Direction", tag = "1")]
+        Id(i32),
+        /// Points with equal scores are ordered by the value of a payload key
+        #[prost(message, tag = "2")]
+        Payload(super::PayloadTieBreak),
+    }
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -512,6 +651,37 @@ pub struct CreateCollection {
     /// Configuration for strict mode
     #[prost(message, optional, tag = "17")]
     pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// Timeout in seconds of read requests, which do not specify one. If not specified - default timeout of the service is used
+    #[prost(uint64, optional, tag = "18")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub default_timeout: ::core::option::Option<u64>,
+    /// Create shard keys on the first write to them, instead of rejecting the write. Only for custom sharding. Default: false
+    #[prost(bool, optional, tag = "19")]
+    pub auto_create_shard_keys: ::core::option::Option<bool>,
+    /// Exact placement of the shards on peers. Shard IDs must be consecutive, starting from 0. Only for auto sharding. If empty - shards are distributed evenly across all peers
+    #[prost(message, repeated, tag = "20")]
+    pub shard_placement: ::prost::alloc::vec::Vec<ShardPlacement>,
+    /// Payload schema of the collection and how it is enforced on updates. If not specified - payloads are not checked
+    #[prost(message, optional, tag = "21")]
+    pub payload_schema: ::core::option::Option<PayloadSchemaConfig>,
+    /// Search params used for parameters not specified in a search request. If not specified - defaults of the service are used
+    #[prost(message, optional, tag = "22")]
+    #[validate(nested)]
+    pub default_search_params: ::core::option::Option<SearchParams>,
+    /// Order of points with equal scores in query results. If not specified - points with equal scores are ordered by id
+    #[prost(message, optional, tag = "23")]
+    pub tie_break: ::core::option::Option<TieBreak>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardPlacement {
+    /// Shard ID
+    #[prost(uint32, tag = "1")]
+    pub shard_id: u32,
+    /// Peers to create the replicas of the shard on
+    #[prost(uint64, repeated, tag = "2")]
+    pub peer_ids: ::prost::alloc::vec::Vec<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -549,6 +719,20 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// New timeout in seconds of read requests, which do not specify one
+    #[prost(uint64, optional, tag = "9")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub default_timeout: ::core::option::Option<u64>,
+    /// New payload schema of the collection, replaces the current one
+    #[prost(message, optional, tag = "10")]
+    pub payload_schema: ::core::option::Option<PayloadSchemaConfig>,
+    /// New search params used for parameters not specified in a search request, replace the current ones
+    #[prost(message, optional, tag = "11")]
+    #[validate(nested)]
+    pub default_search_params: ::core::option::Option<SearchParams>,
+    /// New order of points with equal scores in query results
+    #[prost(message, optional, tag = "12")]
+    pub tie_break: ::core::option::Option<TieBreak>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -605,6 +789,30 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Number of replicas of each shard of specific shard keys, overrides `replication_factor`
+    #[prost(message, repeated, tag = "11")]
+    pub shard_key_replication_factors: ::prost::alloc::vec::Vec<ShardKeyReplicationFactor>,
+    /// Create shard keys on the first write to them, instead of rejecting the write
+    #[prost(bool, optional, tag = "12")]
+    pub auto_create_shard_keys: ::core::option::Option<bool>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardKeyReplicationFactor {
+    /// Shard key
+    #[prost(message, optional, tag = "1")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Number of replicas of each shard of this shard key
+    #[prost(uint32, tag = "2")]
+    pub replication_factor: u32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ShardKeyReplicationFactors {
+    #[prost(message, repeated, tag = "1")]
+    pub factors: ::prost::alloc::vec::Vec<ShardKeyReplicationFactor>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -623,6 +831,9 @@ pub struct CollectionParamsDiff {
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
     #[prost(uint32, optional, tag = "4")]
     pub read_fan_out_factor: ::core::option::Option<u32>,
+    /// Number of replicas of each shard of specific shard keys, replaces all current overrides
+    #[prost(message, optional, tag = "5")]
+    pub shard_key_replication_factors: ::core::option::Option<ShardKeyReplicationFactors>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -650,6 +861,18 @@ pub struct CollectionConfig {
     /// Configuration of strict mode.
     #[prost(message, optional, tag = "6")]
     pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// Timeout in seconds of read requests, which do not specify one
+    #[prost(uint64, optional, tag = "7")]
+    pub default_timeout: ::core::option::Option<u64>,
+    /// Declared payload schema and how it is enforced on updates
+    #[prost(message, optional, tag = "8")]
+    pub payload_schema: ::core::option::Option<PayloadSchemaConfig>,
+    /// Search params used for parameters not specified in a search request
+    #[prost(message, optional, tag = "9")]
+    pub default_search_params: ::core::option::Option<SearchParams>,
+    /// Order of points with equal scores in query results
+    #[prost(message, optional, tag = "10")]
+    pub tie_break: ::core::option::Option<TieBreak>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -661,6 +884,9 @@ pub struct KeywordIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    /// If true - lowercase values at index and query time.
+    #[prost(bool, optional, tag = "3")]
+    pub case_insensitive: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1501,6 +1727,39 @@ impl CompressionRatio {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum WalFsyncPolicyType {
+    /// Fsync only with the periodic flush of the collection, see `flush_interval_sec`
+    OnFlush = 0,
+    /// Fsync after every operation, slowest but no acknowledged operation is ever lost
+    EveryOp = 1,
+    /// Fsync when writing an operation, if the last fsync is older than `interval_ms`
+    Interval = 2,
+}
+impl WalFsyncPolicyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WalFsyncPolicyType::OnFlush => "OnFlush",
+            WalFsyncPolicyType::EveryOp => "EveryOp",
+            WalFsyncPolicyType::Interval => "Interval",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "OnFlush" => Some(Self::OnFlush),
+            "EveryOp" => Some(Self::EveryOp),
+            "Interval" => Some(Self::Interval),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ShardingMethod {
     /// Auto-sharding based on record ids
     Auto = 0,
@@ -1530,6 +1789,66 @@ impl ShardingMethod {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum PayloadSchemaEnforcement {
+    /// Payloads are not checked
+    Off = 0,
+    /// Violations are logged, but payloads are stored
+    Warn = 1,
+    /// Updates with violating payloads are rejected
+    Strict = 2,
+}
+impl PayloadSchemaEnforcement {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PayloadSchemaEnforcement::Off => "Off",
+            PayloadSchemaEnforcement::Warn => "Warn",
+            PayloadSchemaEnforcement::Strict => "Strict",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Off" => Some(Self::Off),
+            "Warn" => Some(Self::Warn),
+            "Strict" => Some(Self::Strict),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Direction {
+    Asc = 0,
+    Desc = 1,
+}
+impl Direction {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Direction::Asc => "Asc",
+            Direction::Desc => "Desc",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Asc" => Some(Self::Asc),
+            "Desc" => Some(Self::Desc),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum TokenizerType {
     Unknown = 0,
     Prefix = 1,
@@ -3013,6 +3332,44 @@ pub struct UpdateShardCutoffPointRequest {
     #[prost(message, optional, tag = "3")]
     pub cutoff: ::core::option::Option<RecoveryPoint>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetHashRingsRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetHashRingsResponse {
+    /// Hash rings of all shard keys
+    #[prost(message, repeated, tag = "1")]
+    pub hash_rings: ::prost::alloc::vec::Vec<HashRingState>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HashRingState {
+    /// Shard key of the hash ring
+    #[prost(message, optional, tag = "1")]
+    pub shard_key: ::core::option::Option<ShardKey>,
+    /// Whether the hash ring is being resharded
+    #[prost(bool, tag = "2")]
+    pub resharding: bool,
+    /// Shards in the hash ring, in the old hash ring during resharding
+    #[prost(uint32, repeated, tag = "3")]
+    pub shards: ::prost::alloc::vec::Vec<u32>,
+    /// Shards in the new hash ring during resharding
+    #[prost(uint32, repeated, tag = "4")]
+    pub new_shards: ::prost::alloc::vec::Vec<u32>,
+}
 /// Generated client implementations.
 pub mod collections_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -3242,6 +3599,32 @@ pub mod collections_internal_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Get hash rings of all shard keys
+        pub async fn get_hash_rings(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetHashRingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetHashRingsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/GetHashRings",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.CollectionsInternal", "GetHashRings"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -3293,6 +3676,14 @@ pub mod collections_internal_server {
             tonic::Response<super::CollectionOperationResponse>,
             tonic::Status,
         >;
+        /// Get hash rings of all shard keys
+        async fn get_hash_rings(
+            &self,
+            request: tonic::Request<super::GetHashRingsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetHashRingsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsInternalServer<T: CollectionsInternal> {
@@ -3618,6 +4009,52 @@ pub mod collections_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.CollectionsInternal/GetHashRings" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetHashRingsSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::GetHashRingsRequest>
+                    for GetHashRingsSvc<T> {
+                        type Response = super::GetHashRingsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetHashRingsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::get_hash_rings(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetHashRingsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -3908,6 +4345,18 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// How to handle points which already exist
+    #[prost(enumeration = "UpsertMode", optional, tag = "6")]
+    pub mode: ::core::option::Option<i32>,
+    /// Apply the upsert only if these points still have the given versions, all points must belong to a single shard
+    #[prost(message, repeated, tag = "7")]
+    pub expected_versions: ::prost::alloc::vec::Vec<PointVersion>,
+    /// Payload key holding the versions of points, required with `expected_versions`
+    #[prost(string, optional, tag = "8")]
+    pub version_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// How to handle points with exactly the same vectors as another point of the upsert or a point stored in the same shard
+    #[prost(enumeration = "VectorDedup", optional, tag = "9")]
+    pub dedup: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4239,50 +4688,6 @@ pub mod with_vectors_selector {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct QuantizationSearchParams {
-    /// If set to true, search will ignore quantized vector data
-    #[prost(bool, optional, tag = "1")]
-    pub ignore: ::core::option::Option<bool>,
-    /// If true, use original vectors to re-score top-k results. If ignored, qdrant decides automatically does rescore enabled or not.
-    #[prost(bool, optional, tag = "2")]
-    pub rescore: ::core::option::Option<bool>,
-    /// Oversampling factor for quantization.
-    ///
-    /// Defines how many extra vectors should be pre-selected using quantized index,
-    /// and then re-scored using original vectors.
-    ///
-    /// For example, if `oversampling` is 2.4 and `limit` is 100, then 240 vectors will be pre-selected using quantized index,
-    /// and then top-100 will be returned after re-scoring.
-    #[prost(double, optional, tag = "3")]
-    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_min_1"))]
-    pub oversampling: ::core::option::Option<f64>,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchParams {
-    /// Params relevant to HNSW index. Size of the beam in a beam-search.
-    /// Larger the value - more accurate the result, more time required for search.
-    #[prost(uint64, optional, tag = "1")]
-    pub hnsw_ef: ::core::option::Option<u64>,
-    /// Search without approximation. If set to true, search may run long but with exact results.
-    #[prost(bool, optional, tag = "2")]
-    pub exact: ::core::option::Option<bool>,
-    /// If set to true, search will ignore quantized vector data
-    #[prost(message, optional, tag = "3")]
-    #[validate(nested)]
-    pub quantization: ::core::option::Option<QuantizationSearchParams>,
-    /// If enabled, the engine will only perform search among indexed or small segments.
-    /// Using this option prevents slow searches in case of delayed index, but does not
-    /// guarantee that all uploaded vectors will be included in search results
-    #[prost(bool, optional, tag = "4")]
-    pub indexed_only: ::core::option::Option<bool>,
-}
-#[derive(validator::Validate)]
-#[derive(serde::Serialize)]
-#[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SearchPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
@@ -4461,6 +4866,9 @@ pub struct OrderBy {
     /// Start from this value
     #[prost(message, optional, tag = "3")]
     pub start_from: ::core::option::Option<StartFrom>,
+    /// Order by distance from this point to the geo value of the key, requires a geo index
+    #[prost(message, optional, tag = "4")]
+    pub geo_distance_from: ::core::option::Option<GeoPoint>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5144,6 +5552,12 @@ pub mod points_update_operation {
         /// Option for custom sharding to specify used shard keys
         #[prost(message, optional, tag = "2")]
         pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// How to handle points with exactly the same vectors as another point of the upsert or a point stored in the same shard
+        #[prost(enumeration = "super::VectorDedup", optional, tag = "3")]
+        pub dedup: ::core::option::Option<i32>,
+        /// How to handle points which already exist
+        #[prost(enumeration = "super::UpsertMode", optional, tag = "4")]
+        pub mode: ::core::option::Option<i32>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5309,6 +5723,9 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Number of points skipped as duplicates, provided if deduplication of vectors is requested
+    #[prost(uint64, optional, tag = "4")]
+    pub deduplicated: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5514,6 +5931,9 @@ pub struct RetrievedPoint {
     /// Order-by value
     #[prost(message, optional, tag = "6")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Last update operation applied to this point
+    #[prost(uint64, optional, tag = "7")]
+    pub version: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5715,12 +6135,15 @@ pub struct FieldCondition {
     /// Check if datetime is within a given range
     #[prost(message, optional, tag = "8")]
     pub datetime_range: ::core::option::Option<DatetimeRange>,
+    /// How to match arrays of values, default is `Any`
+    #[prost(enumeration = "ArrayMatch", optional, tag = "9")]
+    pub array_match: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -5753,6 +6176,12 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match text tokens starting with the prefix
+        #[prost(string, tag = "9")]
+        TextPrefix(::prost::alloc::string::String),
+        /// Match text with `AND`, `OR` and `NOT` operators
+        #[prost(string, tag = "10")]
+        TextBoolean(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -5907,6 +6336,17 @@ pub struct PointStruct {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersion {
+    /// Point id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Expected version of the point
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GeoPoint {
     #[prost(double, tag = "1")]
     pub lon: f64,
@@ -5949,6 +6389,64 @@ impl WriteOrderingType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum UpsertMode {
+    /// Insert new points and overwrite existing ones, default
+    Upsert = 0,
+    /// Insert new points only, points which already exist are left untouched
+    InsertIfAbsent = 1,
+}
+impl UpsertMode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            UpsertMode::Upsert => "Upsert",
+            UpsertMode::InsertIfAbsent => "InsertIfAbsent",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Upsert" => Some(Self::Upsert),
+            "InsertIfAbsent" => Some(Self::InsertIfAbsent),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum VectorDedup {
+    /// Only upsert the canonical point, duplicates are skipped
+    Skip = 0,
+    /// Only upsert the canonical point, with the payload of duplicates merged into it
+    Merge = 1,
+}
+impl VectorDedup {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            VectorDedup::Skip => "Skip",
+            VectorDedup::Merge => "Merge",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Skip" => Some(Self::Skip),
+            "Merge" => Some(Self::Merge),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ReadConsistencyType {
     /// Send request to all nodes and return points which are present on all of them
     All = 0,
@@ -5956,6 +6454,8 @@ pub enum ReadConsistencyType {
     Majority = 1,
     /// Send requests to half + 1 nodes, return points which are present on all of them
     Quorum = 2,
+    /// Send request to the local replica only, fail if it is not active
+    LocalOnly = 3,
 }
 impl ReadConsistencyType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -5967,6 +6467,7 @@ impl ReadConsistencyType {
             ReadConsistencyType::All => "All",
             ReadConsistencyType::Majority => "Majority",
             ReadConsistencyType::Quorum => "Quorum",
+            ReadConsistencyType::LocalOnly => "LocalOnly",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -5975,6 +6476,7 @@ impl ReadConsistencyType {
             "All" => Some(Self::All),
             "Majority" => Some(Self::Majority),
             "Quorum" => Some(Self::Quorum),
+            "LocalOnly" => Some(Self::LocalOnly),
             _ => None,
         }
     }
@@ -6024,33 +6526,6 @@ impl FieldType {
         }
     }
 }
-#[derive(serde::Serialize)]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum Direction {
-    Asc = 0,
-    Desc = 1,
-}
-impl Direction {
-    /// String value of the enum field names used in the ProtoBuf definition.
-    ///
-    /// The values are not transformed in any way and thus are considered stable
-    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
-    pub fn as_str_name(&self) -> &'static str {
-        match self {
-            Direction::Asc => "Asc",
-            Direction::Desc => "Desc",
-        }
-    }
-    /// Creates an enum from field names used in the ProtoBuf definition.
-    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-        match value {
-            "Asc" => Some(Self::Asc),
-            "Desc" => Some(Self::Desc),
-            _ => None,
-        }
-    }
-}
 /// How to use positive and negative vectors to find the results, default is `AverageVector`.
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
@@ -6178,6 +6653,35 @@ impl UpdateStatus {
         }
     }
 }
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ArrayMatch {
+    /// Match if any value of the array satisfies the condition
+    Any = 0,
+    /// Match if the array is not empty, and all of its values satisfy the condition
+    All = 1,
+}
+impl ArrayMatch {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ArrayMatch::Any => "Any",
+            ArrayMatch::All => "All",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Any" => Some(Self::Any),
+            "All" => Some(Self::All),
+            _ => None,
+        }
+    }
+}
 /// Generated client implementations.
 pub mod points_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -8573,6 +9077,9 @@ pub struct UpdateResultInternal {
     pub status: i32,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Number of points skipped as duplicates
+    #[prost(uint64, optional, tag = "4")]
+    pub deduplicated: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -9041,6 +9548,38 @@ pub struct FacetResponseInternal {
     #[prost(double, tag = "2")]
     pub time: f64,
 }
+#[derive(serde::Serialize)]
+#[derive(validator::Validate)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctValuesInternal {
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub key: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<Filter>,
+    /// Max number of values to return, the smallest ones are returned
+    #[prost(uint64, tag = "4")]
+    pub limit: u64,
+    #[prost(uint32, tag = "5")]
+    pub shard_id: u32,
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub timeout: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DistinctValuesResponseInternal {
+    /// Distinct values, in ascending order
+    #[prost(message, repeated, tag = "1")]
+    pub values: ::prost::alloc::vec::Vec<FacetValueInternal>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 /// Generated client implementations.
 pub mod points_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -9566,6 +10105,31 @@ pub mod points_internal_client {
                 .insert(GrpcMethod::new("qdrant.PointsInternal", "Facet"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn distinct_values(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DistinctValuesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctValuesResponseInternal>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.PointsInternal/DistinctValues",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.PointsInternal", "DistinctValues"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -9692,6 +10256,13 @@ pub mod points_internal_server {
             tonic::Response<super::FacetResponseInternal>,
             tonic::Status,
         >;
+        async fn distinct_values(
+            &self,
+            request: tonic::Request<super::DistinctValuesInternal>,
+        ) -> std::result::Result<
+            tonic::Response<super::DistinctValuesResponseInternal>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsInternalServer<T: PointsInternal> {
@@ -10610,6 +11181,53 @@ pub mod points_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.PointsInternal/DistinctValues" => {
+                    #[allow(non_camel_case_types)]
+                    struct DistinctValuesSvc<T: PointsInternal>(pub Arc<T>);
+                    impl<
+                        T: PointsInternal,
+                    > tonic::server::UnaryService<super::DistinctValuesInternal>
+                    for DistinctValuesSvc<T> {
+                        type Response = super::DistinctValuesResponseInternal;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DistinctValuesInternal>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as PointsInternal>::distinct_values(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DistinctValuesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -11665,6 +12283,9 @@ pub struct CreateSnapshotRequest {
     #[prost(string, tag = "1")]
     #[validate(length(min = 1, max = 255))]
     pub collection_name: ::prost::alloc::string::String,
+    /// Payload keys to remove from points stored in the snapshot
+    #[prost(string, repeated, tag = "2")]
+    pub exclude_payload_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -11708,6 +12329,9 @@ pub struct SnapshotDescription {
     /// SHA256 digest of the snapshot file
     #[prost(string, optional, tag = "4")]
     pub checksum: ::core::option::Option<::prost::alloc::string::String>,
+    /// WAL sequence number of the shard at the moment of snapshot creation, only for shard snapshots
+    #[prost(uint64, optional, tag = "5")]
+    pub seq_number: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -190,6 +190,7 @@ impl Validate for grpc::FieldCondition {
             geo_radius,
             geo_polygon,
             values_count,
+            array_match: _,
         } = self;
 
         let all_fields_none = r#match.is_none()
@@ -266,6 +267,11 @@ pub fn validate_u32_range_min_1(value: &u32) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&1), None)
 }
 
+/// Validate the value is in `[10, ]`.
+pub fn validate_u64_range_min_10(value: &u64) -> Result<(), ValidationError> {
+    validate_range_generic(value, Some(&10), None)
+}
+
 /// Validate the value is in `[100, ]`.
 pub fn validate_u64_range_min_100(value: &u64) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&100), None)
@@ -281,6 +287,16 @@ pub fn validate_u64_range_min_4(value: &u64) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&4), None)
 }
 
+/// Validate the value is in `[0, 1024]`.
+pub fn validate_u64_range_max_1024(value: &u64) -> Result<(), ValidationError> {
+    validate_range_generic(value, None, Some(&1024))
+}
+
+/// Validate the value is in `[0, 9]`.
+pub fn validate_u32_range_max_9(value: &u32) -> Result<(), ValidationError> {
+    validate_range_generic(value, None, Some(&9))
+}
+
 /// Validate the value is in `[4, 10000]`.
 pub fn validate_u64_range_min_4_max_10000(value: &u64) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&4), Some(&10_000))
@@ -371,7 +387,7 @@ mod tests {
 
     use crate::grpc::qdrant::{
         CreateCollection, CreateFieldIndexCollection, GeoLineString, GeoPoint, GeoPolygon,
-        SearchPoints, UpdateCollection,
+        HnswConfigDiff, SearchPoints, UpdateCollection,
     };
 
     #[test]
@@ -440,6 +456,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hnsw_config_request() {
+        let request = |hnsw_config| UpdateCollection {
+            collection_name: "test_collection".into(),
+            hnsw_config: Some(hnsw_config),
+            ..Default::default()
+        };
+
+        // Zero `m` disables the global graph, which is allowed
+        let good_request = request(HnswConfigDiff {
+            m: Some(0),
+            ef_construct: Some(100),
+            full_scan_threshold: Some(10_000),
+            ..Default::default()
+        });
+        assert!(
+            good_request.validate().is_ok(),
+            "good HNSW config should not error on validation"
+        );
+
+        let bad_request = request(HnswConfigDiff {
+            ef_construct: Some(10_001),
+            ..Default::default()
+        });
+        assert!(
+            bad_request.validate().is_err(),
+            "huge ef_construct should error on validation"
+        );
+
+        let bad_request = request(HnswConfigDiff {
+            full_scan_threshold: Some(9),
+            ..Default::default()
+        });
+        assert!(
+            bad_request.validate().is_err(),
+            "small full_scan_threshold should error on validation"
+        );
+    }
+
     #[test]
     fn test_bad_index_request() {
         let bad_request = CreateFieldIndexCollection {
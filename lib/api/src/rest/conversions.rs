@@ -4,8 +4,8 @@ use uuid::Uuid;
 
 use super::schema::{BatchVectorStruct, ScoredPoint, Vector, VectorStruct};
 use super::{
-    FacetRequestInternal, FacetResponse, FacetValue, FacetValueHit, NearestQuery, OrderByInterface,
-    Query, QueryInterface,
+    DistinctValuesRequestInternal, FacetRequestInternal, FacetResponse, FacetValue, FacetValueHit,
+    NearestQuery, OrderByInterface, Query, QueryInterface,
 };
 use crate::rest::{DenseVector, NamedVectorStruct};
 
@@ -237,6 +237,7 @@ impl From<OrderByInterface> for OrderBy {
                 key,
                 direction: None,
                 start_from: None,
+                geo_distance_from: None,
             },
             OrderByInterface::Struct(order_by) => order_by,
         }
@@ -281,6 +282,16 @@ impl From<segment::data_types::facets::FacetResponse> for FacetResponse {
     }
 }
 
+impl From<DistinctValuesRequestInternal> for segment::data_types::facets::DistinctValuesParams {
+    fn from(value: DistinctValuesRequestInternal) -> Self {
+        Self {
+            key: value.key,
+            limit: value.limit,
+            filter: value.filter,
+        }
+    }
+}
+
 impl From<FacetRequestInternal> for segment::data_types::facets::FacetParams {
     fn from(value: FacetRequestInternal) -> Self {
         Self {
@@ -104,9 +104,19 @@ pub enum BatchVectorStruct {
 pub enum ShardKeySelector {
     ShardKey(ShardKey),
     ShardKeys(Vec<ShardKey>),
+    TenantScope(TenantScope),
     // ToDo: select by pattern
 }
 
+/// Restrict the request to the shards of a single tenant's shard key.
+///
+/// Unlike plain shard key selection, filter conditions on tenant payload fields must not refer to
+/// any other tenant than the selected one.
+#[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, PartialEq)]
+pub struct TenantScope {
+    pub tenant: ShardKey,
+}
+
 fn version_example() -> segment::types::SeqNumberType {
     3
 }
@@ -140,6 +150,16 @@ pub struct ScoredPoint {
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
 }
 
+/// Id and score of a search result
+#[derive(Serialize, JsonSchema, Clone, Debug)]
+pub struct ScoredPointId {
+    /// Point id
+    pub id: PointIdType,
+    /// Points vector distance to the query vector
+    #[schemars(example = "score_example")]
+    pub score: ScoreType,
+}
+
 /// Point data
 #[derive(Clone, Debug, PartialEq, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -157,6 +177,9 @@ pub struct Record {
     pub shard_key: Option<segment::types::ShardKey>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
+    /// Version of the point, number of the last operation which changed it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<segment::types::SeqNumberType>,
 }
 
 /// Vector data separator for named and unnamed modes
@@ -197,7 +220,7 @@ pub enum OrderByInterface {
 ///
 /// * `rrf` - Reciprocal Rank Fusion
 /// * `dbsf` - Distribution-Based Score Fusion
-#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Fusion {
     Rrf,
@@ -781,3 +804,32 @@ pub struct FacetValueHit {
 pub struct FacetResponse {
     pub hits: Vec<FacetValueHit>,
 }
+
+#[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
+pub struct DistinctValuesRequestInternal {
+    /// Payload key to get distinct values of. Must have a keyword, integer or uuid index.
+    pub key: JsonPath,
+
+    /// Max number of values to return, the smallest ones are returned. Default is 100, or the
+    /// strict mode limit if it is lower.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+
+    /// Filter conditions - only consider points that satisfy these conditions.
+    pub filter: Option<Filter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
+pub struct DistinctValuesRequest {
+    #[validate(nested)]
+    #[serde(flatten)]
+    pub distinct_request: DistinctValuesRequestInternal,
+
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DistinctValuesResponse {
+    /// Distinct values of the payload key, in ascending order
+    pub values: Vec<FacetValue>,
+}
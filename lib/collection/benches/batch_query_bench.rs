@@ -40,6 +40,8 @@ fn setup() -> (TempDir, LocalShard) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -56,6 +58,7 @@ fn setup() -> (TempDir, LocalShard) {
             max_segment_size: Some(100_000),
             memmap_threshold: Some(100_000),
             indexing_threshold: Some(50_000),
+            indexing_min_vector_number: None,
             flush_interval_sec: 30,
             max_optimization_threads: Some(2),
         },
@@ -63,6 +66,10 @@ fn setup() -> (TempDir, LocalShard) {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -3,11 +3,13 @@ use std::sync::Arc;
 
 use futures::{future, TryStreamExt as _};
 use lazy_static::lazy_static;
-use segment::types::QuantizationConfig;
+use segment::types::{QuantizationConfig, SearchParams};
+use segment::utils::scored_point_ties::TieBreak;
 use semver::Version;
 
 use super::Collection;
 use crate::operations::config_diff::*;
+use crate::operations::payload_schema::PayloadSchemaConfig;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::optimizers_builder::OptimizersConfig;
@@ -56,6 +58,56 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates the payload schema and its enforcement:
+    /// Saves new params on disk
+    pub async fn update_payload_schema(
+        &self,
+        payload_schema: PayloadSchemaConfig,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.payload_schema = Some(payload_schema);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates search params used for parameters not specified in a search request:
+    /// Saves new params on disk
+    pub async fn update_default_search_params(
+        &self,
+        default_search_params: SearchParams,
+    ) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.default_search_params = Some(default_search_params);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates the order of points with equal scores in query results:
+    /// Saves new params on disk
+    pub async fn update_tie_break(&self, tie_break: TieBreak) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.tie_break = Some(tie_break);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
+    /// Updates the default timeout of read requests:
+    /// Saves new params on disk
+    pub async fn update_default_timeout(&self, default_timeout: usize) -> CollectionResult<()> {
+        {
+            let mut config = self.collection_config.write().await;
+            config.default_timeout = Some(default_timeout);
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates vectors config:
     /// Saves new params on disk
     ///
@@ -244,6 +296,29 @@ impl Collection {
         Ok(())
     }
 
+    /// Rebuild the vector indices of all local segments with the current configuration
+    ///
+    /// Segments are rebuilt in the background by the optimizers, the collection stays available.
+    /// Only affects shards on this peer. Returns the number of segments scheduled for rebuilding.
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        let shard_holder = self.shards_holder.read().await;
+        let requests = shard_holder
+            .all_shards()
+            .map(|replica_set| replica_set.reindex());
+        let scheduled_segments = future::try_join_all(requests).await?;
+        Ok(scheduled_segments.into_iter().sum())
+    }
+
+    /// Cancel rebuilding of vector indices requested by [`Collection::reindex`]
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        let requests = shard_holder
+            .all_shards()
+            .map(|replica_set| replica_set.cancel_reindex());
+        future::try_join_all(requests).await?;
+        Ok(())
+    }
+
     pub async fn info(
         &self,
         shard_selection: &ShardSelectorInternal,
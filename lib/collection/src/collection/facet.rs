@@ -1,16 +1,20 @@
+use std::collections::BTreeSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use futures::future;
 use itertools::Itertools;
 use segment::data_types::facets::{
-    aggregate_facet_hits, FacetParams, FacetResponse, FacetValueHit,
+    aggregate_facet_hits, DistinctValuesParams, FacetParams, FacetResponse, FacetValue,
+    FacetValueHit,
 };
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::CollectionResult;
+use crate::operations::verification::StrictModeVerification as _;
+use crate::shards::local_shard::facet::truncate_set;
 
 impl Collection {
     pub async fn facet(
@@ -23,6 +27,7 @@ impl Collection {
         if request.limit == 0 {
             return Ok(FacetResponse { hits: vec![] });
         }
+        let timeout = self.read_timeout(timeout, &shard_selection).await;
 
         let request = Arc::new(request);
 
@@ -52,4 +57,67 @@ impl Collection {
 
         Ok(FacetResponse { hits })
     }
+
+    /// Returns distinct values of a payload key, in ascending order.
+    ///
+    /// Values are read from the payload index of the key, without scanning all points.
+    /// Every shard only returns its smallest values up to the limit, which are merged into
+    /// a set truncated to the limit.
+    pub async fn distinct_values(
+        &self,
+        mut request: DistinctValuesParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let timeout = self.read_timeout(timeout, &shard_selection).await;
+        let max_distinct_values = {
+            let config = self.collection_config.read().await;
+            match config
+                .strict_mode_config
+                .as_ref()
+                .filter(|strict_mode_config| strict_mode_config.enabled == Some(true))
+            {
+                Some(strict_mode_config) => {
+                    request.check_strict_mode(self, strict_mode_config)?;
+                    strict_mode_config.max_distinct_values
+                }
+                None => None,
+            }
+        };
+
+        // Without an explicit limit, the default one is lowered to the strict mode limit
+        let limit = request.limit.unwrap_or_else(|| {
+            max_distinct_values.map_or(DistinctValuesParams::DEFAULT_LIMIT, |max| {
+                max.min(DistinctValuesParams::DEFAULT_LIMIT)
+            })
+        });
+        request.limit = Some(limit);
+        let request = Arc::new(request);
+
+        let shard_holder = self.shards_holder.read().await;
+        let target_shards = shard_holder.select_shards(&shard_selection)?;
+
+        let shards_reads_f = target_shards.iter().map(|(shard, _shard_key)| {
+            shard.distinct_values(
+                request.clone(),
+                read_consistency,
+                shard_selection.is_shard_id(),
+                timeout,
+            )
+        });
+
+        let shards_results = future::try_join_all(shards_reads_f).await?;
+
+        let values =
+            shards_results
+                .into_iter()
+                .fold(BTreeSet::new(), |mut values, shard_values| {
+                    values.extend(shard_values);
+                    truncate_set(&mut values, limit);
+                    values
+                });
+
+        Ok(values.into_iter().collect())
+    }
 }
@@ -0,0 +1,135 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use futures::future::join_all;
+use itertools::Itertools as _;
+use schemars::JsonSchema;
+use segment::types::ShardKey;
+use serde::Serialize;
+
+use super::Collection;
+use crate::hash_ring::HashRingState;
+use crate::shards::shard::PeerId;
+
+/// Hash rings of all shard keys of a peer
+pub type PeerHashRings = HashMap<Option<ShardKey>, HashRingState>;
+
+/// Hash rings of a single shard key, as seen by every peer
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ShardKeyHashRings {
+    /// Shard key of the hash rings, not set for collections without custom sharding
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKey>,
+    /// Whether all peers have the same hash ring for this shard key
+    pub consistent: bool,
+    /// Hash ring of each peer, not set if the peer has no hash ring for this shard key
+    pub peers: BTreeMap<PeerId, Option<HashRingState>>,
+}
+
+/// Comparison of the hash rings of a collection across all peers
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct HashRingConsistency {
+    /// Whether the hash rings of all shard keys are the same on all peers, false if the hash
+    /// rings of some peer could not be retrieved
+    pub consistent: bool,
+    /// Hash rings of each shard key
+    pub hash_rings: Vec<ShardKeyHashRings>,
+    /// Error of each peer, whose hash rings could not be retrieved
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub peer_errors: BTreeMap<PeerId, String>,
+}
+
+impl HashRingConsistency {
+    /// Compare the hash rings collected from each peer
+    ///
+    /// Peers with errors are not part of the comparison, but make the hash rings inconsistent.
+    pub fn compare(
+        peer_hash_rings: &HashMap<PeerId, PeerHashRings>,
+        peer_errors: BTreeMap<PeerId, String>,
+    ) -> Self {
+        let shard_keys: HashSet<_> = peer_hash_rings
+            .values()
+            .flat_map(|hash_rings| hash_rings.keys())
+            .collect();
+
+        let hash_rings = shard_keys
+            .into_iter()
+            .sorted_by_key(|shard_key| shard_key.as_ref().map(ToString::to_string))
+            .map(|shard_key| {
+                let peers: BTreeMap<_, _> = peer_hash_rings
+                    .iter()
+                    .map(|(peer_id, hash_rings)| (*peer_id, hash_rings.get(shard_key).cloned()))
+                    .collect();
+                let consistent = peers.values().all_equal();
+
+                ShardKeyHashRings {
+                    shard_key: shard_key.clone(),
+                    consistent,
+                    peers,
+                }
+            })
+            .collect_vec();
+
+        Self {
+            consistent: peer_errors.is_empty()
+                && hash_rings.iter().all(|hash_ring| hash_ring.consistent),
+            hash_rings,
+            peer_errors,
+        }
+    }
+}
+
+impl Collection {
+    /// Hash rings of all shard keys on this peer
+    pub async fn hash_rings(&self) -> PeerHashRings {
+        self.shards_holder
+            .read()
+            .await
+            .rings
+            .iter()
+            .map(|(shard_key, ring)| (shard_key.clone(), ring.state()))
+            .collect()
+    }
+
+    /// Compare the hash rings of this peer with the hash rings of all other peers
+    ///
+    /// Hash rings are changed through consensus, e.g. by resharding. Diverging hash rings mean
+    /// that some peer did not apply the change (yet), and routes points to different shards.
+    ///
+    /// Unreachable peers don't fail the check, their errors are reported along with the hash
+    /// rings of the other peers.
+    pub async fn check_hash_ring_consistency(&self) -> HashRingConsistency {
+        let other_peers = self
+            .channel_service
+            .id_to_address
+            .read()
+            .keys()
+            .copied()
+            .filter(|peer_id| *peer_id != self.this_peer_id)
+            .collect_vec();
+
+        let requests = other_peers.into_iter().map(|peer_id| async move {
+            let hash_rings = self
+                .channel_service
+                .get_hash_rings_on_peer(peer_id, &self.id)
+                .await;
+            (peer_id, hash_rings)
+        });
+
+        let mut peer_hash_rings = HashMap::new();
+        let mut peer_errors = BTreeMap::new();
+        for (peer_id, hash_rings) in join_all(requests).await {
+            match hash_rings {
+                Ok(hash_rings) => {
+                    peer_hash_rings.insert(peer_id, hash_rings);
+                }
+                Err(err) => {
+                    log::warn!("Failed to check hash rings of peer {peer_id}: {err}");
+                    peer_errors.insert(peer_id, err.to_string());
+                }
+            }
+        }
+        peer_hash_rings.insert(self.this_peer_id, self.hash_rings().await);
+
+        HashRingConsistency::compare(&peer_hash_rings, peer_errors)
+    }
+}
@@ -1,15 +1,22 @@
 mod collection_ops;
 pub mod distance_matrix;
 mod facet;
+pub mod hash_ring_consistency;
 pub mod payload_index_schema;
 mod point_ops;
 pub mod query;
+pub mod query_explanation;
+pub mod query_plan;
+pub mod recall_monitor;
 mod resharding;
 mod search;
+pub mod search_log;
 mod shard_transfer;
 mod sharding_keys;
 mod snapshots;
 mod state_management;
+mod text_vocab;
+mod update_validation;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
@@ -26,11 +33,14 @@ use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::recall_monitor::RecallMonitor;
+use crate::collection::search_log::SearchLog;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
 use crate::config::CollectionConfig;
 use crate::operations::cluster_ops::ReshardingDirection;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::optimizers_builder::OptimizersConfig;
@@ -48,7 +58,7 @@ use crate::shards::transfer::helpers::check_transfer_conflicts_strict;
 use crate::shards::transfer::transfer_tasks_pool::{TaskResult, TransferTasksPool};
 use crate::shards::transfer::{ShardTransfer, ShardTransferMethod};
 use crate::shards::{replica_set, CollectionId};
-use crate::telemetry::CollectionTelemetry;
+use crate::telemetry::{index_build_peak_memory_bytes, CollectionTelemetry};
 
 /// Collection's data is split into several shards.
 #[allow(dead_code)]
@@ -81,6 +91,10 @@ pub struct Collection {
     // Search runtime handle.
     search_runtime: Handle,
     optimizer_cpu_budget: CpuBudget,
+    /// Sample of recent search requests, for debugging
+    search_log: SearchLog,
+    /// Recall of approximate search, measured by sampling
+    recall_monitor: RecallMonitor,
 }
 
 pub type RequestShardTransfer = Arc<dyn Fn(ShardTransfer) + Send + Sync>;
@@ -159,6 +173,8 @@ impl Collection {
             collection_config: shared_collection_config,
             optimizers_overwrite,
             payload_index_schema,
+            search_log: SearchLog::new(shared_storage_config.search_log_sample_rate),
+            recall_monitor: RecallMonitor::default(),
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
@@ -268,6 +284,8 @@ impl Collection {
             collection_config: shared_collection_config,
             optimizers_overwrite,
             payload_index_schema,
+            search_log: SearchLog::new(shared_storage_config.search_log_sample_rate),
+            recall_monitor: RecallMonitor::default(),
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
@@ -686,6 +704,7 @@ impl Collection {
                     sync: true,
                     // For automatic shard transfers, always select some default method from this point on
                     method: Some(shard_transfer_method),
+                    filter: None,
                 };
 
                 if check_transfer_conflicts_strict(&transfer, transfers.iter()).is_some() {
@@ -743,6 +762,8 @@ impl Collection {
             )
         };
 
+        let index_build_peak_memory_bytes = index_build_peak_memory_bytes(&shards_telemetry);
+
         CollectionTelemetry {
             id: self.name(),
             init_time_ms: self.init_time.as_millis() as u64,
@@ -750,6 +771,9 @@ impl Collection {
             shards: shards_telemetry,
             transfers,
             resharding,
+            recent_searches: self.search_log.recent(),
+            recall: self.recall_monitor.get_telemetry_data(),
+            index_build_peak_memory_bytes,
         }
     }
 
@@ -763,6 +787,26 @@ impl Collection {
         }
     }
 
+    /// Timeout of a read request, falling back to the default timeout of the collection
+    ///
+    /// Internal requests, targeting a specific shard, get the timeout resolved on the node the
+    /// request was received on, so the default is not applied to them.
+    pub(crate) async fn read_timeout(
+        &self,
+        timeout: Option<Duration>,
+        shard_selection: &ShardSelectorInternal,
+    ) -> Option<Duration> {
+        if timeout.is_some() || shard_selection.is_shard_id() {
+            return timeout;
+        }
+
+        self.collection_config
+            .read()
+            .await
+            .default_timeout
+            .map(|timeout| Duration::from_secs(timeout as u64))
+    }
+
     pub async fn lock_updates(&self) -> RwLockWriteGuard<()> {
         self.updates_lock.write().await
     }
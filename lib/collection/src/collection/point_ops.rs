@@ -7,15 +7,19 @@ use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
 use segment::data_types::order_by::{Direction, OrderBy};
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
+use tokio::sync::OwnedRwLockReadGuard;
 use validator::Validate as _;
 
 use super::Collection;
 use crate::operations::consistency_params::ReadConsistency;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{PointOperations, WriteOrdering};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::shards::replica_set::upsert_coalescer::UpsertCoalescer;
+use crate::shards::replica_set::ShardReplicaSet;
 use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::ShardHolder;
 
 impl Collection {
     /// Apply collection update operation to all local shards.
@@ -90,9 +94,9 @@ impl Collection {
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
         let result = tokio::task::spawn(async move {
-            let _update_lock = update_lock;
+            let guards = Arc::new((update_lock, shard_holder));
 
-            let Some(shard) = shard_holder.get_shard(&shard_selection) else {
+            let Some(shard) = guards.1.get_shard(&shard_selection) else {
                 return Ok(None);
             };
 
@@ -107,8 +111,7 @@ impl Collection {
                         );
                     }
 
-                    shard
-                        .update_with_consistency(operation.operation, wait, ordering)
+                    update_with_coalescing(&guards, shard, operation.operation, wait, ordering)
                         .await
                         .map(Some)
                 }
@@ -138,18 +141,36 @@ impl Collection {
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        self.check_update_strict_mode(&operation).await?;
+        self.check_update_payload_schema(&operation).await?;
+
+        let is_conditional = matches!(
+            operation,
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsIfVersion(_)),
+        );
 
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
 
         let mut results = tokio::task::spawn(async move {
-            let _update_lock = update_lock;
+            let guards = Arc::new((update_lock, shard_holder));
+
+            let shard_operations = guards.1.split_by_shard(operation, &shard_keys_selection)?;
+
+            // Expected versions are checked per shard, a conflict in one shard can't prevent
+            // the upsert in another one
+            if is_conditional && shard_operations.len() > 1 {
+                return Err(CollectionError::bad_request(format!(
+                    "Upsert with expected versions must only contain points of a single shard, \
+                     got points of {} shards",
+                    shard_operations.len(),
+                )));
+            }
 
-            let updates: FuturesUnordered<_> = shard_holder
-                .split_by_shard(operation, &shard_keys_selection)?
+            let updates: FuturesUnordered<_> = shard_operations
                 .into_iter()
-                .map(move |(shard, operation)| {
-                    shard.update_with_consistency(operation, wait, ordering)
+                .map(|(shard, operation)| {
+                    update_with_coalescing(&guards, shard, operation, wait, ordering)
                 })
                 .collect();
 
@@ -188,8 +209,16 @@ impl Collection {
                 first_err
             }
         } else {
+            // Points deduplicated in each shard add up
+            let deduplicated = results
+                .iter()
+                .filter_map(|result| result.as_ref().ok()?.deduplicated)
+                .reduce(|total, count| total + count);
+
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            result.deduplicated = deduplicated;
+            Ok(result)
         }
     }
 
@@ -213,6 +242,7 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<ScrollResult> {
+        let timeout = self.read_timeout(timeout, shard_selection).await;
         let default_request = ScrollRequestInternal::default();
 
         let id_offset = request.offset;
@@ -238,12 +268,20 @@ impl Collection {
             });
         }
 
+        if let Some(keep) = self.check_result_points(shard_selection, limit).await? {
+            limit = keep;
+        }
+
         // `order_by` does not support offset
         if order_by.is_none() {
             // Needed to return next page offset.
             limit = limit.saturating_add(1);
         };
 
+        self.check_tenant_scope(shard_selection, [request.filter.as_ref()])?;
+        self.check_filter_required(shard_selection, [request.filter.as_ref()])
+            .await?;
+
         let local_only = shard_selection.is_shard_id();
 
         let retrieved_points: Vec<_> = {
@@ -338,6 +376,7 @@ impl Collection {
             // remove extra point, it would be a first point of the next page
             Some(points.pop().unwrap().id)
         };
+
         Ok(ScrollResult {
             points,
             next_page_offset,
@@ -351,6 +390,11 @@ impl Collection {
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<CountResult> {
+        let timeout = self.read_timeout(timeout, shard_selection).await;
+        self.check_tenant_scope(shard_selection, [request.filter.as_ref()])?;
+        self.check_filter_required(shard_selection, [request.filter.as_ref()])
+            .await?;
+
         let shards_holder = self.shards_holder.read().await;
         let shards = shards_holder.select_shards(shard_selection)?;
 
@@ -379,16 +423,25 @@ impl Collection {
 
     pub async fn retrieve(
         &self,
-        request: PointRequestInternal,
+        mut request: PointRequestInternal,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Record>> {
+        let timeout = self.read_timeout(timeout, shard_selection).await;
         let with_payload_interface = request
             .with_payload
             .as_ref()
             .unwrap_or(&WithPayloadInterface::Bool(false));
         let with_payload = WithPayload::from(with_payload_interface);
+
+        if let Some(keep) = self
+            .check_result_points(shard_selection, request.ids.len())
+            .await?
+        {
+            request.ids.truncate(keep);
+        }
+
         let request = Arc::new(request);
 
         let all_shard_collection_results = {
@@ -439,3 +492,55 @@ impl Collection {
         Ok(points)
     }
 }
+
+/// Update lock and shard holder guards, held by a coalesced upsert batch until it is executed
+type UpdateGuards = Arc<(OwnedRwLockReadGuard<()>, OwnedRwLockReadGuard<ShardHolder>)>;
+
+/// Update shard with consistency, coalescing single point upserts if enabled
+///
+/// # Cancel safety
+///
+/// This method is *not* cancel safe.
+async fn update_with_coalescing(
+    guards: &UpdateGuards,
+    shard: &ShardReplicaSet,
+    operation: CollectionUpdateOperations,
+    wait: bool,
+    ordering: WriteOrdering,
+) -> CollectionResult<UpdateResult> {
+    let Some(coalescer) = shard.upsert_coalescer() else {
+        return shard
+            .update_with_consistency(operation, wait, ordering)
+            .await;
+    };
+
+    let point = match UpsertCoalescer::single_point_upsert(operation) {
+        Ok(point) => point,
+        Err(operation) => {
+            return shard
+                .update_with_consistency(operation, wait, ordering)
+                .await
+        }
+    };
+
+    // A bad point must not fail the upserts of other callers in the same batch
+    shard.check_point_vectors(&point).await?;
+
+    // Batch is executed in a separate task, which must own the shard holder
+    let guards = guards.clone();
+    let shard_id = shard.shard_id;
+    coalescer
+        .upsert(point, wait, ordering, move |operation, wait, ordering| {
+            let guards = guards.clone();
+            async move {
+                let (_update_lock, shard_holder) = &*guards;
+                let shard = shard_holder.get_shard(&shard_id).ok_or_else(|| {
+                    CollectionError::service_error(format!("Shard {shard_id} not found"))
+                })?;
+                shard
+                    .update_with_consistency(operation, wait, ordering)
+                    .await
+            }
+        })
+        .await
+}
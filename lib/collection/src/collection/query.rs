@@ -8,11 +8,14 @@ use itertools::{Either, Itertools};
 use rand::Rng;
 use segment::common::reciprocal_rank_fusion::rrf_scoring;
 use segment::common::score_fusion::{score_fusion, ScoreFusion};
-use segment::types::{Order, ScoredPoint};
-use segment::utils::scored_point_ties::ScoredPointTies;
+use segment::json_path::JsonPath;
+use segment::types::{Order, PayloadSelector, ScoredPoint, WithPayload, WithPayloadInterface};
+use segment::utils::scored_point_ties::{PayloadTieBreak, ScoredPointTies, TieBreak};
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
 
+use super::query_explanation::{explain_scores, ScoreExplanation};
+use super::search::round_scores;
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -28,6 +31,9 @@ use crate::operations::universal_query::shard_query::{
     Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
 };
 
+/// Points of a query result, with the explanations of their scores if requested
+type QueryResult = (Vec<ScoredPoint>, Option<Vec<ScoreExplanation>>);
+
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
     /// Limit + offset
@@ -46,10 +52,18 @@ impl Collection {
         if request.limit == 0 {
             return Ok(vec![]);
         }
+        let timeout = self.read_timeout(timeout, &shard_selection).await;
         let results = self
-            .do_query_batch(vec![(request)], read_consistency, shard_selection, timeout)
+            .do_query_batch(
+                vec![(request)],
+                read_consistency,
+                shard_selection,
+                timeout,
+                false,
+            )
             .await?;
-        Ok(results.into_iter().next().unwrap())
+        let (points, _) = results.into_iter().next().unwrap();
+        Ok(points)
     }
 
     /// Returns a shape of [shard_id, batch_id, intermediate_response, points]
@@ -90,15 +104,42 @@ impl Collection {
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
+    ///
+    /// If `explain` is set, each result also has the explanations of the scores of its points.
     async fn do_query_batch(
         &self,
-        requests_batch: Vec<ShardQueryRequest>,
+        mut requests_batch: Vec<ShardQueryRequest>,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
-    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        explain: bool,
+    ) -> CollectionResult<Vec<QueryResult>> {
+        self.check_tenant_scope(
+            &shard_selection,
+            requests_batch
+                .iter()
+                .flat_map(|request| request.filter_refs()),
+        )?;
+
         let instant = Instant::now();
 
+        let (tie_break, default_search_params) = {
+            let config = self.collection_config.read().await;
+            (config.tie_break.clone(), config.default_search_params)
+        };
+
+        // Payload values of the tie break are needed to merge results of shards
+        let requested_payloads: Vec<_> = requests_batch
+            .iter_mut()
+            .map(|request| {
+                let requested = request.with_payload.clone();
+                if let Some(TieBreak::Payload(PayloadTieBreak { key, .. })) = &tie_break {
+                    request.with_payload = with_tie_break_payload(&requested, key);
+                }
+                requested
+            })
+            .collect();
+
         let requests_batch = Arc::new(requests_batch);
 
         let all_shards_results = self
@@ -112,24 +153,45 @@ impl Collection {
 
         let results_f = transposed_iter(all_shards_results)
             .zip(requests_batch.iter())
-            .map(|(shards_results, request)| async {
+            .zip(&requested_payloads)
+            .map(|((shards_results, request), requested_payload)| async {
                 // shards_results shape: [num_shards, num_intermediate_results, num_points]
                 let merged_intermediates = self
                     .merge_intermediate_results_from_shards(request, shards_results)
                     .await?;
 
-                let result = Self::intermediates_to_final_list(
+                // Fusion consumes the intermediate results, keep them to explain the scores
+                let explain_intermediates = explain.then(|| merged_intermediates.clone());
+
+                let mut result = Self::intermediates_to_final_list(
                     merged_intermediates,
                     request.query.as_ref(),
                     request.limit,
                     request.offset,
                     request.score_threshold,
+                    tie_break.as_ref(),
                 )?;
 
+                let explanations = explain_intermediates.map(|intermediates| {
+                    explain_scores(&intermediates, request.query.as_ref(), &result)
+                });
+
+                if request.with_payload != *requested_payload {
+                    select_payload(&mut result, requested_payload);
+                }
+
+                round_scores(
+                    &mut result,
+                    request.params.as_ref(),
+                    default_search_params.as_ref(),
+                );
+
                 let filter_refs = request.filter_refs();
                 self.post_process_if_slow_request(instant.elapsed(), filter_refs);
+                self.search_log
+                    .record([(request, result.as_slice())], instant.elapsed());
 
-                Ok::<_, CollectionError>(result)
+                Ok::<_, CollectionError>((result, explanations))
             });
         let results = future::try_join_all(results_f).await?;
 
@@ -142,14 +204,15 @@ impl Collection {
         limit: usize,
         offset: usize,
         score_threshold: Option<ScoreType>,
+        tie_break: Option<&TieBreak>,
     ) -> CollectionResult<Vec<ScoredPoint>> {
         let result = match query {
             Some(ScoringQuery::Fusion(fusion)) => {
                 // If the root query is a Fusion, the returned results correspond to each the prefetches.
-                let mut fused = match fusion {
-                    Fusion::Rrf => rrf_scoring(intermediates),
-                    Fusion::Dbsf => score_fusion(intermediates, ScoreFusion::dbsf()),
-                };
+                let mut fused = fuse(intermediates, fusion);
+                if let Some(tie_break) = tie_break {
+                    fused.sort_by(|a, b| tie_break.cmp_scored(a, b, Order::LargeBetter));
+                }
                 if let Some(score_threshold) = score_threshold {
                     fused = fused
                         .into_iter()
@@ -184,12 +247,60 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        let results = self
+            .query_batch_impl(
+                requests_batch,
+                collection_by_name,
+                read_consistency,
+                timeout,
+                false,
+            )
+            .await?;
+
+        Ok(results.into_iter().map(|(points, _)| points).collect())
+    }
+
+    /// Same as [`Self::query_batch`], with the explanations of the scores if `explain` is set
+    pub(super) async fn query_batch_impl<'a, F, Fut>(
+        &self,
+        mut requests_batch: Vec<(CollectionQueryRequest, ShardSelectorInternal)>,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        explain: bool,
+    ) -> CollectionResult<Vec<QueryResult>>
     where
         F: Fn(String) -> Fut,
         Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
     {
         let start = Instant::now();
 
+        // Batches received from clients have no internal shard selections
+        let timeout = match requests_batch.first() {
+            Some((_, shard_selection)) => self.read_timeout(timeout, shard_selection).await,
+            None => timeout,
+        };
+
+        for (request, shard_selection) in &requests_batch {
+            self.check_filter_required(shard_selection, request.selecting_filters())
+                .await?;
+        }
+
+        // Internal requests to a specific shard are exempt from the result points limit
+        let keep_points = self
+            .check_result_limits(
+                &ShardSelectorInternal::All,
+                requests_batch
+                    .iter_mut()
+                    .filter(|(_, shard_selection)| !shard_selection.is_shard_id())
+                    .map(|(request, _)| &mut request.limit),
+            )
+            .await?;
+
         // Lift nested prefetches to root queries for vector resolution
         let resolver_requests = build_vector_resolver_queries(&requests_batch);
 
@@ -242,18 +353,30 @@ impl Collection {
                     read_consistency,
                     shard_selection,
                     timeout,
+                    explain,
                 ));
 
                 Ok(())
             },
         )?;
 
-        let results = future::try_join_all(futures)
+        let mut results: Vec<QueryResult> = future::try_join_all(futures)
             .await?
             .into_iter()
             .flatten()
             .collect();
 
+        if let Some(mut keep) = keep_points {
+            // Truncate the batch as a whole, results of the last queries are cut first
+            for (points, explanations) in &mut results {
+                points.truncate(keep);
+                if let Some(explanations) = explanations {
+                    explanations.truncate(keep);
+                }
+                keep -= points.len();
+            }
+        }
+
         Ok(results)
     }
 
@@ -311,7 +434,10 @@ impl Collection {
             .iter()
             .all(|shard_results| shard_results.len() == results_len));
 
-        let collection_params = self.collection_config.read().await.params.clone();
+        let (collection_params, tie_break) = {
+            let config = self.collection_config.read().await;
+            (config.params.clone(), config.tie_break.clone())
+        };
 
         // Shape: [num_internal_queries, num_shards, num_scored_points]
         let all_shards_result_by_transposed = transposed_iter(all_shards_results);
@@ -322,6 +448,22 @@ impl Collection {
             // `shards_results` shape: [num_shards, num_scored_points]
             let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
 
+            if let (Some(order), Some(tie_break)) = (order, &tie_break) {
+                // Shards break ties by id, so reorder points with equal scores before merging
+                let intermediate_result = shards_results
+                    .into_iter()
+                    .map(|mut points| {
+                        points.sort_by(|a, b| tie_break.cmp_scored(a, b, order));
+                        points
+                    })
+                    .kmerge_by(|a, b| tie_break.cmp_scored(a, b, order).is_lt())
+                    .dedup()
+                    .take(query_info.take)
+                    .collect();
+                results.push(intermediate_result);
+                continue;
+            }
+
             // Equivalent to:
             //
             // shards_results
@@ -368,6 +510,14 @@ impl Collection {
     }
 }
 
+/// Fuse the results of the prefetches into a single list, ordered by the fused score
+pub(super) fn fuse(intermediates: Vec<Vec<ScoredPoint>>, fusion: &Fusion) -> Vec<ScoredPoint> {
+    match fusion {
+        Fusion::Rrf => rrf_scoring(intermediates),
+        Fusion::Dbsf => score_fusion(intermediates, ScoreFusion::dbsf()),
+    }
+}
+
 /// Returns a list of the query that corresponds to each of the results in each shard.
 ///
 /// Example: `[info1, info2, info3]` corresponds to `[result1, result2, result3]` of each shard
@@ -396,3 +546,42 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
         }]
     }
 }
+
+/// Payload to fetch with the points, so that they can be ordered by the value of `key`
+fn with_tie_break_payload(
+    with_payload: &WithPayloadInterface,
+    key: &JsonPath,
+) -> WithPayloadInterface {
+    match with_payload {
+        WithPayloadInterface::Bool(true) => with_payload.clone(),
+        WithPayloadInterface::Bool(false) => WithPayloadInterface::Fields(vec![key.clone()]),
+        WithPayloadInterface::Fields(fields) => {
+            WithPayloadInterface::Fields(fields.iter().chain([key]).cloned().collect())
+        }
+        WithPayloadInterface::Selector(PayloadSelector::Include(selector)) => {
+            WithPayloadInterface::Selector(PayloadSelector::new_include(
+                selector.include.iter().chain([key]).cloned().collect(),
+            ))
+        }
+        WithPayloadInterface::Selector(PayloadSelector::Exclude(_)) => true.into(),
+    }
+}
+
+/// Reduce payload of the points to the requested one
+fn select_payload(points: &mut [ScoredPoint], requested: &WithPayloadInterface) {
+    let WithPayload {
+        enable,
+        payload_selector,
+    } = WithPayload::from(requested);
+
+    for point in points {
+        if !enable {
+            point.payload = None;
+        } else if let Some(selector) = &payload_selector {
+            point.payload = point
+                .payload
+                .take()
+                .map(|payload| selector.process(payload));
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::time::Duration;
+
+use api::rest::{Fusion as RestFusion, ScoredPoint as RestScoredPoint};
+use common::types::ScoreType;
+use schemars::JsonSchema;
+use segment::types::{PointIdType, ScoredPoint};
+use serde::Serialize;
+use tokio::sync::RwLockReadGuard;
+
+use super::query::fuse;
+use super::Collection;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::universal_query::collection_query::CollectionQueryRequest;
+use crate::operations::universal_query::shard_query::{Fusion, ScoringQuery};
+
+/// Contribution of a prefetch to the fused score of a point
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct PrefetchContribution {
+    /// Position of the prefetch in the request
+    pub prefetch: usize,
+    /// Position of the point in the results of the prefetch, starting from 0
+    pub rank: usize,
+    /// Score of the point in the results of the prefetch
+    pub score: ScoreType,
+    /// Part of the fused score contributed by the prefetch
+    pub contribution: ScoreType,
+}
+
+/// How the score of a query result is composed
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq)]
+pub struct ScoreExplanation {
+    /// Fusion of the prefetch results, not set if the score is computed by the query itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fusion: Option<RestFusion>,
+    /// Prefetches, which results contain the point, the fused score is the sum of their
+    /// contributions
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefetches: Vec<PrefetchContribution>,
+}
+
+/// Query result with the explanation of its score
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExplainedPoint {
+    pub point: RestScoredPoint,
+    pub explanation: ScoreExplanation,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ExplainedQueryResponse {
+    pub points: Vec<ExplainedPoint>,
+}
+
+/// Explain the scores of the final `points` of a query, from the merged results of its
+/// prefetches
+///
+/// Contributions are computed by fusing the results of each prefetch on their own, which gives
+/// the same rank and normalization of the scores as the fusion of all of them.
+pub(super) fn explain_scores(
+    intermediates: &[Vec<ScoredPoint>],
+    query: Option<&ScoringQuery>,
+    points: &[ScoredPoint],
+) -> Vec<ScoreExplanation> {
+    let Some(ScoringQuery::Fusion(fusion)) = query else {
+        return vec![
+            ScoreExplanation {
+                fusion: None,
+                prefetches: vec![],
+            };
+            points.len()
+        ];
+    };
+
+    // Contribution of each point to the fused score, by prefetch
+    let prefetch_contributions: Vec<HashMap<PointIdType, PrefetchContribution>> = intermediates
+        .iter()
+        .enumerate()
+        .map(|(prefetch, results)| {
+            let mut contributions: HashMap<_, _> = results
+                .iter()
+                .enumerate()
+                .map(|(rank, point)| {
+                    let contribution = PrefetchContribution {
+                        prefetch,
+                        rank,
+                        score: point.score,
+                        contribution: 0.0,
+                    };
+                    (point.id, contribution)
+                })
+                .collect();
+
+            for point in fuse(vec![results.clone()], fusion) {
+                if let Some(contribution) = contributions.get_mut(&point.id) {
+                    contribution.contribution = point.score;
+                }
+            }
+
+            contributions
+        })
+        .collect();
+
+    let rest_fusion = match fusion {
+        Fusion::Rrf => RestFusion::Rrf,
+        Fusion::Dbsf => RestFusion::Dbsf,
+    };
+
+    points
+        .iter()
+        .map(|point| ScoreExplanation {
+            fusion: Some(rest_fusion),
+            prefetches: prefetch_contributions
+                .iter()
+                .filter_map(|contributions| contributions.get(&point.id).cloned())
+                .collect(),
+        })
+        .collect()
+}
+
+impl Collection {
+    /// Execute a universal query, explaining how the score of each returned point is composed
+    ///
+    /// For fusion queries, each point lists the prefetches it was found by, with its rank and
+    /// score in their results and the part of the fused score they contributed.
+    pub async fn query_explain<'a, F, Fut>(
+        &self,
+        request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        collection_by_name: F,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<ExplainedQueryResponse>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = Option<RwLockReadGuard<'a, Collection>>>,
+    {
+        let (points, explanations) = self
+            .query_batch_impl(
+                vec![(request, shard_selection)],
+                collection_by_name,
+                read_consistency,
+                timeout,
+                true,
+            )
+            .await?
+            .pop()
+            .ok_or_else(|| {
+                CollectionError::service_error("Expected at least one response for one query")
+            })?;
+
+        let explanations = explanations.ok_or_else(|| {
+            CollectionError::service_error("Query response was expected to have explanations")
+        })?;
+
+        let points = points
+            .into_iter()
+            .zip(explanations)
+            .map(|(point, explanation)| ExplainedPoint {
+                point: RestScoredPoint::from(point),
+                explanation,
+            })
+            .collect();
+
+        Ok(ExplainedQueryResponse { points })
+    }
+}
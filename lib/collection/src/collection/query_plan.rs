@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use api::rest::Fusion as RestFusion;
+use common::types::ScoreType;
+use futures::future::try_join_all;
+use schemars::JsonSchema;
+use segment::common::BYTES_IN_KB;
+use segment::json_path::JsonPath;
+use segment::types::{Filter, SearchParams};
+use serde::Serialize;
+
+use super::Collection;
+use crate::config::CollectionConfig;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionResult, CountRequestInternal, Datatype};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query,
+};
+use crate::operations::universal_query::shard_query::{Fusion, Sample};
+
+/// Expected way of searching the vectors of a query step
+///
+/// The strategy is chosen for each segment at search time, the plan shows the one expected for
+/// the estimated number of points matching the filter.
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorSearchStrategy {
+    /// Approximate search on the HNSW graph, the filter is checked while traversing the graph
+    Hnsw,
+    /// Points matching the filter are selected with the payload index, and scored without the
+    /// vector index, as the filter is too restrictive for the HNSW graph
+    Plain,
+    /// Exact search, all points matching the filter are scored
+    Exact,
+    /// Search on the inverted index of sparse vectors
+    SparseIndex,
+    /// Candidates from the prefetches are scored again, no index is used
+    Rescore,
+}
+
+/// How points are scored in a step of the query plan
+#[derive(Debug, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum QueryPlanScoring {
+    /// Points are scored against the query vector(s)
+    Vector {
+        /// Name of the vector used for scoring
+        using: String,
+        /// Expected way of searching the vectors
+        strategy: VectorSearchStrategy,
+    },
+    /// Results of the prefetches are fused into a single list
+    Fusion { fusion: RestFusion },
+    /// Points are ordered by a payload field
+    OrderBy { key: JsonPath },
+    /// Points are sampled randomly
+    Sample,
+    /// No query, points are ordered by their ID
+    Id,
+}
+
+/// Single step of the query plan, executed after all of its prefetches
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct QueryPlanStep {
+    /// How points are scored in this step
+    pub scoring: QueryPlanScoring,
+    /// Estimated number of points matching the filter, not set if the step has no filter
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cardinality: Option<usize>,
+    /// Minimal score of the returned points
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<ScoreType>,
+    /// Max number of points returned by the step
+    pub limit: usize,
+    /// Steps executed before this one, their results are the candidates of this step
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub prefetches: Vec<QueryPlanStep>,
+}
+
+/// Plan of a universal query, describing how it is going to be executed
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct QueryPlan {
+    /// Number of shards the query is sent to
+    pub shards: usize,
+    /// Number of points skipped from the results of the root step
+    pub offset: usize,
+    /// Root step of the query
+    pub root: QueryPlanStep,
+}
+
+/// Common view of the root query and its prefetches
+struct PlanInput<'a> {
+    prefetch: &'a [CollectionPrefetch],
+    query: Option<&'a Query>,
+    using: &'a String,
+    filter: Option<&'a Filter>,
+    score_threshold: Option<ScoreType>,
+    limit: usize,
+    params: Option<&'a SearchParams>,
+}
+
+impl<'a> From<&'a CollectionQueryRequest> for PlanInput<'a> {
+    fn from(request: &'a CollectionQueryRequest) -> Self {
+        Self {
+            prefetch: &request.prefetch,
+            query: request.query.as_ref(),
+            using: &request.using,
+            filter: request.filter.as_ref(),
+            score_threshold: request.score_threshold,
+            limit: request.limit,
+            params: request.params.as_ref(),
+        }
+    }
+}
+
+impl<'a> From<&'a CollectionPrefetch> for PlanInput<'a> {
+    fn from(prefetch: &'a CollectionPrefetch) -> Self {
+        Self {
+            prefetch: &prefetch.prefetch,
+            query: prefetch.query.as_ref(),
+            using: &prefetch.using,
+            filter: prefetch.filter.as_ref(),
+            score_threshold: prefetch.score_threshold,
+            limit: prefetch.limit,
+            params: prefetch.params.as_ref(),
+        }
+    }
+}
+
+impl<'a> PlanInput<'a> {
+    /// Filters of this step and all of its prefetches, depth-first
+    fn collect_filters(&self, filters: &mut Vec<Option<&'a Filter>>) {
+        filters.push(self.filter);
+        for prefetch in self.prefetch {
+            PlanInput::from(prefetch).collect_filters(filters);
+        }
+    }
+
+    /// Build the step, taking the estimated cardinalities in the order of `collect_filters`
+    fn build_step(
+        &self,
+        config: &CollectionConfig,
+        cardinalities: &mut impl Iterator<Item = Option<usize>>,
+    ) -> CollectionResult<QueryPlanStep> {
+        CollectionQueryRequest::validation(
+            &self.query.cloned(),
+            self.using,
+            self.prefetch,
+            self.score_threshold,
+        )?;
+
+        let estimated_cardinality = cardinalities.next().flatten();
+
+        let scoring = match self.query {
+            Some(Query::Vector(_)) => {
+                // Fails if the vector doesn't exist
+                config.params.get_distance(self.using)?;
+
+                let strategy = if !self.prefetch.is_empty() {
+                    VectorSearchStrategy::Rescore
+                } else if config
+                    .params
+                    .get_sparse_vector_params_opt(self.using)
+                    .is_some()
+                {
+                    VectorSearchStrategy::SparseIndex
+                } else if self.params.is_some_and(|params| params.is_exact()) {
+                    VectorSearchStrategy::Exact
+                } else if estimated_cardinality.is_some_and(|cardinality| {
+                    cardinality < full_scan_threshold(config, self.using)
+                }) {
+                    VectorSearchStrategy::Plain
+                } else {
+                    VectorSearchStrategy::Hnsw
+                };
+
+                QueryPlanScoring::Vector {
+                    using: self.using.clone(),
+                    strategy,
+                }
+            }
+            Some(Query::Fusion(fusion)) => QueryPlanScoring::Fusion {
+                fusion: match fusion {
+                    Fusion::Rrf => RestFusion::Rrf,
+                    Fusion::Dbsf => RestFusion::Dbsf,
+                },
+            },
+            Some(Query::OrderBy(order_by)) => QueryPlanScoring::OrderBy {
+                key: order_by.key.clone(),
+            },
+            Some(Query::Sample(Sample::Random)) => QueryPlanScoring::Sample,
+            None => QueryPlanScoring::Id,
+        };
+
+        let prefetches = self
+            .prefetch
+            .iter()
+            .map(|prefetch| PlanInput::from(prefetch).build_step(config, cardinalities))
+            .collect::<CollectionResult<_>>()?;
+
+        Ok(QueryPlanStep {
+            scoring,
+            estimated_cardinality,
+            score_threshold: self.score_threshold,
+            limit: self.limit,
+            prefetches,
+        })
+    }
+}
+
+/// Number of points below which HNSW search of the given dense vector falls back to plain search
+fn full_scan_threshold(config: &CollectionConfig, vector_name: &str) -> usize {
+    let Some(params) = config.params.vectors.get_params(vector_name) else {
+        return 0;
+    };
+
+    let threshold_kb = params
+        .hnsw_config
+        .as_ref()
+        .and_then(|hnsw_config| hnsw_config.full_scan_threshold)
+        .unwrap_or(config.hnsw_config.full_scan_threshold);
+
+    let element_size = match params.datatype.unwrap_or_default() {
+        Datatype::Float32 => 4,
+        Datatype::Float16 => 2,
+        Datatype::Uint8 => 1,
+    };
+    let vector_size = params.size.get() as usize * element_size;
+
+    threshold_kb.saturating_mul(BYTES_IN_KB) / vector_size
+}
+
+impl Collection {
+    /// Describe how a universal query is going to be executed, without executing it
+    ///
+    /// The plan lists the steps of the query with their prefetches, the expected vector search
+    /// strategy and the estimated number of points matching each filter.
+    pub async fn query_plan(
+        &self,
+        request: &CollectionQueryRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<QueryPlan> {
+        let shards = self
+            .shards_holder
+            .read()
+            .await
+            .select_shards(shard_selection)?
+            .len();
+
+        let input = PlanInput::from(request);
+
+        let mut filters = Vec::new();
+        input.collect_filters(&mut filters);
+
+        let estimations = filters.into_iter().map(|filter| async move {
+            let Some(filter) = filter else {
+                return Ok(None);
+            };
+            let count = self
+                .count(
+                    CountRequestInternal {
+                        filter: Some(filter.clone()),
+                        exact: false,
+                    },
+                    read_consistency,
+                    shard_selection,
+                    timeout,
+                )
+                .await?;
+            CollectionResult::Ok(Some(count.count))
+        });
+        let cardinalities = try_join_all(estimations).await?;
+
+        let config = self.collection_config.read().await;
+        let root = input.build_step(&config, &mut cardinalities.into_iter())?;
+
+        Ok(QueryPlan {
+            shards,
+            offset: request.offset,
+            root,
+        })
+    }
+}
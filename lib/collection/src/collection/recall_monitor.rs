@@ -0,0 +1,116 @@
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use super::Collection;
+use crate::operations::types::CollectionResult;
+
+const fn default_interval_sec() -> u64 {
+    600
+}
+
+const fn default_sample_size() -> usize {
+    10
+}
+
+const fn default_limit() -> usize {
+    10
+}
+
+/// Configuration of the background job, which measures recall of approximate search
+#[derive(Debug, Deserialize, Serialize, Validate, Clone)]
+pub struct RecallMonitorConfig {
+    /// Interval between samples, in seconds
+    #[serde(default = "default_interval_sec")]
+    #[validate(range(min = 1))]
+    pub interval_sec: u64,
+    /// Number of random points per sample, whose vectors are used as queries
+    #[serde(default = "default_sample_size")]
+    #[validate(range(min = 1))]
+    pub sample_size: usize,
+    /// Number of nearest neighbours compared between approximate and exact search
+    #[serde(default = "default_limit")]
+    #[validate(range(min = 1))]
+    pub limit: usize,
+}
+
+impl Default for RecallMonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval_sec: default_interval_sec(),
+            sample_size: default_sample_size(),
+            limit: default_limit(),
+        }
+    }
+}
+
+/// Recall of approximate search, measured against exact search on sampled queries
+#[derive(Serialize, Clone, Debug, Default, JsonSchema)]
+pub struct RecallTelemetry {
+    /// Number of recall samples taken
+    pub samples: usize,
+    /// Recall of the most recent sample, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_recall: Option<f64>,
+    /// Average recall of all samples, from 0 to 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_recall: Option<f64>,
+    /// Time of the most recent sample
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_sample_time: Option<DateTime<Utc>>,
+}
+
+/// Keeps the recall measured by sampling the collection
+#[derive(Debug, Default)]
+pub struct RecallMonitor {
+    telemetry: Mutex<RecallTelemetry>,
+}
+
+impl RecallMonitor {
+    pub fn record(&self, recall: f64) {
+        let mut telemetry = self.telemetry.lock();
+        let previous_sum = telemetry.avg_recall.unwrap_or_default() * telemetry.samples as f64;
+        telemetry.samples += 1;
+        telemetry.last_recall = Some(recall);
+        telemetry.avg_recall = Some((previous_sum + recall) / telemetry.samples as f64);
+        telemetry.last_sample_time = Some(Utc::now());
+    }
+
+    /// Measured recall, `None` if nothing was sampled yet
+    pub fn get_telemetry_data(&self) -> Option<RecallTelemetry> {
+        let telemetry = self.telemetry.lock();
+        (telemetry.samples > 0).then(|| telemetry.clone())
+    }
+}
+
+impl Collection {
+    /// Measure recall of approximate search on a sample of queries
+    ///
+    /// Vectors of random points are used as queries, each one is searched for both with the
+    /// vector indices and exactly. Only local shards of this peer are sampled, and the searches
+    /// are not part of the search log or slow request detection.
+    /// The average share of exact results found by approximate search is recorded into the
+    /// collection telemetry and returned. Returns `None` if there is nothing to sample.
+    pub async fn sample_recall(
+        &self,
+        config: &RecallMonitorConfig,
+    ) -> CollectionResult<Option<f64>> {
+        let mut recalls = Vec::new();
+        {
+            let shard_holder = self.shards_holder.read().await;
+            for replica_set in shard_holder.all_shards() {
+                recalls.extend(replica_set.sample_recall(config).await?);
+            }
+        }
+
+        if recalls.is_empty() {
+            return Ok(None);
+        }
+
+        let recall = recalls.iter().sum::<f64>() / recalls.len() as f64;
+        self.recall_monitor.record(recall);
+        Ok(Some(recall))
+    }
+}
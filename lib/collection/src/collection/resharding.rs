@@ -93,6 +93,9 @@ impl Collection {
     /// This method will check if a resharding operation is in progress according to our state, and
     /// it will start and resume the driving task accordingly.
     ///
+    /// Points that were already migrated before the interruption are kept, the remaining ones are
+    /// migrated by the restarted task.
+    ///
     /// This does not check whether the task is already active.
     ///
     /// If no resharding is active, this returns early without error.
@@ -116,6 +119,37 @@ impl Collection {
         Ok(())
     }
 
+    /// Resume a resharding operation, which is still migrating points
+    ///
+    /// Unlike aborting, this keeps all points that were already migrated. The driving task is
+    /// restarted from its persisted progress, shards that were fully migrated are skipped and
+    /// the remaining shards are scanned again for points that are not in the target shard yet.
+    ///
+    /// Fails if the given resharding is not in progress, or already past migrating points.
+    pub async fn resume_resharding<T, F>(
+        &self,
+        resharding_key: ReshardKey,
+        consensus: Box<dyn ShardTransferConsensus>,
+        on_finish: T,
+        on_error: F,
+    ) -> CollectionResult<()>
+    where
+        T: Future<Output = ()> + Send + 'static,
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.shards_holder
+            .write()
+            .await
+            .check_resume_resharding(&resharding_key)?;
+
+        let _ = self.stop_resharding_task(&resharding_key).await;
+
+        self.drive_resharding(resharding_key, consensus, true, on_finish, on_error)
+            .await?;
+
+        Ok(())
+    }
+
     async fn drive_resharding<T, F>(
         &self,
         resharding_key: ReshardKey,
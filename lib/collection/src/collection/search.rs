@@ -3,11 +3,12 @@ use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::types::ScoreType;
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
-    ExtendedPointId, Filter, Order, ScoredPoint, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, Order, ScoredPoint, SearchParams, WithPayloadInterface, WithVector,
 };
 use tokio::time::Instant;
 
@@ -38,6 +39,37 @@ impl Collection {
         Ok(results.into_iter().next().unwrap())
     }
 
+    /// Search for the ids and scores of the closest points only
+    ///
+    /// Neither payloads nor vectors are fetched, regardless of what the request asks for.
+    pub async fn search_ids_only(
+        &self,
+        mut request: CoreSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<(ExtendedPointId, ScoreType)>> {
+        if request.limit == 0 {
+            return Ok(vec![]);
+        }
+        request.with_payload = Some(WithPayloadInterface::Bool(false));
+        request.with_vector = Some(WithVector::Bool(false));
+
+        let request_batch = CoreSearchRequestBatch {
+            searches: vec![request],
+        };
+        let results = self
+            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+            .await?;
+        Ok(results
+            .into_iter()
+            .next()
+            .unwrap()
+            .into_iter()
+            .map(|point| (point.id, point.score))
+            .collect())
+    }
+
     pub async fn core_search_batch(
         &self,
         request: CoreSearchRequestBatch,
@@ -50,6 +82,7 @@ impl Collection {
         if request.searches.iter().all(|s| s.limit == 0) {
             return Ok(vec![]);
         }
+        let timeout = self.read_timeout(timeout, &shard_selection).await;
         // A factor which determines if we need to use the 2-step search or not
         // Should be adjusted based on usage statistics.
         const PAYLOAD_TRANSFERS_FACTOR_THRESHOLD: usize = 10;
@@ -127,11 +160,28 @@ impl Collection {
 
     async fn do_core_search_batch(
         &self,
-        request: CoreSearchRequestBatch,
+        mut request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let timeout = self.read_timeout(timeout, shard_selection).await;
+        self.check_tenant_scope(
+            shard_selection,
+            request.searches.iter().map(|req| req.filter.as_ref()),
+        )?;
+        self.check_filter_required(
+            shard_selection,
+            request.searches.iter().map(|req| req.filter.as_ref()),
+        )
+        .await?;
+        let keep_points = self
+            .check_result_limits(
+                shard_selection,
+                request.searches.iter_mut().map(|req| &mut req.limit),
+            )
+            .await?;
+
         let request = Arc::new(request);
 
         let instant = Instant::now();
@@ -164,7 +214,7 @@ impl Collection {
             future::try_join_all(all_searches).await?
         };
 
-        let result = self
+        let mut result = self
             .merge_from_shards(
                 all_searches_res,
                 Arc::clone(&request),
@@ -172,10 +222,26 @@ impl Collection {
             )
             .await;
 
+        if let (Ok(results), Some(mut keep)) = (&mut result, keep_points) {
+            // Truncate the batch as a whole, results of the last searches are cut first
+            for batch_result in results.iter_mut() {
+                batch_result.truncate(keep);
+                keep -= batch_result.len();
+            }
+        }
+
         let filters_refs = request.searches.iter().map(|req| req.filter.as_ref());
 
         self.post_process_if_slow_request(instant.elapsed(), filters_refs);
 
+        if let Ok(results) = &result {
+            let searches = request
+                .searches
+                .iter()
+                .zip(results.iter().map(Vec::as_slice));
+            self.search_log.record(searches, instant.elapsed());
+        }
+
         result
     }
 
@@ -238,7 +304,10 @@ impl Collection {
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let batch_size = request.searches.len();
 
-        let collection_params = self.collection_config.read().await.params.clone();
+        let (collection_params, default_search_params) = {
+            let config = self.collection_config.read().await;
+            (config.params.clone(), config.default_search_params)
+        };
 
         // Merge results from shards in order and deduplicate based on point ID
         let mut top_results: Vec<Vec<ScoredPoint>> = Vec::with_capacity(batch_size);
@@ -267,7 +336,7 @@ impl Collection {
 
             // Skip `offset` only for client requests
             // to avoid applying `offset` twice in distributed mode.
-            let top_res = if is_client_request && request.offset > 0 {
+            let mut top_res: Vec<_> = if is_client_request && request.offset > 0 {
                 merged_iter
                     .skip(request.offset)
                     .take(request.limit)
@@ -276,6 +345,15 @@ impl Collection {
                 merged_iter.take(request.offset + request.limit).collect()
             };
 
+            // Round scores once, on the instance responding to the client
+            if is_client_request {
+                round_scores(
+                    &mut top_res,
+                    request.params.as_ref(),
+                    default_search_params.as_ref(),
+                );
+            }
+
             top_results.push(top_res);
 
             seen_ids.clear();
@@ -302,3 +380,15 @@ impl Collection {
         }
     }
 }
+
+/// Round scores of the final results to the precision of the request params, falling back to the
+/// default search params of the collection
+pub(super) fn round_scores(
+    points: &mut [ScoredPoint],
+    params: Option<&SearchParams>,
+    defaults: Option<&SearchParams>,
+) {
+    let params = params.copied().unwrap_or_default();
+    let defaults = defaults.copied().unwrap_or_default();
+    params.with_defaults(&defaults).round_scores(points);
+}
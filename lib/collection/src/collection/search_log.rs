@@ -0,0 +1,207 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use rand::Rng;
+use ringbuffer::{ConstGenericRingBuffer, RingBuffer as _};
+use schemars::JsonSchema;
+use segment::types::{Filter, PointIdType, ScoredPoint, SearchParams};
+use serde::Serialize;
+
+use crate::operations::types::CoreSearchRequest;
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+
+/// Search request sampled for debugging recall and latency.
+///
+/// Only describes the request and the ids of found points, query vectors and payloads are not
+/// recorded.
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct SampledSearch {
+    /// Time when the search was finished
+    pub timestamp: DateTime<Utc>,
+    /// Hash of the query, identifies repeated queries without recording the query vector
+    pub query_hash: u64,
+    /// Name of the vector used for the search, empty if the query is not scored by a vector
+    pub using: String,
+    pub filter: Option<Filter>,
+    pub params: Option<SearchParams>,
+    pub limit: usize,
+    pub offset: usize,
+    pub score_threshold: Option<f32>,
+    /// Duration of the whole search batch the request was part of
+    pub duration_micros: u64,
+    /// Ids of found points, in the order of the result
+    pub result_ids: Vec<PointIdType>,
+}
+
+/// Records a random sample of search requests to a collection, keeping only the most recent ones.
+#[derive(Debug)]
+pub struct SearchLog {
+    sample_rate: f64,
+    recent: Mutex<ConstGenericRingBuffer<SampledSearch, { Self::SIZE }>>,
+}
+
+impl SearchLog {
+    const SIZE: usize = 128;
+
+    /// Sample each search with probability `sample_rate`, nothing is sampled if it is zero.
+    pub fn new(sample_rate: f64) -> Self {
+        Self {
+            sample_rate: sample_rate.clamp(0.0, 1.0),
+            recent: Mutex::new(ConstGenericRingBuffer::new()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.sample_rate > 0.0
+    }
+
+    /// Record a sample of the given searches or queries and their results.
+    pub fn record<'a, R: LoggedSearch + 'a>(
+        &self,
+        searches: impl IntoIterator<Item = (&'a R, &'a [ScoredPoint])>,
+        duration: Duration,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let timestamp = Utc::now();
+        let sampled = searches
+            .into_iter()
+            .filter(|_| rng.gen_bool(self.sample_rate))
+            .map(|(request, result)| request.to_sampled(timestamp, duration, result))
+            .collect::<Vec<_>>();
+
+        if sampled.is_empty() {
+            return;
+        }
+
+        self.recent.lock().extend(sampled);
+    }
+
+    /// Recently sampled searches, oldest first.
+    pub fn recent(&self) -> Vec<SampledSearch> {
+        self.recent.lock().to_vec()
+    }
+}
+
+/// Request, which can be recorded in the [`SearchLog`]
+pub trait LoggedSearch {
+    fn to_sampled(
+        &self,
+        timestamp: DateTime<Utc>,
+        duration: Duration,
+        result: &[ScoredPoint],
+    ) -> SampledSearch;
+}
+
+impl LoggedSearch for CoreSearchRequest {
+    fn to_sampled(
+        &self,
+        timestamp: DateTime<Utc>,
+        duration: Duration,
+        result: &[ScoredPoint],
+    ) -> SampledSearch {
+        SampledSearch {
+            timestamp,
+            query_hash: query_hash(&self.query),
+            using: self.query.get_vector_name().to_string(),
+            filter: self.filter.clone(),
+            params: self.params,
+            limit: self.limit,
+            offset: self.offset,
+            score_threshold: self.score_threshold,
+            duration_micros: duration.as_micros() as u64,
+            result_ids: result.iter().map(|point| point.id).collect(),
+        }
+    }
+}
+
+impl LoggedSearch for ShardQueryRequest {
+    fn to_sampled(
+        &self,
+        timestamp: DateTime<Utc>,
+        duration: Duration,
+        result: &[ScoredPoint],
+    ) -> SampledSearch {
+        let using = match &self.query {
+            Some(ScoringQuery::Vector(query)) => query.get_vector_name().to_string(),
+            Some(ScoringQuery::Fusion(_) | ScoringQuery::OrderBy(_) | ScoringQuery::Sample(_))
+            | None => String::new(),
+        };
+
+        SampledSearch {
+            timestamp,
+            // Prefetches are part of the query, fusion queries only differ by them
+            query_hash: query_hash(&(&self.query, &self.prefetches)),
+            using,
+            filter: self.filter.clone(),
+            params: self.params,
+            limit: self.limit,
+            offset: self.offset,
+            score_threshold: self.score_threshold,
+            duration_micros: duration.as_micros() as u64,
+            result_ids: result.iter().map(|point| point.id).collect(),
+        }
+    }
+}
+
+fn query_hash(query: &impl Debug) -> u64 {
+    // Vectors can't be hashed directly, but their debug representation includes all the values
+    let mut hasher = DefaultHasher::new();
+    format!("{query:?}").hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::NamedVectorStruct;
+
+    use super::*;
+    use crate::operations::query_enum::QueryEnum;
+
+    fn search_request(vector: Vec<f32>) -> CoreSearchRequest {
+        CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedVectorStruct::Default(vector)),
+            filter: None,
+            params: None,
+            limit: 10,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        }
+    }
+
+    #[test]
+    fn test_search_log_sampling() {
+        let request = search_request(vec![1.0, 2.0]);
+        let other_request = search_request(vec![2.0, 1.0]);
+        let no_results: &[ScoredPoint] = &[];
+
+        let disabled = SearchLog::new(0.0);
+        disabled.record([(&request, no_results)], Duration::from_millis(1));
+        assert!(disabled.recent().is_empty());
+
+        let log = SearchLog::new(1.0);
+        for _ in 0..SearchLog::SIZE {
+            log.record(
+                [(&request, no_results), (&other_request, no_results)],
+                Duration::from_millis(1),
+            );
+        }
+
+        // Only the most recent searches are kept
+        let recent = log.recent();
+        assert_eq!(recent.len(), SearchLog::SIZE);
+
+        // Same queries have the same hash
+        assert_eq!(recent[0].query_hash, recent[2].query_hash);
+        assert_ne!(recent[0].query_hash, recent[1].query_hash);
+    }
+}
@@ -5,9 +5,13 @@ use std::time::Duration;
 
 use common::defaults;
 use parking_lot::Mutex;
+use segment::types::Filter;
 
 use super::Collection;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CountRequestInternal, ShardTransferProgressInfo,
+};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
@@ -21,6 +25,39 @@ use crate::shards::transfer::{
 };
 
 impl Collection {
+    /// List shard transfers driven by this peer which are still running, with their progress.
+    pub async fn running_shard_transfers(&self) -> Vec<ShardTransferProgressInfo> {
+        let tasks_progress = self.transfer_tasks.lock().await.running_tasks_progress();
+        let shard_holder = self.shards_holder.read().await;
+
+        let mut transfers: Vec<_> = tasks_progress
+            .into_iter()
+            .filter_map(|(transfer_key, progress)| {
+                let transfer = shard_holder.get_transfer(&transfer_key)?;
+                let progress_percent = (progress.points_total > 0).then(|| {
+                    progress.points_transferred as f64 / progress.points_total as f64 * 100.0
+                });
+                Some(ShardTransferProgressInfo {
+                    collection_name: self.id.clone(),
+                    shard_id: transfer.shard_id,
+                    to_shard_id: transfer.to_shard_id,
+                    from: transfer.from,
+                    to: transfer.to,
+                    sync: transfer.sync,
+                    method: transfer.method,
+                    stage: progress.stage,
+                    points_transferred: progress.points_transferred,
+                    points_total: progress.points_total,
+                    progress_percent,
+                    started_at: progress.started_at,
+                    eta_seconds: progress.eta.map(|eta| eta.as_secs_f64()),
+                })
+            })
+            .collect();
+        transfers.sort_by_key(|transfer| transfer.shard_id);
+        transfers
+    }
+
     pub async fn get_outgoing_transfers(&self, current_peer_id: &PeerId) -> Vec<ShardTransfer> {
         self.shards_holder
             .read()
@@ -35,6 +72,68 @@ impl Collection {
             .check_transfer_exists(transfer_key)
     }
 
+    /// Select method to transfer the given shard with, if none is specified explicitly.
+    ///
+    /// Prefers the configured default method. Otherwise the method is chosen based on the number
+    /// of points in the shard, see [`ShardTransferMethod::auto_select`].
+    pub async fn select_shard_transfer_method(&self, shard_id: ShardId) -> ShardTransferMethod {
+        if let Some(method) = self.shared_storage_config.default_shard_transfer_method {
+            return method;
+        }
+
+        // The shard may not be local, count through any replica
+        let count = self
+            .count(
+                CountRequestInternal {
+                    filter: None,
+                    exact: false,
+                },
+                None,
+                &ShardSelectorInternal::ShardId(shard_id),
+                None,
+            )
+            .await;
+
+        match count {
+            Ok(count) => {
+                let method = ShardTransferMethod::auto_select(count.count);
+                log::debug!(
+                    "Selected {method:?} to transfer shard {shard_id} with {} points",
+                    count.count,
+                );
+                method
+            }
+            Err(err) => {
+                let method = ShardTransferMethod::default();
+                log::warn!(
+                    "Failed to count points of shard {shard_id} to select transfer method, using {method:?}: {err}",
+                );
+                method
+            }
+        }
+    }
+
+    /// Check that a filter limiting a shard transfer only uses indexed payload keys.
+    ///
+    /// The filter is evaluated on every transferred batch, so an unindexed key would turn each
+    /// batch into a full scan of the shard.
+    pub fn check_transfer_filter(&self, filter: &Filter) -> CollectionResult<()> {
+        if let Some((key, schemas)) = self.one_unindexed_key(filter) {
+            let possible_schemas_str = schemas
+                .iter()
+                .map(|schema| schema.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            return Err(CollectionError::strict_mode(
+                format!("Index required but not found for \"{key}\" of one of the following types: [{possible_schemas_str}]"),
+                "Create an index for this key or use a different filter.",
+            ));
+        }
+
+        Ok(())
+    }
+
     pub async fn start_shard_transfer<T, F>(
         &self,
         mut shard_transfer: ShardTransfer,
@@ -57,6 +156,17 @@ impl Collection {
             shard_transfer.method.replace(method);
         }
 
+        // Without WAL, updates made during the transfer can only be forwarded by streaming
+        // records. Config is the same on all peers, so they all select the same method.
+        let method = shard_transfer.method.unwrap_or_default();
+        if method.uses_wal() && self.collection_config.read().await.wal_config.wal_disabled {
+            log::warn!(
+                "Shard transfer method {method:?} requires WAL, which is disabled, using {:?}",
+                ShardTransferMethod::StreamRecords,
+            );
+            shard_transfer.method = Some(ShardTransferMethod::StreamRecords);
+        }
+
         let do_transfer = {
             let this_peer_id = consensus.this_peer_id();
             let is_receiver = this_peer_id == shard_transfer.to;
@@ -202,21 +312,23 @@ impl Collection {
             None => shard_holder_guard.insert(self.shards_holder.read().await),
         };
 
+        // Normally we promote the shard to become active, in case of resharding we do not.
+        // For resharding we have multiple transfers in sequence, during which the shard should
+        // remain in the resharding state. Once all are done, the shard is manually promoted to active.
+        // A filtered transfer only copies some points, so the shard becomes a listener.
+        let finished_state = transfer.finished_replica_state();
+
         // Should happen on transfer side
         // Unwrap forward proxy into local shard, or replace it with remote shard
         // depending on the `sync` flag.
         if self.this_peer_id == transfer.from {
-            // Normally we promote the shard to become active, in case of resharding we do not.
-            // For resharding we have multiple transfers in sequence, during which the shard should
-            // remain in the resharding state. Once all are done, the shard is manually promoted to active.
-            let activate_shard = transfer
-                .method
-                .map_or(true, |method| !method.is_resharding());
+            let target_state =
+                (finished_state != ReplicaState::Resharding).then_some(finished_state);
             let proxy_promoted = transfer::driver::handle_transferred_shard_proxy(
                 shard_holder,
                 transfer.shard_id,
                 transfer.to,
-                activate_shard,
+                target_state,
                 transfer.sync,
             )
             .await?;
@@ -231,8 +343,12 @@ impl Collection {
                 .method
                 .map_or(false, |method| method.is_resharding())
         {
-            let shard_promoted =
-                transfer::driver::finalize_partial_shard(shard_holder, transfer.shard_id).await?;
+            let shard_promoted = transfer::driver::finalize_partial_shard(
+                shard_holder,
+                transfer.shard_id,
+                finished_state,
+            )
+            .await?;
             log::debug!(
                 "shard_promoted: {shard_promoted}, shard_id: {}, peer_id: {}",
                 transfer.shard_id,
@@ -243,24 +359,13 @@ impl Collection {
         // Should happen on a third-party side
         // Change direction of the remote shards or add a new remote shard
         if self.this_peer_id != transfer.from {
-            // Normally we promote the shard to become active, in case of resharding we do not.
-            // For resharding we have multiple transfers in sequence, during which the shard should
-            // remain in the resharding state. Once all are done, the shard is manually promoted to active.
-            let state = if transfer
-                .method
-                .map_or(false, |method| method.is_resharding())
-            {
-                ReplicaState::Resharding
-            } else {
-                ReplicaState::Active
-            };
             let remote_shard_rerouted = transfer::driver::change_remote_shard_route(
                 shard_holder,
                 transfer.shard_id,
                 transfer.to_shard_id.unwrap_or(transfer.shard_id),
                 transfer.from,
                 transfer.to,
-                state,
+                finished_state,
                 transfer.sync,
             )
             .await?;
@@ -327,6 +432,41 @@ impl Collection {
         Ok(())
     }
 
+    /// Abort a single running transfer task driven by this peer
+    ///
+    /// 1. Signal the transfer task to stop and wait for it
+    /// 2. Remove it from the transfer tasks pool
+    /// 3. Revert the proxy, turning the source shard into a plain local shard again
+    ///
+    /// The transfer is not unregistered here. Because its task is gone, it is reported as
+    /// failed to consensus on the next local state synchronization.
+    ///
+    /// Returns `false` if there is no task for this transfer on this peer.
+    pub async fn abort_transfer(&self, transfer_key: ShardTransferKey) -> CollectionResult<bool> {
+        let task_result = self
+            .transfer_tasks
+            .lock()
+            .await
+            .stop_task(&transfer_key)
+            .await;
+
+        if task_result.is_none() {
+            return Ok(false);
+        }
+
+        log::info!(
+            "Aborted transfer of shard {}:{} -> {}",
+            self.id,
+            transfer_key.shard_id,
+            transfer_key.to,
+        );
+
+        let shard_holder = self.shards_holder.read().await;
+        transfer::driver::revert_proxy_shard_to_local(&shard_holder, transfer_key.shard_id).await?;
+
+        Ok(true)
+    }
+
     /// Initiate local partial shard
     pub fn initiate_shard_transfer(
         &self,
@@ -1,10 +1,14 @@
 use std::collections::HashSet;
 
-use segment::types::ShardKey;
+use segment::types::{
+    AnyVariants, Condition, Filter, Match, MatchAny, MatchExcept, MatchValue, PayloadKeyType,
+    ShardKey, ValueVariants,
+};
 
 use crate::collection::Collection;
 use crate::config::ShardingMethod;
-use crate::operations::types::CollectionError;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::{
     CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
@@ -159,4 +163,112 @@ impl Collection {
             .remove_shard_key(&shard_key)
             .await
     }
+
+    /// Reject tenant scoped requests with filters matching other tenants
+    ///
+    /// Conditions on tenant payload fields may only match the value of the shard key the request
+    /// is scoped to. Existence of the shard key itself is validated on shard selection.
+    pub(crate) fn check_tenant_scope<'a>(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+        filters: impl IntoIterator<Item = Option<&'a Filter>>,
+    ) -> CollectionResult<()> {
+        let Some(tenant) = shard_selection.tenant_scope() else {
+            return Ok(());
+        };
+
+        let payload_index_schema = self.payload_index_schema.read();
+        let tenant_fields: HashSet<_> = payload_index_schema
+            .schema
+            .iter()
+            .filter(|(_, schema)| schema.is_tenant())
+            .map(|(key, _)| key)
+            .collect();
+
+        if tenant_fields.is_empty() {
+            return Ok(());
+        }
+
+        for filter in filters.into_iter().flatten() {
+            if let Some(key) = cross_tenant_condition(filter, tenant, &tenant_fields, false) {
+                return Err(CollectionError::bad_request(format!(
+                    "Condition on tenant field {key} reaches outside of tenant scope {tenant}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Find a condition on a tenant field which may match any tenant other than `tenant`
+///
+/// Conditions of a negated filter, e.g. within `must_not`, are checked with inverted polarity.
+fn cross_tenant_condition<'a>(
+    filter: &'a Filter,
+    tenant: &ShardKey,
+    tenant_fields: &HashSet<&PayloadKeyType>,
+    negated: bool,
+) -> Option<&'a PayloadKeyType> {
+    let positive = filter
+        .must
+        .iter()
+        .flatten()
+        .chain(filter.should.iter().flatten())
+        .chain(
+            filter
+                .min_should
+                .iter()
+                .flat_map(|min_should| &min_should.conditions),
+        )
+        .map(|condition| (condition, negated));
+    let negative = filter
+        .must_not
+        .iter()
+        .flatten()
+        .map(|condition| (condition, !negated));
+
+    positive
+        .chain(negative)
+        .find_map(|(condition, negated)| match condition {
+            Condition::Field(field) if tenant_fields.contains(&field.key) => field
+                .r#match
+                .as_ref()
+                .filter(|r#match| !matches_only_tenant(r#match, tenant, negated))
+                .map(|_| &field.key),
+            Condition::Filter(filter) => {
+                cross_tenant_condition(filter, tenant, tenant_fields, negated)
+            }
+            _ => None,
+        })
+}
+
+/// Check whether the match, or its negation if `negated`, only matches `tenant`
+fn matches_only_tenant(r#match: &Match, tenant: &ShardKey, negated: bool) -> bool {
+    let is_tenant_keyword =
+        |value: &String| matches!(tenant, ShardKey::Keyword(key) if key == value);
+    let is_tenant_number =
+        |value: &i64| matches!(tenant, ShardKey::Number(key) if u64::try_from(*value) == Ok(*key));
+    let only_tenant_values = |values: &AnyVariants| match values {
+        AnyVariants::Strings(values) => values.iter().all(is_tenant_keyword),
+        AnyVariants::Integers(values) => values.iter().all(is_tenant_number),
+    };
+
+    if negated {
+        // Negation of any other match also matches other tenants
+        return match r#match {
+            Match::Except(MatchExcept { except }) => only_tenant_values(except),
+            Match::Value(_) | Match::Any(_) | Match::Text(_) => false,
+        };
+    }
+
+    match r#match {
+        Match::Value(MatchValue { value }) => match value {
+            ValueVariants::String(value) => is_tenant_keyword(value),
+            ValueVariants::Integer(value) => is_tenant_number(value),
+            ValueVariants::Bool(_) => false,
+        },
+        Match::Any(MatchAny { any }) => only_tenant_values(any),
+        Match::Text(_) | Match::Except(_) => false,
+    }
 }
@@ -1,17 +1,25 @@
 use std::collections::HashSet;
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
-use io::file_operations::read_json;
+use io::file_operations::{atomic_save_json, read_json};
 use io::storage_version::StorageVersion as _;
+use segment::json_path::JsonPath;
+use segment::segment::Segment;
+use segment::types::{Condition, Filter, IsEmptyCondition, PayloadField};
 use tokio::fs;
 
 use super::Collection;
 use crate::collection::CollectionVersion;
+use crate::common::snapshot_archive::archive_directory;
+use crate::common::snapshot_space::ensure_snapshot_space;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::snapshot_ops::SnapshotDescription;
-use crate::operations::types::{CollectionError, CollectionResult, NodeType};
+use crate::operations::types::{CollectionError, CollectionResult, CountRequestInternal, NodeType};
 use crate::shards::local_shard::LocalShard;
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::replica_set::ShardReplicaSet;
@@ -41,13 +49,19 @@ impl Collection {
     ///
     /// * `global_temp_dir`: directory used to host snapshots while they are being created
     /// * `this_peer_id`: current peer id
+    /// * `exclude_payload_keys`: payload keys to strip from points stored in the snapshot,
+    ///   the collection itself is not modified. Such snapshot has an empty WAL, so it only
+    ///   contains operations already applied to segments
     ///
     /// returns: Result<SnapshotDescription, CollectionError>
     pub async fn create_snapshot(
         &self,
         global_temp_dir: &Path,
         this_peer_id: PeerId,
+        exclude_payload_keys: &[JsonPath],
     ) -> CollectionResult<SnapshotDescription> {
+        self.check_payload_keys_exist(exclude_payload_keys).await?;
+
         let snapshot_name = format!(
             "{}-{this_peer_id}-{}.snapshot",
             self.name(),
@@ -62,6 +76,16 @@ impl Collection {
             snapshot_path
         );
 
+        // Fail fast if the temp directory can't fit the snapshot, before writing anything
+        let shard_paths = self
+            .shards_holder
+            .read()
+            .await
+            .get_shards()
+            .map(|(_, replica_set)| replica_set.shard_path.clone())
+            .collect();
+        ensure_snapshot_space(global_temp_dir, shard_paths).await?;
+
         // Dedicated temporary directory for this snapshot (deleted on drop)
         let snapshot_temp_target_dir = tempfile::Builder::new()
             .prefix(&format!("{snapshot_name}-target-"))
@@ -105,12 +129,15 @@ impl Collection {
                     })?;
 
                 // If node is listener, we can save whatever currently is in the storage
-                let save_wal = self.shared_storage_config.node_type != NodeType::Listener;
+                // WAL is not stripped, replaying it would bring excluded payload keys back
+                let save_wal = self.shared_storage_config.node_type != NodeType::Listener
+                    && exclude_payload_keys.is_empty();
                 replica_set
                     .create_snapshot(
                         snapshot_temp_temp_dir.path(),
                         &shard_snapshot_path,
                         save_wal,
+                        Arc::new(AtomicBool::new(false)),
                     )
                     .await
                     .map_err(|err| {
@@ -120,6 +147,15 @@ impl Collection {
                         ))
                     })?;
             }
+
+            if !exclude_payload_keys.is_empty() {
+                strip_payload_keys_from_snapshot(
+                    &snapshot_temp_target_dir_path,
+                    snapshot_temp_temp_dir.path(),
+                    exclude_payload_keys,
+                )
+                .await?;
+            }
         }
 
         // Save collection config and version
@@ -136,8 +172,17 @@ impl Collection {
 
         let payload_index_schema_tmp_path =
             Self::payload_index_file(&snapshot_temp_target_dir_path);
-        self.payload_index_schema
-            .save_to(&payload_index_schema_tmp_path)?;
+        if exclude_payload_keys.is_empty() {
+            self.payload_index_schema
+                .save_to(&payload_index_schema_tmp_path)?;
+        } else {
+            // Don't recreate indexes of the excluded keys on recovery
+            let mut payload_index_schema = self.payload_index_schema.read().clone();
+            for key in exclude_payload_keys {
+                payload_index_schema.schema.remove(key);
+            }
+            atomic_save_json(&payload_index_schema_tmp_path, &payload_index_schema)?;
+        }
 
         // Dedicated temporary file for archiving this snapshot (deleted on drop)
         let mut snapshot_temp_arc_file = tempfile::Builder::new()
@@ -153,12 +198,14 @@ impl Collection {
 
         // Archive snapshot folder into a single file
         log::debug!("Archiving snapshot {snapshot_temp_target_dir_path:?}");
+        let compression = self.shared_storage_config.snapshots_config.compression;
         let archiving = tokio::task::spawn_blocking(move || -> CollectionResult<_> {
-            let mut builder = tar::Builder::new(snapshot_temp_arc_file.as_file_mut());
             // archive recursively collection directory `snapshot_path_with_arc_extension` into `snapshot_path`
-            builder.append_dir_all(".", &snapshot_temp_target_dir_path)?;
-            builder.finish()?;
-            drop(builder);
+            archive_directory(
+                &snapshot_temp_target_dir_path,
+                snapshot_temp_arc_file.as_file_mut(),
+                compression,
+            )?;
             // return ownership of the file
             Ok(snapshot_temp_arc_file)
         });
@@ -178,6 +225,28 @@ impl Collection {
             })
     }
 
+    /// Check that every key is present in the payload of at least one point.
+    async fn check_payload_keys_exist(&self, keys: &[JsonPath]) -> CollectionResult<()> {
+        for key in keys {
+            let request = CountRequestInternal {
+                filter: Some(Filter::new_must_not(Condition::IsEmpty(IsEmptyCondition {
+                    is_empty: PayloadField { key: key.clone() },
+                }))),
+                exact: true,
+            };
+            let result = self
+                .count(request, None, &ShardSelectorInternal::All, None)
+                .await?;
+            if result.count == 0 {
+                return Err(CollectionError::bad_input(format!(
+                    "Payload key {key} to exclude from snapshot does not exist in collection {}",
+                    self.name(),
+                )));
+            }
+        }
+        Ok(())
+    }
+
     /// Restore collection from snapshot
     ///
     /// This method performs blocking IO.
@@ -286,15 +355,23 @@ impl Collection {
             .await
     }
 
+    /// Snapshot creation is aborted once `stopped` is set.
     pub async fn create_shard_snapshot(
         &self,
         shard_id: ShardId,
         temp_dir: &Path,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<SnapshotDescription> {
         self.shards_holder
             .read()
             .await
-            .create_shard_snapshot(&self.snapshots_path, &self.name(), shard_id, temp_dir)
+            .create_shard_snapshot(
+                &self.snapshots_path,
+                &self.name(),
+                shard_id,
+                temp_dir,
+                stopped,
+            )
             .await
     }
 
@@ -339,3 +416,42 @@ impl Collection {
             .await
     }
 }
+
+/// Strip payload `keys` from all segment snapshots of the shards in `snapshot_dir`.
+async fn strip_payload_keys_from_snapshot(
+    snapshot_dir: &Path,
+    temp_dir: &Path,
+    keys: &[JsonPath],
+) -> CollectionResult<()> {
+    let snapshot_dir = snapshot_dir.to_path_buf();
+    let temp_dir = temp_dir.to_path_buf();
+    let keys = keys.to_vec();
+
+    tokio::task::spawn_blocking(move || -> CollectionResult<()> {
+        for shard_entry in std::fs::read_dir(&snapshot_dir)? {
+            let segments_dir = LocalShard::segments_path(&shard_entry?.path());
+            if !segments_dir.is_dir() {
+                continue;
+            }
+
+            for segment_entry in std::fs::read_dir(&segments_dir)? {
+                let segment_snapshot_path = segment_entry?.path();
+                if segment_snapshot_path.extension() != Some("tar".as_ref()) {
+                    continue;
+                }
+
+                let stripped_points = Segment::strip_payload_keys_from_snapshot(
+                    &segment_snapshot_path,
+                    &temp_dir,
+                    &keys,
+                )?;
+                log::debug!(
+                    "Stripped excluded payload keys from {stripped_points} points of segment snapshot {}",
+                    segment_snapshot_path.display(),
+                );
+            }
+        }
+        Ok(())
+    })
+    .await?
+}
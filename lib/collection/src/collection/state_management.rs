@@ -72,6 +72,8 @@ impl Collection {
         {
             let mut config = self.collection_config.write().await;
             config.params.replication_factor = new_config.params.replication_factor;
+            config.params.shard_key_replication_factors =
+                new_config.params.shard_key_replication_factors;
             config.params.write_consistency_factor = new_config.params.write_consistency_factor;
         }
 
@@ -0,0 +1,46 @@
+use segment::index::field_index::full_text_index::VocabEntry;
+use segment::json_path::JsonPath;
+
+use super::Collection;
+use crate::operations::types::CollectionResult;
+use crate::shards::local_shard::text_vocab::SegmentVocab;
+use crate::shards::shard::ShardId;
+use crate::shards::shard_holder::shard_not_found_error;
+
+impl Collection {
+    /// Dump the vocabulary of the full-text index of `field` in a local shard of this peer
+    pub async fn export_full_text_vocab(
+        &self,
+        shard_id: ShardId,
+        field: &JsonPath,
+    ) -> CollectionResult<Vec<SegmentVocab>> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        replica_set
+            .with_local_shard(|local_shard| local_shard.export_full_text_vocab(field))
+            .await
+    }
+
+    /// Prewarm the vocabulary of the full-text index of `field` in a local shard of this peer,
+    /// from a dump of [`Self::export_full_text_vocab`]
+    ///
+    /// Returns the number of tokens added over all segments of the shard.
+    pub async fn import_full_text_vocab(
+        &self,
+        shard_id: ShardId,
+        field: &JsonPath,
+        vocab: &[VocabEntry],
+    ) -> CollectionResult<usize> {
+        let shard_holder = self.shards_holder.read().await;
+        let replica_set = shard_holder
+            .get_shard(&shard_id)
+            .ok_or_else(|| shard_not_found_error(shard_id))?;
+
+        replica_set
+            .with_local_shard(|local_shard| local_shard.import_full_text_vocab(field, vocab))
+            .await
+    }
+}
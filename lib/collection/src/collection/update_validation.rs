@@ -0,0 +1,289 @@
+use segment::common::check_named_vectors;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::types::{Filter, PointIdType, SegmentConfig};
+use validator::Validate as _;
+
+use super::Collection;
+use crate::collection_manager::segments_updater::points_from_insert_operation;
+use crate::operations::payload_schema::{PayloadSchemaEnforcement, PayloadSchemaViolation};
+use crate::operations::point_ops::{ConditionalUpsert, DedupUpsert, PointOperations};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, UpdateValidationError};
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::verification::StrictModeVerification as _;
+use crate::operations::CollectionUpdateOperations;
+
+impl Collection {
+    /// Check strict mode restrictions for an update operation, if strict mode is enabled.
+    pub(crate) async fn check_update_strict_mode(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+        let strict_mode_config = config
+            .strict_mode_config
+            .as_ref()
+            .filter(|strict_mode_config| strict_mode_config.enabled == Some(true));
+
+        if let Some(strict_mode_config) = strict_mode_config {
+            operation.check_strict_mode(self, strict_mode_config)?;
+        }
+
+        Ok(())
+    }
+
+    /// Check that all read requests carry a filter, if strict mode requires it.
+    ///
+    /// Internal requests, targeting a specific shard, are exempt. Those are either issued by
+    /// the node itself, e.g. for shard transfers, or have been checked by the node they were
+    /// received on.
+    pub(crate) async fn check_filter_required<'a>(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+        filters: impl IntoIterator<Item = Option<&'a Filter>>,
+    ) -> CollectionResult<()> {
+        if shard_selection.is_shard_id() {
+            return Ok(());
+        }
+
+        let config = self.collection_config.read().await;
+        let require_filter = config
+            .strict_mode_config
+            .as_ref()
+            .filter(|strict_mode_config| strict_mode_config.enabled == Some(true))
+            .and_then(|strict_mode_config| strict_mode_config.require_filter)
+            .unwrap_or(false);
+
+        if require_filter && filters.into_iter().any(|filter| filter.is_none()) {
+            return Err(CollectionError::strict_mode(
+                "Filter is required for requests to this collection",
+                "Add a filter to the request.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Check the total number of points a read request may return against the strict mode limit.
+    ///
+    /// Checked before the request is sent to the shards, so that shards never return more points
+    /// than the limit. Returns the number of points to keep, if the request has to be truncated.
+    /// Requests exceeding the limit are rejected, unless strict mode is configured to truncate
+    /// them.
+    ///
+    /// Internal requests, targeting a specific shard, are exempt. The limit is applied on the
+    /// node the request was received on.
+    pub(crate) async fn check_result_points(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+        result_points: usize,
+    ) -> CollectionResult<Option<usize>> {
+        if shard_selection.is_shard_id() {
+            return Ok(None);
+        }
+
+        let config = self.collection_config.read().await;
+        let Some(strict_mode_config) = config
+            .strict_mode_config
+            .as_ref()
+            .filter(|strict_mode_config| strict_mode_config.enabled == Some(true))
+        else {
+            return Ok(None);
+        };
+
+        let Some(max_result_points) = strict_mode_config.max_result_points else {
+            return Ok(None);
+        };
+
+        if result_points <= max_result_points {
+            return Ok(None);
+        }
+
+        if strict_mode_config.truncate_result_points == Some(true) {
+            return Ok(Some(max_result_points));
+        }
+
+        Err(CollectionError::strict_mode(
+            format!(
+                "Request may return {result_points} points, over the limit of {max_result_points}"
+            ),
+            "Request fewer points, e.g. by reducing the limit or the number of ids.",
+        ))
+    }
+
+    /// Check the limits of a batch of read requests against the strict mode limit on result
+    /// points, see [`Self::check_result_points`].
+    ///
+    /// If the batch has to be truncated, limits of the last requests are lowered first. Every
+    /// request keeps a limit of at least 1, so the merged results must still be truncated to the
+    /// returned number of points.
+    pub(crate) async fn check_result_limits<'a>(
+        &self,
+        shard_selection: &ShardSelectorInternal,
+        limits: impl IntoIterator<Item = &'a mut usize>,
+    ) -> CollectionResult<Option<usize>> {
+        let limits: Vec<_> = limits.into_iter().collect();
+        let result_points = limits.iter().map(|limit| **limit).sum();
+
+        let keep = self
+            .check_result_points(shard_selection, result_points)
+            .await?;
+
+        if let Some(keep) = keep {
+            let mut remaining = keep;
+            for limit in limits {
+                *limit = (*limit).min(remaining).max(1);
+                remaining = remaining.saturating_sub(*limit);
+            }
+        }
+
+        Ok(keep)
+    }
+
+    /// Check payloads of an update operation against the payload schema of the collection.
+    ///
+    /// Depending on the enforcement mode, violations are rejected, logged or ignored.
+    pub(crate) async fn check_update_payload_schema(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<()> {
+        let config = self.collection_config.read().await;
+        let Some(payload_schema) = &config.payload_schema else {
+            return Ok(());
+        };
+
+        match payload_schema.enforcement {
+            PayloadSchemaEnforcement::Off => Ok(()),
+            PayloadSchemaEnforcement::Warn => {
+                for violation in payload_schema.violations(operation) {
+                    log::warn!(
+                        "Payload {}in collection {} does not match payload schema: {}",
+                        violation
+                            .point_id
+                            .map(|point_id| format!("of point {point_id} "))
+                            .unwrap_or_default(),
+                        self.id,
+                        violation.description,
+                    );
+                }
+                Ok(())
+            }
+            PayloadSchemaEnforcement::Strict => {
+                match payload_schema.violations(operation).first() {
+                    Some(violation) => Err(payload_schema_error(violation)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+
+    /// Validate an update operation without applying it.
+    ///
+    /// Runs the checks an update goes through before it is written to the WAL, and checks every
+    /// vector against the collection config, as segments do when the update is applied.
+    /// Returns all errors found, empty if the operation would succeed.
+    pub async fn validate_update(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> CollectionResult<Vec<UpdateValidationError>> {
+        // A malformed operation is rejected as a whole, nothing else to check
+        if let Err(err) = operation.validate() {
+            return Ok(vec![UpdateValidationError {
+                point_id: None,
+                error: err.into(),
+            }]);
+        }
+
+        let mut errors = Vec::new();
+
+        if let Err(error) = self.check_update_strict_mode(operation).await {
+            errors.push(UpdateValidationError {
+                point_id: None,
+                error,
+            });
+        }
+
+        {
+            let config = self.collection_config.read().await;
+            let strict_payload_schema = config.payload_schema.as_ref().filter(|payload_schema| {
+                payload_schema.enforcement == PayloadSchemaEnforcement::Strict
+            });
+            if let Some(payload_schema) = strict_payload_schema {
+                errors.extend(
+                    payload_schema
+                        .violations(operation)
+                        .iter()
+                        .map(|violation| UpdateValidationError {
+                            point_id: violation.point_id,
+                            error: payload_schema_error(violation),
+                        }),
+                );
+            }
+        }
+
+        let segment_config = {
+            let config = self.collection_config.read().await;
+            SegmentConfig {
+                vector_data: config.params.to_base_vector_data()?,
+                sparse_vector_data: config.params.to_sparse_vector_data()?,
+                payload_storage_type: config.params.payload_storage_type(),
+            }
+        };
+
+        let mut check_vectors = |point_id: PointIdType, vectors: &NamedVectors| {
+            if let Err(err) = check_named_vectors(vectors, &segment_config) {
+                errors.push(UpdateValidationError {
+                    point_id: Some(point_id),
+                    error: err.into(),
+                });
+            }
+        };
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(insert)
+                | PointOperations::InsertPointsIfAbsent(insert)
+                | PointOperations::UpsertPointsIfVersion(ConditionalUpsert {
+                    points: insert, ..
+                })
+                | PointOperations::UpsertPointsDedup(DedupUpsert { points: insert, .. }),
+            ) => {
+                for point in points_from_insert_operation(insert.clone()) {
+                    check_vectors(point.id, &point.get_vectors());
+                }
+            }
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(sync)) => {
+                for point in &sync.points {
+                    check_vectors(point.id, &point.get_vectors());
+                }
+            }
+            CollectionUpdateOperations::VectorOperation(VectorOperations::UpdateVectors(
+                update,
+            )) => {
+                for point in &update.points {
+                    check_vectors(point.id, &NamedVectors::from(point.vector.clone()));
+                }
+            }
+            CollectionUpdateOperations::PointOperation(_)
+            | CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => {}
+        }
+
+        Ok(errors)
+    }
+}
+
+fn payload_schema_error(violation: &PayloadSchemaViolation) -> CollectionError {
+    let description = match violation.point_id {
+        Some(point_id) => format!(
+            "Payload of point {point_id} does not match payload schema: {}",
+            violation.description,
+        ),
+        None => format!(
+            "Payload does not match payload schema: {}",
+            violation.description,
+        ),
+    };
+    CollectionError::bad_input(description)
+}
@@ -75,14 +75,14 @@ mod tests {
     use segment::data_types::vectors::{
         only_default_vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
     };
-    use segment::types::{Payload, WithPayload};
+    use segment::types::{Payload, PointIdType, WithPayload};
     use serde_json::json;
     use tempfile::Builder;
 
     use super::*;
     use crate::collection_manager::fixtures::build_test_holder;
     use crate::collection_manager::segments_searcher::SegmentsSearcher;
-    use crate::collection_manager::segments_updater::upsert_points;
+    use crate::collection_manager::segments_updater::{insert_points_if_absent, upsert_points};
     use crate::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
     use crate::operations::point_ops::{PointOperations, PointStruct};
 
@@ -133,6 +133,79 @@ mod tests {
                                     // points 11 and 12 are not updated as they are same as before
     }
 
+    #[test]
+    fn test_insert_points_if_absent() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let is_stopped = AtomicBool::new(false);
+
+        let segments = build_test_holder(dir.path());
+        let points = vec![
+            // Point 1 already exists, must not be overwritten
+            PointStruct {
+                id: 1.into(),
+                vector: VectorStructInternal::from(vec![2., 2., 2., 2.]).into(),
+                payload: Some(json!({ "color": "green" }).into()),
+            },
+            PointStruct {
+                id: 2.into(),
+                vector: VectorStructInternal::from(vec![2., 2., 2., 2.]).into(),
+                payload: None,
+            },
+            PointStruct {
+                id: 500.into(),
+                vector: VectorStructInternal::from(vec![2., 0., 2., 0.]).into(),
+                payload: None,
+            },
+        ];
+
+        let (inserted, skipped) = insert_points_if_absent(&segments.read(), 100, &points).unwrap();
+        assert_eq!(inserted, 1);
+        assert_eq!(skipped, 2);
+
+        let segments = Arc::new(segments);
+        let records = SegmentsSearcher::retrieve_blocking(
+            segments.clone(),
+            &[1.into(), 2.into(), 500.into()],
+            &WithPayload::from(true),
+            &true.into(),
+            &is_stopped,
+        )
+        .unwrap();
+
+        assert_eq!(records.len(), 3);
+
+        // Existing points are untouched
+        let record = &records[&PointIdType::NumId(1)];
+        assert_eq!(
+            record.vector.as_ref().unwrap().get(DEFAULT_VECTOR_NAME),
+            Some((&vec![1.0, 0.0, 1.0, 1.0]).into()),
+        );
+        assert_eq!(
+            record.payload.as_ref().unwrap(),
+            &json!({ "color": ["red"] }).into(),
+        );
+        let record = &records[&PointIdType::NumId(2)];
+        assert_eq!(
+            record.vector.as_ref().unwrap().get(DEFAULT_VECTOR_NAME),
+            Some((&vec![1.0, 0.0, 1.0, 0.0]).into()),
+        );
+
+        // New point is inserted
+        let record = &records[&PointIdType::NumId(500)];
+        assert_eq!(
+            record.vector.as_ref().unwrap().get(DEFAULT_VECTOR_NAME),
+            Some((&vec![2., 0., 2., 0.]).into()),
+        );
+
+        // Repeating the operation must not insert anything
+        let res = process_point_operation(
+            &segments,
+            101,
+            PointOperations::InsertPointsIfAbsent(points.into()),
+        );
+        assert!(matches!(res, Ok(0)));
+    }
+
     #[test]
     fn test_point_ops() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
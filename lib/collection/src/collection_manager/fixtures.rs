@@ -218,6 +218,7 @@ pub(crate) fn get_merge_optimizer(
             max_segment_size_kb: 100_000,
             memmap_threshold_kb: 1_000_000,
             indexing_threshold_kb: 1_000_000,
+            indexing_min_vector_number: 0,
         }),
         segment_path.to_owned(),
         collection_temp_dir.to_owned(),
@@ -243,6 +244,7 @@ pub(crate) fn get_indexing_optimizer(
             max_segment_size_kb: 100_000,
             memmap_threshold_kb: 100,
             indexing_threshold_kb: 100,
+            indexing_min_vector_number: 0,
         },
         segment_path.to_owned(),
         collection_temp_dir.to_owned(),
@@ -581,6 +581,20 @@ impl SegmentEntry for ProxySegment {
         Ok(result)
     }
 
+    fn prefetch_vectors(&self, vector_name: &str, point_ids: &[PointIdType]) -> usize {
+        let wrapped_ranges = self
+            .wrapped_segment
+            .get()
+            .read()
+            .prefetch_vectors(vector_name, point_ids);
+        let write_ranges = self
+            .write_segment
+            .get()
+            .read()
+            .prefetch_vectors(vector_name, point_ids);
+        wrapped_ranges + write_ranges
+    }
+
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload> {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment.get().read().payload(point_id)
@@ -902,6 +916,8 @@ impl SegmentEntry for ProxySegment {
             is_appendable: false,
             index_schema: wrapped_info.index_schema,
             vector_data,
+            created_at: wrapped_info.created_at,
+            last_optimized_at: wrapped_info.last_optimized_at,
         }
     }
 
@@ -3,25 +3,26 @@ use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet};
 use std::ops::Deref;
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 use std::thread::sleep;
 use std::time::{Duration, Instant};
 
 use common::iterator_ext::IteratorExt;
 use io::storage_version::StorageVersion;
-use parking_lot::{RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockUpgradableReadGuard, RwLockWriteGuard};
 use rand::seq::SliceRandom;
-use segment::common::operation_error::{OperationError, OperationResult};
+use segment::common::operation_error::{check_process_stopped, OperationError, OperationResult};
 use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
-use segment::types::{Payload, PointIdType, SegmentConfig, SeqNumberType};
+use segment::types::{Payload, PayloadContainer, PointIdType, SegmentConfig, SeqNumberType};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
+use crate::collection_manager::optimizers::OptimizerOutcomes;
 use crate::config::CollectionParams;
-use crate::operations::types::CollectionError;
+use crate::operations::types::{CollectionError, CollectionStatus, OptimizerError};
 use crate::shards::update_tracker::UpdateTracker;
 
 pub type SegmentId = usize;
@@ -145,7 +146,30 @@ pub struct SegmentHolder {
     pub failed_operation: BTreeSet<SeqNumberType>,
 
     /// Holds the first uncorrected error happened with optimizer
-    pub optimizer_errors: Option<CollectionError>,
+    pub optimizer_errors: Option<OptimizerError>,
+
+    /// Outcomes of the last optimization tasks
+    pub optimizer_outcomes: OptimizerOutcomes,
+
+    /// Versions of deleted points, which are not flushed yet.
+    ///
+    /// Segments forget the version of a point once it is deleted. Keeping the version of the
+    /// deletion prevents an older upsert, applied out of order, from bringing the point back.
+    deleted_versions: Mutex<HashMap<PointIdType, SeqNumberType>>,
+
+    /// Version of the last operation, which deleted all points by truncating segments, if it is
+    /// not flushed yet.
+    ///
+    /// Same as `deleted_versions`, but for every point at once.
+    truncated_version: Mutex<Option<SeqNumberType>>,
+
+    /// Segments, which vector indices should be rebuilt with the current configuration.
+    ///
+    /// Segments leave this set once they are replaced by an optimizer.
+    reindex_requested: HashSet<SegmentId>,
+
+    /// Stop flags of running optimizations, which rebuild segments requested to be reindexed
+    reindex_optimizations: Vec<Weak<AtomicBool>>,
 }
 
 pub type LockedSegmentHolder = Arc<RwLock<SegmentHolder>>;
@@ -232,6 +256,7 @@ impl<'s> SegmentHolder {
     pub fn remove(&mut self, remove_ids: &[SegmentId]) -> Vec<LockedSegment> {
         let mut removed_segments = vec![];
         for remove_id in remove_ids {
+            self.reindex_requested.remove(remove_id);
             let removed_segment = self.appendable_segments.remove(remove_id);
             if let Some(segment) = removed_segment {
                 removed_segments.push(segment);
@@ -300,6 +325,54 @@ impl<'s> SegmentHolder {
             .or_else(|| self.non_appendable_segments.get(&id))
     }
 
+    /// Request the vector indices of all indexed segments to be rebuilt
+    ///
+    /// Returns the number of segments scheduled for rebuilding.
+    pub fn request_reindex(&mut self) -> usize {
+        let indexed_ids = self
+            .iter()
+            .filter(|(_, segment)| {
+                segment
+                    .get()
+                    .read()
+                    .config()
+                    .vector_data
+                    .values()
+                    .any(|vector_data| vector_data.index.is_indexed())
+            })
+            .map(|(segment_id, _)| *segment_id)
+            .collect::<Vec<_>>();
+        self.reindex_requested.extend(&indexed_ids);
+        indexed_ids.len()
+    }
+
+    /// Drop all pending requests to rebuild vector indices
+    ///
+    /// Running optimizations registered with [`SegmentHolder::register_reindex_optimization`] are
+    /// asked to stop, other optimizations are not affected.
+    pub fn cancel_reindex(&mut self) {
+        self.reindex_requested.clear();
+        for stop_flag in self.reindex_optimizations.drain(..) {
+            if let Some(stopped) = stop_flag.upgrade() {
+                stopped.store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Register a running optimization, which rebuilds segments requested to be reindexed
+    ///
+    /// The optimization is stopped through its stop flag if the reindex is cancelled.
+    pub fn register_reindex_optimization(&mut self, stop_flag: Weak<AtomicBool>) {
+        // Forget finished optimizations, their stop flags are dropped with the task
+        self.reindex_optimizations
+            .retain(|stop_flag| stop_flag.strong_count() > 0);
+        self.reindex_optimizations.push(stop_flag);
+    }
+
+    pub fn is_reindex_requested(&self, segment_id: SegmentId) -> bool {
+        self.reindex_requested.contains(&segment_id)
+    }
+
     pub fn has_appendable_segment(&self) -> bool {
         !self.appendable_segments.is_empty()
     }
@@ -624,6 +697,95 @@ impl<'s> SegmentHolder {
         Ok(applied_points)
     }
 
+    /// Apply a payload-only operation `point_operation` to a set of points `ids`, and, if
+    /// necessary, move the points into appendable segments.
+    ///
+    /// Unlike [`Self::apply_points_with_conditional_move`], moving is decided per point. A point
+    /// of a non-appendable segment is still updated in place if `payload_cow_operation` leaves the
+    /// values of all indexed fields of its payload unchanged. Updating in place only touches
+    /// payload storage and payload indices, vectors are only read if the point has to be moved.
+    ///
+    /// `payload_cow_operation` must apply the same change to the payload as `point_operation`.
+    /// If `update_nonappendable` returns true for a segment, its points are updated in place
+    /// without checking their payload.
+    ///
+    /// Returns set of point ids which were successfully (already) applied to segments.
+    pub fn apply_payload_with_conditional_move<F, G, H>(
+        &self,
+        op_num: SeqNumberType,
+        ids: &[PointIdType],
+        mut point_operation: F,
+        mut payload_cow_operation: H,
+        mut update_nonappendable: G,
+    ) -> OperationResult<HashSet<PointIdType>>
+    where
+        F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
+        H: FnMut(&mut Payload),
+        G: FnMut(&dyn SegmentEntry) -> bool,
+    {
+        let _update_guard = self.update_tracker.update();
+
+        // Choose random appendable segment from this
+        let appendable_segments = self.appendable_segments_ids();
+
+        let mut applied_points: HashSet<PointIdType> = Default::default();
+
+        let _applied_points_count = self.apply_points(
+            ids,
+            |segment| {
+                // Indexed fields to check per point, `None` if all points are updated in place
+                if segment.is_appendable() || update_nonappendable(segment) {
+                    None
+                } else {
+                    Some(segment.get_indexed_fields().into_keys().collect::<Vec<_>>())
+                }
+            },
+            |point_id, _idx, write_segment, indexed_fields| {
+                if let Some(point_version) = write_segment.point_version(point_id) {
+                    if point_version >= op_num {
+                        applied_points.insert(point_id);
+                        return Ok(false);
+                    }
+                }
+
+                let moved_payload = match indexed_fields {
+                    None => None,
+                    Some(indexed_fields) => {
+                        let old_payload = write_segment.payload(point_id)?;
+                        let mut new_payload = old_payload.clone();
+                        payload_cow_operation(&mut new_payload);
+
+                        let indexed_values_changed = indexed_fields
+                            .iter()
+                            .any(|path| old_payload.get_value(path) != new_payload.get_value(path));
+                        indexed_values_changed.then_some(new_payload)
+                    }
+                };
+
+                let is_applied = match moved_payload {
+                    None => point_operation(point_id, write_segment)?,
+                    Some(payload) => self.aloha_random_write(
+                        &appendable_segments,
+                        |_appendable_idx, appendable_write_segment| {
+                            let all_vectors = write_segment.all_vectors(point_id)?;
+
+                            appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
+                            appendable_write_segment
+                                .set_full_payload(op_num, point_id, &payload)?;
+
+                            write_segment.delete_point(op_num, point_id)?;
+
+                            Ok(true)
+                        },
+                    )?,
+                };
+                applied_points.insert(point_id);
+                Ok(is_applied)
+            },
+        )?;
+        Ok(applied_points)
+    }
+
     pub fn read_points<F>(
         &self,
         ids: &[PointIdType],
@@ -747,11 +909,52 @@ impl<'s> SegmentHolder {
             drop(read_segment);
         }
 
-        if has_unsaved {
-            Ok(min_unsaved_version)
+        let flushed_version = if has_unsaved {
+            min_unsaved_version
         } else {
-            Ok(max_persisted_version)
+            max_persisted_version
+        };
+
+        // Persisted deletions are never replayed against older operations again
+        self.deleted_versions
+            .lock()
+            .retain(|_, version| *version > flushed_version);
+        let mut truncated_version = self.truncated_version.lock();
+        if truncated_version.is_some_and(|version| version <= flushed_version) {
+            *truncated_version = None;
         }
+
+        Ok(flushed_version)
+    }
+
+    /// Remember that the given points were deleted by operation `op_num`.
+    pub fn record_deleted_points(
+        &self,
+        op_num: SeqNumberType,
+        ids: impl IntoIterator<Item = PointIdType>,
+    ) {
+        let mut deleted_versions = self.deleted_versions.lock();
+        for id in ids {
+            let version = deleted_versions.entry(id).or_insert(op_num);
+            *version = max(*version, op_num);
+        }
+    }
+
+    /// Check whether the point was deleted by an operation not older than `op_num`.
+    ///
+    /// Such an operation must not recreate the point, as the deletion supersedes it.
+    pub fn is_deleted_since(&self, point_id: PointIdType, op_num: SeqNumberType) -> bool {
+        if self
+            .truncated_version
+            .lock()
+            .is_some_and(|version| version >= op_num)
+        {
+            return true;
+        }
+        self.deleted_versions
+            .lock()
+            .get(&point_id)
+            .map_or(false, |&version| version >= op_num)
     }
 
     /// Grab the RwLock's for all the given segment IDs.
@@ -834,9 +1037,13 @@ impl<'s> SegmentHolder {
 
             // Call provided function on segment
             if let Err(err) = f(segment) {
-                result = Err(OperationError::service_error(format!(
-                    "Applying function to a proxied shard segment {proxy_id} failed: {err}"
-                )));
+                result = Err(match err {
+                    // Keep cancellation distinguishable for the caller
+                    err @ OperationError::Cancelled { .. } => err,
+                    err => OperationError::service_error(format!(
+                        "Applying function to a proxied shard segment {proxy_id} failed: {err}"
+                    )),
+                });
                 break;
             }
 
@@ -884,6 +1091,52 @@ impl<'s> SegmentHolder {
         Ok(segment)
     }
 
+    /// Replace all segments with a single empty appendable segment, dropping all points at once
+    ///
+    /// The new segment takes the configuration and payload indexes of an existing appendable
+    /// segment. Like a deletion of every point, the truncation by operation `op_num` is
+    /// remembered until flushed, so that older upserts applied out of order don't bring points
+    /// back. Returns the removed segments, their data is not dropped yet. Returns `None` if the
+    /// segments can't be replaced: if any of them is being optimized, or there is no appendable
+    /// segment.
+    pub fn truncate(
+        &mut self,
+        op_num: SeqNumberType,
+    ) -> OperationResult<Option<Vec<LockedSegment>>> {
+        let is_optimizing = self
+            .iter()
+            .any(|(_, segment)| matches!(segment, LockedSegment::Proxy(_)));
+        if is_optimizing {
+            return Ok(None);
+        }
+
+        let Some(appendable_segment) = self.random_appendable_segment() else {
+            return Ok(None);
+        };
+        let (segments_path, payload_index_schema) = {
+            let appendable_segment = appendable_segment.get();
+            let appendable_segment = appendable_segment.read();
+            let Some(segments_path) = appendable_segment.data_path().parent().map(Path::to_owned)
+            else {
+                return Ok(None);
+            };
+            let payload_index_schema = PayloadIndexSchema {
+                schema: appendable_segment.get_indexed_fields(),
+            };
+            (segments_path, payload_index_schema)
+        };
+
+        let segment = self.build_tmp_segment(&segments_path, None, &payload_index_schema, true)?;
+        let segment_ids = self.segment_ids();
+        let (_, removed_segments) = self.swap_new(segment, &segment_ids);
+
+        let mut truncated_version = self.truncated_version.lock();
+        *truncated_version = Some(truncated_version.map_or(op_num, |version| version.max(op_num)));
+        drop(truncated_version);
+
+        Ok(Some(removed_segments))
+    }
+
     /// Build a temporary appendable segment, usually for proxying writes into.
     ///
     /// The segment configuration is sourced from the given collection parameters. If none is
@@ -1147,6 +1400,7 @@ impl<'s> SegmentHolder {
     /// temporary segment, which will source the configuration from it.
     ///
     /// Shortcuts at the first failing segment snapshot.
+    /// Checks `stopped` between segments and cancels the snapshot once it is set.
     pub fn snapshot_all_segments(
         segments: LockedSegmentHolder,
         segments_path: &Path,
@@ -1154,6 +1408,7 @@ impl<'s> SegmentHolder {
         payload_index_schema: &PayloadIndexSchema,
         temp_dir: &Path,
         snapshot_dir_path: &Path,
+        stopped: &AtomicBool,
     ) -> OperationResult<()> {
         // Snapshotting may take long-running read locks on segments blocking incoming writes, do
         // this through proxied segments to allow writes to continue.
@@ -1163,6 +1418,7 @@ impl<'s> SegmentHolder {
             collection_params,
             payload_index_schema,
             |segment| {
+                check_process_stopped(stopped)?;
                 let read_segment = segment.read();
                 read_segment.take_snapshot(temp_dir, snapshot_dir_path)?;
                 Ok(())
@@ -1174,7 +1430,30 @@ impl<'s> SegmentHolder {
         // Save only the first error
         // If is more likely to be the real cause of all further problems
         if self.optimizer_errors.is_none() {
-            self.optimizer_errors = Some(error.into());
+            self.optimizer_errors = Some(OptimizerError::from(error.into()));
+        }
+    }
+
+    /// Report a failed optimization task, with details such as the failed optimizer and segments
+    ///
+    /// Failed optimizations degrade the collection status depending on their rate, see
+    /// [`OptimizerOutcomes::health`].
+    pub fn report_optimizer_failure(&mut self, error: OptimizerError) {
+        self.optimizer_outcomes.record_failure(error);
+    }
+
+    /// Report a successfully finished optimization task
+    pub fn report_optimizer_success(&mut self) {
+        self.optimizer_outcomes.record_success();
+    }
+
+    /// Worst collection status caused by optimizer errors, with the error to report
+    ///
+    /// Uncorrected errors, such as failed flushes, always make the collection red.
+    pub fn optimizer_health(&self) -> Option<(CollectionStatus, OptimizerError)> {
+        match &self.optimizer_errors {
+            Some(error) => Some((CollectionStatus::Red, error.clone())),
+            None => self.optimizer_outcomes.health(),
         }
     }
 
@@ -1291,7 +1570,7 @@ mod tests {
     use segment::data_types::vectors::Vector;
     use segment::json_path::JsonPath;
     use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-    use segment::types::{Distance, PayloadContainer};
+    use segment::types::{Distance, PayloadFieldSchema, PayloadSchemaType};
     use serde_json::{json, Value};
     use tempfile::Builder;
 
@@ -1600,6 +1879,50 @@ mod tests {
         assert!(!holder.get(sid1).unwrap().get().read().has_point(5.into()));
     }
 
+    #[test]
+    fn test_truncate() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut segment1 = build_segment_1(dir.path());
+        let segment2 = build_segment_2(dir.path());
+
+        let color = JsonPath::new("color");
+        let color_schema = PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword);
+        segment1
+            .create_field_index(100, &color, Some(&color_schema))
+            .unwrap();
+
+        let mut holder = SegmentHolder::default();
+        holder.add_new(segment1);
+        holder.add_new(segment2);
+
+        let removed_segments = holder.truncate(200).unwrap().unwrap();
+        assert_eq!(removed_segments.len(), 2);
+        assert_eq!(holder.len(), 1);
+
+        // Truncation supersedes older operations on any point, until it is flushed
+        assert!(holder.is_deleted_since(1.into(), 200));
+        assert!(holder.is_deleted_since(1000.into(), 150));
+        assert!(!holder.is_deleted_since(1.into(), 201));
+
+        // New segment is empty, with the payload index of the old one
+        let (_, segment) = holder.iter().next().unwrap();
+        let segment = segment.get();
+        let segment = segment.read();
+        assert!(segment.is_appendable());
+        assert_eq!(segment.available_point_count(), 0);
+        assert_eq!(
+            segment.get_indexed_fields(),
+            HashMap::from([(color, color_schema)]),
+        );
+        drop(segment);
+
+        removed_segments
+            .into_iter()
+            .for_each(|segment| segment.drop_data().unwrap());
+        assert_eq!(read_dir(dir.path()).unwrap().count(), 1);
+    }
+
     #[test]
     fn test_snapshot_all() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -1630,6 +1953,7 @@ mod tests {
             &PayloadIndexSchema::default(),
             temp_dir.path(),
             snapshot_dir.path(),
+            &AtomicBool::new(false),
         )
         .unwrap();
 
@@ -1648,4 +1972,26 @@ mod tests {
         // one archive produced per concrete segment in the SegmentHolder
         assert_eq!(archive_count, 2);
     }
+
+    #[test]
+    fn test_cancel_reindex_stops_registered_optimizations() {
+        let mut holder = SegmentHolder::default();
+
+        // Finished optimizations are forgotten
+        let finished_stopped = Arc::new(AtomicBool::new(false));
+        holder.register_reindex_optimization(Arc::downgrade(&finished_stopped));
+        drop(finished_stopped);
+
+        let reindex_stopped = Arc::new(AtomicBool::new(false));
+        holder.register_reindex_optimization(Arc::downgrade(&reindex_stopped));
+        assert_eq!(holder.reindex_optimizations.len(), 1);
+
+        // Other optimizations are not registered, and not stopped
+        let other_stopped = Arc::new(AtomicBool::new(false));
+
+        holder.cancel_reindex();
+        assert!(reindex_stopped.load(Ordering::Relaxed));
+        assert!(!other_stopped.load(Ordering::Relaxed));
+        assert!(holder.reindex_optimizations.is_empty());
+    }
 }
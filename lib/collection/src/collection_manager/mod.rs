@@ -5,7 +5,7 @@ pub mod segments_searcher;
 
 mod probabilistic_segment_search_sampling;
 mod search_result_aggregator;
-mod segments_updater;
+pub(crate) mod segments_updater;
 
 #[allow(dead_code)]
 #[cfg(test)]
@@ -12,6 +12,7 @@ use crate::collection_manager::holders::segment_holder::{LockedSegmentHolder, Se
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
 
@@ -20,6 +21,8 @@ use crate::operations::config_diff::DiffConfig;
 /// For example, a user may change the HNSW parameters for a collection. A segment that was already
 /// indexed with different parameters now has a mismatch. This segment should be optimized (and
 /// indexed) again in order to update the effective configuration.
+///
+/// Segments explicitly requested to be reindexed are rebuilt even if their configuration matches.
 pub struct ConfigMismatchOptimizer {
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
@@ -27,6 +30,7 @@ pub struct ConfigMismatchOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    segment_volumes: SegmentVolumes,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -46,10 +50,17 @@ impl ConfigMismatchOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            segment_volumes: SegmentVolumes::default(),
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Spread optimized segments across the given storage volumes.
+    pub fn with_segment_volumes(mut self, segment_volumes: SegmentVolumes) -> Self {
+        self.segment_volumes = segment_volumes;
+        self
+    }
+
     /// Check if current configuration requires vectors to be stored on disk
     fn check_if_vectors_on_disk(&self, vector_name: &str) -> Option<bool> {
         self.collection_params
@@ -120,6 +131,10 @@ impl ConfigMismatchOptimizer {
                     return None; // Never optimize already optimized segment
                 }
 
+                if segments_read_guard.is_reindex_requested(*idx) {
+                    return Some((*idx, vector_size));
+                }
+
                 if self.collection_params.on_disk_payload
                     != segment_config.payload_storage_type.is_on_disk()
                 {
@@ -237,6 +252,10 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
         self.quantization_config.clone()
     }
 
+    fn segment_volumes(&self) -> &SegmentVolumes {
+        &self.segment_volumes
+    }
+
     fn threshold_config(&self) -> &OptimizerThresholds {
         &self.thresholds_config
     }
@@ -252,6 +271,10 @@ impl SegmentOptimizer for ConfigMismatchOptimizer {
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
         &self.telemetry_durations_aggregator
     }
+
+    fn rebuilds_reindex_requested(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -299,6 +322,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            indexing_min_vector_number: 0,
         };
         let collection_params = CollectionParams {
             vectors: VectorsConfig::Single(
@@ -324,6 +348,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            use_heuristic: None,
         };
 
         // Optimizers used in test
@@ -408,6 +433,111 @@ mod tests {
             });
     }
 
+    /// Test that the config mismatch optimizer rebuilds segments requested to be reindexed,
+    /// even if their configuration matches
+    #[test]
+    fn test_reindex_requested() {
+        let (point_count, dim) = (1000, 10);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size_kb: usize::MAX,
+            memmap_threshold_kb: usize::MAX,
+            indexing_threshold_kb: 10,
+            indexing_min_vector_number: 0,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Single(
+                VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
+            ),
+            ..CollectionParams::empty()
+        };
+        let hnsw_config = HnswConfig {
+            m: 16,
+            ef_construct: 100,
+            full_scan_threshold: 10,
+            max_indexing_threads: 0,
+            on_disk: None,
+            payload_m: None,
+            use_heuristic: None,
+        };
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+        let segment_id = holder.add_new(random_segment(dir.path(), 100, point_count, dim));
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let index_optimizer = IndexingOptimizer::new(
+            2,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params.clone(),
+            hnsw_config.clone(),
+            Default::default(),
+        );
+        let config_mismatch_optimizer = ConfigMismatchOptimizer::new(
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            hnsw_config.clone(),
+            Default::default(),
+        );
+
+        let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+        index_optimizer
+            .optimize(
+                locked_holder.clone(),
+                vec![segment_id],
+                permit,
+                &false.into(),
+            )
+            .unwrap();
+
+        // Nothing to do, config matches
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        // Only the indexed segment is scheduled, not the empty appendable one
+        assert_eq!(locked_holder.write().request_reindex(), 1);
+        locked_holder.write().cancel_reindex();
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        assert_eq!(locked_holder.write().request_reindex(), 1);
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 1);
+        let reindexed_id = suggested_to_optimize[0];
+
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+        let changed = config_mismatch_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                permit,
+                &false.into(),
+            )
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+        assert!(locked_holder.read().get(reindexed_id).is_none());
+
+        // Rebuilt segment is not scheduled again and keeps all points
+        let suggested_to_optimize =
+            config_mismatch_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        let total_points: usize = locked_holder
+            .read()
+            .iter()
+            .map(|(_, segment)| segment.get().read().available_point_count())
+            .sum();
+        assert_eq!(total_points, point_count as usize);
+    }
+
     /// This test the config mismatch optimizer for a changed vector specific HNSW config
     ///
     /// Similar to `test_hnsw_config_mismatch` but for multi vector segment with a vector specific
@@ -433,6 +563,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            indexing_min_vector_number: 0,
         };
         let hnsw_config_vector1 = HnswConfigDiff {
             m: Some(10),
@@ -479,6 +610,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            use_heuristic: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config_collection.max_indexing_threads);
@@ -601,6 +733,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            indexing_min_vector_number: 0,
         };
         let quantization_config_vector1 =
             QuantizationConfig::Scalar(segment::types::ScalarQuantization {
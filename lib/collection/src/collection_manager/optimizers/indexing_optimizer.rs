@@ -12,6 +12,7 @@ use crate::collection_manager::holders::segment_holder::{
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::config::CollectionParams;
 
 const BYTES_IN_KB: usize = 1024;
@@ -28,6 +29,7 @@ pub struct IndexingOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    segment_volumes: SegmentVolumes,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -49,10 +51,17 @@ impl IndexingOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            segment_volumes: SegmentVolumes::default(),
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Spread optimized segments across the given storage volumes.
+    pub fn with_segment_volumes(mut self, segment_volumes: SegmentVolumes) -> Self {
+        self.segment_volumes = segment_volumes;
+        self
+    }
+
     fn smallest_indexed_segment(
         segments: &SegmentHolder,
         excluded_ids: &HashSet<SegmentId>,
@@ -117,6 +126,8 @@ impl IndexingOptimizer {
                     .thresholds_config
                     .memmap_threshold_kb
                     .saturating_mul(BYTES_IN_KB);
+                let is_enough_vectors_for_index = read_segment.available_point_count()
+                    >= self.thresholds_config.indexing_min_vector_number;
                 let mut require_optimization = false;
 
                 for (vector_name, vector_config) in self.collection_params.vectors.params_iter() {
@@ -130,7 +141,8 @@ impl IndexingOptimizer {
                         let is_big_for_index = storage_size_bytes >= indexing_threshold_bytes;
                         let is_big_for_mmap = storage_size_bytes >= mmap_threshold_bytes;
 
-                        let optimize_for_index = is_big_for_index && !is_indexed;
+                        let optimize_for_index =
+                            is_big_for_index && is_enough_vectors_for_index && !is_indexed;
                         let optimize_for_mmap = if let Some(on_disk_config) = vector_config.on_disk
                         {
                             on_disk_config && !is_on_disk
@@ -259,6 +271,10 @@ impl SegmentOptimizer for IndexingOptimizer {
         self.quantization_config.clone()
     }
 
+    fn segment_volumes(&self) -> &SegmentVolumes {
+        &self.segment_volumes
+    }
+
     fn threshold_config(&self) -> &OptimizerThresholds {
         &self.thresholds_config
     }
@@ -352,6 +368,7 @@ mod tests {
                 max_segment_size_kb: 300,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 1000,
+                indexing_min_vector_number: 0,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -453,6 +470,7 @@ mod tests {
                 max_segment_size_kb: 300,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 1000,
+                indexing_min_vector_number: 0,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -746,6 +764,7 @@ mod tests {
                 max_segment_size_kb: 1000,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 10, // Always optimize
+                indexing_min_vector_number: 0,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -823,6 +842,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: 10,
             indexing_threshold_kb: usize::MAX,
+            indexing_min_vector_number: 0,
         };
         let mut collection_params = CollectionParams {
             vectors: VectorsConfig::Single(
@@ -850,6 +870,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            use_heuristic: None,
         };
 
         {
@@ -977,4 +998,118 @@ mod tests {
                 );
             });
     }
+
+    /// Test that segments with fewer vectors than the minimum are never indexed, even if they
+    /// exceed the indexing and memmap thresholds
+    #[test]
+    fn test_indexing_min_vector_number() {
+        init();
+
+        const TINY_SEGMENT_VECTORS: u64 = 50;
+        const LARGE_SEGMENT_VECTORS: u64 = 1_000;
+
+        let mut holder = SegmentHolder::default();
+
+        let stopped = AtomicBool::new(false);
+        let dim = 8;
+
+        let segments_dir = Builder::new().prefix("segments_dir").tempdir().unwrap();
+        let segments_temp_dir = Builder::new()
+            .prefix("segments_temp_dir")
+            .tempdir()
+            .unwrap();
+        let mut opnum = 101..1000000;
+
+        let tiny_segment = random_segment(
+            segments_dir.path(),
+            opnum.next().unwrap(),
+            TINY_SEGMENT_VECTORS,
+            dim,
+        );
+        let large_segment = random_segment(
+            segments_dir.path(),
+            opnum.next().unwrap(),
+            LARGE_SEGMENT_VECTORS,
+            dim,
+        );
+        let segment_config = tiny_segment.segment_config.clone();
+
+        holder.add_new(tiny_segment);
+        holder.add_new(large_segment);
+
+        let locked_holder: Arc<RwLock<_, _>> = Arc::new(RwLock::new(holder));
+
+        let index_optimizer = IndexingOptimizer::new(
+            10, // Optimize segments one by one
+            OptimizerThresholds {
+                max_segment_size_kb: 1000,
+                memmap_threshold_kb: 1, // Both segments are big enough for mmap
+                indexing_threshold_kb: 1, // Both segments are big enough for index
+                indexing_min_vector_number: 500,
+            },
+            segments_dir.path().to_owned(),
+            segments_temp_dir.path().to_owned(),
+            CollectionParams {
+                vectors: VectorsConfig::Single(
+                    VectorParamsBuilder::new(
+                        segment_config.vector_data[DEFAULT_VECTOR_NAME].size as u64,
+                        segment_config.vector_data[DEFAULT_VECTOR_NAME].distance,
+                    )
+                    .build(),
+                ),
+                ..CollectionParams::empty()
+            },
+            Default::default(),
+            Default::default(),
+        );
+
+        let permit_cpu_count = num_rayon_threads(0);
+
+        let mut number_of_optimizations = 0;
+        loop {
+            let suggested_to_optimize =
+                index_optimizer.check_condition(locked_holder.clone(), &Default::default());
+            if suggested_to_optimize.is_empty() {
+                break;
+            }
+
+            let permit = CpuPermit::dummy(permit_cpu_count as u32);
+            index_optimizer
+                .optimize(
+                    locked_holder.clone(),
+                    suggested_to_optimize,
+                    permit,
+                    &stopped,
+                )
+                .unwrap();
+            number_of_optimizations += 1;
+            assert!(number_of_optimizations <= 2, "optimization must not loop");
+        }
+
+        let holder = locked_holder.read();
+        let mut checked_segments = 0;
+        for (_, segment) in holder.iter() {
+            let segment = segment.get();
+            let segment = segment.read();
+            let vector_data = &segment.config().vector_data[DEFAULT_VECTOR_NAME];
+            match segment.available_point_count() as u64 {
+                TINY_SEGMENT_VECTORS => {
+                    assert!(
+                        !vector_data.index.is_indexed(),
+                        "tiny segment must stay plain"
+                    );
+                    assert!(vector_data.storage_type.is_on_disk());
+                }
+                LARGE_SEGMENT_VECTORS => {
+                    assert!(
+                        vector_data.index.is_indexed(),
+                        "large segment must be indexed"
+                    );
+                }
+                _ => continue,
+            }
+            checked_segments += 1;
+        }
+        assert_eq!(checked_segments, 2);
+    }
 }
@@ -13,6 +13,7 @@ use crate::collection_manager::holders::segment_holder::{
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::config::CollectionParams;
 
 const BYTES_IN_KB: usize = 1024;
@@ -29,6 +30,7 @@ pub struct MergeOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    segment_volumes: SegmentVolumes,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -51,9 +53,16 @@ impl MergeOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            segment_volumes: SegmentVolumes::default(),
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
+
+    /// Spread optimized segments across the given storage volumes.
+    pub fn with_segment_volumes(mut self, segment_volumes: SegmentVolumes) -> Self {
+        self.segment_volumes = segment_volumes;
+        self
+    }
 }
 
 impl SegmentOptimizer for MergeOptimizer {
@@ -81,6 +90,10 @@ impl SegmentOptimizer for MergeOptimizer {
         self.quantization_config.clone()
     }
 
+    fn segment_volumes(&self) -> &SegmentVolumes {
+        &self.segment_volumes
+    }
+
     fn threshold_config(&self) -> &OptimizerThresholds {
         &self.thresholds_config
     }
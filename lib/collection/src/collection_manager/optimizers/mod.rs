@@ -7,6 +7,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::holders::segment_holder::SegmentId;
+use crate::operations::types::{CollectionStatus, OptimizerError};
 
 pub mod config_mismatch_optimizer;
 pub mod indexing_optimizer;
@@ -19,6 +20,15 @@ pub mod vacuum_optimizer;
 /// Will never remove older trackers for failed or still ongoing optimizations.
 const KEEP_LAST_TRACKERS: usize = 16;
 
+/// Number of last optimization outcomes used to determine the failure rate
+const OUTCOMES_WINDOW: usize = 20;
+
+/// Failure rate of optimizations, at which the collection is reported as degraded
+const DEGRADED_FAILURE_RATE: f64 = 0.2;
+
+/// Failure rate of optimizations, at which the collection is reported as failing
+const FAILING_FAILURE_RATE: f64 = 0.5;
+
 /// A log of optimizer trackers holding their status
 #[derive(Default, Clone, Debug)]
 pub struct TrackerLog {
@@ -68,6 +78,99 @@ impl TrackerLog {
     }
 }
 
+/// Rolling window of the outcomes of the last optimizations
+///
+/// A single failed optimization may be caused by a transient problem, which goes away on retry.
+/// The rate of failures shows whether optimizations keep failing.
+#[derive(Default, Clone, Debug)]
+pub struct OptimizerOutcomes {
+    /// `true` for every successful optimization, `false` for every failed one
+    outcomes: VecDeque<bool>,
+    /// Error of the latest failed optimization
+    last_error: Option<OptimizerError>,
+    /// Number of failed optimizations, which are still being rolled back
+    rollbacks: usize,
+    /// Error of the latest failed optimization, which is being rolled back
+    rollback_error: Option<OptimizerError>,
+}
+
+impl OptimizerOutcomes {
+    pub fn record_success(&mut self) {
+        self.push(true);
+    }
+
+    pub fn record_failure(&mut self, error: OptimizerError) {
+        self.push(false);
+        self.last_error = Some(error);
+    }
+
+    /// Mark a failed optimization as being rolled back, until [`Self::end_rollback`] is called
+    pub fn begin_rollback(&mut self, error: OptimizerError) {
+        self.rollbacks += 1;
+        self.rollback_error = Some(error);
+    }
+
+    pub fn end_rollback(&mut self) {
+        self.rollbacks = self.rollbacks.saturating_sub(1);
+        if self.rollbacks == 0 {
+            self.rollback_error = None;
+        }
+    }
+
+    fn push(&mut self, success: bool) {
+        if self.outcomes.len() >= OUTCOMES_WINDOW {
+            self.outcomes.pop_front();
+        }
+        self.outcomes.push_back(success);
+    }
+
+    fn failures(&self) -> usize {
+        self.outcomes.iter().filter(|success| !**success).count()
+    }
+
+    /// Share of failed optimizations in the window, `None` if there were no optimizations yet
+    pub fn failure_rate(&self) -> Option<f64> {
+        if self.outcomes.is_empty() {
+            return None;
+        }
+        Some(self.failures() as f64 / self.outcomes.len() as f64)
+    }
+
+    /// Degraded collection status with a describing error, if optimizations fail too often
+    ///
+    /// The collection is failing while a failed optimization is being rolled back.
+    pub fn health(&self) -> Option<(CollectionStatus, OptimizerError)> {
+        if let Some(rollback_error) = &self.rollback_error {
+            let error = OptimizerError {
+                error: format!("rolling back failed optimization: {}", rollback_error.error),
+                ..rollback_error.clone()
+            };
+            return Some((CollectionStatus::Red, error));
+        }
+
+        let failure_rate = self.failure_rate()?;
+        let status = if failure_rate >= FAILING_FAILURE_RATE {
+            CollectionStatus::Red
+        } else if failure_rate >= DEGRADED_FAILURE_RATE {
+            CollectionStatus::Yellow
+        } else {
+            return None;
+        };
+
+        let last_error = self.last_error.as_ref()?;
+        let error = OptimizerError {
+            error: format!(
+                "{} of the last {} optimizations failed, last error: {}",
+                self.failures(),
+                self.outcomes.len(),
+                last_error.error,
+            ),
+            ..last_error.clone()
+        };
+        Some((status, error))
+    }
+}
+
 /// Tracks the state of an optimizer
 #[derive(Clone, Debug)]
 pub struct Tracker {
@@ -27,9 +27,10 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentId,
 };
+use crate::common::segment_volumes::{self, SegmentVolumes};
 use crate::config::CollectionParams;
 use crate::operations::config_diff::DiffConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, OptimizerError};
 
 const BYTES_IN_KB: usize = 1024;
 
@@ -38,6 +39,8 @@ pub struct OptimizerThresholds {
     pub max_segment_size_kb: usize,
     pub memmap_threshold_kb: usize,
     pub indexing_threshold_kb: usize,
+    /// Segments with fewer vectors are never indexed
+    pub indexing_min_vector_number: usize,
 }
 
 /// SegmentOptimizer - trait implementing common functionality of the optimizers
@@ -70,6 +73,9 @@ pub trait SegmentOptimizer {
     /// Get thresholds configuration for the current optimizer
     fn threshold_config(&self) -> &OptimizerThresholds;
 
+    /// Get storage volumes, across which optimized segments are spread
+    fn segment_volumes(&self) -> &SegmentVolumes;
+
     /// Checks if segment optimization is required
     fn check_condition(
         &self,
@@ -79,6 +85,13 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator>;
 
+    /// Whether this optimizer rebuilds segments requested to be reindexed
+    ///
+    /// Its optimizations of such segments are stopped if the reindex is cancelled.
+    fn rebuilds_reindex_requested(&self) -> bool {
+        false
+    }
+
     /// Build temp segment
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
@@ -119,6 +132,7 @@ pub trait SegmentOptimizer {
         //     image_vectors: 10100 * dim * VECTOR_ELEMENT_SIZE
         // }
         let mut bytes_count_by_vector_name = HashMap::new();
+        let mut points_count = 0;
 
         // Counting up how much space do the segments being optimized actually take on the fs.
         // If there was at least one error while reading the size, this will be `None`.
@@ -140,6 +154,7 @@ pub trait SegmentOptimizer {
                 let size = bytes_count_by_vector_name.entry(vector_name).or_insert(0);
                 *size += vector_size;
             }
+            points_count += locked_segment.available_point_count();
 
             space_occupied =
                 space_occupied.and_then(|acc| match dir_size(locked_segment.data_path()) {
@@ -216,8 +231,11 @@ pub trait SegmentOptimizer {
         let mut vector_data = collection_params.to_base_vector_data()?;
         let mut sparse_vector_data = collection_params.to_sparse_vector_data()?;
 
+        // Exact search is faster than HNSW on small segments, regardless of their size in bytes
+        let is_enough_vectors_for_index = points_count >= thresholds.indexing_min_vector_number;
+
         // If indexing, change to HNSW index and quantization
-        if threshold_is_indexed {
+        if threshold_is_indexed && is_enough_vectors_for_index {
             let collection_hnsw = self.hnsw_config();
             let collection_quantization = self.quantization_config();
             vector_data.iter_mut().for_each(|(vector_name, config)| {
@@ -303,9 +321,24 @@ pub trait SegmentOptimizer {
             },
         };
 
+        // Build segment directly on the volume it is placed on, to not copy it between disks
+        let (segments_path, temp_path) = match self
+            .segment_volumes()
+            .select_volume(self.segments_path(), fs4::available_space)
+        {
+            Some(volume) => (
+                SegmentVolumes::volume_segments_path(volume),
+                SegmentVolumes::volume_temp_path(volume),
+            ),
+            None => (
+                self.segments_path().to_path_buf(),
+                self.temp_path().to_path_buf(),
+            ),
+        };
+
         Ok(SegmentBuilder::new(
-            self.segments_path(),
-            self.temp_path(),
+            &segments_path,
+            &temp_path,
             &optimized_config,
         )?)
     }
@@ -359,30 +392,60 @@ pub trait SegmentOptimizer {
         Ok(())
     }
 
-    /// Unwraps proxy, adds temp segment into collection and returns a `Cancelled` error.
+    /// Unwraps proxy, adds temp segment into collection and returns the error of the optimization.
     ///
     /// # Arguments
     ///
     /// * `segments` - all registered segments of the collection
+    /// * `ids` - IDs of the segments under optimization
     /// * `proxy_ids` - currently used proxies
     /// * `temp_segment` - currently used temporary segment
+    /// * `error` - error, the optimization has failed or was cancelled with
     ///
     /// # Result
     ///
     /// Rolls back optimization state.
     /// All processed changes will still be there, but the collection should be returned into state
-    /// before optimization.
-    fn handle_cancellation(
+    /// before optimization. The temp segment is dropped if it didn't receive any points.
+    ///
+    /// Unless the optimization was cancelled, the collection is reported as failing until the
+    /// rollback is finished.
+    fn handle_failure(
         &self,
         segments: &LockedSegmentHolder,
+        ids: &[SegmentId],
         proxy_ids: &[SegmentId],
-        temp_segment: &LockedSegment,
-    ) {
+        temp_segment: LockedSegment,
+        error: CollectionError,
+    ) -> CollectionError {
+        let is_cancelled = matches!(error, CollectionError::Cancelled { .. });
+        if !is_cancelled {
+            segments.write().optimizer_outcomes.begin_rollback(
+                OptimizerError::from(error.clone()).with_optimizer(self.name(), ids.to_vec()),
+            );
+        }
+
         self.unwrap_proxy(segments, proxy_ids);
-        if temp_segment.get().read().available_point_count() > 0 {
-            let mut write_segments = segments.write();
-            write_segments.add_new_locked(temp_segment.clone());
+        let rollback_result = if temp_segment.get().read().available_point_count() > 0 {
+            segments.write().add_new_locked(temp_segment);
+            Ok(())
+        } else {
+            // Proxies are unwrapped, so nothing refers to the temp segment anymore
+            temp_segment.drop_data()
+        };
+
+        let mut write_segments = segments.write();
+        if !is_cancelled {
+            write_segments.optimizer_outcomes.end_rollback();
+        }
+        if let Err(rollback_error) = rollback_result {
+            log::error!(
+                "Failed to drop temporary segment of failed optimization: {rollback_error}"
+            );
+            write_segments.report_optimizer_error(rollback_error);
         }
+
+        error
     }
 
     /// Function to wrap slow part of optimization. Performs proxy rollback in case of cancellation.
@@ -457,6 +520,7 @@ pub trait SegmentOptimizer {
         }
 
         let mut optimized_segment: Segment = segment_builder.build(permit, stopped)?;
+        segment_volumes::link_segment(self.segments_path(), &optimized_segment.data_path())?;
 
         // Delete points in 2 steps
         // First step - delete all points with read lock
@@ -557,50 +621,78 @@ pub trait SegmentOptimizer {
             PayloadFieldSchema,
         >::new()));
 
-        let mut proxies = Vec::new();
-        for sg in optimizing_segments.iter() {
-            let mut proxy = ProxySegment::new(
-                sg.clone(),
-                tmp_segment.clone(),
-                proxy_deleted_points.clone(),
-                proxy_created_indexes.clone(),
-                proxy_deleted_indexes.clone(),
-            );
-            // Wrapped segment is fresh, so it has no operations
-            // Operation with number 0 will be applied
-            proxy.replicate_field_indexes(0)?;
-            proxies.push(proxy);
-        }
+        let prepare_proxies = || -> CollectionResult<Vec<ProxySegment>> {
+            let mut proxies = Vec::new();
+            for sg in optimizing_segments.iter() {
+                let mut proxy = ProxySegment::new(
+                    sg.clone(),
+                    tmp_segment.clone(),
+                    proxy_deleted_points.clone(),
+                    proxy_created_indexes.clone(),
+                    proxy_deleted_indexes.clone(),
+                );
+                // Wrapped segment is fresh, so it has no operations
+                // Operation with number 0 will be applied
+                proxy.replicate_field_indexes(0)?;
+                proxies.push(proxy);
+            }
 
-        // Save segment version once all payload indices have been converted
-        // If this ends up not being saved due to a crash, the segment will not be used
-        match &tmp_segment {
-            LockedSegment::Original(segment) => {
-                let segment_path = &segment.read().current_path;
-                SegmentVersion::save(segment_path)?;
+            // Save segment version once all payload indices have been converted
+            // If this ends up not being saved due to a crash, the segment will not be used
+            match &tmp_segment {
+                LockedSegment::Original(segment) => {
+                    let segment_path = &segment.read().current_path;
+                    SegmentVersion::save(segment_path)?;
+                }
+                LockedSegment::Proxy(_) => unreachable!(),
             }
-            LockedSegment::Proxy(_) => unreachable!(),
-        }
 
-        let proxy_ids: Vec<_> = {
+            Ok(proxies)
+        };
+
+        let proxies = match prepare_proxies() {
+            Ok(proxies) => proxies,
+            Err(error) => {
+                // Nothing is swapped yet, only the temp segment has to be removed
+                drop(segments_lock);
+                return Err(self.handle_failure(&segments, &ids, &[], tmp_segment, error));
+            }
+        };
+
+        let (proxy_ids, swap_result) = {
             // Exclusive lock for the segments operations.
             let mut write_segments = RwLockUpgradableReadGuard::upgrade(segments_lock);
             let mut proxy_ids = Vec::new();
+            let mut swap_result = Ok(());
             for (mut proxy, idx) in proxies.into_iter().zip(ids.iter().cloned()) {
                 // replicate_field_indexes for the second time,
                 // because optimized segments could have been changed.
                 // The probability is small, though,
                 // so we can afford this operation under the full collection write lock
                 let op_num = 0;
-                proxy.replicate_field_indexes(op_num)?; // Slow only in case the index is change in the gap between two calls
+                // Slow only in case the index is change in the gap between two calls
+                if let Err(error) = proxy.replicate_field_indexes(op_num) {
+                    swap_result = Err(error);
+                    break;
+                }
                 proxy_ids.push(write_segments.swap_new(proxy, &[idx]).0);
             }
-            proxy_ids
+            (proxy_ids, swap_result)
         };
 
-        check_process_stopped(stopped).inspect_err(|_| {
-            self.handle_cancellation(&segments, &proxy_ids, &tmp_segment);
-        })?;
+        if let Err(error) = swap_result.map_err(CollectionError::from) {
+            return Err(self.handle_failure(&segments, &ids, &proxy_ids, tmp_segment, error));
+        }
+
+        if let Err(error) = check_process_stopped(stopped) {
+            return Err(self.handle_failure(
+                &segments,
+                &ids,
+                &proxy_ids,
+                tmp_segment,
+                error.into(),
+            ));
+        }
 
         // ---- SLOW PART -----
 
@@ -614,10 +706,7 @@ pub trait SegmentOptimizer {
         ) {
             Ok(segment) => segment,
             Err(error) => {
-                if matches!(error, CollectionError::Cancelled { .. }) {
-                    self.handle_cancellation(&segments, &proxy_ids, &tmp_segment);
-                }
-                return Err(error);
+                return Err(self.handle_failure(&segments, &ids, &proxy_ids, tmp_segment, error));
             }
         };
 
@@ -635,33 +724,47 @@ pub trait SegmentOptimizer {
 
         // ---- SLOW PART ENDS HERE -----
 
-        check_process_stopped(stopped).map_err(|error| {
-            self.handle_cancellation(&segments, &proxy_ids, &tmp_segment);
-            error
-        })?;
+        if let Err(error) = check_process_stopped(stopped) {
+            drop_optimized_segment(optimized_segment);
+            return Err(self.handle_failure(
+                &segments,
+                &ids,
+                &proxy_ids,
+                tmp_segment,
+                error.into(),
+            ));
+        }
 
         {
             // This block locks all operations with collection. It should be fast
             let mut write_segments_guard = segments.write();
-            let deleted_points = proxy_deleted_points.read();
-            let points_diff = deleted_points.difference(&already_remove_points);
-            for &point_id in points_diff {
-                optimized_segment
-                    .delete_point(optimized_segment.version(), point_id)
-                    .unwrap();
-            }
 
-            for deleted_field_name in proxy_deleted_indexes.read().iter() {
-                optimized_segment
-                    .delete_field_index(optimized_segment.version(), deleted_field_name)?;
-            }
+            let apply_proxy_changes = |optimized_segment: &mut Segment| -> CollectionResult<()> {
+                let deleted_points = proxy_deleted_points.read();
+                let points_diff = deleted_points.difference(&already_remove_points);
+                for &point_id in points_diff {
+                    optimized_segment.delete_point(optimized_segment.version(), point_id)?;
+                }
+
+                for deleted_field_name in proxy_deleted_indexes.read().iter() {
+                    optimized_segment
+                        .delete_field_index(optimized_segment.version(), deleted_field_name)?;
+                }
+
+                for (created_field_name, schema_type) in proxy_created_indexes.read().iter() {
+                    optimized_segment.create_field_index(
+                        optimized_segment.version(),
+                        created_field_name,
+                        Some(schema_type),
+                    )?;
+                }
+                Ok(())
+            };
 
-            for (created_field_name, schema_type) in proxy_created_indexes.read().iter() {
-                optimized_segment.create_field_index(
-                    optimized_segment.version(),
-                    created_field_name,
-                    Some(schema_type),
-                )?;
+            if let Err(error) = apply_proxy_changes(&mut optimized_segment) {
+                drop(write_segments_guard);
+                drop_optimized_segment(optimized_segment);
+                return Err(self.handle_failure(&segments, &ids, &proxy_ids, tmp_segment, error));
             }
 
             optimized_segment.prefault_mmap_pages();
@@ -702,9 +805,21 @@ pub trait SegmentOptimizer {
                 }
                 tmp_segment.drop_data()?;
             }
+
+            // Optimized segments may have been placed on other volumes
+            if !self.segment_volumes().is_empty() {
+                segment_volumes::remove_dangling_links(self.segments_path())?;
+            }
         }
 
         timer.set_success(true);
         Ok(true)
     }
 }
+
+/// Removes the data of an optimized segment, which is not going to be used
+fn drop_optimized_segment(optimized_segment: Segment) {
+    if let Err(err) = optimized_segment.drop_data() {
+        log::error!("Failed to remove optimized segment of failed optimization: {err}");
+    }
+}
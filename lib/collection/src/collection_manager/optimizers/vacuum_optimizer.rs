@@ -16,6 +16,7 @@ use crate::collection_manager::holders::segment_holder::{
 use crate::collection_manager::optimizers::segment_optimizer::{
     OptimizerThresholds, SegmentOptimizer,
 };
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::config::CollectionParams;
 
 /// Optimizer which looks for segments with high amount of soft-deleted points or vectors
@@ -33,6 +34,7 @@ pub struct VacuumOptimizer {
     collection_params: CollectionParams,
     hnsw_config: HnswConfig,
     quantization_config: Option<QuantizationConfig>,
+    segment_volumes: SegmentVolumes,
     telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
@@ -57,10 +59,17 @@ impl VacuumOptimizer {
             collection_params,
             hnsw_config,
             quantization_config,
+            segment_volumes: SegmentVolumes::default(),
             telemetry_durations_aggregator: OperationDurationsAggregator::new(),
         }
     }
 
+    /// Spread optimized segments across the given storage volumes.
+    pub fn with_segment_volumes(mut self, segment_volumes: SegmentVolumes) -> Self {
+        self.segment_volumes = segment_volumes;
+        self
+    }
+
     fn worst_segment(
         &self,
         segments: LockedSegmentHolder,
@@ -187,6 +196,10 @@ impl SegmentOptimizer for VacuumOptimizer {
         self.quantization_config.clone()
     }
 
+    fn segment_volumes(&self) -> &SegmentVolumes {
+        &self.segment_volumes
+    }
+
     fn threshold_config(&self) -> &OptimizerThresholds {
         &self.thresholds_config
     }
@@ -296,6 +309,7 @@ mod tests {
                 max_segment_size_kb: 1000000,
                 memmap_threshold_kb: 1000000,
                 indexing_threshold_kb: 1000000,
+                indexing_min_vector_number: 0,
             },
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
@@ -387,6 +401,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            indexing_min_vector_number: 0,
         };
         let collection_params = CollectionParams {
             vectors: VectorsConfig::Multi(BTreeMap::from([
@@ -433,6 +448,7 @@ mod tests {
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            use_heuristic: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -615,4 +631,89 @@ mod tests {
                 });
             });
     }
+
+    /// Vacuum must only trigger if both the deleted ratio and the minimal vector count
+    /// thresholds are reached, and must drop all soft-deleted points when it does.
+    #[test]
+    fn test_vacuum_thresholds() {
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+        let segment_id = holder.add_new(random_segment(dir.path(), 100, 200, 4));
+
+        // Delete 30% of points
+        {
+            let segment = holder.get(segment_id).unwrap();
+            let points_to_delete = segment
+                .get()
+                .read()
+                .iter_points()
+                .enumerate()
+                .filter_map(|(i, point_id)| (i % 10 < 3).then_some(point_id))
+                .collect_vec();
+            for point_id in points_to_delete {
+                segment.get().write().delete_point(101, point_id).unwrap();
+            }
+        }
+
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let vacuum_optimizer = |deleted_threshold, min_vectors_number| {
+            VacuumOptimizer::new(
+                deleted_threshold,
+                min_vectors_number,
+                OptimizerThresholds {
+                    max_segment_size_kb: 1000000,
+                    memmap_threshold_kb: 1000000,
+                    indexing_threshold_kb: 1000000,
+                    indexing_min_vector_number: 0,
+                },
+                dir.path().to_owned(),
+                temp_dir.path().to_owned(),
+                CollectionParams {
+                    vectors: VectorsConfig::Single(
+                        VectorParamsBuilder::new(4, Distance::Dot).build(),
+                    ),
+                    ..CollectionParams::empty()
+                },
+                Default::default(),
+                Default::default(),
+            )
+        };
+
+        // Deleted ratio is below the threshold
+        let suggested_to_optimize =
+            vacuum_optimizer(0.5, 50).check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        // Segment is too small
+        let suggested_to_optimize =
+            vacuum_optimizer(0.2, 500).check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+
+        // Both thresholds are reached
+        let optimizer = vacuum_optimizer(0.2, 50);
+        let suggested_to_optimize =
+            optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize, vec![segment_id]);
+
+        let permit = CpuPermit::dummy(num_rayon_threads(0) as u32);
+        let changed = optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                permit,
+                &false.into(),
+            )
+            .unwrap();
+        assert!(changed, "optimizer should have rebuilt this segment");
+
+        let holder_guard = locked_holder.read();
+        assert_eq!(holder_guard.len(), 1);
+        let (_, optimized_segment) = holder_guard.iter().next().unwrap();
+        let segment_arc = optimized_segment.get();
+        let segment_guard = segment_arc.read();
+        assert_eq!(segment_guard.deleted_point_count(), 0);
+        assert_eq!(segment_guard.available_point_count(), 140);
+    }
 }
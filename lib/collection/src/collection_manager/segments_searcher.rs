@@ -18,7 +18,7 @@ use tinyvec::TinyVec;
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
 
-use super::holders::segment_holder::LockedSegmentHolder;
+use super::holders::segment_holder::{LockedSegmentHolder, SegmentHolder};
 use crate::collection_manager::holders::segment_holder::LockedSegment;
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
@@ -381,6 +381,10 @@ impl SegmentsSearcher {
         let mut point_version: HashMap<PointIdType, SeqNumberType> = Default::default();
         let mut point_records: HashMap<PointIdType, Record> = Default::default();
 
+        if with_vector.is_enabled() {
+            Self::prefetch_vectors(&segments.read(), points, with_vector);
+        }
+
         segments
             .read()
             .read_points(points, is_stopped, |id, segment| {
@@ -420,6 +424,7 @@ impl SegmentsSearcher {
                             },
                             shard_key: None,
                             order_value: None,
+                            version: Some(version),
                         },
                     );
                     point_version.insert(id, version);
@@ -430,6 +435,41 @@ impl SegmentsSearcher {
         Ok(point_records)
     }
 
+    /// Let on-disk vector storages read ahead the vectors about to be retrieved.
+    ///
+    /// Points are usually retrieved in id order, e.g. when scrolling, which would otherwise
+    /// result in a random read per vector. Returns the number of ranges read ahead.
+    fn prefetch_vectors(
+        segments: &SegmentHolder,
+        points: &[PointIdType],
+        with_vector: &WithVector,
+    ) -> usize {
+        let mut num_ranges = 0;
+        for (_, segment) in segments.iter() {
+            let segment = segment.get();
+            let segment = segment.read();
+            match with_vector {
+                WithVector::Bool(true) => {
+                    let config = segment.config();
+                    let vector_names = config
+                        .vector_data
+                        .keys()
+                        .chain(config.sparse_vector_data.keys());
+                    for vector_name in vector_names {
+                        num_ranges += segment.prefetch_vectors(vector_name, points);
+                    }
+                }
+                WithVector::Bool(false) => {}
+                WithVector::Selector(vector_names) => {
+                    for vector_name in vector_names {
+                        num_ranges += segment.prefetch_vectors(vector_name, points);
+                    }
+                }
+            }
+        }
+        num_ranges
+    }
+
     pub async fn read_filtered(
         segments: LockedSegmentHolder,
         filter: Option<&Filter>,
@@ -485,6 +525,7 @@ struct BatchSearchParams<'a> {
     pub with_vector: WithVector,
     pub top: usize,
     pub params: Option<&'a SearchParams>,
+    pub score_threshold: Option<ScoreType>,
 }
 
 /// Returns suggested search sampling size for a given number of points and required limit.
@@ -549,14 +590,23 @@ fn search_in_segment(
             .as_ref()
             .unwrap_or(&WithPayloadInterface::Bool(false));
 
+        let search_type: SearchType = search_query.query.as_ref().into();
+
+        // Scores of custom queries are not post-processed, so threshold is applied to nearest
+        // search only. Others are still filtered by the shard.
+        let score_threshold = search_query
+            .score_threshold
+            .filter(|_| search_type == SearchType::Nearest);
+
         let params = BatchSearchParams {
-            search_type: search_query.query.as_ref().into(),
+            search_type,
             vector_name: search_query.query.get_vector_name(),
             filter: search_query.filter.as_ref(),
             with_payload: WithPayload::from(with_payload_interface),
             with_vector: search_query.with_vector.clone().unwrap_or_default(),
             top: search_query.limit + search_query.offset,
             params: search_query.params.as_ref(),
+            score_threshold,
         };
 
         let query = search_query.query.clone().into();
@@ -630,7 +680,9 @@ fn execute_batch_search(
     };
 
     let vectors_batch = &vectors_batch.iter().collect_vec();
-    let segment_query_context = query_context.get_segment_query_context();
+    let segment_query_context = query_context
+        .get_segment_query_context()
+        .with_score_threshold(search_params.score_threshold);
     let res = read_segment.search_batch(
         search_params.vector_name,
         vectors_batch,
@@ -669,10 +721,16 @@ mod tests {
     use std::collections::HashSet;
 
     use api::rest::SearchRequestInternal;
+    use common::cpu::CpuPermit;
     use parking_lot::RwLock;
+    use segment::data_types::vectors::only_default_vector;
+    use segment::entry::entry_point::SegmentEntry;
     use segment::fixtures::index_fixtures::random_vector;
     use segment::index::VectorIndexEnum;
-    use segment::types::{Condition, HasIdCondition};
+    use segment::segment_constructor::segment_builder::SegmentBuilder;
+    use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
+    use segment::types::{Condition, Distance, HasIdCondition, VectorStorageType};
+    use segment::vector_storage::VectorStorage;
     use tempfile::Builder;
 
     use super::*;
@@ -833,6 +891,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_search_in_segment_score_threshold() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let distance = Distance::Euclid;
+        let mut segment = build_simple_segment(dir.path(), 4, distance).unwrap();
+        let mut rnd = rand::thread_rng();
+        for idx in 0..1000u64 {
+            let vector = random_vector(&mut rnd, 4);
+            segment
+                .upsert_point(1, PointIdType::from(idx), only_default_vector(&vector))
+                .unwrap();
+        }
+        let segment = LockedSegment::from(segment);
+
+        let query = random_vector(&mut rnd, 4);
+        let search = |score_threshold| {
+            let request = CoreSearchRequestBatch {
+                searches: vec![CoreSearchRequest {
+                    query: query.clone().into(),
+                    with_payload: None,
+                    with_vector: None,
+                    filter: None,
+                    params: None,
+                    limit: 100,
+                    score_threshold,
+                    offset: 0,
+                }],
+            };
+            let (mut result, _) = search_in_segment(
+                segment.clone(),
+                Arc::new(request),
+                false,
+                Arc::new(QueryContext::default()),
+            )
+            .unwrap();
+            result.pop().unwrap()
+        };
+
+        let unthresholded = search(None);
+        assert_eq!(unthresholded.len(), 100);
+
+        // Distance to the 50th nearest point
+        let threshold = distance.postprocess_score(unthresholded[49].score);
+        let thresholded = search(Some(threshold));
+
+        let expected: Vec<_> = unthresholded
+            .iter()
+            .filter(|point| {
+                distance.check_threshold(distance.postprocess_score(point.score), threshold)
+            })
+            .map(|point| (point.id, point.score))
+            .collect();
+        let thresholded: Vec<_> = thresholded
+            .iter()
+            .map(|point| (point.id, point.score))
+            .collect();
+
+        assert_eq!(thresholded, expected);
+        assert!(thresholded.len() < unthresholded.len());
+    }
+
     #[test]
     fn test_retrieve() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -848,6 +968,69 @@ mod tests {
         assert_eq!(records.len(), 3);
     }
 
+    #[tokio::test]
+    async fn test_scroll_mmap_vectors() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let source = random_segment(dir.path(), 10, 5_000, 64);
+
+        let mut config = source.config().clone();
+        for vector_data in config.vector_data.values_mut() {
+            vector_data.storage_type = VectorStorageType::Mmap;
+        }
+        let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+        builder.update(&[&source], &false.into()).unwrap();
+        let segment = builder.build(CpuPermit::dummy(1), &false.into()).unwrap();
+        assert!(segment.vector_data[""].vector_storage.borrow().is_on_disk());
+
+        let mut holder = SegmentHolder::default();
+        holder.add_new(segment);
+        let segment_holder = Arc::new(RwLock::new(holder));
+
+        let ids = SegmentsSearcher::read_filtered(segment_holder.clone(), None, &Handle::current())
+            .await
+            .unwrap();
+        assert_eq!(ids.len(), 5_000);
+
+        // Vectors of all points are stored contiguously, and read ahead at once
+        let ids: Vec<_> = ids.into_iter().collect();
+        let with_vector = WithVector::Bool(true);
+        assert_eq!(
+            SegmentsSearcher::prefetch_vectors(&segment_holder.read(), &ids, &with_vector),
+            1,
+        );
+        // Vectors in RAM are never read ahead
+        assert_eq!(source.prefetch_vectors("", &ids), 0);
+
+        // Scroll through all points in pages, as a full export does
+        for page in ids.chunks(256) {
+            // Vectors of a page are read ahead in merged ranges, not one by one
+            let num_ranges =
+                SegmentsSearcher::prefetch_vectors(&segment_holder.read(), page, &with_vector);
+            assert!(
+                (1..page.len()).contains(&num_ranges),
+                "{num_ranges} ranges read ahead for {} points",
+                page.len(),
+            );
+
+            let records = SegmentsSearcher::retrieve_blocking(
+                segment_holder.clone(),
+                page,
+                &WithPayload::from(false),
+                &true.into(),
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+            assert_eq!(records.len(), page.len());
+
+            for id in page {
+                let expected = VectorStructInternal::from(source.all_vectors(*id).unwrap());
+                assert_eq!(records[id].vector.as_ref(), Some(&expected));
+            }
+        }
+    }
+
     #[test]
     fn test_sampling_limit() {
         assert_eq!(sampling_limit(1000, None, 464530, 35103551), 1000);
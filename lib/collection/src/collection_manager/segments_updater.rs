@@ -17,7 +17,10 @@ use segment::types::{
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::point_ops::{
+    version_from_payload, ConditionalUpsert, PointInsertOperationsInternal, PointOperations,
+    PointStruct,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
@@ -40,6 +43,9 @@ pub(crate) fn delete_points(
     op_num: SeqNumberType,
     ids: &[PointIdType],
 ) -> CollectionResult<usize> {
+    // Also points which are not there yet, an older upsert may arrive after this deletion
+    segments.record_deleted_points(op_num, ids.iter().copied());
+
     let mut total_deleted_points = 0;
 
     for batch in ids.chunks(VECTOR_OP_BATCH_SIZE) {
@@ -149,11 +155,11 @@ pub(crate) fn overwrite_payload(
     let mut total_updated_points = 0;
 
     for batch in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
-        let updated_points = segments.apply_points_with_conditional_move(
+        let updated_points = segments.apply_payload_with_conditional_move(
             op_num,
             batch,
             |id, write_segment| write_segment.set_full_payload(op_num, id, payload),
-            |_, _, old_payload| {
+            |old_payload| {
                 *old_payload = payload.clone();
             },
             |segment| segment.get_indexed_fields().is_empty(),
@@ -186,11 +192,11 @@ pub(crate) fn set_payload(
     let mut total_updated_points = 0;
 
     for chunk in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
-        let updated_points = segments.apply_points_with_conditional_move(
+        let updated_points = segments.apply_payload_with_conditional_move(
             op_num,
             chunk,
             |id, write_segment| write_segment.set_payload(op_num, id, payload, key),
-            |_, _, old_payload| old_payload.merge(payload),
+            |old_payload| old_payload.merge(payload),
             |segment| {
                 segment.get_indexed_fields().keys().all(|indexed_path| {
                     !indexed_path.is_affected_by_value_set(&payload.0, key.as_ref())
@@ -240,7 +246,7 @@ pub(crate) fn delete_payload(
     let mut total_deleted_points = 0;
 
     for batch in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
-        let updated_points = segments.apply_points_with_conditional_move(
+        let updated_points = segments.apply_payload_with_conditional_move(
             op_num,
             batch,
             |id, write_segment| {
@@ -250,7 +256,7 @@ pub(crate) fn delete_payload(
                 }
                 Ok(res)
             },
-            |_, _, payload| {
+            |payload| {
                 for key in keys {
                     payload.remove(key);
                 }
@@ -289,11 +295,11 @@ pub(crate) fn clear_payload(
     let mut total_updated_points = 0;
 
     for batch in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
-        let updated_points = segments.apply_points_with_conditional_move(
+        let updated_points = segments.apply_payload_with_conditional_move(
             op_num,
             batch,
             |id, write_segment| write_segment.clear_payload(op_num, id),
-            |_, _, payload| payload.0.clear(),
+            |payload| payload.0.clear(),
             |segment| segment.get_indexed_fields().is_empty(),
         )?;
         check_unprocessed_points(batch, &updated_points)?;
@@ -314,11 +320,11 @@ pub(crate) fn clear_payload_by_filter(
     let mut total_updated_points = 0;
 
     for batch in points_to_clear.chunks(PAYLOAD_OP_BATCH_SIZE) {
-        let updated_points = segments.apply_points_with_conditional_move(
+        let updated_points = segments.apply_payload_with_conditional_move(
             op_num,
             batch,
             |id, write_segment| write_segment.clear_payload(op_num, id),
-            |_, _, payload| payload.0.clear(),
+            |payload| payload.0.clear(),
             |segment| segment.get_indexed_fields().is_empty(),
         )?;
         total_updated_points += updated_points.len();
@@ -462,6 +468,10 @@ pub(crate) fn sync_points(
 /// Checks point id in each segment, update point if found.
 /// All not found points are inserted into random segment.
 /// Returns: number of updated points.
+///
+/// For every point the operation with the highest sequence number wins, regardless of the order
+/// in which operations are applied. An upsert older than the current version of a point, or than
+/// a not yet flushed deletion of it, is skipped.
 pub(crate) fn upsert_points<'a, T>(
     segments: &SegmentHolder,
     op_num: SeqNumberType,
@@ -501,11 +511,12 @@ where
     )?;
 
     let mut res = updated_points.len();
-    // Insert new points, which was not updated or existed
+    // Insert new points, which was not updated or existed, nor deleted by a newer operation
     let new_point_ids = ids
         .iter()
         .cloned()
-        .filter(|x| !(updated_points.contains(x)));
+        .filter(|x| !(updated_points.contains(x)))
+        .filter(|x| !segments.is_deleted_since(*x, op_num));
 
     {
         let default_write_segment =
@@ -533,6 +544,112 @@ where
     Ok(res)
 }
 
+/// Inserts points which are not present in any segment yet.
+/// Points which already exist are left untouched.
+/// Returns: (number of inserted points, number of skipped points)
+pub(crate) fn insert_points_if_absent<'a, T>(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: T,
+) -> CollectionResult<(usize, usize)>
+where
+    T: IntoIterator<Item = &'a PointStruct>,
+{
+    let points_map: HashMap<PointIdType, &PointStruct> =
+        points.into_iter().map(|p| (p.id, p)).collect();
+
+    let (existing, absent): (Vec<_>, Vec<_>) = points_map.into_values().partition(|point| {
+        segments
+            .iter()
+            .any(|(_, segment)| segment.get().read().has_point(point.id))
+    });
+
+    if !existing.is_empty() {
+        log::debug!(
+            "Skipped {} existing points in insert-if-absent operation {op_num}",
+            existing.len(),
+        );
+    }
+
+    upsert_points(segments, op_num, absent.iter().copied())?;
+
+    Ok((absent.len(), existing.len()))
+}
+
+/// Checks that points of the upsert still have the versions expected by the client.
+/// If any point was changed in the meantime, a conflict is returned.
+///
+/// Versions are read from the payload of the points. Expected versions of points which are not
+/// part of the upsert are ignored.
+pub(crate) fn check_expected_versions(
+    segments: &SegmentHolder,
+    operation: &ConditionalUpsert,
+) -> CollectionResult<()> {
+    let point_ids: HashSet<PointIdType> = operation.points.point_ids().into_iter().collect();
+
+    for expected in &operation.expected_versions {
+        if !point_ids.contains(&expected.id) {
+            continue;
+        }
+
+        // Point may be present in multiple segments during optimization, the latest copy wins
+        let latest_segment = segments
+            .iter()
+            .filter_map(|(_, segment)| {
+                let version = segment.get().read().point_version(expected.id)?;
+                Some((version, segment))
+            })
+            .max_by_key(|(version, _)| *version);
+
+        let current_version = match latest_segment {
+            Some((_, segment)) => {
+                let payload = segment.get().read().payload(expected.id)?;
+                version_from_payload(&payload, &operation.version_key).unwrap_or(0)
+            }
+            None => 0,
+        };
+
+        if current_version != expected.version {
+            return Err(CollectionError::conflict(format!(
+                "Point {} was expected to have version {}, but has version {current_version}",
+                expected.id, expected.version,
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+pub(crate) fn points_from_insert_operation(
+    operation: PointInsertOperationsInternal,
+) -> Vec<PointStruct> {
+    match operation {
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
+            let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+            let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+            match batch.payloads {
+                None => vectors_iter
+                    .map(|(id, vectors)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload: None,
+                    })
+                    .collect(),
+                Some(payloads) => vectors_iter
+                    .zip(payloads)
+                    .map(|((id, vectors), payload)| PointStruct {
+                        id,
+                        vector: VectorStructInternal::from(vectors).into(),
+                        payload,
+                    })
+                    .collect(),
+            }
+        }
+        PointInsertOperationsInternal::PointsList(points) => points,
+    }
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
@@ -541,34 +658,30 @@ pub(crate) fn process_point_operation(
     match point_operation {
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = points_from_insert_operation(operation);
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
             Ok(res)
         }
+        PointOperations::InsertPointsIfAbsent(operation) => {
+            let points = points_from_insert_operation(operation);
+            let (inserted, _skipped) =
+                insert_points_if_absent(&segments.read(), op_num, points.iter())?;
+            Ok(inserted)
+        }
+        // Versions are checked by the leader replica, before the upsert is sent to replicas
+        PointOperations::UpsertPointsIfVersion(operation) => {
+            let points = points_from_insert_operation(operation.points);
+            upsert_points(&segments.read(), op_num, points.iter())
+        }
+        // Stored duplicates are removed by the leader replica, before the upsert is sent to replicas
+        PointOperations::UpsertPointsDedup(operation) => {
+            let points = points_from_insert_operation(operation.points);
+            upsert_points(&segments.read(), op_num, points.iter())
+        }
+        // Empty filter matches all points, e.g. when clearing a collection
+        PointOperations::DeletePointsByFilter(filter) if filter == Filter::default() => {
+            delete_all_points(segments, op_num)
+        }
         PointOperations::DeletePointsByFilter(filter) => {
             delete_points_by_filter(&segments.read(), op_num, &filter)
         }
@@ -674,6 +787,28 @@ pub(crate) fn process_field_index_operation(
 /// Max amount of points to delete in a batched deletion iteration.
 const DELETION_BATCH_SIZE: usize = 512;
 
+/// Deletes all points by replacing all segments with an empty one
+///
+/// Falls back to deleting points one by one, if segments can't be replaced right now. Either way,
+/// older upserts applied out of order afterwards don't bring the deleted points back.
+pub(crate) fn delete_all_points(
+    segments: &RwLock<SegmentHolder>,
+    op_num: SeqNumberType,
+) -> CollectionResult<usize> {
+    let removed_segments = segments.write().truncate(op_num)?;
+    let Some(removed_segments) = removed_segments else {
+        return delete_points_by_filter(&segments.read(), op_num, &Filter::default());
+    };
+
+    let mut total_deleted = 0;
+    for segment in removed_segments {
+        total_deleted += segment.get().read().available_point_count();
+        segment.drop_data()?;
+    }
+
+    Ok(total_deleted)
+}
+
 /// Deletes points from all segments matching the given filter
 pub(crate) fn delete_points_by_filter(
     segments: &SegmentHolder,
@@ -681,6 +816,8 @@ pub(crate) fn delete_points_by_filter(
     filter: &Filter,
 ) -> CollectionResult<usize> {
     let mut total_deleted = 0;
+    // Empty filter matches all points, don't evaluate it, e.g. when clearing a collection
+    let filter = (filter != &Filter::default()).then_some(filter);
     // we don’t want to cancel this filtered read
     let is_stopped = AtomicBool::new(false);
     let mut points_to_delete: HashMap<_, _> = segments
@@ -691,7 +828,7 @@ pub(crate) fn delete_points_by_filter(
                 segment
                     .get()
                     .read()
-                    .read_filtered(None, None, Some(filter), &is_stopped),
+                    .read_filtered(None, None, filter, &is_stopped),
             )
         })
         .collect();
@@ -706,6 +843,7 @@ pub(crate) fn delete_points_by_filter(
 
         let mut deleted_in_batch = 0;
         while let Some(point_id) = curr_points.pop() {
+            segments.record_deleted_points(op_num, [point_id]);
             if s.delete_point(op_num, point_id)? {
                 total_deleted += 1;
                 deleted_in_batch += 1;
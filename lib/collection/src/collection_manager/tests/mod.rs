@@ -7,9 +7,15 @@ use std::sync::Arc;
 
 use itertools::Itertools;
 use parking_lot::RwLock;
-use segment::data_types::vectors::{only_default_vector, VectorStructInternal};
+use segment::data_types::vectors::{
+    only_default_vector, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, FieldCondition, Filter, Payload, PayloadFieldSchema, PayloadKeyType,
+    PayloadSchemaType, PointIdType,
+};
 use tempfile::Builder;
 
 use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
@@ -17,8 +23,11 @@ use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::segments_updater::upsert_points;
+use crate::collection_manager::segments_updater::{
+    delete_all_points, delete_points, overwrite_payload, set_payload, upsert_points,
+};
 use crate::operations::point_ops::PointStruct;
+use crate::operations::types::{CollectionStatus, OptimizerError};
 
 fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> SegmentId {
     let mut write_segments = segments.write();
@@ -240,3 +249,215 @@ fn test_upsert_points_in_smallest_segment() {
         }
     }
 }
+
+#[test]
+fn test_highest_sequence_wins_for_same_point() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add_new(empty_segment(dir.path()));
+    let segments = Arc::new(RwLock::new(holder));
+
+    let point_id: PointIdType = 1.into();
+    let point = |seq: u64| PointStruct {
+        id: point_id,
+        vector: VectorStructInternal::from(vec![seq as f32, 0.0, 0.0, 0.0]).into(),
+        payload: Some(serde_json::from_value(serde_json::json!({ "seq": seq })).unwrap()),
+    };
+
+    // Deliver upserts out of order
+    for seq in [3, 1, 2] {
+        upsert_points(&segments.read(), seq, [&point(seq)]).unwrap();
+    }
+
+    {
+        let segment = segments.read().get(sid).unwrap().get();
+        let segment = segment.read();
+        assert_eq!(
+            segment.payload(point_id).unwrap(),
+            serde_json::from_value(serde_json::json!({ "seq": 3 })).unwrap(),
+        );
+        assert_eq!(
+            segment.vector(DEFAULT_VECTOR_NAME, point_id).unwrap(),
+            Some(Vector::from(vec![3.0, 0.0, 0.0, 0.0])),
+        );
+    }
+
+    // An upsert older than the deletion must not bring the point back
+    delete_points(&segments.read(), 10, &[point_id]).unwrap();
+    upsert_points(&segments.read(), 7, [&point(7)]).unwrap();
+    assert!(!segments
+        .read()
+        .get(sid)
+        .unwrap()
+        .get()
+        .read()
+        .has_point(point_id));
+
+    // A newer upsert does
+    upsert_points(&segments.read(), 11, [&point(11)]).unwrap();
+    let segment = segments.read().get(sid).unwrap().get();
+    assert_eq!(
+        segment.read().payload(point_id).unwrap(),
+        serde_json::from_value(serde_json::json!({ "seq": 11 })).unwrap(),
+    );
+}
+
+#[test]
+fn test_older_upsert_after_delete_all() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    holder.add_new(build_segment_1(dir.path()));
+    holder.add_new(build_segment_2(dir.path()));
+    let segments = RwLock::new(holder);
+
+    let point = |id: u64| PointStruct {
+        id: id.into(),
+        vector: VectorStructInternal::from(vec![1.0, 0.0, 0.0, 0.0]).into(),
+        payload: None,
+    };
+    let has_point = |id: u64| {
+        segments
+            .read()
+            .iter()
+            .any(|(_, segment)| segment.get().read().has_point(id.into()))
+    };
+
+    let deleted = delete_all_points(&segments, 100).unwrap();
+    assert!(deleted > 0);
+    assert_eq!(segments.read().len(), 1);
+
+    // Upserts older than the deletion, of stored and of new points, must not bring them back
+    upsert_points(&segments.read(), 90, [&point(1), &point(500)]).unwrap();
+    assert!(!has_point(1));
+    assert!(!has_point(500));
+
+    // Newer upserts do
+    upsert_points(&segments.read(), 101, [&point(1)]).unwrap();
+    assert!(has_point(1));
+}
+
+#[test]
+fn test_payload_updates_in_place() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let color_key = JsonPath::new("color");
+    let mut segment = build_segment_1(dir.path());
+    segment
+        .create_field_index(
+            7,
+            &color_key,
+            Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+        )
+        .unwrap();
+    segment.appendable_flag = false;
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add_new(segment);
+    let appendable_sid = holder.add_new(empty_segment(dir.path()));
+
+    let has_point = |sid: SegmentId, point_id: PointIdType| {
+        holder.get(sid).unwrap().get().read().has_point(point_id)
+    };
+
+    // Payload not affecting indexed fields is updated in place
+    let payload: Payload = serde_json::from_value(serde_json::json!({ "status": "done" })).unwrap();
+    let updated = set_payload(&holder, 8, &payload, &[1.into(), 2.into()], &None).unwrap();
+    assert_eq!(updated, 2);
+    assert!(has_point(sid, 1.into()));
+    assert!(has_point(sid, 2.into()));
+
+    // Overwriting payload keeping indexed values of the point is updated in place too
+    let payload: Payload =
+        serde_json::from_value(serde_json::json!({ "color": ["red"], "status": "archived" }))
+            .unwrap();
+    overwrite_payload(&holder, 9, &payload, &[1.into()]).unwrap();
+    assert!(has_point(sid, 1.into()));
+    assert!(!has_point(appendable_sid, 1.into()));
+
+    // Changing an indexed value moves the point out of the non-appendable segment
+    let payload: Payload = serde_json::from_value(serde_json::json!({ "color": "green" })).unwrap();
+    overwrite_payload(&holder, 10, &payload, &[2.into()]).unwrap();
+    assert!(!has_point(sid, 2.into()));
+    assert!(has_point(appendable_sid, 2.into()));
+
+    let segment = holder.get(sid).unwrap().get();
+    assert_eq!(
+        segment.read().payload(1.into()).unwrap(),
+        serde_json::from_value(serde_json::json!({ "color": ["red"], "status": "archived" }))
+            .unwrap(),
+    );
+
+    // Payload index reflects the changes
+    let is_stopped = AtomicBool::new(false);
+    let find = |color: &str| {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            color_key.clone(),
+            color.to_string().into(),
+        )));
+        let mut found = vec![];
+        holder
+            .for_each_segment(|segment| {
+                found.extend(segment.read_filtered(None, None, Some(&filter), &is_stopped));
+                Ok(true)
+            })
+            .unwrap();
+        found.sort();
+        found
+    };
+    assert_eq!(find("red"), vec![1.into(), 4.into(), 5.into()]);
+    assert_eq!(find("green"), vec![2.into()]);
+}
+
+#[test]
+fn test_optimizer_failure_rate_degrades_status() {
+    let mut holder = SegmentHolder::default();
+    let failure =
+        || OptimizerError::new("No space left on device").with_optimizer("indexing", vec![3]);
+
+    // Rare failures don't affect the status
+    holder.report_optimizer_failure(failure());
+    for _ in 0..9 {
+        holder.report_optimizer_success();
+    }
+    assert!(holder.optimizer_health().is_none());
+
+    // Repeated failures degrade the status
+    holder.report_optimizer_failure(failure());
+    holder.report_optimizer_failure(failure());
+    let (status, error) = holder.optimizer_health().unwrap();
+    assert_eq!(status, CollectionStatus::Yellow);
+    assert_eq!(
+        error.error,
+        "3 of the last 12 optimizations failed, last error: No space left on device",
+    );
+    assert_eq!(error.optimizer.as_deref(), Some("indexing"));
+    assert_eq!(error.segment_ids, vec![3]);
+
+    for _ in 0..10 {
+        holder.report_optimizer_failure(failure());
+    }
+    let (status, error) = holder.optimizer_health().unwrap();
+    assert_eq!(status, CollectionStatus::Red);
+    assert!(error
+        .error
+        .starts_with("12 of the last 20 optimizations failed"));
+
+    // Status recovers once optimizations succeed again
+    for _ in 0..20 {
+        holder.report_optimizer_success();
+    }
+    assert!(holder.optimizer_health().is_none());
+
+    // Collection is failing while a failed optimization is rolled back
+    holder.optimizer_outcomes.begin_rollback(failure());
+    let (status, error) = holder.optimizer_health().unwrap();
+    assert_eq!(status, CollectionStatus::Red);
+    assert_eq!(
+        error.error,
+        "rolling back failed optimization: No space left on device",
+    );
+    holder.optimizer_outcomes.end_rollback();
+    assert!(holder.optimizer_health().is_none());
+}
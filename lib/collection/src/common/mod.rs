@@ -4,7 +4,10 @@ pub mod fetch_vectors;
 pub mod file_utils;
 pub mod is_ready;
 pub mod retrieve_request_trait;
+pub mod segment_volumes;
 pub mod sha_256;
+pub mod snapshot_archive;
+pub mod snapshot_space;
 pub mod snapshot_stream;
 pub mod snapshots_manager;
 pub mod stoppable_task;
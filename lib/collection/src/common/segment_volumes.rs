@@ -0,0 +1,745 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use common::disk::dir_size;
+use fs_extra::dir::CopyOptions;
+use uuid::Uuid;
+
+/// Directory of a volume, in which segments placed on it are stored.
+const VOLUME_SEGMENTS_PATH: &str = "segments";
+/// Directory of a volume, in which segments placed on it are built.
+const VOLUME_TEMP_PATH: &str = "temp";
+/// Extension of segments, which are never loaded because they are being added or removed.
+const DELETED_EXTENSION: &str = "deleted";
+
+/// Additional storage volumes, among which segments of local shards are spread.
+///
+/// Segment placed on a volume is stored in the volume directory and linked into the segments
+/// directory of its shard. So shard loading and snapshots keep working with the shard directory
+/// only, regardless of which disk holds the segment data.
+#[derive(Debug, Clone, Default)]
+pub struct SegmentVolumes {
+    volumes: Vec<PathBuf>,
+}
+
+/// Disk on which a segment of a shard is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Location {
+    /// Segments directory of the shard itself
+    Shard,
+    /// One of the additional volumes, by index
+    Volume(usize),
+}
+
+impl SegmentVolumes {
+    pub fn new(volumes: Vec<PathBuf>) -> Self {
+        Self { volumes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.volumes.is_empty()
+    }
+
+    /// Directory in which segments placed on `volume` are stored.
+    pub fn volume_segments_path(volume: &Path) -> PathBuf {
+        volume.join(VOLUME_SEGMENTS_PATH)
+    }
+
+    /// Directory in which segments placed on `volume` are built.
+    ///
+    /// Located on the same volume, so that built segment can be moved in place without copying.
+    pub fn volume_temp_path(volume: &Path) -> PathBuf {
+        volume.join(VOLUME_TEMP_PATH)
+    }
+
+    /// Select a volume for a new segment of the shard with the given segments directory.
+    ///
+    /// Returns the volume with the most space available, or `None` if the shard directory itself
+    /// has the most space available and the segment should be created there as usual.
+    pub fn select_volume(
+        &self,
+        segments_path: &Path,
+        available_space: impl Fn(&Path) -> io::Result<u64>,
+    ) -> Option<&Path> {
+        if self.volumes.is_empty() {
+            return None;
+        }
+
+        match self.emptiest(segments_path, &available_space)? {
+            Location::Shard => None,
+            Location::Volume(index) => Some(&self.volumes[index]),
+        }
+    }
+
+    /// Move segments of the shard between the shard directory and the volumes, to even out their
+    /// available space.
+    ///
+    /// Segments are moved one by one from the fullest to the emptiest disk, as long as it makes
+    /// the difference in available space smaller. Safe to call while segments of the shard are
+    /// loaded, each moved segment is copied and then handed over to the shard:
+    ///
+    /// - `prepare` is called with the path of the segment to move, before it is copied. It returns
+    ///   `None` if the segment must not be moved.
+    /// - `replace` is called with the result of `prepare`, the path of the segment and the path of
+    ///   its copy, and replaces the segment by its copy. It returns `false` if the segment could
+    ///   not be replaced, e.g. because it was changed while being copied. The copy is removed
+    ///   then, and the segment stays where it is.
+    ///
+    /// Returns the number of moved segments.
+    pub fn rebalance<T>(
+        &self,
+        segments_path: &Path,
+        temp_path: &Path,
+        available_space: impl Fn(&Path) -> io::Result<u64>,
+        mut prepare: impl FnMut(&Path) -> io::Result<Option<T>>,
+        mut replace: impl FnMut(T, &Path, &Path) -> io::Result<bool>,
+    ) -> io::Result<usize> {
+        if self.volumes.is_empty() {
+            return Ok(0);
+        }
+
+        let max_moves = std::fs::read_dir(segments_path)?.count();
+        let mut skipped = HashSet::new();
+        let mut moved = 0;
+
+        for _ in 0..max_moves {
+            let Some(spaces) = self.available_spaces(segments_path, &available_space) else {
+                break;
+            };
+            let (fullest, fullest_space) = spaces
+                .iter()
+                .copied()
+                .min_by_key(|(_, space)| *space)
+                .unwrap();
+            let (emptiest, emptiest_space) = spaces
+                .iter()
+                .copied()
+                .max_by_key(|(_, space)| *space)
+                .unwrap();
+            let gap = emptiest_space.saturating_sub(fullest_space);
+
+            let mut candidates = vec![];
+            for entry in std::fs::read_dir(segments_path)? {
+                let entry = entry?;
+                let name = PathBuf::from(entry.file_name());
+                if is_deleted(&name) || skipped.contains(&name) {
+                    continue;
+                }
+                if self.locate(&entry.path())? != Some(fullest) {
+                    continue;
+                }
+                let data_path = self.data_path(segments_path, fullest, &name);
+                candidates.push((dir_size(&data_path)?, name));
+            }
+
+            // Moving the smallest segment changes the balance the least, don't overshoot
+            let Some((size, name)) = candidates.into_iter().min() else {
+                break;
+            };
+            if size.saturating_mul(2) >= gap {
+                break;
+            }
+
+            let path = segments_path.join(&name);
+            let Some(prepared) = prepare(&path)? else {
+                skipped.insert(name);
+                continue;
+            };
+
+            let copy_path =
+                self.copy_segment(segments_path, temp_path, &name, fullest, emptiest)?;
+            if replace(prepared, &path, &copy_path)? {
+                self.remove_segment(&path)?;
+                moved += 1;
+            } else {
+                self.remove_segment(&copy_path)?;
+                skipped.insert(name);
+            }
+        }
+
+        Ok(moved)
+    }
+
+    fn available_spaces(
+        &self,
+        segments_path: &Path,
+        available_space: &impl Fn(&Path) -> io::Result<u64>,
+    ) -> Option<Vec<(Location, u64)>> {
+        let locations = std::iter::once((Location::Shard, segments_path)).chain(
+            self.volumes
+                .iter()
+                .enumerate()
+                .map(|(index, volume)| (Location::Volume(index), volume.as_path())),
+        );
+
+        let mut spaces = Vec::with_capacity(self.volumes.len() + 1);
+        for (location, path) in locations {
+            match available_space(path) {
+                Ok(space) => spaces.push((location, space)),
+                Err(err) => {
+                    log::warn!(
+                        "Could not estimate available storage space in `{}`, segments are not placed on it: {err}",
+                        path.display(),
+                    );
+                    if location == Location::Shard {
+                        return None;
+                    }
+                }
+            }
+        }
+        Some(spaces)
+    }
+
+    /// Location with the most available space, preferring the shard directory on ties.
+    fn emptiest(
+        &self,
+        segments_path: &Path,
+        available_space: &impl Fn(&Path) -> io::Result<u64>,
+    ) -> Option<Location> {
+        let spaces = self.available_spaces(segments_path, available_space)?;
+        spaces
+            .into_iter()
+            .rev()
+            .max_by_key(|(_, space)| *space)
+            .map(|(location, _)| location)
+    }
+
+    /// Location of the segment linked at `path` in the shard segments directory.
+    ///
+    /// Returns `None` for segments linked from unknown locations, they are never moved.
+    fn locate(&self, path: &Path) -> io::Result<Option<Location>> {
+        if !path.is_symlink() {
+            return Ok(Some(Location::Shard));
+        }
+        let target = std::fs::read_link(path)?;
+        let location = self
+            .volumes
+            .iter()
+            .position(|volume| {
+                let volume_segments_path = Self::volume_segments_path(volume).canonicalize().ok();
+                target.parent() == volume_segments_path.as_deref()
+            })
+            .map(Location::Volume);
+        Ok(location)
+    }
+
+    fn data_path(&self, segments_path: &Path, location: Location, name: &Path) -> PathBuf {
+        match location {
+            Location::Shard => segments_path.join(name),
+            Location::Volume(index) => Self::volume_segments_path(&self.volumes[index]).join(name),
+        }
+    }
+
+    /// Copy the segment to another disk, and link the copy into the segments directory of the
+    /// shard under a new name.
+    ///
+    /// The copy is made in a temporary directory on the other disk, and then moved in place
+    /// atomically. Until the copy on a volume is complete, it is linked as deleted, so that
+    /// [`Self::remove_interrupted_moves`] cleans it up if the move is interrupted.
+    ///
+    /// Returns the path of the copy in the segments directory.
+    fn copy_segment(
+        &self,
+        segments_path: &Path,
+        shard_temp_path: &Path,
+        name: &Path,
+        from: Location,
+        to: Location,
+    ) -> io::Result<PathBuf> {
+        let source = self.data_path(segments_path, from, name);
+        let new_name = Uuid::new_v4().to_string();
+        let (temp_dir, destination) = match to {
+            Location::Shard => (shard_temp_path.to_path_buf(), segments_path.join(&new_name)),
+            Location::Volume(index) => {
+                let volume = &self.volumes[index];
+                let volume_segments_path = Self::volume_segments_path(volume);
+                std::fs::create_dir_all(&volume_segments_path)?;
+                (
+                    Self::volume_temp_path(volume),
+                    volume_segments_path.canonicalize()?.join(&new_name),
+                )
+            }
+        };
+
+        log::info!(
+            "Moving segment {} to {}",
+            source.display(),
+            destination.display(),
+        );
+
+        let link = segments_path.join(&new_name);
+        let marker = deleted_path(&link);
+        if to != Location::Shard {
+            symlink_dir(&destination, &marker)?;
+        }
+
+        let temp_copy = temp_dir.join(&new_name);
+        let copied = copy_dir_atomically(&source, &temp_copy, &destination).and_then(|()| {
+            if to == Location::Shard {
+                return Ok(());
+            }
+            std::fs::rename(&marker, &link)
+        });
+
+        if let Err(err) = copied {
+            if to != Location::Shard {
+                self.remove_segment(&marker)?;
+            } else if temp_copy.exists() {
+                std::fs::remove_dir_all(&temp_copy)?;
+            }
+            return Err(err);
+        }
+
+        Ok(link)
+    }
+
+    /// Remove the segment at `path` in the segments directory of a shard, together with its data
+    /// on a volume if it is linked from there.
+    ///
+    /// The segment is marked as deleted first, so that it is never loaded again, even if the
+    /// removal is interrupted.
+    pub fn remove_segment(&self, path: &Path) -> io::Result<()> {
+        let deleted = if is_deleted(path) {
+            path.to_path_buf()
+        } else {
+            let deleted = deleted_path(path);
+            std::fs::rename(path, &deleted)?;
+            deleted
+        };
+
+        if deleted.is_symlink() {
+            self.remove_link(&deleted)
+        } else {
+            std::fs::remove_dir_all(&deleted)
+        }
+    }
+
+    /// Remove leftovers of segment moves and removals, which were interrupted.
+    ///
+    /// Must be called before segments of the shard are loaded.
+    pub fn remove_interrupted_moves(&self, segments_path: &Path) -> io::Result<()> {
+        for entry in std::fs::read_dir(segments_path)? {
+            let path = entry?.path();
+            if is_deleted(&path) {
+                log::debug!("Removing leftover segment {}", path.display());
+                self.remove_segment(&path)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove data of all segments linked into the given directory from other volumes.
+    ///
+    /// Removing the directory itself does not follow the links, so their data would be left
+    /// behind.
+    pub fn remove_linked_segments(&self, path: &Path) -> io::Result<()> {
+        if !path.exists() {
+            return Ok(());
+        }
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_symlink() {
+                self.remove_link(&entry.path())?;
+            } else if file_type.is_dir() {
+                self.remove_linked_segments(&entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the directory, together with data of all segments linked into it from other
+    /// volumes.
+    pub async fn remove_dir_with_linked_segments(&self, path: &Path) -> io::Result<()> {
+        let volumes = self.clone();
+        let path_clone = path.to_path_buf();
+        tokio::task::spawn_blocking(move || volumes.remove_linked_segments(&path_clone))
+            .await
+            .map_err(io::Error::other)??;
+        tokio::fs::remove_dir_all(path).await
+    }
+
+    /// Remove the segment link, together with the segment data on a volume it points to.
+    ///
+    /// Data is only removed from the segments directories of the configured volumes. Links
+    /// pointing anywhere else, e.g. made by hand or corrupted, are removed without following them.
+    fn remove_link(&self, link: &Path) -> io::Result<()> {
+        let target = std::fs::read_link(link)?;
+        match self.linked_data_paths(&target) {
+            Some(data_paths) => {
+                for data_path in data_paths {
+                    if data_path.exists() {
+                        std::fs::remove_dir_all(&data_path)?;
+                    }
+                }
+            }
+            None => log::warn!(
+                "Segment link {} points outside of the segment volumes, keeping {}",
+                link.display(),
+                target.display(),
+            ),
+        }
+        std::fs::remove_file(link)
+    }
+
+    /// Paths of the segment data on a volume, which a segment link pointing to `target` refers to
+    ///
+    /// Includes the copy of the segment in the volume temp directory, it may not have been moved
+    /// into the volume segments directory yet. Returns `None` if `target` is not a segment in the
+    /// segments directory of one of the volumes.
+    fn linked_data_paths(&self, target: &Path) -> Option<[PathBuf; 2]> {
+        let name = target.file_name()?;
+        let target_dir = target.parent()?.canonicalize().ok()?;
+        self.volumes.iter().find_map(|volume| {
+            let volume_segments_path = Self::volume_segments_path(volume).canonicalize().ok()?;
+            (target_dir == volume_segments_path).then(|| {
+                [
+                    volume_segments_path.join(name),
+                    Self::volume_temp_path(volume).join(name),
+                ]
+            })
+        })
+    }
+}
+
+/// Copy the directory to `temp_path`, and move it to `destination` once the copy is complete.
+fn copy_dir_atomically(source: &Path, temp_path: &Path, destination: &Path) -> io::Result<()> {
+    if let Some(temp_dir) = temp_path.parent() {
+        std::fs::create_dir_all(temp_dir)?;
+    }
+    let options = CopyOptions {
+        content_only: true,
+        ..CopyOptions::new()
+    };
+    fs_extra::dir::copy(source, temp_path, &options).map_err(io::Error::other)?;
+    std::fs::rename(temp_path, destination)
+}
+
+fn is_deleted(path: &Path) -> bool {
+    path.extension() == Some(DELETED_EXTENSION.as_ref())
+}
+
+fn deleted_path(path: &Path) -> PathBuf {
+    path.with_extension(DELETED_EXTENSION)
+}
+
+/// Link a segment stored on a volume into the segments directory of its shard.
+///
+/// Does nothing if the segment is stored in the segments directory already.
+pub fn link_segment(segments_path: &Path, segment_path: &Path) -> io::Result<()> {
+    if segment_path.parent() == Some(segments_path) {
+        return Ok(());
+    }
+    let name = segment_path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid segment path {}", segment_path.display()),
+        )
+    })?;
+    // Links are resolved relative to the segments directory, so link absolute paths only
+    symlink_dir(&segment_path.canonicalize()?, &segments_path.join(name))
+}
+
+/// Remove links to segments which don't exist anymore, e.g. after they were optimized.
+pub fn remove_dangling_links(segments_path: &Path) -> io::Result<()> {
+    for entry in std::fs::read_dir(segments_path)? {
+        let path = entry?.path();
+        // Copies which are still being moved in place are linked before they exist
+        if path.is_symlink() && !path.exists() && !is_deleted(&path) {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink_dir(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::unix::fs::symlink(original, link)
+}
+
+#[cfg(windows)]
+fn symlink_dir(original: &Path, link: &Path) -> io::Result<()> {
+    std::os::windows::fs::symlink_dir(original, link)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use tempfile::{Builder, TempDir};
+
+    use super::*;
+
+    /// Capacity of each mock volume
+    const CAPACITY: u64 = 1024 * 1024;
+
+    /// Available space of a mock volume, only counting data stored on it directly.
+    fn mock_available_space(path: &Path) -> io::Result<u64> {
+        fn used(path: &Path) -> io::Result<u64> {
+            let mut total = 0;
+            for entry in fs::read_dir(path)? {
+                let entry = entry?;
+                let file_type = entry.file_type()?;
+                if file_type.is_dir() {
+                    total += used(&entry.path())?;
+                } else if file_type.is_file() {
+                    total += entry.metadata()?.len();
+                }
+            }
+            Ok(total)
+        }
+        Ok(CAPACITY - used(path)?)
+    }
+
+    fn make_segment(dir: &Path, name: &str, size: usize) -> PathBuf {
+        let segment_path = dir.join(name);
+        fs::create_dir_all(&segment_path).unwrap();
+        fs::write(segment_path.join("data"), vec![1u8; size]).unwrap();
+        segment_path
+    }
+
+    /// Sizes of data of all segments in the shard segments directory, in ascending order.
+    fn segment_sizes(segments_path: &Path) -> Vec<usize> {
+        let mut sizes = fs::read_dir(segments_path)
+            .unwrap()
+            .map(|entry| {
+                let data_path = entry.unwrap().path().join("data");
+                fs::read(data_path).unwrap().len()
+            })
+            .collect::<Vec<_>>();
+        sizes.sort();
+        sizes
+    }
+
+    fn tempdir(prefix: &str) -> TempDir {
+        Builder::new().prefix(prefix).tempdir().unwrap()
+    }
+
+    #[test]
+    fn test_new_segments_placed_on_emptiest_volume() {
+        let shard_dir = tempdir("segments");
+        let fuller_volume = tempdir("volume_a");
+        let emptier_volume = tempdir("volume_b");
+        let segments_path = shard_dir.path();
+
+        make_segment(segments_path, "shard_segment", 300_000);
+        make_segment(fuller_volume.path(), "other_data", 200_000);
+        make_segment(emptier_volume.path(), "other_data", 100_000);
+
+        let volumes = SegmentVolumes::new(vec![
+            fuller_volume.path().to_path_buf(),
+            emptier_volume.path().to_path_buf(),
+        ]);
+
+        let selected = volumes.select_volume(segments_path, mock_available_space);
+        assert_eq!(selected, Some(emptier_volume.path()));
+
+        // Place a segment on the selected volume and link it into the shard
+        let segment_path = make_segment(
+            &SegmentVolumes::volume_segments_path(selected.unwrap()),
+            "new_segment",
+            250_000,
+        );
+        link_segment(segments_path, &segment_path).unwrap();
+        assert!(segments_path.join("new_segment").join("data").exists());
+
+        // Now the other volume is the emptiest one
+        let selected = volumes.select_volume(segments_path, mock_available_space);
+        assert_eq!(selected, Some(fuller_volume.path()));
+
+        // Shard directory is preferred if it is the emptiest one
+        fs::remove_dir_all(segments_path.join("shard_segment")).unwrap();
+        assert_eq!(
+            volumes.select_volume(segments_path, mock_available_space),
+            None
+        );
+
+        // No volumes - segments are always in the shard directory
+        assert_eq!(
+            SegmentVolumes::default().select_volume(segments_path, mock_available_space),
+            None
+        );
+
+        volumes.remove_linked_segments(segments_path).unwrap();
+        assert!(!segment_path.exists());
+        assert!(!segments_path.join("new_segment").exists());
+    }
+
+    #[test]
+    fn test_rebalance_segments() {
+        let shard_dir = tempdir("shard");
+        let volume_a = tempdir("volume_a");
+        let volume_b = tempdir("volume_b");
+        let segments_path = shard_dir.path().join("segments");
+        let temp_path = shard_dir.path().join("temp");
+
+        for i in 0..6 {
+            make_segment(&segments_path, &format!("segment_{i}"), 100_000 + i * 1000);
+        }
+
+        let volumes = SegmentVolumes::new(vec![
+            volume_a.path().to_path_buf(),
+            volume_b.path().to_path_buf(),
+        ]);
+
+        let moved = volumes
+            .rebalance(
+                &segments_path,
+                &temp_path,
+                mock_available_space,
+                |_| Ok(Some(())),
+                |(), _, _| Ok(true),
+            )
+            .unwrap();
+        assert_eq!(moved, 4);
+
+        // All disks hold two segments now
+        for path in [
+            segments_path.clone(),
+            SegmentVolumes::volume_segments_path(volume_a.path()),
+            SegmentVolumes::volume_segments_path(volume_b.path()),
+        ] {
+            let stored = fs::read_dir(&path)
+                .unwrap()
+                .filter(|entry| entry.as_ref().unwrap().file_type().unwrap().is_dir())
+                .count();
+            assert_eq!(stored, 2, "{}", path.display());
+        }
+
+        // All segments are still accessible from the shard directory
+        assert_eq!(
+            segment_sizes(&segments_path),
+            (0..6).map(|i| 100_000 + i * 1000).collect::<Vec<_>>(),
+        );
+
+        // Balanced already, nothing to move
+        let moved = volumes
+            .rebalance(
+                &segments_path,
+                &temp_path,
+                mock_available_space,
+                |_| Ok(Some(())),
+                |(), _, _| Ok(true),
+            )
+            .unwrap();
+        assert_eq!(moved, 0);
+    }
+
+    #[test]
+    fn test_rebalance_keeps_segments_which_are_not_replaced() {
+        let shard_dir = tempdir("shard");
+        let volume = tempdir("volume");
+        let segments_path = shard_dir.path().join("segments");
+        let temp_path = shard_dir.path().join("temp");
+
+        make_segment(&segments_path, "appendable", 10_000);
+        make_segment(&segments_path, "changed", 20_000);
+        make_segment(&segments_path, "large", 500_000);
+
+        let volumes = SegmentVolumes::new(vec![volume.path().to_path_buf()]);
+
+        let mut replaced = vec![];
+        let moved = volumes
+            .rebalance(
+                &segments_path,
+                &temp_path,
+                mock_available_space,
+                |path| Ok((!path.ends_with("appendable")).then_some(())),
+                |(), path, copy_path| {
+                    replaced.push(path.to_path_buf());
+                    // Copy is complete and linked into the shard already
+                    assert!(copy_path.join("data").exists());
+                    Ok(false)
+                },
+            )
+            .unwrap();
+        assert_eq!(moved, 0);
+        assert_eq!(replaced, vec![segments_path.join("changed")]);
+
+        // Segments stay in place, and the copy is removed
+        assert_eq!(segment_sizes(&segments_path), vec![10_000, 20_000, 500_000]);
+        let volume_segments_path = SegmentVolumes::volume_segments_path(volume.path());
+        assert_eq!(fs::read_dir(volume_segments_path).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_remove_interrupted_moves() {
+        let shard_dir = tempdir("segments");
+        let volume = tempdir("volume");
+        let segments_path = shard_dir.path();
+        let volume_segments_path = SegmentVolumes::volume_segments_path(volume.path());
+        let volume_temp_path = SegmentVolumes::volume_temp_path(volume.path());
+
+        make_segment(segments_path, "segment", 1000);
+        let linked = make_segment(&volume_segments_path, "linked", 1000);
+        link_segment(segments_path, &linked).unwrap();
+
+        // Copy to the volume, which was not moved in place yet
+        make_segment(&volume_temp_path, "copying", 1000);
+        symlink_dir(
+            &volume_segments_path.canonicalize().unwrap().join("copying"),
+            &segments_path.join("copying.deleted"),
+        )
+        .unwrap();
+
+        // Copy to the volume, which was moved in place but not linked yet
+        let copied = make_segment(&volume_segments_path, "copied", 1000);
+        symlink_dir(
+            &copied.canonicalize().unwrap(),
+            &segments_path.join("copied.deleted"),
+        )
+        .unwrap();
+
+        // Segment which was being removed
+        make_segment(segments_path, "removed.deleted", 1000);
+
+        let volumes = SegmentVolumes::new(vec![volume.path().to_path_buf()]);
+        volumes.remove_interrupted_moves(segments_path).unwrap();
+
+        let mut names = fs::read_dir(segments_path)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["linked", "segment"]);
+        assert!(linked.exists());
+        assert!(!copied.exists());
+        assert!(!volume_temp_path.join("copying").exists());
+    }
+
+    #[test]
+    fn test_remove_links_outside_of_volumes() {
+        let shard_dir = tempdir("shard");
+        let volume = tempdir("volume");
+        let other_dir = tempdir("other");
+        let segments_path = shard_dir.path().join("segments");
+        fs::create_dir_all(&segments_path).unwrap();
+
+        let linked = make_segment(
+            &SegmentVolumes::volume_segments_path(volume.path()),
+            "linked",
+            1000,
+        );
+        link_segment(&segments_path, &linked).unwrap();
+
+        // Links made by hand, to a directory outside of the volumes and out of a volume
+        let outside = make_segment(other_dir.path(), "outside", 1000);
+        symlink_dir(&outside, &segments_path.join("outside")).unwrap();
+        let escaping = SegmentVolumes::volume_segments_path(volume.path())
+            .canonicalize()
+            .unwrap()
+            .join("..")
+            .join("..")
+            .join(other_dir.path().file_name().unwrap());
+        symlink_dir(&escaping, &segments_path.join("escaping.deleted")).unwrap();
+
+        let volumes = SegmentVolumes::new(vec![volume.path().to_path_buf()]);
+        volumes.remove_linked_segments(shard_dir.path()).unwrap();
+
+        // Only data on the volume is removed, together with all links
+        assert!(!linked.exists());
+        assert!(outside.join("data").exists());
+        assert_eq!(fs::read_dir(&segments_path).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,103 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use common::disk::dir_size;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Snapshot data is first copied into a temporary directory and then archived,
+/// so at peak both copies live in the temp directory.
+const SNAPSHOT_SPACE_FACTOR: u64 = 2;
+
+/// Check that the temp directory has enough free space to create a snapshot of the given
+/// shard directories. Fails fast with [`CollectionError::PreConditionFailed`] otherwise.
+///
+/// If either the required or the available space can't be estimated, the check is skipped.
+///
+/// # Cancel safety
+///
+/// This function is cancel safe.
+pub async fn ensure_snapshot_space(
+    temp_dir: &Path,
+    shard_paths: Vec<PathBuf>,
+) -> CollectionResult<()> {
+    let temp_dir = temp_dir.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        check_snapshot_space(&temp_dir, &shard_paths, fs4::available_space)
+    })
+    .await?
+}
+
+fn check_snapshot_space(
+    temp_dir: &Path,
+    shard_paths: &[PathBuf],
+    available_space: impl FnOnce(&Path) -> io::Result<u64>,
+) -> CollectionResult<()> {
+    let space_needed = match estimate_snapshot_size(shard_paths) {
+        Ok(size) => size.saturating_mul(SNAPSHOT_SPACE_FACTOR),
+        Err(err) => {
+            log::warn!("Could not estimate snapshot size, will try creating it anyway: {err}");
+            return Ok(());
+        }
+    };
+
+    let space_available = match available_space(temp_dir) {
+        Ok(available) => available,
+        Err(err) => {
+            log::warn!(
+                "Could not estimate available storage space in `{}`, will try creating snapshot anyway: {err}",
+                temp_dir.display(),
+            );
+            return Ok(());
+        }
+    };
+
+    if space_available < space_needed {
+        return Err(CollectionError::PreConditionFailed {
+            description: format!(
+                "Not enough space available in `{}` to create snapshot: \
+                 {space_needed} bytes required, {space_available} bytes available",
+                temp_dir.display(),
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+fn estimate_snapshot_size(shard_paths: &[PathBuf]) -> io::Result<u64> {
+    shard_paths
+        .iter()
+        .try_fold(0, |acc, path| Ok(acc + dir_size(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_space_precheck() {
+        let shard_dir = tempfile::Builder::new().prefix("shard").tempdir().unwrap();
+        let temp_dir = tempfile::Builder::new().prefix("temp").tempdir().unwrap();
+
+        std::fs::write(shard_dir.path().join("segment.dat"), vec![0u8; 1024]).unwrap();
+        let shard_paths = vec![shard_dir.path().to_path_buf()];
+
+        let result = check_snapshot_space(temp_dir.path(), &shard_paths, |_| Ok(1024));
+        assert!(matches!(
+            result,
+            Err(CollectionError::PreConditionFailed { .. })
+        ));
+
+        // Nothing is written into the temp directory if the check fails
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        check_snapshot_space(temp_dir.path(), &shard_paths, |_| Ok(2048)).unwrap();
+
+        // Unknown available space does not block snapshot creation
+        check_snapshot_space(temp_dir.path(), &shard_paths, |_| {
+            Err(io::Error::other("unsupported"))
+        })
+        .unwrap();
+    }
+}
@@ -7,11 +7,12 @@ use serde::Deserialize;
 use tempfile::TempPath;
 use tokio::io::AsyncWriteExt;
 
+use super::snapshot_archive::SnapshotCompression;
 use super::snapshot_stream::{SnapShotStreamCloudStrage, SnapShotStreamLocalFS, SnapshotStream};
 use crate::common::file_utils::move_file;
 use crate::common::sha_256::hash_file;
 use crate::operations::snapshot_ops::{
-    get_checksum_path, get_snapshot_description, SnapshotDescription,
+    get_checksum_path, get_seq_number_path, get_snapshot_description, SnapshotDescription,
 };
 use crate::operations::snapshot_storage_ops::{self};
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -22,6 +23,9 @@ use crate::shards::shard_holder::LockedShardHolder;
 pub struct SnapShotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
     pub s3_config: Option<S3Config>,
+    /// Compression of snapshot archives. If not defined - archives are not compressed.
+    #[serde(default)]
+    pub compression: Option<SnapshotCompression>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
@@ -130,6 +134,30 @@ impl SnapshotStorageManager {
         }
     }
 
+    /// Same as [`Self::store_file`], but also records the WAL sequence number of the snapshot.
+    ///
+    /// The sequence number is only persisted for local storage.
+    pub async fn store_file_with_seq_number(
+        &self,
+        source_path: &Path,
+        target_path: &Path,
+        seq_number: Option<u64>,
+    ) -> CollectionResult<SnapshotDescription> {
+        match self {
+            SnapshotStorageManager::LocalFS(storage_impl) => {
+                if let Some(seq_number) = seq_number {
+                    storage_impl
+                        .store_seq_number(target_path, seq_number)
+                        .await?;
+                }
+                storage_impl.store_file(source_path, target_path).await
+            }
+            SnapshotStorageManager::S3(storage_impl) => {
+                storage_impl.store_file(source_path, target_path).await
+            }
+        }
+    }
+
     pub async fn get_stored_file(
         &self,
         storage_path: &Path,
@@ -245,6 +273,9 @@ impl SnapshotStorageLocalFS {
             log::warn!("Failed to delete checksum file for snapshot, ignoring: {err}");
         }
 
+        // Only shard snapshots have a sequence number file
+        let _ = tokio::fs::remove_file(get_seq_number_path(snapshot_path)).await;
+
         Ok(true)
     }
 
@@ -263,6 +294,18 @@ impl SnapshotStorageLocalFS {
         Ok(snapshots)
     }
 
+    async fn store_seq_number(&self, target_path: &Path, seq_number: u64) -> CollectionResult<()> {
+        if let Some(target_dir) = target_path.parent() {
+            if !target_dir.exists() {
+                std::fs::create_dir_all(target_dir)?;
+            }
+        }
+
+        let seq_number_path = get_seq_number_path(target_path);
+        tokio::fs::write(&seq_number_path, seq_number.to_string()).await?;
+        Ok(())
+    }
+
     async fn store_file(
         &self,
         source_path: &Path,
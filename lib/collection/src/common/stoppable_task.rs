@@ -22,6 +22,11 @@ impl<T> StoppableTaskHandle<T> {
         self.join_handle.is_finished()
     }
 
+    /// Flag to ask this task to stop, which does not keep the task alive
+    pub fn stop_flag(&self) -> Weak<AtomicBool> {
+        self.stopped.clone()
+    }
+
     pub fn ask_to_stop(&self) {
         if let Some(v) = self.stopped.upgrade() {
             v.store(true, Ordering::Relaxed);
@@ -1,13 +1,14 @@
-use std::fs::File;
 use std::path::Path;
 
-use segment::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
+use segment::common::validate_snapshot_archive::{
+    open_snapshot_archive_with_validation, SnapshotArchiveReader,
+};
 use tar::Archive;
 
 use crate::operations::types::CollectionResult;
 
 pub fn validate_open_snapshot_archive<P: AsRef<Path>>(
     archive_path: P,
-) -> CollectionResult<Archive<File>> {
+) -> CollectionResult<Archive<SnapshotArchiveReader>> {
     Ok(open_snapshot_archive_with_validation(archive_path)?)
 }
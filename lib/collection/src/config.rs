@@ -13,14 +13,16 @@ use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, Spars
 use segment::types::{
     default_replication_factor_const, default_shard_number_const,
     default_write_consistency_factor_const, Distance, HnswConfig, Indexes, PayloadStorageType,
-    QuantizationConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
-    VectorStorageType,
+    QuantizationConfig, SearchParams, ShardKey, SparseVectorDataConfig, VectorDataConfig,
+    VectorStorageDatatype, VectorStorageType,
 };
+use segment::utils::scored_point_ties::TieBreak;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 use wal::WalOptions;
 
 use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff, StrictModeConfig};
+use crate::operations::payload_schema::PayloadSchemaConfig;
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
     VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
@@ -37,6 +39,31 @@ pub struct WalConfig {
     pub wal_capacity_mb: usize,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: usize,
+    /// When to fsync written operations to disk. Default: on_flush
+    #[serde(default)]
+    pub fsync_policy: WalFsyncPolicy,
+    /// Do not write operations to the WAL, apply them to segments directly. Default: false
+    ///
+    /// Unsafe, operations applied since the last flush of segments are lost on a crash.
+    /// Shards are always transferred by streaming records, as other transfer methods forward
+    /// updates from the WAL. Only intended for ephemeral collections, which can be rebuilt from
+    /// scratch.
+    #[serde(default)]
+    pub wal_disabled: bool,
+}
+
+/// Durability of operations written to the WAL
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum WalFsyncPolicy {
+    /// Fsync after every operation, slowest but no acknowledged operation is ever lost
+    EveryOp,
+    /// Fsync when writing an operation, if the last fsync is older than the given number of
+    /// milliseconds
+    Interval(u64),
+    /// Fsync only with the periodic flush of the collection, see `flush_interval_sec`
+    #[default]
+    OnFlush,
 }
 
 impl From<&WalConfig> for WalOptions {
@@ -53,6 +80,8 @@ impl Default for WalConfig {
         WalConfig {
             wal_capacity_mb: 32,
             wal_segments_ahead: 0,
+            fsync_policy: WalFsyncPolicy::default(),
+            wal_disabled: false,
         }
     }
 }
@@ -81,9 +110,18 @@ pub struct CollectionParams {
     #[serde(default)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sharding_method: Option<ShardingMethod>,
+    /// Create shard keys on the first write to them, instead of rejecting the write.
+    /// Only for custom sharding. Default: false
+    #[serde(default)]
+    pub auto_create_shard_keys: bool,
     /// Number of replicas for each shard
     #[serde(default = "default_replication_factor")]
     pub replication_factor: NonZeroU32,
+    /// Number of replicas for each shard of specific shard keys
+    /// Overrides `replication_factor` for the listed shard keys, e.g. to replicate critical
+    /// tenants more heavily than others.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shard_key_replication_factors: Vec<ShardKeyReplicationFactor>,
     /// Defines how many replicas should apply the operation for us to consider it successful.
     /// Increasing this number will make the collection more resilient to inconsistencies, but will
     /// also make it fail if not enough replicas are available.
@@ -108,7 +146,44 @@ pub struct CollectionParams {
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
 }
 
+/// Replication factor of the shards of a single shard key
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardKeyReplicationFactor {
+    pub shard_key: ShardKey,
+    /// Number of replicas for each shard of this shard key
+    pub replication_factor: NonZeroU32,
+}
+
+impl Anonymize for ShardKeyReplicationFactor {
+    fn anonymize(&self) -> Self {
+        let shard_key = match &self.shard_key {
+            ShardKey::Keyword(keyword) => ShardKey::Keyword(keyword.anonymize()),
+            ShardKey::Number(number) => ShardKey::Number(*number),
+        };
+
+        ShardKeyReplicationFactor {
+            shard_key,
+            replication_factor: self.replication_factor,
+        }
+    }
+}
+
 impl CollectionParams {
+    /// Number of replicas for each shard of the given shard key
+    ///
+    /// Falls back to the collection-wide replication factor, if there is no override for the
+    /// shard key.
+    pub fn shard_key_replication_factor(&self, shard_key: Option<&ShardKey>) -> NonZeroU32 {
+        shard_key
+            .and_then(|shard_key| {
+                self.shard_key_replication_factors
+                    .iter()
+                    .find(|factor| &factor.shard_key == shard_key)
+            })
+            .map_or(self.replication_factor, |factor| factor.replication_factor)
+    }
+
     pub fn payload_storage_type(&self) -> PayloadStorageType {
         if self.on_disk_payload {
             PayloadStorageType::OnDisk
@@ -124,7 +199,9 @@ impl Anonymize for CollectionParams {
             vectors: self.vectors.anonymize(),
             shard_number: self.shard_number,
             sharding_method: self.sharding_method,
+            auto_create_shard_keys: self.auto_create_shard_keys,
             replication_factor: self.replication_factor,
+            shard_key_replication_factors: self.shard_key_replication_factors.anonymize(),
             write_consistency_factor: self.write_consistency_factor,
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
@@ -165,6 +242,22 @@ pub struct CollectionConfig {
     #[schemars(skip)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub strict_mode_config: Option<StrictModeConfig>,
+    /// Declared payload schema and how it is enforced on updates
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_schema: Option<PayloadSchemaConfig>,
+    /// Search params used for parameters not specified in a search request
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_search_params: Option<SearchParams>,
+    /// Order of points with equal scores in query results, if not set - points are ordered by id
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tie_break: Option<TieBreak>,
+    /// Timeout in seconds of read requests, which do not specify one
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_timeout: Option<usize>,
 }
 
 impl CollectionConfig {
@@ -205,7 +298,9 @@ impl CollectionParams {
             vectors: Default::default(),
             shard_number: default_shard_number(),
             sharding_method: None,
+            auto_create_shard_keys: false,
             replication_factor: default_replication_factor(),
+            shard_key_replication_factors: Vec::new(),
             write_consistency_factor: default_write_consistency_factor(),
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
@@ -3,8 +3,10 @@ use std::fmt;
 use std::hash::Hash;
 
 use itertools::Itertools as _;
+use schemars::JsonSchema;
 use segment::index::field_index::CardinalityEstimation;
 use segment::types::{CustomIdCheckerCondition, PointIdType};
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
 use crate::operations::cluster_ops::ReshardingDirection;
@@ -205,6 +207,52 @@ impl<T: Hash + Copy + PartialEq + Eq> HashRingRouter<T> {
     }
 }
 
+impl HashRingRouter {
+    /// Shards in the hashrings, which fully determine the routing of points
+    pub fn state(&self) -> HashRingState {
+        let sorted_shards =
+            |ring: &HashRing<ShardId>| ring.unique_nodes().into_iter().sorted().collect();
+
+        match self {
+            Self::Single(ring) => HashRingState::Single {
+                shards: sorted_shards(ring),
+            },
+            Self::Resharding { old, new } => HashRingState::Resharding {
+                old: sorted_shards(old),
+                new: sorted_shards(new),
+            },
+        }
+    }
+
+    /// Filter for points, which are in the given shard
+    ///
+    /// In case of resharding, the new hashring is used, same as in [`Self::is_in_shard`].
+    pub fn hash_ring_filter(&self, expected_shard_id: ShardId) -> HashRingFilter {
+        let ring = match self {
+            Self::Resharding { new, .. } => new,
+            Self::Single(ring) => ring,
+        };
+
+        HashRingFilter::new(ring.clone(), expected_shard_id)
+    }
+}
+
+/// Shards in the hashrings of a peer
+///
+/// All peers build their hashrings the same way, so peers with the same state route points to
+/// the same shards.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HashRingState {
+    /// Single hashring
+    Single { shards: Vec<ShardId> },
+    /// Old and new hashrings during resharding
+    Resharding {
+        old: Vec<ShardId>,
+        new: Vec<ShardId>,
+    },
+}
+
 /// List type for shard IDs
 ///
 /// Uses a `SmallVec` putting two IDs on the stack. That's the maximum number of shards we expect
@@ -2,7 +2,7 @@ use std::num::NonZeroU32;
 
 use common::validation::validate_shard_different_peers;
 use schemars::JsonSchema;
-use segment::types::ShardKey;
+use segment::types::{Filter, ShardKey};
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationErrors};
 
@@ -45,6 +45,9 @@ pub enum ClusterOperations {
     /// Abort resharding
     #[schemars(skip)]
     AbortResharding(AbortReshardingOperation),
+    /// Resume migrating points of an interrupted resharding
+    #[schemars(skip)]
+    ResumeResharding(ResumeReshardingOperation),
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -115,6 +118,7 @@ impl Validate for ClusterOperations {
             ClusterOperations::CommitWriteHashRing(op) => op.validate(),
             ClusterOperations::FinishResharding(op) => op.validate(),
             ClusterOperations::AbortResharding(op) => op.validate(),
+            ClusterOperations::ResumeResharding(op) => op.validate(),
         }
     }
 }
@@ -166,6 +170,13 @@ pub struct AbortReshardingOperation {
     pub abort_resharding: AbortResharding,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct ResumeReshardingOperation {
+    #[validate(nested)]
+    pub resume_resharding: ResumeResharding,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct CommitReadHashRingOperation {
@@ -194,8 +205,14 @@ pub struct ReplicateShard {
     pub to_shard_id: Option<ShardId>,
     pub to_peer_id: PeerId,
     pub from_peer_id: PeerId,
-    /// Method for transferring the shard from one node to another
+    /// Method for transferring the shard from one node to another.
+    /// If not set, the configured default is used, or one is selected based on the shard size.
     pub method: Option<ShardTransferMethod>,
+    /// Only replicate points matching this filter.
+    /// Requires the `stream_records` method and an index for every key used in the filter.
+    /// The new replica becomes a `Listener`, it receives updates but is not used for reads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
 }
 
 impl Validate for ReplicateShard {
@@ -213,7 +230,8 @@ pub struct MoveShard {
     pub to_shard_id: Option<ShardId>,
     pub to_peer_id: PeerId,
     pub from_peer_id: PeerId,
-    /// Method for transferring the shard from one node to another
+    /// Method for transferring the shard from one node to another.
+    /// If not set, the configured default is used, or one is selected based on the shard size.
     pub method: Option<ShardTransferMethod>,
 }
 
@@ -281,3 +299,6 @@ pub struct FinishResharding {}
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
 pub struct AbortResharding {}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct ResumeResharding {}
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use validator::{Validate, ValidationErrors};
 
-use crate::config::{CollectionParams, WalConfig};
+use crate::config::{CollectionParams, ShardKeyReplicationFactor, WalConfig, WalFsyncPolicy};
 use crate::operations::types::CollectionResult;
 use crate::optimizers_builder::OptimizersConfig;
 
@@ -55,10 +55,11 @@ pub trait DiffConfig<T: DeserializeOwned + Serialize> {
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfigDiff {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
+    #[validate(range(max = 1024))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub m: Option<usize>,
     /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build the index.
-    #[validate(range(min = 4))]
+    #[validate(range(min = 4, max = 10000))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ef_construct: Option<usize>,
     /// Minimal size (in kilobytes) of vectors for additional payload-based indexing.
@@ -82,8 +83,13 @@ pub struct HnswConfigDiff {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
+    #[validate(range(max = 1024))]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Select neighbours with the heuristic, which prefers diverse directions over the nearest candidates.
+    /// If set to false, nodes are simply linked to their nearest neighbours. Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_heuristic: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
@@ -123,6 +129,37 @@ pub struct StrictModeConfig {
     /// Max oversampling value allowed in search.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search_max_oversampling: Option<f64>,
+
+    // Distinct values
+    /// Max number of distinct values of a payload key returned at once.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_distinct_values: Option<usize>,
+
+    // Filters
+    /// Max number of conditions in a filter, counting the conditions of nested filters too.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_filter_conditions: Option<usize>,
+
+    /// Reject searches, scrolls and counts without a filter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_filter: Option<bool>,
+
+    // Results
+    /// Max number of points returned by a single search, query, scroll or retrieve request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_result_points: Option<usize>,
+
+    /// Truncate results exceeding `max_result_points` instead of rejecting the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncate_result_points: Option<bool>,
+
+    /// Max estimated size in bytes of the payload returned by a single read request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub max_payload_bytes: Option<usize>,
 }
 
 impl Hash for StrictModeConfig {
@@ -136,6 +173,12 @@ impl Hash for StrictModeConfig {
             search_max_hnsw_ef,
             search_allow_exact,
             search_max_oversampling,
+            max_distinct_values,
+            max_filter_conditions,
+            require_filter,
+            max_result_points,
+            truncate_result_points,
+            max_payload_bytes,
         } = self;
 
         enabled.hash(state);
@@ -146,6 +189,12 @@ impl Hash for StrictModeConfig {
         search_max_hnsw_ef.hash(state);
         search_allow_exact.hash(state);
         search_max_oversampling.map(|i| i.to_le_bytes()).hash(state);
+        max_distinct_values.hash(state);
+        max_filter_conditions.hash(state);
+        require_filter.hash(state);
+        max_result_points.hash(state);
+        truncate_result_points.hash(state);
+        max_payload_bytes.hash(state);
     }
 }
 
@@ -162,6 +211,12 @@ impl PartialEq for StrictModeConfig {
             search_max_hnsw_ef,
             search_allow_exact,
             search_max_oversampling,
+            max_distinct_values,
+            max_filter_conditions,
+            require_filter,
+            max_result_points,
+            truncate_result_points,
+            max_payload_bytes,
         } = self;
 
         *enabled == other.enabled
@@ -173,6 +228,12 @@ impl PartialEq for StrictModeConfig {
             && *search_allow_exact == other.search_allow_exact
             && search_max_oversampling.map(|i| i.to_le_bytes())
                 == other.search_max_oversampling.map(|i| i.to_le_bytes())
+            && *max_distinct_values == other.max_distinct_values
+            && *max_filter_conditions == other.max_filter_conditions
+            && *require_filter == other.require_filter
+            && *max_result_points == other.max_result_points
+            && *truncate_result_points == other.truncate_result_points
+            && *max_payload_bytes == other.max_payload_bytes
     }
 }
 
@@ -185,12 +246,18 @@ pub struct WalConfigDiff {
     pub wal_capacity_mb: Option<usize>,
     /// Number of WAL segments to create ahead of actually used ones
     pub wal_segments_ahead: Option<usize>,
+    /// When to fsync written operations to disk
+    pub fsync_policy: Option<WalFsyncPolicy>,
+    /// Do not write operations to the WAL. Unsafe, recent operations are lost on a crash
+    pub wal_disabled: Option<bool>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Merge, PartialEq, Eq, Hash)]
 pub struct CollectionParamsDiff {
     /// Number of replicas for each shard
     pub replication_factor: Option<NonZeroU32>,
+    /// Number of replicas for each shard of specific shard keys, replaces all current overrides
+    pub shard_key_replication_factors: Option<Vec<ShardKeyReplicationFactor>>,
     /// Minimal number successful responses from replicas to consider operation successful
     pub write_consistency_factor: Option<NonZeroU32>,
     /// Fan-out every read request to these many additional remote nodes (and return first available response)
@@ -246,6 +313,9 @@ pub struct OptimizersConfigDiff {
     /// Note: 1kB = 1 vector of size 256.
     #[serde(alias = "indexing_threshold_kb")]
     pub indexing_threshold: Option<usize>,
+    /// Minimal number of vectors in a segment, required to build a vector index for it.
+    /// Smaller segments keep the plain index, regardless of `indexing_threshold` and `memmap_threshold`.
+    pub indexing_min_vector_number: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: Option<u64>,
     /// Max number of threads (jobs) for running optimizations per shard.
@@ -263,6 +333,7 @@ impl std::hash::Hash for OptimizersConfigDiff {
         self.max_segment_size.hash(state);
         self.memmap_threshold.hash(state);
         self.indexing_threshold.hash(state);
+        self.indexing_min_vector_number.hash(state);
         self.flush_interval_sec.hash(state);
         self.max_optimization_threads.hash(state);
     }
@@ -277,6 +348,7 @@ impl PartialEq for OptimizersConfigDiff {
             && self.max_segment_size == other.max_segment_size
             && self.memmap_threshold == other.memmap_threshold
             && self.indexing_threshold == other.indexing_threshold
+            && self.indexing_min_vector_number == other.indexing_min_vector_number
             && self.flush_interval_sec == other.flush_interval_sec
             && self.max_optimization_threads == other.max_optimization_threads
     }
@@ -436,6 +508,7 @@ mod tests {
 
         let diff = CollectionParamsDiff {
             replication_factor: None,
+            shard_key_replication_factors: None,
             write_consistency_factor: Some(NonZeroU32::new(2).unwrap()),
             read_fan_out_factor: None,
             on_disk_payload: None,
@@ -456,6 +529,20 @@ mod tests {
         assert_eq!(new_config.m, 32)
     }
 
+    #[test]
+    fn test_hnsw_huge_values_rejected() {
+        let update: HnswConfigDiff =
+            serde_json::from_str(r#"{ "ef_construct": 10000000 }"#).unwrap();
+        assert!(update.validate().is_err());
+
+        let update: HnswConfigDiff = serde_json::from_str(r#"{ "m": 100000 }"#).unwrap();
+        assert!(update.validate().is_err());
+
+        let update: HnswConfigDiff =
+            serde_json::from_str(r#"{ "m": 64, "ef_construct": 512 }"#).unwrap();
+        assert!(update.validate().is_ok());
+    }
+
     #[test]
     fn test_optimizer_update() {
         let base_config = OptimizersConfig {
@@ -465,6 +552,7 @@ mod tests {
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(50_000),
+            indexing_min_vector_number: None,
             flush_interval_sec: 30,
             max_optimization_threads: Some(1),
         };
@@ -145,6 +145,8 @@ where
 /// * `quorum` - send requests to all nodes and return points which present on majority of nodes
 ///
 /// * `all` - send requests to all nodes and return points which present on all nodes
+///
+/// * `local_only` - send request to the local replica only, fail if it is not active
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ReadConsistencyType {
@@ -154,6 +156,8 @@ pub enum ReadConsistencyType {
     Quorum,
     // send requests to all nodes and return points which present on all nodes
     All,
+    // send request to the local replica only, fail if it is not active
+    LocalOnly,
 }
 
 impl TryFrom<i32> for ReadConsistencyType {
@@ -176,6 +180,7 @@ impl From<ReadConsistencyTypeGrpc> for ReadConsistencyType {
             ReadConsistencyTypeGrpc::Majority => Self::Majority,
             ReadConsistencyTypeGrpc::Quorum => Self::Quorum,
             ReadConsistencyTypeGrpc::All => Self::All,
+            ReadConsistencyTypeGrpc::LocalOnly => Self::LocalOnly,
         }
     }
 }
@@ -192,6 +197,7 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
             ReadConsistencyType::Majority => ReadConsistencyTypeGrpc::Majority,
             ReadConsistencyType::Quorum => ReadConsistencyTypeGrpc::Quorum,
             ReadConsistencyType::All => ReadConsistencyTypeGrpc::All,
+            ReadConsistencyType::LocalOnly => ReadConsistencyTypeGrpc::LocalOnly,
         }
     }
 }
@@ -239,6 +245,13 @@ mod tests {
         let consistency: ReadConsistency = serde_json::from_str(json).unwrap();
         assert_eq!(consistency, ReadConsistency::Type(ReadConsistencyType::All));
 
+        let json = "\"local_only\"";
+        let consistency: ReadConsistency = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            consistency,
+            ReadConsistency::Type(ReadConsistencyType::LocalOnly)
+        );
+
         let schema = schema_for!(ReadConsistency);
         let schema_str = serde_json::to_string_pretty(&schema).unwrap();
         println!("{schema_str}")
@@ -18,7 +18,7 @@ use itertools::Itertools;
 use segment::data_types::vectors::{
     BatchVectorStructInternal, NamedQuery, Vector, VectorStructInternal,
 };
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
+use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint, ShardKey};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
@@ -33,8 +33,9 @@ use super::types::{
 };
 use crate::config::{
     default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    CollectionParams, ShardKeyReplicationFactor, ShardingMethod, WalConfig, WalFsyncPolicy,
 };
+use crate::hash_ring::HashRingState;
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
 use crate::operations::cluster_ops::{
@@ -47,17 +48,20 @@ use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     WalConfigDiff,
 };
+use crate::operations::payload_schema::{PayloadSchemaConfig, PayloadSchemaEnforcement};
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, FilterSelector, PointIdsList, PointStruct, PointVersion, PointsSelector, UpsertMode,
+    WriteOrdering,
 };
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    LocalShardInfo, OptimizerError, OptimizersStatus, RecommendRequestInternal, Record,
+    RemoteShardInfo, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
 };
+use crate::operations::vector_dedup::VectorDedup;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionCoreSearchRequest;
 use crate::shards::replica_set::ReplicaState;
@@ -115,6 +119,72 @@ pub fn write_ordering_from_proto(
     })
 }
 
+pub fn upsert_mode_to_proto(mode: UpsertMode) -> i32 {
+    match mode {
+        UpsertMode::Upsert => api::grpc::qdrant::UpsertMode::Upsert as i32,
+        UpsertMode::InsertIfAbsent => api::grpc::qdrant::UpsertMode::InsertIfAbsent as i32,
+    }
+}
+
+pub fn vector_dedup_to_proto(dedup: VectorDedup) -> i32 {
+    match dedup {
+        VectorDedup::Skip => api::grpc::qdrant::VectorDedup::Skip as i32,
+        VectorDedup::Merge => api::grpc::qdrant::VectorDedup::Merge as i32,
+    }
+}
+
+pub fn vector_dedup_from_proto(dedup: Option<i32>) -> Result<Option<VectorDedup>, Status> {
+    let Some(dedup) = dedup else {
+        return Ok(None);
+    };
+
+    match api::grpc::qdrant::VectorDedup::try_from(dedup) {
+        Ok(api::grpc::qdrant::VectorDedup::Skip) => Ok(Some(VectorDedup::Skip)),
+        Ok(api::grpc::qdrant::VectorDedup::Merge) => Ok(Some(VectorDedup::Merge)),
+        Err(_) => Err(Status::invalid_argument(format!(
+            "cannot convert vector dedup: {dedup}"
+        ))),
+    }
+}
+
+pub fn upsert_mode_from_proto(mode: Option<i32>) -> Result<UpsertMode, Status> {
+    let Some(mode) = mode else {
+        return Ok(UpsertMode::default());
+    };
+
+    match api::grpc::qdrant::UpsertMode::try_from(mode) {
+        Ok(api::grpc::qdrant::UpsertMode::Upsert) => Ok(UpsertMode::Upsert),
+        Ok(api::grpc::qdrant::UpsertMode::InsertIfAbsent) => Ok(UpsertMode::InsertIfAbsent),
+        Err(_) => Err(Status::invalid_argument(format!(
+            "cannot convert upsert mode: {mode}"
+        ))),
+    }
+}
+
+impl From<PointVersion> for api::grpc::qdrant::PointVersion {
+    fn from(value: PointVersion) -> Self {
+        let PointVersion { id, version } = value;
+        Self {
+            id: Some(id.into()),
+            version,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PointVersion> for PointVersion {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointVersion) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PointVersion { id, version } = value;
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("expected version has no point id"))?
+                .try_into()?,
+            version,
+        })
+    }
+}
+
 pub fn try_record_from_grpc(
     point: api::grpc::qdrant::RetrievedPoint,
     with_payload: bool,
@@ -144,6 +214,7 @@ pub fn try_record_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
         order_value,
+        version: point.version,
     })
 }
 
@@ -236,6 +307,7 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            use_heuristic: value.use_heuristic,
         }
     }
 }
@@ -249,16 +321,68 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            use_heuristic: value.use_heuristic,
         }
     }
 }
 
-impl From<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
-    fn from(value: api::grpc::qdrant::WalConfigDiff) -> Self {
+impl From<WalFsyncPolicy> for api::grpc::qdrant::WalFsyncPolicy {
+    fn from(value: WalFsyncPolicy) -> Self {
+        let (policy_type, interval_ms) = match value {
+            WalFsyncPolicy::EveryOp => (api::grpc::qdrant::WalFsyncPolicyType::EveryOp, None),
+            WalFsyncPolicy::Interval(interval_ms) => (
+                api::grpc::qdrant::WalFsyncPolicyType::Interval,
+                Some(interval_ms),
+            ),
+            WalFsyncPolicy::OnFlush => (api::grpc::qdrant::WalFsyncPolicyType::OnFlush, None),
+        };
+
         Self {
+            r#type: policy_type as i32,
+            interval_ms,
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WalFsyncPolicy> for WalFsyncPolicy {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WalFsyncPolicy) -> Result<Self, Self::Error> {
+        use api::grpc::qdrant::WalFsyncPolicyType;
+
+        match (
+            WalFsyncPolicyType::try_from(value.r#type),
+            value.interval_ms,
+        ) {
+            (Ok(WalFsyncPolicyType::OnFlush), None) => Ok(WalFsyncPolicy::OnFlush),
+            (Ok(WalFsyncPolicyType::EveryOp), None) => Ok(WalFsyncPolicy::EveryOp),
+            (Ok(WalFsyncPolicyType::Interval), Some(interval_ms)) => {
+                Ok(WalFsyncPolicy::Interval(interval_ms))
+            }
+            (Ok(WalFsyncPolicyType::Interval), None) => Err(Status::invalid_argument(
+                "`interval_ms` is required for the Interval fsync policy",
+            )),
+            (Ok(_), Some(_)) => Err(Status::invalid_argument(
+                "`interval_ms` is only allowed for the Interval fsync policy",
+            )),
+            (Err(_), _) => Err(Status::invalid_argument(format!(
+                "Cannot convert WAL fsync policy: {}",
+                value.r#type,
+            ))),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::WalConfigDiff> for WalConfigDiff {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::WalConfigDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
             wal_capacity_mb: value.wal_capacity_mb.map(|v| v as usize),
             wal_segments_ahead: value.wal_segments_ahead.map(|v| v as usize),
-        }
+            fsync_policy: value.fsync_policy.map(TryInto::try_into).transpose()?,
+            wal_disabled: None,
+        })
     }
 }
 
@@ -284,6 +408,45 @@ impl TryFrom<api::grpc::qdrant::CollectionParamsDiff> for CollectionParamsDiff {
                 .transpose()?,
             read_fan_out_factor: value.read_fan_out_factor,
             on_disk_payload: value.on_disk_payload,
+            shard_key_replication_factors: value
+                .shard_key_replication_factors
+                .map(|factors| {
+                    factors
+                        .factors
+                        .into_iter()
+                        .map(ShardKeyReplicationFactor::try_from)
+                        .collect::<Result<_, _>>()
+                })
+                .transpose()?,
+        })
+    }
+}
+
+impl From<ShardKeyReplicationFactor> for api::grpc::qdrant::ShardKeyReplicationFactor {
+    fn from(value: ShardKeyReplicationFactor) -> Self {
+        let ShardKeyReplicationFactor {
+            shard_key,
+            replication_factor,
+        } = value;
+
+        Self {
+            shard_key: Some(convert_shard_key_to_grpc(shard_key)),
+            replication_factor: replication_factor.get(),
+        }
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::ShardKeyReplicationFactor> for ShardKeyReplicationFactor {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ShardKeyReplicationFactor) -> Result<Self, Self::Error> {
+        Ok(Self {
+            shard_key: value
+                .shard_key
+                .and_then(convert_shard_key_from_grpc)
+                .ok_or_else(|| Status::invalid_argument("Shard key is not specified"))?,
+            replication_factor: NonZeroU32::new(value.replication_factor)
+                .ok_or_else(|| Status::invalid_argument("`replication_factor` cannot be 0"))?,
         })
     }
 }
@@ -297,6 +460,7 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             max_segment_size: value.max_segment_size.map(|v| v as usize),
             memmap_threshold: value.memmap_threshold.map(|v| v as usize),
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
+            indexing_min_vector_number: value.indexing_min_vector_number.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
         }
@@ -346,10 +510,19 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 OptimizersStatus::Ok => api::grpc::qdrant::OptimizerStatus {
                     ok: true,
                     error: "".to_string(),
+                    optimizer: None,
+                    segment_ids: vec![],
+                },
+                OptimizersStatus::Error(OptimizerError {
+                    error,
+                    optimizer,
+                    segment_ids,
+                }) => api::grpc::qdrant::OptimizerStatus {
+                    ok: false,
+                    error,
+                    optimizer,
+                    segment_ids: segment_ids.into_iter().map(|id| id as u64).collect(),
                 },
-                OptimizersStatus::Error(error) => {
-                    api::grpc::qdrant::OptimizerStatus { ok: false, error }
-                }
             }),
             vectors_count: vectors_count.map(|count| count as u64),
             indexed_vectors_count: indexed_vectors_count.map(|count| count as u64),
@@ -381,10 +554,17 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     },
                     shard_number: config.params.shard_number.get(),
                     replication_factor: Some(config.params.replication_factor.get()),
+                    shard_key_replication_factors: config
+                        .params
+                        .shard_key_replication_factors
+                        .into_iter()
+                        .map(From::from)
+                        .collect(),
                     on_disk_payload: config.params.on_disk_payload,
                     write_consistency_factor: Some(config.params.write_consistency_factor.get()),
                     read_fan_out_factor: config.params.read_fan_out_factor,
                     sharding_method: config.params.sharding_method.map(sharding_method_to_proto),
+                    auto_create_shard_keys: Some(config.params.auto_create_shard_keys),
                     sparse_vectors_config: config.params.sparse_vectors.map(|sparse_vectors| {
                         api::grpc::qdrant::SparseVectorConfig {
                             map: sparse_vectors
@@ -403,6 +583,7 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    use_heuristic: config.hnsw_config.use_heuristic,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -418,6 +599,10 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .optimizer_config
                         .indexing_threshold
                         .map(|x| x as u64),
+                    indexing_min_vector_number: config
+                        .optimizer_config
+                        .indexing_min_vector_number
+                        .map(|x| x as u64),
                     flush_interval_sec: Some(config.optimizer_config.flush_interval_sec),
                     max_optimization_threads: config
                         .optimizer_config
@@ -427,9 +612,14 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
                     wal_segments_ahead: Some(config.wal_config.wal_segments_ahead as u64),
+                    fsync_policy: Some(config.wal_config.fsync_policy.into()),
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
                 strict_mode_config: config.strict_mode_config.map(From::from),
+                default_timeout: config.default_timeout.map(|timeout| timeout as u64),
+                payload_schema: config.payload_schema.map(From::from),
+                default_search_params: config.default_search_params.map(From::from),
+                tie_break: config.tie_break.map(From::from),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -450,6 +640,12 @@ impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
             search_max_hnsw_ef: value.search_max_hnsw_ef.map(|i| i as u32),
             search_allow_exact: value.search_allow_exact,
             search_max_oversampling: value.search_max_oversampling.map(|i| i as f32),
+            max_distinct_values: value.max_distinct_values.map(|i| i as u32),
+            max_filter_conditions: value.max_filter_conditions.map(|i| i as u32),
+            require_filter: value.require_filter,
+            max_result_points: value.max_result_points.map(|i| i as u32),
+            truncate_result_points: value.truncate_result_points,
+            max_payload_bytes: value.max_payload_bytes.map(|i| i as u32),
         }
     }
 }
@@ -464,6 +660,7 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             vectors: vectors.map(api::grpc::qdrant::Vectors::from),
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
             order_value: record.order_value.map(From::from),
+            version: record.version,
         }
     }
 }
@@ -493,6 +690,9 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_segment_size: optimizer_config.max_segment_size.map(|x| x as usize),
             memmap_threshold: optimizer_config.memmap_threshold.map(|x| x as usize),
             indexing_threshold: optimizer_config.indexing_threshold.map(|x| x as usize),
+            indexing_min_vector_number: optimizer_config
+                .indexing_min_vector_number
+                .map(|x| x as usize),
             flush_interval_sec: optimizer_config.flush_interval_sec.unwrap_or_default(),
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
@@ -501,12 +701,20 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
     }
 }
 
-impl From<api::grpc::qdrant::WalConfigDiff> for WalConfig {
-    fn from(wal_config: api::grpc::qdrant::WalConfigDiff) -> Self {
-        Self {
+impl TryFrom<api::grpc::qdrant::WalConfigDiff> for WalConfig {
+    type Error = Status;
+
+    fn try_from(wal_config: api::grpc::qdrant::WalConfigDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
             wal_capacity_mb: wal_config.wal_capacity_mb.unwrap_or_default() as usize,
             wal_segments_ahead: wal_config.wal_segments_ahead.unwrap_or_default() as usize,
-        }
+            fsync_policy: wal_config
+                .fsync_policy
+                .map(TryInto::try_into)
+                .transpose()?
+                .unwrap_or_default(),
+            wal_disabled: false,
+        })
     }
 }
 
@@ -742,6 +950,11 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                     .ok_or_else(|| {
                         Status::invalid_argument("`replication_factor` cannot be zero")
                     })?,
+                    shard_key_replication_factors: params
+                        .shard_key_replication_factors
+                        .into_iter()
+                        .map(ShardKeyReplicationFactor::try_from)
+                        .collect::<Result<_, _>>()?,
                     write_consistency_factor: NonZeroU32::new(
                         params
                             .write_consistency_factor
@@ -756,6 +969,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    auto_create_shard_keys: params.auto_create_shard_keys.unwrap_or_default(),
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -768,7 +982,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
             },
             wal_config: match config.wal_config {
                 None => return Err(Status::invalid_argument("Malformed WalConfig type")),
-                Some(wal_config) => wal_config.into(),
+                Some(wal_config) => wal_config.try_into()?,
             },
             quantization_config: {
                 if let Some(config) = config.quantization_config {
@@ -778,6 +992,13 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                 }
             },
             strict_mode_config: config.strict_mode_config.map(StrictModeConfig::from),
+            payload_schema: config
+                .payload_schema
+                .map(PayloadSchemaConfig::try_from)
+                .transpose()?,
+            default_search_params: config.default_search_params.map(From::from),
+            tie_break: config.tie_break.map(TryFrom::try_from).transpose()?,
+            default_timeout: config.default_timeout.map(|timeout| timeout as usize),
         })
     }
 }
@@ -794,11 +1015,20 @@ impl TryFrom<api::grpc::qdrant::GetCollectionInfoResponse> for CollectionInfo {
                 status: collection_info_response.status.try_into()?,
                 optimizer_status: match collection_info_response.optimizer_status {
                     None => return Err(Status::invalid_argument("Malformed OptimizerStatus type")),
-                    Some(api::grpc::qdrant::OptimizerStatus { ok, error }) => {
+                    Some(api::grpc::qdrant::OptimizerStatus {
+                        ok,
+                        error,
+                        optimizer,
+                        segment_ids,
+                    }) => {
                         if ok {
                             OptimizersStatus::Ok
                         } else {
-                            OptimizersStatus::Error(error)
+                            OptimizersStatus::Error(OptimizerError {
+                                error,
+                                optimizer,
+                                segment_ids: segment_ids.into_iter().map(|id| id as _).collect(),
+                            })
                         }
                     }
                 },
@@ -839,10 +1069,80 @@ impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
             search_max_hnsw_ef: value.search_max_hnsw_ef.map(|i| i as usize),
             search_allow_exact: value.search_allow_exact,
             search_max_oversampling: value.search_max_oversampling.map(f64::from),
+            max_distinct_values: value.max_distinct_values.map(|i| i as usize),
+            max_filter_conditions: value.max_filter_conditions.map(|i| i as usize),
+            require_filter: value.require_filter,
+            max_result_points: value.max_result_points.map(|i| i as usize),
+            truncate_result_points: value.truncate_result_points,
+            max_payload_bytes: value.max_payload_bytes.map(|i| i as usize),
+        }
+    }
+}
+
+impl From<PayloadSchemaConfig> for api::grpc::qdrant::PayloadSchemaConfig {
+    fn from(value: PayloadSchemaConfig) -> Self {
+        let PayloadSchemaConfig {
+            fields,
+            enforcement,
+        } = value;
+        let enforcement = match enforcement {
+            PayloadSchemaEnforcement::Off => api::grpc::qdrant::PayloadSchemaEnforcement::Off,
+            PayloadSchemaEnforcement::Warn => api::grpc::qdrant::PayloadSchemaEnforcement::Warn,
+            PayloadSchemaEnforcement::Strict => api::grpc::qdrant::PayloadSchemaEnforcement::Strict,
+        };
+        Self {
+            fields: fields
+                .into_iter()
+                .map(|(key, schema_type)| {
+                    let schema_type = api::grpc::qdrant::PayloadSchemaType::from(schema_type);
+                    (key, schema_type as i32)
+                })
+                .collect(),
+            enforcement: enforcement as i32,
         }
     }
 }
 
+impl TryFrom<api::grpc::qdrant::PayloadSchemaConfig> for PayloadSchemaConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PayloadSchemaConfig) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PayloadSchemaConfig {
+            fields,
+            enforcement,
+        } = value;
+        let fields = fields
+            .into_iter()
+            .map(|(key, schema_type)| {
+                let schema_type = api::grpc::qdrant::PayloadSchemaType::try_from(schema_type)
+                    .map_err(|_| {
+                        Status::invalid_argument(format!(
+                            "Unknown payload schema type of key `{key}`: {schema_type}",
+                        ))
+                    })?
+                    .try_into()?;
+                Ok((key, schema_type))
+            })
+            .collect::<Result<_, Status>>()?;
+        let enforcement = match api::grpc::qdrant::PayloadSchemaEnforcement::try_from(enforcement) {
+            Ok(api::grpc::qdrant::PayloadSchemaEnforcement::Off) => PayloadSchemaEnforcement::Off,
+            Ok(api::grpc::qdrant::PayloadSchemaEnforcement::Warn) => PayloadSchemaEnforcement::Warn,
+            Ok(api::grpc::qdrant::PayloadSchemaEnforcement::Strict) => {
+                PayloadSchemaEnforcement::Strict
+            }
+            Err(_) => {
+                return Err(Status::invalid_argument(format!(
+                    "Unknown payload schema enforcement: {enforcement}",
+                )))
+            }
+        };
+        Ok(Self {
+            fields,
+            enforcement,
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
     type Error = Status;
 
@@ -959,6 +1259,7 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
             operation_id: res.operation_id,
             status: res.status.into(),
             clock_tag: res.clock_tag.map(Into::into),
+            deduplicated: res.deduplicated.map(|count| count as u64),
         }
     }
 }
@@ -977,6 +1278,7 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            deduplicated: res.deduplicated.map(|count| count as usize),
         };
 
         Ok(res)
@@ -1656,6 +1958,7 @@ impl TryFrom<api::grpc::qdrant::ReplicateShard> for ReplicateShard {
             from_peer_id: value.from_peer_id,
             to_peer_id: value.to_peer_id,
             method,
+            filter: None,
         })
     }
 }
@@ -1841,3 +2144,168 @@ impl TryFrom<api::grpc::qdrant::SparseVectorConfig> for SparseVectorsConfig {
             .map(SparseVectorsConfig)
     }
 }
+
+pub fn hash_ring_state_to_grpc(
+    shard_key: Option<ShardKey>,
+    state: HashRingState,
+) -> api::grpc::qdrant::HashRingState {
+    let shard_key = shard_key.map(convert_shard_key_to_grpc);
+    match state {
+        HashRingState::Single { shards } => api::grpc::qdrant::HashRingState {
+            shard_key,
+            resharding: false,
+            shards,
+            new_shards: vec![],
+        },
+        HashRingState::Resharding { old, new } => api::grpc::qdrant::HashRingState {
+            shard_key,
+            resharding: true,
+            shards: old,
+            new_shards: new,
+        },
+    }
+}
+
+pub fn hash_ring_state_from_grpc(
+    value: api::grpc::qdrant::HashRingState,
+) -> (Option<ShardKey>, HashRingState) {
+    let api::grpc::qdrant::HashRingState {
+        shard_key,
+        resharding,
+        shards,
+        new_shards,
+    } = value;
+
+    let state = if resharding {
+        HashRingState::Resharding {
+            old: shards,
+            new: new_shards,
+        }
+    } else {
+        HashRingState::Single { shards }
+    };
+
+    (convert_shard_key_from_grpc_opt(shard_key), state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimizer_error_detail_conversion() {
+        use crate::tests::fixtures::create_collection_config;
+
+        let optimizer_error = OptimizerError::new("failed to build HNSW index")
+            .with_optimizer("indexing", vec![3, 7]);
+        let info = CollectionInfo {
+            status: CollectionStatus::Red,
+            optimizer_status: OptimizersStatus::Error(optimizer_error.clone()),
+            ..CollectionInfo::empty(create_collection_config())
+        };
+
+        let grpc_info = api::grpc::qdrant::CollectionInfo::from(info);
+        let grpc_status = grpc_info.optimizer_status.clone().unwrap();
+        assert!(!grpc_status.ok);
+        assert_eq!(grpc_status.error, "failed to build HNSW index");
+        assert_eq!(grpc_status.optimizer.as_deref(), Some("indexing"));
+        assert_eq!(grpc_status.segment_ids, vec![3, 7]);
+
+        let response = api::grpc::qdrant::GetCollectionInfoResponse {
+            result: Some(grpc_info),
+            time: 0.0,
+        };
+        let info = CollectionInfo::try_from(response).unwrap();
+        assert_eq!(
+            info.optimizer_status,
+            OptimizersStatus::Error(optimizer_error)
+        );
+    }
+
+    #[test]
+    fn test_wal_fsync_policy_conversion() {
+        for policy in [
+            WalFsyncPolicy::EveryOp,
+            WalFsyncPolicy::Interval(100),
+            WalFsyncPolicy::OnFlush,
+        ] {
+            let grpc_policy = api::grpc::qdrant::WalFsyncPolicy::from(policy);
+            assert_eq!(WalFsyncPolicy::try_from(grpc_policy).unwrap(), policy);
+        }
+
+        // The interval is required for the interval policy only
+        let invalid_policies = [
+            (api::grpc::qdrant::WalFsyncPolicyType::Interval, None),
+            (api::grpc::qdrant::WalFsyncPolicyType::EveryOp, Some(100)),
+        ];
+        for (policy_type, interval_ms) in invalid_policies {
+            let grpc_policy = api::grpc::qdrant::WalFsyncPolicy {
+                r#type: policy_type as i32,
+                interval_ms,
+            };
+            assert!(WalFsyncPolicy::try_from(grpc_policy).is_err());
+        }
+
+        // Not specified policy is not changed by a diff
+        let diff = WalConfigDiff::try_from(api::grpc::qdrant::WalConfigDiff::default()).unwrap();
+        assert_eq!(diff.fsync_policy, None);
+    }
+
+    #[test]
+    fn test_collection_config_defaults_conversion() {
+        use segment::data_types::order_by::Direction;
+        use segment::json_path::JsonPath;
+        use segment::types::SearchParams;
+        use segment::utils::scored_point_ties::{PayloadTieBreak, TieBreak};
+
+        use crate::tests::fixtures::create_collection_config;
+
+        let mut config = create_collection_config();
+        config.default_search_params = Some(SearchParams {
+            hnsw_ef: Some(256),
+            exact: Some(false),
+            indexed_only: Some(true),
+            ..Default::default()
+        });
+        config.tie_break = Some(TieBreak::Payload(PayloadTieBreak {
+            key: JsonPath::new("rank"),
+            direction: Direction::Desc,
+        }));
+
+        let grpc_info =
+            api::grpc::qdrant::CollectionInfo::from(CollectionInfo::empty(config.clone()));
+        let converted = CollectionConfig::try_from(grpc_info.config.unwrap()).unwrap();
+        assert_eq!(
+            converted.default_search_params,
+            config.default_search_params
+        );
+        assert_eq!(converted.tie_break, config.tie_break);
+    }
+
+    #[test]
+    fn test_payload_schema_conversion() {
+        use segment::types::PayloadSchemaType;
+
+        let payload_schema = PayloadSchemaConfig {
+            fields: [
+                ("city".to_string(), PayloadSchemaType::Keyword),
+                ("population".to_string(), PayloadSchemaType::Integer),
+                ("founded".to_string(), PayloadSchemaType::Datetime),
+            ]
+            .into(),
+            enforcement: PayloadSchemaEnforcement::Warn,
+        };
+        let grpc_payload_schema =
+            api::grpc::qdrant::PayloadSchemaConfig::from(payload_schema.clone());
+        assert_eq!(
+            PayloadSchemaConfig::try_from(grpc_payload_schema).unwrap(),
+            payload_schema,
+        );
+
+        let grpc_payload_schema = api::grpc::qdrant::PayloadSchemaConfig {
+            fields: [("city".to_string(), 100)].into(),
+            enforcement: api::grpc::qdrant::PayloadSchemaEnforcement::Strict as i32,
+        };
+        assert!(PayloadSchemaConfig::try_from(grpc_payload_schema).is_err());
+    }
+}
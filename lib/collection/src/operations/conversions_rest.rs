@@ -10,6 +10,7 @@ impl From<Record> for api::rest::Record {
             vector: value.vector.map(api::rest::VectorStruct::from),
             shard_key: value.shard_key,
             order_value: value.order_value,
+            version: value.version,
         }
     }
 }
@@ -22,6 +23,7 @@ impl From<api::rest::Record> for Record {
             vector: value.vector.map(VectorStructInternal::from),
             shard_key: value.shard_key,
             order_value: value.order_value,
+            version: value.version,
         }
     }
 }
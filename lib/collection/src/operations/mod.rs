@@ -5,6 +5,7 @@ pub mod conversions;
 pub mod conversions_rest;
 pub mod operation_effect;
 pub mod payload_ops;
+pub mod payload_schema;
 pub mod point_ops;
 pub mod query_enum;
 pub mod shard_selector_internal;
@@ -14,6 +15,7 @@ pub mod snapshot_storage_ops;
 pub mod types;
 pub mod universal_query;
 pub mod validation;
+pub mod vector_dedup;
 pub mod vector_ops;
 pub mod vector_params_builder;
 pub mod verification;
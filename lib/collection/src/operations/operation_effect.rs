@@ -45,9 +45,16 @@ impl EstimateOperationEffectArea for CollectionUpdateOperations {
 impl EstimateOperationEffectArea for point_ops::PointOperations {
     fn estimate_effect_area(&self) -> OperationEffectArea {
         match self {
-            point_ops::PointOperations::UpsertPoints(insert_operations) => {
+            point_ops::PointOperations::UpsertPoints(insert_operations)
+            | point_ops::PointOperations::InsertPointsIfAbsent(insert_operations) => {
                 insert_operations.estimate_effect_area()
             }
+            point_ops::PointOperations::UpsertPointsIfVersion(conditional_upsert) => {
+                conditional_upsert.points.estimate_effect_area()
+            }
+            point_ops::PointOperations::UpsertPointsDedup(dedup_upsert) => {
+                dedup_upsert.points.estimate_effect_area()
+            }
             point_ops::PointOperations::DeletePoints { ids } => {
                 OperationEffectArea::Points(ids.clone())
             }
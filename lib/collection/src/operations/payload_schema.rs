@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use schemars::JsonSchema;
+use segment::types::{DateTimePayloadType, Payload, PayloadSchemaType, PointIdType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::operations::payload_ops::{PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::{
+    ConditionalUpsert, DedupUpsert, PointInsertOperationsInternal, PointOperations,
+};
+use crate::operations::CollectionUpdateOperations;
+
+/// How payloads violating the payload schema of a collection are handled
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadSchemaEnforcement {
+    /// Payloads are not checked
+    #[default]
+    Off,
+    /// Violations are logged, but payloads are stored
+    Warn,
+    /// Updates with violating payloads are rejected
+    Strict,
+}
+
+/// Declared payload schema of a collection
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadSchemaConfig {
+    /// Allowed top level payload keys and the type of their values.
+    /// Arrays of values of the declared type and `null` are accepted too.
+    pub fields: BTreeMap<String, PayloadSchemaType>,
+    /// How to handle payloads not matching the schema. Default: off
+    #[serde(default)]
+    pub enforcement: PayloadSchemaEnforcement,
+}
+
+/// Payload of an update operation not matching the payload schema
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadSchemaViolation {
+    /// Point with the violating payload, `None` if the payload is assigned to many points
+    pub point_id: Option<PointIdType>,
+    pub description: String,
+}
+
+impl PayloadSchemaConfig {
+    /// Check a payload against the schema, returns a description of the first violation.
+    pub fn check_payload(&self, payload: &Payload) -> Result<(), String> {
+        for (key, value) in payload.0.iter() {
+            self.check_key(key)?;
+            let expected = self.fields[key];
+            if !value_matches(expected, value) {
+                return Err(format!(
+                    "value of payload key `{key}` is not of type {}",
+                    expected.name(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_key(&self, key: &str) -> Result<(), String> {
+        if self.fields.contains_key(key) {
+            Ok(())
+        } else {
+            Err(format!("payload key `{key}` is not declared in schema"))
+        }
+    }
+
+    fn check_set_payload(&self, op: &SetPayloadOp) -> Result<(), String> {
+        match &op.key {
+            // Nested values are only checked to be under a declared key
+            Some(key) => self.check_key(&key.first_key),
+            None => self.check_payload(&op.payload),
+        }
+    }
+
+    /// Collect all payloads of an update operation which don't match the schema.
+    pub fn violations(
+        &self,
+        operation: &CollectionUpdateOperations,
+    ) -> Vec<PayloadSchemaViolation> {
+        let mut violations = Vec::new();
+        let mut check = |point_id: Option<PointIdType>, result: Result<(), String>| {
+            if let Err(description) = result {
+                violations.push(PayloadSchemaViolation {
+                    point_id,
+                    description,
+                });
+            }
+        };
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(insert)
+                | PointOperations::InsertPointsIfAbsent(insert)
+                | PointOperations::UpsertPointsIfVersion(ConditionalUpsert {
+                    points: insert, ..
+                })
+                | PointOperations::UpsertPointsDedup(DedupUpsert { points: insert, .. }),
+            ) => match insert {
+                PointInsertOperationsInternal::PointsBatch(batch) => {
+                    let payloads = batch.payloads.iter().flatten();
+                    for (point_id, payload) in batch.ids.iter().zip(payloads) {
+                        if let Some(payload) = payload {
+                            check(Some(*point_id), self.check_payload(payload));
+                        }
+                    }
+                }
+                PointInsertOperationsInternal::PointsList(points) => {
+                    for point in points {
+                        if let Some(payload) = &point.payload {
+                            check(Some(point.id), self.check_payload(payload));
+                        }
+                    }
+                }
+            },
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(sync)) => {
+                for point in &sync.points {
+                    if let Some(payload) = &point.payload {
+                        check(Some(point.id), self.check_payload(payload));
+                    }
+                }
+            }
+            CollectionUpdateOperations::PayloadOperation(
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op),
+            ) => {
+                let point_id = match op.points.as_deref() {
+                    Some([point_id]) => Some(*point_id),
+                    _ => None,
+                };
+                check(point_id, self.check_set_payload(op));
+            }
+            CollectionUpdateOperations::PointOperation(_)
+            | CollectionUpdateOperations::VectorOperation(_)
+            | CollectionUpdateOperations::PayloadOperation(_)
+            | CollectionUpdateOperations::FieldIndexOperation(_) => {}
+        }
+
+        violations
+    }
+}
+
+fn value_matches(expected: PayloadSchemaType, value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Array(values) => values
+            .iter()
+            .all(|value| !value.is_array() && value_matches(expected, value)),
+        Value::Bool(_) => expected == PayloadSchemaType::Bool,
+        Value::Number(number) => match expected {
+            PayloadSchemaType::Integer => number.is_i64(),
+            PayloadSchemaType::Float => true,
+            _ => false,
+        },
+        Value::String(string) => match expected {
+            PayloadSchemaType::Keyword | PayloadSchemaType::Text => true,
+            PayloadSchemaType::Datetime => DateTimePayloadType::from_str(string).is_ok(),
+            PayloadSchemaType::Uuid => uuid::Uuid::parse_str(string).is_ok(),
+            _ => false,
+        },
+        Value::Object(object) => {
+            expected == PayloadSchemaType::Geo
+                && object.get("lat").and_then(Value::as_f64).is_some()
+                && object.get("lon").and_then(Value::as_f64).is_some()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> PayloadSchemaConfig {
+        PayloadSchemaConfig {
+            fields: BTreeMap::from([
+                ("city".to_string(), PayloadSchemaType::Keyword),
+                ("count".to_string(), PayloadSchemaType::Integer),
+                ("location".to_string(), PayloadSchemaType::Geo),
+                ("created".to_string(), PayloadSchemaType::Datetime),
+            ]),
+            enforcement: PayloadSchemaEnforcement::Strict,
+        }
+    }
+
+    fn payload(value: Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_check_payload() {
+        let schema = schema();
+
+        let conforming = payload(json!({
+            "city": ["Berlin", "London"],
+            "count": 3,
+            "location": {"lat": 52.52, "lon": 13.405},
+            "created": "2024-01-01T00:00:00Z",
+        }));
+        assert_eq!(schema.check_payload(&conforming), Ok(()));
+        assert_eq!(
+            schema.check_payload(&payload(json!({"count": null}))),
+            Ok(())
+        );
+
+        for non_conforming in [
+            json!({"country": "Germany"}),
+            json!({"count": 3.5}),
+            json!({"city": ["Berlin", 1]}),
+            json!({"location": {"lat": 52.52}}),
+            json!({"created": "yesterday"}),
+        ] {
+            assert!(schema.check_payload(&payload(non_conforming)).is_err());
+        }
+    }
+}
@@ -7,7 +7,8 @@ use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{MultiDenseVectorInternal, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::json_path::JsonPath;
+use segment::types::{Filter, Payload, PayloadContainer, PointIdType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
@@ -15,6 +16,7 @@ use validator::Validate;
 use super::{point_to_shards, split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRingRouter;
 use crate::operations::types::Record;
+use crate::operations::vector_dedup::VectorDedup;
 use crate::shards::shard::ShardId;
 
 /// Defines write ordering guarantees for collection operations
@@ -25,7 +27,7 @@ use crate::shards::shard::ShardId;
 ///
 /// * `strong` - Write operations go through the permanent leader, consistent, but may be unavailable if leader is down
 ///
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum WriteOrdering {
     #[default]
@@ -34,6 +36,31 @@ pub enum WriteOrdering {
     Strong,
 }
 
+/// Defines how to handle points which already exist in the collection
+///
+/// * `upsert` - insert new points and overwrite existing ones, default
+///
+/// * `insert_if_absent` - insert new points only, points which already exist are left untouched
+///
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpsertMode {
+    #[default]
+    Upsert,
+    InsertIfAbsent,
+}
+
+/// Version of a point, which an update expects it to have
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PointVersion {
+    /// Point id
+    pub id: PointIdType,
+    /// Expected version of the point, as stored in its payload under the version key.
+    /// Points, which don't exist or have no version, have version 0.
+    pub version: u64,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointStruct {
@@ -58,6 +85,7 @@ impl TryFrom<Record> for PointStruct {
             vector,
             shard_key: _,
             order_value: _,
+            version: _,
         } = record;
 
         if vector.is_none() {
@@ -105,6 +133,20 @@ pub struct FilterSelector {
     pub shard_key: Option<ShardKeySelector>,
 }
 
+/// Delete points matching a filter in batches, in background
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+#[serde(rename_all = "snake_case")]
+pub struct StreamingDeleteRequest {
+    #[validate(nested)]
+    pub filter: Filter,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+    /// Number of points deleted at once. Default: 1000
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub batch_size: Option<usize>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged, rename_all = "snake_case")]
 pub enum PointsSelector {
@@ -138,6 +180,24 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// How to handle points which already exist, default is `upsert`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<UpsertMode>,
+    /// Apply the upsert only if these points still have the given versions.
+    /// If any of them was changed in the meantime, nothing is applied and a conflict is returned.
+    /// All upserted points must belong to a single shard. Versions are checked by the leader
+    /// replica of the shard, so the upsert is applied with at least `medium` write ordering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_versions: Option<Vec<PointVersion>>,
+    /// Payload key holding the versions of points, which are compared with `expected_versions`.
+    /// Required with `expected_versions`. Upserted points must have a greater version than expected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_key: Option<JsonPath>,
+    /// How to handle points with exactly the same vectors as a previous point of the upsert or a
+    /// point stored in the same shard.
+    /// Default: no deduplication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup: Option<VectorDedup>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -146,6 +206,24 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// How to handle points which already exist, default is `upsert`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<UpsertMode>,
+    /// Apply the upsert only if these points still have the given versions.
+    /// If any of them was changed in the meantime, nothing is applied and a conflict is returned.
+    /// All upserted points must belong to a single shard. Versions are checked by the leader
+    /// replica of the shard, so the upsert is applied with at least `medium` write ordering.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_versions: Option<Vec<PointVersion>>,
+    /// Payload key holding the versions of points, which are compared with `expected_versions`.
+    /// Required with `expected_versions`. Upserted points must have a greater version than expected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_key: Option<JsonPath>,
+    /// How to handle points with exactly the same vectors as a previous point of the upsert or a
+    /// point stored in the same shard.
+    /// Default: no deduplication
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dedup: Option<VectorDedup>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -196,6 +274,42 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
+    pub fn mode(&self) -> UpsertMode {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.mode,
+            PointInsertOperations::PointsList(list) => list.mode,
+        }
+        .unwrap_or_default()
+    }
+
+    pub fn expected_versions(&self) -> Option<&[PointVersion]> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.expected_versions.as_deref(),
+            PointInsertOperations::PointsList(list) => list.expected_versions.as_deref(),
+        }
+    }
+
+    pub fn version_key(&self) -> Option<&JsonPath> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.version_key.as_ref(),
+            PointInsertOperations::PointsList(list) => list.version_key.as_ref(),
+        }
+    }
+
+    pub fn dedup(&self) -> Option<VectorDedup> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.dedup,
+            PointInsertOperations::PointsList(list) => list.dedup,
+        }
+    }
+
+    pub fn shard_key(&self) -> Option<&ShardKeySelector> {
+        match self {
+            PointInsertOperations::PointsBatch(batch) => batch.shard_key.as_ref(),
+            PointInsertOperations::PointsList(list) => list.shard_key.as_ref(),
+        }
+    }
+
     pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
         match self {
             PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
@@ -216,6 +330,17 @@ pub enum PointInsertOperationsInternal {
     PointsList(Vec<PointStruct>),
 }
 
+impl PointInsertOperationsInternal {
+    pub fn point_ids(&self) -> Vec<PointIdType> {
+        match self {
+            PointInsertOperationsInternal::PointsBatch(batch) => batch.ids.clone(),
+            PointInsertOperationsInternal::PointsList(points) => {
+                points.iter().map(|point| point.id).collect()
+            }
+        }
+    }
+}
+
 impl Validate for PointInsertOperationsInternal {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -307,6 +432,10 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            mode: None,
+            expected_versions: None,
+            version_key: None,
+            dedup: None,
         })
     }
 }
@@ -316,6 +445,10 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            mode: None,
+            expected_versions: None,
+            version_key: None,
+            dedup: None,
         })
     }
 }
@@ -332,12 +465,146 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Upsert, which is applied only if the points still have the expected versions
+///
+/// Versions are managed by the client and stored in the payload of the points, under
+/// `version_key`. The leader replica of the shard checks them once, and sends a plain upsert to
+/// all replicas if they match. Points of the upsert must belong to a single shard.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct ConditionalUpsert {
+    #[validate(nested)]
+    pub points: PointInsertOperationsInternal,
+    pub expected_versions: Vec<PointVersion>,
+    pub version_key: JsonPath,
+}
+
+impl ConditionalUpsert {
+    /// Check that upserted points, which have an expected version, get a greater version
+    pub fn check_new_versions(&self) -> Result<(), String> {
+        let expected: HashMap<_, _> = self
+            .expected_versions
+            .iter()
+            .map(|expected| (expected.id, expected.version))
+            .collect();
+
+        let payloads: Vec<(PointIdType, Option<&Payload>)> = match &self.points {
+            PointInsertOperationsInternal::PointsBatch(batch) => match &batch.payloads {
+                Some(payloads) => batch
+                    .ids
+                    .iter()
+                    .copied()
+                    .zip(payloads.iter().map(Option::as_ref))
+                    .collect(),
+                None => batch.ids.iter().map(|id| (*id, None)).collect(),
+            },
+            PointInsertOperationsInternal::PointsList(points) => points
+                .iter()
+                .map(|point| (point.id, point.payload.as_ref()))
+                .collect(),
+        };
+
+        for (id, payload) in payloads {
+            let Some(&expected_version) = expected.get(&id) else {
+                continue;
+            };
+            let new_version =
+                payload.and_then(|payload| version_from_payload(payload, &self.version_key));
+            match new_version {
+                Some(new_version) if new_version > expected_version => {}
+                _ => {
+                    return Err(format!(
+                        "Point {id} must have a version greater than {expected_version} in `{}`",
+                        self.version_key,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Version of a point, stored in its payload under `version_key`
+pub fn version_from_payload(payload: &Payload, version_key: &JsonPath) -> Option<u64> {
+    payload
+        .get_value(version_key)
+        .iter()
+        .find_map(|value| value.as_u64())
+}
+
+impl SplitByShard for ConditionalUpsert {
+    fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
+        let ConditionalUpsert {
+            points,
+            expected_versions,
+            version_key,
+        } = self;
+
+        let mut versions_by_shard: HashMap<ShardId, Vec<PointVersion>> = HashMap::new();
+        for expected_version in &expected_versions {
+            for shard_id in point_to_shards(&expected_version.id, ring) {
+                versions_by_shard
+                    .entry(shard_id)
+                    .or_default()
+                    .push(expected_version.clone());
+            }
+        }
+
+        match points.split_by_shard(ring) {
+            OperationToShard::ByShard(by_shard) => {
+                OperationToShard::by_shard(by_shard.into_iter().map(|(shard_id, points)| {
+                    let expected_versions = versions_by_shard.remove(&shard_id).unwrap_or_default();
+                    (
+                        shard_id,
+                        ConditionalUpsert {
+                            points,
+                            expected_versions,
+                            version_key: version_key.clone(),
+                        },
+                    )
+                }))
+            }
+            OperationToShard::ToAll(points) => OperationToShard::to_all(ConditionalUpsert {
+                points,
+                expected_versions,
+                version_key,
+            }),
+        }
+    }
+}
+
+/// Upsert, which skips points with exactly the same vectors as a point stored in the shard
+///
+/// The leader replica of the shard looks up stored duplicates once, and sends a plain upsert of
+/// the remaining points to all replicas. With `merge` policy, payload of skipped points is then
+/// set on their stored duplicates.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct DedupUpsert {
+    #[validate(nested)]
+    pub points: PointInsertOperationsInternal,
+    pub policy: VectorDedup,
+}
+
+impl SplitByShard for DedupUpsert {
+    fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
+        let DedupUpsert { points, policy } = self;
+        points
+            .split_by_shard(ring)
+            .map(|points| DedupUpsert { points, policy })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert points, skipping those which already exist
+    InsertPointsIfAbsent(PointInsertOperationsInternal),
+    /// Insert or update points, if they still have the expected versions
+    UpsertPointsIfVersion(ConditionalUpsert),
+    /// Insert or update points, skipping those with the same vectors as a stored point
+    UpsertPointsDedup(DedupUpsert),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
     /// Delete points by given filter criteria
@@ -350,6 +617,9 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::InsertPointsIfAbsent(_) => true,
+            PointOperations::UpsertPointsIfVersion(_) => true,
+            PointOperations::UpsertPointsDedup(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
@@ -361,6 +631,11 @@ impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
+            PointOperations::InsertPointsIfAbsent(insert_points) => insert_points.validate(),
+            PointOperations::UpsertPointsIfVersion(conditional_upsert) => {
+                conditional_upsert.validate()
+            }
+            PointOperations::UpsertPointsDedup(dedup_upsert) => dedup_upsert.validate(),
             PointOperations::DeletePoints { ids: _ } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
@@ -541,6 +816,15 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
+            PointOperations::InsertPointsIfAbsent(insert_points) => insert_points
+                .split_by_shard(ring)
+                .map(PointOperations::InsertPointsIfAbsent),
+            PointOperations::UpsertPointsIfVersion(conditional_upsert) => conditional_upsert
+                .split_by_shard(ring)
+                .map(PointOperations::UpsertPointsIfVersion),
+            PointOperations::UpsertPointsDedup(dedup_upsert) => dedup_upsert
+                .split_by_shard(ring)
+                .map(PointOperations::UpsertPointsDedup),
             PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
                 .map(|ids| PointOperations::DeletePoints { ids }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
@@ -13,6 +13,8 @@ pub enum ShardSelectorInternal {
     ShardKey(ShardKey),
     /// Select multiple shard keys
     ShardKeys(Vec<ShardKey>),
+    /// Select one shard key, and reject filters reaching other tenants
+    TenantScope(ShardKey),
     /// ShardId
     ShardId(ShardId),
 }
@@ -21,6 +23,14 @@ impl ShardSelectorInternal {
     pub fn is_shard_id(&self) -> bool {
         matches!(self, ShardSelectorInternal::ShardId(_))
     }
+
+    /// Shard key of the tenant this request is isolated to, if any
+    pub fn tenant_scope(&self) -> Option<&ShardKey> {
+        match self {
+            ShardSelectorInternal::TenantScope(shard_key) => Some(shard_key),
+            _ => None,
+        }
+    }
 }
 
 impl From<Option<ShardKey>> for ShardSelectorInternal {
@@ -43,6 +53,9 @@ impl From<ShardKeySelector> for ShardSelectorInternal {
         match selector {
             ShardKeySelector::ShardKey(key) => ShardSelectorInternal::ShardKey(key),
             ShardKeySelector::ShardKeys(keys) => ShardSelectorInternal::ShardKeys(keys),
+            ShardKeySelector::TenantScope(scope) => {
+                ShardSelectorInternal::TenantScope(scope.tenant)
+            }
         }
     }
 }
@@ -2,6 +2,7 @@ use std::default;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::common::snapshots_manager::SnapShotsConfig;
 use crate::operations::types::NodeType;
 use crate::shards::transfer::ShardTransferMethod;
@@ -11,6 +12,7 @@ use crate::shards::transfer::ShardTransferMethod;
 const DEFAULT_SEARCH_TIMEOUT: Duration = Duration::from_secs(60);
 const DEFAULT_UPDATE_QUEUE_SIZE: usize = 100;
 const DEFAULT_UPDATE_QUEUE_SIZE_LISTENER: usize = 10_000;
+const DEFAULT_UPSERT_COALESCE_MAX_DELAY: Duration = Duration::from_millis(5);
 pub const DEFAULT_IO_SHARD_TRANSFER_LIMIT: Option<usize> = Some(1);
 pub const DEFAULT_SNAPSHOTS_PATH: &str = "./snapshots";
 
@@ -20,8 +22,22 @@ pub const DEFAULT_SNAPSHOTS_PATH: &str = "./snapshots";
 #[derive(Clone, Debug)]
 pub struct SharedStorageConfig {
     pub update_queue_size: usize,
+    /// Maximum number of update operations per shard accepted, but not yet applied.
+    /// If not defined - no limit is applied.
+    pub max_in_flight_updates: Option<usize>,
+    /// Maximum number of concurrent single point upserts coalesced into one operation.
+    /// If not defined - upserts are not coalesced.
+    pub upsert_coalesce_max_batch: Option<usize>,
+    /// Maximum time a single point upsert waits for others to be coalesced with.
+    pub upsert_coalesce_max_delay: Duration,
+    /// Maximum random delay before starting optimizations in a shard, to stagger optimizations
+    /// of many collections triggered at the same time. Disabled if zero.
+    pub optimizer_jitter: Duration,
     pub node_type: NodeType,
     pub handle_collection_load_errors: bool,
+    /// Quarantine WAL operations which can't be read or applied on load, instead of failing to
+    /// load the shard.
+    pub lenient_wal_recovery: bool,
     pub recovery_mode: Option<String>,
     pub search_timeout: Duration,
     pub update_concurrency: Option<NonZeroUsize>,
@@ -31,14 +47,27 @@ pub struct SharedStorageConfig {
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    /// Additional storage volumes to spread segments of local shards across.
+    pub segment_volumes: SegmentVolumes,
+    /// Fraction of search requests recorded for debugging, from 0 to 1.
+    pub search_log_sample_rate: f64,
+    /// Maximum number of points cached per local shard for retrieval by id, disabled if zero.
+    pub retrieve_cache_size: usize,
+    /// Maximum time to load a local shard on startup. If not defined - no limit is applied.
+    pub shard_load_timeout: Option<Duration>,
 }
 
 impl Default for SharedStorageConfig {
     fn default() -> Self {
         Self {
             update_queue_size: DEFAULT_UPDATE_QUEUE_SIZE,
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay: DEFAULT_UPSERT_COALESCE_MAX_DELAY,
+            optimizer_jitter: Duration::ZERO,
             node_type: Default::default(),
             handle_collection_load_errors: false,
+            lenient_wal_recovery: false,
             recovery_mode: None,
             search_timeout: DEFAULT_SEARCH_TIMEOUT,
             update_concurrency: None,
@@ -48,6 +77,10 @@ impl Default for SharedStorageConfig {
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            segment_volumes: SegmentVolumes::default(),
+            search_log_sample_rate: 0.0,
+            retrieve_cache_size: 0,
+            shard_load_timeout: None,
         }
     }
 }
@@ -56,8 +89,13 @@ impl SharedStorageConfig {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         update_queue_size: Option<usize>,
+        max_in_flight_updates: Option<usize>,
+        upsert_coalesce_max_batch: Option<usize>,
+        upsert_coalesce_max_delay: Option<Duration>,
+        optimizer_jitter: Option<Duration>,
         node_type: NodeType,
         handle_collection_load_errors: bool,
+        lenient_wal_recovery: bool,
         recovery_mode: Option<String>,
         search_timeout: Option<Duration>,
         update_concurrency: Option<NonZeroUsize>,
@@ -67,6 +105,10 @@ impl SharedStorageConfig {
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        segment_volumes: SegmentVolumes,
+        search_log_sample_rate: Option<f64>,
+        retrieve_cache_size: Option<usize>,
+        shard_load_timeout: Option<Duration>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -74,8 +116,14 @@ impl SharedStorageConfig {
         });
         Self {
             update_queue_size,
+            max_in_flight_updates,
+            upsert_coalesce_max_batch,
+            upsert_coalesce_max_delay: upsert_coalesce_max_delay
+                .unwrap_or(DEFAULT_UPSERT_COALESCE_MAX_DELAY),
+            optimizer_jitter: optimizer_jitter.unwrap_or_default(),
             node_type,
             handle_collection_load_errors,
+            lenient_wal_recovery,
             recovery_mode,
             search_timeout: search_timeout.unwrap_or(DEFAULT_SEARCH_TIMEOUT),
             update_concurrency,
@@ -85,6 +133,10 @@ impl SharedStorageConfig {
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            segment_volumes,
+            search_log_sample_rate: search_log_sample_rate.unwrap_or_default(),
+            retrieve_cache_size: retrieve_cache_size.unwrap_or_default(),
+            shard_load_timeout,
         }
     }
 }
@@ -5,6 +5,7 @@ use std::time::SystemTime;
 use api::grpc::conversions::naive_date_time_to_proto;
 use chrono::{DateTime, NaiveDateTime};
 use schemars::JsonSchema;
+use segment::json_path::JsonPath;
 use serde::{Deserialize, Serialize};
 use url::Url;
 use validator::Validate;
@@ -82,12 +83,21 @@ pub struct SnapshotRecover {
     pub api_key: Option<String>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Default)]
+pub struct CreateSnapshot {
+    /// Payload keys to remove from the points stored in the snapshot.
+    /// The collection itself is not modified. Every key must exist in the collection.
+    #[serde(default)]
+    pub exclude_payload_keys: Option<Vec<JsonPath>>,
+}
+
 fn snapshot_description_example() -> SnapshotDescription {
     SnapshotDescription {
         name: "my-collection-3766212330831337-2024-07-22-08-31-55.snapshot".to_string(),
         creation_time: Some(NaiveDateTime::from_str("2022-08-04T10:49:10").unwrap()),
         size: 1_000_000,
         checksum: Some("a1b2c3d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0".to_string()),
+        seq_number: None,
     }
 }
 
@@ -99,6 +109,10 @@ pub struct SnapshotDescription {
     pub size: u64,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub checksum: Option<String>,
+    /// WAL sequence number of the shard at the moment of snapshot creation.
+    /// Only present for shard snapshots.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seq_number: Option<u64>,
 }
 
 impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
@@ -108,6 +122,7 @@ impl From<SnapshotDescription> for api::grpc::qdrant::SnapshotDescription {
             creation_time: value.creation_time.map(naive_date_time_to_proto),
             size: value.size as i64,
             checksum: value.checksum,
+            seq_number: value.seq_number,
         }
     }
 }
@@ -127,12 +142,14 @@ pub async fn get_snapshot_description(path: &Path) -> CollectionResult<SnapshotD
     });
 
     let checksum = read_checksum_for_snapshot(path).await;
+    let seq_number = read_seq_number_for_snapshot(path).await;
     let size = file_meta.len();
     Ok(SnapshotDescription {
         name: name.to_string(),
         creation_time,
         size,
         checksum,
+        seq_number,
     })
 }
 
@@ -147,6 +164,18 @@ pub fn get_checksum_path(snapshot_path: impl Into<PathBuf>) -> PathBuf {
     checksum_path.into()
 }
 
+async fn read_seq_number_for_snapshot(snapshot_path: impl Into<PathBuf>) -> Option<u64> {
+    let seq_number_path = get_seq_number_path(snapshot_path);
+    let seq_number = tokio::fs::read_to_string(&seq_number_path).await.ok()?;
+    seq_number.trim().parse().ok()
+}
+
+pub fn get_seq_number_path(snapshot_path: impl Into<PathBuf>) -> PathBuf {
+    let mut seq_number_path = snapshot_path.into().into_os_string();
+    seq_number_path.push(".seq_number");
+    seq_number_path.into()
+}
+
 #[derive(Clone, Debug, serde::Deserialize, serde::Serialize, schemars::JsonSchema)]
 pub struct ShardSnapshotRecover {
     pub location: ShardSnapshotLocation,
@@ -58,6 +58,7 @@ pub async fn get_snapshot_description(
         creation_time: Some(last_modified),
         size,
         checksum,
+        seq_number: None,
     })
 }
 
@@ -150,6 +151,7 @@ pub async fn list_snapshot_descriptions(
             creation_time: Some(meta.last_modified.naive_local()),
             size: meta.size as u64,
             checksum: None,
+            seq_number: None,
         });
     }
     Ok(snapshots)
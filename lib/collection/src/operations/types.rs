@@ -2,9 +2,9 @@ use std::backtrace::Backtrace;
 use std::collections::{BTreeMap, HashMap};
 use std::error::Error as _;
 use std::fmt::Write as _;
-use std::iter;
 use std::num::NonZeroU64;
 use std::time::SystemTimeError;
+use std::{fmt, iter};
 
 use api::grpc::transport_channel_pool::RequestError;
 use api::rest::{
@@ -44,6 +44,7 @@ use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
 use super::ClockTag;
+use crate::collection_manager::holders::segment_holder::SegmentId;
 use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::cluster_ops::ReshardingDirection;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
@@ -52,6 +53,7 @@ use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRe
 use crate::save_on_disk;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::transfer_tasks_pool::TransferStage;
 use crate::shards::transfer::ShardTransferMethod;
 use crate::wal::WalError;
 
@@ -86,7 +88,66 @@ pub enum OptimizersStatus {
     #[default]
     Ok,
     /// Something wrong happened with optimizers
-    Error(String),
+    #[serde(untagged)]
+    Error(OptimizerError),
+}
+
+impl OptimizersStatus {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error(OptimizerError::new(message))
+    }
+}
+
+/// Structured cause of an optimizer failure
+#[derive(Debug, Serialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct OptimizerError {
+    /// Description of the error
+    pub error: String,
+    /// Name of the optimizer which failed, if the error originates from an optimizer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub optimizer: Option<String>,
+    /// IDs of the segments which were being optimized when the error occurred
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub segment_ids: Vec<SegmentId>,
+}
+
+impl OptimizerError {
+    pub fn new(error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            optimizer: None,
+            segment_ids: Vec::new(),
+        }
+    }
+
+    pub fn with_optimizer(
+        mut self,
+        optimizer: impl Into<String>,
+        segment_ids: Vec<SegmentId>,
+    ) -> Self {
+        self.optimizer = Some(optimizer.into());
+        self.segment_ids = segment_ids;
+        self
+    }
+}
+
+impl fmt::Display for OptimizerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.optimizer {
+            Some(optimizer) => write!(
+                f,
+                "{optimizer} optimizer failed on segments {:?}: {}",
+                self.segment_ids, self.error,
+            ),
+            None => write!(f, "{}", self.error),
+        }
+    }
+}
+
+impl From<CollectionError> for OptimizerError {
+    fn from(error: CollectionError) -> Self {
+        Self::new(error.to_string())
+    }
 }
 
 /// Point data
@@ -102,6 +163,8 @@ pub struct Record {
     pub shard_key: Option<ShardKey>,
     /// Order value, if used for order_by
     pub order_value: Option<OrderValue>,
+    /// Version of the point, number of the last operation which changed it
+    pub version: Option<SeqNumberType>,
 }
 
 /// Current statistics and configuration of the collection
@@ -192,6 +255,13 @@ pub struct CollectionInfoInternal {
     pub payload_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
 }
 
+/// Vector indices scheduled for rebuilding on this peer
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReindexResult {
+    /// Number of segments, which vector indices will be rebuilt
+    pub scheduled_segments: usize,
+}
+
 /// Current clustering distribution for the collection
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct CollectionClusterInfo {
@@ -237,6 +307,52 @@ pub struct ShardTransferInfo {
     pub comment: Option<String>,
 }
 
+/// Progress of a shard transfer which is driven by this peer.
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+pub struct ShardTransferProgressInfo {
+    /// Name of the collection the shard belongs to
+    pub collection_name: String,
+
+    pub shard_id: ShardId,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)] // TODO(resharding): expose once we release resharding
+    pub to_shard_id: Option<ShardId>,
+
+    /// Source peer id
+    pub from: PeerId,
+
+    /// Destination peer id
+    pub to: PeerId,
+
+    /// If `true` transfer is a synchronization of a replicas
+    /// If `false` transfer is a moving of a shard from one peer to another
+    pub sync: bool,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub method: Option<ShardTransferMethod>,
+
+    /// Stage the transfer is currently in
+    pub stage: TransferStage,
+
+    /// Number of points or updates transferred in the current stage
+    pub points_transferred: usize,
+
+    /// Total number of points or updates to transfer in the current stage
+    pub points_total: usize,
+
+    /// Percentage of the current stage completed, if the total is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_percent: Option<f64>,
+
+    /// Time when the transfer was started
+    pub started_at: chrono::DateTime<chrono::Utc>,
+
+    /// Estimated time to complete the current stage, in seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<f64>,
+}
+
 #[derive(Debug, Serialize, JsonSchema, Clone)]
 pub struct ReshardingInfo {
     pub direction: ReshardingDirection,
@@ -302,12 +418,25 @@ pub struct UpdateResult {
     /// Update status
     pub status: UpdateStatus,
 
+    /// Number of points skipped as duplicates of other points of the upsert or of stored points
+    /// Provided if deduplication of vectors is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deduplicated: Option<usize>,
+
     /// Updated value for the external clock tick
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
 }
 
+/// Error an update operation would fail with, found without applying it
+#[derive(Clone, Debug)]
+pub struct UpdateValidationError {
+    /// Point the error is caused by, `None` if it applies to the whole operation
+    pub point_id: Option<PointIdType>,
+    pub error: CollectionError,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct ScrollRequest {
@@ -415,6 +544,7 @@ fn points_example() -> Vec<api::rest::Record> {
             vector: Some(VectorStruct::Single(vec![0.875, 0.140625, 0.897_6])),
             shard_key: Some("region_1".into()),
             order_value: None,
+            version: None,
         },
         api::rest::Record {
             id: PointIdType::NumId(41),
@@ -422,6 +552,7 @@ fn points_example() -> Vec<api::rest::Record> {
             vector: Some(VectorStruct::Single(vec![0.75, 0.640625, 0.8945])),
             shard_key: Some("region_1".into()),
             order_value: None,
+            version: None,
         },
     ]
 }
@@ -891,6 +1022,8 @@ pub enum CollectionError {
     ObjectStoreError { what: String },
     #[error("Strict mode error: {description}")]
     StrictMode { description: String },
+    #[error("Conflict: {description}")]
+    Conflict { description: String },
 }
 
 impl CollectionError {
@@ -965,6 +1098,12 @@ impl CollectionError {
         }
     }
 
+    pub fn conflict(description: impl Into<String>) -> CollectionError {
+        CollectionError::Conflict {
+            description: description.into(),
+        }
+    }
+
     pub fn strict_mode(error: impl Into<String>, solution: impl Into<String>) -> Self {
         let description = format!("{}. Help: {}", error.into(), solution.into());
         Self::StrictMode { description }
@@ -990,6 +1129,7 @@ impl CollectionError {
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
             Self::StrictMode { .. } => false,
+            Self::Conflict { .. } => false,
         }
     }
 }
@@ -1038,6 +1178,7 @@ impl From<OperationError> for CollectionError {
                 description: format!("{err}"),
             },
             OperationError::Cancelled { description } => Self::Cancelled { description },
+            OperationError::Timeout { description } => Self::Timeout { description },
             OperationError::TypeInferenceError { .. } => Self::BadInput {
                 description: format!("{err}"),
             },
@@ -1060,6 +1201,7 @@ impl From<OperationError> for CollectionError {
             OperationError::WrongPayloadKey { description } => Self::BadInput { description },
             OperationError::MissingRangeIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
             OperationError::MissingMapIndexForFacet { .. } => Self::bad_input(format!("{err}")),
+            OperationError::MissingGeoIndexForOrderBy { .. } => Self::bad_input(format!("{err}")),
         }
     }
 }
@@ -1161,6 +1303,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::Aborted => CollectionError::Conflict {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
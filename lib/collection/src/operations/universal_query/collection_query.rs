@@ -48,6 +48,20 @@ impl CollectionQueryRequest {
     pub const DEFAULT_WITH_VECTOR: WithVector = WithVector::Bool(false);
 
     pub const DEFAULT_WITH_PAYLOAD: WithPayloadInterface = WithPayloadInterface::Bool(false);
+
+    /// Filters restricting the points this query is evaluated on.
+    ///
+    /// The root filter applies to all prefetches. Without it, each prefetch is restricted by its
+    /// own filters only.
+    pub fn selecting_filters(&self) -> Vec<Option<&Filter>> {
+        if self.filter.is_some() || self.prefetch.is_empty() {
+            return vec![self.filter.as_ref()];
+        }
+        self.prefetch
+            .iter()
+            .flat_map(CollectionPrefetch::selecting_filters)
+            .collect()
+    }
 }
 
 /// Lightweight representation of a query request to implement the [RetrieveRequest] trait.
@@ -318,6 +332,16 @@ fn exclude_referenced_ids(ids: Vec<ExtendedPointId>, filter: Option<Filter>) ->
 }
 
 impl CollectionPrefetch {
+    fn selecting_filters(&self) -> Vec<Option<&Filter>> {
+        if self.filter.is_some() || self.prefetch.is_empty() {
+            return vec![self.filter.as_ref()];
+        }
+        self.prefetch
+            .iter()
+            .flat_map(CollectionPrefetch::selecting_filters)
+            .collect()
+    }
+
     fn get_lookup_collection(&self) -> Option<&String> {
         self.lookup_from.as_ref().map(|x| &x.collection)
     }
@@ -668,7 +668,7 @@ mod tests {
     fn test_base_params_mapping_in_try_from() {
         let dummy_vector = vec![1.0, 2.0, 3.0];
         let dummy_params = Some(SearchParams {
-            indexed_only: true,
+            indexed_only: Some(true),
             ..Default::default()
         });
         let dummy_filter = Some(Filter::new_must(Condition::Field(
@@ -700,7 +700,7 @@ mod tests {
 
             // these params will be ignored because we have a prefetch
             params: Some(SearchParams {
-                exact: true,
+                exact: Some(true),
                 ..Default::default()
             }),
             with_payload: WithPayloadInterface::Bool(true),
@@ -0,0 +1,468 @@
+use std::borrow::Cow;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash as _, Hasher as _};
+
+use api::rest::{BatchVectorStruct, Vector, VectorStruct};
+use schemars::JsonSchema;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::primitive::PrimitiveVectorElement;
+use segment::data_types::vectors::{
+    self as segment_vectors, VectorElementType, VectorElementTypeByte, VectorElementTypeHalf,
+};
+use segment::types::{Payload, VectorDataConfig, VectorStorageDatatype};
+use serde::{Deserialize, Serialize};
+
+use super::point_ops::{Batch, PointInsertOperationsInternal, PointStruct};
+
+/// Defines how to handle points of an upsert, which have exactly the same vectors as a previous
+/// point of the same upsert, or as a point already stored in the same shard. The first point
+/// with the vectors is the canonical one, stored points come first.
+/// Can't be combined with expected versions or `insert_if_absent` mode.
+///
+/// * `skip` - only upsert the canonical point, duplicates are skipped
+///
+/// * `merge` - only upsert the canonical point, with the payload of duplicates merged into it
+///
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VectorDedup {
+    Skip,
+    Merge,
+}
+
+/// Exact binary representation of all vectors of a point, used to find duplicates
+#[derive(Default, PartialEq, Eq, Hash)]
+struct VectorKey(Vec<u8>);
+
+impl VectorKey {
+    fn push_len(&mut self, len: usize) {
+        self.0.extend_from_slice(&(len as u64).to_le_bytes());
+    }
+
+    fn push_dense(&mut self, vector: &[f32]) {
+        self.push_len(vector.len());
+        for value in vector {
+            self.0.extend_from_slice(&value.to_bits().to_le_bytes());
+        }
+    }
+
+    fn push_multi_dense(&mut self, vectors: &[Vec<f32>]) {
+        self.push_len(vectors.len());
+        for vector in vectors {
+            self.push_dense(vector);
+        }
+    }
+
+    /// Documents are not vectors yet, so they are never considered duplicates
+    fn push_vector(&mut self, vector: &Vector) -> Option<()> {
+        match vector {
+            Vector::Dense(vector) => {
+                self.0.push(0);
+                self.push_dense(vector);
+            }
+            Vector::Sparse(vector) => {
+                self.0.push(1);
+                self.push_len(vector.indices.len());
+                for index in &vector.indices {
+                    self.0.extend_from_slice(&index.to_le_bytes());
+                }
+                self.push_dense(&vector.values);
+            }
+            Vector::MultiDense(vectors) => {
+                self.0.push(2);
+                self.push_multi_dense(vectors);
+            }
+            Vector::Document(_) => return None,
+        }
+        Some(())
+    }
+
+    fn push_named<'a>(
+        &mut self,
+        vectors: impl IntoIterator<Item = (&'a str, &'a Vector)>,
+    ) -> Option<()> {
+        let mut vectors: Vec<_> = vectors.into_iter().collect();
+        vectors.sort_unstable_by_key(|(name, _)| *name);
+        for (name, vector) in vectors {
+            self.push_len(name.len());
+            self.0.extend_from_slice(name.as_bytes());
+            self.push_vector(vector)?;
+        }
+        Some(())
+    }
+
+    fn push_internal(&mut self, vector: &segment_vectors::Vector) {
+        match vector {
+            segment_vectors::Vector::Dense(vector) => {
+                self.0.push(0);
+                self.push_dense(vector);
+            }
+            segment_vectors::Vector::Sparse(vector) => {
+                self.0.push(1);
+                self.push_len(vector.indices.len());
+                for index in &vector.indices {
+                    self.0.extend_from_slice(&index.to_le_bytes());
+                }
+                self.push_dense(&vector.values);
+            }
+            segment_vectors::Vector::MultiDense(vectors) => {
+                self.0.push(2);
+                self.push_len(vectors.flattened_vectors.len() / vectors.dim);
+                for vector in vectors.multi_vectors() {
+                    self.push_dense(vector);
+                }
+            }
+        }
+    }
+
+    fn from_internal(vectors: &HashMap<String, segment_vectors::Vector>) -> Self {
+        let mut key = Self::default();
+        let mut vectors: Vec<_> = vectors.iter().collect();
+        vectors.sort_unstable_by_key(|(name, _)| *name);
+        for (name, vector) in vectors {
+            key.push_len(name.len());
+            key.0.extend_from_slice(name.as_bytes());
+            key.push_internal(vector);
+        }
+        key
+    }
+
+    fn from_vector_struct(vector: &VectorStruct) -> Option<Self> {
+        let mut key = Self::default();
+        match vector {
+            VectorStruct::Single(vector) => key.push_dense(vector),
+            VectorStruct::MultiDense(vectors) => key.push_multi_dense(vectors),
+            VectorStruct::Named(vectors) => {
+                key.push_named(vectors.iter().map(|(name, vector)| (name.as_str(), vector)))?
+            }
+            VectorStruct::Document(_) => return None,
+        }
+        Some(key)
+    }
+
+    fn from_batch(vectors: &BatchVectorStruct, index: usize) -> Option<Self> {
+        let mut key = Self::default();
+        match vectors {
+            BatchVectorStruct::Single(vectors) => key.push_dense(&vectors[index]),
+            BatchVectorStruct::MultiDense(vectors) => key.push_multi_dense(&vectors[index]),
+            BatchVectorStruct::Named(vectors) => key.push_named(
+                vectors
+                    .iter()
+                    .map(|(name, vectors)| (name.as_str(), &vectors[index])),
+            )?,
+            BatchVectorStruct::Document(_) => return None,
+        }
+        Some(key)
+    }
+}
+
+/// Vectors of a point in the form they are stored in, to compare them with stored points
+///
+/// Vectors are preprocessed for their distance and converted to their storage datatype, the same
+/// way as on upsert. Points with documents or unknown vectors are never considered duplicates.
+pub(crate) struct StoredVectors(VectorKey);
+
+impl StoredVectors {
+    pub(crate) fn new(
+        vector: &VectorStruct,
+        vector_data: &HashMap<String, VectorDataConfig>,
+    ) -> Option<Self> {
+        let has_documents = match vector {
+            VectorStruct::Document(_) => true,
+            VectorStruct::Named(vectors) => vectors
+                .values()
+                .any(|vector| matches!(vector, Vector::Document(_))),
+            VectorStruct::Single(_) | VectorStruct::MultiDense(_) => false,
+        };
+        if has_documents {
+            return None;
+        }
+
+        let mut vectors = NamedVectors::from(vector.clone());
+        let all_configured = vectors.iter().all(|(name, vector)| {
+            matches!(vector, segment_vectors::VectorRef::Sparse(_))
+                || vector_data.contains_key(name)
+        });
+        if !all_configured {
+            return None;
+        }
+        vectors.preprocess(|name| &vector_data[name]);
+
+        let mut vectors = vectors.into_owned_map();
+        for (name, vector) in vectors.iter_mut() {
+            let datatype = vector_data.get(name).and_then(|config| config.datatype);
+            match vector {
+                segment_vectors::Vector::Dense(vector) => to_storage_datatype(vector, datatype),
+                segment_vectors::Vector::MultiDense(vectors) => {
+                    to_storage_datatype(&mut vectors.flattened_vectors, datatype)
+                }
+                segment_vectors::Vector::Sparse(_) => {}
+            }
+        }
+        Some(Self::from_stored(&vectors))
+    }
+
+    /// Vectors as read from a segment
+    pub(crate) fn from_stored(vectors: &HashMap<String, segment_vectors::Vector>) -> Self {
+        Self(VectorKey::from_internal(vectors))
+    }
+
+    /// Hash of the exact binary representation of the vectors
+    pub(crate) fn hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.0.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Whether vectors of a stored point are exactly the same
+    pub(crate) fn is_same(&self, stored: &HashMap<String, segment_vectors::Vector>) -> bool {
+        self.0 == VectorKey::from_internal(stored)
+    }
+}
+
+/// Round vector values the same way as storing them with `datatype` does
+fn to_storage_datatype(vector: &mut [VectorElementType], datatype: Option<VectorStorageDatatype>) {
+    let stored = match datatype {
+        Some(VectorStorageDatatype::Float32) | None => return,
+        Some(VectorStorageDatatype::Float16) => VectorElementTypeHalf::slice_to_float_cow(
+            VectorElementTypeHalf::slice_from_float_cow(Cow::Borrowed(vector)),
+        )
+        .into_owned(),
+        Some(VectorStorageDatatype::Uint8) => VectorElementTypeByte::slice_to_float_cow(
+            VectorElementTypeByte::slice_from_float_cow(Cow::Borrowed(vector)),
+        )
+        .into_owned(),
+    };
+    vector.copy_from_slice(&stored);
+}
+
+/// Vectors of every point of an upsert
+pub(crate) fn point_vectors(points: &PointInsertOperationsInternal) -> Vec<VectorStruct> {
+    match points {
+        PointInsertOperationsInternal::PointsList(points) => {
+            points.iter().map(|point| point.vector.clone()).collect()
+        }
+        PointInsertOperationsInternal::PointsBatch(batch) => (0..batch.ids.len())
+            .map(|index| match &batch.vectors {
+                BatchVectorStruct::Single(vectors) => VectorStruct::Single(vectors[index].clone()),
+                BatchVectorStruct::MultiDense(vectors) => {
+                    VectorStruct::MultiDense(vectors[index].clone())
+                }
+                BatchVectorStruct::Named(vectors) => VectorStruct::Named(
+                    vectors
+                        .iter()
+                        .map(|(name, vectors)| (name.clone(), vectors[index].clone()))
+                        .collect(),
+                ),
+                BatchVectorStruct::Document(documents) => {
+                    VectorStruct::Document(documents[index].clone())
+                }
+            })
+            .collect(),
+    }
+}
+
+/// For every point, the position of the canonical point if it is a duplicate
+fn find_duplicates(keys: impl IntoIterator<Item = Option<VectorKey>>) -> Vec<Option<usize>> {
+    let mut canonical: HashMap<VectorKey, usize> = HashMap::new();
+    keys.into_iter()
+        .enumerate()
+        .map(|(index, key)| {
+            let key = key?;
+            let first = *canonical.entry(key).or_insert(index);
+            (first != index).then_some(first)
+        })
+        .collect()
+}
+
+/// Merge payloads of duplicates into their canonical points, in order of the points
+fn merge_payloads(payloads: &mut [Option<Payload>], duplicate_of: &[Option<usize>]) {
+    for (index, canonical) in duplicate_of.iter().enumerate() {
+        let Some(canonical) = *canonical else {
+            continue;
+        };
+        if let Some(payload) = payloads[index].take() {
+            payloads[canonical]
+                .get_or_insert_with(Payload::default)
+                .merge(&payload);
+        }
+    }
+}
+
+/// Keep only the items of canonical points
+fn retain_canonical<T, C>(items: &mut Vec<T>, duplicate_of: &[Option<C>]) {
+    let mut duplicate_of = duplicate_of.iter();
+    items.retain(|_| duplicate_of.next().is_some_and(Option::is_none));
+}
+
+/// Remove points with exactly the same vectors as a previous point of the upsert.
+///
+/// Returns the number of removed duplicates.
+pub fn dedup_vectors(points: &mut PointInsertOperationsInternal, policy: VectorDedup) -> usize {
+    match points {
+        PointInsertOperationsInternal::PointsList(points) => dedup_points_list(points, policy),
+        PointInsertOperationsInternal::PointsBatch(batch) => dedup_batch(batch, policy),
+    }
+}
+
+fn dedup_points_list(points: &mut Vec<PointStruct>, policy: VectorDedup) -> usize {
+    let duplicate_of = find_duplicates(
+        points
+            .iter()
+            .map(|point| VectorKey::from_vector_struct(&point.vector)),
+    );
+
+    if policy == VectorDedup::Merge {
+        let mut payloads: Vec<_> = points
+            .iter_mut()
+            .map(|point| point.payload.take())
+            .collect();
+        merge_payloads(&mut payloads, &duplicate_of);
+        for (point, payload) in points.iter_mut().zip(payloads) {
+            point.payload = payload;
+        }
+    }
+
+    let len = points.len();
+    retain_canonical(points, &duplicate_of);
+    len - points.len()
+}
+
+fn dedup_batch(batch: &mut Batch, policy: VectorDedup) -> usize {
+    let duplicate_of = find_duplicates(
+        (0..batch.ids.len()).map(|index| VectorKey::from_batch(&batch.vectors, index)),
+    );
+
+    if let Some(payloads) = &mut batch.payloads {
+        if policy == VectorDedup::Merge {
+            merge_payloads(payloads, &duplicate_of);
+        }
+    }
+
+    retain_batch(batch, &duplicate_of)
+}
+
+/// Remove points, which are duplicates of stored points
+///
+/// Returns payloads of the removed points, in order of the points.
+pub(crate) fn remove_stored_duplicates<C>(
+    points: &mut PointInsertOperationsInternal,
+    duplicate_of: &[Option<C>],
+) -> Vec<Option<Payload>> {
+    match points {
+        PointInsertOperationsInternal::PointsList(points) => {
+            let payloads = points
+                .iter_mut()
+                .zip(duplicate_of)
+                .filter(|(_, canonical)| canonical.is_some())
+                .map(|(point, _)| point.payload.take())
+                .collect();
+            retain_canonical(points, duplicate_of);
+            payloads
+        }
+        PointInsertOperationsInternal::PointsBatch(batch) => {
+            let payloads = match &mut batch.payloads {
+                Some(payloads) => payloads
+                    .iter_mut()
+                    .zip(duplicate_of)
+                    .filter(|(_, canonical)| canonical.is_some())
+                    .map(|(payload, _)| payload.take())
+                    .collect(),
+                None => vec![None; duplicate_of.iter().flatten().count()],
+            };
+            retain_batch(batch, duplicate_of);
+            payloads
+        }
+    }
+}
+
+/// Keep only canonical points of a batch, returns the number of removed points
+fn retain_batch<C>(batch: &mut Batch, duplicate_of: &[Option<C>]) -> usize {
+    if let Some(payloads) = &mut batch.payloads {
+        retain_canonical(payloads, duplicate_of);
+    }
+
+    match &mut batch.vectors {
+        BatchVectorStruct::Single(vectors) => retain_canonical(vectors, duplicate_of),
+        BatchVectorStruct::MultiDense(vectors) => retain_canonical(vectors, duplicate_of),
+        BatchVectorStruct::Named(vectors) => {
+            for vectors in vectors.values_mut() {
+                retain_canonical(vectors, duplicate_of);
+            }
+        }
+        BatchVectorStruct::Document(documents) => retain_canonical(documents, duplicate_of),
+    }
+
+    let len = batch.ids.len();
+    retain_canonical(&mut batch.ids, duplicate_of);
+    len - batch.ids.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn payload(value: serde_json::Value) -> Option<Payload> {
+        Some(value.into())
+    }
+
+    #[test]
+    fn test_dedup_batch() {
+        let mut batch = Batch {
+            ids: vec![1.into(), 2.into(), 3.into(), 4.into()],
+            vectors: BatchVectorStruct::Single(vec![
+                vec![1.0, 0.0],
+                vec![0.0, 1.0],
+                vec![1.0, 0.0],
+                vec![1.0, 0.0],
+            ]),
+            payloads: Some(vec![
+                payload(json!({ "a": 1 })),
+                payload(json!({ "b": 2 })),
+                payload(json!({ "a": 3, "c": 3 })),
+                None,
+            ]),
+        };
+
+        assert_eq!(dedup_batch(&mut batch, VectorDedup::Merge), 2);
+        assert_eq!(batch.ids, vec![1.into(), 2.into()]);
+        assert_eq!(
+            batch.vectors,
+            BatchVectorStruct::Single(vec![vec![1.0, 0.0], vec![0.0, 1.0]]),
+        );
+        assert_eq!(
+            batch.payloads,
+            Some(vec![
+                payload(json!({ "a": 3, "c": 3 })),
+                payload(json!({ "b": 2 })),
+            ]),
+        );
+    }
+
+    #[test]
+    fn test_dedup_named_vectors_in_any_order() {
+        let point = |id: u64, vectors: [(&str, Vec<f32>); 2]| PointStruct {
+            id: id.into(),
+            vector: VectorStruct::Named(
+                vectors
+                    .into_iter()
+                    .map(|(name, vector)| (name.to_string(), Vector::Dense(vector)))
+                    .collect(),
+            ),
+            payload: None,
+        };
+
+        let mut points = vec![
+            point(1, [("a", vec![1.0]), ("b", vec![2.0])]),
+            point(2, [("b", vec![2.0]), ("a", vec![1.0])]),
+            point(3, [("a", vec![2.0]), ("b", vec![1.0])]),
+        ];
+
+        assert_eq!(dedup_points_list(&mut points, VectorDedup::Skip), 1);
+        let ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        assert_eq!(ids, vec![1.into(), 3.into()]);
+    }
+}
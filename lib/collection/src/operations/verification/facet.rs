@@ -0,0 +1,38 @@
+use segment::data_types::facets::DistinctValuesParams;
+use segment::types::Filter;
+
+use super::{check_limit_opt, StrictModeVerification};
+use crate::collection::Collection;
+use crate::operations::config_diff::StrictModeConfig;
+use crate::operations::types::CollectionError;
+
+impl StrictModeVerification for DistinctValuesParams {
+    fn check_custom(
+        &self,
+        _: &Collection,
+        strict_mode_config: &StrictModeConfig,
+    ) -> Result<(), CollectionError> {
+        check_limit_opt(self.limit, strict_mode_config.max_distinct_values, "limit")
+    }
+
+    // Distinct values are bounded by `max_distinct_values` instead
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        None
+    }
+
+    fn query_payload_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        None
+    }
+}
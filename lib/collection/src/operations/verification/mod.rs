@@ -1,13 +1,26 @@
+mod facet;
 mod search;
+mod update;
+
+pub use search::check_search_params;
 
 use std::fmt::Display;
 
-use segment::types::Filter;
+use segment::types::{
+    Condition, Filter, PayloadSelector, PayloadSelectorInclude, WithPayloadInterface,
+};
 
 use super::config_diff::StrictModeConfig;
 use super::types::CollectionError;
+use super::universal_query::shard_query::{ShardPrefetch, ShardQueryRequest};
 use crate::collection::Collection;
 
+/// Estimated size in bytes of the full payload of a point.
+const ESTIMATED_POINT_PAYLOAD_BYTES: usize = 1024;
+
+/// Estimated size in bytes of a single payload field of a point.
+const ESTIMATED_PAYLOAD_FIELD_BYTES: usize = 128;
+
 /// Trait to verify strict mode for requests.
 /// This trait ignores the `enabled` parameter in `StrictModeConfig`.
 pub trait StrictModeVerification {
@@ -26,6 +39,10 @@ pub trait StrictModeVerification {
     /// Implement this to check the timeout of a request.
     fn timeout(&self) -> Option<usize>;
 
+    /// Implement this to check the estimated size in bytes of the payload returned by a request.
+    /// Use `estimate_payload_bytes` for requests returning points with a payload selection.
+    fn query_payload_limit(&self) -> Option<usize>;
+
     /// Verifies that all keys in the given filter have an index available. Only implement this
     /// if the filter operates on a READ-operation, like search.
     /// For filtered updates implement `request_indexed_filter_write`!
@@ -36,6 +53,18 @@ pub trait StrictModeVerification {
     /// For read only filters implement `request_indexed_filter_read`!
     fn indexed_filter_write(&self) -> Option<&Filter>;
 
+    /// Same as `indexed_filter_read`, but for batched requests, with one filter per request of
+    /// the batch. Takes precedence over `indexed_filter_read` if implemented.
+    fn indexed_filters_read(&self) -> Option<Vec<&Filter>> {
+        None
+    }
+
+    /// Same as `indexed_filter_write`, but for batched updates, with one filter per update of the
+    /// batch. Takes precedence over `indexed_filter_write` if implemented.
+    fn indexed_filters_write(&self) -> Option<Vec<&Filter>> {
+        None
+    }
+
     /// Checks the request limit.
     fn check_request_query_limit(
         &self,
@@ -56,6 +85,28 @@ pub trait StrictModeVerification {
         check_limit_opt(self.timeout(), strict_mode_config.max_timeout, "timeout")
     }
 
+    /// Checks the estimated size of the payload returned by the request.
+    fn check_request_payload_limit(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+    ) -> Result<(), CollectionError> {
+        let (Some(limit), Some(payload_bytes)) = (
+            strict_mode_config.max_payload_bytes,
+            self.query_payload_limit(),
+        ) else {
+            return Ok(());
+        };
+
+        if payload_bytes > limit {
+            return Err(CollectionError::strict_mode(
+                format!("Estimated payload size exceeded {payload_bytes} > {limit} bytes"),
+                "Reduce with_payload selection to fewer fields, or reduce the \"limit\" parameter.",
+            ));
+        }
+
+        Ok(())
+    }
+
     // Checks all filters use indexed fields only.
     fn check_request_filter(
         &self,
@@ -63,7 +114,8 @@ pub trait StrictModeVerification {
         strict_mode_config: &StrictModeConfig,
     ) -> Result<(), CollectionError> {
         let check_filter = |filter: Option<&Filter>,
-                            allow_unindexed_filter: Option<bool>|
+                            allow_unindexed_filter: Option<bool>,
+                            batch_index: Option<usize>|
          -> Result<(), CollectionError> {
             if let Some(read_filter) = filter {
                 if allow_unindexed_filter == Some(false) {
@@ -73,9 +125,12 @@ pub trait StrictModeVerification {
                             .map(|schema| schema.to_string())
                             .collect::<Vec<_>>()
                             .join(", ");
+                        let location = batch_index
+                            .map(|index| format!(" in request {index} of the batch"))
+                            .unwrap_or_default();
 
                         return Err(CollectionError::strict_mode(
-                            format!("Index required but not found for \"{key}\" of one of the following types: [{possible_schemas_str}]"),
+                            format!("Index required but not found for \"{key}\"{location} of one of the following types: [{possible_schemas_str}]"),
                             "Create an index for this key or use a different filter.",
                         ));
                     }
@@ -85,18 +140,47 @@ pub trait StrictModeVerification {
             Ok(())
         };
 
-        check_filter(
-            self.indexed_filter_read(),
-            strict_mode_config.unindexed_filtering_retrieve,
-        )?;
-        check_filter(
-            self.indexed_filter_write(),
-            strict_mode_config.unindexed_filtering_update,
-        )?;
+        let checks = [
+            (
+                self.indexed_filters_read(),
+                self.indexed_filter_read(),
+                strict_mode_config.unindexed_filtering_retrieve,
+            ),
+            (
+                self.indexed_filters_write(),
+                self.indexed_filter_write(),
+                strict_mode_config.unindexed_filtering_update,
+            ),
+        ];
+
+        for (batch_filters, filter, allow_unindexed_filter) in checks {
+            match batch_filters {
+                Some(filters) => {
+                    for (index, filter) in filters.into_iter().enumerate() {
+                        check_filter(Some(filter), allow_unindexed_filter, Some(index))?;
+                    }
+                }
+                None => check_filter(filter, allow_unindexed_filter, None)?,
+            }
+        }
 
         Ok(())
     }
 
+    /// Checks that no filter of the request has more conditions than allowed.
+    fn check_request_filter_conditions(
+        &self,
+        strict_mode_config: &StrictModeConfig,
+    ) -> Result<(), CollectionError> {
+        for filter in [self.indexed_filter_read(), self.indexed_filter_write()]
+            .into_iter()
+            .flatten()
+        {
+            check_filter_conditions(filter, strict_mode_config)?;
+        }
+        Ok(())
+    }
+
     /// Does the verification of all configured parameters. Only implement this function if you know what
     /// you are doing. In most cases implementing `check_custom` is sufficient.
     fn check_strict_mode(
@@ -106,11 +190,112 @@ pub trait StrictModeVerification {
     ) -> Result<(), CollectionError> {
         self.check_custom(collection, strict_mode_config)?;
         self.check_request_query_limit(strict_mode_config)?;
+        self.check_request_payload_limit(strict_mode_config)?;
         self.check_request_filter(collection, strict_mode_config)?;
+        self.check_request_filter_conditions(strict_mode_config)?;
         Ok(())
     }
 }
 
+/// Estimated size in bytes of the payload of `points` returned with the given selection.
+///
+/// Payload sizes are not known before the request is executed, so a fixed size is assumed for
+/// the full payload of a point and for each selected field.
+pub(crate) fn estimate_payload_bytes(
+    with_payload: Option<&WithPayloadInterface>,
+    points: usize,
+) -> Option<usize> {
+    let point_payload_bytes = match with_payload? {
+        WithPayloadInterface::Bool(false) => return None,
+        WithPayloadInterface::Bool(true)
+        | WithPayloadInterface::Selector(PayloadSelector::Exclude(_)) => {
+            ESTIMATED_POINT_PAYLOAD_BYTES
+        }
+        WithPayloadInterface::Fields(fields)
+        | WithPayloadInterface::Selector(PayloadSelector::Include(PayloadSelectorInclude {
+            include: fields,
+        })) => (fields.len() * ESTIMATED_PAYLOAD_FIELD_BYTES).min(ESTIMATED_POINT_PAYLOAD_BYTES),
+    };
+
+    Some(points.saturating_mul(point_payload_bytes))
+}
+
+/// Check the number of conditions in a filter against the limit of strict mode.
+pub fn check_filter_conditions(
+    filter: &Filter,
+    strict_mode_config: &StrictModeConfig,
+) -> Result<(), CollectionError> {
+    check_conditions_count(count_filter_conditions(filter), strict_mode_config)
+}
+
+/// Check the filters of a query and of all its prefetches against the limit of strict mode.
+///
+/// A prefetch is also filtered by the filters of all queries it is nested in, so its conditions
+/// are counted together with theirs.
+pub fn check_query_filter_conditions(
+    request: &ShardQueryRequest,
+    strict_mode_config: &StrictModeConfig,
+) -> Result<(), CollectionError> {
+    let conditions = request.filter.as_ref().map_or(0, count_filter_conditions);
+    check_conditions_count(conditions, strict_mode_config)?;
+    for prefetch in &request.prefetches {
+        check_prefetch_filter_conditions(prefetch, conditions, strict_mode_config)?;
+    }
+
+    Ok(())
+}
+
+fn check_prefetch_filter_conditions(
+    prefetch: &ShardPrefetch,
+    outer_conditions: usize,
+    strict_mode_config: &StrictModeConfig,
+) -> Result<(), CollectionError> {
+    let conditions = outer_conditions + prefetch.filter.as_ref().map_or(0, count_filter_conditions);
+    check_conditions_count(conditions, strict_mode_config)?;
+    for prefetch in &prefetch.prefetches {
+        check_prefetch_filter_conditions(prefetch, conditions, strict_mode_config)?;
+    }
+
+    Ok(())
+}
+
+fn check_conditions_count(
+    conditions: usize,
+    strict_mode_config: &StrictModeConfig,
+) -> Result<(), CollectionError> {
+    let Some(limit) = strict_mode_config.max_filter_conditions else {
+        return Ok(());
+    };
+
+    if conditions > limit {
+        return Err(CollectionError::strict_mode(
+            format!("Filter is too complex: {conditions} conditions > {limit}"),
+            format!("Reduce the number of filter conditions to or below {limit}."),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Total number of conditions in a filter, including conditions of nested filters.
+///
+/// A nested filter used as a condition only counts with its conditions, while a `nested`
+/// condition counts by itself in addition to the conditions of its filter.
+fn count_filter_conditions(filter: &Filter) -> usize {
+    filter
+        .iter_conditions()
+        .map(|condition| match condition {
+            Condition::Filter(filter) => count_filter_conditions(filter),
+            Condition::Nested(nested) => 1 + count_filter_conditions(nested.filter()),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasId(_)
+            | Condition::CustomIdChecker(_) => 1,
+        })
+        .sum()
+}
+
 pub(crate) fn check_bool(
     value: bool,
     allowed: Option<bool>,
@@ -153,3 +338,183 @@ pub(crate) fn check_limit_opt<T: PartialOrd + Display>(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use api::rest::SearchRequestInternal;
+    use segment::json_path::JsonPath;
+    use segment::types::{FieldCondition, Match, Nested, NestedCondition, WithVector};
+
+    use super::*;
+    use crate::operations::types::SearchRequest;
+
+    fn strict_mode_config(max_filter_conditions: usize) -> StrictModeConfig {
+        StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: None,
+            max_timeout: None,
+            unindexed_filtering_retrieve: None,
+            unindexed_filtering_update: None,
+            search_max_hnsw_ef: None,
+            search_allow_exact: None,
+            search_max_oversampling: None,
+            max_distinct_values: None,
+            max_filter_conditions: Some(max_filter_conditions),
+            require_filter: None,
+            max_result_points: None,
+            truncate_result_points: None,
+            max_payload_bytes: None,
+        }
+    }
+
+    fn field_condition(i: usize) -> Condition {
+        Condition::Field(FieldCondition::new_match(
+            JsonPath::new(&format!("key_{i}")),
+            Match::from(i as i64),
+        ))
+    }
+
+    #[test]
+    fn test_flat_filter_conditions_limit() {
+        let filter = Filter {
+            should: Some((0..3).map(field_condition).collect()),
+            min_should: None,
+            must: Some((3..5).map(field_condition).collect()),
+            must_not: Some(vec![field_condition(5)]),
+        };
+        assert_eq!(count_filter_conditions(&filter), 6);
+
+        assert!(check_filter_conditions(&filter, &strict_mode_config(6)).is_ok());
+        assert!(matches!(
+            check_filter_conditions(&filter, &strict_mode_config(5)),
+            Err(CollectionError::StrictMode { .. }),
+        ));
+    }
+
+    #[test]
+    fn test_nested_filter_conditions_limit() {
+        // Each level only has two conditions, but the filter has 6 conditions in total
+        let mut filter = Filter::new_must(field_condition(0));
+        for i in 1..4 {
+            filter = Filter {
+                should: None,
+                min_should: None,
+                must: Some(vec![field_condition(i), Condition::Filter(filter)]),
+                must_not: None,
+            };
+        }
+        let filter = Filter {
+            should: None,
+            min_should: None,
+            must: None,
+            must_not: Some(vec![
+                field_condition(4),
+                Condition::Nested(NestedCondition::new(Nested {
+                    key: JsonPath::new("nested"),
+                    filter,
+                })),
+            ]),
+        };
+        assert_eq!(count_filter_conditions(&filter), 6);
+
+        assert!(check_filter_conditions(&filter, &strict_mode_config(6)).is_ok());
+        assert!(matches!(
+            check_filter_conditions(&filter, &strict_mode_config(5)),
+            Err(CollectionError::StrictMode { .. }),
+        ));
+    }
+
+    #[test]
+    fn test_query_prefetch_filter_conditions_limit() {
+        let filter = |range: std::ops::Range<usize>| {
+            Some(Filter {
+                should: None,
+                min_should: None,
+                must: Some(range.map(field_condition).collect()),
+                must_not: None,
+            })
+        };
+        let prefetch = |filter, prefetches| ShardPrefetch {
+            prefetches,
+            query: None,
+            limit: 10,
+            params: None,
+            filter,
+            score_threshold: None,
+        };
+
+        // The inner prefetch is filtered by 6 conditions in total
+        let request = ShardQueryRequest {
+            prefetches: vec![
+                prefetch(filter(2..3), vec![]),
+                prefetch(filter(2..4), vec![prefetch(filter(4..6), vec![])]),
+            ],
+            query: None,
+            filter: filter(0..2),
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(false),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+
+        assert!(check_query_filter_conditions(&request, &strict_mode_config(6)).is_ok());
+        assert!(matches!(
+            check_query_filter_conditions(&request, &strict_mode_config(5)),
+            Err(CollectionError::StrictMode { .. }),
+        ));
+    }
+
+    #[test]
+    fn test_payload_bytes_limit() {
+        let search = |with_payload: Option<WithPayloadInterface>| SearchRequest {
+            search_request: SearchRequestInternal {
+                vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+                filter: None,
+                params: None,
+                limit: 10,
+                offset: None,
+                with_payload,
+                with_vector: None,
+                score_threshold: None,
+            },
+            shard_key: None,
+        };
+
+        // Full payload of 10 points is estimated at 10 KiB, 2 fields of 10 points at 2.5 KiB
+        let config = StrictModeConfig {
+            max_payload_bytes: Some(4096),
+            ..strict_mode_config(usize::MAX)
+        };
+
+        let full = search(Some(WithPayloadInterface::Bool(true)));
+        assert_eq!(
+            full.query_payload_limit(),
+            Some(10 * ESTIMATED_POINT_PAYLOAD_BYTES)
+        );
+        assert!(matches!(
+            full.check_request_payload_limit(&config),
+            Err(CollectionError::StrictMode { .. }),
+        ));
+
+        let fields = search(Some(WithPayloadInterface::Fields(vec![
+            JsonPath::new("a"),
+            JsonPath::new("b"),
+        ])));
+        assert_eq!(
+            fields.query_payload_limit(),
+            Some(20 * ESTIMATED_PAYLOAD_FIELD_BYTES),
+        );
+        assert!(fields.check_request_payload_limit(&config).is_ok());
+
+        let without_payload = search(None);
+        assert_eq!(without_payload.query_payload_limit(), None);
+        assert!(without_payload.check_request_payload_limit(&config).is_ok());
+
+        // Not limited without the setting
+        assert!(full
+            .check_request_payload_limit(&strict_mode_config(usize::MAX))
+            .is_ok());
+    }
+}
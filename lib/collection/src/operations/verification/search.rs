@@ -1,10 +1,45 @@
-use segment::types::Filter;
+use segment::types::{Filter, SearchParams};
 
-use super::{check_bool, check_limit_opt, StrictModeVerification};
+use super::{check_bool, check_limit_opt, estimate_payload_bytes, StrictModeVerification};
 use crate::collection::Collection;
 use crate::operations::config_diff::StrictModeConfig;
 use crate::operations::types::{CollectionError, SearchRequest, SearchRequestBatch};
 
+/// Filter of unfiltered requests in a batch, keeping filters at the index of their request
+static NO_FILTER: Filter = Filter {
+    should: None,
+    min_should: None,
+    must: None,
+    must_not: None,
+};
+
+/// Check search params against the limits of strict mode.
+pub fn check_search_params(
+    search_params: &SearchParams,
+    strict_mode_config: &StrictModeConfig,
+) -> Result<(), CollectionError> {
+    check_bool(
+        search_params.is_exact(),
+        strict_mode_config.search_allow_exact,
+        "Exact search",
+        "exact",
+    )?;
+
+    check_limit_opt(
+        search_params.quantization.and_then(|i| i.oversampling),
+        strict_mode_config.search_max_oversampling,
+        "oversampling",
+    )?;
+
+    check_limit_opt(
+        search_params.hnsw_ef,
+        strict_mode_config.search_max_hnsw_ef,
+        "hnsw_ef",
+    )?;
+
+    Ok(())
+}
+
 impl StrictModeVerification for SearchRequest {
     fn check_custom(
         &self,
@@ -14,24 +49,7 @@ impl StrictModeVerification for SearchRequest {
         let search_request = &self.search_request;
 
         if let Some(search_parameter) = &search_request.params {
-            check_bool(
-                search_parameter.exact,
-                strict_mode_config.search_allow_exact,
-                "Exact search",
-                "exact",
-            )?;
-
-            check_limit_opt(
-                search_parameter.quantization.and_then(|i| i.oversampling),
-                strict_mode_config.search_max_oversampling,
-                "oversampling",
-            )?;
-
-            check_limit_opt(
-                search_parameter.hnsw_ef,
-                strict_mode_config.search_max_hnsw_ef,
-                "hnsw_ef",
-            )?;
+            check_search_params(search_parameter, strict_mode_config)?;
         }
 
         Ok(())
@@ -45,6 +63,13 @@ impl StrictModeVerification for SearchRequest {
         Some(self.search_request.limit)
     }
 
+    fn query_payload_limit(&self) -> Option<usize> {
+        estimate_payload_bytes(
+            self.search_request.with_payload.as_ref(),
+            self.search_request.limit,
+        )
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
@@ -55,13 +80,18 @@ impl StrictModeVerification for SearchRequest {
 }
 
 impl StrictModeVerification for SearchRequestBatch {
-    fn check_strict_mode(
+    /// Check each request of the batch, except for unindexed filters, which are checked for the
+    /// whole batch to report which request is rejected
+    fn check_custom(
         &self,
         collection: &Collection,
         strict_mode_config: &StrictModeConfig,
     ) -> Result<(), CollectionError> {
         for search_request in &self.searches {
-            search_request.check_strict_mode(collection, strict_mode_config)?;
+            search_request.check_custom(collection, strict_mode_config)?;
+            search_request.check_request_query_limit(strict_mode_config)?;
+            search_request.check_request_payload_limit(strict_mode_config)?;
+            search_request.check_request_filter_conditions(strict_mode_config)?;
         }
         Ok(())
     }
@@ -70,6 +100,10 @@ impl StrictModeVerification for SearchRequestBatch {
         None
     }
 
+    fn query_payload_limit(&self) -> Option<usize> {
+        None
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
@@ -81,4 +115,13 @@ impl StrictModeVerification for SearchRequestBatch {
     fn indexed_filter_write(&self) -> Option<&Filter> {
         None
     }
+
+    fn indexed_filters_read(&self) -> Option<Vec<&Filter>> {
+        Some(
+            self.searches
+                .iter()
+                .map(|search| search.search_request.filter.as_ref().unwrap_or(&NO_FILTER))
+                .collect(),
+        )
+    }
 }
@@ -0,0 +1,50 @@
+use segment::types::Filter;
+
+use super::StrictModeVerification;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::PointOperations;
+use crate::operations::vector_ops::VectorOperations;
+use crate::operations::CollectionUpdateOperations;
+
+impl StrictModeVerification for CollectionUpdateOperations {
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        None
+    }
+
+    fn query_payload_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        match self {
+            CollectionUpdateOperations::PointOperation(operation) => match operation {
+                PointOperations::DeletePointsByFilter(filter) => Some(filter),
+                PointOperations::UpsertPoints(_)
+                | PointOperations::InsertPointsIfAbsent(_)
+                | PointOperations::UpsertPointsIfVersion(_)
+                | PointOperations::UpsertPointsDedup(_)
+                | PointOperations::DeletePoints { .. }
+                | PointOperations::SyncPoints(_) => None,
+            },
+            CollectionUpdateOperations::VectorOperation(operation) => match operation {
+                VectorOperations::DeleteVectorsByFilter(filter, _) => Some(filter),
+                VectorOperations::UpdateVectors(_) | VectorOperations::DeleteVectors(..) => None,
+            },
+            CollectionUpdateOperations::PayloadOperation(operation) => match operation {
+                PayloadOps::SetPayload(op) | PayloadOps::OverwritePayload(op) => op.filter.as_ref(),
+                PayloadOps::DeletePayload(op) => op.filter.as_ref(),
+                PayloadOps::ClearPayloadByFilter(filter) => Some(filter),
+                PayloadOps::ClearPayload { .. } => None,
+            },
+            CollectionUpdateOperations::FieldIndexOperation(_) => None,
+        }
+    }
+}
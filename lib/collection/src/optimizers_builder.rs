@@ -12,13 +12,14 @@ use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
+use crate::common::segment_volumes::SegmentVolumes;
 use crate::config::CollectionParams;
 use crate::update_handler::Optimizer;
 
 const DEFAULT_MAX_SEGMENT_PER_CPU_KB: usize = 200_000;
 pub const DEFAULT_INDEXING_THRESHOLD_KB: usize = 20_000;
 const SEGMENTS_PATH: &str = "segments";
-const TEMP_SEGMENTS_PATH: &str = "temp_segments";
+pub(crate) const TEMP_SEGMENTS_PATH: &str = "temp_segments";
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct OptimizersConfig {
@@ -69,6 +70,13 @@ pub struct OptimizersConfig {
     #[serde(alias = "indexing_threshold_kb")]
     #[serde(default)]
     pub indexing_threshold: Option<usize>,
+    /// Minimal number of vectors in a segment, required to build a vector index for it.
+    /// Smaller segments keep the plain index, regardless of `indexing_threshold` and `memmap_threshold`,
+    /// as exact search is faster on them than HNSW.
+    ///
+    /// If not set, there is no minimum.
+    #[serde(default)]
+    pub indexing_min_vector_number: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: u64,
     /// Max number of threads (jobs) for running optimizations per shard.
@@ -89,6 +97,7 @@ impl OptimizersConfig {
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(100_000),
+            indexing_min_vector_number: None,
             flush_interval_sec: 60,
             max_optimization_threads: Some(0),
         }
@@ -120,6 +129,7 @@ impl OptimizersConfig {
         OptimizerThresholds {
             memmap_threshold_kb,
             indexing_threshold_kb,
+            indexing_min_vector_number: self.indexing_min_vector_number.unwrap_or_default(),
             max_segment_size_kb: self.get_max_segment_size_in_kilobytes(num_indexing_threads),
         }
     }
@@ -153,6 +163,7 @@ pub fn build_optimizers(
     optimizers_config: &OptimizersConfig,
     hnsw_config: &HnswConfig,
     quantization_config: &Option<QuantizationConfig>,
+    segment_volumes: &SegmentVolumes,
 ) -> Arc<Vec<Arc<Optimizer>>> {
     let num_indexing_threads = num_rayon_threads(hnsw_config.max_indexing_threads);
     let segments_path = shard_path.join(SEGMENTS_PATH);
@@ -160,41 +171,53 @@ pub fn build_optimizers(
     let threshold_config = optimizers_config.optimizer_thresholds(num_indexing_threads);
 
     Arc::new(vec![
-        Arc::new(MergeOptimizer::new(
-            optimizers_config.get_number_segments(),
-            threshold_config,
-            segments_path.clone(),
-            temp_segments_path.clone(),
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-        Arc::new(IndexingOptimizer::new(
-            optimizers_config.get_number_segments(),
-            threshold_config,
-            segments_path.clone(),
-            temp_segments_path.clone(),
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-        Arc::new(VacuumOptimizer::new(
-            optimizers_config.deleted_threshold,
-            optimizers_config.vacuum_min_vector_number,
-            threshold_config,
-            segments_path.clone(),
-            temp_segments_path.clone(),
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
-        Arc::new(ConfigMismatchOptimizer::new(
-            threshold_config,
-            segments_path,
-            temp_segments_path,
-            collection_params.clone(),
-            hnsw_config.clone(),
-            quantization_config.clone(),
-        )),
+        Arc::new(
+            MergeOptimizer::new(
+                optimizers_config.get_number_segments(),
+                threshold_config,
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_segment_volumes(segment_volumes.clone()),
+        ),
+        Arc::new(
+            IndexingOptimizer::new(
+                optimizers_config.get_number_segments(),
+                threshold_config,
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_segment_volumes(segment_volumes.clone()),
+        ),
+        Arc::new(
+            VacuumOptimizer::new(
+                optimizers_config.deleted_threshold,
+                optimizers_config.vacuum_min_vector_number,
+                threshold_config,
+                segments_path.clone(),
+                temp_segments_path.clone(),
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_segment_volumes(segment_volumes.clone()),
+        ),
+        Arc::new(
+            ConfigMismatchOptimizer::new(
+                threshold_config,
+                segments_path,
+                temp_segments_path,
+                collection_params.clone(),
+                hnsw_config.clone(),
+                quantization_config.clone(),
+            )
+            .with_segment_volumes(segment_volumes.clone()),
+        ),
     ])
 }
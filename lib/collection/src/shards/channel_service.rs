@@ -2,8 +2,9 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use api::grpc::qdrant::collections_internal_client::CollectionsInternalClient;
 use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
-use api::grpc::qdrant::WaitOnConsensusCommitRequest;
+use api::grpc::qdrant::{GetHashRingsRequest, WaitOnConsensusCommitRequest};
 use api::grpc::transport_channel_pool::{AddTimeout, TransportChannelPool};
 use futures::future::try_join_all;
 use futures::Future;
@@ -13,6 +14,8 @@ use tonic::transport::{Channel, Uri};
 use tonic::{Request, Status};
 use url::Url;
 
+use crate::collection::hash_ring_consistency::PeerHashRings;
+use crate::operations::conversions::hash_ring_state_from_grpc;
 use crate::operations::types::{CollectionError, CollectionResult, PeerMetadata};
 use crate::shards::shard::PeerId;
 
@@ -142,12 +145,7 @@ impl ChannelService {
         peer_id: PeerId,
         f: impl Fn(QdrantInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
     ) -> Result<T, CollectionError> {
-        let address = self
-            .id_to_address
-            .read()
-            .get(&peer_id)
-            .ok_or_else(|| CollectionError::service_error("Address for peer ID is not found."))?
-            .clone();
+        let address = self.peer_address(peer_id)?;
         self.channel_pool
             .with_channel(&address, |channel| {
                 let client = QdrantInternalClient::new(channel);
@@ -158,6 +156,59 @@ impl ChannelService {
             .map_err(Into::into)
     }
 
+    /// Get the hash rings of all shard keys of the given collection on the given peer
+    pub async fn get_hash_rings_on_peer(
+        &self,
+        peer_id: PeerId,
+        collection_name: &str,
+    ) -> CollectionResult<PeerHashRings> {
+        let response = self
+            .with_collections_client(peer_id, |mut client| async move {
+                client
+                    .get_hash_rings(GetHashRingsRequest {
+                        collection_name: collection_name.to_string(),
+                    })
+                    .await
+            })
+            .await
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Failed to get hash rings from peer {peer_id}: {err}"
+                ))
+            })?
+            .into_inner();
+
+        Ok(response
+            .hash_rings
+            .into_iter()
+            .map(hash_ring_state_from_grpc)
+            .collect())
+    }
+
+    async fn with_collections_client<T, O: Future<Output = Result<T, Status>>>(
+        &self,
+        peer_id: PeerId,
+        f: impl Fn(CollectionsInternalClient<InterceptedService<Channel, AddTimeout>>) -> O,
+    ) -> Result<T, CollectionError> {
+        let address = self.peer_address(peer_id)?;
+        self.channel_pool
+            .with_channel(&address, |channel| {
+                let client = CollectionsInternalClient::new(channel);
+                let client = client.max_decoding_message_size(usize::MAX);
+                f(client)
+            })
+            .await
+            .map_err(Into::into)
+    }
+
+    fn peer_address(&self, peer_id: PeerId) -> CollectionResult<Uri> {
+        self.id_to_address
+            .read()
+            .get(&peer_id)
+            .cloned()
+            .ok_or_else(|| CollectionError::service_error("Address for peer ID is not found."))
+    }
+
     /// Check whether all peers are running at least the given version
     ///
     /// If the version is not known for any peer, this returns `false`.
@@ -191,7 +242,16 @@ impl ChannelService {
             })?;
 
         // Construct REST URL from URI
-        let mut url = Url::parse(&local_peer_uri.to_string()).expect("Malformed URL");
+        let mut url = Url::parse(&local_peer_uri.to_string()).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Cannot determine REST address, malformed address {local_peer_uri} for peer ID {this_peer_id}: {err}",
+            ))
+        })?;
+        if url.host().is_none() {
+            return Err(CollectionError::service_error(format!(
+                "Cannot determine REST address, no host in address {url} for peer ID {this_peer_id}",
+            )));
+        }
         url.set_port(Some(self.current_rest_port))
             .map_err(|()| {
                 CollectionError::service_error(format!(
@@ -214,3 +274,38 @@ impl Default for ChannelService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn channel_service_with_address(peer_id: PeerId, address: &'static str) -> ChannelService {
+        let channel_service = ChannelService::new(6333, None);
+        channel_service
+            .id_to_address
+            .write()
+            .insert(peer_id, Uri::from_static(address));
+        channel_service
+    }
+
+    #[test]
+    fn test_current_rest_address() {
+        let channel_service = channel_service_with_address(1, "http://peer-1:6335");
+        let url = channel_service.current_rest_address(1).unwrap();
+        assert_eq!(url.as_str(), "http://peer-1:6333/");
+
+        // Unknown peer
+        assert!(channel_service.current_rest_address(2).is_err());
+    }
+
+    #[test]
+    fn test_current_rest_address_malformed() {
+        // No scheme and no host, not a valid absolute URL
+        let channel_service = channel_service_with_address(1, "/peer-1");
+        assert!(channel_service.current_rest_address(1).is_err());
+
+        // No scheme, host is parsed as scheme, can't have a port
+        let channel_service = channel_service_with_address(1, "peer-1:6335");
+        assert!(channel_service.current_rest_address(1).is_err());
+    }
+}
@@ -14,10 +14,13 @@ use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema, PointIdType, ScoredPoint};
 use tonic::Status;
 
-use crate::operations::conversions::write_ordering_to_proto;
+use crate::operations::conversions::{
+    upsert_mode_to_proto, vector_dedup_to_proto, write_ordering_to_proto,
+};
 use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    ConditionalUpsert, DedupUpsert, PointInsertOperationsInternal, PointSyncOperation, UpsertMode,
+    WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
@@ -55,6 +58,7 @@ pub fn internal_upsert_points(
     clock_tag: Option<ClockTag>,
     collection_name: String,
     point_insert_operations: PointInsertOperationsInternal,
+    mode: UpsertMode,
     wait: bool,
     ordering: Option<WriteOrdering>,
 ) -> CollectionResult<UpsertPointsInternal> {
@@ -73,10 +77,69 @@ pub fn internal_upsert_points(
             },
             ordering: ordering.map(write_ordering_to_proto),
             shard_key_selector: None,
+            mode: Some(upsert_mode_to_proto(mode)),
+            expected_versions: Vec::new(),
+            version_key: None,
+            dedup: None,
         }),
     })
 }
 
+pub fn internal_conditional_upsert_points(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    conditional_upsert: ConditionalUpsert,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> CollectionResult<UpsertPointsInternal> {
+    let ConditionalUpsert {
+        points,
+        expected_versions,
+        version_key,
+    } = conditional_upsert;
+
+    let mut request = internal_upsert_points(
+        shard_id,
+        clock_tag,
+        collection_name,
+        points,
+        UpsertMode::Upsert,
+        wait,
+        ordering,
+    )?;
+    if let Some(upsert_points) = &mut request.upsert_points {
+        upsert_points.expected_versions = expected_versions.into_iter().map(Into::into).collect();
+        upsert_points.version_key = Some(version_key.to_string());
+    }
+    Ok(request)
+}
+
+pub fn internal_dedup_upsert_points(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    dedup_upsert: DedupUpsert,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+) -> CollectionResult<UpsertPointsInternal> {
+    let DedupUpsert { points, policy } = dedup_upsert;
+
+    let mut request = internal_upsert_points(
+        shard_id,
+        clock_tag,
+        collection_name,
+        points,
+        UpsertMode::Upsert,
+        wait,
+        ordering,
+    )?;
+    if let Some(upsert_points) = &mut request.upsert_points {
+        upsert_points.dedup = Some(vector_dedup_to_proto(policy));
+    }
+    Ok(request)
+}
+
 pub fn internal_delete_points(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
@@ -1,15 +1,18 @@
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, Payload, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use tokio::runtime::Handle;
 
+use crate::operations::point_ops::{ConditionalUpsert, PointInsertOperationsInternal};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
@@ -36,6 +39,7 @@ impl DummyShard {
         _temp_path: &Path,
         _target_path: &Path,
         _save_wal: bool,
+        _stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         self.dummy()
     }
@@ -44,11 +48,34 @@ impl DummyShard {
         self.dummy()
     }
 
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        self.dummy()
+    }
+
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        self.dummy()
+    }
+
+    pub async fn check_expected_versions(
+        &self,
+        _conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        self.dummy()
+    }
+
+    pub async fn dedup_stored_vectors(
+        &self,
+        _points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        self.dummy()
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         LocalShardTelemetry {
             variant_name: Some("dummy shard".into()),
             segments: vec![],
             optimizations: Default::default(),
+            update_queue: Default::default(),
         }
     }
 
@@ -128,4 +155,13 @@ impl ShardOperation for DummyShard {
     ) -> CollectionResult<FacetResponse> {
         self.dummy()
     }
+
+    async fn distinct_values(
+        &self,
+        _: Arc<DistinctValuesParams>,
+        _search_runtime_handle: &Handle,
+        _: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        self.dummy()
+    }
 }
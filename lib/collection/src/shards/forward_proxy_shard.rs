@@ -1,13 +1,14 @@
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    ExtendedPointId, Filter, Payload, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
 };
 use tokio::runtime::Handle;
@@ -17,7 +18,8 @@ use super::shard::ShardId;
 use super::update_tracker::UpdateTracker;
 use crate::hash_ring::HashRingRouter;
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation,
+    ConditionalUpsert, PointInsertOperationsInternal, PointOperations, PointStruct,
+    PointSyncOperation,
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
@@ -109,6 +111,8 @@ impl ForwardProxyShard {
     /// Move batch of points to the remote shard.
     /// Returns an offset of the next batch to be transferred.
     ///
+    /// If `filter` is given, only points matching it are transferred.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -117,6 +121,7 @@ impl ForwardProxyShard {
         offset: Option<PointIdType>,
         batch_size: usize,
         hashring_filter: Option<&HashRingRouter>,
+        filter: Option<&Filter>,
         merge_points: bool,
         runtime_handle: &Handle,
     ) -> CollectionResult<Option<PointIdType>> {
@@ -130,7 +135,7 @@ impl ForwardProxyShard {
                 limit,
                 &WithPayloadInterface::Bool(true),
                 &true.into(),
-                None,
+                filter,
                 runtime_handle,
                 None,
                 None, // no timeout
@@ -160,7 +165,8 @@ impl ForwardProxyShard {
         // Use sync API to leverage potentially existing points
         // Normally use SyncPoints, to completely replace everything in the target shard
         // For resharding we need to merge points from multiple transfers, requiring a different operation
-        let point_operation = if !merge_points {
+        // A filtered transfer must not remove points in the target that don't match the filter
+        let point_operation = if !merge_points && filter.is_none() {
             PointOperations::SyncPoints(PointSyncOperation {
                 from_id: offset,
                 to_id: next_page_offset,
@@ -182,6 +188,41 @@ impl ForwardProxyShard {
         Ok(next_page_offset)
     }
 
+    /// Count points in the wrapped shard and in the remote shard.
+    ///
+    /// Updates are blocked while counting, so both counts are taken at the same point in time
+    /// from the perspective of this proxy.
+    ///
+    /// If `filter` is given, only points matching it are counted.
+    ///
+    /// Returns `(local_count, remote_count)`.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn count_transferred_points(
+        &self,
+        filter: Option<Filter>,
+        runtime_handle: &Handle,
+    ) -> CollectionResult<(usize, usize)> {
+        let _update_lock = self.update_lock.lock().await;
+        let request = Arc::new(CountRequestInternal {
+            filter,
+            exact: true,
+        });
+        let local_count = self
+            .wrapped_shard
+            .count(request.clone(), runtime_handle, None)
+            .await?
+            .count;
+        let remote_count = self
+            .remote_shard
+            .count(request, runtime_handle, None)
+            .await?
+            .count;
+        Ok((local_count, remote_count))
+    }
+
     pub fn deconstruct(self) -> (LocalShard, RemoteShard) {
         (self.wrapped_shard, self.remote_shard)
     }
@@ -192,9 +233,10 @@ impl ForwardProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, stopped)
             .await
     }
 
@@ -202,6 +244,34 @@ impl ForwardProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        self.wrapped_shard.reindex().await
+    }
+
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        self.wrapped_shard.cancel_reindex().await
+    }
+
+    pub async fn check_expected_versions(
+        &self,
+        conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        self.wrapped_shard
+            .check_expected_versions(conditional_upsert)
+            .await
+    }
+
+    pub async fn dedup_stored_vectors(
+        &self,
+        points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        self.wrapped_shard.dedup_stored_vectors(points).await
+    }
+
+    pub fn wal_last_index(&self) -> u64 {
+        self.wrapped_shard.wal_last_index()
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data(detail)
     }
@@ -409,4 +479,16 @@ impl ShardOperation for ForwardProxyShard {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .distinct_values(request, search_runtime_handle, timeout)
+            .await
+    }
 }
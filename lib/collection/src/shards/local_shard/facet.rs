@@ -5,7 +5,7 @@ use std::time::Duration;
 use futures::future;
 use futures::future::try_join_all;
 use itertools::{process_results, Itertools};
-use segment::data_types::facets::{FacetParams, FacetValue, FacetValueHit};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetValue, FacetValueHit};
 use segment::types::{Condition, FieldCondition, Filter, Match};
 use tokio::runtime::Handle;
 use tokio::time::error::Elapsed;
@@ -170,4 +170,68 @@ impl LocalShard {
 
         Ok(all_values)
     }
+
+    /// Returns the smallest unique values for the given distinct values request.
+    ///
+    /// Each segment only contributes its smallest values up to the limit, and the values merged
+    /// across segments are truncated to the limit as well.
+    pub async fn smallest_unique_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        let limit = request.limit.unwrap_or(DistinctValuesParams::DEFAULT_LIMIT);
+
+        let stopping_guard = StoppingGuard::new();
+
+        let spawn_read = |segment: LockedSegment| {
+            let request = Arc::clone(&request);
+            let is_stopped = stopping_guard.get_is_stopped();
+
+            handle.spawn_blocking(move || {
+                let get_segment = segment.get();
+                let read_segment = get_segment.read();
+
+                read_segment
+                    .unique_values(&request.key, request.filter.as_ref(), &is_stopped)
+                    .map(|values| values.into_iter().take(limit).collect_vec())
+            })
+        };
+
+        let all_reads = {
+            let segments_lock = self.segments().read();
+
+            tokio::time::timeout(
+                timeout,
+                try_join_all(
+                    segments_lock
+                        .non_appendable_then_appendable_segments()
+                        .map(spawn_read),
+                ),
+            )
+        }
+        .await
+        .map_err(|_: Elapsed| {
+            CollectionError::timeout(timeout.as_secs() as usize, "distinct values")
+        })??;
+
+        let values = process_results(all_reads, |reads| {
+            reads.fold(BTreeSet::new(), |mut values, segment_values| {
+                values.extend(segment_values);
+                truncate_set(&mut values, limit);
+                values
+            })
+        })?;
+
+        Ok(values.into_iter().collect())
+    }
+}
+
+/// Keep only the `limit` smallest values of the set
+pub(crate) fn truncate_set<T: Ord>(values: &mut BTreeSet<T>, limit: usize) {
+    while values.len() > limit {
+        values.pop_last();
+    }
 }
@@ -1,16 +1,25 @@
 pub mod clock_map;
 pub mod disk_usage_watcher;
-pub(super) mod facet;
+pub(crate) mod facet;
 pub(super) mod query;
+pub(super) mod recall;
+pub mod retrieve_cache;
 pub(super) mod scroll;
 pub(super) mod search;
+#[cfg(feature = "segment-debug")]
+pub(super) mod segment_debug;
 pub(super) mod shard_ops;
+pub mod text_vocab;
+pub mod update_limiter;
+pub mod update_stats;
+pub mod vector_hashes;
+pub mod wal_quarantine;
 
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -22,14 +31,15 @@ use common::types::TelemetryDetail;
 use indicatif::{ProgressBar, ProgressStyle};
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
+use segment::common::operation_error::OperationResult;
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
 use segment::segment::Segment;
 use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::{
-    CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    CompressionRatio, Filter, Payload, PayloadIndexInfo, PayloadKeyType, PayloadStorageType,
+    PointIdType, QuantizationConfig, SegmentConfig, SegmentType, SeqNumberType,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{copy, create_dir_all, remove_dir_all, remove_file};
@@ -40,27 +50,39 @@ use wal::{Wal, WalOptions};
 
 use self::clock_map::{ClockMap, RecoveryPoint};
 use self::disk_usage_watcher::DiskUsageWatcher;
+use self::retrieve_cache::RetrieveCache;
+use self::update_limiter::UpdateLimiter;
+use self::update_stats::UpdateStats;
+use self::vector_hashes::VectorHashes;
+use self::wal_quarantine::QuarantinedOperation;
 use super::update_tracker::UpdateTracker;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{
-    LockedSegment, LockedSegmentHolder, SegmentHolder,
+    LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
 use crate::collection_manager::optimizers::TrackerLog;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
+use crate::collection_manager::segments_updater::check_expected_versions;
 use crate::common::file_utils::{move_dir, move_file};
+use crate::common::segment_volumes::SegmentVolumes;
+use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
+use crate::operations::point_ops::{ConditionalUpsert, PointInsertOperationsInternal};
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionInfoInternal,
     CollectionResult, CollectionStatus, OptimizersStatus,
 };
+use crate::operations::vector_dedup::{point_vectors, remove_stored_duplicates, StoredVectors};
 use crate::operations::OperationWithClockTag;
-use crate::optimizers_builder::{build_optimizers, clear_temp_segments, OptimizersConfig};
+use crate::optimizers_builder::{
+    build_optimizers, clear_temp_segments, OptimizersConfig, TEMP_SEGMENTS_PATH,
+};
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_config::{ShardConfig, SHARD_CONFIG_FILE};
-use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
+use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry, UpdateQueueTelemetry};
 use crate::shards::CollectionId;
 use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
@@ -69,6 +91,10 @@ use crate::wal_delta::{LockedWal, RecoverableWal};
 /// If rendering WAL load progression in basic text form, report progression every 60 seconds.
 const WAL_LOAD_REPORT_EVERY: Duration = Duration::from_secs(60);
 
+/// Number of attempts to apply a WAL operation failing with a transient error on load, before
+/// giving up on it.
+const WAL_REPLAY_ATTEMPTS: usize = 3;
+
 /// LocalShard
 ///
 /// LocalShard is an entity that can be moved between peers and contains some part of one collections data.
@@ -82,13 +108,23 @@ pub struct LocalShard {
     pub(super) wal: RecoverableWal,
     pub(super) update_handler: Arc<Mutex<UpdateHandler>>,
     pub(super) update_sender: ArcSwap<Sender<UpdateSignal>>,
+    /// Bounds update operations which are accepted, but not yet applied
+    pub(super) update_limiter: UpdateLimiter,
     pub(super) update_tracker: UpdateTracker,
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
+    /// Rate and latency of recently applied update operations
+    update_stats: Arc<ParkingMutex<UpdateStats>>,
+    /// Recently retrieved points, invalidated by the update handler
+    pub(super) retrieve_cache: Arc<RetrieveCache>,
+    /// Hashes of stored vectors to find duplicates, updated by the update handler
+    vector_hashes: Arc<VectorHashes>,
     update_runtime: Handle,
     pub(super) search_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
+    /// Set if WAL operations were quarantined on load, the shard misses their changes
+    wal_quarantined: AtomicBool,
 }
 
 /// Shard holds information about segments and WAL.
@@ -117,17 +153,22 @@ impl LocalShard {
     /// Clear local shard related data.
     ///
     /// Do NOT remove config file.
-    pub async fn clear(shard_path: &Path) -> CollectionResult<()> {
+    pub async fn clear(
+        shard_path: &Path,
+        segment_volumes: &SegmentVolumes,
+    ) -> CollectionResult<()> {
         // Delete WAL
         let wal_path = Self::wal_path(shard_path);
         if wal_path.exists() {
             remove_dir_all(wal_path).await?;
         }
 
-        // Delete segments
+        // Delete segments, including the ones placed on other volumes
         let segments_path = Self::segments_path(shard_path);
         if segments_path.exists() {
-            remove_dir_all(segments_path).await?;
+            segment_volumes
+                .remove_dir_with_linked_segments(&segments_path)
+                .await?;
         }
 
         LocalShardClocks::delete_data(shard_path).await?;
@@ -153,6 +194,11 @@ impl LocalShard {
         let config = collection_config.read().await;
         let locked_wal = Arc::new(ParkingMutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
+        let update_stats = Arc::new(ParkingMutex::new(Default::default()));
+        let retrieve_cache = Arc::new(RetrieveCache::new(
+            shared_storage_config.retrieve_cache_size,
+        ));
+        let vector_hashes = Arc::new(VectorHashes::default());
 
         // default to 2x the WAL capacity
         let disk_buffer_threshold_mb =
@@ -169,6 +215,9 @@ impl LocalShard {
             payload_index_schema.clone(),
             optimizers.clone(),
             optimizers_log.clone(),
+            update_stats.clone(),
+            retrieve_cache.clone(),
+            vector_hashes.clone(),
             optimizer_cpu_budget.clone(),
             update_runtime.clone(),
             segment_holder.clone(),
@@ -183,6 +232,8 @@ impl LocalShard {
             mpsc::channel(shared_storage_config.update_queue_size);
         update_handler.run_workers(update_receiver);
 
+        let update_limiter = UpdateLimiter::new(shared_storage_config.max_in_flight_updates);
+
         let update_tracker = segment_holder.read().update_tracker();
 
         drop(config); // release `shared_config` from borrow checker
@@ -195,16 +246,94 @@ impl LocalShard {
             wal: RecoverableWal::new(locked_wal, clocks.newest_clocks, clocks.oldest_clocks),
             update_handler: Arc::new(Mutex::new(update_handler)),
             update_sender: ArcSwap::from_pointee(update_sender),
+            update_limiter,
             update_tracker,
             path: shard_path.to_owned(),
             update_runtime,
             search_runtime,
             optimizers,
             optimizers_log,
+            update_stats,
+            retrieve_cache,
+            vector_hashes,
             disk_usage_watcher,
+            wal_quarantined: AtomicBool::new(false),
         }
     }
 
+    /// Check expected point versions of a conditional upsert
+    ///
+    /// Waits for all updates accepted so far to be applied, then compares versions stored in the
+    /// points. Called by the leader replica before the update is sent to any replica.
+    pub async fn check_expected_versions(
+        &self,
+        conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        check_expected_versions(&self.segments.read(), conditional_upsert)
+    }
+
+    /// Remove points of an upsert, which have exactly the same vectors as a point stored in this
+    /// shard
+    ///
+    /// Waits for all updates accepted so far to be applied, then looks up stored points by the
+    /// hash of their vectors. Called by the leader replica before the upsert is sent to any
+    /// replica. Stored points, which are overwritten by the upsert, are not duplicates.
+    ///
+    /// Returns the id of the stored duplicate and the payload of every removed point.
+    pub async fn dedup_stored_vectors(
+        &self,
+        points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        let (tx, rx) = oneshot::channel();
+        self.update_sender
+            .load()
+            .send(UpdateSignal::Plunger(tx))
+            .await?;
+        rx.await?;
+
+        let vector_data = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .to_base_vector_data()?;
+        let lookups: Vec<_> = point_vectors(points)
+            .iter()
+            .map(|vector| StoredVectors::new(vector, &vector_data))
+            .collect();
+        let upserted_ids: HashSet<_> = points.point_ids().into_iter().collect();
+
+        let segments = self.segments.clone();
+        let vector_hashes = self.vector_hashes.clone();
+        let duplicate_of = tokio::task::spawn_blocking(move || {
+            let segments = segments.read();
+            lookups
+                .iter()
+                .map(|vectors| match vectors {
+                    Some(vectors) => vector_hashes.find(&segments, vectors, &upserted_ids),
+                    None => Ok(None),
+                })
+                .collect::<OperationResult<Vec<_>>>()
+        })
+        .await??;
+
+        let stored_ids: Vec<_> = duplicate_of.iter().flatten().copied().collect();
+        let payloads = remove_stored_duplicates(points, &duplicate_of);
+        Ok(stored_ids.into_iter().zip(payloads).collect())
+    }
+
+    /// Number of update operations accepted by this shard, but not yet applied.
+    pub fn in_flight_updates(&self) -> usize {
+        self.update_limiter.in_flight()
+    }
+
     pub(super) fn segments(&self) -> &RwLock<SegmentHolder> {
         self.segments.deref()
     }
@@ -228,11 +357,29 @@ impl LocalShard {
         let wal_path = Self::wal_path(shard_path);
         let segments_path = Self::segments_path(shard_path);
 
-        let wal: SerdeWal<OperationWithClockTag> = SerdeWal::new(
-            wal_path.to_str().unwrap(),
-            (&collection_config_read.wal_config).into(),
-        )
-        .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?;
+        // Disabled WAL is created once segments are loaded, it continues their operation numbers
+        let wal: Option<SerdeWal<OperationWithClockTag>> =
+            if collection_config_read.wal_config.wal_disabled {
+                None
+            } else {
+                let wal = SerdeWal::new(
+                    wal_path.to_str().unwrap(),
+                    (&collection_config_read.wal_config).into(),
+                )
+                .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?
+                .with_fsync_policy(collection_config_read.wal_config.fsync_policy);
+                Some(wal)
+            };
+
+        shared_storage_config
+            .segment_volumes
+            .remove_interrupted_moves(&segments_path)
+            .map_err(|err| {
+                CollectionError::service_error(format!(
+                    "Can't remove leftovers of interrupted segment moves due to {err}\nat {}",
+                    segments_path.display(),
+                ))
+            })?;
 
         let segment_dirs = std::fs::read_dir(&segments_path).map_err(|err| {
             CollectionError::service_error(format!(
@@ -244,6 +391,9 @@ impl LocalShard {
 
         let mut load_handlers = vec![];
 
+        // Stops loading segments if loading of the shard is cancelled, e.g. on timeout
+        let stopping_guard = StoppingGuard::new();
+
         // This semaphore is used to limit the number of threads that load segments concurrently.
         // Uncomment it if you need to debug segment loading.
         // let semaphore = Arc::new(parking_lot::Mutex::new(()));
@@ -251,13 +401,14 @@ impl LocalShard {
         for entry in segment_dirs {
             let segments_path = entry.unwrap().path();
             let payload_index_schema = payload_index_schema.clone();
+            let stopped = stopping_guard.get_is_stopped();
             // let semaphore_clone = semaphore.clone();
             load_handlers.push(
                 thread::Builder::new()
                     .name(format!("shard-load-{collection_id}-{id}"))
                     .spawn(move || {
                         // let _guard = semaphore_clone.lock();
-                        let mut res = load_segment(&segments_path, &AtomicBool::new(false))?;
+                        let mut res = load_segment(&segments_path, &stopped)?;
                         if let Some(segment) = &mut res {
                             segment.check_consistency_and_repair()?;
                             segment.update_all_field_indices(
@@ -278,8 +429,17 @@ impl LocalShard {
 
         let mut segment_holder = SegmentHolder::default();
 
-        for handler in load_handlers {
-            let segment = handler.join().map_err(|err| {
+        // Wait for segments without blocking the runtime, so that loading can be cancelled
+        let load_results = tokio::task::spawn_blocking(move || {
+            load_handlers
+                .into_iter()
+                .map(|handler| handler.join())
+                .collect::<Vec<_>>()
+        })
+        .await?;
+
+        for load_result in load_results {
+            let segment = load_result.map_err(|err| {
                 CollectionError::service_error(format!(
                     "Can't join segment load thread: {:?}",
                     err.type_id()
@@ -315,6 +475,23 @@ impl LocalShard {
             log::debug!("Deduplicated {} points", res);
         }
 
+        let wal = match wal {
+            Some(wal) => wal,
+            None => {
+                let next_op_num = segment_holder
+                    .iter()
+                    .map(|(_id, segment)| segment.get().read().version())
+                    .max()
+                    .map_or(0, |version| version + 1);
+                SerdeWal::new_disabled(
+                    wal_path.to_str().unwrap(),
+                    (&collection_config_read.wal_config).into(),
+                    next_op_num,
+                )
+                .map_err(|e| CollectionError::service_error(format!("Wal error: {e}")))?
+            }
+        };
+
         clear_temp_segments(shard_path);
         let optimizers = build_optimizers(
             shard_path,
@@ -322,6 +499,7 @@ impl LocalShard {
             &effective_optimizers_config,
             &collection_config_read.hnsw_config,
             &collection_config_read.quantization_config,
+            &shared_storage_config.segment_volumes,
         );
 
         drop(collection_config_read); // release `shared_config` from borrow checker
@@ -363,6 +541,8 @@ impl LocalShard {
         // Apply outstanding operations from WAL
         local_shard.load_from_wal(collection_id).await?;
 
+        local_shard.spawn_segment_rebalance(format!("{collection_id}:{id}"));
+
         let available_memory_bytes = Mem::new().available_memory_bytes() as usize;
         let vectors_size_bytes = local_shard.estimate_vector_data_size().await;
 
@@ -391,6 +571,46 @@ impl LocalShard {
         self.path.clone()
     }
 
+    /// Move segments between storage volumes in the background, to even out their available space.
+    ///
+    /// Moved segments are copied while loaded, and replaced by their copies in the segment
+    /// holder. Stops once the shard is dropped.
+    fn spawn_segment_rebalance(&self, shard_name: String) {
+        let segment_volumes = self.shared_storage_config.segment_volumes.clone();
+        if segment_volumes.is_empty() {
+            return;
+        }
+
+        let segments = Arc::downgrade(&self.segments);
+        let segments_path = Self::segments_path(&self.path);
+        let temp_path = self.path.join(TEMP_SEGMENTS_PATH);
+
+        self.update_runtime.spawn_blocking(move || {
+            let result = segment_volumes.rebalance(
+                &segments_path,
+                &temp_path,
+                fs4::available_space,
+                |path| match segments.upgrade() {
+                    Some(segments) => prepare_segment_move(&segments, path),
+                    None => Ok(None),
+                },
+                |prepared, path, copy_path| match segments.upgrade() {
+                    Some(segments) => replace_moved_segment(&segments, prepared, path, copy_path),
+                    None => Ok(false),
+                },
+            );
+            match result {
+                Ok(0) => {}
+                Ok(moved) => log::info!(
+                    "Moved {moved} segments of shard {shard_name} between storage volumes",
+                ),
+                Err(err) => log::warn!(
+                    "Failed to move segments of shard {shard_name} between storage volumes: {err}",
+                ),
+            }
+        });
+    }
+
     pub fn wal_path(shard_path: &Path) -> PathBuf {
         shard_path.join("wal")
     }
@@ -506,8 +726,12 @@ impl LocalShard {
             segment_holder.add_new(segment);
         }
 
-        let wal: SerdeWal<OperationWithClockTag> =
-            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
+        let wal: SerdeWal<OperationWithClockTag> = if config.wal_config.wal_disabled {
+            SerdeWal::new_disabled(wal_path.to_str().unwrap(), (&config.wal_config).into(), 0)?
+        } else {
+            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?
+                .with_fsync_policy(config.wal_config.fsync_policy)
+        };
 
         let optimizers = build_optimizers(
             shard_path,
@@ -515,6 +739,7 @@ impl LocalShard {
             &effective_optimizers_config,
             &config.hnsw_config,
             &config.quantization_config,
+            &shared_storage_config.segment_volumes,
         );
 
         drop(config); // release `shared_config` from borrow checker
@@ -547,6 +772,109 @@ impl LocalShard {
         update_handler.wait_workers_stops().await
     }
 
+    /// Read and apply a single WAL operation on load
+    ///
+    /// Propagates errors which should fail the load, other errors are only logged.
+    fn replay_wal_operation(
+        &self,
+        wal: &SerdeWal<OperationWithClockTag>,
+        newest_clocks: &mut ClockMap,
+        op_num: u64,
+        collection_id: &CollectionId,
+    ) -> CollectionResult<()> {
+        let update = wal.try_read_entry(op_num)?;
+
+        if let Some(clock_tag) = update.clock_tag {
+            newest_clocks.advance_clock(clock_tag);
+        }
+
+        // Propagate `CollectionError::ServiceError`, but skip other error types.
+        match CollectionUpdater::update(self.segments(), op_num, update.operation) {
+            Err(CollectionError::ServiceError { error, backtrace }) => {
+                let path = self.path.display();
+
+                log::error!(
+                    "Can't apply WAL operation: {error}, \
+                     collection: {collection_id}, \
+                     shard: {path}, \
+                     op_num: {op_num}"
+                );
+
+                if let Some(backtrace) = &backtrace {
+                    log::error!("Backtrace: {}", backtrace);
+                }
+
+                Err(CollectionError::ServiceError { error, backtrace })
+            }
+            Err(err @ CollectionError::OutOfMemory { .. }) => {
+                log::error!("{err}");
+                Err(err)
+            }
+            Err(err @ CollectionError::NotFound { .. }) => {
+                log::warn!("{err}");
+                Ok(())
+            }
+            Err(err) => {
+                log::error!("{err}");
+                Ok(())
+            }
+            Ok(_) => Ok(()),
+        }
+    }
+
+    /// Whether WAL operations were quarantined when the shard was loaded
+    ///
+    /// The shard then misses changes of these operations, and should be recovered from another
+    /// replica.
+    pub fn has_quarantined_operations(&self) -> bool {
+        self.wal_quarantined.load(Ordering::Relaxed)
+    }
+
+    /// Skip a WAL operation, which keeps failing on load, and record it in the shard directory
+    ///
+    /// The shard is flagged as missing changes, see [`Self::has_quarantined_operations`].
+    fn quarantine_wal_operation(
+        &self,
+        wal: &SerdeWal<OperationWithClockTag>,
+        op_num: u64,
+        err: &CollectionError,
+    ) -> CollectionResult<()> {
+        log::error!(
+            "Quarantining WAL operation {op_num} of shard {}, which can't be applied: {err}",
+            self.path.display(),
+        );
+
+        wal_quarantine::quarantine_operation(
+            &self.path,
+            QuarantinedOperation {
+                op_num,
+                error: err.to_string(),
+                operation: wal.try_read_entry(op_num).ok(),
+            },
+        )?;
+        self.wal_quarantined.store(true, Ordering::Relaxed);
+
+        // Segments reject newer operations until the failed one succeeds, and the update
+        // worker would retry it, forget about the failure instead. The replica set takes care
+        // of recovering the shard, which is flagged above.
+        let mut segments = self.segments.write();
+        segments.failed_operation.remove(&op_num);
+        for (_idx, segment) in segments.iter() {
+            if let LockedSegment::Original(raw_segment) = segment {
+                let mut raw_segment = raw_segment.write();
+                let failed_on_op = raw_segment
+                    .error_status
+                    .as_ref()
+                    .is_some_and(|failed| failed.version == op_num);
+                if failed_on_op {
+                    raw_segment.error_status = None;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Loads latest collection operations from WAL
     pub async fn load_from_wal(&self, collection_id: CollectionId) -> CollectionResult<()> {
         let mut newest_clocks = self.wal.newest_clocks.lock().await;
@@ -559,7 +887,6 @@ impl LocalShard {
         bar.set_style(progress_style);
 
         bar.set_message(format!("Recovering collection {collection_id}"));
-        let segments = self.segments();
 
         // Fall back to basic text output if the progress bar is hidden (e.g. not a tty)
         let show_progress_bar = !bar.is_hidden();
@@ -584,36 +911,33 @@ impl LocalShard {
         // (`SerdeWal::read_all` may even start reading WAL from some already truncated
         // index *occasionally*), but the storage can handle it.
 
-        for (op_num, update) in wal.read_all(false) {
-            if let Some(clock_tag) = update.clock_tag {
-                newest_clocks.advance_clock(clock_tag);
-            }
-
-            // Propagate `CollectionError::ServiceError`, but skip other error types.
-            match &CollectionUpdater::update(segments, op_num, update.operation) {
-                Err(err @ CollectionError::ServiceError { error, backtrace }) => {
-                    let path = self.path.display();
-
-                    log::error!(
-                        "Can't apply WAL operation: {error}, \
-                         collection: {collection_id}, \
-                         shard: {path}, \
-                         op_num: {op_num}"
-                    );
-
-                    if let Some(backtrace) = &backtrace {
-                        log::error!("Backtrace: {}", backtrace);
+        let mut last_op_num = None;
+
+        let first_index = wal.first_index();
+        for op_num in first_index..first_index + wal.len(false) {
+            last_op_num = Some(op_num);
+
+            // Retry operations failing with transient errors, in lenient mode skip the ones
+            // which can't be applied
+            let mut attempt = 1;
+            loop {
+                match self.replay_wal_operation(&wal, &mut newest_clocks, op_num, &collection_id) {
+                    Ok(()) => break,
+                    Err(err @ CollectionError::OutOfMemory { .. }) => return Err(err),
+                    Err(err)
+                        if attempt < WAL_REPLAY_ATTEMPTS && is_transient_replay_error(&err) =>
+                    {
+                        log::warn!(
+                            "Retrying WAL operation {op_num}, attempt {attempt} failed: {err}"
+                        );
+                        attempt += 1;
                     }
-
-                    return Err(err.clone());
-                }
-                Err(err @ CollectionError::OutOfMemory { .. }) => {
-                    log::error!("{err}");
-                    return Err(err.clone());
+                    Err(err) if self.shared_storage_config.lenient_wal_recovery => {
+                        self.quarantine_wal_operation(&wal, op_num, &err)?;
+                        break;
+                    }
+                    Err(err) => return Err(err),
                 }
-                Err(err @ CollectionError::NotFound { .. }) => log::warn!("{err}"),
-                Err(err) => log::error!("{err}"),
-                Ok(_) => (),
             }
 
             // Update progress bar or show text progress every WAL_LOAD_REPORT_EVERY
@@ -652,6 +976,10 @@ impl LocalShard {
             segments.flush_all(true, true)?;
         }
 
+        if let Some(op_num) = last_op_num {
+            self.update_stats.lock().record_applied_op_num(op_num);
+        }
+
         bar.finish();
         if !show_progress_bar {
             log::info!(
@@ -714,6 +1042,7 @@ impl LocalShard {
             &config.optimizer_config,
             &config.hnsw_config,
             &config.quantization_config,
+            &self.shared_storage_config.segment_volumes,
         );
         update_handler.optimizers = new_optimizers;
         update_handler.flush_interval_sec = config.optimizer_config.flush_interval_sec;
@@ -724,6 +1053,27 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Rebuild the vector indices of all indexed segments with the current configuration
+    ///
+    /// Segments are rebuilt online by the optimizers, the shard stays available for reads and
+    /// writes meanwhile. Returns the number of segments scheduled for rebuilding.
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        let scheduled_segments = self.segments.write().request_reindex();
+        self.update_sender.load().send(UpdateSignal::Nop).await?;
+        Ok(scheduled_segments)
+    }
+
+    /// Cancel rebuilding of vector indices requested by [`LocalShard::reindex`]
+    ///
+    /// Running optimizations rebuilding the requested segments are stopped, unfinished segments
+    /// keep their previous index. Other optimizations are not affected.
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        self.segments.write().cancel_reindex();
+        // Wake up the optimizers to clean up stopped optimizations
+        self.update_sender.load().send(UpdateSignal::Nop).await?;
+        Ok(())
+    }
+
     /// Finishes ongoing update tasks
     pub async fn stop_gracefully(&self) {
         if let Err(err) = self.update_sender.load().send(UpdateSignal::Stop).await {
@@ -735,6 +1085,13 @@ impl LocalShard {
         if let Err(err) = self.wait_update_workers_stop().await {
             log::warn!("Update workers failed with: {}", err);
         }
+
+        // Without WAL, applied operations are only persisted by flushing segments
+        if self.wal.wal.lock().is_disabled() {
+            if let Err(err) = self.segments.read().flush_all(true, false) {
+                log::warn!("Failed to flush segments of shard without WAL: {err}");
+            }
+        }
     }
 
     pub fn restore_snapshot(snapshot_path: &Path) -> CollectionResult<()> {
@@ -761,11 +1118,13 @@ impl LocalShard {
     }
 
     /// Create snapshot for local shard into `target_path`
+    /// Snapshot creation is cancelled between segments once `stopped` is set.
     pub async fn create_snapshot(
         &self,
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         let snapshot_shard_path = target_path;
 
@@ -801,6 +1160,7 @@ impl LocalShard {
                 &payload_index_schema.read().clone(),
                 &temp_path,
                 &snapshot_segments_shard_path,
+                &stopped,
             )?;
 
             if save_wal {
@@ -822,6 +1182,11 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Last operation sequence number in the WAL of this shard.
+    pub fn wal_last_index(&self) -> u64 {
+        self.wal.wal.lock().last_index()
+    }
+
     /// Create empty WAL which is compatible with currently stored data
     pub fn snapshot_empty_wal(wal: LockedWal, snapshot_shard_path: &Path) -> CollectionResult<()> {
         let (segment_capacity, latest_op_num) = {
@@ -905,9 +1270,9 @@ impl LocalShard {
             .map(|(_id, segment)| segment.get().read().get_telemetry_data(detail))
             .collect();
 
-        let optimizer_status = match &segments_read_guard.optimizer_errors {
+        let optimizer_status = match segments_read_guard.optimizer_health() {
             None => OptimizersStatus::Ok,
-            Some(error) => OptimizersStatus::Error(error.to_string()),
+            Some((_, error)) => OptimizersStatus::Error(error),
         };
         drop(segments_read_guard);
         let optimizations = self
@@ -929,6 +1294,32 @@ impl LocalShard {
                 optimizations,
                 log: self.optimizers_log.lock().to_telemetry(),
             },
+            update_queue: self.get_update_queue_telemetry(),
+        }
+    }
+
+    fn get_update_queue_telemetry(&self) -> UpdateQueueTelemetry {
+        let (last_wal_sequence, acknowledged_sequence) = {
+            let wal = self.wal.wal.lock();
+            if wal.len(true) > 0 {
+                // Operations before the first index of the logical WAL are applied and flushed
+                (Some(wal.last_index()), wal.first_index().checked_sub(1))
+            } else {
+                (None, None)
+            }
+        };
+
+        let update_stats = self.update_stats.lock();
+        UpdateQueueTelemetry {
+            queue_depth: self.in_flight_updates(),
+            ops_per_sec: update_stats.ops_per_sec(),
+            avg_apply_latency_ms: update_stats
+                .avg_apply_latency()
+                .map(|latency| latency.as_secs_f64() * 1000.0),
+            applied_sequence: update_stats
+                .last_applied_op_num()
+                .max(acknowledged_sequence),
+            last_wal_sequence,
         }
     }
 
@@ -999,12 +1390,13 @@ impl LocalShard {
                         .or_insert(val);
                 }
             }
-            if !segments.failed_operation.is_empty() || segments.optimizer_errors.is_some() {
+            if !segments.failed_operation.is_empty() {
                 status = CollectionStatus::Red;
             }
 
-            if let Some(error) = &segments.optimizer_errors {
-                optimizer_status = OptimizersStatus::Error(error.to_string());
+            if let Some((optimizer_health, error)) = segments.optimizer_health() {
+                status = status.max(optimizer_health);
+                optimizer_status = OptimizersStatus::Error(error);
             }
         }
 
@@ -1015,9 +1407,8 @@ impl LocalShard {
             // TODO(1.10): enable grey status in Qdrant 1.10+
             // status = CollectionStatus::Grey;
             if optimizer_status == OptimizersStatus::Ok {
-                optimizer_status = OptimizersStatus::Error(
-                    "optimizations pending, awaiting update operation".into(),
-                );
+                optimizer_status =
+                    OptimizersStatus::error("optimizations pending, awaiting update operation");
             }
         }
 
@@ -1067,6 +1458,89 @@ impl Drop for LocalShard {
     }
 }
 
+/// Whether applying a WAL operation again may succeed, if it failed with this error on load
+///
+/// Operations which can't be read from the WAL or are rejected by the segments fail the same way
+/// on every attempt. I/O errors only keep their message when converted into service errors.
+fn is_transient_replay_error(err: &CollectionError) -> bool {
+    match err {
+        CollectionError::Timeout { .. } | CollectionError::Cancelled { .. } => true,
+        CollectionError::ServiceError { error, .. } => {
+            error.starts_with("IO Error") || error.starts_with("File IO error")
+        }
+        _ => false,
+    }
+}
+
+/// Flush the segment at `path` before it is copied to another storage volume.
+///
+/// Returns the ID and version of the segment, or `None` if it must not be moved. Only
+/// non-appendable segments are moved, others keep receiving updates while being copied.
+fn prepare_segment_move(
+    segments: &RwLock<SegmentHolder>,
+    path: &Path,
+) -> std::io::Result<Option<(SegmentId, SeqNumberType)>> {
+    let segments = segments.read();
+    let Some((segment_id, segment)) = segments.iter().find_map(|(segment_id, segment)| {
+        let LockedSegment::Original(segment) = segment else {
+            return None;
+        };
+        is_segment_at(&segment.read(), path).then(|| (*segment_id, segment.clone()))
+    }) else {
+        return Ok(None);
+    };
+
+    let segment = segment.read();
+    if segment.is_appendable() || segment.segment_type() == SegmentType::Special {
+        return Ok(None);
+    }
+    let version = segment.version();
+    segment.flush(true, false).map_err(std::io::Error::other)?;
+    Ok(Some((segment_id, version)))
+}
+
+/// Whether the segment is stored at `path` in the segments directory.
+fn is_segment_at(segment: &Segment, path: &Path) -> bool {
+    // Optimized segments placed on a volume are loaded from there, not through their link
+    let segment_path = segment.data_path();
+    segment_path == path
+        || matches!(
+            (segment_path.canonicalize(), path.canonicalize()),
+            (Ok(segment_path), Ok(path)) if segment_path == path,
+        )
+}
+
+/// Replace the segment at `path` by its copy at `copy_path`, if it was not changed since it was
+/// prepared to be moved.
+fn replace_moved_segment(
+    segments: &RwLock<SegmentHolder>,
+    (segment_id, version): (SegmentId, SeqNumberType),
+    path: &Path,
+    copy_path: &Path,
+) -> std::io::Result<bool> {
+    let Some(copy) =
+        load_segment(copy_path, &AtomicBool::new(false)).map_err(std::io::Error::other)?
+    else {
+        return Ok(false);
+    };
+
+    // Updates hold the segment holder while they are applied, none can be in progress now
+    let mut segments = segments.write();
+    let unchanged = match segments.get(segment_id) {
+        Some(LockedSegment::Original(segment)) => {
+            let segment = segment.read();
+            is_segment_at(&segment, path) && segment.version() == version
+        }
+        _ => false,
+    };
+    if !unchanged || copy.version() != version {
+        return Ok(false);
+    }
+
+    segments.swap_new(copy, &[segment_id]);
+    Ok(true)
+}
+
 /// Convenience struct for combining clock maps belonging to a shard
 ///
 /// Holds a clock map for tracking the highest clocks and the cutoff clocks.
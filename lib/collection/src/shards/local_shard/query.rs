@@ -62,7 +62,7 @@ impl LocalShard {
         let start_time = std::time::Instant::now();
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
-        let searches_f = self.do_search(
+        let searches_f = self.do_search_with_tie_break(
             Arc::new(CoreSearchRequestBatch {
                 searches: request.searches,
             }),
@@ -279,7 +279,7 @@ impl LocalShard {
                     searches: vec![search_request],
                 };
 
-                self.do_search(
+                self.do_search_with_tie_break(
                     Arc::new(rescoring_core_search_request),
                     search_runtime_handle,
                     Some(timeout),
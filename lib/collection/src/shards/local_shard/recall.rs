@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use itertools::Itertools;
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::types::{SearchParams, WithPayloadInterface, WithVector};
+use tokio::runtime::Handle;
+
+use super::LocalShard;
+use crate::collection::recall_monitor::RecallMonitorConfig;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::types::{CollectionResult, CoreSearchRequest, CoreSearchRequestBatch};
+use crate::operations::universal_query::shard_query::{Sample, ScoringQuery, ShardQueryRequest};
+use crate::shards::shard_trait::ShardOperation;
+
+impl LocalShard {
+    /// Measure recall of approximate search on a sample of queries
+    ///
+    /// Vectors of random points are used as queries, each one is searched for both with the
+    /// vector indices and exactly. Searches are internal: strict mode is not applied to them.
+    ///
+    /// Returns the share of exact results found by approximate search, for every query.
+    pub(crate) async fn sample_recall(
+        &self,
+        config: &RecallMonitorConfig,
+        search_runtime_handle: &Handle,
+    ) -> CollectionResult<Vec<f64>> {
+        let vector_names = self
+            .collection_config
+            .read()
+            .await
+            .params
+            .vectors
+            .params_iter()
+            .map(|(name, _)| name.to_string())
+            .collect_vec();
+
+        let sample_request = ShardQueryRequest {
+            prefetches: vec![],
+            query: Some(ScoringQuery::Sample(Sample::Random)),
+            filter: None,
+            score_threshold: None,
+            limit: config.sample_size,
+            offset: 0,
+            params: None,
+            with_vector: WithVector::Bool(true),
+            with_payload: WithPayloadInterface::Bool(false),
+        };
+        let sampled_points = self
+            .query_batch(Arc::new(vec![sample_request]), search_runtime_handle, None)
+            .await?
+            .into_iter()
+            .flatten()
+            .flatten()
+            .collect_vec();
+
+        let queries = sampled_points
+            .iter()
+            .filter_map(|point| point.vector.as_ref())
+            .flat_map(|vectors| {
+                vector_names.iter().filter_map(|name| {
+                    let vector = vectors.get(name)?.to_owned();
+                    Some(QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                        vector, name,
+                    )))
+                })
+            })
+            .collect_vec();
+
+        if queries.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let search_batch = |exact: bool| {
+            Arc::new(CoreSearchRequestBatch {
+                searches: queries
+                    .iter()
+                    .map(|query| CoreSearchRequest {
+                        query: query.clone(),
+                        filter: None,
+                        params: Some(SearchParams {
+                            exact: Some(exact),
+                            ..Default::default()
+                        }),
+                        limit: config.limit,
+                        offset: 0,
+                        with_payload: None,
+                        with_vector: None,
+                        score_threshold: None,
+                    })
+                    .collect(),
+            })
+        };
+
+        let approximate_results = self
+            .do_internal_search(search_batch(false), search_runtime_handle, None)
+            .await?;
+        let exact_results = self
+            .do_internal_search(search_batch(true), search_runtime_handle, None)
+            .await?;
+
+        let recalls = approximate_results
+            .iter()
+            .zip(&exact_results)
+            .filter(|(_, exact)| !exact.is_empty())
+            .map(|(approximate, exact)| {
+                let exact_ids: HashSet<_> = exact.iter().map(|point| point.id).collect();
+                let found = approximate
+                    .iter()
+                    .filter(|point| exact_ids.contains(&point.id))
+                    .count();
+                found as f64 / exact_ids.len() as f64
+            })
+            .collect();
+
+        Ok(recalls)
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use parking_lot::Mutex;
+use segment::types::{PointIdType, WithPayload, WithVector};
+
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
+use crate::operations::point_ops::PointOperations;
+use crate::operations::types::Record;
+use crate::operations::CollectionUpdateOperations;
+
+/// Bounded LRU cache of records retrieved by id from a local shard.
+///
+/// Records are cached separately for each payload and vector selector they were retrieved with.
+/// Entries are invalidated by the update handler whenever it applies an update to the shard,
+/// updates with a filter clear the whole cache. Records read while an update is being applied are
+/// not cached.
+#[derive(Debug)]
+pub struct RetrieveCache {
+    /// Maximum number of cached point ids, cache is disabled if zero
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// Incremented on each invalidation, records read before it are not cached
+    epoch: u64,
+    /// Number of updates being applied, nothing is cached while there are any
+    updates_in_progress: usize,
+    /// Incremented on each access, orders the entries by their last use
+    tick: u64,
+    entries: HashMap<PointIdType, CacheEntry>,
+    /// Cached ids by the tick of their last use, least recently used first
+    by_last_use: BTreeMap<u64, PointIdType>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    last_use: u64,
+    records: Vec<CachedRecord>,
+}
+
+#[derive(Debug)]
+struct CachedRecord {
+    with_payload: WithPayload,
+    with_vector: WithVector,
+    record: Record,
+}
+
+/// Result of looking up requested ids in the cache
+pub struct CacheLookup {
+    pub found: Vec<Record>,
+    pub missing: Vec<PointIdType>,
+    pub epoch: u64,
+}
+
+impl RetrieveCache {
+    /// Maximum number of selectors cached for a single point
+    const MAX_RECORDS_PER_POINT: usize = 4;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn lookup(
+        &self,
+        ids: &[PointIdType],
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CacheLookup {
+        let mut state = self.state.lock();
+        let CacheState {
+            epoch,
+            updates_in_progress: _,
+            tick,
+            entries,
+            by_last_use,
+        } = &mut *state;
+
+        let mut found = Vec::new();
+        let mut missing = Vec::new();
+
+        let mut seen = HashSet::new();
+        for &id in ids.iter().filter(|id| seen.insert(**id)) {
+            let cached = entries.get_mut(&id).and_then(|entry| {
+                let cached = entry.records.iter().find(|cached| {
+                    &cached.with_payload == with_payload && &cached.with_vector == with_vector
+                })?;
+
+                *tick += 1;
+                by_last_use.remove(&entry.last_use);
+                by_last_use.insert(*tick, id);
+                entry.last_use = *tick;
+
+                Some(cached.record.clone())
+            });
+
+            match cached {
+                Some(record) => found.push(record),
+                None => missing.push(id),
+            }
+        }
+
+        CacheLookup {
+            found,
+            missing,
+            epoch: *epoch,
+        }
+    }
+
+    /// Cache records retrieved with the given selectors, unless the cache was invalidated since
+    /// `epoch`
+    pub fn insert<'a>(
+        &self,
+        records: impl IntoIterator<Item = &'a Record>,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+        epoch: u64,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        let CacheState {
+            epoch: current_epoch,
+            updates_in_progress,
+            tick,
+            entries,
+            by_last_use,
+        } = &mut *state;
+
+        // Records may have been changed while they were read
+        if *current_epoch != epoch || *updates_in_progress > 0 {
+            return;
+        }
+
+        for record in records {
+            *tick += 1;
+
+            let entry = entries.entry(record.id).or_insert_with(|| CacheEntry {
+                last_use: *tick,
+                records: Vec::new(),
+            });
+            by_last_use.remove(&entry.last_use);
+            by_last_use.insert(*tick, record.id);
+            entry.last_use = *tick;
+
+            entry.records.retain(|cached| {
+                &cached.with_payload != with_payload || &cached.with_vector != with_vector
+            });
+            if entry.records.len() >= Self::MAX_RECORDS_PER_POINT {
+                entry.records.remove(0);
+            }
+            entry.records.push(CachedRecord {
+                with_payload: with_payload.clone(),
+                with_vector: with_vector.clone(),
+                record: record.clone(),
+            });
+        }
+
+        while entries.len() > self.capacity {
+            let Some((_, id)) = by_last_use.pop_first() else {
+                break;
+            };
+            entries.remove(&id);
+        }
+    }
+
+    /// Remove cached records of the given points, or all records if `None`
+    pub fn invalidate(&self, ids: Option<&[PointIdType]>) {
+        if !self.is_enabled() {
+            return;
+        }
+
+        let mut state = self.state.lock();
+        state.epoch += 1;
+
+        let Some(ids) = ids else {
+            state.entries.clear();
+            state.by_last_use.clear();
+            return;
+        };
+
+        for id in ids {
+            if let Some(entry) = state.entries.remove(id) {
+                state.by_last_use.remove(&entry.last_use);
+            }
+        }
+    }
+
+    /// Remove cached records of the points affected by the operation, and stop caching until the
+    /// returned guard is dropped, once the operation is applied to the shard
+    pub fn begin_update(&self, operation: &CollectionUpdateOperations) -> Option<UpdateGuard<'_>> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        match operation {
+            // Sync deletes points, which are not listed in the operation
+            CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(_)) => {
+                self.invalidate(None)
+            }
+            operation => match operation.estimate_effect_area() {
+                OperationEffectArea::Empty => {}
+                OperationEffectArea::Points(ids) => self.invalidate(Some(&ids)),
+                OperationEffectArea::Filter(_) => self.invalidate(None),
+            },
+        }
+
+        self.state.lock().updates_in_progress += 1;
+        Some(UpdateGuard { cache: self })
+    }
+
+    #[cfg(test)]
+    fn cached_points(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+}
+
+/// Update of the shard in progress, see [`RetrieveCache::begin_update`]
+pub struct UpdateGuard<'a> {
+    cache: &'a RetrieveCache,
+}
+
+impl Drop for UpdateGuard<'_> {
+    fn drop(&mut self) {
+        let mut state = self.cache.state.lock();
+        state.updates_in_progress -= 1;
+        // Records read during the update may be outdated
+        state.epoch += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    fn record(id: u64) -> Record {
+        Record {
+            id: ExtendedPointId::from(id),
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            version: None,
+        }
+    }
+
+    fn ids(ids: impl IntoIterator<Item = u64>) -> Vec<PointIdType> {
+        ids.into_iter().map(ExtendedPointId::from).collect()
+    }
+
+    fn lookup_ids(cache: &RetrieveCache, ids: &[PointIdType]) -> CacheLookup {
+        cache.lookup(ids, &WithPayload::default(), &WithVector::Bool(false))
+    }
+
+    fn insert_records(cache: &RetrieveCache, records: &[Record], epoch: u64) {
+        cache.insert(
+            records,
+            &WithPayload::default(),
+            &WithVector::Bool(false),
+            epoch,
+        );
+    }
+
+    #[test]
+    fn test_retrieve_cache_lru() {
+        let cache = RetrieveCache::new(2);
+
+        let lookup = lookup_ids(&cache, &ids([1, 2]));
+        assert_eq!(lookup.missing.len(), 2);
+        insert_records(&cache, &[record(1), record(2)], lookup.epoch);
+
+        // Use 1, so 2 is evicted on insertion of 3
+        assert!(lookup_ids(&cache, &ids([1])).missing.is_empty());
+        let lookup = lookup_ids(&cache, &ids([3]));
+        insert_records(&cache, &[record(3)], lookup.epoch);
+        assert_eq!(cache.cached_points(), 2);
+
+        let lookup = lookup_ids(&cache, &ids([1, 2, 3]));
+        assert_eq!(lookup.found.len(), 2);
+        assert_eq!(lookup.missing, ids([2]));
+
+        // Different selector is not served from the cache
+        let with_vector = cache.lookup(&ids([1]), &WithPayload::default(), &WithVector::Bool(true));
+        assert_eq!(with_vector.missing.len(), 1);
+
+        // Records read before an invalidation are not cached
+        let lookup = lookup_ids(&cache, &ids([4]));
+        cache.invalidate(Some(&ids([1])));
+        insert_records(&cache, &[record(4)], lookup.epoch);
+        assert_eq!(cache.cached_points(), 1);
+        assert_eq!(lookup_ids(&cache, &ids([1, 4])).missing.len(), 2);
+
+        // Nothing is cached while an update is in progress
+        let operation = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+            ids: vec![ExtendedPointId::from(5)],
+        });
+        let guard = cache.begin_update(&operation);
+        let lookup = lookup_ids(&cache, &ids([1]));
+        insert_records(&cache, &[record(1)], lookup.epoch);
+        assert_eq!(cache.cached_points(), 0);
+        drop(guard);
+    }
+}
@@ -81,7 +81,7 @@ impl LocalShard {
                 .into_iter()
                 .map(|record| ScoredPoint {
                     id: record.id,
-                    version: 0,
+                    version: record.version.unwrap_or_default(),
                     score: 0.0,
                     payload: record.payload,
                     vector: record.vector,
@@ -107,7 +107,7 @@ impl LocalShard {
                     .zip(values)
                     .map(|(record, value)| ScoredPoint {
                         id: record.id,
-                        version: 0,
+                        version: record.version.unwrap_or_default(),
                         score: 0.0,
                         payload: record.payload,
                         vector: record.vector,
@@ -132,7 +132,7 @@ impl LocalShard {
                     .into_iter()
                     .map(|record| ScoredPoint {
                         id: record.id,
-                        version: 0,
+                        version: record.version.unwrap_or_default(),
                         score: 0.0,
                         payload: record.payload,
                         vector: record.vector,
@@ -1,14 +1,24 @@
+use std::mem;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
-use segment::types::ScoredPoint;
+use segment::data_types::query_context::DeadlinePolicy;
+use segment::types::{Order, PayloadSelector, ScoredPoint, WithPayload, WithVector};
+use segment::utils::scored_point_ties::TieBreak;
 use tokio::runtime::Handle;
+use tokio::time::error::Elapsed;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionConfig;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
+use crate::operations::verification::{check_filter_conditions, check_search_params};
+
+/// Largest limit searches are repeated with to find points tied with the last one, unless strict
+/// mode sets a lower query limit
+const MAX_TIE_BREAK_SEARCH_LIMIT: usize = 10_000;
 
 impl LocalShard {
     pub async fn do_search(
@@ -16,12 +26,42 @@ impl LocalShard {
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.search_impl(core_request, search_runtime_handle, timeout, true)
+            .await
+    }
+
+    /// Same as [`Self::do_search`], but strict mode is not applied
+    ///
+    /// Only for searches issued by the service itself, never for user requests.
+    pub(crate) async fn do_internal_search(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.search_impl(core_request, search_runtime_handle, timeout, false)
+            .await
+    }
+
+    async fn search_impl(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        check_strict_mode: bool,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let is_stopped_guard = StoppingGuard::new();
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+        // Segment searches stop by themselves once the timeout is reached
+        let deadline = Instant::now().checked_add(timeout);
 
-        let (query_context, collection_params) = {
+        let (core_request, query_context, collection_params) = {
             let collection_config = self.collection_config.read().await;
 
+            let core_request =
+                prepare_search_params(core_request, &collection_config, check_strict_mode)?;
+
             let query_context_opt = SegmentsSearcher::prepare_query_context(
                 self.segments.clone(),
                 &core_request,
@@ -30,12 +70,19 @@ impl LocalShard {
             )
             .await?;
 
-            let Some(query_context) = query_context_opt else {
+            let Some(mut query_context) = query_context_opt else {
                 // No segments to search
                 return Ok(vec![]);
             };
+            if let Some(deadline) = deadline {
+                query_context = query_context.with_deadline(deadline, DeadlinePolicy::Error);
+            }
 
-            (query_context, collection_config.params.clone())
+            (
+                core_request,
+                query_context,
+                collection_config.params.clone(),
+            )
         };
 
         let search_request = SegmentsSearcher::search(
@@ -46,8 +93,6 @@ impl LocalShard {
             query_context,
         );
 
-        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
-
         let res = tokio::time::timeout(timeout, search_request)
             .await
             .map_err(|_| {
@@ -88,4 +133,324 @@ impl LocalShard {
             .collect();
         Ok(top_results)
     }
+
+    /// Search, ordering points with equal scores by the tie break of the collection.
+    ///
+    /// Segments order points with equal scores by their offsets, so the limit may cut off points
+    /// which the tie break puts first. Searches are repeated with a larger limit, until all points
+    /// tied with the last one within the limit are found, or the limit reaches
+    /// [`tie_break_search_limit`]. Results are then ordered by the tie break and truncated to the
+    /// limit. Tied points beyond the largest limit are not considered.
+    pub async fn do_search_with_tie_break(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let (tie_break, collection_params, max_limit) = {
+            let collection_config = self.collection_config.read().await;
+            (
+                collection_config.tie_break.clone(),
+                collection_config.params.clone(),
+                tie_break_search_limit(&collection_config),
+            )
+        };
+        let Some(tie_break) = tie_break else {
+            return self
+                .do_search(core_request, search_runtime_handle, timeout)
+                .await;
+        };
+
+        let start_time = Instant::now();
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        // Search one point beyond the limit, to find out if it is tied with the last one
+        let mut searches = core_request.searches.clone();
+        for search in &mut searches {
+            search.limit += 1;
+        }
+        let mut results = self
+            .do_search(
+                Arc::new(CoreSearchRequestBatch {
+                    searches: searches.clone(),
+                }),
+                search_runtime_handle,
+                Some(timeout),
+            )
+            .await?;
+
+        for ((result, search), request) in results
+            .iter_mut()
+            .zip(&mut searches)
+            .zip(&core_request.searches)
+        {
+            let take = request.limit + request.offset;
+            if take == 0 {
+                continue;
+            }
+
+            while search.limit < max_limit
+                && result.len() == search.limit + search.offset
+                && result.last().map(|point| point.score)
+                    == result.get(take - 1).map(|point| point.score)
+            {
+                search.limit = (search.limit * 2).min(max_limit);
+                *result = self
+                    .do_search(
+                        Arc::new(CoreSearchRequestBatch {
+                            searches: vec![search.clone()],
+                        }),
+                        search_runtime_handle,
+                        Some(timeout.saturating_sub(start_time.elapsed())),
+                    )
+                    .await?
+                    .pop()
+                    .unwrap_or_default();
+            }
+
+            let order = if request.query.is_distance_scored() {
+                collection_params
+                    .get_distance(request.query.get_vector_name())?
+                    .distance_order()
+            } else {
+                Order::LargeBetter
+            };
+
+            let timeout = timeout.saturating_sub(start_time.elapsed());
+            self.sort_by_tie_break(result, &tie_break, order, timeout)
+                .await?;
+            result.truncate(take);
+        }
+
+        Ok(results)
+    }
+
+    /// Sort points by score, and by the tie break if scores are equal.
+    ///
+    /// Payload values used by the tie break are read from the segments, points keep the payload
+    /// they were found with.
+    async fn sort_by_tie_break(
+        &self,
+        points: &mut Vec<ScoredPoint>,
+        tie_break: &TieBreak,
+        order: Order,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let key = match tie_break {
+            TieBreak::Id(_) => {
+                points.sort_by(|a, b| tie_break.cmp_scored(a, b, order));
+                return Ok(());
+            }
+            TieBreak::Payload(payload_tie_break) => &payload_tie_break.key,
+        };
+
+        let point_ids: Vec<_> = points.iter().map(|point| point.id).collect();
+        let with_payload = WithPayload {
+            enable: true,
+            payload_selector: Some(PayloadSelector::new_include(vec![key.clone()])),
+        };
+        let records = tokio::time::timeout(
+            timeout,
+            SegmentsSearcher::retrieve(
+                self.segments.clone(),
+                &point_ids,
+                &with_payload,
+                &WithVector::Bool(false),
+                &self.search_runtime,
+            ),
+        )
+        .await
+        .map_err(|_: Elapsed| CollectionError::timeout(timeout.as_secs() as usize, "retrieve"))??;
+
+        let mut keyed_points: Vec<_> = mem::take(points)
+            .into_iter()
+            .map(|mut point| {
+                let tie_payload = records
+                    .get(&point.id)
+                    .and_then(|record| record.payload.clone());
+                let payload = mem::replace(&mut point.payload, tie_payload);
+                (point, payload)
+            })
+            .collect();
+        keyed_points.sort_by(|(a, _), (b, _)| tie_break.cmp_scored(a, b, order));
+
+        points.extend(keyed_points.into_iter().map(|(mut point, payload)| {
+            point.payload = payload;
+            point
+        }));
+        Ok(())
+    }
+}
+
+/// Fill in search params not specified in the requests from the collection defaults, and check
+/// the resulting params and filters against strict mode.
+fn prepare_search_params(
+    mut core_request: Arc<CoreSearchRequestBatch>,
+    collection_config: &CollectionConfig,
+    check_strict_mode: bool,
+) -> CollectionResult<Arc<CoreSearchRequestBatch>> {
+    if let Some(defaults) = &collection_config.default_search_params {
+        let mut request = Arc::unwrap_or_clone(core_request);
+        for search in &mut request.searches {
+            search.params = Some(match search.params {
+                Some(params) => params.with_defaults(defaults),
+                None => *defaults,
+            });
+        }
+        core_request = Arc::new(request);
+    }
+
+    let strict_mode_config = collection_config
+        .strict_mode_config
+        .as_ref()
+        .filter(|strict_mode_config| check_strict_mode && strict_mode_config.enabled == Some(true));
+
+    if let Some(strict_mode_config) = strict_mode_config {
+        for search in &core_request.searches {
+            if let Some(params) = &search.params {
+                check_search_params(params, strict_mode_config)?;
+            }
+            if let Some(filter) = &search.filter {
+                check_filter_conditions(filter, strict_mode_config)?;
+            }
+        }
+    }
+
+    Ok(core_request)
+}
+
+/// Largest limit of searches repeated to find points with equal scores for the tie break
+fn tie_break_search_limit(collection_config: &CollectionConfig) -> usize {
+    let strict_mode_limit = collection_config
+        .strict_mode_config
+        .as_ref()
+        .filter(|strict_mode_config| strict_mode_config.enabled == Some(true))
+        .and_then(|strict_mode_config| strict_mode_config.max_query_limit);
+    strict_mode_limit.map_or(MAX_TIE_BREAK_SEARCH_LIMIT, |limit| {
+        limit.min(MAX_TIE_BREAK_SEARCH_LIMIT)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::NamedVectorStruct;
+    use segment::types::SearchParams;
+
+    use super::*;
+    use crate::operations::config_diff::StrictModeConfig;
+    use crate::operations::types::CoreSearchRequest;
+    use crate::tests::fixtures::create_collection_config;
+
+    fn search_request(params: Option<SearchParams>) -> CoreSearchRequest {
+        CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedVectorStruct::Default(vec![0.1, 0.2, 0.3, 0.4])),
+            filter: None,
+            params,
+            limit: 10,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        }
+    }
+
+    #[test]
+    fn test_default_search_params() {
+        let mut config = create_collection_config();
+        config.default_search_params = Some(SearchParams {
+            hnsw_ef: Some(256),
+            indexed_only: Some(true),
+            ..Default::default()
+        });
+
+        let explicit_params = SearchParams {
+            hnsw_ef: Some(32),
+            exact: Some(true),
+            indexed_only: Some(false),
+            ..Default::default()
+        };
+        let request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![
+                search_request(None),
+                search_request(Some(SearchParams::default())),
+                search_request(Some(explicit_params)),
+            ],
+        });
+
+        let prepared = prepare_search_params(request, &config, true).unwrap();
+        let hnsw_efs: Vec<_> = prepared
+            .searches
+            .iter()
+            .map(|search| search.params.unwrap().hnsw_ef)
+            .collect();
+        assert_eq!(hnsw_efs, vec![Some(256), Some(256), Some(32)]);
+
+        // Flags fall back to the defaults only if not specified, explicitly disabled flags win
+        let indexed_only: Vec<_> = prepared
+            .searches
+            .iter()
+            .map(|search| search.params.unwrap().is_indexed_only())
+            .collect();
+        assert_eq!(indexed_only, vec![true, true, false]);
+        assert_eq!(prepared.searches[2].params, Some(explicit_params));
+
+        // Defaults are subject to strict mode limits
+        config.strict_mode_config = Some(StrictModeConfig {
+            enabled: Some(true),
+            max_query_limit: None,
+            max_timeout: None,
+            unindexed_filtering_retrieve: None,
+            unindexed_filtering_update: None,
+            search_max_hnsw_ef: Some(128),
+            search_allow_exact: None,
+            search_max_oversampling: None,
+            max_distinct_values: None,
+            max_filter_conditions: None,
+            require_filter: None,
+            max_result_points: None,
+            truncate_result_points: None,
+            max_payload_bytes: None,
+        });
+        let request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![search_request(None)],
+        });
+        assert!(prepare_search_params(request.clone(), &config, true).is_err());
+
+        // Internal searches are not
+        assert!(prepare_search_params(request, &config, false).is_ok());
+    }
+
+    #[test]
+    fn test_tie_break_search_limit() {
+        let mut config = create_collection_config();
+        assert_eq!(tie_break_search_limit(&config), MAX_TIE_BREAK_SEARCH_LIMIT);
+
+        let mut strict_mode_config = StrictModeConfig {
+            enabled: Some(false),
+            max_query_limit: Some(100),
+            max_timeout: None,
+            unindexed_filtering_retrieve: None,
+            unindexed_filtering_update: None,
+            search_max_hnsw_ef: None,
+            search_allow_exact: None,
+            search_max_oversampling: None,
+            max_distinct_values: None,
+            max_filter_conditions: None,
+            require_filter: None,
+            max_result_points: None,
+            truncate_result_points: None,
+            max_payload_bytes: None,
+        };
+        config.strict_mode_config = Some(strict_mode_config.clone());
+        assert_eq!(tie_break_search_limit(&config), MAX_TIE_BREAK_SEARCH_LIMIT);
+
+        // Enabled strict mode lowers the limit, but never raises it
+        strict_mode_config.enabled = Some(true);
+        config.strict_mode_config = Some(strict_mode_config.clone());
+        assert_eq!(tie_break_search_limit(&config), 100);
+
+        strict_mode_config.max_query_limit = Some(MAX_TIE_BREAK_SEARCH_LIMIT * 10);
+        config.strict_mode_config = Some(strict_mode_config);
+        assert_eq!(tie_break_search_limit(&config), MAX_TIE_BREAK_SEARCH_LIMIT);
+    }
 }
@@ -0,0 +1,87 @@
+use segment::data_types::named_vectors::NamedVectors;
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::types::{PointIdType, SeqNumberType, WithPayload, WithVector};
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::operations::types::{CollectionError, CollectionResult, Record};
+
+/// Diagnostic access to the data of individual segments
+///
+/// Regular reads merge the points of all segments and only return their latest version.
+/// During optimization the same point may exist in multiple segments, these methods allow to
+/// inspect each copy separately.
+impl LocalShard {
+    /// Ids of all segments, which contain the given point, with the version of the point in
+    /// each of them, ordered by segment id
+    ///
+    /// Only the latest version is visible to regular reads.
+    pub fn segments_with_point(&self, point_id: PointIdType) -> Vec<(SegmentId, SeqNumberType)> {
+        let mut segments: Vec<_> = self
+            .segments
+            .read()
+            .iter()
+            .filter_map(|(segment_id, segment)| {
+                let version = segment.get().read().point_version(point_id)?;
+                Some((*segment_id, version))
+            })
+            .collect();
+        segments.sort_unstable_by_key(|(segment_id, _)| *segment_id);
+        segments
+    }
+
+    /// Retrieve a point as it is stored in the given segment, ignoring all other segments
+    ///
+    /// Returns `None` if the segment doesn't contain the point.
+    pub fn retrieve_from_segment(
+        &self,
+        segment_id: SegmentId,
+        point_id: PointIdType,
+        with_payload: &WithPayload,
+        with_vector: &WithVector,
+    ) -> CollectionResult<Option<Record>> {
+        let segments = self.segments.read();
+        let segment = segments
+            .get(segment_id)
+            .ok_or_else(|| CollectionError::not_found(format!("Segment {segment_id}")))?;
+        let segment = segment.get();
+        let segment = segment.read();
+
+        if !segment.has_point(point_id) {
+            return Ok(None);
+        }
+
+        let payload = if with_payload.enable {
+            let payload = segment.payload(point_id)?;
+            match &with_payload.payload_selector {
+                Some(selector) => Some(selector.process(payload)),
+                None => Some(payload),
+            }
+        } else {
+            None
+        };
+
+        let vector = match with_vector {
+            WithVector::Bool(true) => Some(segment.all_vectors(point_id)?.into()),
+            WithVector::Bool(false) => None,
+            WithVector::Selector(vector_names) => {
+                let mut selected_vectors = NamedVectors::default();
+                for vector_name in vector_names {
+                    if let Some(vector) = segment.vector(vector_name, point_id)? {
+                        selected_vectors.insert(vector_name.into(), vector);
+                    }
+                }
+                Some(selected_vectors.into())
+            }
+        };
+
+        Ok(Some(Record {
+            id: point_id,
+            payload,
+            vector,
+            shard_key: None,
+            order_value: None,
+            version: segment.point_version(point_id),
+        }))
+    }
+}
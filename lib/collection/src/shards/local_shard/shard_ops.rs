@@ -1,8 +1,9 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -18,7 +19,9 @@ use crate::operations::types::{
 };
 use crate::operations::universal_query::planned_query::PlannedQuery;
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
+use crate::operations::verification::check_query_filter_conditions;
 use crate::operations::OperationWithClockTag;
+use crate::shards::local_shard::retrieve_cache::CacheLookup;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::update_handler::{OperationData, UpdateSignal};
@@ -58,6 +61,9 @@ impl ShardOperation for LocalShard {
             ));
         }
 
+        // Wait for a free slot before accepting the operation, applies backpressure to the caller
+        let in_flight_permit = self.update_limiter.acquire().await;
+
         let operation_id = {
             let update_sender = self.update_sender.load();
             let channel_permit = update_sender.reserve().await?;
@@ -74,6 +80,7 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        deduplicated: None,
                     });
                 }
 
@@ -85,6 +92,7 @@ impl ShardOperation for LocalShard {
                 operation: operation.operation,
                 sender: callback_sender,
                 wait,
+                in_flight_permit,
             }));
 
             operation_id
@@ -96,12 +104,14 @@ impl ShardOperation for LocalShard {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
+                deduplicated: None,
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                deduplicated: None,
             })
         }
     }
@@ -205,18 +215,37 @@ impl ShardOperation for LocalShard {
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Record>> {
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
-        let records_map = tokio::time::timeout(
-            timeout,
-            SegmentsSearcher::retrieve(
-                self.segments.clone(),
-                &request.ids,
-                with_payload,
-                with_vector,
-                search_runtime_handle,
-            ),
-        )
-        .await
-        .map_err(|_: Elapsed| CollectionError::timeout(timeout.as_secs() as usize, "retrieve"))??;
+
+        let CacheLookup {
+            found,
+            missing,
+            epoch,
+        } = self
+            .retrieve_cache
+            .lookup(&request.ids, with_payload, with_vector);
+
+        let mut records_map = if missing.is_empty() {
+            HashMap::new()
+        } else {
+            tokio::time::timeout(
+                timeout,
+                SegmentsSearcher::retrieve(
+                    self.segments.clone(),
+                    &missing,
+                    with_payload,
+                    with_vector,
+                    search_runtime_handle,
+                ),
+            )
+            .await
+            .map_err(|_: Elapsed| {
+                CollectionError::timeout(timeout.as_secs() as usize, "retrieve")
+            })??
+        };
+
+        self.retrieve_cache
+            .insert(records_map.values(), with_payload, with_vector, epoch);
+        records_map.extend(found.into_iter().map(|record| (record.id, record)));
 
         let ordered_records = request
             .ids
@@ -233,6 +262,19 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<ShardQueryResponse>> {
+        if let Some(strict_mode_config) = self
+            .collection_config
+            .read()
+            .await
+            .strict_mode_config
+            .as_ref()
+            .filter(|strict_mode_config| strict_mode_config.enabled == Some(true))
+        {
+            for request in requests.iter() {
+                check_query_filter_conditions(request, strict_mode_config)?;
+            }
+        }
+
         let planned_query = PlannedQuery::try_from(requests.as_ref().to_owned())?;
 
         self.do_planned_query(planned_query, search_runtime_handle, timeout)
@@ -254,4 +296,14 @@ impl ShardOperation for LocalShard {
         };
         Ok(FacetResponse { hits })
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        self.smallest_unique_values(request, search_runtime_handle, timeout)
+            .await
+    }
 }
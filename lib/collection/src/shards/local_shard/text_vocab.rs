@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use parking_lot::RwLock;
+use segment::entry::entry_point::SegmentEntry as _;
+use segment::index::field_index::full_text_index::VocabEntry;
+use segment::index::field_index::FieldIndex;
+use segment::json_path::JsonPath;
+use segment::segment::Segment;
+use serde::{Deserialize, Serialize};
+
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentId};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Vocabulary of the full-text index of a field in one segment
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SegmentVocab {
+    pub segment_id: SegmentId,
+    pub vocab: Vec<VocabEntry>,
+}
+
+impl LocalShard {
+    /// Dump the vocabulary of the full-text index of `field` in every segment of this shard
+    ///
+    /// Segments are dumped one by one, only the lock of the dumped segment is held while its
+    /// vocabulary is copied. Segments which are being optimized are skipped.
+    pub fn export_full_text_vocab(&self, field: &JsonPath) -> CollectionResult<Vec<SegmentVocab>> {
+        let mut segment_vocabs = Vec::new();
+
+        for (segment_id, segment) in self.original_segments() {
+            let segment = segment.read();
+            let payload_index = segment.payload_index.borrow();
+
+            let vocab = payload_index
+                .field_indexes
+                .get(field)
+                .into_iter()
+                .flatten()
+                .find_map(|index| match index {
+                    FieldIndex::FullTextIndex(index) => Some(index.export_vocab()),
+                    _ => None,
+                });
+
+            if let Some(vocab) = vocab {
+                segment_vocabs.push(SegmentVocab { segment_id, vocab });
+            }
+        }
+
+        if segment_vocabs.is_empty() {
+            return Err(CollectionError::bad_request(format!(
+                "Field {field} has no full-text index",
+            )));
+        }
+
+        Ok(segment_vocabs)
+    }
+
+    /// Prewarm the vocabulary of the full-text index of `field` in the appendable segments of
+    /// this shard, from a dump of [`Self::export_full_text_vocab`]
+    ///
+    /// Vocabulary of non-appendable segments is built from their storage, so they are not
+    /// changed. Returns the number of tokens added over all segments.
+    pub fn import_full_text_vocab(
+        &self,
+        field: &JsonPath,
+        vocab: &[VocabEntry],
+    ) -> CollectionResult<usize> {
+        let mut added = 0;
+
+        for (_, segment) in self.original_segments() {
+            let segment = segment.write();
+            if !segment.is_appendable() {
+                continue;
+            }
+            let mut payload_index = segment.payload_index.borrow_mut();
+
+            let index = payload_index
+                .field_indexes
+                .get_mut(field)
+                .into_iter()
+                .flatten()
+                .find_map(|index| match index {
+                    FieldIndex::FullTextIndex(index) => Some(index),
+                    _ => None,
+                });
+
+            if let Some(index) = index {
+                added += index.import_vocab(vocab.iter().cloned())?;
+            }
+        }
+
+        Ok(added)
+    }
+
+    /// Segments, which are not wrapped into a proxy by an ongoing optimization
+    fn original_segments(&self) -> Vec<(SegmentId, Arc<RwLock<Segment>>)> {
+        self.segments
+            .read()
+            .iter()
+            .filter_map(|(&segment_id, segment)| match segment {
+                LockedSegment::Original(segment) => Some((segment_id, segment.clone())),
+                LockedSegment::Proxy(_) => None,
+            })
+            .collect()
+    }
+}
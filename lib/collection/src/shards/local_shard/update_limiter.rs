@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits the number of update operations of a shard which are accepted, but not yet applied.
+///
+/// Senders wait for a free slot once the limit is reached, which applies backpressure to callers
+/// instead of queueing an unbounded amount of operations in memory.
+#[derive(Debug)]
+pub struct UpdateLimiter {
+    /// If not defined - no limit is applied.
+    semaphore: Option<Arc<Semaphore>>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Keeps an update operation counted as in-flight, releases the slot on drop.
+#[must_use]
+#[derive(Debug)]
+pub struct UpdatePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for UpdatePermit {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl UpdateLimiter {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            semaphore: limit.map(|limit| Arc::new(Semaphore::new(limit.max(1)))),
+            in_flight: Default::default(),
+        }
+    }
+
+    /// Wait for a free slot to submit an update operation.
+    ///
+    /// # Cancel safety
+    ///
+    /// This function is cancel safe.
+    pub async fn acquire(&self) -> UpdatePermit {
+        let permit = match &self.semaphore {
+            Some(semaphore) => Some(
+                semaphore
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("update semaphore is never closed"),
+            ),
+            None => None,
+        };
+
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+
+        UpdatePermit {
+            _permit: permit,
+            in_flight: self.in_flight.clone(),
+        }
+    }
+
+    /// Number of update operations accepted, but not yet applied.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_update_limiter_unlimited() {
+        let limiter = UpdateLimiter::new(None);
+
+        let permits: Vec<_> = futures::future::join_all((0..10).map(|_| limiter.acquire())).await;
+        assert_eq!(limiter.in_flight(), 10);
+
+        drop(permits);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_update_limiter_cancelled_wait() {
+        let limiter = UpdateLimiter::new(Some(1));
+        let permit = limiter.acquire().await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(waiting.is_err());
+        assert_eq!(limiter.in_flight(), 1);
+
+        drop(permit);
+        assert_eq!(limiter.in_flight(), 0);
+    }
+}
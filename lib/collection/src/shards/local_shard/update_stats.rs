@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use ringbuffer::{ConstGenericRingBuffer, RingBuffer as _};
+use segment::types::SeqNumberType;
+
+/// Time window over which the update rate and latency are averaged
+const WINDOW: Duration = Duration::from_secs(10);
+
+/// Moving statistics of update operations applied by the update worker of a shard.
+#[derive(Debug, Default)]
+pub struct UpdateStats {
+    /// Completion time and apply duration of the most recent operations
+    applied: ConstGenericRingBuffer<(Instant, Duration), { Self::SIZE }>,
+    /// Highest WAL sequence number applied to the segments
+    last_applied_op_num: Option<SeqNumberType>,
+}
+
+impl UpdateStats {
+    const SIZE: usize = 256;
+
+    /// Record an operation which was applied just now and took `duration` to apply.
+    pub fn record_applied(&mut self, op_num: SeqNumberType, duration: Duration) {
+        self.record_applied_op_num(op_num);
+        self.record_applied_raw(Instant::now(), duration);
+    }
+
+    /// Record that all operations up to `op_num` are applied, e.g. when recovering from WAL.
+    pub fn record_applied_op_num(&mut self, op_num: SeqNumberType) {
+        self.last_applied_op_num = self.last_applied_op_num.max(Some(op_num));
+    }
+
+    /// Highest WAL sequence number applied since the shard was loaded.
+    pub fn last_applied_op_num(&self) -> Option<SeqNumberType> {
+        self.last_applied_op_num
+    }
+
+    /// Operations applied per second, averaged over recent operations.
+    pub fn ops_per_sec(&self) -> f64 {
+        self.ops_per_sec_raw(Instant::now())
+    }
+
+    /// Average time it took to apply recent operations.
+    pub fn avg_apply_latency(&self) -> Option<Duration> {
+        self.avg_apply_latency_raw(Instant::now())
+    }
+
+    fn record_applied_raw(&mut self, now: Instant, duration: Duration) {
+        self.applied.push((now, duration));
+    }
+
+    fn recent(&self, now: Instant) -> impl Iterator<Item = &(Instant, Duration)> {
+        self.applied
+            .iter()
+            .filter(move |(time, _)| now.saturating_duration_since(*time) <= WINDOW)
+    }
+
+    fn ops_per_sec_raw(&self, now: Instant) -> f64 {
+        let mut recent = self.recent(now).peekable();
+        let Some(&(oldest, _)) = recent.peek() else {
+            return 0.0;
+        };
+        let count = recent.count();
+
+        // Don't extrapolate short bursts beyond one second
+        let elapsed = now
+            .saturating_duration_since(oldest)
+            .max(Duration::from_secs(1));
+        count as f64 / elapsed.as_secs_f64()
+    }
+
+    fn avg_apply_latency_raw(&self, now: Instant) -> Option<Duration> {
+        let (count, total) = self
+            .recent(now)
+            .fold((0u32, Duration::ZERO), |(count, total), (_, duration)| {
+                (count + 1, total + *duration)
+            });
+        total.checked_div(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use approx::assert_relative_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_update_stats() {
+        let start = Instant::now();
+        let mut stats = UpdateStats::default();
+        assert_eq!(stats.ops_per_sec_raw(start), 0.0);
+        assert_eq!(stats.avg_apply_latency_raw(start), None);
+
+        // 20 operations within 2 seconds, each taking 10 or 30 ms
+        for i in 0..20 {
+            let duration = Duration::from_millis(if i % 2 == 0 { 10 } else { 30 });
+            stats.record_applied_raw(start + Duration::from_millis(100 * i), duration);
+        }
+
+        let now = start + Duration::from_secs(2);
+        assert_relative_eq!(stats.ops_per_sec_raw(now), 10.0);
+        assert_eq!(
+            stats.avg_apply_latency_raw(now),
+            Some(Duration::from_millis(20)),
+        );
+
+        // Old operations fall out of the window
+        let later = start + Duration::from_secs(60);
+        assert_eq!(stats.ops_per_sec_raw(later), 0.0);
+        assert_eq!(stats.avg_apply_latency_raw(later), None);
+    }
+}
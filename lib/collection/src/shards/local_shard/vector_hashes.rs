@@ -0,0 +1,155 @@
+use std::collections::{HashMap, HashSet};
+
+use parking_lot::Mutex;
+use segment::common::operation_error::OperationResult;
+use segment::data_types::vectors::Vector;
+use segment::types::PointIdType;
+
+use crate::collection_manager::holders::segment_holder::SegmentHolder;
+use crate::operations::operation_effect::{EstimateOperationEffectArea, OperationEffectArea};
+use crate::operations::vector_dedup::StoredVectors;
+use crate::operations::CollectionUpdateOperations;
+
+/// Hashes of the vectors stored in a local shard, to find points with exactly the same vectors.
+///
+/// Built on first use by reading all stored points, then kept up to date by the update handler
+/// after it applies each update to the shard. Points found by hash are compared with their stored
+/// vectors, so hash collisions and entries of points, which were deleted or got other vectors
+/// since, are never reported.
+#[derive(Debug, Default)]
+pub struct VectorHashes {
+    /// Point ids by hash of their vectors, `None` until first used
+    hashes: Mutex<Option<HashMap<u64, Vec<PointIdType>>>>,
+}
+
+impl VectorHashes {
+    /// Points, whose vectors are written by the operation
+    ///
+    /// Must be taken before the operation is applied, and passed to [`Self::update`] after it.
+    pub fn written_vectors(operation: &CollectionUpdateOperations) -> OperationEffectArea {
+        match operation {
+            CollectionUpdateOperations::PointOperation(operation)
+                if operation.is_write_operation() =>
+            {
+                operation.estimate_effect_area()
+            }
+            CollectionUpdateOperations::VectorOperation(operation) => {
+                operation.estimate_effect_area()
+            }
+            _ => OperationEffectArea::Empty,
+        }
+    }
+
+    /// Update hashes of the points, whose vectors were written by an applied operation
+    ///
+    /// Hashes are dropped, and built again on next use, if the written points are not known.
+    pub fn update(&self, segments: &SegmentHolder, written: OperationEffectArea) {
+        let mut state = self.hashes.lock();
+        let Some(hashes) = state.as_mut() else {
+            return;
+        };
+
+        let is_updated = match written {
+            OperationEffectArea::Empty => true,
+            OperationEffectArea::Filter(_) => false,
+            OperationEffectArea::Points(point_ids) => point_ids
+                .into_iter()
+                .try_for_each(|point_id| {
+                    if let Some(vectors) = stored_vectors(segments, point_id)? {
+                        insert(
+                            hashes,
+                            StoredVectors::from_stored(&vectors).hash(),
+                            point_id,
+                        );
+                    }
+                    OperationResult::Ok(())
+                })
+                .map_err(|err| log::warn!("Failed to read vectors to hash them: {err}"))
+                .is_ok(),
+        };
+
+        if !is_updated {
+            *state = None;
+        }
+    }
+
+    /// Find a stored point with exactly the same vectors, other than the `excluded` ones
+    pub fn find(
+        &self,
+        segments: &SegmentHolder,
+        vectors: &StoredVectors,
+        excluded: &HashSet<PointIdType>,
+    ) -> OperationResult<Option<PointIdType>> {
+        let mut state = self.hashes.lock();
+        let hashes = match state.take() {
+            Some(hashes) => hashes,
+            None => build(segments)?,
+        };
+        let hashes = state.insert(hashes);
+
+        let Some(candidates) = hashes.get_mut(&vectors.hash()) else {
+            return Ok(None);
+        };
+
+        let mut found = None;
+        let mut outdated = Vec::new();
+        for &point_id in candidates.iter() {
+            match stored_vectors(segments, point_id)? {
+                Some(stored) if vectors.is_same(&stored) => {
+                    if found.is_none() && !excluded.contains(&point_id) {
+                        found = Some(point_id);
+                    }
+                }
+                _ => outdated.push(point_id),
+            }
+        }
+        candidates.retain(|point_id| !outdated.contains(point_id));
+
+        Ok(found)
+    }
+}
+
+fn insert(hashes: &mut HashMap<u64, Vec<PointIdType>>, hash: u64, point_id: PointIdType) {
+    let point_ids = hashes.entry(hash).or_default();
+    if !point_ids.contains(&point_id) {
+        point_ids.push(point_id);
+    }
+}
+
+fn build(segments: &SegmentHolder) -> OperationResult<HashMap<u64, Vec<PointIdType>>> {
+    let mut hashes = HashMap::new();
+    for (_, segment) in segments.iter() {
+        let segment_arc = segment.get();
+        let read_segment = segment_arc.read();
+        for point_id in read_segment.iter_points() {
+            let vectors = read_segment.all_vectors(point_id)?.into_owned_map();
+            insert(
+                &mut hashes,
+                StoredVectors::from_stored(&vectors).hash(),
+                point_id,
+            );
+        }
+    }
+    Ok(hashes)
+}
+
+/// Vectors of the latest version of a stored point
+fn stored_vectors(
+    segments: &SegmentHolder,
+    point_id: PointIdType,
+) -> OperationResult<Option<HashMap<String, Vector>>> {
+    // Point may be present in multiple segments during optimization, the latest copy wins
+    let latest_segment = segments
+        .iter()
+        .filter_map(|(_, segment)| {
+            let version = segment.get().read().point_version(point_id)?;
+            Some((version, segment))
+        })
+        .max_by_key(|(version, _)| *version);
+
+    let Some((_, segment)) = latest_segment else {
+        return Ok(None);
+    };
+    let vectors = segment.get().read().all_vectors(point_id)?;
+    Ok(Some(vectors.into_owned_map()))
+}
@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::operations::types::CollectionResult;
+use crate::operations::OperationWithClockTag;
+
+/// File in the shard directory, which lists WAL operations skipped on load
+pub const WAL_QUARANTINE_FILE: &str = "wal_quarantine.json";
+
+/// WAL operation, which repeatedly failed to be read or applied on load, and was skipped
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct QuarantinedOperation {
+    pub op_num: u64,
+    /// Error of the last attempt
+    pub error: String,
+    /// Skipped operation, if it could be read from the WAL
+    pub operation: Option<OperationWithClockTag>,
+}
+
+fn wal_quarantine_path(shard_path: &Path) -> PathBuf {
+    shard_path.join(WAL_QUARANTINE_FILE)
+}
+
+/// Operations quarantined in the shard, ordered by their number
+pub fn read_quarantined_operations(
+    shard_path: &Path,
+) -> CollectionResult<Vec<QuarantinedOperation>> {
+    let path = wal_quarantine_path(shard_path);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(read_json(&path)?)
+}
+
+/// Record the operation in the quarantine of the shard, replacing a previous record of it
+pub fn quarantine_operation(
+    shard_path: &Path,
+    operation: QuarantinedOperation,
+) -> CollectionResult<()> {
+    let mut operations = read_quarantined_operations(shard_path)?;
+    operations.retain(|quarantined| quarantined.op_num != operation.op_num);
+    operations.push(operation);
+    operations.sort_by_key(|quarantined| quarantined.op_num);
+
+    atomic_save_json(&wal_quarantine_path(shard_path), &operations)?;
+    Ok(())
+}
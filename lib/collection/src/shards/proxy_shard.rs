@@ -6,10 +6,10 @@ use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    ExtendedPointId, Filter, Payload, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
     WithVector,
 };
 use tokio::runtime::Handle;
@@ -20,6 +20,7 @@ use super::update_tracker::UpdateTracker;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
+use crate::operations::point_ops::{ConditionalUpsert, PointInsertOperationsInternal};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
@@ -72,9 +73,10 @@ impl ProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         self.wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, stopped)
             .await
     }
 
@@ -82,6 +84,34 @@ impl ProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        self.wrapped_shard.reindex().await
+    }
+
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        self.wrapped_shard.cancel_reindex().await
+    }
+
+    pub async fn check_expected_versions(
+        &self,
+        conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        self.wrapped_shard
+            .check_expected_versions(conditional_upsert)
+            .await
+    }
+
+    pub async fn dedup_stored_vectors(
+        &self,
+        points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        self.wrapped_shard.dedup_stored_vectors(points).await
+    }
+
+    pub fn wal_last_index(&self) -> u64 {
+        self.wrapped_shard.wal_last_index()
+    }
+
     pub async fn reinit_changelog(&self) -> CollectionResult<()> {
         // Blocks updates in the wrapped shard.
         let mut changed_points_guard = self.changed_points.write().await;
@@ -292,4 +322,16 @@ impl ShardOperation for ProxyShard {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .distinct_values(request, search_runtime_handle, timeout)
+            .await
+    }
 }
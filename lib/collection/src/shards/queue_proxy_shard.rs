@@ -1,15 +1,16 @@
 use std::path::Path;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
-    ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
+    ExtendedPointId, Filter, Payload, PointIdType, ScoredPoint, WithPayload, WithPayloadInterface,
+    WithVector,
 };
 use tokio::runtime::Handle;
 use tokio::sync::Mutex;
@@ -18,7 +19,9 @@ use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{
+    ConditionalUpsert, PointInsertOperationsInternal, WriteOrdering,
+};
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
     CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
@@ -125,13 +128,18 @@ impl QueueProxyShard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         self.inner_unchecked()
             .wrapped_shard
-            .create_snapshot(temp_path, target_path, save_wal)
+            .create_snapshot(temp_path, target_path, save_wal, stopped)
             .await
     }
 
+    pub fn wal_last_index(&self) -> u64 {
+        self.inner_unchecked().wrapped_shard.wal_last_index()
+    }
+
     /// Transfer all updates that the remote missed from WAL
     ///
     /// # Cancel safety
@@ -154,6 +162,34 @@ impl QueueProxyShard {
             .await
     }
 
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        self.inner_unchecked().wrapped_shard.reindex().await
+    }
+
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        self.inner_unchecked().wrapped_shard.cancel_reindex().await
+    }
+
+    pub async fn check_expected_versions(
+        &self,
+        conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .check_expected_versions(conditional_upsert)
+            .await
+    }
+
+    pub async fn dedup_stored_vectors(
+        &self,
+        points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .dedup_stored_vectors(points)
+            .await
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         self.inner_unchecked()
             .wrapped_shard
@@ -307,6 +343,18 @@ impl ShardOperation for QueueProxyShard {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .distinct_values(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 // Safe guard in debug mode to ensure that `finalize()` is called before dropping
@@ -621,6 +669,18 @@ impl ShardOperation for Inner {
             .facet(request, search_runtime_handle, timeout)
             .await
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let local_shard = &self.wrapped_shard;
+        local_shard
+            .distinct_values(request, search_runtime_handle, timeout)
+            .await
+    }
 }
 
 /// Transfer batch of operations without retries
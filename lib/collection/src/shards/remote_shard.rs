@@ -10,9 +10,9 @@ use api::grpc::qdrant::shard_snapshot_location::Location;
 use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
-    FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints,
-    GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
-    InitiateShardTransferRequest, QueryBatchPointsInternal, QueryShardPoints,
+    DistinctValuesInternal, FacetCountsInternal, GetCollectionInfoRequest,
+    GetCollectionInfoRequestInternal, GetPoints, GetPointsInternal, GetShardRecoveryPointRequest,
+    HealthCheckRequest, InitiateShardTransferRequest, QueryBatchPointsInternal, QueryShardPoints,
     RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
     ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
@@ -24,7 +24,9 @@ use parking_lot::Mutex;
 use segment::common::operation_time_statistics::{
     OperationDurationsAggregator, ScopeDurationMeasurer,
 };
-use segment::data_types::facets::{FacetParams, FacetResponse, FacetValueHit};
+use segment::data_types::facets::{
+    DistinctValuesParams, FacetParams, FacetResponse, FacetValue, FacetValueHit,
+};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -42,7 +44,7 @@ use super::local_shard::clock_map::RecoveryPoint;
 use super::replica_set::ReplicaState;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::point_ops::{PointOperations, UpsertMode, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
@@ -53,10 +55,10 @@ use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations, OperationWithClockTag};
 use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
-    internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
-    internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_clear_payload, internal_clear_payload_by_filter, internal_conditional_upsert_points,
+    internal_create_index, internal_dedup_upsert_points, internal_delete_index,
+    internal_delete_payload, internal_delete_points, internal_delete_points_by_filter,
+    internal_set_payload, internal_sync_points, internal_upsert_points, try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -259,6 +261,53 @@ impl RemoteShard {
                         operation.clock_tag,
                         collection_name,
                         point_insert_operations,
+                        UpsertMode::Upsert,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::InsertPointsIfAbsent(point_insert_operations) => {
+                    let request = &internal_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        point_insert_operations,
+                        UpsertMode::InsertIfAbsent,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsIfVersion(conditional_upsert) => {
+                    let request = &internal_conditional_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        conditional_upsert,
+                        wait,
+                        ordering,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsDedup(dedup_upsert) => {
+                    let request = &internal_dedup_upsert_points(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        dedup_upsert,
                         wait,
                         ordering,
                     )?;
@@ -973,4 +1022,48 @@ impl ShardOperation for RemoteShard {
 
         Ok(result)
     }
+
+    async fn distinct_values(
+        &self,
+        request: Arc<DistinctValuesParams>,
+        _search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<FacetValue>> {
+        let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
+        timer.set_success(false);
+
+        let DistinctValuesParams { key, limit, filter } = request.as_ref();
+
+        let response = self
+            .with_points_client(|mut client| async move {
+                let request = &DistinctValuesInternal {
+                    collection_name: self.collection_id.clone(),
+                    key: key.to_string(),
+                    filter: filter.clone().map(api::grpc::qdrant::Filter::from),
+                    limit: limit.unwrap_or(DistinctValuesParams::DEFAULT_LIMIT) as u64,
+                    shard_id: self.id,
+                    timeout: timeout.map(|t| t.as_secs()),
+                };
+
+                let mut request = tonic::Request::new(request.clone());
+
+                if let Some(timeout) = timeout {
+                    request.set_timeout(timeout);
+                }
+
+                client.distinct_values(request).await
+            })
+            .await?
+            .into_inner();
+
+        let values = response
+            .values
+            .into_iter()
+            .map(FacetValue::try_from)
+            .try_collect()?;
+
+        timer.set_success(true);
+
+        Ok(values)
+    }
 }
@@ -55,6 +55,19 @@ impl ShardReplicaSet {
 
         let read_consistency = read_consistency.unwrap_or_default();
 
+        // Never fall back to remote replicas, even if the local one is not available
+        if read_consistency == ReadConsistency::Type(ReadConsistencyType::LocalOnly) {
+            if !self.peer_is_active(&self.this_peer_id()) {
+                return Err(CollectionError::service_error(format!(
+                    "Shard {} has no active local replica on peer {}",
+                    self.shard_id,
+                    self.this_peer_id(),
+                )));
+            }
+
+            return self.execute_local_read_operation(read_operation).await;
+        }
+
         let local_count = usize::from(self.peer_state(&self.this_peer_id()).is_some());
         let active_local_count = usize::from(self.peer_is_active(&self.this_peer_id()));
 
@@ -85,6 +98,10 @@ impl ShardReplicaSet {
             ReadConsistency::Factor(factor) => {
                 (factor.clamp(1, total_count), ResolveCondition::All)
             }
+
+            ReadConsistency::Type(ReadConsistencyType::LocalOnly) => {
+                unreachable!("local only reads are executed on the local replica")
+            }
         };
 
         if active_count < required_successful_results {
@@ -0,0 +1,101 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::Mutex;
+
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Load a local shard on startup, within the configured timeout
+///
+/// Loading runs in a separate task, so that the timeout fires even while loading blocks its
+/// thread, e.g. when replaying the WAL. If the shard does not load in time, an error is returned
+/// and the task is aborted. Aborting only takes effect once loading yields, a load blocked in WAL
+/// replay or segment I/O keeps running and touching the shard directory until then.
+///
+/// `files_lock` is held until the task really stops, so that anything else working on the shard
+/// directory waits for an abandoned load to finish first.
+pub(super) async fn load_with_timeout<T: Send + 'static>(
+    load: impl Future<Output = CollectionResult<T>> + Send + 'static,
+    files_lock: Arc<Mutex<()>>,
+    timeout: Option<Duration>,
+) -> CollectionResult<T> {
+    let files_guard = files_lock.lock_owned().await;
+
+    let Some(timeout) = timeout else {
+        return load.await;
+    };
+
+    let mut task = tokio::spawn(async move {
+        let _files_guard = files_guard;
+        load.await
+    });
+    match tokio::time::timeout(timeout, &mut task).await {
+        Ok(result) => result?,
+        Err(_) => {
+            task.abort();
+            Err(CollectionError::timeout(
+                timeout.as_secs() as usize,
+                "load local shard",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    const LOAD_TIMEOUT: Duration = Duration::from_millis(100);
+
+    /// Blocks its thread like loading segments or replaying the WAL does.
+    async fn blocking_load(load_time: Duration) -> CollectionResult<&'static str> {
+        std::thread::sleep(load_time);
+        Ok("shard")
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_load_timeout_fires_while_loading_blocks() {
+        let files_lock = Arc::new(Mutex::new(()));
+        let start = Instant::now();
+        let loaded = load_with_timeout(
+            blocking_load(Duration::from_secs(1)),
+            files_lock.clone(),
+            Some(LOAD_TIMEOUT),
+        )
+        .await;
+
+        assert!(
+            matches!(loaded, Err(CollectionError::Timeout { .. })),
+            "{loaded:?}",
+        );
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // The abandoned load still runs, the shard directory is only free once it stops
+        assert!(files_lock.try_lock().is_err());
+        let _files_guard = files_lock.lock().await;
+        assert!(start.elapsed() >= Duration::from_secs(1));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_load_within_timeout() {
+        let files_lock = Arc::new(Mutex::new(()));
+        let loaded = load_with_timeout(
+            blocking_load(Duration::ZERO),
+            files_lock.clone(),
+            Some(LOAD_TIMEOUT),
+        )
+        .await;
+        assert_eq!(loaded.unwrap(), "shard");
+        assert!(files_lock.try_lock().is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_load_without_timeout() {
+        let files_lock = Arc::new(Mutex::new(()));
+        let loaded = load_with_timeout(blocking_load(LOAD_TIMEOUT * 2), files_lock, None).await;
+        assert_eq!(loaded.unwrap(), "shard");
+    }
+}
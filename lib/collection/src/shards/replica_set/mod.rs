@@ -1,10 +1,13 @@
 pub mod clock_set;
 mod execute_read_operation;
+mod load_timeout;
 mod locally_disabled_peers;
 mod read_ops;
 mod shard_transfer;
 mod snapshots;
+pub mod state_audit;
 mod update;
+pub(crate) mod upsert_coalescer;
 
 use std::collections::{HashMap, HashSet};
 use std::ops::Deref as _;
@@ -25,6 +28,7 @@ use super::remote_shard::RemoteShard;
 use super::transfer::ShardTransfer;
 use super::CollectionId;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::recall_monitor::RecallMonitorConfig;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::config::CollectionConfig;
 use crate::operations::shared_storage_config::SharedStorageConfig;
@@ -34,6 +38,8 @@ use crate::save_on_disk::SaveOnDisk;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::replica_set::clock_set::ClockSet;
+use crate::shards::replica_set::state_audit::{ReplicaStateAuditLog, ReplicaStateTransition};
+use crate::shards::replica_set::upsert_coalescer::UpsertCoalescer;
 use crate::shards::shard::{PeerId, Shard, ShardId};
 use crate::shards::shard_config::ShardConfig;
 use crate::shards::telemetry::ReplicaSetTelemetry;
@@ -88,6 +94,8 @@ pub struct ShardReplicaSet {
     /// If the state of the peer is changed in the consensus, it is removed from the list.
     /// Update and read operations are not performed on the peers marked as dead.
     locally_disabled_peers: parking_lot::RwLock<locally_disabled_peers::Registry>,
+    /// Recent transitions of replica states, for post-incident analysis.
+    state_audit: parking_lot::Mutex<ReplicaStateAuditLog>,
     pub(crate) shard_path: PathBuf,
     pub(crate) shard_id: ShardId,
     notify_peer_failure_cb: ChangePeerState,
@@ -103,8 +111,13 @@ pub struct ShardReplicaSet {
     optimizer_cpu_budget: CpuBudget,
     /// Lock to serialized write operations on the replicaset when a write ordering is used.
     write_ordering_lock: Mutex<()>,
+    /// Held while the local shard directory is loaded or replaced, including by a load that timed
+    /// out on startup but is still running in the background.
+    local_files_lock: Arc<Mutex<()>>,
     /// Local clock set, used to tag new operations on this shard.
     clock_set: Mutex<ClockSet>,
+    /// Coalesces concurrent single point upserts, if enabled.
+    upsert_coalescer: Option<UpsertCoalescer>,
 }
 
 pub type AbortShardTransfer = Arc<dyn Fn(ShardTransfer, &str) + Send + Sync>;
@@ -186,6 +199,8 @@ impl ShardReplicaSet {
             remotes: RwLock::new(remote_shards),
             replica_state: replica_state.into(),
             locally_disabled_peers: Default::default(),
+            state_audit: Default::default(),
+            upsert_coalescer: UpsertCoalescer::from_config(&shared_storage_config),
             shard_path,
             abort_shard_transfer_cb: abort_shard_transfer,
             notify_peer_failure_cb: on_peer_failure,
@@ -199,6 +214,7 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
+            local_files_lock: Default::default(),
             clock_set: Default::default(),
         })
     }
@@ -251,27 +267,51 @@ impl ShardReplicaSet {
             &channel_service,
         );
 
+        let local_files_lock = Arc::new(Mutex::new(()));
         let mut local_load_failure = false;
+        let mut local_quarantined = false;
         let local = if replica_state.read().is_local {
             let shard = if let Some(recovery_reason) = &shared_storage_config.recovery_mode {
                 Shard::Dummy(DummyShard::new(recovery_reason))
             } else {
-                let res = LocalShard::load(
-                    shard_id,
-                    collection_id.clone(),
-                    shard_path,
-                    collection_config.clone(),
-                    effective_optimizers_config.clone(),
-                    shared_storage_config.clone(),
-                    payload_index_schema.clone(),
-                    update_runtime.clone(),
-                    search_runtime.clone(),
-                    optimizer_cpu_budget.clone(),
+                let load = {
+                    let collection_id = collection_id.clone();
+                    let shard_path = shard_path.to_path_buf();
+                    let collection_config = collection_config.clone();
+                    let effective_optimizers_config = effective_optimizers_config.clone();
+                    let shared_storage_config = shared_storage_config.clone();
+                    let payload_index_schema = payload_index_schema.clone();
+                    let update_runtime = update_runtime.clone();
+                    let search_runtime = search_runtime.clone();
+                    let optimizer_cpu_budget = optimizer_cpu_budget.clone();
+                    async move {
+                        LocalShard::load(
+                            shard_id,
+                            collection_id,
+                            &shard_path,
+                            collection_config,
+                            effective_optimizers_config,
+                            shared_storage_config,
+                            payload_index_schema,
+                            update_runtime,
+                            search_runtime,
+                            optimizer_cpu_budget,
+                        )
+                        .await
+                    }
+                };
+                let res = load_timeout::load_with_timeout(
+                    load,
+                    local_files_lock.clone(),
+                    shared_storage_config.shard_load_timeout,
                 )
                 .await;
 
                 match res {
-                    Ok(shard) => Shard::Local(shard),
+                    Ok(shard) => {
+                        local_quarantined = shard.has_quarantined_operations();
+                        Shard::Local(shard)
+                    }
                     Err(err) => {
                         if !shared_storage_config.handle_collection_load_errors {
                             panic!("Failed to load local shard {shard_path:?}: {err}")
@@ -304,6 +344,8 @@ impl ShardReplicaSet {
             replica_state: replica_state.into(),
             // TODO: move to collection config
             locally_disabled_peers: Default::default(),
+            state_audit: Default::default(),
+            upsert_coalescer: UpsertCoalescer::from_config(&shared_storage_config),
             shard_path: shard_path.to_path_buf(),
             notify_peer_failure_cb: on_peer_failure,
             abort_shard_transfer_cb: abort_shard_transfer,
@@ -317,6 +359,7 @@ impl ShardReplicaSet {
             search_runtime,
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
+            local_files_lock,
             clock_set: Default::default(),
         };
 
@@ -327,6 +370,15 @@ impl ShardReplicaSet {
                 .disable_peer(this_peer_id);
         }
 
+        // Local replica misses changes of quarantined WAL operations, report it as failed to
+        // recover it from another replica. The last active replica keeps serving.
+        if local_quarantined {
+            log::warn!(
+                "Local shard {shard_path:?} skipped WAL operations on load, recovering it from another replica",
+            );
+            replica_set.add_locally_disabled(&replica_set.replica_state.read(), this_peer_id);
+        }
+
         replica_set
     }
 
@@ -343,6 +395,20 @@ impl ShardReplicaSet {
         matches!(*local_read, Some(Shard::Local(_) | Shard::Dummy(_)))
     }
 
+    /// Run `f` on the local shard, fails if this peer doesn't hold a regular local shard
+    pub async fn with_local_shard<T>(
+        &self,
+        f: impl FnOnce(&LocalShard) -> CollectionResult<T>,
+    ) -> CollectionResult<T> {
+        match self.local.read().await.deref() {
+            Some(Shard::Local(local_shard)) => f(local_shard),
+            _ => Err(CollectionError::bad_request(format!(
+                "Shard {} is not a local shard on this peer",
+                self.shard_id,
+            ))),
+        }
+    }
+
     pub async fn is_queue_proxy(&self) -> bool {
         let local_read = self.local.read().await;
         matches!(*local_read, Some(Shard::QueueProxy(_)))
@@ -468,6 +534,7 @@ impl ShardReplicaSet {
     }
 
     pub async fn init_empty_local_shard(&self) -> CollectionResult<()> {
+        let _files_guard = self.local_files_lock.lock().await;
         let mut local = self.local.write().await;
 
         let current_shard = local.take();
@@ -511,7 +578,7 @@ impl ShardReplicaSet {
         let old_shard = self.local.write().await.replace(Shard::Local(local));
 
         if !self.replica_state.read().is_local || state.is_some() {
-            self.replica_state.write(|rs| {
+            self.write_replica_state("local shard set", |rs| {
                 rs.is_local = true;
                 if let Some(active) = state {
                     rs.set_peer_state(self.this_peer_id(), active);
@@ -525,7 +592,7 @@ impl ShardReplicaSet {
     pub async fn remove_local(&self) -> CollectionResult<()> {
         // TODO: Ensure cancel safety!
 
-        self.replica_state.write(|rs| {
+        self.write_replica_state("local shard removed", |rs| {
             rs.is_local = false;
             let this_peer_id = rs.this_peer_id;
             rs.remove_peer_state(&this_peer_id);
@@ -541,7 +608,12 @@ impl ShardReplicaSet {
         if let Some(removing_local) = removing_local {
             // stop ongoing tasks and delete data
             drop(removing_local);
-            LocalShard::clear(&self.shard_path).await?;
+            let _files_guard = self.local_files_lock.lock().await;
+            LocalShard::clear(
+                &self.shard_path,
+                &self.shared_storage_config.segment_volumes,
+            )
+            .await?;
         }
         Ok(())
     }
@@ -549,7 +621,7 @@ impl ShardReplicaSet {
     pub async fn add_remote(&self, peer_id: PeerId, state: ReplicaState) -> CollectionResult<()> {
         debug_assert!(peer_id != self.this_peer_id());
 
-        self.replica_state.write(|rs| {
+        self.write_replica_state("remote replica added", |rs| {
             rs.set_peer_state(peer_id, state);
         })?;
 
@@ -573,7 +645,7 @@ impl ShardReplicaSet {
     }
 
     pub async fn remove_remote(&self, peer_id: PeerId) -> CollectionResult<()> {
-        self.replica_state.write(|rs| {
+        self.write_replica_state("remote replica removed", |rs| {
             rs.remove_peer_state(&peer_id);
         })?;
 
@@ -608,7 +680,7 @@ impl ShardReplicaSet {
             self.replica_state.read().get_peer_state(peer_id),
         );
 
-        self.replica_state.write(|rs| {
+        self.write_replica_state("replica state set", |rs| {
             if rs.this_peer_id == *peer_id {
                 rs.is_local = true;
             }
@@ -618,6 +690,30 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    /// Modify the replica set state, recording all replica state transitions in the audit log.
+    fn write_replica_state(
+        &self,
+        reason: &str,
+        modify: impl FnOnce(&mut ReplicaSetState),
+    ) -> CollectionResult<()> {
+        let (old_peers, new_peers) = self.replica_state.write(|rs| {
+            let old_peers = rs.peers();
+            modify(rs);
+            (old_peers, rs.peers())
+        })?;
+
+        self.state_audit
+            .lock()
+            .record_changes(self.shard_id, &old_peers, &new_peers, reason);
+
+        Ok(())
+    }
+
+    /// Recent replica state transitions of this replica set, oldest first.
+    pub fn replica_state_transitions(&self) -> Vec<ReplicaStateTransition> {
+        self.state_audit.lock().to_telemetry()
+    }
+
     pub async fn remove_peer(&self, peer_id: PeerId) -> CollectionResult<()> {
         if self.this_peer_id() == peer_id {
             self.remove_local().await?;
@@ -633,7 +729,7 @@ impl ShardReplicaSet {
     ) -> CollectionResult<()> {
         let old_peers = self.replica_state.read().peers();
 
-        self.replica_state.write(|state| {
+        self.write_replica_state("consensus state applied", |state| {
             state.set_peers(replicas.clone());
         })?;
 
@@ -659,6 +755,7 @@ impl ShardReplicaSet {
 
             if peer_id == self.this_peer_id() {
                 // Consensus wants a local replica on this peer
+                let _files_guard = self.local_files_lock.lock().await;
                 let local_shard = LocalShard::build(
                     self.shard_id,
                     self.collection_id.clone(),
@@ -714,6 +811,43 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Rebuild vector indices of the local replica, returns the number of scheduled segments
+    pub(crate) async fn reindex(&self) -> CollectionResult<usize> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.reindex().await
+        } else {
+            Ok(0)
+        }
+    }
+
+    /// Measure recall of approximate search in the local replica, see
+    /// [`LocalShard::sample_recall`]
+    ///
+    /// Returns nothing if this peer doesn't hold a regular local shard.
+    pub(crate) async fn sample_recall(
+        &self,
+        config: &RecallMonitorConfig,
+    ) -> CollectionResult<Vec<f64>> {
+        match self.local.read().await.deref() {
+            Some(Shard::Local(local_shard)) => {
+                local_shard
+                    .sample_recall(config, &self.search_runtime)
+                    .await
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    pub(crate) async fn cancel_reindex(&self) -> CollectionResult<()> {
+        let read_local = self.local.read().await;
+        if let Some(shard) = &*read_local {
+            shard.cancel_reindex().await
+        } else {
+            Ok(())
+        }
+    }
+
     /// Check if the are any locally disabled peers
     /// And if so, report them to the consensus
     pub fn sync_local_state<F>(&self, get_shard_transfers: F) -> CollectionResult<()>
@@ -760,6 +894,7 @@ impl ShardReplicaSet {
                 .map(|remote| remote.get_telemetry_data(detail))
                 .collect(),
             replicate_states: self.replica_state.read().peers(),
+            state_transitions: self.replica_state_transitions(),
         }
     }
 
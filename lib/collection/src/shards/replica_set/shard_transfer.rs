@@ -2,7 +2,7 @@ use std::ops::Deref as _;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
-use segment::types::PointIdType;
+use segment::types::{Filter, PointIdType};
 
 use super::ShardReplicaSet;
 use crate::hash_ring::HashRingRouter;
@@ -341,6 +341,7 @@ impl ShardReplicaSet {
         offset: Option<PointIdType>,
         batch_size: usize,
         hashring_filter: Option<&HashRingRouter>,
+        filter: Option<&Filter>,
         merge_points: bool,
     ) -> CollectionResult<Option<PointIdType>> {
         let local = self.local.read().await;
@@ -357,12 +358,38 @@ impl ShardReplicaSet {
                 offset,
                 batch_size,
                 hashring_filter,
+                filter,
                 merge_points,
                 &self.search_runtime,
             )
             .await
     }
 
+    /// Count points in the local shard and in the remote shard it is being transferred to.
+    ///
+    /// Returns `(local_count, remote_count)`.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn count_transferred_points(
+        &self,
+        filter: Option<Filter>,
+    ) -> CollectionResult<(usize, usize)> {
+        let local = self.local.read().await;
+
+        let Some(Shard::ForwardProxy(proxy)) = local.deref() else {
+            return Err(CollectionError::service_error(format!(
+                "Cannot count transferred points of shard {} because it is not proxified",
+                self.shard_id,
+            )));
+        };
+
+        proxy
+            .count_transferred_points(filter, &self.search_runtime)
+            .await
+    }
+
     /// Custom operation for transferring indexes from one shard to another during transfer
     ///
     /// # Cancel safety
@@ -1,5 +1,7 @@
 use std::ops::Deref as _;
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use super::{ReplicaSetState, ReplicaState, ShardReplicaSet, REPLICA_STATE_FILE};
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -15,12 +17,13 @@ impl ShardReplicaSet {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         let local_read = self.local.read().await;
 
         if let Some(local) = &*local_read {
             local
-                .create_snapshot(temp_path, target_path, save_wal)
+                .create_snapshot(temp_path, target_path, save_wal, stopped)
                 .await?
         }
 
@@ -32,6 +35,11 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    /// Last operation sequence number in the WAL of the local shard, if there is one.
+    pub async fn wal_last_index(&self) -> Option<u64> {
+        self.local.read().await.as_ref()?.wal_last_index()
+    }
+
     pub fn restore_snapshot(
         snapshot_path: &Path,
         this_peer_id: PeerId,
@@ -88,6 +96,9 @@ impl ShardReplicaSet {
         //   Check that shard snapshot is compatible with the collection
         //   (see `VectorsConfig::check_compatible_with_segment_config`)
 
+        // Wait for a local shard load, which timed out on startup, to stop touching shard files
+        let _files_guard =
+            cancel::future::cancel_on_token(cancel.clone(), self.local_files_lock.lock()).await?;
         let mut local = cancel::future::cancel_on_token(cancel.clone(), self.local.write()).await?;
 
         // Check `cancel` token one last time before starting non-cancellable section
@@ -101,7 +112,11 @@ impl ShardReplicaSet {
         // Try to restore local replica from specified shard snapshot directory
         let restore = async {
             if clear {
-                LocalShard::clear(&self.shard_path).await?;
+                LocalShard::clear(
+                    &self.shard_path,
+                    &self.shared_storage_config.segment_volumes,
+                )
+                .await?;
             }
 
             LocalShard::move_data(replica_path, &self.shard_path).await?;
@@ -0,0 +1,139 @@
+use std::collections::{HashMap, VecDeque};
+
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use super::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+
+/// Number of last replica state transitions to keep in the audit log of a replica set
+const KEEP_LAST_TRANSITIONS: usize = 64;
+
+/// Change of the state of a single replica, used in telemetry
+#[derive(Serialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReplicaStateTransition {
+    /// Time of the change
+    pub timestamp: DateTime<Utc>,
+    pub shard_id: ShardId,
+    pub peer_id: PeerId,
+    /// State before the change, `None` if the replica was added
+    pub from: Option<ReplicaState>,
+    /// State after the change, `None` if the replica was removed
+    pub to: Option<ReplicaState>,
+    /// What caused the change
+    pub reason: String,
+}
+
+/// A bounded log of replica state transitions
+///
+/// Once full, the oldest transitions are forgotten first.
+#[derive(Clone, Debug, Default)]
+pub struct ReplicaStateAuditLog {
+    transitions: VecDeque<ReplicaStateTransition>,
+}
+
+impl ReplicaStateAuditLog {
+    /// Record a transition for every peer whose state differs between `old` and `new`
+    pub fn record_changes(
+        &mut self,
+        shard_id: ShardId,
+        old: &HashMap<PeerId, ReplicaState>,
+        new: &HashMap<PeerId, ReplicaState>,
+        reason: &str,
+    ) {
+        let mut changed_peers: Vec<_> = old
+            .keys()
+            .chain(new.keys())
+            .filter(|peer_id| old.get(peer_id) != new.get(peer_id))
+            .copied()
+            .collect();
+        changed_peers.sort_unstable();
+        changed_peers.dedup();
+
+        let timestamp = Utc::now();
+        for peer_id in changed_peers {
+            self.record(ReplicaStateTransition {
+                timestamp,
+                shard_id,
+                peer_id,
+                from: old.get(&peer_id).copied(),
+                to: new.get(&peer_id).copied(),
+                reason: reason.to_string(),
+            });
+        }
+    }
+
+    fn record(&mut self, transition: ReplicaStateTransition) {
+        log::debug!(
+            "Replica of shard {} on peer {} changed state from {:?} to {:?}: {}",
+            transition.shard_id,
+            transition.peer_id,
+            transition.from,
+            transition.to,
+            transition.reason,
+        );
+
+        if self.transitions.len() >= KEEP_LAST_TRANSITIONS {
+            self.transitions.pop_front();
+        }
+        self.transitions.push_back(transition);
+    }
+
+    /// Convert log into list of transitions usable in telemetry, oldest first
+    pub fn to_telemetry(&self) -> Vec<ReplicaStateTransition> {
+        self.transitions.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_log_is_bounded() {
+        let mut log = ReplicaStateAuditLog::default();
+
+        let mut old = HashMap::from([(1, ReplicaState::Dead)]);
+        for i in 0..KEEP_LAST_TRANSITIONS + 10 {
+            let state = if i % 2 == 0 {
+                ReplicaState::Active
+            } else {
+                ReplicaState::Dead
+            };
+            let new = HashMap::from([(1, state)]);
+            log.record_changes(0, &old, &new, &format!("change {i}"));
+            old = new;
+        }
+
+        let transitions = log.to_telemetry();
+        assert_eq!(transitions.len(), KEEP_LAST_TRANSITIONS);
+        assert_eq!(transitions.first().unwrap().reason, "change 10");
+        assert_eq!(
+            transitions.last().unwrap().reason,
+            format!("change {}", KEEP_LAST_TRANSITIONS + 9),
+        );
+    }
+
+    #[test]
+    fn test_audit_log_ignores_unchanged_peers() {
+        let mut log = ReplicaStateAuditLog::default();
+
+        let old = HashMap::from([(1, ReplicaState::Active), (2, ReplicaState::Partial)]);
+        let new = HashMap::from([(1, ReplicaState::Active), (3, ReplicaState::Partial)]);
+        log.record_changes(0, &old, &new, "test");
+
+        let changes: Vec<_> = log
+            .to_telemetry()
+            .into_iter()
+            .map(|transition| (transition.peer_id, transition.from, transition.to))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                (2, Some(ReplicaState::Partial), None),
+                (3, None, Some(ReplicaState::Partial)),
+            ],
+        );
+    }
+}
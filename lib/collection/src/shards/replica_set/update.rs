@@ -4,12 +4,17 @@ use std::time::Duration;
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use itertools::Itertools as _;
+use segment::common::check_named_vectors;
+use segment::types::SegmentConfig;
 
+use super::upsert_coalescer::UpsertCoalescer;
 use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::payload_ops::{PayloadOps, SetPayloadOp};
+use crate::operations::point_ops::{DedupUpsert, PointOperations, PointStruct, WriteOrdering};
 use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use crate::operations::vector_dedup::VectorDedup;
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation as _;
 
 /// Maximum number of attempts for applying an update with a new clock.
@@ -21,6 +26,28 @@ const UPDATE_MAX_CLOCK_REJECTED_RETRIES: usize = 3;
 const DEFAULT_SHARD_DEACTIVATION_TIMEOUT: Duration = Duration::from_secs(30);
 
 impl ShardReplicaSet {
+    /// Coalescer of concurrent single point upserts, if enabled
+    pub(crate) fn upsert_coalescer(&self) -> Option<&UpsertCoalescer> {
+        self.upsert_coalescer.as_ref()
+    }
+
+    /// Check vectors of a point against the collection config
+    ///
+    /// Used before a point is coalesced with upserts of other callers, so that a bad point fails
+    /// only the upsert of its own caller.
+    pub(crate) async fn check_point_vectors(&self, point: &PointStruct) -> CollectionResult<()> {
+        let segment_config = {
+            let config = self.collection_config.read().await;
+            SegmentConfig {
+                vector_data: config.params.to_base_vector_data()?,
+                sparse_vector_data: config.params.to_sparse_vector_data()?,
+                payload_storage_type: config.params.payload_storage_type(),
+            }
+        };
+        check_named_vectors(&point.get_vectors(), &segment_config)?;
+        Ok(())
+    }
+
     /// Update local shard if any without forwarding to remote shards
     ///
     /// # Cancel safety
@@ -74,6 +101,20 @@ impl ShardReplicaSet {
     ) -> CollectionResult<UpdateResult> {
         // `ShardReplicaSet::update` is not cancel safe, so this method is not cancel safe.
 
+        // Expected versions of a conditional upsert and stored duplicates of a dedup upsert are
+        // checked once, by the leader, so it has to go through the leader even with weak ordering
+        let is_checked_by_leader = matches!(
+            operation,
+            CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPointsIfVersion(_) | PointOperations::UpsertPointsDedup(_)
+            ),
+        );
+        let ordering = if is_checked_by_leader && ordering == WriteOrdering::Weak {
+            WriteOrdering::Medium
+        } else {
+            ordering
+        };
+
         let Some(leader_peer) = self.leader_peer_for_update(ordering) else {
             return Err(CollectionError::service_error(format!(
                 "Cannot update shard {}:{} with {ordering:?} ordering because no leader could be selected",
@@ -91,7 +132,12 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
-            self.update(operation, wait).await
+            match self.check_conditional_update(operation).await? {
+                CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsDedup(
+                    dedup_upsert,
+                )) => self.update_with_dedup(dedup_upsert, wait).await,
+                operation => self.update(operation, wait).await,
+            }
         } else {
             // Forward the update to the designated leader
             self.forward_update(leader_peer, operation, wait, ordering)
@@ -112,6 +158,98 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Check expected point versions of a conditional upsert on the local replica of the leader
+    ///
+    /// Returns a plain upsert if all versions match, which is then sent to all replicas and
+    /// applied by them unconditionally. Other operations are returned as is.
+    ///
+    /// Must be called while holding `write_ordering_lock`, so that no other ordered update is
+    /// accepted between the check and the upsert.
+    async fn check_conditional_update(
+        &self,
+        operation: CollectionUpdateOperations,
+    ) -> CollectionResult<CollectionUpdateOperations> {
+        let conditional_upsert = match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsIfVersion(
+                conditional_upsert,
+            )) => conditional_upsert,
+            operation => return Ok(operation),
+        };
+
+        let local = self.local.read().await;
+        self.leader_local_shard(&local, "check expected point versions")?
+            .check_expected_versions(&conditional_upsert)
+            .await?;
+
+        Ok(CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(conditional_upsert.points),
+        ))
+    }
+
+    /// Upsert points, except those with exactly the same vectors as a point stored in the shard
+    ///
+    /// Stored duplicates are looked up on the local replica of the leader, which then sends
+    /// a plain upsert of the remaining points to all replicas. With `merge` policy, payload of
+    /// the skipped points is then set on their stored duplicates.
+    ///
+    /// Must be called while holding `write_ordering_lock`, so that no other ordered update is
+    /// accepted between the lookup and the upsert.
+    async fn update_with_dedup(
+        &self,
+        dedup_upsert: DedupUpsert,
+        wait: bool,
+    ) -> CollectionResult<UpdateResult> {
+        let DedupUpsert { mut points, policy } = dedup_upsert;
+
+        let stored_duplicates = {
+            let local = self.local.read().await;
+            self.leader_local_shard(&local, "look up duplicates of stored vectors")?
+                .dedup_stored_vectors(&mut points)
+                .await?
+        };
+
+        let upsert =
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(points));
+        let mut result = self.update(upsert, wait).await?;
+
+        if policy == VectorDedup::Merge {
+            for (stored_id, payload) in &stored_duplicates {
+                let Some(payload) = payload else {
+                    continue;
+                };
+                let set_payload = CollectionUpdateOperations::PayloadOperation(
+                    PayloadOps::SetPayload(SetPayloadOp {
+                        payload: payload.clone(),
+                        points: Some(vec![*stored_id]),
+                        filter: None,
+                        key: None,
+                    }),
+                );
+                result = self.update(set_payload, wait).await?;
+            }
+        }
+
+        result.deduplicated = Some(stored_duplicates.len());
+        Ok(result)
+    }
+
+    /// Local replica of the leader, which checks an update before it is sent to all replicas
+    fn leader_local_shard<'a>(
+        &self,
+        local: &'a Option<Shard>,
+        action: &str,
+    ) -> CollectionResult<&'a Shard> {
+        match local {
+            Some(local_shard) if self.peer_is_active(&self.this_peer_id()) => Ok(local_shard),
+            _ => Err(CollectionError::service_error(format!(
+                "Cannot {action} in shard {}:{}, leader peer {} has no active local replica",
+                self.collection_id,
+                self.shard_id,
+                self.this_peer_id(),
+            ))),
+        }
+    }
+
     /// Designated a leader replica for the update based on the WriteOrdering
     fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
@@ -511,9 +649,11 @@ impl ShardReplicaSet {
 mod tests {
     use std::collections::HashSet;
     use std::num::NonZeroU32;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
 
     use common::cpu::CpuBudget;
+    use common::types::TelemetryDetail;
     use segment::types::Distance;
     use tempfile::{Builder, TempDir};
     use tokio::runtime::Handle;
@@ -521,6 +661,8 @@ mod tests {
 
     use super::*;
     use crate::config::*;
+    use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
+    use crate::operations::types::CountRequestInternal;
     use crate::operations::types::VectorsConfig;
     use crate::operations::vector_params_builder::VectorParamsBuilder;
     use crate::optimizers_builder::OptimizersConfig;
@@ -545,6 +687,80 @@ mod tests {
         assert_eq!(rs.highest_alive_replica_peer_id(), Some(4));
     }
 
+    #[tokio::test]
+    async fn test_replica_state_audit_log() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir).await;
+        let initial_transitions = rs.replica_state_transitions().len();
+
+        rs.set_replica_state(&2, ReplicaState::Partial).unwrap();
+        rs.set_replica_state(&2, ReplicaState::Active).unwrap();
+        rs.set_replica_state(&3, ReplicaState::Dead).unwrap();
+        rs.set_replica_state(&2, ReplicaState::Dead).unwrap();
+        rs.remove_peer(3).await.unwrap();
+
+        let transitions: Vec<_> = rs
+            .replica_state_transitions()
+            .into_iter()
+            .skip(initial_transitions)
+            .map(|transition| (transition.peer_id, transition.from, transition.to))
+            .collect();
+
+        // Setting an unchanged state is not a transition
+        assert_eq!(
+            transitions,
+            vec![
+                (2, Some(ReplicaState::Dead), Some(ReplicaState::Partial)),
+                (2, Some(ReplicaState::Partial), Some(ReplicaState::Active)),
+                (2, Some(ReplicaState::Active), Some(ReplicaState::Dead)),
+                (3, Some(ReplicaState::Dead), None),
+            ],
+        );
+
+        let telemetry = rs.get_telemetry_data(TelemetryDetail::default()).await;
+        assert_eq!(
+            telemetry.state_transitions.len(),
+            initial_transitions + transitions.len(),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_local_only_read_consistency() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir).await;
+        for peer_id in 2..=5 {
+            rs.set_replica_state(&peer_id, ReplicaState::Active)
+                .unwrap();
+        }
+
+        let search_runtime = Handle::current();
+        let request = Arc::new(CountRequestInternal {
+            filter: None,
+            exact: true,
+        });
+        let executed = AtomicUsize::new(0);
+        let count = || {
+            rs.execute_and_resolve_read_operation(
+                |shard| {
+                    executed.fetch_add(1, Ordering::SeqCst);
+                    let request = request.clone();
+                    let search_runtime = search_runtime.clone();
+                    async move { shard.count(request, &search_runtime, None).await }.boxed()
+                },
+                Some(ReadConsistency::Type(ReadConsistencyType::LocalOnly)),
+                false,
+            )
+        };
+
+        // Local replica is not active, remote replicas must not be used instead
+        assert!(count().await.is_err());
+        assert_eq!(executed.load(Ordering::SeqCst), 0);
+
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        assert_eq!(count().await.unwrap().count, 0);
+        assert_eq!(executed.load(Ordering::SeqCst), 1);
+    }
+
     const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
         deleted_threshold: 0.9,
         vacuum_min_vector_number: 1000,
@@ -552,6 +768,7 @@ mod tests {
         max_segment_size: None,
         memmap_threshold: None,
         indexing_threshold: Some(50_000),
+        indexing_min_vector_number: None,
         flush_interval_sec: 30,
         max_optimization_threads: Some(2),
     };
@@ -563,6 +780,8 @@ mod tests {
         let wal_config = WalConfig {
             wal_capacity_mb: 1,
             wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
         };
 
         let collection_params = CollectionParams {
@@ -580,6 +799,10 @@ mod tests {
             hnsw_config: Default::default(),
             quantization_config: None,
             strict_mode_config: None,
+            payload_schema: None,
+            default_search_params: None,
+            tie_break: None,
+            default_timeout: None,
         };
 
         let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
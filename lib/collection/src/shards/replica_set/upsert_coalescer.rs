@@ -0,0 +1,406 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::mem;
+use std::sync::Arc;
+use std::time::Duration;
+
+use parking_lot::Mutex;
+use tokio::sync::{oneshot, Notify};
+
+use crate::common::batching::batch_requests;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult};
+use crate::operations::CollectionUpdateOperations;
+
+/// Coalesces concurrent single point upserts into batched upserts.
+///
+/// The first upsert arriving at an empty queue waits for up to `max_delay`, or until
+/// `max_batch_size` upserts are queued, and then executes all queued upserts in as few
+/// operations as possible. This results in a single WAL entry and segment write per batch,
+/// instead of one per point.
+///
+/// Batches are executed in a separate task, every caller receives the result of the batch its
+/// point was written in. If a batch fails, the upsert of every caller is applied on its own
+/// instead, so that only callers with bad points receive an error.
+pub struct UpsertCoalescer {
+    max_batch_size: usize,
+    max_delay: Duration,
+    pending: Arc<Mutex<Vec<PendingUpsert>>>,
+    batch_full: Arc<Notify>,
+}
+
+struct PendingUpsert {
+    point: PointStruct,
+    wait: bool,
+    ordering: WriteOrdering,
+    sender: oneshot::Sender<CollectionResult<UpdateResult>>,
+}
+
+/// Key of a coalesced batch, consecutive upserts with the same key are executed together
+#[derive(Clone, Copy, PartialEq)]
+struct BatchKey {
+    ordering: WriteOrdering,
+    batch_number: usize,
+}
+
+impl UpsertCoalescer {
+    pub fn new(max_batch_size: usize, max_delay: Duration) -> Self {
+        Self {
+            max_batch_size: max_batch_size.max(1),
+            max_delay,
+            pending: Default::default(),
+            batch_full: Default::default(),
+        }
+    }
+
+    /// Create a coalescer if enabled in the storage configuration.
+    pub fn from_config(config: &SharedStorageConfig) -> Option<Self> {
+        config
+            .upsert_coalesce_max_batch
+            .map(|max_batch_size| Self::new(max_batch_size, config.upsert_coalesce_max_delay))
+    }
+
+    /// Take the point out of a single point upsert, which can be coalesced.
+    ///
+    /// Returns the operation unchanged if it can't be coalesced.
+    pub fn single_point_upsert(
+        operation: CollectionUpdateOperations,
+    ) -> Result<PointStruct, CollectionUpdateOperations> {
+        match operation {
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(mut points),
+            )) if points.len() == 1 => Ok(points.pop().unwrap()),
+            operation => Err(operation),
+        }
+    }
+
+    /// Queue a single point upsert and wait for the result of the batch it is written in.
+    ///
+    /// The first upsert of a batch spawns a task, which calls `execute` to apply each coalesced
+    /// batch.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe. Cancelling the caller doesn't affect other upserts of the batch,
+    /// but the point of the cancelled caller may still be written.
+    pub async fn upsert<F, Fut>(
+        &self,
+        point: PointStruct,
+        wait: bool,
+        ordering: WriteOrdering,
+        execute: F,
+    ) -> CollectionResult<UpdateResult>
+    where
+        F: Fn(CollectionUpdateOperations, bool, WriteOrdering) -> Fut + Send + 'static,
+        Fut: Future<Output = CollectionResult<UpdateResult>> + Send,
+    {
+        let (sender, receiver) = oneshot::channel();
+
+        let executes_batch = {
+            let mut pending = self.pending.lock();
+            pending.push(PendingUpsert {
+                point,
+                wait,
+                ordering,
+                sender,
+            });
+            if pending.len() >= self.max_batch_size {
+                self.batch_full.notify_one();
+            }
+            pending.len() == 1
+        };
+
+        if executes_batch {
+            let max_batch_size = self.max_batch_size;
+            let max_delay = self.max_delay;
+            let pending = self.pending.clone();
+            let batch_full = self.batch_full.clone();
+
+            tokio::spawn(async move {
+                // Give other upserts a chance to join the batch
+                let _ = tokio::time::timeout(max_delay, batch_full.notified()).await;
+                let batch = mem::take(&mut *pending.lock());
+                execute_batch(max_batch_size, batch, execute).await;
+            });
+        }
+
+        receiver.await.map_err(|_| {
+            CollectionError::service_error("Coalesced upsert batch was cancelled before completion")
+        })?
+    }
+}
+
+async fn execute_batch<F, Fut>(max_batch_size: usize, batch: Vec<PendingUpsert>, execute: F)
+where
+    F: Fn(CollectionUpdateOperations, bool, WriteOrdering) -> Fut,
+    Fut: Future<Output = CollectionResult<UpdateResult>>,
+{
+    // Split into batches of limited size, which never upsert the same point twice
+    let mut batch_number = 0;
+    let mut batch_size = 0;
+    let mut batch_ids = HashSet::new();
+    let keyed_upserts = batch.into_iter().map(|upsert| {
+        if batch_size >= max_batch_size || !batch_ids.insert(upsert.point.id) {
+            batch_number += 1;
+            batch_size = 0;
+            batch_ids.clear();
+            batch_ids.insert(upsert.point.id);
+        }
+        batch_size += 1;
+        let key = BatchKey {
+            ordering: upsert.ordering,
+            batch_number,
+        };
+        (key, upsert)
+    });
+
+    let batches = batch_requests::<_, BatchKey, Vec<PendingUpsert>, Vec<_>>(
+        keyed_upserts,
+        |(key, _)| key,
+        |(_, upsert), upserts| {
+            upserts.push(upsert);
+            Ok(())
+        },
+        |key, upserts, batches| {
+            batches.push((key, upserts));
+            Ok(())
+        },
+    )
+    .expect("batching upserts never fails");
+
+    for (key, upserts) in batches {
+        let wait = upserts.iter().any(|upsert| upsert.wait);
+        let points = upserts.iter().map(|upsert| upsert.point.clone()).collect();
+
+        let result = execute(upsert_operation(points), wait, key.ordering).await;
+
+        match result {
+            // A single bad point fails the whole batch, apply the upsert of every caller on its
+            // own, so that only callers with bad points receive an error
+            Err(err) if upserts.len() > 1 && !err.is_transient() => {
+                log::debug!("Coalesced upsert batch failed, applying upserts one by one: {err}");
+                for upsert in upserts {
+                    let result = execute(
+                        upsert_operation(vec![upsert.point]),
+                        upsert.wait,
+                        key.ordering,
+                    )
+                    .await;
+                    // Caller may not wait for the result anymore
+                    let _ = upsert.sender.send(result);
+                }
+            }
+            result => {
+                for upsert in upserts {
+                    // Caller may not wait for the result anymore
+                    let _ = upsert.sender.send(result.clone());
+                }
+            }
+        }
+    }
+}
+
+fn upsert_operation(points: Vec<PointStruct>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use futures::future::{self, join_all};
+    use segment::data_types::vectors::VectorStructInternal;
+
+    use super::*;
+    use crate::operations::types::UpdateStatus;
+
+    fn point(id: u64) -> PointStruct {
+        PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        }
+    }
+
+    fn counting_execute(
+        executions: Arc<AtomicUsize>,
+    ) -> impl Fn(
+        CollectionUpdateOperations,
+        bool,
+        WriteOrdering,
+    ) -> future::Ready<CollectionResult<UpdateResult>>
+           + Send
+           + 'static {
+        move |_operation, _wait, _ordering| {
+            executions.fetch_add(1, Ordering::SeqCst);
+            future::ready(Ok(UpdateResult {
+                operation_id: None,
+                status: UpdateStatus::Completed,
+                clock_tag: None,
+                deduplicated: None,
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_concurrent_upserts() {
+        const NUM_UPSERTS: u64 = 100;
+        const MAX_BATCH_SIZE: usize = 32;
+
+        let coalescer = UpsertCoalescer::new(MAX_BATCH_SIZE, Duration::from_millis(50));
+        let executed = Arc::new(Mutex::new(Vec::new()));
+
+        let execute = {
+            let executed = executed.clone();
+            move |operation: CollectionUpdateOperations, _wait: bool, _ordering: WriteOrdering| {
+                let executed = executed.clone();
+                async move {
+                    let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                        PointInsertOperationsInternal::PointsList(points),
+                    )) = operation
+                    else {
+                        panic!("unexpected operation");
+                    };
+                    let mut executed = executed.lock();
+                    executed.push(points.len());
+                    CollectionResult::Ok(UpdateResult {
+                        operation_id: Some(executed.len() as u64),
+                        status: UpdateStatus::Completed,
+                        clock_tag: None,
+                        deduplicated: None,
+                    })
+                }
+            }
+        };
+
+        let results = join_all((0..NUM_UPSERTS).map(|id| {
+            coalescer.upsert(point(id), id % 2 == 0, WriteOrdering::Weak, execute.clone())
+        }))
+        .await;
+
+        for result in results {
+            assert_eq!(result.unwrap().status, UpdateStatus::Completed);
+        }
+
+        let executed = executed.lock();
+        assert_eq!(executed.iter().sum::<usize>(), NUM_UPSERTS as usize);
+        assert!(executed.iter().all(|&size| size <= MAX_BATCH_SIZE));
+        assert!(executed.len() < NUM_UPSERTS as usize / 2);
+    }
+
+    #[tokio::test]
+    async fn test_coalesce_same_point_in_separate_batches() {
+        let coalescer = UpsertCoalescer::new(10, Duration::from_millis(50));
+        let executions = Arc::new(AtomicUsize::new(0));
+
+        let results = join_all([1, 2, 1].into_iter().map(|id| {
+            coalescer.upsert(
+                point(id),
+                true,
+                WriteOrdering::Weak,
+                counting_execute(executions.clone()),
+            )
+        }))
+        .await;
+        assert!(results.into_iter().all(|result| result.is_ok()));
+
+        // The second upsert of point 1 must be applied after the first one
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_caller_does_not_fail_batch() {
+        let coalescer = UpsertCoalescer::new(10, Duration::from_millis(100));
+        let executions = Arc::new(AtomicUsize::new(0));
+
+        // First caller starts the batch and is cancelled while other upserts may join it
+        let cancelled = tokio::time::timeout(
+            Duration::from_millis(10),
+            coalescer.upsert(
+                point(1),
+                true,
+                WriteOrdering::Weak,
+                counting_execute(executions.clone()),
+            ),
+        )
+        .await;
+        assert!(cancelled.is_err());
+
+        let result = coalescer
+            .upsert(
+                point(2),
+                true,
+                WriteOrdering::Weak,
+                counting_execute(executions.clone()),
+            )
+            .await;
+        assert_eq!(result.unwrap().status, UpdateStatus::Completed);
+
+        // Both points were written in the batch of the cancelled caller
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_fails_only_bad_upserts() {
+        const BAD_POINT: u64 = 3;
+
+        let coalescer = UpsertCoalescer::new(10, Duration::from_millis(50));
+        let executed = Arc::new(Mutex::new(Vec::new()));
+
+        let execute = {
+            let executed = executed.clone();
+            move |operation: CollectionUpdateOperations, _wait: bool, _ordering: WriteOrdering| {
+                let executed = executed.clone();
+                async move {
+                    let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                        PointInsertOperationsInternal::PointsList(points),
+                    )) = operation
+                    else {
+                        panic!("unexpected operation");
+                    };
+                    executed.lock().push(points.len());
+                    if points.iter().any(|point| point.id == BAD_POINT.into()) {
+                        return Err(CollectionError::bad_input("bad point"));
+                    }
+                    CollectionResult::Ok(UpdateResult {
+                        operation_id: None,
+                        status: UpdateStatus::Completed,
+                        clock_tag: None,
+                        deduplicated: None,
+                    })
+                }
+            }
+        };
+
+        let results = join_all(
+            (1..=5)
+                .map(|id| coalescer.upsert(point(id), true, WriteOrdering::Weak, execute.clone())),
+        )
+        .await;
+
+        for (id, result) in (1..=5).zip(results) {
+            assert_eq!(result.is_err(), id == BAD_POINT, "{result:?}");
+        }
+
+        // The batch, then every upsert on its own
+        assert_eq!(*executed.lock(), vec![5, 1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn test_single_point_upsert() {
+        let single = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![point(1)]),
+        ));
+        assert!(UpsertCoalescer::single_point_upsert(single).is_ok());
+
+        let multiple = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![point(1), point(2)]),
+        ));
+        assert!(UpsertCoalescer::single_point_upsert(multiple).is_err());
+    }
+}
@@ -5,7 +5,7 @@ mod stage_commit_read_hashring;
 mod stage_commit_write_hashring;
 mod stage_finalize;
 mod stage_init;
-mod stage_migrate_points;
+pub(crate) mod stage_migrate_points;
 mod stage_propagate_deletes;
 mod stage_replicate;
 
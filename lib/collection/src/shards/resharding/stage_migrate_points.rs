@@ -1,20 +1,23 @@
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Duration;
 
 use parking_lot::Mutex;
 use rand::seq::SliceRandom;
+use segment::types::{Condition, Filter};
 use tokio::task::block_in_place;
 use tokio::time::sleep;
 
 use super::driver::{PersistedState, Stage};
 use super::tasks_pool::ReshardTaskProgress;
 use super::ReshardKey;
+use crate::hash_ring::HashRingRouter;
 use crate::operations::cluster_ops::ReshardingDirection;
 use crate::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
 };
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, PointRequestInternal};
 use crate::operations::CollectionUpdateOperations;
 use crate::shards::channel_service::ChannelService;
 use crate::shards::remote_shard::RemoteShard;
@@ -182,6 +185,7 @@ async fn drive_up(
                         to: this_peer_id,
                         sync: true,
                         method: Some(ShardTransferMethod::ReshardingStreamRecords),
+                        filter: None,
                     };
                     (Some(transfer), true)
                 } else {
@@ -292,59 +296,121 @@ async fn drive_down(
     };
 
     while let Some(target_shard_id) = block_in_place(|| state.read().shards_to_migrate().next()) {
-        let mut offset = None;
-
-        loop {
-            let shard_holder = shard_holder.read().await;
-
-            let source_replica_set =
-                shard_holder
-                    .get_shard(&reshard_key.shard_id)
-                    .ok_or_else(|| {
-                        CollectionError::service_error(format!(
-                            "Shard {} not found in the shard holder for resharding",
-                            reshard_key.shard_id,
-                        ))
-                    })?;
-            let target_replica_set = shard_holder.get_shard(&target_shard_id).ok_or_else(|| {
-                CollectionError::service_error(format!(
-                    "Shard {target_shard_id} not found in the shard holder for resharding",
-                ))
-            })?;
-
-            // Take batch of points, if full, pop the last entry as next batch offset
-            let mut points = source_replica_set
-                .scroll_by(
-                    offset,
-                    MIGRATE_BATCH_SIZE + 1,
-                    &true.into(),
-                    &true.into(),
-                    // TODO(resharding): directly apply hash ring filter here?
-                    None,
-                    None,
-                    false,
-                    None,
-                    None,
-                )
-                .await?;
+        let migrated = migrate_points(
+            &shard_holder,
+            &hashring,
+            reshard_key.shard_id,
+            target_shard_id,
+        )
+        .await?;
+        log::trace!(
+            "Migrated {migrated} points of shard {} into shard {target_shard_id} for resharding",
+            reshard_key.shard_id,
+        );
 
-            offset = if points.len() > MIGRATE_BATCH_SIZE {
-                points.pop().map(|point| point.id)
-            } else {
-                None
-            };
-
-            let points: Result<Vec<_>, _> = points
-                .into_iter()
-                .filter(|point| hashring.is_in_shard(&point.id, target_shard_id))
-                .map(PointStruct::try_from)
-                .collect();
-            let points = points.map_err(|err| {
-                CollectionError::service_error(format!(
-                    "Failed to migrate points from shard {target_shard_id} to {} for resharding: {err}",
-                    reshard_key.shard_id,
-                ))
-            })?;
+        state.write(|data| {
+            data.migrated_shards.push(target_shard_id);
+            data.update(progress, consensus);
+        })?;
+        log::debug!(
+            "Points of shard {} successfully migrated into shard {target_shard_id} for resharding",
+            reshard_key.shard_id,
+        );
+    }
+
+    Ok(())
+}
+
+/// Migrate points from the source shard, which belong to the target shard by the hash ring
+///
+/// Points that already exist in the target shard are skipped. These were either migrated by an
+/// earlier, interrupted run of this migration, or written into the target shard directly, because
+/// updates are forwarded to both shards during resharding. This makes the migration resumable.
+///
+/// Returns the number of migrated points.
+pub(crate) async fn migrate_points(
+    shard_holder: &LockedShardHolder,
+    hashring: &HashRingRouter,
+    source_shard_id: ShardId,
+    target_shard_id: ShardId,
+) -> CollectionResult<usize> {
+    let filter = Filter::new_must(Condition::CustomIdChecker(Arc::new(
+        hashring.hash_ring_filter(target_shard_id),
+    )));
+
+    let mut offset = None;
+    let mut migrated = 0;
+
+    loop {
+        let shard_holder = shard_holder.read().await;
+
+        let source_replica_set = shard_holder.get_shard(&source_shard_id).ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Shard {source_shard_id} not found in the shard holder for resharding",
+            ))
+        })?;
+        let target_replica_set = shard_holder.get_shard(&target_shard_id).ok_or_else(|| {
+            CollectionError::service_error(format!(
+                "Shard {target_shard_id} not found in the shard holder for resharding",
+            ))
+        })?;
+
+        // Take batch of points, if full, pop the last entry as next batch offset
+        let mut points = source_replica_set
+            .scroll_by(
+                offset,
+                MIGRATE_BATCH_SIZE + 1,
+                &true.into(),
+                &true.into(),
+                Some(&filter),
+                None,
+                false,
+                None,
+                None,
+            )
+            .await?;
+
+        offset = if points.len() > MIGRATE_BATCH_SIZE {
+            points.pop().map(|point| point.id)
+        } else {
+            None
+        };
+
+        // Custom ID checker is not sent to remote replicas, check the hash ring here again
+        points.retain(|point| hashring.is_in_shard(&point.id, target_shard_id));
+
+        // Skip points already in the target shard
+        let migrated_ids: HashSet<_> = target_replica_set
+            .retrieve(
+                Arc::new(PointRequestInternal {
+                    ids: points.iter().map(|point| point.id).collect(),
+                    with_payload: None,
+                    with_vector: false.into(),
+                }),
+                &false.into(),
+                &false.into(),
+                None,
+                None,
+                false,
+            )
+            .await?
+            .into_iter()
+            .map(|point| point.id)
+            .collect();
+
+        let points: Result<Vec<_>, _> = points
+            .into_iter()
+            .filter(|point| !migrated_ids.contains(&point.id))
+            .map(PointStruct::try_from)
+            .collect();
+        let points = points.map_err(|err| {
+            CollectionError::service_error(format!(
+                "Failed to migrate points from shard {source_shard_id} to {target_shard_id} for resharding: {err}",
+            ))
+        })?;
+
+        if !points.is_empty() {
+            migrated += points.len();
 
             let operation = CollectionUpdateOperations::PointOperation(
                 PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
@@ -355,23 +421,14 @@ async fn drive_down(
             target_replica_set
                 .update_with_consistency(operation, true, WriteOrdering::Weak)
                 .await?;
-
-            if offset.is_none() {
-                break;
-            }
         }
 
-        state.write(|data| {
-            data.migrated_shards.push(target_shard_id);
-            data.update(progress, consensus);
-        })?;
-        log::debug!(
-            "Points of shard {} successfully migrated into shard {target_shard_id} for resharding",
-            reshard_key.shard_id,
-        );
+        if offset.is_none() {
+            break;
+        }
     }
 
-    Ok(())
+    Ok(migrated)
 }
 
 /// Migrate a shard locally, within the same node.
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::hash;
 use std::iter::Peekable;
 use std::rc::Rc;
@@ -132,6 +132,24 @@ impl Resolve for FacetResponse {
     }
 }
 
+impl Resolve for Vec<FacetValue> {
+    /// Keep the values returned by enough replicas, in ascending order
+    fn resolve(responses: Vec<Self>, condition: ResolveCondition) -> Self {
+        let resolution_count = condition.resolution_count(responses.len());
+
+        let mut replica_counts: BTreeMap<FacetValue, usize> = BTreeMap::new();
+        for value in responses.into_iter().flatten() {
+            *replica_counts.entry(value).or_default() += 1;
+        }
+
+        replica_counts
+            .into_iter()
+            .filter(|(_, count)| *count >= resolution_count)
+            .map(|(value, _)| value)
+            .collect()
+    }
+}
+
 impl Resolve for Vec<Record> {
     fn resolve(records: Vec<Self>, condition: ResolveCondition) -> Self {
         Resolver::resolve(records, |record| record.id, record_eq, condition)
@@ -1,11 +1,15 @@
 use core::marker::{Send, Sync};
 use std::future::{self, Future};
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
 use common::types::TelemetryDetail;
+use segment::types::{Payload, PointIdType};
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
+use crate::operations::point_ops::{ConditionalUpsert, PointInsertOperationsInternal};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
@@ -80,36 +84,48 @@ impl Shard {
         temp_path: &Path,
         target_path: &Path,
         save_wal: bool,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<()> {
         match self {
             Shard::Local(local_shard) => {
                 local_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, stopped)
                     .await
             }
             Shard::Proxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, stopped)
                     .await
             }
             Shard::ForwardProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, stopped)
                     .await
             }
             Shard::QueueProxy(proxy_shard) => {
                 proxy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, stopped)
                     .await
             }
             Shard::Dummy(dummy_shard) => {
                 dummy_shard
-                    .create_snapshot(temp_path, target_path, save_wal)
+                    .create_snapshot(temp_path, target_path, save_wal, stopped)
                     .await
             }
         }
     }
 
+    /// Last operation sequence number in the WAL of the underlying local shard, if any.
+    pub fn wal_last_index(&self) -> Option<u64> {
+        match self {
+            Shard::Local(local_shard) => Some(local_shard.wal_last_index()),
+            Shard::Proxy(proxy_shard) => Some(proxy_shard.wal_last_index()),
+            Shard::ForwardProxy(proxy_shard) => Some(proxy_shard.wal_last_index()),
+            Shard::QueueProxy(proxy_shard) => Some(proxy_shard.wal_last_index()),
+            Shard::Dummy(_) => None,
+        }
+    }
+
     pub async fn on_optimizer_config_update(&self) -> CollectionResult<()> {
         match self {
             Shard::Local(local_shard) => local_shard.on_optimizer_config_update().await,
@@ -120,6 +136,75 @@ impl Shard {
         }
     }
 
+    pub async fn reindex(&self) -> CollectionResult<usize> {
+        match self {
+            Shard::Local(local_shard) => local_shard.reindex().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.reindex().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.reindex().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.reindex().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.reindex().await,
+        }
+    }
+
+    pub async fn cancel_reindex(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.cancel_reindex().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.cancel_reindex().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.cancel_reindex().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.cancel_reindex().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.cancel_reindex().await,
+        }
+    }
+
+    /// Check expected point versions of a conditional upsert against the underlying local shard
+    pub async fn check_expected_versions(
+        &self,
+        conditional_upsert: &ConditionalUpsert,
+    ) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => {
+                local_shard
+                    .check_expected_versions(conditional_upsert)
+                    .await
+            }
+            Shard::Proxy(proxy_shard) => {
+                proxy_shard
+                    .check_expected_versions(conditional_upsert)
+                    .await
+            }
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard
+                    .check_expected_versions(conditional_upsert)
+                    .await
+            }
+            Shard::QueueProxy(proxy_shard) => {
+                proxy_shard
+                    .check_expected_versions(conditional_upsert)
+                    .await
+            }
+            Shard::Dummy(dummy_shard) => {
+                dummy_shard
+                    .check_expected_versions(conditional_upsert)
+                    .await
+            }
+        }
+    }
+
+    /// Remove points of an upsert, which are duplicates of points stored in the underlying local
+    /// shard
+    pub async fn dedup_stored_vectors(
+        &self,
+        points: &mut PointInsertOperationsInternal,
+    ) -> CollectionResult<Vec<(PointIdType, Option<Payload>)>> {
+        match self {
+            Shard::Local(local_shard) => local_shard.dedup_stored_vectors(points).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.dedup_stored_vectors(points).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.dedup_stored_vectors(points).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.dedup_stored_vectors(points).await,
+            Shard::Dummy(dummy_shard) => dummy_shard.dedup_stored_vectors(points).await,
+        }
+    }
+
     pub fn is_update_in_progress(&self) -> bool {
         self.update_tracker()
             .map_or(false, UpdateTracker::is_update_in_progress)
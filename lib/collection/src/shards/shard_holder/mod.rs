@@ -2,14 +2,15 @@ mod resharding;
 
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Duration;
 
 use common::cpu::CpuBudget;
 use futures::Future;
 use itertools::Itertools;
+use segment::common::operation_error::check_process_stopped;
 use segment::types::ShardKey;
-use tar::Builder as TarBuilder;
 use tokio::runtime::Handle;
 use tokio::sync::{broadcast, RwLock};
 
@@ -18,6 +19,8 @@ use super::resharding::tasks_pool::ReshardTasksPool;
 use super::resharding::{ReshardStage, ReshardState};
 use super::transfer::transfer_tasks_pool::TransferTasksPool;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::common::snapshot_archive::archive_directory;
+use crate::common::snapshot_space::ensure_snapshot_space;
 use crate::common::validate_snapshot_archive::validate_open_snapshot_archive;
 use crate::config::{CollectionConfig, ShardingMethod};
 use crate::hash_ring::HashRingRouter;
@@ -483,7 +486,8 @@ impl ShardHolder {
                     res.push((shard, shard_key));
                 }
             }
-            ShardSelectorInternal::ShardKey(shard_key) => {
+            ShardSelectorInternal::ShardKey(shard_key)
+            | ShardSelectorInternal::TenantScope(shard_key) => {
                 for shard_id in self.get_shard_ids_by_key(shard_key)? {
                     if let Some(replica_set) = self.shards.get(&shard_id) {
                         res.push((replica_set, Some(shard_key)));
@@ -807,6 +811,8 @@ impl ShardHolder {
         snapshot_manager.list_snapshots(&snapshots_path).await
     }
 
+    /// Snapshot creation is aborted once `stopped` is set, partial files are cleaned up.
+    ///
     /// # Cancel safety
     ///
     /// This method is cancel safe.
@@ -816,9 +822,10 @@ impl ShardHolder {
         collection_name: &str,
         shard_id: ShardId,
         temp_dir: &Path,
+        stopped: Arc<AtomicBool>,
     ) -> CollectionResult<SnapshotDescription> {
         // - `snapshot_temp_dir`, `snapshot_target_dir` and `temp_file` are handled by `tempfile`
-        //   and would be deleted, if future is cancelled
+        //   and would be deleted, if future is cancelled or snapshot creation is stopped
 
         let shard = self
             .get_shard(&shard_id)
@@ -830,6 +837,8 @@ impl ShardHolder {
             )));
         }
 
+        ensure_snapshot_space(temp_dir, vec![shard.shard_path.clone()]).await?;
+
         let snapshot_file_name = format!(
             "{collection_name}-shard-{shard_id}-{}.snapshot",
             chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
@@ -843,8 +852,16 @@ impl ShardHolder {
             .prefix(&format!("{snapshot_file_name}-target-"))
             .tempdir_in(temp_dir)?;
 
+        // Captured before snapshotting, so the snapshot contains at least all operations up to it
+        let seq_number = shard.wal_last_index().await;
+
         shard
-            .create_snapshot(snapshot_temp_dir.path(), snapshot_target_dir.path(), false)
+            .create_snapshot(
+                snapshot_temp_dir.path(),
+                snapshot_target_dir.path(),
+                false,
+                stopped.clone(),
+            )
             .await?;
 
         let snapshot_temp_dir_path = snapshot_temp_dir.path().to_path_buf();
@@ -861,22 +878,22 @@ impl ShardHolder {
 
         let task = {
             let snapshot_target_dir = snapshot_target_dir.path().to_path_buf();
+            let compression = shard.shared_storage_config.snapshots_config.compression;
 
-            cancel::blocking::spawn_cancel_on_drop(move |cancel| -> CollectionResult<_> {
-                let mut tar = TarBuilder::new(temp_file.as_file_mut());
+            let stopped = stopped.clone();
 
+            cancel::blocking::spawn_cancel_on_drop(move |cancel| -> CollectionResult<_> {
                 if cancel.is_cancelled() {
                     return Err(cancel::Error::Cancelled.into());
                 }
+                check_process_stopped(&stopped)?;
 
-                tar.append_dir_all(".", &snapshot_target_dir)?;
+                archive_directory(&snapshot_target_dir, temp_file.as_file_mut(), compression)?;
 
                 if cancel.is_cancelled() {
                     return Err(cancel::Error::Cancelled.into());
                 }
-
-                tar.finish()?;
-                drop(tar);
+                check_process_stopped(&stopped)?;
 
                 Ok(temp_file)
             })
@@ -899,7 +916,7 @@ impl ShardHolder {
 
         let snapshot_manager = shard.get_snapshots_storage_manager()?;
         let snapshot_description = snapshot_manager
-            .store_file(temp_file.path(), &snapshot_path)
+            .store_file_with_seq_number(temp_file.path(), &snapshot_path, seq_number)
             .await;
         if snapshot_description.is_ok() {
             let _ = temp_file.keep();
@@ -149,6 +149,10 @@ impl ShardHolder {
         Ok(())
     }
 
+    pub fn check_resume_resharding(&mut self, resharding_key: &ReshardKey) -> CollectionResult<()> {
+        self.check_resharding(resharding_key, check_stage(ReshardStage::MigratingPoints))
+    }
+
     pub fn check_finish_resharding(&mut self, resharding_key: &ReshardKey) -> CollectionResult<()> {
         self.check_resharding(
             resharding_key,
@@ -8,6 +8,7 @@ use serde::Serialize;
 
 use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::operations::types::OptimizersStatus;
+use crate::shards::replica_set::state_audit::ReplicaStateTransition;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::shard::{PeerId, ShardId};
 
@@ -17,6 +18,9 @@ pub struct ReplicaSetTelemetry {
     pub local: Option<LocalShardTelemetry>,
     pub remote: Vec<RemoteShardTelemetry>,
     pub replicate_states: HashMap<PeerId, ReplicaState>,
+    /// Recent replica state transitions, oldest first
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub state_transitions: Vec<ReplicaStateTransition>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -32,6 +36,26 @@ pub struct LocalShardTelemetry {
     pub variant_name: Option<String>,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
+    pub update_queue: UpdateQueueTelemetry,
+}
+
+/// Load of the update pipeline of a shard
+#[derive(Serialize, Clone, Debug, JsonSchema, Default)]
+pub struct UpdateQueueTelemetry {
+    /// Number of update operations accepted, but not yet applied
+    pub queue_depth: usize,
+    /// Update operations applied per second, averaged over the last seconds
+    pub ops_per_sec: f64,
+    /// Average time in milliseconds to apply an update operation, over the last seconds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avg_apply_latency_ms: Option<f64>,
+    /// Sequence number of the last WAL operation applied to the segments.
+    /// Replication lag is `last_wal_sequence - applied_sequence`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_sequence: Option<u64>,
+    /// Sequence number of the last operation written to the WAL
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_wal_sequence: Option<u64>,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema, Default)]
@@ -57,6 +81,7 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
+            update_queue: self.update_queue.clone(),
         }
     }
 }
@@ -91,6 +116,7 @@ impl Anonymize for ReplicaSetTelemetry {
             local: self.local.anonymize(),
             remote: self.remote.anonymize(),
             replicate_states: Default::default(),
+            state_transitions: Default::default(),
         }
     }
 }
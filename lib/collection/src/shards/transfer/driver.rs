@@ -9,7 +9,7 @@ use tokio::time::sleep;
 use super::resharding_stream_records::transfer_resharding_stream_records;
 use super::snapshot::transfer_snapshot;
 use super::stream_records::transfer_stream_records;
-use super::transfer_tasks_pool::TransferTaskProgress;
+use super::transfer_tasks_pool::{TransferStage, TransferTaskProgress};
 use super::wal_delta::transfer_wal_delta;
 use super::{ShardTransfer, ShardTransferConsensus, ShardTransferMethod};
 use crate::common::stoppable_task_async::{spawn_async_cancellable, CancellableAsyncTaskHandle};
@@ -55,6 +55,11 @@ pub async fn transfer_shard(
         channel_service.clone(),
     );
 
+    transfer_config.check_filter_supported()?;
+
+    // Reset the stage in case of a retry
+    progress.lock().set_stage(TransferStage::Proxifying);
+
     // Prepare the remote for receiving the shard, waits for the correct state on the remote
     remote_shard.initiate_transfer().await?;
 
@@ -66,6 +71,7 @@ pub async fn transfer_shard(
                 progress,
                 local_shard_id,
                 remote_shard,
+                transfer_config.filter.clone(),
                 &collection_id,
             )
             .await?;
@@ -210,12 +216,13 @@ pub async fn change_remote_shard_route(
     Ok(true)
 }
 
-/// Mark partial shard as ready
+/// Mark partial shard as ready, by switching it into the given state
 ///
 /// Returns `true` if the shard was promoted, `false` if the shard was not found.
 pub async fn finalize_partial_shard(
     shard_holder: &ShardHolder,
     shard_id: ShardId,
+    state: ReplicaState,
 ) -> CollectionResult<bool> {
     let replica_set = match shard_holder.get_shard(&shard_id) {
         None => return Ok(false),
@@ -226,7 +233,7 @@ pub async fn finalize_partial_shard(
         return Ok(false);
     }
 
-    replica_set.set_replica_state(&replica_set.this_peer_id(), ReplicaState::Active)?;
+    replica_set.set_replica_state(&replica_set.this_peer_id(), state)?;
     Ok(true)
 }
 
@@ -237,7 +244,7 @@ pub async fn handle_transferred_shard_proxy(
     shard_holder: &ShardHolder,
     shard_id: ShardId,
     to: PeerId,
-    activate_shard: bool,
+    target_state: Option<ReplicaState>,
     sync: bool,
 ) -> CollectionResult<bool> {
     // TODO: Ensure cancel safety!
@@ -247,8 +254,8 @@ pub async fn handle_transferred_shard_proxy(
         Some(replica_set) => replica_set,
     };
 
-    if activate_shard {
-        replica_set.add_remote(to, ReplicaState::Active).await?;
+    if let Some(state) = target_state {
+        replica_set.add_remote(to, state).await?;
     }
 
     if sync {
@@ -336,7 +343,10 @@ where
         }
 
         match &result {
-            Ok(Ok(true)) => on_finish.await,
+            Ok(Ok(true)) => {
+                progress.lock().set_stage(TransferStage::WaitingConsensus);
+                on_finish.await
+            }
             Ok(Ok(false)) => (), // do nothing, we should not finish the task
             Ok(Err(_)) => on_error.await,
             Err(_) => (), // do nothing, if task was cancelled
@@ -1,8 +1,10 @@
+use std::hash::{Hash, Hasher};
 use std::time::Duration;
 
 use async_trait::async_trait;
 use common::defaults::{self, CONSENSUS_CONFIRM_RETRIES};
 use schemars::JsonSchema;
+use segment::types::Filter;
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
@@ -22,13 +24,19 @@ pub mod stream_records;
 pub mod transfer_tasks_pool;
 pub mod wal_delta;
 
+/// Minimum number of points in a shard to transfer it as snapshot, if no method is selected.
+///
+/// Streaming records is cheaper for small shards, but for large shards recovering a snapshot is
+/// much faster than re-inserting and re-indexing every point on the receiver.
+pub const AUTO_SNAPSHOT_TRANSFER_MIN_POINTS: usize = 100_000;
+
 /// Time between consensus confirmation retries.
 const CONSENSUS_CONFIRM_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 /// Time after which confirming a consensus operation times out.
 const CONSENSUS_CONFIRM_TIMEOUT: Duration = defaults::CONSENSUS_META_OP_WAIT;
 
-#[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
 pub struct ShardTransfer {
     pub shard_id: ShardId,
     /// For resharding, a different target shard ID may be configured
@@ -44,6 +52,11 @@ pub struct ShardTransfer {
     /// Method to transfer shard with. `None` to choose automatically.
     #[serde(default)]
     pub method: Option<ShardTransferMethod>,
+    /// If set, only points matching this filter are transferred.
+    /// Only supported when replicating a shard by streaming records. The target replica doesn't
+    /// hold all points, so it becomes a listener instead of an active replica.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Filter>,
 }
 
 impl ShardTransfer {
@@ -55,6 +68,61 @@ impl ShardTransfer {
             to: self.to,
         }
     }
+
+    /// Check whether this transfer can be limited to its filter, if any.
+    ///
+    /// Only a replicating stream records transfer can transfer a subset of points. Other
+    /// methods always transfer the whole shard, and moving a subset would drop the rest of the
+    /// shard on the source.
+    pub fn check_filter_supported(&self) -> CollectionResult<()> {
+        if self.filter.is_none() {
+            return Ok(());
+        }
+
+        if !self.sync {
+            return Err(CollectionError::bad_request(
+                "Filtered shard transfer is only supported for replicating a shard",
+            ));
+        }
+
+        let method = self.method.unwrap_or_default();
+        if method != ShardTransferMethod::StreamRecords {
+            return Err(CollectionError::bad_request(format!(
+                "Filtered shard transfer is only supported by {:?} method, got {method:?}",
+                ShardTransferMethod::StreamRecords,
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// State of the target replica once the transfer is finished.
+    pub fn finished_replica_state(&self) -> ReplicaState {
+        if self.method.map_or(false, |method| method.is_resharding()) {
+            // Resharding has multiple transfers in sequence, the shard is promoted manually
+            ReplicaState::Resharding
+        } else if self.filter.is_some() {
+            // Target only holds points matching the filter, it must never be used for reads
+            ReplicaState::Listener
+        } else {
+            ReplicaState::Active
+        }
+    }
+}
+
+// Filter is not `Eq`, but it never contains values which are not equal to themselves
+impl Eq for ShardTransfer {}
+
+// Filter is not `Hash`, hash the fields identifying the transfer only. Transfers equal to each
+// other have equal hashes, which is all `Hash` requires.
+impl Hash for ShardTransfer {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.shard_id.hash(state);
+        self.to_shard_id.hash(state);
+        self.from.hash(state);
+        self.to.hash(state);
+        self.method.hash(state);
+    }
 }
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
@@ -126,6 +194,20 @@ pub enum ShardTransferMethod {
 }
 
 impl ShardTransferMethod {
+    /// Select transfer method for a shard with the given number of points.
+    pub fn auto_select(point_count: usize) -> Self {
+        if point_count >= AUTO_SNAPSHOT_TRANSFER_MIN_POINTS {
+            Self::Snapshot
+        } else {
+            Self::StreamRecords
+        }
+    }
+
+    /// Whether updates made during the transfer are forwarded from the WAL of the source shard
+    pub fn uses_wal(&self) -> bool {
+        matches!(self, Self::Snapshot | Self::WalDelta)
+    }
+
     pub fn is_resharding(&self) -> bool {
         matches!(self, Self::ReshardingStreamRecords)
     }
@@ -602,3 +684,28 @@ pub trait ShardTransferConsensus: Send + Sync {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_auto_select_transfer_method() {
+        assert_eq!(
+            ShardTransferMethod::auto_select(0),
+            ShardTransferMethod::StreamRecords,
+        );
+        assert_eq!(
+            ShardTransferMethod::auto_select(AUTO_SNAPSHOT_TRANSFER_MIN_POINTS - 1),
+            ShardTransferMethod::StreamRecords,
+        );
+        assert_eq!(
+            ShardTransferMethod::auto_select(AUTO_SNAPSHOT_TRANSFER_MIN_POINTS),
+            ShardTransferMethod::Snapshot,
+        );
+        assert_eq!(
+            ShardTransferMethod::auto_select(10 * AUTO_SNAPSHOT_TRANSFER_MIN_POINTS),
+            ShardTransferMethod::Snapshot,
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use parking_lot::Mutex;
 
-use super::transfer_tasks_pool::TransferTaskProgress;
+use super::transfer_tasks_pool::{TransferStage, TransferTaskProgress};
 use crate::operations::types::{CollectionError, CollectionResult, CountRequestInternal};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard::ShardId;
@@ -81,6 +81,8 @@ pub(crate) async fn transfer_resharding_stream_records(
         replica_set.transfer_indexes().await?;
     }
 
+    progress.lock().set_stage(TransferStage::StreamingRecords);
+
     // Transfer contents batch by batch
     log::trace!("Transferring points to shard {shard_id} by reshard streaming records");
 
@@ -98,7 +100,7 @@ pub(crate) async fn transfer_resharding_stream_records(
         };
 
         offset = replica_set
-            .transfer_batch(offset, TRANSFER_BATCH_SIZE, Some(&hashring), true)
+            .transfer_batch(offset, TRANSFER_BATCH_SIZE, Some(&hashring), None, true)
             .await?;
 
         {
@@ -1,11 +1,12 @@
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common::defaults;
 use parking_lot::Mutex;
 use tempfile::TempPath;
 
-use super::transfer_tasks_pool::TransferTaskProgress;
+use super::transfer_tasks_pool::{TransferStage, TransferTaskProgress};
 use super::{ShardTransfer, ShardTransferConsensus};
 use crate::operations::snapshot_ops::{get_checksum_path, SnapshotPriority};
 use crate::operations::types::{CollectionError, CollectionResult};
@@ -183,7 +184,7 @@ pub(super) async fn transfer_snapshot(
 
     // Queue proxy local shard
     replica_set
-        .queue_proxify_local(remote_shard.clone(), None, progress)
+        .queue_proxify_local(remote_shard.clone(), None, progress.clone())
         .await?;
 
     debug_assert!(
@@ -193,8 +194,15 @@ pub(super) async fn transfer_snapshot(
 
     // Create shard snapshot
     log::trace!("Creating snapshot of shard {shard_id} for shard snapshot transfer");
+    progress.lock().set_stage(TransferStage::Snapshotting);
     let snapshot_description = shard_holder_read
-        .create_shard_snapshot(snapshots_path, collection_id, shard_id, temp_dir)
+        .create_shard_snapshot(
+            snapshots_path,
+            collection_id,
+            shard_id,
+            temp_dir,
+            Arc::new(AtomicBool::new(false)),
+        )
         .await?;
 
     // TODO: If future is cancelled until `get_shard_snapshot_path` resolves, shard snapshot may not be cleaned up...
@@ -217,6 +225,7 @@ pub(super) async fn transfer_snapshot(
     ));
 
     log::trace!("Transferring and recovering shard {shard_id} snapshot on peer {remote_peer_id}");
+    progress.lock().set_stage(TransferStage::RecoveringSnapshot);
     remote_shard
         .recover_shard_snapshot_from_url(
             collection_id,
@@ -257,11 +266,15 @@ pub(super) async fn transfer_snapshot(
 
     // Transfer queued updates to remote, transform into forward proxy
     log::trace!("Transfer all queue proxy updates and transform into forward proxy");
+    progress
+        .lock()
+        .set_stage(TransferStage::TransferringQueuedUpdates);
     replica_set.queue_proxy_into_forward_proxy().await?;
 
     // Wait for Partial state in our replica set
     let partial_state = ReplicaState::Partial;
     log::trace!("Wait for local shard to reach {partial_state:?} state");
+    progress.lock().set_stage(TransferStage::WaitingConsensus);
     replica_set
         .wait_for_state(
             transfer_config.to,
@@ -1,15 +1,25 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use parking_lot::Mutex;
+use segment::types::Filter;
 
-use super::transfer_tasks_pool::TransferTaskProgress;
+use super::transfer_tasks_pool::{TransferStage, TransferTaskProgress};
 use crate::operations::types::{CollectionError, CollectionResult, CountRequestInternal};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::shard::ShardId;
 use crate::shards::shard_holder::LockedShardHolder;
 use crate::shards::CollectionId;
 
-pub(super) const TRANSFER_BATCH_SIZE: usize = 100;
+pub(crate) const TRANSFER_BATCH_SIZE: usize = 100;
+
+/// How many times to compare point counts of source and target after streaming all records
+///
+/// Updates applied directly to the target by other peers may briefly make counts diverge, so a
+/// mismatch is re-checked before failing the transfer.
+pub(crate) const VERIFY_POINT_COUNT_ATTEMPTS: usize = 3;
+
+const VERIFY_POINT_COUNT_RETRY_DELAY: Duration = Duration::from_secs(1);
 
 /// Orchestrate shard transfer by streaming records
 ///
@@ -19,6 +29,8 @@ pub(super) const TRANSFER_BATCH_SIZE: usize = 100;
 /// This first transfers configured indices. Then it transfers all point records in batches.
 /// Updates to the local shard are forwarded to the remote concurrently.
 ///
+/// If `filter` is given, only point records matching it are transferred.
+///
 /// # Cancel safety
 ///
 /// This function is cancel safe.
@@ -27,6 +39,7 @@ pub(super) async fn transfer_stream_records(
     progress: Arc<Mutex<TransferTaskProgress>>,
     shard_id: ShardId,
     remote_shard: RemoteShard,
+    filter: Option<Filter>,
     collection_id: &CollectionId,
 ) -> CollectionResult<()> {
     let remote_peer_id = remote_shard.peer_id;
@@ -50,7 +63,7 @@ pub(super) async fn transfer_stream_records(
         let Some(count_result) = replica_set
             .count_local(
                 Arc::new(CountRequestInternal {
-                    filter: None,
+                    filter: filter.clone(),
                     exact: true,
                 }),
                 None, // no timeout
@@ -66,6 +79,8 @@ pub(super) async fn transfer_stream_records(
         replica_set.transfer_indexes().await?;
     }
 
+    progress.lock().set_stage(TransferStage::StreamingRecords);
+
     // Transfer contents batch by batch
     log::trace!("Transferring points to shard {shard_id} by streaming records");
 
@@ -83,7 +98,7 @@ pub(super) async fn transfer_stream_records(
         };
 
         offset = replica_set
-            .transfer_batch(offset, TRANSFER_BATCH_SIZE, None, false)
+            .transfer_batch(offset, TRANSFER_BATCH_SIZE, None, filter.as_ref(), false)
             .await?;
 
         {
@@ -100,6 +115,34 @@ pub(super) async fn transfer_stream_records(
         }
     }
 
+    // Verify the remote shard received all points before it may be finalized
+    log::trace!("Verifying point count of shard {shard_id} on peer {remote_peer_id}");
+
+    for attempt in 1..=VERIFY_POINT_COUNT_ATTEMPTS {
+        let shard_holder = shard_holder.read().await;
+        let Some(replica_set) = shard_holder.get_shard(&shard_id) else {
+            // Forward proxy gone?!
+            // That would be a programming error.
+            return Err(CollectionError::service_error(format!(
+                "Shard {shard_id} is not found"
+            )));
+        };
+
+        let (local_count, remote_count) =
+            replica_set.count_transferred_points(filter.clone()).await?;
+        let verified =
+            verify_transferred_point_count(shard_id, local_count, remote_count, filter.is_some());
+        match verified {
+            Ok(()) => break,
+            Err(err) if attempt < VERIFY_POINT_COUNT_ATTEMPTS => {
+                log::warn!("{err}, checking again");
+                drop(shard_holder);
+                tokio::time::sleep(VERIFY_POINT_COUNT_RETRY_DELAY).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
     // Update cutoff point on remote shard, disallow recovery before our current last seen
     {
         let shard_holder = shard_holder.read().await;
@@ -136,3 +179,53 @@ pub(super) async fn transfer_stream_records(
 
     Ok(())
 }
+
+/// Check that the target shard holds as many points as the source shard after a transfer.
+///
+/// Records are streamed with sync operations, which replace everything on the target, so both
+/// counts must be the same. A difference means some batch did not make it to the target.
+///
+/// A filtered transfer merges points into the target, which may already hold matching points
+/// the source doesn't have. Then the target must hold at least as many points as the source.
+fn verify_transferred_point_count(
+    shard_id: ShardId,
+    source_count: usize,
+    target_count: usize,
+    filtered: bool,
+) -> CollectionResult<()> {
+    let is_complete = if filtered {
+        target_count >= source_count
+    } else {
+        target_count == source_count
+    };
+
+    if !is_complete {
+        return Err(CollectionError::service_error(format!(
+            "Point count mismatch after transferring shard {shard_id}: \
+             source has {source_count} points, target has {target_count} points",
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_transferred_point_count() {
+        verify_transferred_point_count(0, 0, 0, false).unwrap();
+        verify_transferred_point_count(0, 1_000, 1_000, false).unwrap();
+
+        // A dropped batch leaves the target under-populated
+        let result = verify_transferred_point_count(0, 1_000, 1_000 - TRANSFER_BATCH_SIZE, false);
+        assert!(matches!(result, Err(CollectionError::ServiceError { .. })));
+
+        // Stale points left on the target are not acceptable either
+        assert!(verify_transferred_point_count(0, 1_000, 1_001, false).is_err());
+
+        // Filtered transfers merge into points already present on the target
+        verify_transferred_point_count(0, 1_000, 1_001, true).unwrap();
+        assert!(verify_transferred_point_count(0, 1_000, 999, true).is_err());
+    }
+}
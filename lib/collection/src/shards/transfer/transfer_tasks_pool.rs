@@ -3,6 +3,8 @@ use std::fmt::Write as _;
 use std::sync::Arc;
 
 use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use crate::common::eta_calculator::EtaCalculator;
 use crate::common::stoppable_task_async::CancellableAsyncTaskHandle;
@@ -21,11 +23,41 @@ pub struct TransferTaskItem {
 }
 
 pub struct TransferTaskProgress {
+    pub stage: TransferStage,
     pub points_transferred: usize,
     pub points_total: usize,
     pub eta: EtaCalculator,
 }
 
+/// Stage a running shard transfer is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStage {
+    /// Local shard is being wrapped into a proxy
+    #[default]
+    Proxifying,
+    /// Snapshot of the local shard is being created
+    Snapshotting,
+    /// Remote peer is recovering the shard snapshot
+    RecoveringSnapshot,
+    /// Points are being streamed to the remote shard batch by batch
+    StreamingRecords,
+    /// Updates queued during the transfer are being sent to the remote shard
+    TransferringQueuedUpdates,
+    /// Waiting for the cluster to agree on the new shard state
+    WaitingConsensus,
+}
+
+/// Point-in-time progress of a running shard transfer task.
+#[derive(Debug, Clone)]
+pub struct TransferTaskProgressSummary {
+    pub stage: TransferStage,
+    pub points_transferred: usize,
+    pub points_total: usize,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub eta: Option<std::time::Duration>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum TaskResult {
     Running,
@@ -42,11 +74,16 @@ impl TransferTaskProgress {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
         Self {
+            stage: TransferStage::default(),
             points_transferred: 0,
             points_total: 0,
             eta: EtaCalculator::new(),
         }
     }
+
+    pub fn set_stage(&mut self, stage: TransferStage) {
+        self.stage = stage;
+    }
 }
 
 impl TransferTasksPool {
@@ -85,6 +122,25 @@ impl TransferTasksPool {
         Some(TransferTaskStatus { result, comment })
     }
 
+    /// Get progress of all tasks which are still running.
+    pub fn running_tasks_progress(&self) -> Vec<(ShardTransferKey, TransferTaskProgressSummary)> {
+        self.tasks
+            .iter()
+            .filter(|(_, task)| !task.task.is_finished())
+            .map(|(key, task)| {
+                let progress = task.progress.lock();
+                let summary = TransferTaskProgressSummary {
+                    stage: progress.stage,
+                    points_transferred: progress.points_transferred,
+                    points_total: progress.points_total,
+                    started_at: task.started_at,
+                    eta: progress.eta.estimate(progress.points_total),
+                };
+                (*key, summary)
+            })
+            .collect()
+    }
+
     /// Stop the task and return the result. If the task is not found, return None.
     pub async fn stop_task(&mut self, transfer_key: &ShardTransferKey) -> Option<TaskResult> {
         let task = self.tasks.remove(transfer_key)?;
@@ -123,3 +179,66 @@ impl TransferTasksPool {
         self.tasks.insert(shard_transfer.key(), item);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::stoppable_task_async::spawn_async_cancellable;
+    use crate::shards::transfer::ShardTransferMethod;
+
+    #[tokio::test]
+    async fn test_running_tasks_progress() {
+        let mut pool = TransferTasksPool::new("test".to_string());
+
+        let transfer = ShardTransfer {
+            shard_id: 1,
+            to_shard_id: None,
+            from: 1,
+            to: 2,
+            sync: false,
+            method: Some(ShardTransferMethod::StreamRecords),
+            filter: None,
+        };
+
+        let progress = Arc::new(Mutex::new(TransferTaskProgress::new()));
+        let task = spawn_async_cancellable(|cancel| async move {
+            cancel.cancelled().await;
+            false
+        });
+        pool.add_task(
+            &transfer,
+            TransferTaskItem {
+                task,
+                started_at: chrono::Utc::now(),
+                progress: progress.clone(),
+            },
+        );
+
+        let running = pool.running_tasks_progress();
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].0, transfer.key());
+        assert_eq!(running[0].1.stage, TransferStage::Proxifying);
+
+        {
+            let mut progress = progress.lock();
+            progress.set_stage(TransferStage::StreamingRecords);
+            progress.points_total = 100;
+            progress.points_transferred = 40;
+        }
+
+        let running = pool.running_tasks_progress();
+        assert_eq!(running.len(), 1);
+        let (transfer_key, summary) = &running[0];
+        assert_eq!(transfer_key, &transfer.key());
+        assert_eq!(summary.stage, TransferStage::StreamingRecords);
+        assert_eq!(summary.points_transferred, 40);
+        assert_eq!(summary.points_total, 100);
+
+        // Stopped transfers are not listed anymore
+        assert_eq!(
+            pool.stop_task(&transfer.key()).await,
+            Some(TaskResult::Failed),
+        );
+        assert!(pool.running_tasks_progress().is_empty());
+    }
+}
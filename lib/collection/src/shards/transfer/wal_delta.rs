@@ -3,7 +3,7 @@ use std::sync::Arc;
 use common::defaults;
 use parking_lot::Mutex;
 
-use super::transfer_tasks_pool::TransferTaskProgress;
+use super::transfer_tasks_pool::{TransferStage, TransferTaskProgress};
 use super::{ShardTransfer, ShardTransferConsensus};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::channel_service::ChannelService;
@@ -116,7 +116,11 @@ pub(super) async fn transfer_wal_delta(
     if let Some(wal_delta_version) = wal_delta_version {
         // Queue proxy local shard
         replica_set
-            .queue_proxify_local(remote_shard.clone(), Some(wal_delta_version), progress)
+            .queue_proxify_local(
+                remote_shard.clone(),
+                Some(wal_delta_version),
+                progress.clone(),
+            )
             .await?;
 
         debug_assert!(
@@ -125,6 +129,9 @@ pub(super) async fn transfer_wal_delta(
         );
 
         log::trace!("Transfer WAL diff by transferring all current queue proxy updates");
+        progress
+            .lock()
+            .set_stage(TransferStage::TransferringQueuedUpdates);
         replica_set.queue_proxy_flush().await?;
     } else {
         log::trace!("Shard is already up-to-date as WAL diff if zero records");
@@ -154,6 +161,7 @@ pub(super) async fn transfer_wal_delta(
     // Wait for Partial state in our replica set
     let partial_state = ReplicaState::Partial;
     log::trace!("Wait for local shard to reach {partial_state:?} state");
+    progress.lock().set_stage(TransferStage::WaitingConsensus);
     replica_set
         .wait_for_state(
             transfer_config.to,
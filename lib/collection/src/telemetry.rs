@@ -2,6 +2,8 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::Serialize;
 
+use crate::collection::recall_monitor::RecallTelemetry;
+use crate::collection::search_log::SampledSearch;
 use crate::config::CollectionConfig;
 use crate::operations::types::{ReshardingInfo, ShardTransferInfo};
 use crate::shards::telemetry::ReplicaSetTelemetry;
@@ -14,6 +16,15 @@ pub struct CollectionTelemetry {
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
     pub resharding: Vec<ReshardingInfo>,
+    /// Recently sampled search requests, if sampling is enabled
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub recent_searches: Vec<SampledSearch>,
+    /// Recall of approximate search, if recall monitoring is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recall: Option<RecallTelemetry>,
+    /// Highest peak memory in bytes used to build a vector index of a local segment, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_build_peak_memory_bytes: Option<usize>,
 }
 
 impl CollectionTelemetry {
@@ -27,6 +38,17 @@ impl CollectionTelemetry {
     }
 }
 
+/// Highest peak memory used to build any vector index of the local segments of the shards
+pub(crate) fn index_build_peak_memory_bytes(shards: &[ReplicaSetTelemetry]) -> Option<usize> {
+    shards
+        .iter()
+        .filter_map(|shard| shard.local.as_ref())
+        .flat_map(|local| &local.segments)
+        .flat_map(|segment| &segment.vector_index_searches)
+        .filter_map(|index| index.build_peak_memory_bytes)
+        .max()
+}
+
 impl Anonymize for CollectionTelemetry {
     fn anonymize(&self) -> Self {
         Self {
@@ -36,6 +58,9 @@ impl Anonymize for CollectionTelemetry {
             shards: self.shards.anonymize(),
             transfers: vec![],
             resharding: vec![],
+            recent_searches: vec![],
+            recall: self.recall.clone(),
+            index_build_peak_memory_bytes: self.index_build_peak_memory_bytes,
         }
     }
 }
@@ -49,6 +74,10 @@ impl Anonymize for CollectionConfig {
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
             strict_mode_config: self.strict_mode_config.clone(),
+            payload_schema: self.payload_schema.clone(),
+            default_search_params: self.default_search_params,
+            tie_break: self.tie_break.clone(),
+            default_timeout: self.default_timeout,
         }
     }
 }
@@ -0,0 +1,82 @@
+use std::time::{Duration, Instant};
+
+use segment::data_types::vectors::NamedVectorStruct;
+use tempfile::Builder;
+
+use crate::operations::point_ops::WriteOrdering;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CoreSearchRequest};
+use crate::tests::fixtures::*;
+
+fn search_request() -> CoreSearchRequest {
+    CoreSearchRequest {
+        query: QueryEnum::Nearest(NamedVectorStruct::Default(vec![0.1, 0.2, 0.3, 0.4])),
+        filter: None,
+        params: None,
+        limit: 10,
+        offset: 0,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_default_timeout_of_collection() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+    collection
+        .update_from_client_simple(upsert_operation(), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let results = collection
+        .search(search_request(), None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 5);
+
+    // Zero timeout can't be met by any search, which is spawned on the search runtime
+    collection.update_default_timeout(0).await.unwrap();
+
+    let start = Instant::now();
+    let result = collection
+        .search(search_request(), None, &ShardSelectorInternal::All, None)
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::Timeout { .. })),
+        "{result:?}",
+    );
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // Timeout of the request takes precedence over the default
+    let results = collection
+        .search(
+            search_request(),
+            None,
+            &ShardSelectorInternal::All,
+            Some(Duration::from_secs(10)),
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 5);
+
+    // Internal requests to a specific shard don't use the default of the collection
+    let results = collection
+        .search(
+            search_request(),
+            None,
+            &ShardSelectorInternal::ShardId(0),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 5);
+}
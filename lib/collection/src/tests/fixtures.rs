@@ -1,16 +1,35 @@
 use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
 
+use common::cpu::CpuBudget;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     Condition, Distance, Filter, PayloadFieldSchema, PayloadSchemaType, PointIdType,
 };
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
 
+use crate::collection::Collection;
 use crate::config::{CollectionConfig, CollectionParams, WalConfig};
 use crate::operations::point_ops::{PointOperations, PointStruct};
+use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::VectorsConfig;
 use crate::operations::vector_params_builder::VectorParamsBuilder;
 use crate::operations::{CollectionUpdateOperations, CreateIndex, FieldIndexOperations};
 use crate::optimizers_builder::OptimizersConfig;
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::channel_service::ChannelService;
+use crate::shards::collection_shard_distribution::CollectionShardDistribution;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::PeerId;
+use crate::tests::snapshot_test::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer,
+};
+
+/// Peer of collections created by [`collection_fixture`]
+pub const THIS_PEER_ID: PeerId = 1;
 
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
@@ -19,6 +38,7 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
+    indexing_min_vector_number: None,
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
 };
@@ -27,6 +47,8 @@ pub fn create_collection_config() -> CollectionConfig {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -46,6 +68,10 @@ pub fn create_collection_config() -> CollectionConfig {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     }
 }
 
@@ -111,3 +137,119 @@ pub fn delete_point_operation(idx: u64) -> CollectionUpdateOperations {
 pub fn filter_single_id(id: impl Into<PointIdType>) -> Filter {
     Filter::new_must(Condition::HasId(HashSet::from([id.into()]).into()))
 }
+
+/// Local shard with the config of [`create_collection_config`], running on the current runtime
+pub async fn local_shard_fixture(
+    shard_dir: &Path,
+    payload_index_schema_dir: &Path,
+    shared_storage_config: SharedStorageConfig,
+) -> LocalShard {
+    let config = create_collection_config();
+    let current_runtime = Handle::current();
+    let payload_index_schema = Arc::new(
+        SaveOnDisk::load_or_init_default(payload_index_schema_dir.join("payload-schema.json"))
+            .unwrap(),
+    );
+
+    LocalShard::build(
+        0,
+        "test".to_string(),
+        shard_dir,
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(shared_storage_config),
+        payload_index_schema,
+        current_runtime.clone(),
+        current_runtime,
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap()
+}
+
+/// Collection with all shards local and active
+pub async fn collection_fixture(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    config: &CollectionConfig,
+) -> Collection {
+    collection_fixture_with_storage_config(
+        collection_dir,
+        snapshots_path,
+        config,
+        Default::default(),
+    )
+    .await
+}
+
+/// Same as [`collection_fixture`], with a custom storage config
+pub async fn collection_fixture_with_storage_config(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    config: &CollectionConfig,
+    shared_storage_config: Arc<SharedStorageConfig>,
+) -> Collection {
+    new_collection_fixture(
+        collection_dir,
+        snapshots_path,
+        config,
+        shared_storage_config,
+        ChannelService::default(),
+    )
+    .await
+}
+
+/// Same as [`collection_fixture`], reaching other peers through the given channel service
+pub async fn collection_fixture_with_channel_service(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    config: &CollectionConfig,
+    channel_service: ChannelService,
+) -> Collection {
+    new_collection_fixture(
+        collection_dir,
+        snapshots_path,
+        config,
+        Default::default(),
+        channel_service,
+    )
+    .await
+}
+
+async fn new_collection_fixture(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    config: &CollectionConfig,
+    shared_storage_config: Arc<SharedStorageConfig>,
+    channel_service: ChannelService,
+) -> Collection {
+    let collection = Collection::new(
+        "test".to_string(),
+        THIS_PEER_ID,
+        collection_dir,
+        snapshots_path,
+        config,
+        shared_storage_config,
+        CollectionShardDistribution::all_local(
+            Some(config.params.shard_number.get()),
+            THIS_PEER_ID,
+        ),
+        channel_service,
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+        None,
+    )
+    .await
+    .unwrap();
+    for shard_id in 0..config.params.shard_number.get() {
+        collection
+            .set_shard_replica_state(shard_id, THIS_PEER_ID, ReplicaState::Active, None)
+            .await
+            .unwrap();
+    }
+    collection
+}
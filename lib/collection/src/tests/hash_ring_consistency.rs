@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashMap};
+
+use segment::types::ShardKey;
+
+use crate::collection::hash_ring_consistency::{HashRingConsistency, PeerHashRings};
+use crate::hash_ring::{HashRingRouter, HashRingState};
+use crate::operations::cluster_ops::ReshardingDirection;
+
+fn hash_ring(shards: impl IntoIterator<Item = u32>) -> HashRingRouter {
+    let mut ring = HashRingRouter::single();
+    for shard in shards {
+        ring.add(shard);
+    }
+    ring
+}
+
+fn peer_hash_rings(
+    rings: impl IntoIterator<Item = (&'static str, HashRingRouter)>,
+) -> PeerHashRings {
+    rings
+        .into_iter()
+        .map(|(shard_key, ring)| (Some(ShardKey::from(shard_key)), ring.state()))
+        .collect()
+}
+
+#[test]
+fn test_consistent_hash_rings() {
+    let peer_hash_rings = HashMap::from([
+        (
+            1,
+            peer_hash_rings([("a", hash_ring([0, 1])), ("b", hash_ring([2]))]),
+        ),
+        (
+            2,
+            peer_hash_rings([("a", hash_ring([1, 0])), ("b", hash_ring([2]))]),
+        ),
+    ]);
+
+    let consistency = HashRingConsistency::compare(&peer_hash_rings, BTreeMap::new());
+    assert!(consistency.consistent);
+    assert_eq!(consistency.hash_rings.len(), 2);
+    for hash_rings in &consistency.hash_rings {
+        assert!(hash_rings.consistent);
+        assert_eq!(hash_rings.peers.len(), 2);
+    }
+}
+
+#[test]
+fn test_diverged_hash_rings() {
+    // Peer 2 did not apply the start of resharding of shard key "a" yet,
+    // and is missing shard key "c"
+    let mut resharding_ring = hash_ring([0, 1]);
+    resharding_ring.start_resharding(3, ReshardingDirection::Up);
+
+    let peer_hash_rings = HashMap::from([
+        (
+            1,
+            peer_hash_rings([
+                ("a", resharding_ring),
+                ("b", hash_ring([2])),
+                ("c", hash_ring([4])),
+            ]),
+        ),
+        (
+            2,
+            peer_hash_rings([("a", hash_ring([0, 1])), ("b", hash_ring([2]))]),
+        ),
+    ]);
+
+    let consistency = HashRingConsistency::compare(&peer_hash_rings, BTreeMap::new());
+    assert!(!consistency.consistent);
+
+    let shard_keys: Vec<_> = consistency
+        .hash_rings
+        .iter()
+        .map(|hash_rings| hash_rings.shard_key.clone().unwrap())
+        .collect();
+    assert_eq!(shard_keys, vec!["a".into(), "b".into(), "c".into()]);
+
+    let [a, b, c] = consistency.hash_rings.as_slice() else {
+        unreachable!();
+    };
+
+    assert!(!a.consistent);
+    assert_eq!(
+        a.peers[&1],
+        Some(HashRingState::Resharding {
+            old: vec![0, 1],
+            new: vec![0, 1, 3],
+        }),
+    );
+    assert_eq!(
+        a.peers[&2],
+        Some(HashRingState::Single { shards: vec![0, 1] })
+    );
+
+    assert!(b.consistent);
+
+    assert!(!c.consistent);
+    assert_eq!(c.peers[&1], Some(HashRingState::Single { shards: vec![4] }));
+    assert_eq!(c.peers[&2], None);
+}
+
+#[test]
+fn test_hash_rings_with_unreachable_peer() {
+    // Peer 3 could not be reached, the rings of the other peers are still compared
+    let peer_hash_rings = HashMap::from([
+        (1, peer_hash_rings([("a", hash_ring([0, 1]))])),
+        (2, peer_hash_rings([("a", hash_ring([0, 1]))])),
+    ]);
+    let peer_errors = BTreeMap::from([(3, "Service internal error: connection refused".into())]);
+
+    let consistency = HashRingConsistency::compare(&peer_hash_rings, peer_errors);
+    assert!(!consistency.consistent);
+
+    let [a] = consistency.hash_rings.as_slice() else {
+        unreachable!();
+    };
+    assert!(a.consistent);
+    assert_eq!(a.peers.len(), 2);
+
+    assert_eq!(
+        consistency.peer_errors.keys().copied().collect::<Vec<_>>(),
+        vec![3],
+    );
+}
@@ -0,0 +1,228 @@
+use api::rest::SearchRequestInternal;
+use segment::data_types::vectors::Vector;
+use segment::types::{WithPayloadInterface, WithVector};
+use tempfile::Builder;
+
+use crate::operations::config_diff::StrictModeConfig;
+use crate::operations::point_ops::WriteOrdering;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, PointRequestInternal, ScrollRequestInternal};
+use crate::operations::universal_query::collection_query::{
+    CollectionQueryRequest, Query, VectorInput, VectorQuery,
+};
+use crate::tests::fixtures::{collection_fixture, create_collection_config, upsert_operation};
+
+fn strict_mode_config(truncate: bool) -> StrictModeConfig {
+    StrictModeConfig {
+        enabled: Some(true),
+        max_query_limit: None,
+        max_timeout: None,
+        unindexed_filtering_retrieve: None,
+        unindexed_filtering_update: None,
+        search_max_hnsw_ef: None,
+        search_allow_exact: None,
+        search_max_oversampling: None,
+        max_distinct_values: None,
+        max_filter_conditions: None,
+        require_filter: None,
+        max_result_points: Some(2),
+        truncate_result_points: Some(truncate),
+        max_payload_bytes: None,
+    }
+}
+
+fn retrieve_request() -> PointRequestInternal {
+    PointRequestInternal {
+        ids: (1..=5).map(Into::into).collect(),
+        with_payload: Some(WithPayloadInterface::Bool(false)),
+        with_vector: false.into(),
+    }
+}
+
+fn scroll_request() -> ScrollRequestInternal {
+    ScrollRequestInternal {
+        offset: None,
+        limit: Some(4),
+        filter: None,
+        with_payload: None,
+        with_vector: false.into(),
+        order_by: None,
+    }
+}
+
+fn search_request() -> SearchRequestInternal {
+    SearchRequestInternal {
+        vector: vec![1.0, 2.0, 3.0, 4.0].into(),
+        filter: None,
+        params: None,
+        limit: 4,
+        offset: None,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+    }
+}
+
+fn query_request() -> CollectionQueryRequest {
+    CollectionQueryRequest {
+        prefetch: vec![],
+        query: Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            Vector::from(vec![1.0, 2.0, 3.0, 4.0]),
+        )))),
+        using: "".to_string(),
+        filter: None,
+        score_threshold: None,
+        limit: 4,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+        lookup_from: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_result_points_reject() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.strict_mode_config = Some(strict_mode_config(false));
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+    collection
+        .update_from_client_simple(upsert_operation(), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Retrieving more ids than the limit is rejected
+    let result = collection
+        .retrieve(retrieve_request(), None, &ShardSelectorInternal::All, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let result = collection
+        .scroll_by(scroll_request(), None, &ShardSelectorInternal::All, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let result = collection
+        .search(
+            search_request().into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let result = collection
+        .query_batch(
+            vec![(query_request(), ShardSelectorInternal::All)],
+            |_| async { None },
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    // Limits of a batch are summed up
+    let request = CollectionQueryRequest {
+        limit: 2,
+        ..query_request()
+    };
+    let result = collection
+        .query_batch(
+            vec![
+                (request.clone(), ShardSelectorInternal::All),
+                (request, ShardSelectorInternal::All),
+            ],
+            |_| async { None },
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    // Responses within the limit pass
+    let request = PointRequestInternal {
+        ids: vec![1.into(), 2.into()],
+        ..retrieve_request()
+    };
+    let points = collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(points.len(), 2);
+
+    // Internal requests to a specific shard are exempt
+    let points = collection
+        .retrieve(
+            retrieve_request(),
+            None,
+            &ShardSelectorInternal::ShardId(0),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(points.len(), 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_result_points_truncate() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.strict_mode_config = Some(strict_mode_config(true));
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+    collection
+        .update_from_client_simple(upsert_operation(), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Retrieving more ids than the limit returns the first points only
+    let points = collection
+        .retrieve(retrieve_request(), None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(points.len(), 2);
+
+    // Scroll continues from the first truncated point
+    let scrolled = collection
+        .scroll_by(scroll_request(), None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    let ids: Vec<_> = scrolled.points.iter().map(|point| point.id).collect();
+    assert_eq!(ids, vec![1.into(), 2.into()]);
+    assert_eq!(scrolled.next_page_offset, Some(3.into()));
+
+    // Search returns the best points only
+    let found = collection
+        .search(
+            search_request().into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 2);
+
+    // Query batch is truncated as a whole, the last queries are cut first
+    let found = collection
+        .query_batch(
+            vec![
+                (query_request(), ShardSelectorInternal::All),
+                (query_request(), ShardSelectorInternal::All),
+            ],
+            |_| async { None },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(found[0].len(), 2);
+    assert!(found[1].is_empty());
+}
@@ -1,11 +1,33 @@
+mod default_timeout;
 mod fix_payload_indices;
 pub mod fixtures;
+mod hash_ring_consistency;
+mod max_result_points;
+mod optimizer_jitter;
 mod payload;
+mod payload_schema_enforcement;
+mod point_versions;
 mod points_dedup;
+mod query_explanation;
+mod query_plan;
+mod require_filter;
+mod resharding_resume;
+mod retrieve_cache;
+mod score_rounding;
+#[cfg(feature = "segment-debug")]
+mod segment_debug;
 mod sha_256_test;
 mod shard_query;
+mod shard_transfer_test;
+mod shard_update_limit;
 mod snapshot_test;
 mod sparse_vectors_validation_tests;
+mod strict_mode_batch;
+mod tie_break;
+mod update_validation;
+mod upsert_coalescing;
+mod vector_dedup;
+mod wal_disabled;
 mod wal_recovery_test;
 
 use std::sync::Arc;
@@ -224,6 +246,7 @@ async fn test_new_segment_when_all_over_capacity() {
         max_segment_size_kb: 1,
         memmap_threshold_kb: 1_000_000,
         indexing_threshold_kb: 1_000_000,
+        indexing_min_vector_number: 0,
     };
     let payload_index_schema = PayloadIndexSchema::default();
     let mut holder = SegmentHolder::default();
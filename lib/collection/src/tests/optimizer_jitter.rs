@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use common::types::{DetailsLevel, TelemetryDetail};
+use futures::future::join_all;
+use segment::data_types::vectors::VectorStructInternal;
+use tempfile::Builder;
+use tokio::time::{sleep, Instant};
+
+use crate::collection::Collection;
+use crate::config::CollectionConfig;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture_with_storage_config, create_collection_config};
+
+const NUM_COLLECTIONS: usize = 8;
+const NUM_POINTS: u64 = 200;
+const OPTIMIZER_JITTER: Duration = Duration::from_secs(2);
+
+/// Time to wait for optimizations on top of the jitter, generous to not depend on machine speed
+const START_TIMEOUT: Duration = Duration::from_secs(30);
+
+fn jitter_fixture() -> (CollectionConfig, Arc<SharedStorageConfig>) {
+    // Points of a single upsert are enough to be indexed
+    let mut config = create_collection_config();
+    config.optimizer_config.indexing_threshold = Some(1);
+
+    let shared_storage_config = Arc::new(SharedStorageConfig {
+        optimizer_jitter: OPTIMIZER_JITTER,
+        ..Default::default()
+    });
+
+    (config, shared_storage_config)
+}
+
+async fn upsert_points(collection: &Collection, ids: impl Iterator<Item = u64>) {
+    let points = ids
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+}
+
+/// Start time of the first optimization of the collection, if any was started yet
+async fn first_optimization_start(collection: &Collection) -> Option<DateTime<Utc>> {
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level0,
+        histograms: false,
+    };
+    let telemetry = collection.get_telemetry_data(detail).await;
+    telemetry
+        .shards
+        .iter()
+        .filter_map(|shard| shard.local.as_ref())
+        .flat_map(|local| &local.optimizations.log)
+        .map(|tracker| tracker.start_at)
+        .min()
+}
+
+/// Wait for an optimization to be started in every collection, and return its start times
+async fn wait_for_optimization_starts(collections: &[Collection]) -> Vec<DateTime<Utc>> {
+    let deadline = Instant::now() + OPTIMIZER_JITTER + START_TIMEOUT;
+    loop {
+        let mut start_times = Vec::with_capacity(collections.len());
+        for collection in collections {
+            start_times.extend(first_optimization_start(collection).await);
+        }
+
+        if start_times.len() == collections.len() {
+            return start_times;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "optimizations started in only {} of {} collections",
+            start_times.len(),
+            collections.len(),
+        );
+        sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_optimizer_jitter_staggers_collections() {
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collections_dir = Builder::new().prefix("test_collections").tempdir().unwrap();
+    let (config, shared_storage_config) = jitter_fixture();
+
+    let mut collections = Vec::with_capacity(NUM_COLLECTIONS);
+    for i in 0..NUM_COLLECTIONS {
+        let collection_dir = collections_dir.path().join(i.to_string());
+        std::fs::create_dir_all(&collection_dir).unwrap();
+        collections.push(
+            collection_fixture_with_storage_config(
+                &collection_dir,
+                snapshots_dir.path(),
+                &config,
+                shared_storage_config.clone(),
+            )
+            .await,
+        );
+    }
+
+    // Trigger optimizations of all collections at once
+    join_all(
+        collections
+            .iter()
+            .map(|collection| upsert_points(collection, 0..NUM_POINTS)),
+    )
+    .await;
+
+    let start_times = wait_for_optimization_starts(&collections).await;
+
+    // Optimizations did not start simultaneously, but spread over the jitter range
+    // With independent uniform delays, all of them falling within a tenth of the range has a
+    // probability in the order of 1e-6
+    let first = start_times.iter().min().unwrap();
+    let last = start_times.iter().max().unwrap();
+    let spread = (*last - *first).num_milliseconds();
+    assert!(
+        spread >= OPTIMIZER_JITTER.as_millis() as i64 / 10,
+        "optimizations started within {spread}ms",
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_optimizer_jitter_with_signals_during_delay() {
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let (config, shared_storage_config) = jitter_fixture();
+
+    let collection = collection_fixture_with_storage_config(
+        collection_dir.path(),
+        snapshots_dir.path(),
+        &config,
+        shared_storage_config,
+    )
+    .await;
+
+    // Keep signalling the optimizers while the start of optimizations is delayed, none of the
+    // signals may postpone or swallow the delayed start
+    for batch in 0..NUM_POINTS / 10 {
+        upsert_points(&collection, batch * 10..(batch + 1) * 10).await;
+        sleep(OPTIMIZER_JITTER / 20).await;
+    }
+
+    wait_for_optimization_starts(std::slice::from_ref(&collection)).await;
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Payload, PayloadSchemaType};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::payload_schema::{PayloadSchemaConfig, PayloadSchemaEnforcement};
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::types::CollectionError;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+async fn collection_with_schema(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    enforcement: PayloadSchemaEnforcement,
+) -> Collection {
+    let mut config = create_collection_config();
+    config.payload_schema = Some(PayloadSchemaConfig {
+        fields: BTreeMap::from([
+            ("city".to_string(), PayloadSchemaType::Keyword),
+            ("population".to_string(), PayloadSchemaType::Integer),
+        ]),
+        enforcement,
+    });
+
+    collection_fixture(collection_dir, snapshots_path, &config).await
+}
+
+async fn upsert_with_payload(
+    collection: &Collection,
+    payload: serde_json::Value,
+) -> Result<(), CollectionError> {
+    let payload: Payload = serde_json::from_value(payload).unwrap();
+    let operation = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: 1.into(),
+            vector: VectorStructInternal::from(vec![1.0, 0.0, 0.0, 0.0]).into(),
+            payload: Some(payload),
+        }]),
+    ));
+    collection
+        .update_from_client_simple(operation, true, WriteOrdering::default())
+        .await
+        .map(|_| ())
+}
+
+fn conforming() -> serde_json::Value {
+    json!({"city": "Berlin", "population": 3_850_000})
+}
+
+fn non_conforming() -> serde_json::Value {
+    json!({"city": "Berlin", "population": "many", "country": "Germany"})
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_payload_schema_off() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_with_schema(
+        collection_dir.path(),
+        snapshots_path.path(),
+        PayloadSchemaEnforcement::Off,
+    )
+    .await;
+
+    upsert_with_payload(&collection, conforming())
+        .await
+        .unwrap();
+    upsert_with_payload(&collection, non_conforming())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_payload_schema_warn() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_with_schema(
+        collection_dir.path(),
+        snapshots_path.path(),
+        PayloadSchemaEnforcement::Warn,
+    )
+    .await;
+
+    upsert_with_payload(&collection, conforming())
+        .await
+        .unwrap();
+    upsert_with_payload(&collection, non_conforming())
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_payload_schema_strict() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_with_schema(
+        collection_dir.path(),
+        snapshots_path.path(),
+        PayloadSchemaEnforcement::Strict,
+    )
+    .await;
+
+    upsert_with_payload(&collection, conforming())
+        .await
+        .unwrap();
+
+    let error = upsert_with_payload(&collection, non_conforming())
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(error, CollectionError::BadInput { .. }),
+        "unexpected error: {error}",
+    );
+    assert!(error.to_string().contains("population"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_payload_schema_update() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_with_schema(
+        collection_dir.path(),
+        snapshots_path.path(),
+        PayloadSchemaEnforcement::Off,
+    )
+    .await;
+
+    upsert_with_payload(&collection, non_conforming())
+        .await
+        .unwrap();
+
+    // Stricter enforcement applies to following updates
+    let mut payload_schema = collection
+        .collection_config
+        .read()
+        .await
+        .payload_schema
+        .clone()
+        .unwrap();
+    payload_schema.enforcement = PayloadSchemaEnforcement::Strict;
+    collection
+        .update_payload_schema(payload_schema.clone())
+        .await
+        .unwrap();
+    assert!(upsert_with_payload(&collection, non_conforming())
+        .await
+        .is_err());
+
+    // Declaring the missing key with a matching type makes the payload conforming
+    payload_schema
+        .fields
+        .insert("population".to_string(), PayloadSchemaType::Keyword);
+    payload_schema
+        .fields
+        .insert("country".to_string(), PayloadSchemaType::Keyword);
+    collection
+        .update_payload_schema(payload_schema)
+        .await
+        .unwrap();
+    upsert_with_payload(&collection, non_conforming())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,177 @@
+use std::num::NonZeroU32;
+
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Payload, WithPayloadInterface, WithVector};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    ConditionalUpsert, PointInsertOperationsInternal, PointOperations, PointStruct, PointVersion,
+    WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, PointRequestInternal};
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+fn point(id: u64, version: u64) -> PointStruct {
+    let payload: Payload = json!({ "version": version }).into();
+    PointStruct {
+        id: id.into(),
+        vector: VectorStructInternal::from(vec![id as f32, 0.0, 0.0, 0.0]).into(),
+        payload: Some(payload),
+    }
+}
+
+fn conditional_upsert(
+    id: u64,
+    expected_version: u64,
+    new_version: u64,
+) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsIfVersion(
+        ConditionalUpsert {
+            points: PointInsertOperationsInternal::PointsList(vec![point(id, new_version)]),
+            expected_versions: vec![PointVersion {
+                id: id.into(),
+                version: expected_version,
+            }],
+            version_key: "version".parse().unwrap(),
+        },
+    ))
+}
+
+async fn stored_version(collection: &Collection, id: u64) -> Option<u64> {
+    let request = PointRequestInternal {
+        ids: vec![id.into()],
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(false),
+    };
+    let records = collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    records.first().map(|record| {
+        record.payload.as_ref().unwrap().0["version"]
+            .as_u64()
+            .unwrap()
+    })
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_upsert_with_expected_versions() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+
+    // Point which doesn't exist yet has version 0
+    collection
+        .update_from_client_simple(conditional_upsert(1, 0, 1), true, WriteOrdering::Weak)
+        .await
+        .unwrap();
+    assert_eq!(stored_version(&collection, 1).await, Some(1));
+
+    // Current version is accepted, without waiting for the update to be applied
+    collection
+        .update_from_client_simple(conditional_upsert(1, 1, 2), false, WriteOrdering::Weak)
+        .await
+        .unwrap();
+
+    // Stale version is rejected, even without waiting, as versions are checked by the leader
+    // replica before the update is sent to any replica
+    let result = collection
+        .update_from_client_simple(conditional_upsert(1, 1, 3), false, WriteOrdering::Weak)
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::Conflict { .. })),
+        "{result:?}",
+    );
+    assert_eq!(stored_version(&collection, 1).await, Some(2));
+
+    // Version is read from the payload, a plain upsert changes it as well
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(vec![point(1, 5)]),
+            )),
+            true,
+            WriteOrdering::Weak,
+        )
+        .await
+        .unwrap();
+    let result = collection
+        .update_from_client_simple(conditional_upsert(1, 2, 3), true, WriteOrdering::Weak)
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::Conflict { .. })),
+        "{result:?}",
+    );
+    collection
+        .update_from_client_simple(conditional_upsert(1, 5, 6), true, WriteOrdering::Weak)
+        .await
+        .unwrap();
+    assert_eq!(stored_version(&collection, 1).await, Some(6));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_upsert_with_expected_versions_in_multiple_shards() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let mut config = create_collection_config();
+    config.params.shard_number = NonZeroU32::new(2).unwrap();
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+
+    // Points of both shards, versions are not checked atomically across shards
+    let ids = 1..=10;
+    let operation = CollectionUpdateOperations::PointOperation(
+        PointOperations::UpsertPointsIfVersion(ConditionalUpsert {
+            points: PointInsertOperationsInternal::PointsList(
+                ids.clone().map(|id| point(id, 1)).collect(),
+            ),
+            expected_versions: ids
+                .clone()
+                .map(|id| PointVersion {
+                    id: id.into(),
+                    version: 0,
+                })
+                .collect(),
+            version_key: "version".parse().unwrap(),
+        }),
+    );
+    let result = collection
+        .update_from_client_simple(operation, true, WriteOrdering::Weak)
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::BadRequest { .. })),
+        "{result:?}",
+    );
+    for id in ids {
+        assert_eq!(stored_version(&collection, id).await, None);
+    }
+
+    // A single point is in a single shard
+    collection
+        .update_from_client_simple(conditional_upsert(1, 0, 1), true, WriteOrdering::Weak)
+        .await
+        .unwrap();
+    assert_eq!(stored_version(&collection, 1).await, Some(1));
+}
+
+#[test]
+fn test_new_versions_must_grow() {
+    let check_new_versions = |operation: CollectionUpdateOperations| match operation {
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsIfVersion(
+            conditional_upsert,
+        )) => conditional_upsert.check_new_versions(),
+        _ => unreachable!(),
+    };
+
+    assert!(check_new_versions(conditional_upsert(1, 3, 3)).is_err());
+    assert!(check_new_versions(conditional_upsert(1, 3, 4)).is_ok());
+}
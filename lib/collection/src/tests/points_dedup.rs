@@ -39,6 +39,8 @@ async fn fixture() -> Collection {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -56,6 +58,10 @@ async fn fixture() -> Collection {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -244,7 +250,7 @@ async fn test_search_dedup() {
                 query: QueryEnum::Nearest(NamedVectorStruct::Default(vec![0.1, 0.2, 0.3, 0.4])),
                 filter: None,
                 params: Some(SearchParams {
-                    exact: true,
+                    exact: Some(true),
                     ..Default::default()
                 }),
                 limit: 100,
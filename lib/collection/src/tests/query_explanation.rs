@@ -0,0 +1,152 @@
+use std::collections::{BTreeMap, HashMap};
+
+use segment::data_types::vectors::{Vector, VectorStructInternal};
+use segment::types::{Distance, ExtendedPointId, WithPayloadInterface, WithVector};
+use tempfile::Builder;
+
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::VectorsConfig;
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
+};
+use crate::operations::universal_query::shard_query::Fusion;
+use crate::operations::vector_params_builder::VectorParamsBuilder;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+const NUM_POINTS: u64 = 10;
+const PREFETCH_LIMIT: usize = 5;
+
+fn prefetch(using: &str) -> CollectionPrefetch {
+    CollectionPrefetch {
+        prefetch: vec![],
+        query: Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            Vector::from(vec![1.0, 0.0, 0.0, 0.0]),
+        )))),
+        using: using.to_string(),
+        filter: None,
+        score_threshold: None,
+        limit: PREFETCH_LIMIT,
+        params: None,
+        lookup_from: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_explain_rrf() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.params.vectors = VectorsConfig::Multi(BTreeMap::from([
+        (
+            "a".to_string(),
+            VectorParamsBuilder::new(4, Distance::Dot).build(),
+        ),
+        (
+            "b".to_string(),
+            VectorParamsBuilder::new(4, Distance::Dot).build(),
+        ),
+    ]));
+
+    let collection = collection_fixture(collection_dir.path(), snapshots_dir.path(), &config).await;
+
+    // Score of point `i` is `i` for vector `a`, and `(i + 3) % 10` for vector `b`
+    let points = (0..NUM_POINTS)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::Named(HashMap::from([
+                ("a".to_string(), Vector::from(vec![i as f32, 0.0, 0.0, 0.0])),
+                (
+                    "b".to_string(),
+                    Vector::from(vec![((i + 3) % NUM_POINTS) as f32, 0.0, 0.0, 0.0]),
+                ),
+            ]))
+            .into(),
+            payload: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let request = CollectionQueryRequest {
+        prefetch: vec![prefetch("a"), prefetch("b")],
+        query: Some(Query::Fusion(Fusion::Rrf)),
+        using: "".to_string(),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+        lookup_from: None,
+    };
+
+    let response = collection
+        .query_explain(
+            request,
+            ShardSelectorInternal::All,
+            |_| async { None },
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Ranks in the results of each prefetch: `a` returns 9, 8, 7, 6, 5 and `b` 6, 5, 4, 3, 2
+    let expected_ranks: HashMap<u64, Vec<(usize, usize)>> = HashMap::from([
+        (9, vec![(0, 0)]),
+        (8, vec![(0, 1)]),
+        (7, vec![(0, 2)]),
+        (6, vec![(0, 3), (1, 0)]),
+        (5, vec![(0, 4), (1, 1)]),
+        (4, vec![(1, 2)]),
+        (3, vec![(1, 3)]),
+        (2, vec![(1, 4)]),
+    ]);
+
+    assert_eq!(response.points.len(), expected_ranks.len());
+
+    for explained in &response.points {
+        let ExtendedPointId::NumId(id) = explained.point.id else {
+            panic!("unexpected point id {:?}", explained.point.id);
+        };
+        let explanation = &explained.explanation;
+        assert_eq!(explanation.fusion, Some(api::rest::Fusion::Rrf));
+
+        let ranks: Vec<_> = explanation
+            .prefetches
+            .iter()
+            .map(|contribution| (contribution.prefetch, contribution.rank))
+            .collect();
+        assert_eq!(ranks, expected_ranks[&id], "point {id}");
+
+        for contribution in &explanation.prefetches {
+            assert_eq!(
+                contribution.contribution,
+                1.0 / (contribution.rank as f32 + 2.0)
+            );
+        }
+        let sum: f32 = explanation
+            .prefetches
+            .iter()
+            .map(|contribution| contribution.contribution)
+            .sum();
+        assert!((sum - explained.point.score).abs() < 1e-6, "point {id}");
+    }
+
+    // Point ranked high by both prefetches is the best one
+    assert_eq!(response.points[0].point.id, ExtendedPointId::from(6));
+}
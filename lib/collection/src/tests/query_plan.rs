@@ -0,0 +1,161 @@
+use std::collections::BTreeMap;
+
+use segment::data_types::vectors::Vector;
+use segment::types::{Distance, Filter, SearchParams, WithPayloadInterface, WithVector};
+use sparse::common::sparse_vector::SparseVector;
+use tempfile::Builder;
+
+use crate::collection::query_plan::{QueryPlanScoring, VectorSearchStrategy};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{SparseVectorParams, VectorsConfig};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
+};
+use crate::operations::universal_query::shard_query::Fusion;
+use crate::operations::vector_params_builder::VectorParamsBuilder;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+fn prefetch(using: &str, vector: Vector, limit: usize) -> CollectionPrefetch {
+    CollectionPrefetch {
+        prefetch: vec![],
+        query: Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            vector,
+        )))),
+        using: using.to_string(),
+        filter: None,
+        score_threshold: None,
+        limit,
+        params: None,
+        lookup_from: None,
+    }
+}
+
+fn query_request(prefetch: Vec<CollectionPrefetch>, query: Query) -> CollectionQueryRequest {
+    CollectionQueryRequest {
+        prefetch,
+        query: Some(query),
+        using: "".to_string(),
+        filter: None,
+        score_threshold: None,
+        limit: 10,
+        offset: 5,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+        lookup_from: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_query_plan_hybrid_rrf() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.params.vectors = VectorsConfig::Multi(BTreeMap::from([(
+        "dense".to_string(),
+        VectorParamsBuilder::new(4, Distance::Dot).build(),
+    )]));
+    config.params.sparse_vectors = Some(BTreeMap::from([(
+        "sparse".to_string(),
+        SparseVectorParams {
+            index: None,
+            modifier: None,
+        },
+    )]));
+
+    let collection = collection_fixture(collection_dir.path(), snapshots_dir.path(), &config).await;
+
+    let dense_vector = Vector::from(vec![1.0, 2.0, 3.0, 4.0]);
+    let sparse_vector = Vector::from(SparseVector {
+        indices: vec![1, 5],
+        values: vec![0.5, 1.0],
+    });
+
+    let request = query_request(
+        vec![
+            prefetch("dense", dense_vector.clone(), 20),
+            prefetch("sparse", sparse_vector, 30),
+        ],
+        Query::Fusion(Fusion::Rrf),
+    );
+
+    let plan = collection
+        .query_plan(&request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+
+    assert_eq!(plan.shards, 1);
+    assert_eq!(plan.offset, 5);
+
+    // Root step fuses the results of both prefetches
+    assert!(matches!(
+        plan.root.scoring,
+        QueryPlanScoring::Fusion {
+            fusion: api::rest::Fusion::Rrf
+        }
+    ));
+    assert_eq!(plan.root.limit, 10);
+    assert_eq!(plan.root.prefetches.len(), 2);
+
+    let dense = &plan.root.prefetches[0];
+    assert!(matches!(
+        &dense.scoring,
+        QueryPlanScoring::Vector { using, strategy: VectorSearchStrategy::Hnsw } if using == "dense",
+    ));
+    assert_eq!(dense.limit, 20);
+    assert!(dense.prefetches.is_empty());
+
+    let sparse = &plan.root.prefetches[1];
+    assert!(matches!(
+        &sparse.scoring,
+        QueryPlanScoring::Vector { using, strategy: VectorSearchStrategy::SparseIndex } if using == "sparse",
+    ));
+    assert_eq!(sparse.limit, 30);
+    assert!(sparse.prefetches.is_empty());
+
+    // Filtered exact search reports the estimated number of matching points
+    let mut exact_prefetch = prefetch("dense", dense_vector.clone(), 20);
+    exact_prefetch.filter = Some(Filter::default());
+    exact_prefetch.params = Some(SearchParams {
+        exact: Some(true),
+        ..Default::default()
+    });
+    let request = query_request(vec![exact_prefetch], Query::Fusion(Fusion::Dbsf));
+    let plan = collection
+        .query_plan(&request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    let exact = &plan.root.prefetches[0];
+    assert!(matches!(
+        exact.scoring,
+        QueryPlanScoring::Vector {
+            strategy: VectorSearchStrategy::Exact,
+            ..
+        },
+    ));
+    assert_eq!(exact.estimated_cardinality, Some(0));
+
+    // Unknown vectors are rejected
+    let request = query_request(vec![], Query::Fusion(Fusion::Rrf));
+    let mut request = CollectionQueryRequest {
+        query: Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            dense_vector,
+        )))),
+        using: "missing".to_string(),
+        ..request
+    };
+    assert!(collection
+        .query_plan(&request, None, &ShardSelectorInternal::All, None)
+        .await
+        .is_err());
+
+    // Prefetches without a query are rejected
+    request.using = "dense".to_string();
+    request.query = None;
+    request.prefetch = vec![prefetch("sparse", Vector::from(vec![1.0]), 10)];
+    assert!(collection
+        .query_plan(&request, None, &ShardSelectorInternal::All, None)
+        .await
+        .is_err());
+}
@@ -0,0 +1,221 @@
+use api::rest::SearchRequestInternal;
+use segment::data_types::vectors::Vector;
+use segment::types::{
+    Condition, FieldCondition, Filter, ValuesCount, WithPayloadInterface, WithVector,
+};
+use tempfile::Builder;
+
+use crate::operations::config_diff::StrictModeConfig;
+use crate::operations::point_ops::WriteOrdering;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CountRequestInternal, ScrollRequestInternal};
+use crate::operations::universal_query::collection_query::{
+    CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
+};
+use crate::tests::fixtures::{collection_fixture, create_collection_config, upsert_operation};
+
+fn location_filter() -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_values_count(
+        "location".parse().unwrap(),
+        ValuesCount {
+            lt: None,
+            gt: None,
+            gte: Some(1),
+            lte: None,
+        },
+    )))
+}
+
+fn search_request(filter: Option<Filter>) -> SearchRequestInternal {
+    SearchRequestInternal {
+        vector: vec![1.0, 2.0, 3.0, 4.0].into(),
+        filter,
+        params: None,
+        limit: 3,
+        offset: None,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+    }
+}
+
+fn query_request(
+    filter: Option<Filter>,
+    prefetch_filter: Option<Filter>,
+) -> CollectionQueryRequest {
+    let nearest = || {
+        Some(Query::Vector(VectorQuery::Nearest(VectorInput::Vector(
+            Vector::from(vec![1.0, 2.0, 3.0, 4.0]),
+        ))))
+    };
+    CollectionQueryRequest {
+        prefetch: vec![CollectionPrefetch {
+            prefetch: vec![],
+            query: nearest(),
+            using: "".to_string(),
+            filter: prefetch_filter,
+            score_threshold: None,
+            limit: 3,
+            params: None,
+            lookup_from: None,
+        }],
+        query: nearest(),
+        using: "".to_string(),
+        filter,
+        score_threshold: None,
+        limit: 3,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+        lookup_from: None,
+    }
+}
+
+fn scroll_request(filter: Option<Filter>) -> ScrollRequestInternal {
+    ScrollRequestInternal {
+        offset: None,
+        limit: Some(3),
+        filter,
+        with_payload: None,
+        with_vector: false.into(),
+        order_by: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_require_filter() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.strict_mode_config = Some(StrictModeConfig {
+        enabled: Some(true),
+        max_query_limit: None,
+        max_timeout: None,
+        unindexed_filtering_retrieve: None,
+        unindexed_filtering_update: None,
+        search_max_hnsw_ef: None,
+        search_allow_exact: None,
+        search_max_oversampling: None,
+        max_distinct_values: None,
+        max_filter_conditions: None,
+        require_filter: Some(true),
+        max_result_points: None,
+        truncate_result_points: None,
+        max_payload_bytes: None,
+    });
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+    collection
+        .update_from_client_simple(upsert_operation(), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Requests without a filter are rejected
+    let result = collection
+        .search(
+            search_request(None).into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let result = collection
+        .scroll_by(
+            scroll_request(None),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let count_request = CountRequestInternal {
+        filter: None,
+        exact: true,
+    };
+    let result = collection
+        .count(count_request, None, &ShardSelectorInternal::All, None)
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    let result = collection
+        .query_batch(
+            vec![(query_request(None, None), ShardSelectorInternal::All)],
+            |_| async { None },
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::StrictMode { .. })));
+
+    // Filtered requests pass
+    let found = collection
+        .search(
+            search_request(Some(location_filter())).into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 3);
+
+    let scrolled = collection
+        .scroll_by(
+            scroll_request(Some(location_filter())),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(scrolled.points.len(), 3);
+
+    let count_request = CountRequestInternal {
+        filter: Some(location_filter()),
+        exact: true,
+    };
+    let counted = collection
+        .count(count_request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(counted.count, 5);
+
+    // Root filter of a query applies to its prefetches, otherwise every prefetch needs a filter
+    let queries = [
+        query_request(Some(location_filter()), None),
+        query_request(None, Some(location_filter())),
+    ];
+    for query in queries {
+        let found = collection
+            .query_batch(
+                vec![(query, ShardSelectorInternal::All)],
+                |_| async { None },
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(found[0].len(), 3);
+    }
+
+    // Internal requests to a specific shard are exempt
+    let count_request = CountRequestInternal {
+        filter: None,
+        exact: true,
+    };
+    let counted = collection
+        .count(
+            count_request,
+            None,
+            &ShardSelectorInternal::ShardId(0),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(counted.count, 5);
+}
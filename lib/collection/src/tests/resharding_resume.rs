@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{PointIdType, SeqNumberType};
+use tempfile::Builder;
+
+use crate::operations::cluster_ops::ReshardingDirection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::types::{PointRequestInternal, Record};
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::resharding::stage_migrate_points::migrate_points;
+use crate::shards::resharding::ReshardKey;
+use crate::tests::fixtures::{collection_fixture, create_collection_config, THIS_PEER_ID};
+
+const NUM_POINTS: u64 = 200;
+
+fn upsert(points: Vec<PointStruct>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+async fn all_points(replica_set: &ShardReplicaSet) -> Vec<Record> {
+    replica_set
+        .scroll_by(
+            None,
+            NUM_POINTS as usize,
+            &true.into(),
+            &true.into(),
+            None,
+            None,
+            true,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+}
+
+async fn point_versions(
+    replica_set: &ShardReplicaSet,
+    ids: &[PointIdType],
+) -> HashMap<PointIdType, Option<SeqNumberType>> {
+    replica_set
+        .retrieve(
+            Arc::new(PointRequestInternal {
+                ids: ids.to_vec(),
+                with_payload: None,
+                with_vector: false.into(),
+            }),
+            &false.into(),
+            &false.into(),
+            None,
+            None,
+            true,
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|point| (point.id, point.version))
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_resume_resharding_migration() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("snapshots").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.params.shard_number = NonZeroU32::new(2).unwrap();
+
+    let collection = collection_fixture(collection_dir.path(), snapshots_dir.path(), &config).await;
+
+    let points = (0..NUM_POINTS)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32, 1.0, 2.0, 3.0]).into(),
+            payload: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(upsert(points), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Scale down, migrating all points of shard 1 into shard 0
+    let resharding_key = ReshardKey {
+        direction: ReshardingDirection::Down,
+        peer_id: THIS_PEER_ID,
+        shard_id: 1,
+        shard_key: None,
+    };
+
+    let shard_holder = collection.shards_holder();
+    shard_holder
+        .write()
+        .await
+        .start_resharding_unchecked(resharding_key.clone(), None)
+        .unwrap();
+
+    let hashring = shard_holder.read().await.rings[&None].clone();
+
+    // Stage a partial migration, half of the points were moved before it was interrupted
+    let (source_points, staged_ids) = {
+        let shard_holder = shard_holder.read().await;
+        let source = shard_holder.get_shard(&1).unwrap();
+        let target = shard_holder.get_shard(&0).unwrap();
+
+        let source_points = all_points(source).await;
+        assert!(source_points.len() > 1);
+
+        let staged_points: Vec<_> = source_points
+            .iter()
+            .take(source_points.len() / 2)
+            .cloned()
+            .map(|point| PointStruct::try_from(point).unwrap())
+            .collect();
+        let staged_ids: Vec<_> = staged_points.iter().map(|point| point.id).collect();
+        target
+            .update_with_consistency(upsert(staged_points), true, WriteOrdering::Weak)
+            .await
+            .unwrap();
+
+        (source_points, staged_ids)
+    };
+
+    let staged_versions = {
+        let shard_holder = shard_holder.read().await;
+        point_versions(shard_holder.get_shard(&0).unwrap(), &staged_ids).await
+    };
+    assert_eq!(staged_versions.len(), staged_ids.len());
+
+    // Resuming is only possible while migrating points
+    shard_holder
+        .write()
+        .await
+        .check_resume_resharding(&resharding_key)
+        .unwrap();
+
+    // Resume migrates only the remaining points
+    let migrated = migrate_points(&shard_holder, &hashring, 1, 0)
+        .await
+        .unwrap();
+    assert_eq!(migrated, source_points.len() - staged_ids.len());
+
+    {
+        let shard_holder = shard_holder.read().await;
+        let target = shard_holder.get_shard(&0).unwrap();
+
+        let source_ids: Vec<_> = source_points.iter().map(|point| point.id).collect();
+        let target_versions = point_versions(target, &source_ids).await;
+        assert_eq!(target_versions.len(), source_ids.len());
+
+        // Already moved points were not written again
+        assert_eq!(point_versions(target, &staged_ids).await, staged_versions);
+
+        assert_eq!(all_points(target).await.len(), NUM_POINTS as usize);
+    }
+
+    // Nothing is left to migrate
+    let migrated = migrate_points(&shard_holder, &hashring, 1, 0)
+        .await
+        .unwrap();
+    assert_eq!(migrated, 0);
+
+    shard_holder
+        .write()
+        .await
+        .commit_read_hashring(resharding_key.clone())
+        .unwrap();
+    assert!(shard_holder
+        .write()
+        .await
+        .check_resume_resharding(&resharding_key)
+        .is_err());
+}
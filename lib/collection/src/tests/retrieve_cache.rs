@@ -0,0 +1,91 @@
+use std::sync::Arc;
+
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Payload, WithPayloadInterface, WithVector};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::PointRequestInternal;
+use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use crate::tests::fixtures::{collection_fixture_with_storage_config, create_collection_config};
+
+fn upsert(id: u64, version: u64) -> CollectionUpdateOperations {
+    let payload: Payload = json!({ "version": version }).into();
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32, 0.0, 0.0, 0.0]).into(),
+            payload: Some(payload),
+        }]),
+    ))
+}
+
+async fn retrieve_version(collection: &Collection, id: u64) -> u64 {
+    let request = PointRequestInternal {
+        ids: vec![id.into()],
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(false),
+    };
+    let records = collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    records[0].payload.as_ref().unwrap().0["version"]
+        .as_u64()
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_retrieve_cache_invalidated_on_update() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let shared_storage_config = SharedStorageConfig {
+        retrieve_cache_size: 100,
+        ..Default::default()
+    };
+
+    let collection = collection_fixture_with_storage_config(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+        Arc::new(shared_storage_config),
+    )
+    .await;
+
+    collection
+        .update_from_client_simple(upsert(1, 1), true, WriteOrdering::Weak)
+        .await
+        .unwrap();
+
+    // First retrieve reads the shard and caches the point
+    assert_eq!(retrieve_version(&collection, 1).await, 1);
+    assert_eq!(retrieve_version(&collection, 1).await, 1);
+
+    // Update the shard directly, the update handler of the shard invalidates the cached point
+    collection
+        .shards_holder
+        .read()
+        .await
+        .get_shard(&0)
+        .unwrap()
+        .update_local(OperationWithClockTag::from(upsert(1, 2)), true)
+        .await
+        .unwrap();
+
+    assert_eq!(retrieve_version(&collection, 1).await, 2);
+
+    // Update through the collection invalidates the cached point as well
+    collection
+        .update_from_client_simple(upsert(1, 3), true, WriteOrdering::Weak)
+        .await
+        .unwrap();
+    assert_eq!(retrieve_version(&collection, 1).await, 3);
+}
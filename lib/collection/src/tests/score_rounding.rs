@@ -0,0 +1,86 @@
+use api::rest::SearchRequestInternal;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{ExtendedPointId, ScoredPoint, SearchParams};
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+const NUM_POINTS: u64 = 20;
+
+async fn search(collection: &Collection, score_precision: Option<u32>) -> Vec<ScoredPoint> {
+    let request = SearchRequestInternal {
+        vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+        filter: None,
+        params: Some(SearchParams {
+            score_precision,
+            ..Default::default()
+        }),
+        limit: NUM_POINTS as usize,
+        offset: None,
+        with_payload: None,
+        with_vector: None,
+        score_threshold: None,
+    };
+
+    collection
+        .search(request.into(), None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_search_score_rounding() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_dir.path(),
+        &create_collection_config(),
+    )
+    .await;
+
+    // Dot product scores of the points differ by 0.0003
+    let points = (0..NUM_POINTS)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![1.0 + id as f32 * 0.0003, 0.0, 0.0, 0.0])
+                .into(),
+            payload: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let exact = search(&collection, None).await;
+    let rounded = search(&collection, Some(2)).await;
+
+    // Order of the results is preserved
+    let ids = |points: &[ScoredPoint]| points.iter().map(|point| point.id).collect::<Vec<_>>();
+    let expected_ids: Vec<_> = (0..NUM_POINTS).rev().map(ExtendedPointId::from).collect();
+    assert_eq!(ids(&exact), expected_ids);
+    assert_eq!(ids(&rounded), expected_ids);
+
+    for (exact, rounded) in exact.iter().zip(&rounded) {
+        let expected = (exact.score * 100.0).round() / 100.0;
+        assert!((rounded.score - expected).abs() < 1e-6);
+    }
+
+    // Near-identical scores collapse
+    assert_eq!(rounded[0].score, rounded[1].score);
+    assert_ne!(exact[0].score, exact[1].score);
+}
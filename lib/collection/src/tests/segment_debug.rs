@@ -0,0 +1,66 @@
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{WithPayload, WithVector};
+use tempfile::Builder;
+
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::CollectionError;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_retrieve_from_segment() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+    let shard = local_shard_fixture(
+        collection_dir.path(),
+        payload_index_schema_dir.path(),
+        SharedStorageConfig::default(),
+    )
+    .await;
+
+    shard.update(upsert_operation().into(), true).await.unwrap();
+
+    let segments = shard.segments_with_point(1.into());
+    let [(segment_id, version)] = segments[..] else {
+        panic!("point must be in exactly one segment, found in {segments:?}");
+    };
+    assert!(shard.segments_with_point(100.into()).is_empty());
+
+    let record = shard
+        .retrieve_from_segment(
+            segment_id,
+            1.into(),
+            &WithPayload::from(true),
+            &WithVector::Bool(true),
+        )
+        .unwrap()
+        .expect("point must be found in its segment");
+    assert_eq!(record.id, 1.into());
+    assert_eq!(
+        record.vector,
+        Some(VectorStructInternal::from(vec![1.0, 2.0, 3.0, 4.0])),
+    );
+    assert!(record.payload.unwrap().0.contains_key("location"));
+    assert_eq!(record.version, Some(version));
+
+    // Points unknown to the segment are not found
+    let record = shard
+        .retrieve_from_segment(
+            segment_id,
+            100.into(),
+            &WithPayload::from(false),
+            &WithVector::Bool(true),
+        )
+        .unwrap();
+    assert!(record.is_none());
+
+    // Unknown segments are reported as such
+    let result = shard.retrieve_from_segment(
+        usize::MAX,
+        1.into(),
+        &WithPayload::from(false),
+        &WithVector::Bool(true),
+    );
+    assert!(matches!(result, Err(CollectionError::NotFound { .. })));
+}
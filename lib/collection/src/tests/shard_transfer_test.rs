@@ -0,0 +1,601 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::grpc::qdrant as grpc;
+use api::grpc::qdrant::collections_internal_server::{
+    CollectionsInternal, CollectionsInternalServer,
+};
+use api::grpc::qdrant::points_internal_server::{PointsInternal, PointsInternalServer};
+use async_trait::async_trait;
+use parking_lot::Mutex;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Condition, FieldCondition, Filter, PointIdType};
+use tempfile::Builder;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio::time::timeout;
+use tonic::transport::server::TcpIncoming;
+use tonic::transport::{Server, Uri};
+use tonic::{Request, Response, Status};
+
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::types::CollectionResult;
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::channel_service::ChannelService;
+use crate::shards::remote_shard::RemoteShard;
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::driver::MAX_RETRY_COUNT;
+use crate::shards::transfer::stream_records::{TRANSFER_BATCH_SIZE, VERIFY_POINT_COUNT_ATTEMPTS};
+use crate::shards::transfer::{
+    ShardTransfer, ShardTransferConsensus, ShardTransferKey, ShardTransferMethod,
+};
+use crate::shards::CollectionId;
+use crate::tests::fixtures::{
+    collection_fixture, collection_fixture_with_channel_service, create_collection_config,
+    THIS_PEER_ID,
+};
+
+const REMOTE_PEER_ID: PeerId = 2;
+
+/// Consensus which accepts every proposal without doing anything.
+struct NoopConsensus;
+
+#[async_trait]
+impl ShardTransferConsensus for NoopConsensus {
+    fn this_peer_id(&self) -> PeerId {
+        THIS_PEER_ID
+    }
+
+    fn peers(&self) -> Vec<PeerId> {
+        vec![THIS_PEER_ID, REMOTE_PEER_ID]
+    }
+
+    fn consensus_commit_term(&self) -> (u64, u64) {
+        (0, 0)
+    }
+
+    fn snapshot_recovered_switch_to_partial(
+        &self,
+        _transfer_config: &ShardTransfer,
+        _collection_id: CollectionId,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    async fn start_shard_transfer(
+        &self,
+        _transfer_config: ShardTransfer,
+        _collection_id: CollectionId,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    async fn restart_shard_transfer(
+        &self,
+        _transfer_config: ShardTransfer,
+        _collection_id: CollectionId,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    async fn abort_shard_transfer(
+        &self,
+        _transfer: ShardTransferKey,
+        _collection_id: CollectionId,
+        _reason: &str,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+
+    async fn set_shard_replica_set_state(
+        &self,
+        _collection_id: CollectionId,
+        _shard_id: ShardId,
+        _state: ReplicaState,
+        _from_state: Option<ReplicaState>,
+    ) -> CollectionResult<()> {
+        Ok(())
+    }
+}
+
+/// First point of the streamed batch, which [`LossyPeer`] drops
+const DROPPED_BATCH_OFFSET: u64 = TRANSFER_BATCH_SIZE as u64;
+
+/// Peer receiving a shard by streamed records, which silently drops one of the batches.
+///
+/// Serves only what the sender of a stream records transfer calls, up to verifying point counts.
+#[derive(Clone, Default)]
+struct LossyPeer {
+    /// Points stored on this peer
+    points: Arc<Mutex<HashSet<PointIdType>>>,
+    /// How many times the batch at `DROPPED_BATCH_OFFSET` was dropped
+    dropped_batches: Arc<AtomicUsize>,
+    /// How many times points on this peer were counted
+    count_requests: Arc<AtomicUsize>,
+}
+
+impl LossyPeer {
+    /// Serve internal points and collections APIs on a local port, return the peer address
+    async fn serve(&self) -> Uri {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let incoming = TcpIncoming::from_listener(listener, true, None).unwrap();
+
+        let server = Server::builder()
+            .add_service(PointsInternalServer::new(self.clone()))
+            .add_service(CollectionsInternalServer::new(self.clone()))
+            .serve_with_incoming(incoming);
+        tokio::spawn(server);
+
+        format!("http://{address}").parse().unwrap()
+    }
+}
+
+#[async_trait]
+impl PointsInternal for LossyPeer {
+    async fn upsert(
+        &self,
+        _request: Request<grpc::UpsertPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("upsert"))
+    }
+
+    /// Store points of every streamed batch, except the dropped one
+    async fn sync(
+        &self,
+        request: Request<grpc::SyncPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        let sync_points = request.into_inner().sync_points.unwrap_or_default();
+        let from_id = sync_points.from_id.map(PointIdType::try_from).transpose()?;
+
+        if from_id == Some(DROPPED_BATCH_OFFSET.into()) {
+            self.dropped_batches.fetch_add(1, Ordering::Relaxed);
+        } else {
+            let point_ids = sync_points
+                .points
+                .into_iter()
+                .filter_map(|point| point.id)
+                .map(PointIdType::try_from)
+                .collect::<Result<Vec<_>, _>>()?;
+            self.points.lock().extend(point_ids);
+        }
+
+        // Batch is acknowledged, whether it was stored or not
+        Ok(Response::new(grpc::PointsOperationResponseInternal {
+            result: Some(grpc::UpdateResultInternal {
+                operation_id: None,
+                status: grpc::UpdateStatus::Completed as i32,
+                clock_tag: None,
+                deduplicated: None,
+            }),
+            time: 0.0,
+        }))
+    }
+
+    async fn delete(
+        &self,
+        _request: Request<grpc::DeletePointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("delete"))
+    }
+
+    async fn update_vectors(
+        &self,
+        _request: Request<grpc::UpdateVectorsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("update_vectors"))
+    }
+
+    async fn delete_vectors(
+        &self,
+        _request: Request<grpc::DeleteVectorsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("delete_vectors"))
+    }
+
+    async fn set_payload(
+        &self,
+        _request: Request<grpc::SetPayloadPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("set_payload"))
+    }
+
+    async fn overwrite_payload(
+        &self,
+        _request: Request<grpc::SetPayloadPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("overwrite_payload"))
+    }
+
+    async fn delete_payload(
+        &self,
+        _request: Request<grpc::DeletePayloadPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("delete_payload"))
+    }
+
+    async fn clear_payload(
+        &self,
+        _request: Request<grpc::ClearPayloadPointsInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("clear_payload"))
+    }
+
+    async fn create_field_index(
+        &self,
+        _request: Request<grpc::CreateFieldIndexCollectionInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("create_field_index"))
+    }
+
+    async fn delete_field_index(
+        &self,
+        _request: Request<grpc::DeleteFieldIndexCollectionInternal>,
+    ) -> Result<Response<grpc::PointsOperationResponseInternal>, Status> {
+        Err(Status::unimplemented("delete_field_index"))
+    }
+
+    async fn core_search_batch(
+        &self,
+        _request: Request<grpc::CoreSearchBatchPointsInternal>,
+    ) -> Result<Response<grpc::SearchBatchResponse>, Status> {
+        Err(Status::unimplemented("core_search_batch"))
+    }
+
+    async fn scroll(
+        &self,
+        _request: Request<grpc::ScrollPointsInternal>,
+    ) -> Result<Response<grpc::ScrollResponse>, Status> {
+        Err(Status::unimplemented("scroll"))
+    }
+
+    async fn count(
+        &self,
+        _request: Request<grpc::CountPointsInternal>,
+    ) -> Result<Response<grpc::CountResponse>, Status> {
+        self.count_requests.fetch_add(1, Ordering::Relaxed);
+        Ok(Response::new(grpc::CountResponse {
+            result: Some(grpc::CountResult {
+                count: self.points.lock().len() as u64,
+            }),
+            time: 0.0,
+        }))
+    }
+
+    async fn recommend(
+        &self,
+        _request: Request<grpc::RecommendPointsInternal>,
+    ) -> Result<Response<grpc::RecommendResponse>, Status> {
+        Err(Status::unimplemented("recommend"))
+    }
+
+    async fn get(
+        &self,
+        _request: Request<grpc::GetPointsInternal>,
+    ) -> Result<Response<grpc::GetResponse>, Status> {
+        Err(Status::unimplemented("get"))
+    }
+
+    async fn query_batch(
+        &self,
+        _request: Request<grpc::QueryBatchPointsInternal>,
+    ) -> Result<Response<grpc::QueryBatchResponseInternal>, Status> {
+        Err(Status::unimplemented("query_batch"))
+    }
+
+    async fn facet(
+        &self,
+        _request: Request<grpc::FacetCountsInternal>,
+    ) -> Result<Response<grpc::FacetResponseInternal>, Status> {
+        Err(Status::unimplemented("facet"))
+    }
+
+    async fn distinct_values(
+        &self,
+        _request: Request<grpc::DistinctValuesInternal>,
+    ) -> Result<Response<grpc::DistinctValuesResponseInternal>, Status> {
+        Err(Status::unimplemented("distinct_values"))
+    }
+}
+
+#[async_trait]
+impl CollectionsInternal for LossyPeer {
+    async fn get(
+        &self,
+        _request: Request<grpc::GetCollectionInfoRequestInternal>,
+    ) -> Result<Response<grpc::GetCollectionInfoResponse>, Status> {
+        Err(Status::unimplemented("get"))
+    }
+
+    async fn initiate(
+        &self,
+        _request: Request<grpc::InitiateShardTransferRequest>,
+    ) -> Result<Response<grpc::CollectionOperationResponse>, Status> {
+        Ok(Response::new(grpc::CollectionOperationResponse {
+            result: true,
+            time: 0.0,
+        }))
+    }
+
+    async fn wait_for_shard_state(
+        &self,
+        _request: Request<grpc::WaitForShardStateRequest>,
+    ) -> Result<Response<grpc::CollectionOperationResponse>, Status> {
+        Err(Status::unimplemented("wait_for_shard_state"))
+    }
+
+    async fn get_shard_recovery_point(
+        &self,
+        _request: Request<grpc::GetShardRecoveryPointRequest>,
+    ) -> Result<Response<grpc::GetShardRecoveryPointResponse>, Status> {
+        Err(Status::unimplemented("get_shard_recovery_point"))
+    }
+
+    async fn update_shard_cutoff_point(
+        &self,
+        _request: Request<grpc::UpdateShardCutoffPointRequest>,
+    ) -> Result<Response<grpc::CollectionOperationResponse>, Status> {
+        Err(Status::unimplemented("update_shard_cutoff_point"))
+    }
+
+    async fn get_hash_rings(
+        &self,
+        _request: Request<grpc::GetHashRingsRequest>,
+    ) -> Result<Response<grpc::GetHashRingsResponse>, Status> {
+        Err(Status::unimplemented("get_hash_rings"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_abort_stream_records_transfer() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+
+    let transfer = ShardTransfer {
+        shard_id: 0,
+        to_shard_id: None,
+        from: THIS_PEER_ID,
+        to: REMOTE_PEER_ID,
+        sync: true,
+        method: Some(ShardTransferMethod::StreamRecords),
+        filter: None,
+    };
+
+    // The remote peer is unreachable, so the transfer task keeps retrying until aborted
+    let started = collection
+        .start_shard_transfer(
+            transfer.clone(),
+            Box::new(NoopConsensus),
+            temp_dir.path().to_path_buf(),
+            async {},
+            async {},
+        )
+        .await
+        .unwrap();
+    assert!(started);
+    assert_eq!(collection.running_shard_transfers().await.len(), 1);
+
+    // Bring the source shard into the state a stream records transfer puts it in
+    {
+        let shard_holder = collection.shards_holder.read().await;
+        let replica_set = shard_holder.get_shard(&transfer.shard_id).unwrap();
+        let remote_shard = RemoteShard::new(
+            transfer.shard_id,
+            collection.id.clone(),
+            REMOTE_PEER_ID,
+            ChannelService::default(),
+        );
+        replica_set.proxify_local(remote_shard, None).await.unwrap();
+        assert!(!replica_set.is_local().await);
+    }
+
+    assert!(collection.abort_transfer(transfer.key()).await.unwrap());
+
+    {
+        let shard_holder = collection.shards_holder.read().await;
+        let replica_set = shard_holder.get_shard(&transfer.shard_id).unwrap();
+        assert!(replica_set.is_local().await);
+    }
+    assert!(collection.running_shard_transfers().await.is_empty());
+
+    // Nothing left to abort
+    assert!(!collection.abort_transfer(transfer.key()).await.unwrap());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_filtered_transfer_finishes_as_listener() {
+    const OTHER_PEER_ID: PeerId = 3;
+
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "tenant".parse().unwrap(),
+        "a".to_string().into(),
+    )));
+    let transfer = ShardTransfer {
+        shard_id: 0,
+        to_shard_id: None,
+        from: REMOTE_PEER_ID,
+        to: OTHER_PEER_ID,
+        sync: true,
+        method: Some(ShardTransferMethod::StreamRecords),
+        filter: Some(filter),
+    };
+
+    // Transfers differing only in their filter are different transfers
+    let unfiltered = ShardTransfer {
+        filter: None,
+        ..transfer.clone()
+    };
+    assert_eq!(HashSet::from([transfer.clone(), unfiltered]).len(), 2);
+
+    // This peer observes a transfer between two other peers
+    let started = collection
+        .start_shard_transfer(
+            transfer.clone(),
+            Box::new(NoopConsensus),
+            temp_dir.path().to_path_buf(),
+            async {},
+            async {},
+        )
+        .await
+        .unwrap();
+    assert!(!started);
+
+    collection
+        .finish_shard_transfer(transfer.clone(), None)
+        .await
+        .unwrap();
+
+    // The target only holds matching points, so it must never be used for reads
+    let shard_holder = collection.shards_holder.read().await;
+    let replica_set = shard_holder.get_shard(&transfer.shard_id).unwrap();
+    assert_eq!(
+        replica_set.peer_state(&OTHER_PEER_ID),
+        Some(ReplicaState::Listener),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_select_shard_transfer_method_small_shard() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+    let points = (0..100)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        collection.select_shard_transfer_method(0).await,
+        ShardTransferMethod::StreamRecords,
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_stream_records_transfer_fails_on_dropped_batch() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+
+    let peer = LossyPeer::default();
+    let channel_service = ChannelService::default();
+    channel_service
+        .id_to_address
+        .write()
+        .insert(REMOTE_PEER_ID, peer.serve().await);
+
+    let collection = collection_fixture_with_channel_service(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+        channel_service,
+    )
+    .await;
+
+    // Three batches to stream, the second one gets dropped
+    let num_points = TRANSFER_BATCH_SIZE * 5 / 2;
+    let points = (0..num_points as u64)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let transfer = ShardTransfer {
+        shard_id: 0,
+        to_shard_id: None,
+        from: THIS_PEER_ID,
+        to: REMOTE_PEER_ID,
+        sync: true,
+        method: Some(ShardTransferMethod::StreamRecords),
+        filter: None,
+    };
+
+    let (finished_sender, mut finished_receiver) = mpsc::unbounded_channel();
+    let on_finish = {
+        let finished_sender = finished_sender.clone();
+        async move {
+            let _ = finished_sender.send(true);
+        }
+    };
+    let on_error = async move {
+        let _ = finished_sender.send(false);
+    };
+
+    let started = collection
+        .start_shard_transfer(
+            transfer,
+            Box::new(NoopConsensus),
+            temp_dir.path().to_path_buf(),
+            on_finish,
+            on_error,
+        )
+        .await
+        .unwrap();
+    assert!(started);
+
+    // The transfer is reported as failed, instead of being finalized
+    let finished = timeout(Duration::from_secs(60), finished_receiver.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!finished);
+
+    // Every attempt lost the same batch, and found the target incomplete on each check
+    assert_eq!(
+        peer.dropped_batches.load(Ordering::Relaxed),
+        MAX_RETRY_COUNT
+    );
+    assert_eq!(
+        peer.count_requests.load(Ordering::Relaxed),
+        MAX_RETRY_COUNT * VERIFY_POINT_COUNT_ATTEMPTS,
+    );
+    assert_eq!(peer.points.lock().len(), num_points - TRANSFER_BATCH_SIZE);
+}
@@ -0,0 +1,224 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use common::types::{DetailsLevel, TelemetryDetail};
+use futures::future::join_all;
+use segment::data_types::vectors::VectorStructInternal;
+use tempfile::Builder;
+
+use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::UpdateStatus;
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::local_shard_fixture;
+
+fn upsert_point(id: u64) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        }]),
+    ))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_max_in_flight_updates() {
+    const MAX_IN_FLIGHT_UPDATES: usize = 3;
+    const NUM_UPDATES: u64 = 200;
+
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+    let shared_storage_config = SharedStorageConfig {
+        max_in_flight_updates: Some(MAX_IN_FLIGHT_UPDATES),
+        ..Default::default()
+    };
+    let shard = Arc::new(
+        local_shard_fixture(
+            collection_dir.path(),
+            payload_index_schema_dir.path(),
+            shared_storage_config,
+        )
+        .await,
+    );
+
+    // Keep sampling the in-flight count while the shard is flooded with updates
+    let done = Arc::new(AtomicBool::new(false));
+    let max_observed = Arc::new(AtomicUsize::new(0));
+    let sampler = tokio::spawn({
+        let shard = shard.clone();
+        let done = done.clone();
+        let max_observed = max_observed.clone();
+        async move {
+            while !done.load(Ordering::SeqCst) {
+                max_observed.fetch_max(shard.in_flight_updates(), Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    let updates = (0..NUM_UPDATES).map(|i| {
+        let shard = shard.clone();
+        tokio::spawn(async move {
+            // Mix waiting and non-waiting callers
+            shard
+                .update(upsert_point(i).into(), i % 2 == 0)
+                .await
+                .unwrap()
+        })
+    });
+
+    for (i, result) in join_all(updates).await.into_iter().enumerate() {
+        let result = result.unwrap();
+        // Waiting callers still get the result of their operation
+        if i % 2 == 0 {
+            assert_eq!(result.status, UpdateStatus::Completed);
+        }
+    }
+
+    done.store(true, Ordering::SeqCst);
+    sampler.await.unwrap();
+
+    assert!(max_observed.load(Ordering::SeqCst) <= MAX_IN_FLIGHT_UPDATES);
+
+    // Slots are released once queued operations are applied
+    let released = async {
+        while shard.in_flight_updates() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), released)
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_update_queue_telemetry() {
+    const NUM_UPDATES: u64 = 500;
+
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let shard = Arc::new(
+        local_shard_fixture(
+            collection_dir.path(),
+            payload_index_schema_dir.path(),
+            SharedStorageConfig::default(),
+        )
+        .await,
+    );
+
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level0,
+        histograms: false,
+    };
+
+    let idle = shard.get_telemetry_data(detail).update_queue;
+    assert_eq!(idle.queue_depth, 0);
+    assert_eq!(idle.ops_per_sec, 0.0);
+    assert_eq!(idle.avg_apply_latency_ms, None);
+
+    // Keep sampling the reported queue depth while the shard is flooded with updates
+    let done = Arc::new(AtomicBool::new(false));
+    let max_observed = Arc::new(AtomicUsize::new(0));
+    let sampler = tokio::spawn({
+        let shard = shard.clone();
+        let done = done.clone();
+        let max_observed = max_observed.clone();
+        async move {
+            while !done.load(Ordering::SeqCst) {
+                let queue_depth = shard.get_telemetry_data(detail).update_queue.queue_depth;
+                max_observed.fetch_max(queue_depth, Ordering::SeqCst);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    // Don't wait for updates to be applied, so they pile up in the queue
+    let updates = (0..NUM_UPDATES).map(|i| {
+        let shard = shard.clone();
+        tokio::spawn(async move { shard.update(upsert_point(i).into(), false).await.unwrap() })
+    });
+    for result in join_all(updates).await {
+        result.unwrap();
+    }
+
+    // Wait for the queue to drain
+    let drained = async {
+        while shard.in_flight_updates() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), drained)
+        .await
+        .unwrap();
+
+    done.store(true, Ordering::SeqCst);
+    sampler.await.unwrap();
+
+    let max_observed = max_observed.load(Ordering::SeqCst);
+    assert!(max_observed > 0);
+    assert!(max_observed <= NUM_UPDATES as usize);
+
+    let loaded = shard.get_telemetry_data(detail).update_queue;
+    assert_eq!(loaded.queue_depth, 0);
+    assert!(loaded.ops_per_sec > 0.0);
+    assert!(loaded.avg_apply_latency_ms.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_applied_sequence_telemetry() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let shard = local_shard_fixture(
+        collection_dir.path(),
+        payload_index_schema_dir.path(),
+        SharedStorageConfig::default(),
+    )
+    .await;
+
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level0,
+        histograms: false,
+    };
+
+    let empty = shard.get_telemetry_data(detail).update_queue;
+    assert_eq!(empty.applied_sequence, None);
+    assert_eq!(empty.last_wal_sequence, None);
+
+    let mut last_applied = None;
+    for i in 0..10 {
+        shard.update(upsert_point(i).into(), true).await.unwrap();
+
+        let telemetry = shard.get_telemetry_data(detail).update_queue;
+        assert_eq!(telemetry.applied_sequence, telemetry.last_wal_sequence);
+        assert!(telemetry.applied_sequence > last_applied);
+        last_applied = telemetry.applied_sequence;
+    }
+
+    // Operations not waited for are reported as applied once processed
+    for i in 10..20 {
+        shard.update(upsert_point(i).into(), false).await.unwrap();
+    }
+    let last_wal_sequence = shard
+        .get_telemetry_data(detail)
+        .update_queue
+        .last_wal_sequence;
+    assert!(last_wal_sequence > last_applied);
+
+    let applied = async {
+        while shard
+            .get_telemetry_data(detail)
+            .update_queue
+            .applied_sequence
+            < last_wal_sequence
+        {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    };
+    tokio::time::timeout(Duration::from_secs(10), applied)
+        .await
+        .unwrap();
+}
@@ -36,6 +36,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -53,6 +55,10 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -93,7 +99,7 @@ async fn _test_snapshot_collection(node_type: NodeType) {
 
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, &[])
         .await
         .unwrap();
 
@@ -64,6 +64,10 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        mode: None,
+        expected_versions: None,
+        version_key: None,
+        dedup: None,
     });
 }
 
@@ -72,6 +76,10 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        mode: None,
+        expected_versions: None,
+        version_key: None,
+        dedup: None,
     });
 }
 
@@ -0,0 +1,94 @@
+use api::rest::SearchRequestInternal;
+use segment::types::{Condition, FieldCondition, Filter};
+use tempfile::Builder;
+
+use crate::operations::config_diff::StrictModeConfig;
+use crate::operations::types::{CollectionError, SearchRequest, SearchRequestBatch};
+use crate::operations::verification::StrictModeVerification;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+fn search(filter: Option<Filter>) -> SearchRequest {
+    SearchRequest {
+        search_request: SearchRequestInternal {
+            vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+            filter,
+            params: None,
+            limit: 3,
+            offset: None,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        },
+        shard_key: None,
+    }
+}
+
+fn unindexed_filter() -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_match(
+        "city".parse().unwrap(),
+        "Berlin".to_string().into(),
+    )))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_batch_unindexed_filter_location() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let strict_mode_config = StrictModeConfig {
+        enabled: Some(true),
+        max_query_limit: None,
+        max_timeout: None,
+        unindexed_filtering_retrieve: Some(false),
+        unindexed_filtering_update: None,
+        search_max_hnsw_ef: None,
+        search_allow_exact: None,
+        search_max_oversampling: None,
+        max_distinct_values: None,
+        max_filter_conditions: None,
+        require_filter: None,
+        max_result_points: None,
+        truncate_result_points: None,
+        max_payload_bytes: None,
+    };
+    let mut config = create_collection_config();
+    config.strict_mode_config = Some(strict_mode_config.clone());
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+
+    // Only the last request of the batch filters by an unindexed field
+    let batch = SearchRequestBatch {
+        searches: vec![search(None), search(None), search(Some(unindexed_filter()))],
+    };
+
+    let filters = batch.indexed_filters_read().unwrap();
+    assert_eq!(filters.len(), 3);
+    assert!(filters[..2]
+        .iter()
+        .all(|filter| **filter == Filter::default()));
+
+    let Err(CollectionError::StrictMode { description }) =
+        batch.check_strict_mode(&collection, &strict_mode_config)
+    else {
+        panic!("unindexed filter in the batch is not rejected");
+    };
+    assert!(
+        description.contains("\"city\" in request 2 of the batch"),
+        "{description}",
+    );
+
+    // Single requests do not report a batch location
+    let Err(CollectionError::StrictMode { description }) =
+        search(Some(unindexed_filter())).check_strict_mode(&collection, &strict_mode_config)
+    else {
+        panic!("unindexed filter is not rejected");
+    };
+    assert!(!description.contains("batch"), "{description}");
+
+    let batch = SearchRequestBatch {
+        searches: vec![search(None), search(None)],
+    };
+    assert!(batch
+        .check_strict_mode(&collection, &strict_mode_config)
+        .is_ok());
+}
@@ -0,0 +1,129 @@
+use std::num::NonZeroU32;
+
+use segment::data_types::order_by::Direction;
+use segment::data_types::vectors::{
+    NamedVectorStruct, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::json_path::JsonPath;
+use segment::types::{ExtendedPointId, Payload, WithPayloadInterface, WithVector};
+use segment::utils::scored_point_ties::{PayloadTieBreak, TieBreak};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+const NUM_SHARDS: u32 = 3;
+const NUM_POINTS: u64 = 30;
+
+/// Payload value of the point, distinct for all points and not ordered by id
+fn rank(id: u64) -> u64 {
+    (id * 7) % NUM_POINTS
+}
+
+async fn query_ids(
+    collection: &Collection,
+    tie_break: TieBreak,
+    limit: usize,
+) -> Vec<ExtendedPointId> {
+    collection.collection_config.write().await.tie_break = Some(tie_break);
+
+    let request = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::new_from_vector(
+                Vector::Dense(vec![1.0, 0.0, 0.0, 0.0]),
+                DEFAULT_VECTOR_NAME,
+            ),
+        ))),
+        filter: None,
+        score_threshold: None,
+        limit,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let points = collection
+        .query(request, None, ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(points.len(), limit);
+    assert!(points.iter().all(|point| point.score == 1.0));
+    // Payload read for the tie break is not returned
+    assert!(points.iter().all(|point| point.payload.is_none()));
+
+    points.into_iter().map(|point| point.id).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tie_break_of_equal_scores() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.params.shard_number = NonZeroU32::new(NUM_SHARDS).unwrap();
+
+    let collection = collection_fixture(collection_dir.path(), snapshots_dir.path(), &config).await;
+
+    // All points have the same score
+    let points = (0..NUM_POINTS)
+        .map(|id| {
+            let payload: Payload = json!({ "rank": rank(id) }).into();
+            PointStruct {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![1.0, 0.0, 0.0, 0.0]).into(),
+                payload: Some(payload),
+            }
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let ids_asc: Vec<_> = (0..NUM_POINTS).map(ExtendedPointId::from).collect();
+    let ids_desc: Vec<_> = ids_asc.iter().rev().copied().collect();
+    let mut ids_by_rank: Vec<_> = (0..NUM_POINTS).collect();
+    ids_by_rank.sort_by_key(|&id| std::cmp::Reverse(rank(id)));
+    let ids_by_rank: Vec<_> = ids_by_rank.into_iter().map(ExtendedPointId::from).collect();
+
+    let rank_desc = TieBreak::Payload(PayloadTieBreak {
+        key: JsonPath::new("rank"),
+        direction: Direction::Desc,
+    });
+
+    // Limits below the number of tied points select the first points of the tie break, not the
+    // first points found in segments
+    for limit in [1, 5, NUM_POINTS as usize] {
+        assert_eq!(
+            query_ids(&collection, TieBreak::Id(Direction::Asc), limit).await,
+            ids_asc[..limit],
+            "limit: {limit}",
+        );
+        assert_eq!(
+            query_ids(&collection, TieBreak::Id(Direction::Desc), limit).await,
+            ids_desc[..limit],
+            "limit: {limit}",
+        );
+        assert_eq!(
+            query_ids(&collection, rank_desc.clone(), limit).await,
+            ids_by_rank[..limit],
+            "limit: {limit}",
+        );
+    }
+}
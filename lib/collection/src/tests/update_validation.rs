@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use segment::data_types::vectors::{Vector, VectorStructInternal};
+use segment::types::{Condition, FieldCondition, Filter, PointIdType};
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::config::CollectionConfig;
+use crate::operations::config_diff::StrictModeConfig;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::CountRequestInternal;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+fn upsert(points: Vec<PointStruct>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ))
+}
+
+async fn count_points(collection: &Collection) -> usize {
+    collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+        .count
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_validate_mixed_batch() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection = collection_fixture(
+        collection_dir.path(),
+        snapshots_path.path(),
+        &create_collection_config(),
+    )
+    .await;
+
+    let valid_point = |id: u64| PointStruct {
+        id: id.into(),
+        vector: VectorStructInternal::from(vec![1.0, 0.0, 0.0, 0.0]).into(),
+        payload: None,
+    };
+    let wrong_dimension = PointStruct {
+        id: 2.into(),
+        vector: VectorStructInternal::from(vec![1.0, 0.0, 0.0]).into(),
+        payload: None,
+    };
+    let wrong_name = PointStruct {
+        id: 3.into(),
+        vector: VectorStructInternal::Named(HashMap::from([(
+            "missing".to_string(),
+            Vector::from(vec![1.0, 0.0, 0.0, 0.0]),
+        )]))
+        .into(),
+        payload: None,
+    };
+
+    let batch = upsert(vec![
+        valid_point(1),
+        wrong_dimension.clone(),
+        wrong_name.clone(),
+        valid_point(4),
+    ]);
+
+    let errors = collection.validate_update(&batch).await.unwrap();
+    let mut invalid_ids: Vec<_> = errors.iter().map(|error| error.point_id).collect();
+    invalid_ids.sort();
+    assert_eq!(invalid_ids, vec![Some(2.into()), Some(3.into())]);
+
+    // Nothing is written by a dry run
+    assert_eq!(count_points(&collection).await, 0);
+
+    // Reported errors are the same a real apply fails with
+    for (point_id, point) in [(2, wrong_dimension), (3, wrong_name)] {
+        let point_id = PointIdType::from(point_id);
+        let reported = errors
+            .iter()
+            .find(|error| error.point_id == Some(point_id))
+            .unwrap();
+        let applied = collection
+            .update_from_client_simple(upsert(vec![point]), true, WriteOrdering::default())
+            .await
+            .unwrap_err();
+        assert_eq!(reported.error.to_string(), applied.to_string());
+    }
+
+    // Valid points pass validation and are applied
+    let valid = upsert(vec![valid_point(1), valid_point(4)]);
+    assert!(collection.validate_update(&valid).await.unwrap().is_empty());
+    collection
+        .update_from_client_simple(valid, true, WriteOrdering::default())
+        .await
+        .unwrap();
+    assert_eq!(count_points(&collection).await, 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_validate_unindexed_filtered_delete() {
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.strict_mode_config = Some(StrictModeConfig {
+        enabled: Some(true),
+        max_query_limit: None,
+        max_timeout: None,
+        unindexed_filtering_retrieve: None,
+        unindexed_filtering_update: Some(false),
+        search_max_hnsw_ef: None,
+        search_allow_exact: None,
+        search_max_oversampling: None,
+        max_distinct_values: None,
+        max_filter_conditions: None,
+        require_filter: None,
+        max_result_points: None,
+        truncate_result_points: None,
+        max_payload_bytes: None,
+    });
+    let collection =
+        collection_fixture(collection_dir.path(), snapshots_path.path(), &config).await;
+
+    let delete = CollectionUpdateOperations::PointOperation(PointOperations::DeletePointsByFilter(
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            "city".parse().unwrap(),
+            "Berlin".to_string().into(),
+        ))),
+    ));
+
+    let errors = collection.validate_update(&delete).await.unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].point_id, None);
+
+    let applied = collection
+        .update_from_client_simple(delete, true, WriteOrdering::default())
+        .await
+        .unwrap_err();
+    assert_eq!(errors[0].error.to_string(), applied.to_string());
+}
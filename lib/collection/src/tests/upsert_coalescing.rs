@@ -0,0 +1,138 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future::join_all;
+use segment::data_types::vectors::VectorStructInternal;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::{CollectionError, CountRequestInternal, UpdateStatus};
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture_with_storage_config, create_collection_config};
+
+async fn coalescing_collection_fixture(
+    collection_dir: &Path,
+    snapshots_path: &Path,
+    max_batch: usize,
+) -> Collection {
+    let shared_storage_config = SharedStorageConfig {
+        upsert_coalesce_max_batch: Some(max_batch),
+        upsert_coalesce_max_delay: Duration::from_millis(50),
+        ..Default::default()
+    };
+
+    collection_fixture_with_storage_config(
+        collection_dir,
+        snapshots_path,
+        &create_collection_config(),
+        Arc::new(shared_storage_config),
+    )
+    .await
+}
+
+fn single_upsert(id: u64, vector: Vec<f32>) -> CollectionUpdateOperations {
+    CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vector).into(),
+            payload: None,
+        }]),
+    ))
+}
+
+async fn count_points(collection: &Collection) -> usize {
+    collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+        .count
+}
+
+async fn wal_entries(collection: &Collection) -> u64 {
+    let shard_holder = collection.shards_holder.read().await;
+    let replica_set = shard_holder.get_shard(&0).unwrap();
+    replica_set.wal_last_index().await.unwrap() + 1
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_coalesced_upserts_share_wal_entries() {
+    const NUM_UPSERTS: u64 = 200;
+    const MAX_BATCH: usize = 50;
+
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection =
+        coalescing_collection_fixture(collection_dir.path(), snapshots_path.path(), MAX_BATCH)
+            .await;
+
+    let upserts = (0..NUM_UPSERTS).map(|i| {
+        let operation = single_upsert(i, vec![i as f32, 0.0, 0.0, 0.0]);
+        collection.update_from_client_simple(operation, i % 2 == 0, WriteOrdering::Weak)
+    });
+
+    for result in join_all(upserts).await {
+        let result = result.unwrap();
+        assert!(matches!(
+            result.status,
+            UpdateStatus::Completed | UpdateStatus::Acknowledged,
+        ));
+    }
+
+    // Every point is stored
+    assert_eq!(count_points(&collection).await, NUM_UPSERTS as usize);
+
+    // But in far fewer WAL entries than upserts
+    let wal_entries = wal_entries(&collection).await;
+    assert!(wal_entries >= NUM_UPSERTS / MAX_BATCH as u64);
+    assert!(wal_entries < NUM_UPSERTS / 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_bad_point_fails_only_its_upsert() {
+    const NUM_UPSERTS: u64 = 10;
+    const BAD_POINT: u64 = 3;
+
+    let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let collection =
+        coalescing_collection_fixture(collection_dir.path(), snapshots_path.path(), 50).await;
+
+    let upserts = (0..NUM_UPSERTS).map(|i| {
+        // Collection has vectors of dimension 4
+        let vector = if i == BAD_POINT {
+            vec![i as f32, 0.0]
+        } else {
+            vec![i as f32, 0.0, 0.0, 0.0]
+        };
+        collection.update_from_client_simple(single_upsert(i, vector), true, WriteOrdering::Weak)
+    });
+
+    for (i, result) in (0..NUM_UPSERTS).zip(join_all(upserts).await) {
+        if i == BAD_POINT {
+            assert!(
+                matches!(result, Err(CollectionError::BadInput { .. })),
+                "{result:?}",
+            );
+        } else {
+            assert_eq!(result.unwrap().status, UpdateStatus::Completed);
+        }
+    }
+
+    // All good points are written in a single WAL entry
+    assert_eq!(count_points(&collection).await, NUM_UPSERTS as usize - 1);
+    assert_eq!(wal_entries(&collection).await, 1);
+}
@@ -0,0 +1,147 @@
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Payload, PointIdType, WithPayloadInterface, WithVector};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::collection::Collection;
+use crate::operations::point_ops::{
+    DedupUpsert, PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{PointRequestInternal, UpdateResult};
+use crate::operations::vector_dedup::VectorDedup;
+use crate::operations::CollectionUpdateOperations;
+use crate::tests::fixtures::{collection_fixture, create_collection_config};
+
+fn point(id: u64, vector: [f32; 4], payload: Option<Payload>) -> PointStruct {
+    PointStruct {
+        id: id.into(),
+        vector: VectorStructInternal::from(vector.to_vec()).into(),
+        payload,
+    }
+}
+
+async fn update(collection: &Collection, operation: PointOperations) -> UpdateResult {
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(operation),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap()
+}
+
+async fn upsert(collection: &Collection, points: Vec<PointStruct>) {
+    let points = PointInsertOperationsInternal::PointsList(points);
+    update(collection, PointOperations::UpsertPoints(points)).await;
+}
+
+async fn dedup_upsert(
+    collection: &Collection,
+    points: Vec<PointStruct>,
+    policy: VectorDedup,
+) -> Option<usize> {
+    let points = PointInsertOperationsInternal::PointsList(points);
+    let operation = PointOperations::UpsertPointsDedup(DedupUpsert { points, policy });
+    update(collection, operation).await.deduplicated
+}
+
+async fn stored_payload(collection: &Collection, id: u64) -> Option<Payload> {
+    let request = PointRequestInternal {
+        ids: vec![PointIdType::from(id)],
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(false),
+    };
+    let records = collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    records
+        .into_iter()
+        .next()
+        .map(|record| record.payload.unwrap_or_default())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_dedup_stored_vectors() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_dir = Builder::new().prefix("test_snapshots").tempdir().unwrap();
+    let config = create_collection_config();
+    let collection = collection_fixture(collection_dir.path(), snapshots_dir.path(), &config).await;
+
+    // More stored points than any search limit, all with the same dot product score as the
+    // duplicate, but with other vectors
+    let decoys = (1..=20)
+        .map(|id| point(id, [1.0, id as f32, 0.0, 0.0], None))
+        .collect();
+    upsert(&collection, decoys).await;
+    upsert(&collection, vec![point(100, [1.0, 0.0, 0.0, 0.0], None)]).await;
+
+    let deduplicated = dedup_upsert(
+        &collection,
+        vec![
+            point(200, [1.0, 0.0, 0.0, 0.0], None),
+            point(201, [0.0, 0.0, 1.0, 0.0], None),
+        ],
+        VectorDedup::Skip,
+    )
+    .await;
+    assert_eq!(deduplicated, Some(1));
+    assert!(stored_payload(&collection, 200).await.is_none());
+    assert!(stored_payload(&collection, 201).await.is_some());
+
+    // Points written after the hashes were built are found too
+    let deduplicated = dedup_upsert(
+        &collection,
+        vec![point(202, [0.0, 0.0, 1.0, 0.0], None)],
+        VectorDedup::Skip,
+    )
+    .await;
+    assert_eq!(deduplicated, Some(1));
+    assert!(stored_payload(&collection, 202).await.is_none());
+
+    // Payload of a duplicate is merged into the stored point
+    let payload: Payload = json!({ "source": "merged" }).into();
+    let deduplicated = dedup_upsert(
+        &collection,
+        vec![point(203, [1.0, 0.0, 0.0, 0.0], Some(payload.clone()))],
+        VectorDedup::Merge,
+    )
+    .await;
+    assert_eq!(deduplicated, Some(1));
+    assert!(stored_payload(&collection, 203).await.is_none());
+    assert_eq!(stored_payload(&collection, 100).await, Some(payload));
+
+    // Deleted points and points with changed vectors are no duplicates anymore
+    update(
+        &collection,
+        PointOperations::DeletePoints {
+            ids: vec![100.into()],
+        },
+    )
+    .await;
+    upsert(&collection, vec![point(201, [0.0, 0.0, 0.0, 1.0], None)]).await;
+
+    let deduplicated = dedup_upsert(
+        &collection,
+        vec![
+            point(204, [1.0, 0.0, 0.0, 0.0], None),
+            point(205, [0.0, 0.0, 1.0, 0.0], None),
+        ],
+        VectorDedup::Skip,
+    )
+    .await;
+    assert_eq!(deduplicated, Some(0));
+    assert!(stored_payload(&collection, 204).await.is_some());
+    assert!(stored_payload(&collection, 205).await.is_some());
+
+    // A stored point overwritten by the upsert is not a duplicate of itself
+    let deduplicated = dedup_upsert(
+        &collection,
+        vec![point(204, [1.0, 0.0, 0.0, 0.0], None)],
+        VectorDedup::Skip,
+    )
+    .await;
+    assert_eq!(deduplicated, Some(0));
+}
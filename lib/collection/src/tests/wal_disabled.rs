@@ -0,0 +1,107 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use common::cpu::CpuBudget;
+use tempfile::Builder;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use crate::config::CollectionConfig;
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::*;
+
+async fn load_shard(
+    shard_path: &Path,
+    config: &CollectionConfig,
+    payload_index_schema_dir: &Path,
+) -> LocalShard {
+    let payload_index_schema_file = payload_index_schema_dir.join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    LocalShard::load(
+        0,
+        "test".to_string(),
+        shard_path,
+        Arc::new(RwLock::new(config.clone())),
+        config.optimizer_config.clone(),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        Handle::current(),
+        Handle::current(),
+        CpuBudget::default(),
+    )
+    .await
+    .unwrap()
+}
+
+async fn points_count(shard: &LocalShard) -> usize {
+    shard.info().await.unwrap().points_count.unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wal_disabled_persists_on_clean_reload() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+
+    let mut config = create_collection_config();
+    config.wal_config.wal_disabled = true;
+    // Data must be persisted by the shutdown, not by the periodic flush
+    config.optimizer_config.flush_interval_sec = 3600;
+
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        "test".to_string(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        Handle::current(),
+        Handle::current(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation().into(), true).await.unwrap();
+    assert_eq!(points_count(&shard).await, 5);
+    drop(shard);
+
+    // Nothing was written to the WAL
+    let wal_path = LocalShard::wal_path(collection_dir.path());
+    assert_eq!(std::fs::read_dir(&wal_path).unwrap().count(), 0);
+
+    let shard = load_shard(
+        collection_dir.path(),
+        &config,
+        payload_index_schema_dir.path(),
+    )
+    .await;
+    assert_eq!(points_count(&shard).await, 5);
+
+    // Operations after reload are numbered after the persisted ones, so they are applied
+    shard
+        .update(delete_point_operation(4).into(), true)
+        .await
+        .unwrap();
+    assert_eq!(points_count(&shard).await, 4);
+    drop(shard);
+
+    let shard = load_shard(
+        collection_dir.path(),
+        &config,
+        payload_index_schema_dir.path(),
+    )
+    .await;
+    assert_eq!(points_count(&shard).await, 4);
+    drop(shard);
+
+    assert_eq!(std::fs::read_dir(&wal_path).unwrap().count(), 0);
+}
@@ -5,11 +5,16 @@ use segment::types::{PayloadFieldSchema, PayloadSchemaType};
 use tempfile::Builder;
 use tokio::runtime::Handle;
 use tokio::sync::RwLock;
+use wal::Wal;
 
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::OperationWithClockTag;
 use crate::save_on_disk::SaveOnDisk;
+use crate::shards::local_shard::wal_quarantine::read_quarantined_operations;
 use crate::shards::local_shard::LocalShard;
 use crate::shards::shard_trait::ShardOperation;
 use crate::tests::fixtures::*;
+use crate::wal::SerdeWal;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_delete_from_indexed_payload() {
@@ -120,3 +125,93 @@ async fn test_delete_from_indexed_payload() {
     assert_eq!(number_of_indexed_points, 4);
     assert_eq!(number_of_indexed_points_after_load, 3);
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_quarantine_poison_wal_operation() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let config = create_collection_config();
+
+    let collection_name = "test".to_string();
+
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        collection_name.clone(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema.clone(),
+        current_runtime.clone(),
+        current_runtime.clone(),
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    shard.update(upsert_operation().into(), true).await.unwrap();
+
+    drop(shard);
+
+    // Append an operation, which can never be read, followed by a valid one
+    let wal_path = LocalShard::wal_path(collection_dir.path());
+    let poison_op_num = {
+        let mut wal = Wal::with_options(&wal_path, &(&config.wal_config).into()).unwrap();
+        let op_num = wal.append(&[0xffu8; 16]).unwrap();
+        wal.flush_open_segment().unwrap();
+        op_num
+    };
+    {
+        let mut wal: SerdeWal<OperationWithClockTag> =
+            SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into()).unwrap();
+        wal.write(&delete_point_operation(1).into()).unwrap();
+        wal.flush().unwrap();
+    }
+
+    let load_shard = |shared_storage_config: SharedStorageConfig| {
+        LocalShard::load(
+            0,
+            collection_name.clone(),
+            collection_dir.path(),
+            Arc::new(RwLock::new(config.clone())),
+            config.optimizer_config.clone(),
+            Arc::new(shared_storage_config),
+            payload_index_schema.clone(),
+            current_runtime.clone(),
+            current_runtime.clone(),
+            CpuBudget::default(),
+        )
+    };
+
+    // Poison operation fails the load by default
+    assert!(load_shard(SharedStorageConfig::default()).await.is_err());
+    assert!(read_quarantined_operations(collection_dir.path())
+        .unwrap()
+        .is_empty());
+
+    // In lenient mode it is quarantined, and the operations around it are applied
+    let shard = load_shard(SharedStorageConfig {
+        lenient_wal_recovery: true,
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    let info = shard.info().await.unwrap();
+    assert_eq!(info.points_count, 4);
+
+    // Shard is flagged to be recovered from another replica
+    assert!(shard.has_quarantined_operations());
+
+    let quarantined = read_quarantined_operations(collection_dir.path()).unwrap();
+    assert_eq!(quarantined.len(), 1);
+    assert_eq!(quarantined[0].op_num, poison_op_num);
+    assert!(quarantined[0].operation.is_none());
+}
@@ -9,6 +9,7 @@ use common::panic;
 use itertools::Itertools;
 use log::{debug, error, info, trace, warn};
 use parking_lot::Mutex;
+use rand::Rng;
 use segment::common::operation_error::OperationResult;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::types::SeqNumberType;
@@ -17,7 +18,7 @@ use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::{oneshot, Mutex as TokioMutex};
 use tokio::task::{self, JoinHandle};
 use tokio::time::error::Elapsed;
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
@@ -29,9 +30,13 @@ use crate::collection_manager::optimizers::{Tracker, TrackerLog, TrackerStatus};
 use crate::common::stoppable_task::{spawn_stoppable, StoppableTaskHandle};
 use crate::config::CollectionParams;
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::types::{CollectionError, CollectionResult, OptimizerError};
 use crate::operations::CollectionUpdateOperations;
 use crate::save_on_disk::SaveOnDisk;
+use crate::shards::local_shard::retrieve_cache::RetrieveCache;
+use crate::shards::local_shard::update_limiter::UpdatePermit;
+use crate::shards::local_shard::update_stats::UpdateStats;
+use crate::shards::local_shard::vector_hashes::VectorHashes;
 use crate::shards::local_shard::LocalShardClocks;
 use crate::wal::WalError;
 use crate::wal_delta::LockedWal;
@@ -54,6 +59,8 @@ pub struct OperationData {
     pub wait: bool,
     /// Callback notification channel
     pub sender: Option<oneshot::Sender<CollectionResult<usize>>>,
+    /// Keeps the operation counted as in-flight until it is applied
+    pub in_flight_permit: UpdatePermit,
 }
 
 /// Signal, used to inform Updater process
@@ -88,6 +95,12 @@ pub struct UpdateHandler {
     pub optimizers: Arc<Vec<Arc<Optimizer>>>,
     /// Log of optimizer statuses
     optimizers_log: Arc<Mutex<TrackerLog>>,
+    /// Rate and latency of applied update operations
+    update_stats: Arc<Mutex<UpdateStats>>,
+    /// Cache of retrieved points, invalidated by applied update operations
+    retrieve_cache: Arc<RetrieveCache>,
+    /// Hashes of stored vectors, updated by applied update operations
+    vector_hashes: Arc<VectorHashes>,
     /// Global CPU budget in number of cores for all optimization tasks.
     /// Assigns CPU permits to tasks to limit overall resource utilization.
     optimizer_cpu_budget: CpuBudget,
@@ -129,6 +142,9 @@ impl UpdateHandler {
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
+        update_stats: Arc<Mutex<UpdateStats>>,
+        retrieve_cache: Arc<RetrieveCache>,
+        vector_hashes: Arc<VectorHashes>,
         optimizer_cpu_budget: CpuBudget,
         runtime_handle: Handle,
         segments: LockedSegmentHolder,
@@ -146,6 +162,9 @@ impl UpdateHandler {
             update_worker: None,
             optimizer_worker: None,
             optimizers_log,
+            update_stats,
+            retrieve_cache,
+            vector_hashes,
             optimizer_cpu_budget,
             flush_worker: None,
             flush_stop: None,
@@ -175,12 +194,18 @@ impl UpdateHandler {
             self.max_optimization_threads,
             self.has_triggered_optimizers.clone(),
             self.payload_index_schema.clone(),
+            self.shared_storage_config.optimizer_jitter,
+            self.retrieve_cache.clone(),
+            self.vector_hashes.clone(),
         )));
         self.update_worker = Some(self.runtime_handle.spawn(Self::update_worker_fn(
             update_receiver,
             tx,
             self.wal.clone(),
             self.segments.clone(),
+            self.update_stats.clone(),
+            self.retrieve_cache.clone(),
+            self.vector_hashes.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
@@ -235,7 +260,12 @@ impl UpdateHandler {
 
     /// Checks if there are any failed operations.
     /// If so - attempts to re-apply all failed operations.
-    async fn try_recover(segments: LockedSegmentHolder, wal: LockedWal) -> CollectionResult<usize> {
+    async fn try_recover(
+        segments: LockedSegmentHolder,
+        wal: LockedWal,
+        retrieve_cache: &RetrieveCache,
+        vector_hashes: &VectorHashes,
+    ) -> CollectionResult<usize> {
         // Try to re-apply everything starting from the first failed operation
         let first_failed_operation_option = segments.read().failed_operation.iter().cloned().min();
         match first_failed_operation_option {
@@ -243,7 +273,11 @@ impl UpdateHandler {
             Some(first_failed_op) => {
                 let wal_lock = wal.lock();
                 for (op_num, operation) in wal_lock.read(first_failed_op) {
-                    CollectionUpdater::update(&segments, op_num, operation.operation)?;
+                    let _cache_guard = retrieve_cache.begin_update(&operation.operation);
+                    let written_vectors = VectorHashes::written_vectors(&operation.operation);
+                    let result = CollectionUpdater::update(&segments, op_num, operation.operation);
+                    vector_hashes.update(&segments.read(), written_vectors);
+                    result?;
                 }
             }
         };
@@ -303,7 +337,18 @@ impl UpdateHandler {
                     optimizer.name(),
                 );
 
+                // Optimizations rebuilding segments requested to be reindexed are registered in
+                // the segment holder, to be stopped if the reindex is cancelled
+                let is_reindex = optimizer.rebuilds_reindex_requested() && {
+                    let segments_read = segments.read();
+                    nonoptimal_segment_ids
+                        .iter()
+                        .any(|&segment_id| segments_read.is_reindex_requested(segment_id))
+                };
+                let reindex_segments = is_reindex.then(|| segments.clone());
+
                 let optimizer = optimizer.clone();
+                let optimizer_name = optimizer.name().to_string();
                 let optimizers_log = optimizers_log.clone();
                 let segments = segments.clone();
                 let nsi = nonoptimal_segment_ids.clone();
@@ -323,13 +368,14 @@ impl UpdateHandler {
                             // Optimize and handle result
                             match optimizer.as_ref().optimize(
                                 segments.clone(),
-                                nsi,
+                                nsi.clone(),
                                 permit,
                                 stopped,
                             ) {
                                 // Perform some actions when optimization if finished
                                 Ok(result) => {
                                     tracker_handle.update(TrackerStatus::Done);
+                                    segments.write().report_optimizer_success();
                                     callback(result);
                                     result
                                 }
@@ -342,18 +388,20 @@ impl UpdateHandler {
                                         false
                                     }
                                     _ => {
-                                        segments.write().report_optimizer_error(error.clone());
-
                                         // Error of the optimization can not be handled by API user
-                                        // It is only possible to fix after full restart,
-                                        // so the best available action here is to stop whole
-                                        // optimization thread and log the error
+                                        // The failure is reported in the collection status, which
+                                        // degrades if optimizations keep failing
                                         log::error!("Optimization error: {error}");
 
+                                        segments.write().report_optimizer_failure(
+                                            OptimizerError::from(error.clone())
+                                                .with_optimizer(optimizer.name(), nsi),
+                                        );
+
                                         tracker_handle
                                             .update(TrackerStatus::Error(error.to_string()));
 
-                                        panic!("Optimization error: {error}");
+                                        false
                                     }
                                 },
                             }
@@ -369,13 +417,19 @@ impl UpdateHandler {
                              {separator}{message}"
                         );
 
-                        segments
-                            .write()
-                            .report_optimizer_error(CollectionError::service_error(format!(
+                        segments.write().report_optimizer_failure(
+                            OptimizerError::new(format!(
                                 "Optimization task panicked{separator}{message}"
-                            )));
+                            ))
+                            .with_optimizer(optimizer_name, nonoptimal_segment_ids),
+                        );
                     })),
                 );
+                if let Some(reindex_segments) = reindex_segments {
+                    reindex_segments
+                        .write()
+                        .register_reindex_optimization(handle.stop_flag());
+                }
                 handles.push(handle);
             }
         }
@@ -512,6 +566,9 @@ impl UpdateHandler {
         max_handles: Option<usize>,
         has_triggered_optimizers: Arc<AtomicBool>,
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
+        optimizer_jitter: Duration,
+        retrieve_cache: Arc<RetrieveCache>,
+        vector_hashes: Arc<VectorHashes>,
     ) {
         let max_handles = max_handles.unwrap_or(usize::MAX);
         let max_indexing_threads = optimizers
@@ -522,13 +579,36 @@ impl UpdateHandler {
         // Asynchronous task to trigger optimizers once CPU budget is available again
         let mut cpu_available_trigger: Option<JoinHandle<()>> = None;
 
+        // Point in time at which optimizers delayed by the random start jitter are triggered
+        let mut jitter_deadline: Option<Instant> = None;
+
         loop {
-            let receiver = timeout(OPTIMIZER_CLEANUP_INTERVAL, receiver.recv());
+            // Wake up no later than the jitter deadline
+            let wait = jitter_deadline.map_or(OPTIMIZER_CLEANUP_INTERVAL, |deadline| {
+                min(
+                    deadline.saturating_duration_since(Instant::now()),
+                    OPTIMIZER_CLEANUP_INTERVAL,
+                )
+            });
+            let receiver = timeout(wait, receiver.recv());
             let result = receiver.await;
 
             // Always clean up on any signal
             Self::cleanup_optimization_handles(optimization_handles.clone()).await;
 
+            // Check on every wake-up whether the jitter delay has passed, so that the next
+            // optimization check is not delayed again
+            let jitter_elapsed = jitter_deadline.is_some_and(|deadline| deadline <= Instant::now());
+            if jitter_elapsed {
+                jitter_deadline = None;
+            }
+
+            let result = match result {
+                // Jitter delay passed, trigger optimizers as with Nop operation
+                Err(Elapsed { .. }) if jitter_elapsed => Ok(Some(OptimizerSignal::Nop)),
+                result => result,
+            };
+
             match result {
                 // Channel closed or stop signal
                 Ok(None | Some(OptimizerSignal::Stop)) => break,
@@ -561,9 +641,14 @@ impl UpdateHandler {
                         continue;
                     }
 
-                    if Self::try_recover(segments.clone(), wal.clone())
-                        .await
-                        .is_err()
+                    if Self::try_recover(
+                        segments.clone(),
+                        wal.clone(),
+                        &retrieve_cache,
+                        &vector_hashes,
+                    )
+                    .await
+                    .is_err()
                     {
                         continue;
                     }
@@ -599,6 +684,22 @@ impl UpdateHandler {
                         continue;
                     }
 
+                    // Delay the start of optimizations by a random jitter if none are running, so
+                    // that optimizations of many collections triggered at once are staggered
+                    // Signals received meanwhile are handled once the single deadline has passed
+                    if !jitter_elapsed
+                        && !optimizer_jitter.is_zero()
+                        && optimization_handles.lock().await.is_empty()
+                    {
+                        if jitter_deadline.is_none() {
+                            let delay =
+                                rand::thread_rng().gen_range(Duration::ZERO..=optimizer_jitter);
+                            log::trace!("Delaying optimization checks by {delay:?}");
+                            jitter_deadline = Some(Instant::now() + delay);
+                        }
+                        continue;
+                    }
+
                     Self::process_optimization(
                         optimizers.clone(),
                         segments.clone(),
@@ -619,6 +720,9 @@ impl UpdateHandler {
         optimize_sender: Sender<OptimizerSignal>,
         wal: LockedWal,
         segments: LockedSegmentHolder,
+        update_stats: Arc<Mutex<UpdateStats>>,
+        retrieve_cache: Arc<RetrieveCache>,
+        vector_hashes: Arc<VectorHashes>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -627,6 +731,7 @@ impl UpdateHandler {
                     operation,
                     sender,
                     wait,
+                    in_flight_permit,
                 }) => {
                     let flush_res = if wait {
                         wal.lock().flush().map_err(|err| {
@@ -638,8 +743,17 @@ impl UpdateHandler {
                         Ok(())
                     };
 
+                    let apply_start = Instant::now();
+                    // Points retrieved until the operation is applied are not cached
+                    let cache_guard = retrieve_cache.begin_update(&operation);
+                    let written_vectors = VectorHashes::written_vectors(&operation);
                     let operation_result = flush_res
                         .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
+                    vector_hashes.update(&segments.read(), written_vectors);
+                    drop(cache_guard);
+                    update_stats
+                        .lock()
+                        .record_applied(op_num, apply_start.elapsed());
 
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
@@ -658,6 +772,8 @@ impl UpdateHandler {
                             );
                         });
                     };
+
+                    drop(in_flight_permit);
                 }
                 UpdateSignal::Stop => {
                     optimize_sender
@@ -711,7 +827,7 @@ impl UpdateHandler {
             trace!("Attempting flushing");
             let wal_flash_job = wal.lock().flush_async();
 
-            if let Err(err) = wal_flash_job.join() {
+            if let Some(Err(err)) = wal_flash_job.map(|job| job.join()) {
                 error!("Failed to flush wal: {:?}", err);
                 segments
                     .write()
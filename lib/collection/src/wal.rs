@@ -1,8 +1,9 @@
 use std::fmt::Debug;
 use std::marker::PhantomData;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::result;
 use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use io::file_operations::{atomic_save_json, read_json};
 use serde::de::DeserializeOwned;
@@ -10,6 +11,8 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use wal::{Wal, WalOptions};
 
+use crate::config::WalFsyncPolicy;
+
 #[derive(Error, Debug)]
 #[error("{0}")]
 pub enum WalError {
@@ -19,6 +22,8 @@ pub enum WalError {
     WriteWalError(String),
     #[error("Can't truncate WAL: {0}")]
     TruncateWalError(String),
+    #[error("Can't read WAL: {0}")]
+    ReadWalError(String),
     #[error("Operation rejected by WAL for old clock")]
     ClockRejected,
 }
@@ -63,13 +68,25 @@ impl WalState {
 /// Each stored record is enumerated with sequential number.
 /// Sequential number can be used to read stored records starting from some IDs,
 /// for removing old, no longer required, records.
+///
+/// A disabled WAL only enumerates records, without storing them, see [`SerdeWal::new_disabled`].
 #[derive(Debug)]
 pub struct SerdeWal<R> {
     record: PhantomData<R>,
-    wal: Wal,
+    /// Underlying WAL, `None` if the WAL is disabled
+    wal: Option<Wal>,
+    path: PathBuf,
+    /// Sequential number of the next record written to a disabled WAL
+    next_index: u64,
     options: WalOptions,
     /// First index of our logical WAL.
     first_index: Option<u64>,
+    fsync_policy: WalFsyncPolicy,
+    last_fsync: Instant,
+    /// Number of records written since the last fsync
+    unsynced_records: u64,
+    /// Number of fsyncs of the underlying WAL since it was opened
+    fsync_count: u64,
 }
 
 const FIRST_INDEX_FILE: &str = "first-index";
@@ -97,19 +114,94 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
 
         Ok(SerdeWal {
             record: PhantomData,
-            wal,
+            path: wal.path().to_owned(),
+            next_index: 0,
+            wal: Some(wal),
             options: wal_options,
             first_index,
+            fsync_policy: WalFsyncPolicy::default(),
+            last_fsync: Instant::now(),
+            unsynced_records: 0,
+            fsync_count: 0,
         })
     }
 
-    /// Write a record to the WAL but does guarantee durability.
+    /// Create a disabled WAL, which does not store records.
+    ///
+    /// Written records are only enumerated, starting from `next_index`. Nothing is ever read
+    /// back, so records not yet persisted elsewhere are lost on a crash.
+    pub fn new_disabled(
+        dir: &str,
+        wal_options: WalOptions,
+        next_index: u64,
+    ) -> Result<SerdeWal<R>> {
+        std::fs::create_dir_all(dir).map_err(|err| {
+            WalError::InitWalError(format!("failed to create WAL directory: {err}"))
+        })?;
+
+        Ok(SerdeWal {
+            record: PhantomData,
+            wal: None,
+            path: PathBuf::from(dir),
+            next_index,
+            options: wal_options,
+            first_index: None,
+            fsync_policy: WalFsyncPolicy::default(),
+            last_fsync: Instant::now(),
+            unsynced_records: 0,
+            fsync_count: 0,
+        })
+    }
+
+    pub fn is_disabled(&self) -> bool {
+        self.wal.is_none()
+    }
+
+    pub fn with_fsync_policy(mut self, fsync_policy: WalFsyncPolicy) -> Self {
+        self.fsync_policy = fsync_policy;
+        self
+    }
+
+    /// Write a record to the WAL.
+    ///
+    /// The record is only guaranteed to be durable, if the fsync policy requires an fsync now.
+    /// Otherwise durability is guaranteed after the next [`SerdeWal::flush`].
     pub fn write(&mut self, entity: &R) -> Result<u64> {
+        let Some(wal) = &mut self.wal else {
+            let index = self.next_index;
+            self.next_index += 1;
+            return Ok(index);
+        };
+
         // ToDo: Replace back to faster rmp, once this https://github.com/serde-rs/serde/issues/2055 solved
         let binary_entity = serde_cbor::to_vec(&entity).unwrap();
-        self.wal
+        let index = wal
             .append(&binary_entity)
-            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
+            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+        self.unsynced_records += 1;
+
+        let fsync = match self.fsync_policy {
+            WalFsyncPolicy::EveryOp => true,
+            WalFsyncPolicy::Interval(interval_ms) => {
+                self.last_fsync.elapsed() >= Duration::from_millis(interval_ms)
+            }
+            WalFsyncPolicy::OnFlush => false,
+        };
+        if fsync {
+            self.flush()?;
+        }
+
+        Ok(index)
+    }
+
+    /// Number of records written since the last fsync, which may be lost on a crash.
+    pub fn unsynced_records(&self) -> u64 {
+        self.unsynced_records
+    }
+
+    /// Number of fsyncs of the underlying WAL, including scheduled ones, since it was opened.
+    pub fn fsync_count(&self) -> u64 {
+        self.fsync_count
     }
 
     pub fn read_all(
@@ -128,10 +220,19 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
         // concise `from..=self.last_index()`, because if the WAL is empty, `Wal::last_index`
         // returns `Wal::first_index`, so we end up with `1..=1` instead of an empty range. 😕
 
-        let to = self.first_index() + self.len(false);
+        // Disabled WAL has no records to read, even if indices were assigned
+        let to = if self.is_disabled() {
+            from
+        } else {
+            self.first_index() + self.len(false)
+        };
 
         (from..to).map(move |idx| {
-            let record_bin = self.wal.entry(idx).expect("Can't read entry from WAL");
+            let record_bin = self
+                .wal
+                .as_ref()
+                .and_then(|wal| wal.entry(idx))
+                .expect("Can't read entry from WAL");
 
             let record: R = serde_cbor::from_slice(&record_bin)
                 .or_else(|_err| rmp_serde::from_slice(&record_bin))
@@ -141,16 +242,33 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
         })
     }
 
+    /// Read a single record, returning an error instead of panicking if the entry is missing or
+    /// can't be deserialized.
+    pub fn try_read_entry(&self, idx: u64) -> Result<R> {
+        let record_bin = self
+            .wal
+            .as_ref()
+            .and_then(|wal| wal.entry(idx))
+            .ok_or_else(|| WalError::ReadWalError(format!("entry {idx} not found")))?;
+
+        serde_cbor::from_slice(&record_bin)
+            .or_else(|_err| rmp_serde::from_slice(&record_bin))
+            .map_err(|err| WalError::ReadWalError(format!("can't deserialize entry {idx}: {err}")))
+    }
+
     pub fn is_empty(&self) -> bool {
         self.len(false) == 0
     }
 
     pub fn len(&self, with_acknowledged: bool) -> u64 {
+        let Some(wal) = &self.wal else {
+            return 0;
+        };
+
         if with_acknowledged {
-            self.wal.num_entries()
+            wal.num_entries()
         } else {
-            self.wal
-                .num_entries()
+            wal.num_entries()
                 .saturating_sub(self.truncated_prefix_entries_num())
         }
     }
@@ -172,7 +290,7 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     //
     // `truncated_prefix_entries_num` returns the length of the "logically deleted" part of the WAL.
     fn truncated_prefix_entries_num(&self) -> u64 {
-        self.first_index().saturating_sub(self.wal.first_index())
+        self.first_index().saturating_sub(self.first_closed_index())
     }
 
     /// Inform WAL, that records older than `until_index` are no longer required.
@@ -182,18 +300,17 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     ///
     /// * `until_index` - the newest no longer required record sequence number
     pub(super) fn ack(&mut self, until_index: u64) -> Result<()> {
+        let Some(wal) = &mut self.wal else {
+            return Ok(());
+        };
+
         // Truncate WAL
-        self.wal
-            .prefix_truncate(until_index)
+        wal.prefix_truncate(until_index)
             .map_err(|err| WalError::TruncateWalError(format!("{err:?}")))?;
 
         // Acknowledge index should not decrease
-        let minimal_first_index = self.first_index.unwrap_or(self.wal.first_index());
-        let new_first_index = Some(
-            until_index
-                .max(minimal_first_index)
-                .min(self.wal.last_index()),
-        );
+        let minimal_first_index = self.first_index.unwrap_or(wal.first_index());
+        let new_first_index = Some(until_index.max(minimal_first_index).min(wal.last_index()));
 
         // Update current `first_index`
         if self.first_index != new_first_index {
@@ -223,17 +340,28 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     }
 
     pub fn flush(&mut self) -> Result<()> {
-        self.wal
-            .flush_open_segment()
-            .map_err(|err| WalError::WriteWalError(format!("{err:?}")))
+        if let Some(wal) = &mut self.wal {
+            wal.flush_open_segment()
+                .map_err(|err| WalError::WriteWalError(format!("{err:?}")))?;
+            self.fsync_count += 1;
+        }
+        self.last_fsync = Instant::now();
+        self.unsynced_records = 0;
+        Ok(())
     }
 
-    pub fn flush_async(&mut self) -> JoinHandle<std::io::Result<()>> {
-        self.wal.flush_open_segment_async()
+    /// Schedule flush of written records, returns `None` if the WAL is disabled
+    pub fn flush_async(&mut self) -> Option<JoinHandle<std::io::Result<()>>> {
+        // Written records are considered synced as soon as the flush is scheduled
+        self.last_fsync = Instant::now();
+        self.unsynced_records = 0;
+        let wal = self.wal.as_mut()?;
+        self.fsync_count += 1;
+        Some(wal.flush_open_segment_async())
     }
 
     pub fn path(&self) -> &Path {
-        self.wal.path()
+        &self.path
     }
 
     /// First index that we still have in the first closed segment.
@@ -241,7 +369,10 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
     /// If the index is lower than `first_index`, it means we have already acknowledged it but we
     /// are still holding it in a closed segment until it gets truncated.
     pub fn first_closed_index(&self) -> u64 {
-        self.wal.first_index()
+        match &self.wal {
+            Some(wal) => wal.first_index(),
+            None => self.next_index,
+        }
     }
 
     /// First index that is in our logical WAL, right after the last acknowledged operation.
@@ -252,7 +383,10 @@ impl<R: DeserializeOwned + Serialize + Debug> SerdeWal<R> {
 
     /// Last index that is still available in logical WAL.
     pub fn last_index(&self) -> u64 {
-        self.wal.last_index()
+        match &self.wal {
+            Some(wal) => wal.last_index(),
+            None => self.next_index.saturating_sub(1),
+        }
     }
 
     pub fn segment_capacity(&self) -> usize {
@@ -323,4 +457,114 @@ mod tests {
             }
         }
     }
+
+    /// Write records, return numbers of unsynced records and of fsyncs after each write
+    fn write_records(
+        dir: &Path,
+        fsync_policy: WalFsyncPolicy,
+        count: usize,
+    ) -> (Vec<u64>, Vec<u64>) {
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.to_str().unwrap(), WalOptions::default())
+                .unwrap()
+                .with_fsync_policy(fsync_policy);
+
+        let mut unsynced = Vec::new();
+        let mut fsyncs = Vec::new();
+        for data in 0..count {
+            let record = TestRecord::Struct1(TestInternalStruct1 { data });
+            serde_wal.write(&record).unwrap();
+            unsynced.push(serde_wal.unsynced_records());
+            fsyncs.push(serde_wal.fsync_count());
+        }
+
+        // Simulate a crash, WAL is dropped without a final flush
+        drop(serde_wal);
+        (unsynced, fsyncs)
+    }
+
+    #[test]
+    fn test_wal_fsync_every_op() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+
+        // Every write is synced by itself
+        let (unsynced, fsyncs) = write_records(dir.path(), WalFsyncPolicy::EveryOp, 10);
+        assert!(unsynced.iter().all(|&count| count == 0));
+        assert_eq!(fsyncs, (1..=10).collect::<Vec<_>>());
+
+        let serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), WalOptions::default()).unwrap();
+        let records: Vec<_> = serde_wal
+            .read_all(false)
+            .map(|(_, record)| match record {
+                TestRecord::Struct1(x) => x.data,
+                TestRecord::Struct2(_) => panic!("Wrong structure"),
+            })
+            .collect();
+        assert_eq!(records, (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_wal_fsync_interval() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+
+        // Interval too long to elapse during the test, records are batched until a flush
+        let (unsynced, fsyncs) = write_records(dir.path(), WalFsyncPolicy::Interval(60_000), 10);
+        assert_eq!(unsynced, (1..=10).collect::<Vec<_>>());
+        assert!(fsyncs.iter().all(|&count| count == 0));
+
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), WalOptions::default())
+                .unwrap()
+                .with_fsync_policy(WalFsyncPolicy::Interval(10));
+        let record = TestRecord::Struct1(TestInternalStruct1 { data: 10 });
+        serde_wal.write(&record).unwrap();
+        assert_eq!(serde_wal.fsync_count(), 0);
+
+        // Once the interval has passed, the next write syncs all pending records at once
+        std::thread::sleep(Duration::from_millis(20));
+        serde_wal.write(&record).unwrap();
+        assert_eq!(serde_wal.fsync_count(), 1);
+        assert_eq!(serde_wal.unsynced_records(), 0);
+        assert_eq!(serde_wal.len(false), 12);
+    }
+
+    #[test]
+    fn test_wal_fsync_on_flush() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new(dir.path().to_str().unwrap(), WalOptions::default())
+                .unwrap()
+                .with_fsync_policy(WalFsyncPolicy::OnFlush);
+
+        for data in 0..10 {
+            let record = TestRecord::Struct1(TestInternalStruct1 { data });
+            serde_wal.write(&record).unwrap();
+        }
+        assert_eq!(serde_wal.fsync_count(), 0);
+        assert_eq!(serde_wal.unsynced_records(), 10);
+
+        // Records are only synced by the flush
+        serde_wal.flush().unwrap();
+        assert_eq!(serde_wal.fsync_count(), 1);
+        assert_eq!(serde_wal.unsynced_records(), 0);
+    }
+
+    #[test]
+    fn test_disabled_wal() {
+        let dir = Builder::new().prefix("wal_test").tempdir().unwrap();
+        let mut serde_wal: SerdeWal<TestRecord> =
+            SerdeWal::new_disabled(dir.path().to_str().unwrap(), WalOptions::default(), 5).unwrap();
+
+        let record = TestRecord::Struct1(TestInternalStruct1 { data: 10 });
+        assert_eq!(serde_wal.write(&record).unwrap(), 5);
+        assert_eq!(serde_wal.write(&record).unwrap(), 6);
+        assert_eq!(serde_wal.last_index(), 6);
+
+        // Records are enumerated, but nothing can be read back
+        assert_eq!(serde_wal.read(5).count(), 0);
+        assert_eq!(serde_wal.read(0).count(), 0);
+        assert_eq!(serde_wal.read_all(true).count(), 0);
+        assert!(serde_wal.flush_async().is_none());
+    }
 }
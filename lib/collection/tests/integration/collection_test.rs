@@ -13,13 +13,13 @@ use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
 use itertools::Itertools;
-use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
 use segment::types::{
-    Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
+    Condition, ExtendedPointId, FieldCondition, Filter, GeoPoint, HasIdCondition, Payload,
     PayloadFieldSchema, PayloadSchemaType, PointIdType, WithPayloadInterface,
 };
-use serde_json::Map;
+use serde_json::{json, Map};
 use tempfile::Builder;
 
 use crate::common::{load_local_collection, simple_collection_fixture, N_SHARDS};
@@ -184,6 +184,81 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
     assert_eq!(count_res.count, 1);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_ids_only() {
+    test_collection_search_ids_only_with_shards(1).await;
+    test_collection_search_ids_only_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_ids_only_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10u64).map(Into::into).collect(),
+            vectors: BatchVectorStructInternal::from(
+                (0..10).map(|i| vec![i as f32, 1.0, 0.0, 1.0]).collect_vec(),
+            )
+            .into(),
+            payloads: Some(
+                (0..10)
+                    .map(|i| Some(serde_json::from_value(serde_json::json!({ "i": i })).unwrap()))
+                    .collect(),
+            ),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Payload and vectors are requested, but must not be fetched in ids-only mode
+    let search_request = SearchRequestInternal {
+        vector: vec![1.0, 0.0, 1.0, 1.0].into(),
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: Some(true.into()),
+        filter: None,
+        params: None,
+        limit: 5,
+        offset: None,
+        score_threshold: None,
+    };
+
+    let full_result = collection
+        .search(
+            search_request.clone().into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(full_result.len(), 5);
+    assert!(full_result
+        .iter()
+        .all(|point| point.payload.is_some() && point.vector.is_some()));
+
+    let ids_only_result = collection
+        .search_ids_only(
+            search_request.into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        ids_only_result,
+        full_result
+            .iter()
+            .map(|point| (point.id, point.score))
+            .collect_vec(),
+    );
+}
+
 // FIXME: does not work
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_loading() {
@@ -549,6 +624,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_distance_from: None,
                     })),
                 },
                 None,
@@ -580,6 +656,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_distance_from: None,
                     })),
                 },
                 None,
@@ -620,6 +697,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Asc),
                         start_from: None,
+                        geo_distance_from: None,
                     })),
                 },
                 None,
@@ -659,6 +737,7 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
                         key: key.parse().unwrap(),
                         direction: Some(Direction::Desc),
                         start_from: None,
+                        geo_distance_from: None,
                     })),
                 },
                 None,
@@ -719,6 +798,90 @@ async fn test_ordered_scroll_api_with_shards(shard_number: u32) {
         .all(|&x| x == 2));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_geo_distance_ordered_scroll() {
+    test_geo_distance_ordered_scroll_with_shards(1).await;
+    test_geo_distance_ordered_scroll_with_shards(N_SHARDS).await;
+}
+
+async fn test_geo_distance_ordered_scroll_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    const LOCATION_KEY: &str = "location";
+    let reference = GeoPoint {
+        lon: 13.4,
+        lat: 52.5,
+    };
+
+    // Points are further away from the reference point with decreasing id
+    let payloads = (0..10)
+        .map(|i| {
+            let location = json!({ "lon": 13.4, "lat": 52.5 + f64::from(i) * 0.1 });
+            Some(Payload::from(json!({ LOCATION_KEY: location })))
+        })
+        .collect_vec();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        Batch {
+            ids: (0..10).rev().map(|x: u64| x.into()).collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![vec![1.0, 0.0, 1.0, 1.0]; 10]).into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let scroll_by_distance = || {
+        collection.scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(5),
+                filter: None,
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: false.into(),
+                order_by: Some(OrderByInterface::Struct(OrderBy {
+                    key: LOCATION_KEY.parse().unwrap(),
+                    direction: Some(Direction::Asc),
+                    start_from: None,
+                    geo_distance_from: Some(reference.clone()),
+                })),
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    // A geo index is required
+    assert!(scroll_by_distance().await.is_err());
+
+    collection
+        .create_payload_index_with_wait(
+            LOCATION_KEY.parse().unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Geo),
+            true,
+        )
+        .await
+        .unwrap();
+
+    let result = scroll_by_distance().await.unwrap();
+
+    let ids = result.points.iter().map(|point| point.id).collect_vec();
+    let expected_ids = (5..10).rev().map(|x: u64| x.into()).collect_vec();
+    assert_eq!(ids, expected_ids);
+
+    let distances = result
+        .points
+        .iter()
+        .map(|point| point.order_value.unwrap())
+        .collect_vec();
+    assert_eq!(distances[0], OrderValue::Float(0.0));
+    assert!(distances.iter().tuple_windows().all(|(a, b)| a < b));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_delete_points_by_filter() {
     test_collection_delete_points_by_filter_with_shards(1).await;
@@ -28,6 +28,7 @@ pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
+    indexing_min_vector_number: None,
     flush_interval_sec: 30,
     max_optimization_threads: Some(2),
 };
@@ -37,6 +38,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -52,6 +55,10 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
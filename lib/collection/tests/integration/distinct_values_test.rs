@@ -0,0 +1,312 @@
+use std::collections::BTreeSet;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::config_diff::StrictModeConfig;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CollectionError, ScrollRequestInternal};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use segment::data_types::facets::{DistinctValuesParams, FacetValue};
+use segment::data_types::vectors::VectorStructInternal;
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, Match, Payload, PayloadFieldSchema,
+    PayloadSchemaType, WithPayloadInterface,
+};
+use serde_json::{json, Value};
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, TEST_OPTIMIZERS_CONFIG};
+
+const CITIES: [&str; 7] = [
+    "Amsterdam",
+    "Berlin",
+    "Lisbon",
+    "London",
+    "Madrid",
+    "Paris",
+    "Rome",
+];
+
+const NUM_POINTS: u64 = 60;
+
+async fn distinct_values_fixture(
+    collection_path: &Path,
+    strict_mode_config: Option<StrictModeConfig>,
+) -> Collection {
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            shard_number: NonZeroU32::new(2).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config,
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_path,
+        &collection_path.join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+
+    collection
+        .create_payload_index_with_wait(
+            JsonPath::new("city"),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Only some of the cities are used, some points have many cities or none at all
+    let points = (0..NUM_POINTS)
+        .map(|id| {
+            let city = match id % 5 {
+                0 => json!([CITIES[id as usize % 4], CITIES[id as usize % 3]]),
+                1 => Value::Null,
+                _ => json!(CITIES[id as usize % 6]),
+            };
+            let payload: Payload =
+                serde_json::from_value(json!({"city": city, "group": id % 3})).unwrap();
+            PointStruct {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![1.0, 0.0, 1.0, 1.0]).into(),
+                payload: Some(payload),
+            }
+        })
+        .collect();
+
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(
+                PointInsertOperationsInternal::PointsList(points).into(),
+            ),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    collection
+}
+
+/// Distinct cities of all points matching the filter, by reading all their payloads.
+async fn brute_force_distinct_cities(
+    collection: &Collection,
+    filter: Option<Filter>,
+) -> Vec<FacetValue> {
+    let points = collection
+        .scroll_by(
+            ScrollRequestInternal {
+                offset: None,
+                limit: Some(NUM_POINTS as usize),
+                filter,
+                with_payload: Some(WithPayloadInterface::Bool(true)),
+                with_vector: false.into(),
+                order_by: None,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+        .points;
+
+    let mut cities = BTreeSet::new();
+    for point in points {
+        match point.payload.unwrap().0.get("city") {
+            Some(Value::String(city)) => {
+                cities.insert(FacetValue::Keyword(city.clone()));
+            }
+            Some(Value::Array(values)) => {
+                cities.extend(
+                    values
+                        .iter()
+                        .map(|city| FacetValue::Keyword(city.as_str().unwrap().to_string())),
+                );
+            }
+            _ => {}
+        }
+    }
+    cities.into_iter().collect()
+}
+
+async fn distinct_cities(
+    collection: &Collection,
+    filter: Option<Filter>,
+    limit: Option<usize>,
+) -> Result<Vec<FacetValue>, CollectionError> {
+    collection
+        .distinct_values(
+            DistinctValuesParams {
+                key: JsonPath::new("city"),
+                limit,
+                filter,
+            },
+            ShardSelectorInternal::All,
+            None,
+            None,
+        )
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_distinct_values_match_brute_force() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = distinct_values_fixture(collection_dir.path(), None).await;
+
+    let expected = brute_force_distinct_cities(&collection, None).await;
+    assert!(!expected.is_empty());
+    assert!(expected.len() < CITIES.len());
+    assert_eq!(
+        distinct_cities(&collection, None, None).await.unwrap(),
+        expected
+    );
+
+    let group_filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        JsonPath::new("group"),
+        Match::from(1_i64),
+    )));
+    let expected_filtered =
+        brute_force_distinct_cities(&collection, Some(group_filter.clone())).await;
+    assert_eq!(
+        distinct_cities(&collection, Some(group_filter), None)
+            .await
+            .unwrap(),
+        expected_filtered,
+    );
+
+    // Limit keeps the smallest values
+    assert_eq!(
+        distinct_cities(&collection, None, Some(2)).await.unwrap(),
+        expected[..2],
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_distinct_values_strict_mode_limit() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let strict_mode_config = StrictModeConfig {
+        enabled: Some(true),
+        max_query_limit: None,
+        max_timeout: None,
+        unindexed_filtering_retrieve: None,
+        unindexed_filtering_update: None,
+        search_max_hnsw_ef: None,
+        search_allow_exact: None,
+        search_max_oversampling: None,
+        max_distinct_values: Some(3),
+        max_filter_conditions: None,
+        require_filter: None,
+        max_result_points: None,
+        truncate_result_points: None,
+        max_payload_bytes: None,
+    };
+    let collection = distinct_values_fixture(collection_dir.path(), Some(strict_mode_config)).await;
+
+    let expected = brute_force_distinct_cities(&collection, None).await;
+    assert!(expected.len() > 3);
+
+    // Values are bounded by the strict mode limit
+    assert_eq!(
+        distinct_cities(&collection, None, None).await.unwrap(),
+        expected[..3],
+    );
+    assert_eq!(
+        distinct_cities(&collection, None, Some(2)).await.unwrap(),
+        expected[..2],
+    );
+
+    let result = distinct_cities(&collection, None, Some(4)).await;
+    assert!(
+        matches!(result, Err(CollectionError::StrictMode { .. })),
+        "{result:?}",
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_distinct_values_limit_on_high_cardinality_key() {
+    const NUM_SERIALS: i64 = 1000;
+
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = distinct_values_fixture(collection_dir.path(), None).await;
+
+    collection
+        .create_payload_index_with_wait(
+            JsonPath::new("serial"),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Integer),
+            true,
+        )
+        .await
+        .unwrap();
+
+    // Every point has its own value, spread over both shards in descending order
+    let points = (0..NUM_SERIALS)
+        .map(|serial| {
+            let payload: Payload =
+                serde_json::from_value(json!({ "serial": NUM_SERIALS - serial })).unwrap();
+            PointStruct {
+                id: (NUM_POINTS + serial as u64).into(),
+                vector: VectorStructInternal::from(vec![1.0, 0.0, 1.0, 1.0]).into(),
+                payload: Some(payload),
+            }
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(
+                PointInsertOperationsInternal::PointsList(points).into(),
+            ),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let distinct_serials = |limit| {
+        collection.distinct_values(
+            DistinctValuesParams {
+                key: JsonPath::new("serial"),
+                limit,
+                filter: None,
+            },
+            ShardSelectorInternal::All,
+            None,
+            None,
+        )
+    };
+
+    let serials =
+        |range: std::ops::RangeInclusive<i64>| range.map(FacetValue::Int).collect::<Vec<_>>();
+
+    assert_eq!(distinct_serials(Some(5)).await.unwrap(), serials(1..=5));
+
+    // Without a limit, only the default number of values is returned
+    assert_eq!(
+        distinct_serials(None).await.unwrap(),
+        serials(1..=DistinctValuesParams::DEFAULT_LIMIT as i64),
+    );
+}
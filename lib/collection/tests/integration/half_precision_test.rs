@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use std::num::NonZeroU32;
+use std::path::Path;
+
+use api::rest::SearchRequestInternal;
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{Datatype, PointRequestInternal};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use itertools::Itertools;
+use rand::prelude::SmallRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
+use segment::types::{Distance, ExtendedPointId, SearchParams};
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, TEST_OPTIMIZERS_CONFIG};
+
+const SEED: u64 = 42;
+const DIM: usize = 32;
+const NUM_POINTS: u64 = 1000;
+const NUM_QUERIES: usize = 20;
+const TOP: usize = 10;
+
+async fn collection_with_datatype(collection_path: &Path, datatype: Datatype) -> Collection {
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(DIM as u64, Distance::Cosine)
+                .with_datatype(datatype)
+                .build()
+                .into(),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    new_local_collection(
+        "test".to_string(),
+        collection_path,
+        &collection_path.join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap()
+}
+
+async fn upsert_vectors(collection: &Collection, vectors: &[Vec<f32>]) {
+    let upsert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..vectors.len() as u64).map_into().collect(),
+            vectors: BatchVectorStructInternal::from(vectors.to_vec()).into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(upsert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+}
+
+async fn search_top(collection: &Collection, query: &[f32]) -> Vec<(u64, f32)> {
+    let search_request = SearchRequestInternal {
+        vector: query.to_vec().into(),
+        with_payload: None,
+        with_vector: None,
+        filter: None,
+        params: Some(SearchParams {
+            exact: Some(true),
+            ..Default::default()
+        }),
+        limit: TOP,
+        offset: None,
+        score_threshold: None,
+    };
+
+    collection
+        .search(
+            search_request.into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|point| {
+            let ExtendedPointId::NumId(id) = point.id else {
+                panic!("unexpected point id {:?}", point.id);
+            };
+            (id, point.score)
+        })
+        .collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_float16_collection_recall() {
+    let mut rng = SmallRng::seed_from_u64(SEED);
+    let vectors = (0..NUM_POINTS)
+        .map(|_| (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect_vec())
+        .collect_vec();
+    let queries = (0..NUM_QUERIES)
+        .map(|_| (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect_vec())
+        .collect_vec();
+
+    let f32_dir = Builder::new().prefix("collection_f32").tempdir().unwrap();
+    let f32_collection = collection_with_datatype(f32_dir.path(), Datatype::Float32).await;
+    upsert_vectors(&f32_collection, &vectors).await;
+
+    let f16_dir = Builder::new().prefix("collection_f16").tempdir().unwrap();
+    let f16_collection = collection_with_datatype(f16_dir.path(), Datatype::Float16).await;
+    upsert_vectors(&f16_collection, &vectors).await;
+
+    // Stored vectors are downconverted, but stay close to the original ones
+    let records = f16_collection
+        .retrieve(
+            PointRequestInternal {
+                ids: vec![0.into()],
+                with_payload: None,
+                with_vector: true.into(),
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    let Some(VectorStructInternal::Single(stored)) = records[0].vector.clone() else {
+        panic!("unexpected vector {:?}", records[0].vector);
+    };
+    // Vectors are normalized for cosine distance
+    let norm = vectors[0].iter().map(|x| x * x).sum::<f32>().sqrt();
+    for (stored, original) in stored.iter().zip(&vectors[0]) {
+        assert!((stored - original / norm).abs() < 1e-3);
+    }
+
+    let mut found = 0;
+    for query in &queries {
+        let baseline = search_top(&f32_collection, query).await;
+        let result = search_top(&f16_collection, query).await;
+        assert_eq!(result.len(), TOP);
+
+        let baseline_ids: HashSet<_> = baseline.iter().map(|(id, _)| *id).collect();
+        found += result
+            .iter()
+            .filter(|(id, _)| baseline_ids.contains(id))
+            .count();
+
+        for ((_, baseline_score), (_, score)) in baseline.iter().zip(&result) {
+            assert!((baseline_score - score).abs() < 1e-2);
+        }
+    }
+
+    let recall = found as f64 / (NUM_QUERIES * TOP) as f64;
+    assert!(
+        recall >= 0.95,
+        "recall {recall} is too far from f32 baseline"
+    );
+}
@@ -2,8 +2,16 @@ mod collection_restore_test;
 mod collection_test;
 mod common;
 mod distance_matrix_test;
+mod distinct_values_test;
 mod grouping_test;
+mod half_precision_test;
 mod lookup_test;
 mod multi_vec_test;
+mod multivector_test;
+mod named_vector_hnsw_test;
 mod pagination_test;
+mod recall_monitor_test;
+mod reindex_test;
+mod search_log_test;
 mod snapshot_recovery_test;
+mod tenant_scope_test;
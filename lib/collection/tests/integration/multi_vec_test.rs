@@ -36,6 +36,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let vector_params1 = VectorParamsBuilder::new(4, Distance::Dot).build();
@@ -59,6 +61,10 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let snapshot_path = collection_path.join("snapshots");
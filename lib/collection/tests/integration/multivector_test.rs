@@ -0,0 +1,116 @@
+use std::num::NonZeroU32;
+
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CoreSearchRequest, CoreSearchRequestBatch};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use itertools::Itertools;
+use segment::data_types::vectors::{
+    MultiDenseVectorInternal, NamedVectorStruct, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{Distance, MultiVectorComparator, MultiVectorConfig, PointIdType};
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, TEST_OPTIMIZERS_CONFIG};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multivector_max_sim_scores() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(2, Distance::Dot)
+                .with_multivector_config(MultiVectorConfig {
+                    comparator: MultiVectorComparator::MaxSim,
+                })
+                .build()
+                .into(),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+
+    let multivector = |vectors: Vec<Vec<f32>>| MultiDenseVectorInternal::new_unchecked(vectors);
+
+    let points = vec![
+        (1, vec![vec![1.0, 0.0], vec![0.0, 0.5]]),
+        (2, vec![vec![2.0, 0.0]]),
+        (3, vec![vec![0.3, 0.3], vec![0.0, 1.0]]),
+    ]
+    .into_iter()
+    .map(|(id, vectors)| PointStruct {
+        id: PointIdType::from(id as u64),
+        vector: VectorStructInternal::MultiDense(multivector(vectors)).into(),
+        payload: None,
+    })
+    .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let query = multivector(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+    let request = CoreSearchRequestBatch {
+        searches: vec![CoreSearchRequest {
+            query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
+                Vector::MultiDense(query),
+                DEFAULT_VECTOR_NAME,
+            )),
+            filter: None,
+            params: None,
+            limit: 10,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+        }],
+    };
+
+    let result = collection
+        .core_search_batch(request, None, ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+
+    // Score is the sum over query vectors of the best similarity to any of the point vectors
+    let scored = result[0]
+        .iter()
+        .map(|point| (point.id, point.score))
+        .collect_vec();
+    let expected = [(2, 2.0), (1, 1.5), (3, 1.3)];
+    assert_eq!(scored.len(), expected.len());
+    for ((id, score), (expected_id, expected_score)) in scored.into_iter().zip(expected) {
+        assert_eq!(id, PointIdType::from(expected_id as u64));
+        assert!((score - expected_score).abs() < 1e-5, "{id}: {score}");
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::config_diff::HnswConfigDiff;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointStruct, WriteOrdering,
+};
+use collection::operations::types::VectorsConfig;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use collection::optimizers_builder::OptimizersConfig;
+use common::types::{DetailsLevel, TelemetryDetail};
+use itertools::Itertools;
+use segment::data_types::named_vectors::NamedVectors;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Distance, Indexes};
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, TEST_OPTIMIZERS_CONFIG};
+
+const SMALL_DIM: usize = 4;
+const LARGE_DIM: usize = 256;
+const NUM_POINTS: u64 = 1000;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_named_vectors_with_distinct_hnsw_configs() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let vectors = BTreeMap::from([
+        (
+            "small".to_string(),
+            VectorParamsBuilder::new(SMALL_DIM as u64, Distance::Dot)
+                .with_hnsw_config(HnswConfigDiff {
+                    m: Some(8),
+                    ef_construct: Some(32),
+                    ..Default::default()
+                })
+                .build(),
+        ),
+        (
+            "large".to_string(),
+            VectorParamsBuilder::new(LARGE_DIM as u64, Distance::Dot)
+                .with_hnsw_config(HnswConfigDiff {
+                    m: Some(32),
+                    ..Default::default()
+                })
+                .build(),
+        ),
+    ]);
+
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorsConfig::Multi(vectors),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: OptimizersConfig {
+            indexing_threshold: Some(100), // KB
+            indexing_min_vector_number: None,
+            ..TEST_OPTIMIZERS_CONFIG.clone()
+        },
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+
+    let points = (0..NUM_POINTS)
+        .map(|i| {
+            let mut vectors = NamedVectors::default();
+            vectors.insert("small".to_string(), vec![i as f32; SMALL_DIM].into());
+            vectors.insert("large".to_string(), vec![i as f32; LARGE_DIM].into());
+            PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vectors).into(),
+                payload: None,
+            }
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        PointInsertOperationsInternal::PointsList(points).into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Wait for the indexing optimizer to build the indices
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level2,
+        histograms: false,
+    };
+    let timeout = Instant::now() + Duration::from_secs(60);
+    let indexed_segments = loop {
+        let telemetry = collection.get_telemetry_data(detail).await;
+        let indexed_segments = telemetry
+            .shards
+            .into_iter()
+            .flat_map(|shard| shard.local.unwrap().segments)
+            .filter(|segment| segment.info.num_points > 0)
+            .map(|segment| segment.config)
+            .collect_vec();
+        let all_indexed = indexed_segments.iter().all(|config| {
+            config
+                .vector_data
+                .values()
+                .all(|vector_config| matches!(vector_config.index, Indexes::Hnsw(_)))
+        });
+        if !indexed_segments.is_empty() && all_indexed {
+            break indexed_segments;
+        }
+        assert!(Instant::now() < timeout, "indices were not built in time");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    };
+
+    // Each named vector is indexed with its own config, on top of the collection config
+    for config in indexed_segments {
+        let Indexes::Hnsw(small_hnsw) = &config.vector_data["small"].index else {
+            unreachable!();
+        };
+        assert_eq!(small_hnsw.m, 8);
+        assert_eq!(small_hnsw.ef_construct, 32);
+
+        let Indexes::Hnsw(large_hnsw) = &config.vector_data["large"].index else {
+            unreachable!();
+        };
+        assert_eq!(large_hnsw.m, 32);
+        assert_eq!(
+            large_hnsw.ef_construct,
+            collection_config.hnsw_config.ef_construct,
+        );
+    }
+}
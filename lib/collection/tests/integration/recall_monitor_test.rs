@@ -0,0 +1,62 @@
+use collection::collection::recall_monitor::RecallMonitorConfig;
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::CollectionUpdateOperations;
+use common::types::TelemetryDetail;
+use itertools::Itertools;
+use rand::prelude::SmallRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::vectors::BatchVectorStructInternal;
+use tempfile::Builder;
+
+use crate::common::simple_collection_fixture;
+
+const NUM_POINTS: u64 = 100;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_sample_recall_is_recorded() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let config = RecallMonitorConfig {
+        interval_sec: 1,
+        sample_size: 5,
+        limit: 10,
+    };
+
+    // Nothing to sample in an empty collection
+    assert_eq!(collection.sample_recall(&config).await.unwrap(), None);
+    let telemetry = collection
+        .get_telemetry_data(TelemetryDetail::default())
+        .await;
+    assert!(telemetry.recall.is_none());
+
+    let mut rng = SmallRng::seed_from_u64(42);
+    let vectors = (0..NUM_POINTS)
+        .map(|_| (0..4).map(|_| rng.gen_range(-1.0..1.0)).collect_vec())
+        .collect_vec();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..NUM_POINTS).map_into().collect(),
+            vectors: BatchVectorStructInternal::from(vectors).into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Segments are not indexed, so approximate search is exact
+    let recall = collection.sample_recall(&config).await.unwrap();
+    assert_eq!(recall, Some(1.0));
+
+    let telemetry = collection
+        .get_telemetry_data(TelemetryDetail::default())
+        .await;
+    let recall_telemetry = telemetry.recall.unwrap();
+    assert_eq!(recall_telemetry.samples, 1);
+    assert_eq!(recall_telemetry.last_recall, Some(1.0));
+    assert_eq!(recall_telemetry.avg_recall, Some(1.0));
+    assert!(recall_telemetry.last_sample_time.is_some());
+}
@@ -0,0 +1,172 @@
+use std::num::NonZeroU32;
+use std::time::{Duration, Instant};
+
+use api::rest::SearchRequestInternal;
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::config_diff::HnswConfigDiff;
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use collection::optimizers_builder::OptimizersConfig;
+use common::types::{DetailsLevel, TelemetryDetail};
+use itertools::Itertools;
+use rand::prelude::SmallRng;
+use rand::{Rng, SeedableRng};
+use segment::data_types::vectors::BatchVectorStructInternal;
+use segment::types::{Distance, HnswConfig, Indexes};
+use tempfile::Builder;
+
+use crate::common::{new_local_collection, TEST_OPTIMIZERS_CONFIG};
+
+const DIM: usize = 32;
+const NUM_POINTS: u64 = 2000;
+
+/// Wait until the optimizers built HNSW indices of all non-empty segments, return their configs
+async fn wait_for_hnsw_configs(
+    collection: &Collection,
+    is_done: impl Fn(&HnswConfig) -> bool,
+) -> Vec<HnswConfig> {
+    let detail = TelemetryDetail {
+        level: DetailsLevel::Level2,
+        histograms: false,
+    };
+    let timeout = Instant::now() + Duration::from_secs(60);
+    loop {
+        let telemetry = collection.get_telemetry_data(detail).await;
+        let hnsw_configs = telemetry
+            .shards
+            .into_iter()
+            .flat_map(|shard| shard.local.unwrap().segments)
+            .filter(|segment| segment.info.num_points > 0)
+            .map(|segment| match &segment.config.vector_data[""].index {
+                Indexes::Plain {} => None,
+                Indexes::Hnsw(hnsw_config) => Some(hnsw_config.clone()),
+            })
+            .collect::<Option<Vec<_>>>();
+        if let Some(hnsw_configs) = hnsw_configs {
+            if !hnsw_configs.is_empty() && hnsw_configs.iter().all(&is_done) {
+                return hnsw_configs;
+            }
+        }
+        assert!(Instant::now() < timeout, "indices were not built in time");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_reindex_with_changed_hnsw_config() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(DIM as u64, Distance::Cosine)
+                .build()
+                .into(),
+            shard_number: NonZeroU32::new(1).unwrap(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: OptimizersConfig {
+            indexing_threshold: Some(100), // KB
+            indexing_min_vector_number: None,
+            ..TEST_OPTIMIZERS_CONFIG.clone()
+        },
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: HnswConfig {
+            m: 16,
+            ..Default::default()
+        },
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    let collection = new_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &collection_config,
+    )
+    .await
+    .unwrap();
+
+    let mut rng = SmallRng::seed_from_u64(42);
+    let vectors = (0..NUM_POINTS)
+        .map(|_| (0..DIM).map(|_| rng.gen_range(-1.0..1.0)).collect_vec())
+        .collect_vec();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..NUM_POINTS).map_into().collect(),
+            vectors: BatchVectorStructInternal::from(vectors.clone()).into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    wait_for_hnsw_configs(&collection, |hnsw_config| hnsw_config.m == 16).await;
+
+    // Change `m` and rebuild all indexed segments with it
+    collection
+        .update_hnsw_config_from_diff(HnswConfigDiff {
+            m: Some(8),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    collection.recreate_optimizers_blocking().await.unwrap();
+
+    let scheduled_segments = collection.reindex().await.unwrap();
+    assert!(scheduled_segments > 0);
+
+    let hnsw_configs = wait_for_hnsw_configs(&collection, |hnsw_config| hnsw_config.m == 8).await;
+    for hnsw_config in hnsw_configs {
+        assert_eq!(
+            hnsw_config,
+            HnswConfig {
+                m: 8,
+                ..collection_config.hnsw_config.clone()
+            },
+        );
+    }
+
+    // No data is lost, search over the rebuilt graph still finds the stored points
+    let info = collection.info(&ShardSelectorInternal::All).await.unwrap();
+    assert_eq!(info.points_count, Some(NUM_POINTS as usize));
+
+    for (id, vector) in vectors.iter().enumerate().take(10) {
+        let result = collection
+            .search(
+                SearchRequestInternal {
+                    vector: vector.clone().into(),
+                    with_payload: None,
+                    with_vector: None,
+                    filter: None,
+                    params: None,
+                    limit: 10,
+                    offset: None,
+                    score_threshold: None,
+                }
+                .into(),
+                None,
+                &ShardSelectorInternal::All,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.len(), 10);
+        assert!(result.iter().any(|point| point.id == (id as u64).into()));
+    }
+}
@@ -0,0 +1,198 @@
+use std::sync::Arc;
+
+use api::rest::SearchRequestInternal;
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, WalConfig};
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::shared_storage_config::SharedStorageConfig;
+use collection::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::channel_service::ChannelService;
+use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use collection::shards::replica_set::ReplicaState;
+use common::cpu::CpuBudget;
+use common::types::TelemetryDetail;
+use segment::data_types::vectors::{BatchVectorStructInternal, NamedVectorStruct};
+use segment::json_path::JsonPath;
+use segment::types::{
+    Condition, Distance, FieldCondition, Filter, Match, Payload, SearchParams,
+    WithPayloadInterface, WithVector,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+use crate::common::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer, REST_PORT,
+    TEST_OPTIMIZERS_CONFIG,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_search_log_captures_sampled_searches() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+    // Sample all searches
+    let shared_storage_config = SharedStorageConfig {
+        search_log_sample_rate: 1.0,
+        ..Default::default()
+    };
+
+    let collection = Collection::new(
+        "test".to_string(),
+        0,
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+        &config,
+        Arc::new(shared_storage_config),
+        CollectionShardDistribution::all_local(Some(1), 0),
+        ChannelService::new(REST_PORT, None),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+        None,
+    )
+    .await
+    .unwrap();
+    collection
+        .set_shard_replica_state(0, 0, ReplicaState::Active, None)
+        .await
+        .unwrap();
+
+    let payloads: Vec<Option<Payload>> = (0..10)
+        .map(|i| {
+            let color = if i % 2 == 0 { "red" } else { "blue" };
+            Some(serde_json::from_value(json!({ "color": color })).unwrap())
+        })
+        .collect();
+    let vectors: Vec<Vec<f32>> = (0..10).map(|i| vec![i as f32, 1.0, 0.0, 1.0]).collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10u64).map(Into::into).collect(),
+            vectors: BatchVectorStructInternal::from(vectors).into(),
+            payloads: Some(payloads),
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    assert!(collection
+        .get_telemetry_data(TelemetryDetail::default())
+        .await
+        .recent_searches
+        .is_empty());
+
+    let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+        JsonPath::new("color"),
+        Match::from("red".to_string()),
+    )));
+    let params = SearchParams {
+        hnsw_ef: Some(32),
+        ..Default::default()
+    };
+    let search_request = SearchRequestInternal {
+        vector: vec![1.0, 1.0, 0.0, 1.0].into(),
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: None,
+        filter: Some(filter.clone()),
+        params: Some(params),
+        limit: 3,
+        offset: None,
+        score_threshold: None,
+    };
+    let result = collection
+        .search(
+            search_request.into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.len(), 3);
+
+    let recent_searches = collection
+        .get_telemetry_data(TelemetryDetail::default())
+        .await
+        .recent_searches;
+    assert_eq!(recent_searches.len(), 1);
+
+    let sampled = &recent_searches[0];
+    assert_eq!(sampled.using, "");
+    assert_eq!(sampled.filter, Some(filter.clone()));
+    assert_eq!(sampled.params, Some(params));
+    assert_eq!(sampled.limit, 3);
+    assert_eq!(sampled.offset, 0);
+    assert_eq!(
+        sampled.result_ids,
+        result.iter().map(|point| point.id).collect::<Vec<_>>(),
+    );
+
+    // Neither query vectors nor payloads of found points are recorded
+    let serialized = serde_json::to_value(sampled).unwrap();
+    let fields = serialized.as_object().unwrap();
+    assert!(!fields.contains_key("vector"));
+    assert!(!fields.contains_key("payload"));
+
+    // Queries of the Query API are sampled as well
+    let query_request = ShardQueryRequest {
+        prefetches: vec![],
+        query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
+            NamedVectorStruct::Default(vec![1.0, 1.0, 0.0, 1.0]),
+        ))),
+        filter: Some(filter.clone()),
+        score_threshold: None,
+        limit: 2,
+        offset: 1,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+    let result = collection
+        .query(query_request, None, ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(result.len(), 2);
+
+    let recent_searches = collection
+        .get_telemetry_data(TelemetryDetail::default())
+        .await
+        .recent_searches;
+    assert_eq!(recent_searches.len(), 2);
+
+    let sampled = &recent_searches[1];
+    assert_eq!(sampled.filter, Some(filter));
+    assert_eq!(sampled.limit, 2);
+    assert_eq!(sampled.offset, 1);
+    assert_eq!(
+        sampled.result_ids,
+        result.iter().map(|point| point.id).collect::<Vec<_>>(),
+    );
+}
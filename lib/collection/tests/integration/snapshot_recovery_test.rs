@@ -1,33 +1,48 @@
+use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use api::rest::SearchRequestInternal;
 use collection::collection::Collection;
+use collection::common::snapshot_archive::SnapshotCompression;
+use collection::common::snapshots_manager::SnapShotsConfig;
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
 use collection::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::shared_storage_config::SharedStorageConfig;
-use collection::operations::types::{NodeType, VectorsConfig};
+use collection::operations::types::{
+    CollectionError, NodeType, PointRequestInternal, VectorsConfig,
+};
 use collection::operations::vector_params_builder::VectorParamsBuilder;
-use collection::operations::CollectionUpdateOperations;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
+use collection::wal::SerdeWal;
 use common::cpu::CpuBudget;
 use segment::data_types::vectors::VectorStructInternal;
-use segment::types::{Distance, WithPayloadInterface, WithVector};
+use segment::json_path::JsonPath;
+use segment::types::{Distance, PayloadContainer, WithPayloadInterface, WithVector};
+use serde_json::json;
 use tempfile::Builder;
+use wal::WalOptions;
 
 use crate::common::{
-    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer, REST_PORT,
-    TEST_OPTIMIZERS_CONFIG,
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer,
+    simple_collection_fixture, REST_PORT, TEST_OPTIMIZERS_CONFIG,
 };
 
-async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
+/// Returns the size of the created snapshot archive
+async fn _test_snapshot_and_recover_collection(
+    node_type: NodeType,
+    compression: Option<SnapshotCompression>,
+) -> u64 {
     let wal_config = WalConfig {
         wal_capacity_mb: 1,
         wal_segments_ahead: 0,
+        fsync_policy: Default::default(),
+        wal_disabled: false,
     };
 
     let collection_params = CollectionParams {
@@ -42,6 +57,10 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -55,6 +74,10 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
 
     let storage_config: SharedStorageConfig = SharedStorageConfig {
         node_type,
+        snapshots_config: SnapShotsConfig {
+            compression,
+            ..Default::default()
+        },
         ..Default::default()
     };
 
@@ -112,12 +135,12 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
     // Take a snapshot
     let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_description = collection
-        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .create_snapshot(snapshots_temp_dir.path(), 0, &[])
         .await
         .unwrap();
 
     if let Err(err) = Collection::restore_snapshot(
-        &snapshots_path.path().join(snapshot_description.name),
+        &snapshots_path.path().join(&snapshot_description.name),
         recover_dir.path(),
         0,
         false,
@@ -182,14 +205,240 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         assert_eq!(reference.payload, recovered.payload);
         assert_eq!(reference.vector, recovered.vector);
     }
+
+    snapshot_description.size
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_snapshot_and_recover_collection_normal() {
-    _test_snapshot_and_recover_collection(NodeType::Normal).await;
+    _test_snapshot_and_recover_collection(NodeType::Normal, None).await;
 }
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_snapshot_and_recover_collection_listener() {
-    _test_snapshot_and_recover_collection(NodeType::Listener).await;
+    _test_snapshot_and_recover_collection(NodeType::Listener, None).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snapshot_and_recover_collection_compressed() {
+    let uncompressed_size = _test_snapshot_and_recover_collection(NodeType::Normal, None).await;
+    let low_level_size = _test_snapshot_and_recover_collection(
+        NodeType::Normal,
+        Some(SnapshotCompression::Zstd { level: 1 }),
+    )
+    .await;
+    let high_level_size = _test_snapshot_and_recover_collection(
+        NodeType::Normal,
+        Some(SnapshotCompression::Zstd { level: 19 }),
+    )
+    .await;
+
+    assert!(low_level_size < uncompressed_size);
+    assert!(high_level_size < low_level_size);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_shard_snapshot_seq_number() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let upsert = |from: u64, to: u64| {
+        let points = (from..to)
+            .map(|i| PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+                payload: None,
+            })
+            .collect();
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        ))
+    };
+
+    collection
+        .update_from_client_simple(upsert(0, 10), true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let first = collection
+        .create_shard_snapshot(0, snapshots_temp_dir.path(), Default::default())
+        .await
+        .unwrap();
+
+    for i in 1..=3 {
+        collection
+            .update_from_client_simple(upsert(i * 10, i * 10 + 10), true, WriteOrdering::default())
+            .await
+            .unwrap();
+    }
+
+    // Snapshot names have a resolution of one second
+    tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+
+    let second = collection
+        .create_shard_snapshot(0, snapshots_temp_dir.path(), Default::default())
+        .await
+        .unwrap();
+
+    let first_seq_number = first.seq_number.expect("shard snapshot has a seq number");
+    let second_seq_number = second.seq_number.expect("shard snapshot has a seq number");
+    assert!(second_seq_number > first_seq_number);
+
+    let listed = collection.list_shard_snapshots(0).await.unwrap();
+    assert_eq!(listed.len(), 2);
+    for snapshot in [first, second] {
+        let listed_snapshot = listed
+            .iter()
+            .find(|listed| listed.name == snapshot.name)
+            .unwrap();
+        assert_eq!(listed_snapshot.seq_number, snapshot.seq_number);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancelled_shard_snapshot_leaves_no_files() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let points = (0..10)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let stopped = Arc::new(AtomicBool::new(true));
+    let result = collection
+        .create_shard_snapshot(0, snapshots_temp_dir.path(), stopped)
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::Cancelled { .. })),
+        "{result:?}",
+    );
+
+    assert!(collection.list_shard_snapshots(0).await.unwrap().is_empty());
+    assert_eq!(
+        std::fs::read_dir(snapshots_temp_dir.path())
+            .unwrap()
+            .count(),
+        0,
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_snapshot_with_excluded_payload_keys() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 2).await;
+
+    let points = (0..20)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: Some(json!({"name": format!("point {i}"), "secret": i}).into()),
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let secret_key: JsonPath = "secret".parse().unwrap();
+    let name_key: JsonPath = "name".parse().unwrap();
+
+    // Keys missing in the collection are rejected
+    let missing_key: JsonPath = "missing".parse().unwrap();
+    let result = collection
+        .create_snapshot(snapshots_temp_dir.path(), 0, &[missing_key])
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::BadInput { .. })),
+        "{result:?}",
+    );
+
+    let snapshot_description = collection
+        .create_snapshot(snapshots_temp_dir.path(), 0, &[secret_key.clone()])
+        .await
+        .unwrap();
+
+    Collection::restore_snapshot(
+        &collection_dir
+            .path()
+            .join("snapshots")
+            .join(&snapshot_description.name),
+        recover_dir.path(),
+        0,
+        false,
+    )
+    .unwrap();
+
+    // WAL is not stripped, so it is not included, replaying it would restore the excluded key
+    for shard_id in 0..2 {
+        let wal_path = recover_dir.path().join(shard_id.to_string()).join("wal");
+        let wal: SerdeWal<OperationWithClockTag> =
+            SerdeWal::new(wal_path.to_str().unwrap(), WalOptions::default()).unwrap();
+        assert!(wal.is_empty());
+    }
+
+    let recovered_collection = Collection::load(
+        "test_rec".to_string(),
+        0,
+        recover_dir.path(),
+        &recover_dir.path().join("snapshots"),
+        Default::default(),
+        ChannelService::new(REST_PORT, None),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+        None,
+    )
+    .await;
+
+    let request = PointRequestInternal {
+        ids: (0..20).map(|i: u64| i.into()).collect(),
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(false),
+    };
+
+    let recovered = recovered_collection
+        .retrieve(request.clone(), None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(recovered.len(), 20);
+    for record in &recovered {
+        let payload = record.payload.as_ref().unwrap();
+        assert!(payload.get_value(&secret_key).is_empty());
+        assert_eq!(payload.get_value(&name_key).len(), 1);
+    }
+
+    // Live collection keeps the excluded key
+    let original = collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(original.len(), 20);
+    for record in &original {
+        let payload = record.payload.as_ref().unwrap();
+        assert_eq!(payload.get_value(&secret_key).len(), 1);
+    }
 }
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use api::rest::SearchRequestInternal;
+use collection::collection::Collection;
+use collection::config::{CollectionConfig, CollectionParams, ShardingMethod, WalConfig};
+use collection::operations::point_ops::{Batch, WriteOrdering};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CountRequestInternal;
+use collection::operations::universal_query::shard_query::{
+    ScoringQuery, ShardPrefetch, ShardQueryRequest,
+};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::CollectionUpdateOperations;
+use collection::shards::channel_service::ChannelService;
+use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use common::cpu::CpuBudget;
+use segment::data_types::index::{KeywordIndexParams, KeywordIndexType};
+use segment::data_types::vectors::{BatchVectorStructInternal, NamedVectorStruct, Vector};
+use segment::json_path::JsonPath;
+use segment::types::{
+    AnyVariants, Condition, Distance, FieldCondition, Filter, Match, Payload, PayloadContainer,
+    PayloadFieldSchema, PayloadSchemaParams, ShardKey, ValueVariants, WithPayloadInterface,
+    WithVector,
+};
+use tempfile::Builder;
+
+use crate::common::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer, REST_PORT,
+    TEST_OPTIMIZERS_CONFIG,
+};
+
+const TENANTS: [&str; 2] = ["acme", "globex"];
+
+const POINTS_PER_TENANT: u64 = 5;
+
+async fn tenant_collection_fixture(collection_path: &Path) -> Collection {
+    let collection_config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            sharding_method: Some(ShardingMethod::Custom),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+            fsync_policy: Default::default(),
+            wal_disabled: false,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        payload_schema: None,
+        default_search_params: None,
+        tie_break: None,
+        default_timeout: None,
+    };
+
+    // Shard key placement is validated against known peers
+    let channel_service = ChannelService::new(REST_PORT, None);
+    channel_service
+        .id_to_address
+        .write()
+        .insert(0, "http://localhost:6335".parse().unwrap());
+
+    let collection = Collection::new(
+        "test".to_string(),
+        0,
+        collection_path,
+        &collection_path.join("snapshots"),
+        &collection_config,
+        Default::default(),
+        CollectionShardDistribution {
+            shards: HashMap::new(),
+        },
+        channel_service,
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+        None,
+    )
+    .await
+    .unwrap();
+
+    for tenant in TENANTS {
+        collection
+            .create_shard_key(ShardKey::from(tenant), vec![vec![0]])
+            .await
+            .unwrap();
+    }
+
+    collection
+        .create_payload_index_with_wait(
+            JsonPath::new("tenant"),
+            PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+                r#type: KeywordIndexType::Keyword,
+                is_tenant: Some(true),
+                on_disk: None,
+                case_insensitive: None,
+            })),
+            true,
+        )
+        .await
+        .unwrap();
+
+    for (tenant_idx, tenant) in TENANTS.into_iter().enumerate() {
+        let first_id = tenant_idx as u64 * POINTS_PER_TENANT;
+        let payload: Payload =
+            serde_json::from_str(&format!(r#"{{"tenant":"{tenant}"}}"#)).unwrap();
+
+        let insert_points = CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: (first_id..first_id + POINTS_PER_TENANT)
+                    .map(Into::into)
+                    .collect(),
+                vectors: BatchVectorStructInternal::from(vec![
+                    vec![1.0, 0.0, 1.0, 1.0];
+                    POINTS_PER_TENANT as usize
+                ])
+                .into(),
+                payloads: Some(vec![Some(payload); POINTS_PER_TENANT as usize]),
+            }
+            .into(),
+        );
+
+        collection
+            .update_from_client(
+                insert_points,
+                true,
+                WriteOrdering::default(),
+                Some(ShardKey::from(tenant)),
+            )
+            .await
+            .unwrap();
+    }
+
+    collection
+}
+
+fn tenant_condition(r#match: Match) -> Condition {
+    Condition::Field(FieldCondition::new_match(JsonPath::new("tenant"), r#match))
+}
+
+fn tenant_filter(tenant: &str) -> Filter {
+    Filter::new_must(tenant_condition(Match::new_value(ValueVariants::String(
+        tenant.to_string(),
+    ))))
+}
+
+fn search_request(filter: Option<Filter>) -> SearchRequestInternal {
+    SearchRequestInternal {
+        vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: None,
+        filter,
+        params: None,
+        limit: 2 * POINTS_PER_TENANT as usize,
+        offset: None,
+        score_threshold: None,
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tenant_scoped_search_stays_within_shard_key() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = tenant_collection_fixture(collection_dir.path()).await;
+
+    let acme_scope = ShardSelectorInternal::TenantScope(ShardKey::from("acme"));
+
+    let result = collection
+        .search(search_request(None).into(), None, &acme_scope, None)
+        .await
+        .unwrap();
+
+    assert_eq!(result.len(), POINTS_PER_TENANT as usize);
+    for point in &result {
+        let tenant = point
+            .payload
+            .as_ref()
+            .unwrap()
+            .get_value(&JsonPath::new("tenant"))
+            .into_iter()
+            .next()
+            .cloned();
+        assert_eq!(tenant, Some(serde_json::json!("acme")));
+    }
+
+    // Filtering on own tenant is allowed
+    let result = collection
+        .search(
+            search_request(Some(tenant_filter("acme"))).into(),
+            None,
+            &acme_scope,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.len(), POINTS_PER_TENANT as usize);
+
+    // Excluding all other tenants is allowed
+    let result = collection
+        .search(
+            search_request(Some(Filter::new_must_not(tenant_condition(
+                Match::new_except(AnyVariants::Strings(
+                    ["acme".to_string()].into_iter().collect(),
+                )),
+            ))))
+            .into(),
+            None,
+            &acme_scope,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.len(), POINTS_PER_TENANT as usize);
+
+    // Filtering on another tenant is rejected, also in nested and negated filters
+    let cross_tenant_filters = [
+        tenant_filter("globex"),
+        Filter::new_should(Condition::Filter(tenant_filter("globex"))),
+        Filter::new_must_not(Condition::Filter(tenant_filter("acme"))),
+        Filter::new_must_not(Condition::Filter(Filter::new_must_not(Condition::Filter(
+            tenant_filter("globex"),
+        )))),
+    ];
+    for filter in cross_tenant_filters {
+        let result = collection
+            .search(search_request(Some(filter)).into(), None, &acme_scope, None)
+            .await;
+        assert!(result.is_err(), "cross tenant filter must be rejected");
+    }
+
+    let count_request = CountRequestInternal {
+        filter: Some(tenant_filter("globex")),
+        exact: true,
+    };
+    let result = collection
+        .count(count_request, None, &acme_scope, None)
+        .await;
+    assert!(result.is_err(), "cross tenant filter must be rejected");
+
+    // Unknown shard keys are rejected
+    let unknown_scope = ShardSelectorInternal::TenantScope(ShardKey::from("initech"));
+    let result = collection
+        .search(search_request(None).into(), None, &unknown_scope, None)
+        .await;
+    assert!(result.is_err(), "unknown shard key must be rejected");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_tenant_scoped_query_checks_prefetch_filters() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = tenant_collection_fixture(collection_dir.path()).await;
+
+    let acme_scope = ShardSelectorInternal::TenantScope(ShardKey::from("acme"));
+
+    let nearest_query = ScoringQuery::Vector(QueryEnum::Nearest(
+        NamedVectorStruct::new_from_vector(Vector::Dense(vec![1.0, 1.0, 1.0, 1.0]), ""),
+    ));
+    let query_request = |prefetch_filter: Option<Filter>| ShardQueryRequest {
+        prefetches: vec![ShardPrefetch {
+            prefetches: vec![],
+            query: Some(nearest_query.clone()),
+            limit: 2 * POINTS_PER_TENANT as usize,
+            params: None,
+            filter: prefetch_filter,
+            score_threshold: None,
+        }],
+        query: Some(nearest_query.clone()),
+        filter: None,
+        score_threshold: None,
+        limit: 2 * POINTS_PER_TENANT as usize,
+        offset: 0,
+        params: None,
+        with_vector: WithVector::Bool(false),
+        with_payload: WithPayloadInterface::Bool(false),
+    };
+
+    let result = collection
+        .query(
+            query_request(Some(tenant_filter("acme"))),
+            None,
+            acme_scope.clone(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.len(), POINTS_PER_TENANT as usize);
+
+    let result = collection
+        .query(
+            query_request(Some(tenant_filter("globex"))),
+            None,
+            acme_scope,
+            None,
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "cross tenant prefetch filter must be rejected"
+    );
+}
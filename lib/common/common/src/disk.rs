@@ -1,11 +1,13 @@
 use std::path::PathBuf;
 
 /// How many bytes a directory takes.
+///
+/// Symlinks are followed, so that data linked into the directory from elsewhere is counted too.
 pub fn dir_size(path: impl Into<PathBuf>) -> std::io::Result<u64> {
     fn dir_size(mut dir: std::fs::ReadDir) -> std::io::Result<u64> {
         dir.try_fold(0, |acc, file| {
             let file = file?;
-            let size = match file.metadata()? {
+            let size = match std::fs::metadata(file.path())? {
                 data if data.is_dir() => dir_size(std::fs::read_dir(file.path())?)?,
                 data => data.len(),
             };
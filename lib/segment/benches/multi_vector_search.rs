@@ -72,6 +72,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         max_indexing_threads: 0,
         on_disk: None,
         payload_m: None,
+        use_heuristic: None,
     };
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
@@ -48,6 +48,8 @@ pub enum OperationError {
     OutOfMemory { description: String, free: u64 },
     #[error("Operation cancelled: {description}")]
     Cancelled { description: String },
+    #[error("Operation timed out: {description}")]
+    Timeout { description: String },
     #[error("Validation failed: {description}")]
     ValidationError { description: String },
     #[error("Wrong usage of sparse vectors")]
@@ -60,6 +62,8 @@ pub enum OperationError {
     MissingRangeIndexForOrderBy { key: String },
     #[error("No appropriate index for faceting: `{key}`. Please create one to facet on this field. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Match conditions")]
     MissingMapIndexForFacet { key: String },
+    #[error("No geo index for `order_by` key: `{key}`. Please create one to order by geo distance. Check https://qdrant.tech/documentation/concepts/indexing/#payload-index to see which payload schemas support Geo conditions")]
+    MissingGeoIndexForOrderBy { key: String },
 }
 
 impl OperationError {
@@ -1,54 +1,102 @@
 use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 
-use tar::Archive;
+use tar::{Archive, Entries};
 
 use crate::common::operation_error::{OperationError, OperationResult};
 
+/// Magic number at the beginning of every zstd frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Reader over a snapshot archive file, transparently decompressing it if required.
+pub enum SnapshotArchiveReader {
+    Plain(File),
+    Zstd(zstd::Decoder<'static, BufReader<File>>),
+}
+
+impl Read for SnapshotArchiveReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            SnapshotArchiveReader::Plain(file) => file.read(buf),
+            SnapshotArchiveReader::Zstd(decoder) => decoder.read(buf),
+        }
+    }
+}
+
+impl SnapshotArchiveReader {
+    /// Open snapshot archive, detecting compression by the magic number of the file.
+    fn open(path: &Path) -> io::Result<Self> {
+        let mut file = File::open(path)?;
+
+        let mut magic = [0u8; ZSTD_MAGIC.len()];
+        let is_zstd = match file.read_exact(&mut magic) {
+            Ok(()) => magic == ZSTD_MAGIC,
+            Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => false,
+            Err(err) => return Err(err),
+        };
+        file.seek(SeekFrom::Start(0))?;
+
+        if is_zstd {
+            Ok(SnapshotArchiveReader::Zstd(zstd::Decoder::new(file)?))
+        } else {
+            Ok(SnapshotArchiveReader::Plain(file))
+        }
+    }
+}
+
+fn validate_entries<R: Read>(entries: Entries<'_, R>) -> OperationResult<()> {
+    for entry in entries {
+        // Read next archive entry type
+        // Deliberately mask real error here for API users, it can expose arbitrary file contents
+        let entry_type = match entry {
+            Ok(entry) => entry.header().entry_type(),
+            Err(err) => {
+                log::warn!("Error while reading snapshot archive, malformed entry: {err}");
+                return Err(OperationError::service_error(
+                    "Failed to open snapshot archive, malformed format",
+                ));
+            }
+        };
+        if !matches!(
+            entry_type,
+            tar::EntryType::Regular | tar::EntryType::Directory,
+        ) {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Malformed snapshot, tar archive contains {entry_type:?} entry",
+                ),
+            });
+        }
+    }
+    Ok(())
+}
+
 pub fn open_snapshot_archive_with_validation<P: AsRef<Path>>(
     snapshot_path: P,
-) -> OperationResult<Archive<File>> {
+) -> OperationResult<Archive<SnapshotArchiveReader>> {
     let path = snapshot_path.as_ref();
-    {
-        let archive_file = File::open(path).map_err(|err| {
+    let open_archive = || {
+        SnapshotArchiveReader::open(path).map_err(|err| {
             OperationError::service_error(format!(
                 "failed to open segment snapshot archive {path:?}: {err}"
             ))
-        })?;
-        let mut ar = Archive::new(archive_file);
-
-        for entry in ar.entries_with_seek()? {
-            // Read next archive entry type
-            // Deliberately mask real error here for API users, it can expose arbitrary file contents
-            let entry_type = match entry {
-                Ok(entry) => entry.header().entry_type(),
-                Err(err) => {
-                    log::warn!("Error while reading snapshot archive, malformed entry: {err}");
-                    return Err(OperationError::service_error(
-                        "Failed to open snapshot archive, malformed format",
-                    ));
-                }
-            };
-            if !matches!(
-                entry_type,
-                tar::EntryType::Regular | tar::EntryType::Directory,
-            ) {
-                return Err(OperationError::ValidationError {
-                    description: format!(
-                        "Malformed snapshot, tar archive contains {entry_type:?} entry",
-                    ),
-                });
-            }
+        })
+    };
+
+    match open_archive()? {
+        SnapshotArchiveReader::Plain(archive_file) => {
+            let mut ar = Archive::new(archive_file);
+            validate_entries(ar.entries_with_seek()?)?;
+        }
+        // Compressed stream is not seekable, read it through
+        reader @ SnapshotArchiveReader::Zstd(_) => {
+            let mut ar = Archive::new(reader);
+            validate_entries(ar.entries()?)?;
         }
     }
 
-    let archive_file = File::open(path).map_err(|err| {
-        OperationError::service_error(format!(
-            "failed to open segment snapshot archive {path:?}: {err}"
-        ))
-    })?;
-
-    let mut ar = Archive::new(archive_file);
+    let mut ar = Archive::new(open_archive()?);
     ar.set_overwrite(false);
 
     Ok(ar)
@@ -127,6 +127,8 @@ impl From<SegmentStateV5> for SegmentState {
         Self {
             version: old.version,
             config: old.config.into(),
+            created_at: None,
+            last_optimized_at: None,
         }
     }
 }
@@ -152,6 +154,7 @@ mod tests {
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            use_heuristic: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -183,6 +186,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                use_heuristic: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -258,6 +262,7 @@ mod tests {
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                use_heuristic: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -26,6 +26,22 @@ impl FacetParams {
     pub const DEFAULT_EXACT: bool = false;
 }
 
+/// Request for the distinct values of a payload key, read from its payload index.
+#[derive(Clone, Debug, Validate)]
+pub struct DistinctValuesParams {
+    pub key: JsonPath,
+
+    /// Max number of values to return. If not set - `DEFAULT_LIMIT`, or the strict mode limit if
+    /// it is lower.
+    #[validate(range(min = 1))]
+    pub limit: Option<usize>,
+    pub filter: Option<Filter>,
+}
+
+impl DistinctValuesParams {
+    pub const DEFAULT_LIMIT: usize = 100;
+}
+
 #[derive(Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum FacetValueRef<'a> {
     Keyword(&'a str),
@@ -23,6 +23,11 @@ pub struct KeywordIndexParams {
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+
+    /// If true - values are lowercased at index and query time, so matching ignores case.
+    /// Default: false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub case_insensitive: Option<bool>,
 }
 
 // Integer
@@ -6,12 +6,13 @@ use validator::Validate;
 
 use crate::json_path::JsonPath;
 use crate::types::{
-    DateTimePayloadType, FloatPayloadType, IntPayloadType, Order, Payload, Range, RangeInterface,
+    DateTimePayloadType, FloatPayloadType, GeoPoint, IntPayloadType, Order, Payload, Range,
+    RangeInterface,
 };
 
 const INTERNAL_KEY_OF_ORDER_BY_VALUE: &str = "____ordered_with____";
 
-#[derive(Deserialize, Serialize, JsonSchema, Copy, Clone, Debug, Default, PartialEq)]
+#[derive(Deserialize, Serialize, JsonSchema, Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum Direction {
     #[default]
@@ -20,6 +21,14 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// Apply the direction to an ascending ordering
+    pub fn apply(&self, ordering: std::cmp::Ordering) -> std::cmp::Ordering {
+        match self {
+            Direction::Asc => ordering,
+            Direction::Desc => ordering.reverse(),
+        }
+    }
+
     pub fn as_range_from<T>(&self, from: T) -> Range<T> {
         match self {
             Direction::Asc => Range {
@@ -68,6 +77,11 @@ pub struct OrderBy {
 
     /// Which payload value to start scrolling from. Default is the lowest value for `asc` and the highest for `desc`
     pub start_from: Option<StartFrom>,
+
+    /// Order by haversine distance, in meters, from this point to the geo value of `key`, instead of the value itself.
+    /// Requires a geo index on `key`. `start_from` is then a distance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub geo_distance_from: Option<GeoPoint>,
 }
 
 impl OrderBy {
@@ -2,11 +2,15 @@ use std::collections::HashMap;
 use std::ops::Deref;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Instant;
 
 use bitvec::prelude::BitSlice;
+use common::types::{ScoreType, ScoredPointOffset};
 use sparse::common::types::{DimId, DimWeight};
 
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::tiny_map;
+use crate::types::Distance;
 
 #[derive(Debug)]
 pub struct QueryContext {
@@ -21,6 +25,12 @@ pub struct QueryContext {
     /// Is changed externally if API times out or cancelled.
     is_stopped: Arc<AtomicBool>,
 
+    /// Searches stop early once this moment is reached.
+    deadline: Option<Instant>,
+
+    /// Defines what to return if the deadline is exceeded.
+    deadline_policy: DeadlinePolicy,
+
     /// Statistics of the element frequency,
     /// collected over all segments.
     /// Required for processing sparse vector search with `idf-dot` similarity.
@@ -34,6 +44,8 @@ impl QueryContext {
             available_point_count: 0,
             search_optimized_threshold_kb,
             is_stopped: Arc::new(AtomicBool::new(false)),
+            deadline: None,
+            deadline_policy: DeadlinePolicy::default(),
             idf: tiny_map::TinyMap::new(),
         }
     }
@@ -47,6 +59,12 @@ impl QueryContext {
         self
     }
 
+    pub fn with_deadline(mut self, deadline: Instant, policy: DeadlinePolicy) -> Self {
+        self.deadline = Some(deadline);
+        self.deadline_policy = policy;
+        self
+    }
+
     pub fn available_point_count(&self) -> usize {
         self.available_point_count
     }
@@ -83,6 +101,7 @@ impl QueryContext {
         SegmentQueryContext {
             query_context: Some(self),
             deleted_points: None,
+            score_threshold: None,
         }
     }
 }
@@ -93,26 +112,45 @@ impl Default for QueryContext {
     }
 }
 
+/// Defines the outcome of a search, which exceeded its deadline
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DeadlinePolicy {
+    /// Return the best results found before the deadline
+    #[default]
+    Partial,
+    /// Fail the search with a timeout error
+    Error,
+}
+
 /// Defines context of the search query on the segment level
 #[derive(Default, Clone, Debug)]
 pub struct SegmentQueryContext<'a> {
     query_context: Option<&'a QueryContext>,
     deleted_points: Option<&'a BitSlice>,
+    /// Results with a worse score are not returned.
+    /// Compared against the post-processed score, same as the `score_threshold` of the request.
+    score_threshold: Option<ScoreType>,
 }
 
 impl<'a> SegmentQueryContext<'a> {
-    pub fn get_vector_context(&self, vector_name: &str) -> VectorQueryContext {
+    /// Create context for searching the given vector, scored with `distance`
+    pub fn get_vector_context(&self, vector_name: &str, distance: Distance) -> VectorQueryContext {
+        let score_threshold = self.score_threshold.map(|threshold| (distance, threshold));
         if let Some(query_context) = self.query_context {
             VectorQueryContext {
                 available_point_count: query_context.available_point_count,
                 search_optimized_threshold_kb: query_context.search_optimized_threshold_kb,
                 is_stopped: Some(&query_context.is_stopped),
+                deadline: query_context.deadline,
+                deadline_policy: query_context.deadline_policy,
                 idf: query_context.idf.get(vector_name),
                 deleted_points: self.deleted_points,
+                score_threshold,
             }
         } else {
             VectorQueryContext {
                 deleted_points: self.deleted_points,
+                score_threshold,
                 ..Default::default()
             }
         }
@@ -122,6 +160,11 @@ impl<'a> SegmentQueryContext<'a> {
         self.deleted_points = Some(deleted_points);
         self
     }
+
+    pub fn with_score_threshold(mut self, score_threshold: Option<ScoreType>) -> Self {
+        self.score_threshold = score_threshold;
+        self
+    }
 }
 
 /// Query context related to a specific vector
@@ -136,9 +179,16 @@ pub struct VectorQueryContext<'a> {
 
     is_stopped: Option<&'a AtomicBool>,
 
+    deadline: Option<Instant>,
+
+    deadline_policy: DeadlinePolicy,
+
     idf: Option<&'a HashMap<DimId, usize>>,
 
     deleted_points: Option<&'a BitSlice>,
+
+    /// Distance of the vector and the threshold of the post-processed score
+    score_threshold: Option<(Distance, ScoreType)>,
 }
 
 pub enum SimpleCow<'a, T> {
@@ -176,6 +226,22 @@ impl VectorQueryContext<'_> {
             .unwrap_or_else(|| SimpleCow::Owned(AtomicBool::new(false)))
     }
 
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Check the outcome of a search, which may have been interrupted by the deadline.
+    ///
+    /// Partial results are fine unless the deadline policy requires an error.
+    pub fn check_deadline_exceeded(&self, deadline_exceeded: bool) -> OperationResult<()> {
+        if deadline_exceeded && self.deadline_policy == DeadlinePolicy::Error {
+            return Err(OperationError::Timeout {
+                description: "search deadline exceeded".to_string(),
+            });
+        }
+        Ok(())
+    }
+
     /// Compute advanced formula for Inverse Document Frequency (IDF) according to wikipedia.
     /// This should account for corner cases when `df` and `n` are small or zero.
     #[inline]
@@ -201,6 +267,18 @@ impl VectorQueryContext<'_> {
     pub fn is_require_idf(&self) -> bool {
         self.idf.is_some()
     }
+
+    /// Remove search results, which don't satisfy the score threshold of the query
+    ///
+    /// Raw scores are post-processed before comparison, so the result is exactly the same as
+    /// applying the threshold to the final scores.
+    pub fn apply_score_threshold(&self, results: &mut Vec<ScoredPointOffset>) {
+        if let Some((distance, threshold)) = self.score_threshold {
+            results.retain(|scored| {
+                distance.check_threshold(distance.postprocess_score(scored.score), threshold)
+            });
+        }
+    }
 }
 
 impl Default for VectorQueryContext<'_> {
@@ -209,8 +287,11 @@ impl Default for VectorQueryContext<'_> {
             available_point_count: 0,
             search_optimized_threshold_kb: usize::MAX,
             is_stopped: None,
+            deadline: None,
+            deadline_policy: DeadlinePolicy::default(),
             idf: None,
             deleted_points: None,
+            score_threshold: None,
         }
     }
 }
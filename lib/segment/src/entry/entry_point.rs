@@ -102,6 +102,12 @@ pub trait SegmentEntry {
 
     fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
 
+    /// Hint that the given vector of these points is about to be read.
+    ///
+    /// Lets on-disk vector storages read ahead, it does not affect any results.
+    /// Returns the number of ranges read ahead, 0 for vectors kept in RAM.
+    fn prefetch_vectors(&self, vector_name: &str, point_ids: &[PointIdType]) -> usize;
+
     /// Retrieve payload for the point
     /// If not found, return empty payload
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
@@ -21,10 +21,11 @@ use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::index::field_index::geo_index::GeoMapIndex;
 use crate::index::field_index::numeric_index::NumericIndexInner;
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition};
+use crate::payload_storage::condition_checker::ValueChecker;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, MatchText,
-    PayloadKeyType, RangeInterface, UuidIntType, UuidPayloadType,
+    DateTimePayloadType, FieldCondition, FloatPayloadType, IntPayloadType, Match, PayloadKeyType,
+    RangeInterface, UuidIntType, UuidPayloadType,
 };
 
 pub trait PayloadFieldIndex {
@@ -156,13 +157,19 @@ impl FieldIndex {
             FieldIndex::IntIndex(_) => None,
             FieldIndex::DatetimeIndex(_) => None,
             FieldIndex::IntMapIndex(_) => None,
-            FieldIndex::KeywordIndex(_) => None,
+            FieldIndex::KeywordIndex(index) => {
+                if !index.is_case_insensitive() {
+                    return None;
+                }
+                let r#match = index.normalize_match(condition.r#match.clone()?);
+                Some(r#match.check(&index.normalize_payload_value(payload_value)))
+            }
             FieldIndex::FloatIndex(_) => None,
             FieldIndex::GeoIndex(_) => None,
             FieldIndex::BinaryIndex(_) => None,
             FieldIndex::FullTextIndex(full_text_index) => match &condition.r#match {
-                Some(Match::Text(MatchText { text })) => {
-                    let query = full_text_index.parse_query(text);
+                Some(Match::Text(text_match)) => {
+                    let query = full_text_index.parse_match_text(text_match);
                     for value in FullTextIndex::get_values(payload_value) {
                         let document = full_text_index.parse_document(&value);
                         if query.check_match(&document) {
@@ -369,6 +376,21 @@ impl FieldIndex {
         }
     }
 
+    pub fn as_geo(&self) -> Option<&GeoMapIndex> {
+        match self {
+            FieldIndex::GeoIndex(index) => Some(index),
+            FieldIndex::IntIndex(_)
+            | FieldIndex::DatetimeIndex(_)
+            | FieldIndex::IntMapIndex(_)
+            | FieldIndex::KeywordIndex(_)
+            | FieldIndex::FloatIndex(_)
+            | FieldIndex::BinaryIndex(_)
+            | FieldIndex::UuidMapIndex(_)
+            | FieldIndex::UuidIndex(_)
+            | FieldIndex::FullTextIndex(_) => None,
+        }
+    }
+
     pub fn as_facet_index(&self) -> Option<FacetIndex> {
         match self {
             FieldIndex::KeywordIndex(index) => Some(FacetIndex::Keyword(index)),
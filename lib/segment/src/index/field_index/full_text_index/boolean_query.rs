@@ -0,0 +1,202 @@
+use std::iter::Peekable;
+use std::vec;
+
+use super::inverted_index::{Document, ParsedQuery};
+
+/// Text query with boolean operators
+///
+/// Terms are combined with `AND`, `OR` and `NOT` operators and grouped with parentheses, e.g.
+/// `a AND (b OR c) NOT d`. Terms without an operator in between are combined with `AND`, same as
+/// tokens of a plain text query. `NOT` binds strongest, then `AND`, then `OR`.
+#[derive(Debug)]
+pub enum TextQuery {
+    /// All tokens of a term must be present
+    Tokens(ParsedQuery),
+    And(Vec<TextQuery>),
+    Or(Vec<TextQuery>),
+    Not(Box<TextQuery>),
+}
+
+impl TextQuery {
+    /// Parse boolean query, using `parse_term` to tokenize single terms
+    ///
+    /// Returns `None` if `text` contains no operators or parentheses, or if it is malformed.
+    /// Such text should be handled as a plain query.
+    pub fn parse(text: &str, parse_term: impl FnMut(&str) -> ParsedQuery) -> Option<Self> {
+        let lexemes = lex(text);
+        if lexemes
+            .iter()
+            .all(|lexeme| matches!(lexeme, Lexeme::Term(_)))
+        {
+            return None;
+        }
+
+        let mut parser = Parser {
+            lexemes: lexemes.into_iter().peekable(),
+            parse_term,
+        };
+        let query = parser.parse_or()?;
+
+        // Whole text must be consumed, otherwise there are unbalanced parentheses
+        parser.lexemes.peek().is_none().then_some(query)
+    }
+
+    pub fn check_match(&self, document: &Document) -> bool {
+        self.check(&|query| query.check_match(document))
+    }
+
+    /// Evaluate the query, checking token leaves with `check_tokens`
+    pub fn check(&self, check_tokens: &impl Fn(&ParsedQuery) -> bool) -> bool {
+        match self {
+            TextQuery::Tokens(query) => check_tokens(query),
+            TextQuery::And(queries) => queries.iter().all(|query| query.check(check_tokens)),
+            TextQuery::Or(queries) => queries.iter().any(|query| query.check(check_tokens)),
+            TextQuery::Not(query) => !query.check(check_tokens),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Lexeme<'a> {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(&'a str),
+}
+
+fn lex(text: &str) -> Vec<Lexeme<'_>> {
+    fn word(word: &str) -> Lexeme<'_> {
+        match word {
+            "AND" => Lexeme::And,
+            "OR" => Lexeme::Or,
+            "NOT" => Lexeme::Not,
+            term => Lexeme::Term(term),
+        }
+    }
+
+    let mut lexemes = vec![];
+    let mut word_start = None;
+
+    for (pos, c) in text.char_indices() {
+        if c == '(' || c == ')' || c.is_whitespace() {
+            if let Some(start) = word_start.take() {
+                lexemes.push(word(&text[start..pos]));
+            }
+            match c {
+                '(' => lexemes.push(Lexeme::Open),
+                ')' => lexemes.push(Lexeme::Close),
+                _ => {}
+            }
+        } else if word_start.is_none() {
+            word_start = Some(pos);
+        }
+    }
+
+    if let Some(start) = word_start {
+        lexemes.push(word(&text[start..]));
+    }
+
+    lexemes
+}
+
+struct Parser<'a, F> {
+    lexemes: Peekable<vec::IntoIter<Lexeme<'a>>>,
+    parse_term: F,
+}
+
+impl<F: FnMut(&str) -> ParsedQuery> Parser<'_, F> {
+    fn parse_or(&mut self) -> Option<TextQuery> {
+        let mut queries = vec![self.parse_and()?];
+        while self.lexemes.next_if_eq(&Lexeme::Or).is_some() {
+            queries.push(self.parse_and()?);
+        }
+        Some(combine(queries, TextQuery::Or))
+    }
+
+    fn parse_and(&mut self) -> Option<TextQuery> {
+        let mut queries = vec![self.parse_unary()?];
+        loop {
+            match self.lexemes.peek() {
+                Some(Lexeme::And) => {
+                    self.lexemes.next();
+                }
+                // Implicit `AND`
+                Some(Lexeme::Not | Lexeme::Open | Lexeme::Term(_)) => {}
+                Some(Lexeme::Or | Lexeme::Close) | None => break,
+            }
+            queries.push(self.parse_unary()?);
+        }
+        Some(combine(queries, TextQuery::And))
+    }
+
+    fn parse_unary(&mut self) -> Option<TextQuery> {
+        match self.lexemes.next()? {
+            Lexeme::Not => Some(TextQuery::Not(Box::new(self.parse_unary()?))),
+            Lexeme::Open => {
+                let query = self.parse_or()?;
+                self.lexemes.next_if_eq(&Lexeme::Close)?;
+                Some(query)
+            }
+            Lexeme::Term(term) => Some(TextQuery::Tokens((self.parse_term)(term))),
+            Lexeme::Close | Lexeme::And | Lexeme::Or => None,
+        }
+    }
+}
+
+fn combine(mut queries: Vec<TextQuery>, operator: fn(Vec<TextQuery>) -> TextQuery) -> TextQuery {
+    if queries.len() == 1 {
+        queries.pop().unwrap()
+    } else {
+        operator(queries)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Render the query structure, with term token ids as leaves
+    fn render(query: &TextQuery) -> String {
+        let join = |queries: &[TextQuery], operator: &str| {
+            let queries: Vec<_> = queries.iter().map(render).collect();
+            format!("({})", queries.join(operator))
+        };
+        match query {
+            TextQuery::Tokens(query) => format!("{:?}", query.tokens),
+            TextQuery::And(queries) => join(queries, " & "),
+            TextQuery::Or(queries) => join(queries, " | "),
+            TextQuery::Not(query) => format!("!{}", render(query)),
+        }
+    }
+
+    fn parse(text: &str) -> Option<String> {
+        let parse_term = |term: &str| ParsedQuery {
+            tokens: vec![term.parse().ok()],
+        };
+        TextQuery::parse(text, parse_term).map(|query| render(&query))
+    }
+
+    #[test]
+    fn test_parse_boolean_query() {
+        assert_eq!(parse("1 2 3"), None);
+        assert_eq!(
+            parse("1 AND (2 OR 3) NOT 4").as_deref(),
+            Some("([Some(1)] & ([Some(2)] | [Some(3)]) & ![Some(4)])"),
+        );
+        assert_eq!(
+            parse("1 OR 2 3").as_deref(),
+            Some("([Some(1)] | ([Some(2)] & [Some(3)]))"),
+        );
+        assert_eq!(parse("NOT NOT (1)").as_deref(), Some("!![Some(1)]"));
+
+        // Malformed queries
+        assert_eq!(parse("(1 OR 2"), None);
+        assert_eq!(parse("1 OR 2)"), None);
+        assert_eq!(parse("1 AND"), None);
+        assert_eq!(parse("OR 1"), None);
+        assert_eq!(parse("1 NOT"), None);
+        assert_eq!(parse("()"), None);
+    }
+}
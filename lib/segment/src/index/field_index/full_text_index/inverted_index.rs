@@ -1,11 +1,14 @@
 use std::collections::{BTreeSet, HashMap};
 
 use common::types::PointOffsetType;
+use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 
+use super::boolean_query::TextQuery;
 use super::posting_list::{CompressedPostingList, PostingList};
 use super::postings_iterator::{
-    intersect_compressed_postings_iterator, intersect_postings_iterator,
+    difference_sorted_iterators, intersect_compressed_postings_iterator,
+    intersect_postings_iterator, intersect_sorted_iterators, merge_sorted_iterators,
 };
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::index::field_index::{CardinalityEstimation, PayloadBlockCondition, PrimaryCondition};
@@ -13,6 +16,15 @@ use crate::types::{FieldCondition, Match, PayloadKeyType};
 
 pub type TokenId = u32;
 
+/// Token of the vocabulary with the number of points containing it, see
+/// [`InvertedIndex::vocab_iter`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VocabEntry {
+    pub token: String,
+    pub token_id: TokenId,
+    pub posting_length: usize,
+}
+
 #[derive(Default, Serialize, Deserialize, Debug, Clone)]
 pub struct Document {
     tokens: Vec<TokenId>,
@@ -74,15 +86,19 @@ impl InvertedIndex {
     }
 
     pub fn document_from_tokens(&mut self, tokens: &BTreeSet<String>) -> Document {
-        let vocab = match self {
-            InvertedIndex::Mutable(index) => &mut index.vocab,
-            InvertedIndex::Immutable(index) => &mut index.vocab,
-        };
-        Self::document_from_tokens_impl(vocab, tokens)
+        match self {
+            InvertedIndex::Mutable(index) => {
+                Self::document_from_tokens_impl(&mut index.vocab, &mut index.free_token_ids, tokens)
+            }
+            InvertedIndex::Immutable(index) => {
+                Self::document_from_tokens_impl(&mut index.vocab, &mut vec![], tokens)
+            }
+        }
     }
 
     fn document_from_tokens_impl(
         vocab: &mut HashMap<String, TokenId>,
+        free_token_ids: &mut Vec<TokenId>,
         tokens: &BTreeSet<String>,
     ) -> Document {
         let mut document_tokens = vec![];
@@ -91,7 +107,7 @@ impl InvertedIndex {
             let vocab_idx = match vocab.get(token) {
                 Some(&idx) => idx,
                 None => {
-                    let next_token_id = vocab.len() as TokenId;
+                    let next_token_id = Self::next_token_id(vocab, free_token_ids);
                     vocab.insert(token.to_string(), next_token_id);
                     next_token_id
                 }
@@ -102,6 +118,16 @@ impl InvertedIndex {
         Document::new(document_tokens)
     }
 
+    fn next_token_id(
+        vocab: &HashMap<String, TokenId>,
+        free_token_ids: &mut Vec<TokenId>,
+    ) -> TokenId {
+        // Every allocated id is either in vocab or free, so the next fresh one is the total of both
+        free_token_ids
+            .pop()
+            .unwrap_or_else(|| (vocab.len() + free_token_ids.len()) as TokenId)
+    }
+
     pub fn index_document(
         &mut self,
         idx: PointOffsetType,
@@ -125,6 +151,43 @@ impl InvertedIndex {
         }
     }
 
+    /// Free vocabulary ids of tokens which are not part of any indexed document anymore, so new
+    /// tokens can reuse them. Returns the number of reclaimed ids.
+    ///
+    /// Immutable index does not reclaim ids, they are reclaimed when it is built on load.
+    pub fn reclaim_vocab_ids(&mut self) -> usize {
+        match self {
+            InvertedIndex::Mutable(index) => index.reclaim_vocab_ids(),
+            InvertedIndex::Immutable(_) => 0,
+        }
+    }
+
+    /// Iterate over the vocabulary as `(token, token_id, posting_length)`, where the posting length
+    /// is the number of indexed points containing the token
+    pub fn vocab_iter(&self) -> Box<dyn Iterator<Item = (&str, TokenId, usize)> + '_> {
+        match self {
+            InvertedIndex::Mutable(index) => Box::new(index.vocab_iter()),
+            InvertedIndex::Immutable(index) => Box::new(index.vocab_iter()),
+        }
+    }
+
+    /// Add tokens to the vocabulary without indexing any document, so documents indexed later
+    /// don't need to extend it. Returns the number of added tokens.
+    ///
+    /// Added tokens are not part of any document, so [`Self::reclaim_vocab_ids`] frees them again.
+    /// Immutable index can't be prewarmed, its vocabulary is built from storage on load.
+    pub fn prewarm_vocab(
+        &mut self,
+        tokens: impl IntoIterator<Item = String>,
+    ) -> OperationResult<usize> {
+        match self {
+            InvertedIndex::Mutable(index) => Ok(index.prewarm_vocab(tokens)),
+            InvertedIndex::Immutable(_index) => Err(OperationError::service_error(
+                "Can't prewarm vocabulary of immutable text index",
+            )),
+        }
+    }
+
     pub fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         match self {
             InvertedIndex::Mutable(index) => index.filter(query),
@@ -132,6 +195,110 @@ impl InvertedIndex {
         }
     }
 
+    /// Points matching boolean `query`, in increasing order
+    pub fn filter_text_query(
+        &self,
+        query: &TextQuery,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match query {
+            TextQuery::Tokens(query) => self.filter(query),
+            TextQuery::And(queries) => queries
+                .iter()
+                .map(|query| self.filter_text_query(query))
+                .reduce(intersect_sorted_iterators)
+                .unwrap_or_else(|| Box::new(std::iter::empty())),
+            TextQuery::Or(queries) => merge_sorted_iterators(
+                queries
+                    .iter()
+                    .map(|query| self.filter_text_query(query))
+                    .collect_vec(),
+            ),
+            TextQuery::Not(query) => {
+                difference_sorted_iterators(self.indexed_points(), self.filter_text_query(query))
+            }
+        }
+    }
+
+    /// All points with an indexed document, in increasing order
+    fn indexed_points(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            InvertedIndex::Mutable(index) => Box::new(
+                index
+                    .point_to_docs
+                    .iter()
+                    .positions(|doc| doc.is_some())
+                    .map(|idx| idx as PointOffsetType),
+            ),
+            InvertedIndex::Immutable(index) => Box::new(
+                index
+                    .point_documents_tokens
+                    .iter()
+                    .positions(|doc| doc.is_some())
+                    .map(|idx| idx as PointOffsetType),
+            ),
+        }
+    }
+
+    pub fn estimate_text_query_cardinality(
+        &self,
+        query: &TextQuery,
+        condition: &FieldCondition,
+    ) -> CardinalityEstimation {
+        let points_count = self.points_count();
+        let estimation = |min: usize, exp: usize, max: usize| CardinalityEstimation {
+            primary_clauses: vec![PrimaryCondition::Condition(condition.clone())],
+            min,
+            exp: exp.max(min).min(max),
+            max,
+        };
+
+        if points_count == 0 {
+            return estimation(0, 0, 0);
+        }
+
+        let estimate_all = |queries: &[TextQuery]| {
+            queries
+                .iter()
+                .map(|query| self.estimate_text_query_cardinality(query, condition))
+                .collect_vec()
+        };
+        let fraction =
+            |estimation: &CardinalityEstimation| estimation.exp as f64 / points_count as f64;
+
+        match query {
+            TextQuery::Tokens(query) => self.estimate_cardinality(query, condition),
+            TextQuery::And(queries) => {
+                let estimations = estimate_all(queries);
+                let max = estimations.iter().map(|e| e.max).min().unwrap_or(0);
+                let expected_frac: f64 = estimations.iter().map(fraction).product();
+                estimation(0, (expected_frac * points_count as f64) as usize, max)
+            }
+            TextQuery::Or(queries) => {
+                let estimations = estimate_all(queries);
+                let min = estimations.iter().map(|e| e.min).max().unwrap_or(0);
+                let max = estimations
+                    .iter()
+                    .map(|e| e.max)
+                    .sum::<usize>()
+                    .min(points_count);
+                let missing_frac: f64 = estimations.iter().map(|e| 1.0 - fraction(e)).product();
+                estimation(
+                    min,
+                    ((1.0 - missing_frac) * points_count as f64) as usize,
+                    max,
+                )
+            }
+            TextQuery::Not(query) => {
+                let inner = self.estimate_text_query_cardinality(query, condition);
+                estimation(
+                    points_count.saturating_sub(inner.max),
+                    points_count.saturating_sub(inner.exp),
+                    points_count.saturating_sub(inner.min),
+                )
+            }
+        }
+    }
+
     pub fn estimate_cardinality(
         &self,
         query: &ParsedQuery,
@@ -239,12 +406,16 @@ impl InvertedIndex {
         }
     }
 
+    /// Build the index from stored documents, keeping token ids of the stored `vocab`
+    ///
+    /// Ids of stored tokens, which are not part of any document anymore, are reclaimed.
     pub fn build_index(
         &mut self,
+        vocab: HashMap<String, TokenId>,
         iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
     ) -> OperationResult<()> {
         let mut index = MutableInvertedIndex::default();
-        index.build_index(iter)?;
+        index.build_index(vocab, iter)?;
 
         match self {
             InvertedIndex::Mutable(i) => {
@@ -258,6 +429,12 @@ impl InvertedIndex {
         Ok(())
     }
 
+    pub fn check_text_query_match(&self, query: &TextQuery, point_id: PointOffsetType) -> bool {
+        // Negated queries only match points with a document
+        !self.values_is_empty(point_id)
+            && query.check(&|parsed_query| self.check_match(parsed_query, point_id))
+    }
+
     pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         match self {
             InvertedIndex::Mutable(index) => index.check_match(parsed_query, point_id),
@@ -292,12 +469,29 @@ impl InvertedIndex {
             InvertedIndex::Immutable(index) => index.vocab.get(token).copied(),
         }
     }
+
+    /// Ids of all tokens of the vocabulary starting with `prefix`, in increasing order
+    pub fn prefix_tokens(&self, prefix: &str) -> Vec<TokenId> {
+        let vocab = match self {
+            InvertedIndex::Mutable(index) => &index.vocab,
+            InvertedIndex::Immutable(index) => &index.vocab,
+        };
+        let mut token_ids: Vec<_> = vocab
+            .iter()
+            .filter(|(token, _)| token.starts_with(prefix))
+            .map(|(_, &token_id)| token_id)
+            .collect();
+        token_ids.sort_unstable();
+        token_ids
+    }
 }
 
 #[derive(Default)]
 pub struct MutableInvertedIndex {
     postings: Vec<Option<PostingList>>,
     vocab: HashMap<String, TokenId>,
+    /// Ids of removed tokens, available for reuse
+    free_token_ids: Vec<TokenId>,
     point_to_docs: Vec<Option<Document>>,
     points_count: usize,
 }
@@ -305,13 +499,25 @@ pub struct MutableInvertedIndex {
 impl MutableInvertedIndex {
     fn build_index(
         &mut self,
+        vocab: HashMap<String, TokenId>,
         iter: impl Iterator<Item = OperationResult<(PointOffsetType, BTreeSet<String>)>>,
     ) -> OperationResult<()> {
         self.points_count = 0;
-        self.vocab.clear();
         self.postings.clear();
         self.point_to_docs.clear();
 
+        // Ids missing from the stored vocab are free
+        let next_token_id = vocab.values().max().map_or(0, |&max_id| max_id + 1);
+        let mut used_ids = vec![false; next_token_id as usize];
+        for &token_id in vocab.values() {
+            used_ids[token_id as usize] = true;
+        }
+        self.free_token_ids = (0..next_token_id)
+            .rev()
+            .filter(|&token_id| !used_ids[token_id as usize])
+            .collect();
+        self.vocab = vocab;
+
         // update point_to_docs
         for i in iter {
             self.points_count += 1;
@@ -322,7 +528,11 @@ impl MutableInvertedIndex {
                     .resize_with(idx as usize + 1, Default::default);
             }
 
-            let document = InvertedIndex::document_from_tokens_impl(&mut self.vocab, &tokens);
+            let document = InvertedIndex::document_from_tokens_impl(
+                &mut self.vocab,
+                &mut self.free_token_ids,
+                &tokens,
+            );
             self.point_to_docs[idx as usize] = Some(document);
         }
 
@@ -347,6 +557,8 @@ impl MutableInvertedIndex {
             }
         }
 
+        self.reclaim_vocab_ids();
+
         Ok(())
     }
 
@@ -396,6 +608,43 @@ impl MutableInvertedIndex {
         true
     }
 
+    fn reclaim_vocab_ids(&mut self) -> usize {
+        let free_before = self.free_token_ids.len();
+        let postings = &mut self.postings;
+        let free_token_ids = &mut self.free_token_ids;
+
+        self.vocab.retain(|_token, &mut token_id| {
+            if let Some(posting) = postings.get_mut(token_id as usize) {
+                if posting.as_ref().map_or(false, |posting| posting.len() > 0) {
+                    return true;
+                }
+                *posting = None;
+            }
+            free_token_ids.push(token_id);
+            false
+        });
+
+        self.free_token_ids.len() - free_before
+    }
+
+    fn prewarm_vocab(&mut self, tokens: impl IntoIterator<Item = String>) -> usize {
+        let mut added = 0;
+        for token in tokens {
+            if self.vocab.contains_key(&token) {
+                continue;
+            }
+            let token_id = InvertedIndex::next_token_id(&self.vocab, &mut self.free_token_ids);
+            // Queries expect a posting entry for every token of the vocabulary
+            if self.postings.len() <= token_id as usize {
+                self.postings
+                    .resize_with(token_id as usize + 1, Default::default);
+            }
+            self.vocab.insert(token, token_id);
+            added += 1;
+        }
+        added
+    }
+
     fn filter(&self, query: &ParsedQuery) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let postings_opt: Option<Vec<_>> = query
             .tokens
@@ -449,6 +698,16 @@ impl MutableInvertedIndex {
             }
         })
     }
+
+    fn vocab_iter(&self) -> impl Iterator<Item = (&str, TokenId, usize)> + '_ {
+        self.vocab.iter().map(|(token, &token_id)| {
+            let posting_length = match self.postings.get(token_id as usize) {
+                Some(Some(postings)) => postings.len(),
+                _ => 0,
+            };
+            (token.as_str(), token_id, posting_length)
+        })
+    }
 }
 
 #[derive(Default)]
@@ -541,6 +800,20 @@ impl ImmutableInvertedIndex {
             }
         })
     }
+
+    fn vocab_iter(&self) -> impl Iterator<Item = (&str, TokenId, usize)> + '_ {
+        self.vocab.iter().map(|(token, &token_id)| {
+            // Removed documents are still in the postings, don't count them
+            let posting_length = match self.postings.get(token_id as usize) {
+                Some(Some(postings)) => postings
+                    .iter()
+                    .filter(|&idx| !self.values_is_empty(idx))
+                    .count(),
+                _ => 0,
+            };
+            (token.as_str(), token_id, posting_length)
+        })
+    }
 }
 
 impl From<MutableInvertedIndex> for ImmutableInvertedIndex {
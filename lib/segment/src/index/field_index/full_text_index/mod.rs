@@ -1,8 +1,11 @@
+mod boolean_query;
 mod inverted_index;
 mod posting_list;
 mod postings_iterator;
 pub mod text_index;
 mod tokenizers;
 
+pub use inverted_index::VocabEntry;
+
 #[cfg(test)]
 mod tests;
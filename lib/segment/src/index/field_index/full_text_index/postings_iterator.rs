@@ -1,4 +1,5 @@
 use common::types::PointOffsetType;
+use itertools::{EitherOrBoth, Itertools};
 
 use super::posting_list::{CompressedPostingList, CompressedPostingVisitor, PostingList};
 
@@ -49,6 +50,41 @@ pub fn intersect_compressed_postings_iterator<'a>(
     Box::new(and_iter)
 }
 
+/// Intersection of two iterators over increasing point offsets
+pub fn intersect_sorted_iterators<'a>(
+    left: impl Iterator<Item = PointOffsetType> + 'a,
+    right: impl Iterator<Item = PointOffsetType> + 'a,
+) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+    Box::new(
+        left.merge_join_by(right, Ord::cmp)
+            .filter_map(|item| match item {
+                EitherOrBoth::Both(idx, _) => Some(idx),
+                EitherOrBoth::Left(_) | EitherOrBoth::Right(_) => None,
+            }),
+    )
+}
+
+/// Union of iterators over increasing point offsets, without duplicates
+pub fn merge_sorted_iterators<'a>(
+    iterators: impl IntoIterator<Item = Box<dyn Iterator<Item = PointOffsetType> + 'a>>,
+) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+    Box::new(iterators.into_iter().kmerge().dedup())
+}
+
+/// Offsets of `left` which are not in `right`, both iterating over increasing point offsets
+pub fn difference_sorted_iterators<'a>(
+    left: impl Iterator<Item = PointOffsetType> + 'a,
+    right: impl Iterator<Item = PointOffsetType> + 'a,
+) -> Box<dyn Iterator<Item = PointOffsetType> + 'a> {
+    Box::new(
+        left.merge_join_by(right, Ord::cmp)
+            .filter_map(|item| match item {
+                EitherOrBoth::Left(idx) => Some(idx),
+                EitherOrBoth::Both(_, _) | EitherOrBoth::Right(_) => None,
+            }),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
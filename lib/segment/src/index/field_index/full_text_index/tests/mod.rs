@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use common::types::PointOffsetType;
 use rstest::rstest;
 use tempfile::Builder;
@@ -201,3 +203,106 @@ fn test_prefix_search(#[case] immutable: bool) {
 
     assert_eq!(res.len(), 0);
 }
+
+#[test]
+fn test_vocab_ids_reuse() {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Word,
+        min_token_len: None,
+        max_token_len: None,
+        lowercase: None,
+    };
+
+    let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+    let mut index = FullTextIndex::builder(db, config, "text")
+        .make_empty()
+        .unwrap();
+
+    let token_id = |index: &FullTextIndex, token: &str| index.parse_query(token).tokens[0];
+
+    index.add_many(0, vec!["alpha beta".to_string()]).unwrap();
+    index.add_many(1, vec!["beta gamma".to_string()]).unwrap();
+
+    let alpha_id = token_id(&index, "alpha").unwrap();
+
+    // Tokens still referenced by documents are not reclaimed
+    assert_eq!(index.reclaim_vocab_ids(), 0);
+
+    index.remove_point(0).unwrap();
+    assert_eq!(index.reclaim_vocab_ids(), 1);
+    assert_eq!(token_id(&index, "alpha"), None);
+    assert!(token_id(&index, "beta").is_some());
+
+    // New token reuses the reclaimed id, and only matches its own documents
+    index.add_many(2, vec!["delta".to_string()]).unwrap();
+    assert_eq!(token_id(&index, "delta"), Some(alpha_id));
+    assert_eq!(index.query("delta").collect::<Vec<_>>(), vec![2]);
+    assert_eq!(index.query("alpha").count(), 0);
+
+    // Fresh ids do not collide with live ones
+    index.add_many(3, vec!["epsilon".to_string()]).unwrap();
+    let token_ids: HashSet<_> = ["beta", "gamma", "delta", "epsilon"]
+        .into_iter()
+        .map(|token| token_id(&index, token).unwrap())
+        .collect();
+    assert_eq!(token_ids.len(), 4);
+    assert_eq!(index.query("beta").collect::<Vec<_>>(), vec![1]);
+}
+
+#[test]
+fn test_vocab_ids_reclaimed_on_load() {
+    let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+    let config = TextIndexParams {
+        r#type: TextIndexType::Text,
+        tokenizer: TokenizerType::Word,
+        min_token_len: None,
+        max_token_len: None,
+        lowercase: None,
+    };
+
+    let token_id = |index: &FullTextIndex, token: &str| index.parse_query(token).tokens[0];
+
+    let (alpha_id, beta_id) = {
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::builder(db, config.clone(), "text")
+            .make_empty()
+            .unwrap();
+
+        index.add_many(0, vec!["alpha beta".to_string()]).unwrap();
+        index.add_many(1, vec!["beta gamma".to_string()]).unwrap();
+        index.remove_point(0).unwrap();
+        index.flusher()().unwrap();
+
+        (
+            token_id(&index, "alpha").unwrap(),
+            token_id(&index, "beta").unwrap(),
+        )
+    };
+
+    {
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config.clone(), "text", true);
+        assert!(index.load().unwrap());
+
+        // Removed token is reclaimed on load, ids of the others are kept
+        assert_eq!(token_id(&index, "alpha"), None);
+        assert_eq!(token_id(&index, "beta"), Some(beta_id));
+
+        index.add_many(2, vec!["delta".to_string()]).unwrap();
+        assert_eq!(token_id(&index, "delta"), Some(alpha_id));
+        index.flusher()().unwrap();
+    }
+
+    {
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new(db, config, "text", false);
+        assert!(index.load().unwrap());
+
+        assert_eq!(token_id(&index, "alpha"), None);
+        assert_eq!(token_id(&index, "beta"), Some(beta_id));
+        assert_eq!(token_id(&index, "delta"), Some(alpha_id));
+        assert_eq!(index.query("delta").collect::<Vec<_>>(), vec![2]);
+    }
+}
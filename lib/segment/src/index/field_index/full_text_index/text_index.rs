@@ -1,4 +1,5 @@
-use std::collections::{BTreeSet, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::mem::size_of;
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -13,8 +14,9 @@ use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDelet
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::index::TextIndexParams;
+use crate::index::field_index::full_text_index::boolean_query::TextQuery;
 use crate::index::field_index::full_text_index::inverted_index::{
-    Document, InvertedIndex, ParsedQuery,
+    Document, InvertedIndex, ParsedQuery, TokenId, VocabEntry,
 };
 use crate::index::field_index::full_text_index::tokenizers::Tokenizer;
 use crate::index::field_index::{
@@ -22,21 +24,48 @@ use crate::index::field_index::{
     ValueIndexer,
 };
 use crate::telemetry::PayloadIndexTelemetry;
-use crate::types::{FieldCondition, Match, PayloadKeyType};
+use crate::types::{FieldCondition, Match, MatchText, PayloadKeyType};
 
 pub struct FullTextIndex {
     inverted_index: InvertedIndex,
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    /// Token ids of the vocabulary, so ids are kept and reclaimed ones are reused across loads
+    vocab_db_wrapper: DatabaseColumnWrapper,
     config: TextIndexParams,
+    /// Reject all changes of the index, in memory and in the storage
+    read_only: bool,
 }
 
 impl FullTextIndex {
+    /// Same encoding as bincode, which was used to store keys before
     fn store_key(id: &PointOffsetType) -> Vec<u8> {
-        bincode::serialize(&id).unwrap()
+        id.to_le_bytes().to_vec()
     }
 
-    fn restore_key(data: &[u8]) -> PointOffsetType {
-        bincode::deserialize(data).unwrap()
+    /// Decode a key, which must be exactly as long as an encoded id
+    ///
+    /// Unlike bincode, trailing bytes are not ignored, so a malformed key is never silently
+    /// truncated into a wrong id.
+    fn restore_key(data: &[u8]) -> OperationResult<PointOffsetType> {
+        let bytes = <[u8; size_of::<PointOffsetType>()]>::try_from(data).map_err(|_| {
+            OperationError::service_error(format!(
+                "Malformed key in full-text index: {} bytes, expected {}",
+                data.len(),
+                size_of::<PointOffsetType>(),
+            ))
+        })?;
+        Ok(PointOffsetType::from_le_bytes(bytes))
+    }
+
+    fn restore_token_id(data: &[u8]) -> OperationResult<TokenId> {
+        let bytes = <[u8; size_of::<TokenId>()]>::try_from(data).map_err(|_| {
+            OperationError::service_error(format!(
+                "Malformed token id in full-text index vocabulary: {} bytes, expected {}",
+                data.len(),
+                size_of::<TokenId>(),
+            ))
+        })?;
+        Ok(TokenId::from_le_bytes(bytes))
     }
 
     fn serialize_document_tokens(tokens: BTreeSet<String>) -> OperationResult<Vec<u8>> {
@@ -66,6 +95,10 @@ impl FullTextIndex {
         format!("{field}_fts")
     }
 
+    fn vocab_cf_name(field: &str) -> String {
+        format!("{field}_fts_vocab")
+    }
+
     pub fn new(
         db: Arc<RwLock<DB>>,
         config: TextIndexParams,
@@ -73,6 +106,7 @@ impl FullTextIndex {
         is_appendable: bool,
     ) -> Self {
         let store_cf_name = Self::storage_cf_name(field);
+        let vocab_db_wrapper = DatabaseColumnWrapper::new(db.clone(), &Self::vocab_cf_name(field));
         let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
             db,
             &store_cf_name,
@@ -80,10 +114,30 @@ impl FullTextIndex {
         FullTextIndex {
             inverted_index: InvertedIndex::new(is_appendable),
             db_wrapper,
+            vocab_db_wrapper,
             config,
+            read_only: false,
         }
     }
 
+    /// Open the index from the same storage as [`Self::new`], but reject any changes of it
+    ///
+    /// Allows to query an index, without risking to write into its storage, e.g. during a
+    /// snapshot export. The index must be loaded before querying it.
+    pub fn new_read_only(db: Arc<RwLock<DB>>, config: TextIndexParams, field: &str) -> Self {
+        FullTextIndex {
+            read_only: true,
+            ..Self::new(db, config, field, false)
+        }
+    }
+
+    fn check_writable(&self) -> OperationResult<()> {
+        if self.read_only {
+            return Err(OperationError::service_error("index is read-only"));
+        }
+        Ok(())
+    }
+
     pub fn builder(
         db: Arc<RwLock<DB>>,
         config: TextIndexParams,
@@ -111,6 +165,94 @@ impl FullTextIndex {
         }
     }
 
+    /// Parse query text, with support for `AND`, `OR` and `NOT` operators
+    ///
+    /// Text without operators, or with malformed ones, is parsed as a plain query.
+    pub fn parse_text_query(&self, text: &str) -> TextQuery {
+        TextQuery::parse(text, |term| self.parse_query(term))
+            .unwrap_or_else(|| TextQuery::Tokens(self.parse_query(text)))
+    }
+
+    /// Parse query matching documents with any token starting with `prefix`
+    ///
+    /// The prefix is not tokenized, so it works the same with any tokenizer. It is only
+    /// lowercased, if tokens of the index are. Postings of all matching tokens are unioned.
+    pub fn parse_prefix_query(&self, prefix: &str) -> TextQuery {
+        let prefix = if self.config.lowercase.unwrap_or(true) {
+            prefix.to_lowercase()
+        } else {
+            prefix.to_owned()
+        };
+        TextQuery::Or(
+            self.inverted_index
+                .prefix_tokens(&prefix)
+                .into_iter()
+                .map(|token_id| {
+                    TextQuery::Tokens(ParsedQuery {
+                        tokens: vec![Some(token_id)],
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    /// Parse query of the text match condition, either plain, boolean or prefix
+    pub fn parse_match_text(&self, text_match: &MatchText) -> TextQuery {
+        if text_match.prefix {
+            self.parse_prefix_query(&text_match.text)
+        } else if text_match.boolean {
+            self.parse_text_query(&text_match.text)
+        } else {
+            TextQuery::Tokens(self.parse_query(&text_match.text))
+        }
+    }
+
+    /// Check the text match condition on text of a field without a full-text index
+    ///
+    /// Boolean and prefix queries are checked against tokens of the text, as if it was indexed
+    /// with default parameters. Plain queries are matched as a substring of the text.
+    pub fn check_unindexed_match(text_match: &MatchText, text: &str) -> bool {
+        let config = TextIndexParams::default();
+
+        if text_match.prefix {
+            let prefix = if config.lowercase.unwrap_or(true) {
+                text_match.text.to_lowercase()
+            } else {
+                text_match.text.clone()
+            };
+            let mut is_match = false;
+            Tokenizer::tokenize_doc(text, &config, |token| {
+                is_match |= token.starts_with(&prefix);
+            });
+            return is_match;
+        }
+
+        if !text_match.boolean {
+            return text.contains(&text_match.text);
+        }
+
+        // Vocabulary of the query tokens only, other tokens of the text can't match anything
+        let mut vocab = HashMap::new();
+        let mut parse_term = |term: &str| {
+            let mut tokens = HashSet::new();
+            Tokenizer::tokenize_query(term, &config, |token| {
+                let next_id = vocab.len() as TokenId;
+                tokens.insert(Some(*vocab.entry(token.to_owned()).or_insert(next_id)));
+            });
+            ParsedQuery {
+                tokens: tokens.into_iter().collect(),
+            }
+        };
+        let query = TextQuery::parse(&text_match.text, &mut parse_term)
+            .unwrap_or_else(|| TextQuery::Tokens(parse_term(&text_match.text)));
+
+        let mut document_tokens = vec![];
+        Tokenizer::tokenize_doc(text, &config, |token| {
+            document_tokens.extend(vocab.get(token).copied());
+        });
+        query.check_match(&Document::new(document_tokens))
+    }
+
     pub fn parse_document(&self, text: &str) -> Document {
         let mut document_tokens = vec![];
         Tokenizer::tokenize_doc(text, &self.config, |token| {
@@ -121,6 +263,100 @@ impl FullTextIndex {
         Document::new(document_tokens)
     }
 
+    /// Free vocabulary ids of tokens removed from all documents, see
+    /// [`InvertedIndex::reclaim_vocab_ids`]
+    pub fn reclaim_vocab_ids(&mut self) -> usize {
+        self.inverted_index.reclaim_vocab_ids()
+    }
+
+    /// Store ids of the given tokens of the vocabulary
+    fn store_tokens<'a>(&self, tokens: impl IntoIterator<Item = &'a str>) -> OperationResult<()> {
+        for token in tokens {
+            if let Some(token_id) = self.inverted_index.get_token(token) {
+                self.vocab_db_wrapper.put(token, token_id.to_le_bytes())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn load_vocab(&self) -> OperationResult<HashMap<String, TokenId>> {
+        if !self.vocab_db_wrapper.has_column_family()? {
+            return Ok(HashMap::new());
+        }
+        let db = self.vocab_db_wrapper.lock_db();
+        let mut vocab = HashMap::new();
+        for (token, token_id) in db.iter()? {
+            let token = String::from_utf8(token.into_vec()).map_err(|err| {
+                OperationError::service_error(format!(
+                    "Malformed token in full-text index vocabulary: {err}"
+                ))
+            })?;
+            vocab.insert(token, Self::restore_token_id(&token_id)?);
+        }
+        Ok(vocab)
+    }
+
+    /// Bring the stored vocabulary in line with the loaded one: drop reclaimed tokens, and add
+    /// tokens of documents stored before the vocabulary was
+    fn store_loaded_vocab(&self, stored_vocab: &HashMap<String, TokenId>) -> OperationResult<()> {
+        self.vocab_db_wrapper.create_column_family_if_not_exists()?;
+
+        for (token, token_id) in stored_vocab {
+            if self.inverted_index.get_token(token) != Some(*token_id) {
+                self.vocab_db_wrapper.remove(token)?;
+            }
+        }
+
+        let new_tokens: Vec<_> = self
+            .inverted_index
+            .vocab_iter()
+            .filter(|(token, _, _)| !stored_vocab.contains_key(*token))
+            .map(|(token, _, _)| token)
+            .collect();
+        self.store_tokens(new_tokens)
+    }
+
+    /// Dump the vocabulary of the index, ordered by token id
+    ///
+    /// The vocabulary is kept in memory, so the dump doesn't read or lock the storage.
+    pub fn export_vocab(&self) -> Vec<VocabEntry> {
+        let mut vocab: Vec<_> = self
+            .inverted_index
+            .vocab_iter()
+            .map(|(token, token_id, posting_length)| VocabEntry {
+                token: token.to_owned(),
+                token_id,
+                posting_length,
+            })
+            .collect();
+        vocab.sort_unstable_by_key(|entry| entry.token_id);
+        vocab
+    }
+
+    /// Prewarm the vocabulary from a dump of [`Self::export_vocab`], see
+    /// [`InvertedIndex::prewarm_vocab`]
+    ///
+    /// Token ids of the dump are not preserved, missing tokens are added in the order of their
+    /// ids. Returns the number of added tokens.
+    pub fn import_vocab(
+        &mut self,
+        vocab: impl IntoIterator<Item = VocabEntry>,
+    ) -> OperationResult<usize> {
+        self.check_writable()?;
+        let mut vocab: Vec<_> = vocab.into_iter().collect();
+        vocab.sort_unstable_by_key(|entry| entry.token_id);
+        let new_tokens: Vec<_> = vocab
+            .into_iter()
+            .map(|entry| entry.token)
+            .filter(|token| self.inverted_index.get_token(token).is_none())
+            .collect();
+        let added = self
+            .inverted_index
+            .prewarm_vocab(new_tokens.iter().cloned())?;
+        self.store_tokens(new_tokens.iter().map(String::as_str))?;
+        Ok(added)
+    }
+
     #[cfg(test)]
     pub fn query(&self, query: &str) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
         let parsed_query = self.parse_query(query);
@@ -138,6 +374,10 @@ impl FullTextIndex {
     pub fn check_match(&self, parsed_query: &ParsedQuery, point_id: PointOffsetType) -> bool {
         self.inverted_index.check_match(parsed_query, point_id)
     }
+
+    pub fn check_text_query_match(&self, query: &TextQuery, point_id: PointOffsetType) -> bool {
+        self.inverted_index.check_text_query_match(query, point_id)
+    }
 }
 
 pub struct FullTextIndexBuilder(FullTextIndex);
@@ -146,7 +386,8 @@ impl FieldIndexBuilderTrait for FullTextIndexBuilder {
     type FieldIndexType = FullTextIndex;
 
     fn init(&mut self) -> OperationResult<()> {
-        self.0.db_wrapper.recreate_column_family()
+        self.0.db_wrapper.recreate_column_family()?;
+        self.0.vocab_db_wrapper.recreate_column_family()
     }
 
     fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
@@ -162,6 +403,7 @@ impl ValueIndexer for FullTextIndex {
     type ValueType = String;
 
     fn add_many(&mut self, idx: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
+        self.check_writable()?;
         if values.is_empty() {
             return Ok(());
         }
@@ -174,8 +416,15 @@ impl ValueIndexer for FullTextIndex {
             });
         }
 
+        let new_tokens: Vec<_> = tokens
+            .iter()
+            .filter(|token| self.inverted_index.get_token(token).is_none())
+            .cloned()
+            .collect();
+
         let document = self.inverted_index.document_from_tokens(&tokens);
         self.inverted_index.index_document(idx, document)?;
+        self.store_tokens(new_tokens.iter().map(String::as_str))?;
 
         let db_idx = Self::store_key(&idx);
         let db_document = Self::serialize_document_tokens(tokens)?;
@@ -193,6 +442,7 @@ impl ValueIndexer for FullTextIndex {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
+        self.check_writable()?;
         if self.inverted_index.remove_document(id) {
             let db_doc_id = Self::store_key(&id);
             self.db_wrapper.remove(db_doc_id)?;
@@ -211,23 +461,37 @@ impl PayloadFieldIndex for FullTextIndex {
             return Ok(false);
         };
 
+        let stored_vocab = self.load_vocab()?;
+
         let db = self.db_wrapper.lock_db();
         let i = db.iter()?.map(|(key, value)| {
-            let idx = Self::restore_key(&key);
+            let idx = Self::restore_key(&key)?;
             let tokens = Self::deserialize_document(&value)?;
             Ok((idx, tokens))
         });
-        self.inverted_index.build_index(i)?;
+        self.inverted_index.build_index(stored_vocab.clone(), i)?;
+        drop(db);
+
+        if !self.read_only {
+            self.store_loaded_vocab(&stored_vocab)?;
+        }
 
         Ok(true)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper.remove_column_family()
+        self.check_writable()?;
+        self.db_wrapper.remove_column_family()?;
+        self.vocab_db_wrapper.remove_column_family()
     }
 
     fn flusher(&self) -> Flusher {
-        self.db_wrapper.flusher()
+        let db_flusher = self.db_wrapper.flusher();
+        let vocab_flusher = self.vocab_db_wrapper.flusher();
+        Box::new(move || {
+            db_flusher()?;
+            vocab_flusher()
+        })
     }
 
     fn files(&self) -> Vec<PathBuf> {
@@ -239,18 +503,18 @@ impl PayloadFieldIndex for FullTextIndex {
         condition: &FieldCondition,
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + '_>> {
         if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
-            return Some(self.inverted_index.filter(&parsed_query));
+            let query = self.parse_match_text(text_match);
+            return Some(self.inverted_index.filter_text_query(&query));
         }
         None
     }
 
     fn estimate_cardinality(&self, condition: &FieldCondition) -> Option<CardinalityEstimation> {
         if let Some(Match::Text(text_match)) = &condition.r#match {
-            let parsed_query = self.parse_query(&text_match.text);
+            let query = self.parse_match_text(text_match);
             return Some(
                 self.inverted_index
-                    .estimate_cardinality(&parsed_query, condition),
+                    .estimate_text_query_cardinality(&query, condition),
             );
         }
         None
@@ -267,6 +531,7 @@ impl PayloadFieldIndex for FullTextIndex {
 
 #[cfg(test)]
 mod tests {
+    use rand::Rng;
     use rstest::rstest;
     use tempfile::Builder;
 
@@ -386,4 +651,310 @@ mod tests {
             assert_eq!(index.count_indexed_points(), 2);
         }
     }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_boolean_text_query(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!([
+                "For the first time in a decade, technicians were not scurrying about the vitals of the giant computer, ",
+                "the soft lights did not wink out their erratic patterns, the flow of information in and out had halted."
+            ]),
+            serde_json::json!("It would not be halted long, of course, for the needs of peace would be pressing."),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+        };
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::builder(db.clone(), config.clone(), "text")
+            .make_empty()
+            .unwrap();
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+        }
+
+        if immutable {
+            index = FullTextIndex::new(db, config, "text", false);
+            index.load().unwrap();
+        }
+
+        let cases: [(&str, &[PointOffsetType]); 7] = [
+            ("the AND (multivac OR halted) NOT time", &[0, 3]),
+            ("multivac OR silence", &[0, 1, 4]),
+            ("giant computer OR silence", &[1, 2]),
+            ("(halted OR peace) AND NOT computer", &[3]),
+            ("NOT multivac", &[1, 2, 3]),
+            ("NOT (multivac OR the)", &[]),
+            ("unknown OR multivac", &[0, 4]),
+        ];
+
+        for (text, expected) in cases {
+            let filter_condition =
+                FieldCondition::new_match(JsonPath::new("text"), Match::new_text_boolean(text));
+            let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+            assert_eq!(search_res, expected, "query: {text}");
+
+            let query = index.parse_text_query(text);
+            for idx in 0..payloads.len() as PointOffsetType {
+                assert_eq!(
+                    index.check_text_query_match(&query, idx),
+                    expected.contains(&idx),
+                    "query: {text}, point: {idx}",
+                );
+            }
+
+            let estimation = index.estimate_cardinality(&filter_condition).unwrap();
+            assert!(estimation.min <= expected.len(), "query: {text}");
+            assert!(expected.len() <= estimation.max, "query: {text}");
+        }
+
+        // Without boolean mode, operators are matched as regular words
+        let search_res: Vec<_> = index
+            .filter(&filter_request("multivac OR silence"))
+            .unwrap()
+            .collect();
+        assert!(search_res.is_empty());
+        let search_res: Vec<_> = index
+            .filter(&filter_request("NOT multivac"))
+            .unwrap()
+            .collect();
+        assert!(search_res.is_empty());
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_prefix_query(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!("It would not be halted long, of course, for the needs of peace would be pressing."),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+        };
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::builder(db.clone(), config.clone(), "text")
+            .make_empty()
+            .unwrap();
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+        }
+
+        if immutable {
+            index = FullTextIndex::new(db, config, "text", false);
+            index.load().unwrap();
+        }
+
+        let cases: [(&str, &[PointOffsetType]); 6] = [
+            ("multi", &[0, 3]),
+            ("MULTI", &[0, 3]),
+            ("multivac", &[0, 3]),
+            ("sil", &[0, 1]),
+            // Prefix is not tokenized, no token contains a space
+            ("multivac might", &[]),
+            ("unknown", &[]),
+        ];
+
+        for (prefix, expected) in cases {
+            let filter_condition =
+                FieldCondition::new_match(JsonPath::new("text"), Match::new_text_prefix(prefix));
+            let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+            assert_eq!(search_res, expected, "prefix: {prefix}");
+
+            let query = index.parse_prefix_query(prefix);
+            for idx in 0..payloads.len() as PointOffsetType {
+                assert_eq!(
+                    index.check_text_query_match(&query, idx),
+                    expected.contains(&idx),
+                    "prefix: {prefix}, point: {idx}",
+                );
+            }
+
+            let estimation = index.estimate_cardinality(&filter_condition).unwrap();
+            assert!(estimation.min <= expected.len(), "prefix: {prefix}");
+            assert!(expected.len() <= estimation.max, "prefix: {prefix}");
+        }
+
+        // Removed documents don't match anymore
+        index.remove_point(3).unwrap();
+        let filter_condition =
+            FieldCondition::new_match(JsonPath::new("text"), Match::new_text_prefix("multi"));
+        let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+        assert_eq!(search_res, vec![0]);
+    }
+
+    #[rstest]
+    #[case(true)]
+    #[case(false)]
+    fn test_export_import_vocab(#[case] immutable: bool) {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!("It would not be halted long, of course, for the needs of peace would be pressing."),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+        };
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::builder(db.clone(), config.clone(), "text")
+            .make_empty()
+            .unwrap();
+        for (idx, payload) in payloads.iter().enumerate() {
+            index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+        }
+
+        if immutable {
+            index = FullTextIndex::new(db.clone(), config.clone(), "text", false);
+            index.load().unwrap();
+        }
+
+        // Removed points must not be counted
+        index.remove_point(2).unwrap();
+
+        let vocab = index.export_vocab();
+        assert!(!vocab.is_empty());
+        assert!(vocab
+            .windows(2)
+            .all(|pair| pair[0].token_id < pair[1].token_id));
+
+        for entry in &vocab {
+            assert_eq!(
+                index.inverted_index.get_token(&entry.token),
+                Some(entry.token_id),
+            );
+            assert_eq!(
+                entry.posting_length,
+                index.query(&entry.token).count(),
+                "token: {}",
+                entry.token,
+            );
+        }
+
+        let total_postings: usize = vocab.iter().map(|entry| entry.posting_length).sum();
+        let total_tokens: usize = (0..payloads.len() as PointOffsetType)
+            .map(|idx| index.values_count(idx))
+            .sum();
+        assert_eq!(total_postings, total_tokens);
+
+        // Prewarm an empty index from the dump
+        let mut prewarmed = FullTextIndex::builder(db, config, "prewarmed")
+            .make_empty()
+            .unwrap();
+        assert_eq!(prewarmed.import_vocab(vocab.clone()).unwrap(), vocab.len());
+        assert_eq!(prewarmed.import_vocab(vocab.clone()).unwrap(), 0);
+
+        let prewarmed_vocab = prewarmed.export_vocab();
+        assert_eq!(prewarmed_vocab.len(), vocab.len());
+        for (prewarmed_entry, entry) in prewarmed_vocab.iter().zip(&vocab) {
+            assert_eq!(prewarmed_entry.token, entry.token);
+            assert_eq!(prewarmed_entry.token_id, entry.token_id);
+            assert_eq!(prewarmed_entry.posting_length, 0);
+        }
+
+        // Prewarmed tokens are searchable once documents are indexed
+        prewarmed.add_point(0, &[&payloads[3]]).unwrap();
+        let search_res: Vec<_> = prewarmed.query("multivac").collect();
+        assert_eq!(search_res, vec![0]);
+        assert_eq!(prewarmed.export_vocab().len(), vocab.len());
+    }
+
+    #[test]
+    fn test_read_only_index() {
+        let payloads: Vec<_> = vec![
+            serde_json::json!("The celebration had a long way to go and even in the silent depths of Multivac's underground chambers, it hung in the air."),
+            serde_json::json!("If nothing else, there was the mere fact of isolation and silence."),
+            serde_json::json!("Yet now, for a day, perhaps for a week, even Multivac might celebrate the great time, and rest."),
+        ];
+
+        let temp_dir = Builder::new().prefix("test_dir").tempdir().unwrap();
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: None,
+        };
+
+        {
+            let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+            let mut index = FullTextIndex::builder(db, config.clone(), "text")
+                .make_empty()
+                .unwrap();
+            for (idx, payload) in payloads.iter().enumerate() {
+                index.add_point(idx as PointOffsetType, &[payload]).unwrap();
+            }
+            index.flusher()().unwrap();
+        }
+
+        let db = open_db_with_existing_cf(&temp_dir.path().join("test_db")).unwrap();
+        let mut index = FullTextIndex::new_read_only(db, config, "text");
+        assert!(index.load().unwrap());
+
+        // Reads work as usual
+        assert_eq!(index.count_indexed_points(), payloads.len());
+        let filter_condition = filter_request("multivac");
+        let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+        assert_eq!(search_res, vec![0, 2]);
+        let cardinality = index.estimate_cardinality(&filter_condition).unwrap();
+        assert!(cardinality.min <= 2 && 2 <= cardinality.max);
+        assert!(index.inverted_index.get_token("silence").is_some());
+
+        // Writes are rejected and change nothing
+        let payload = serde_json::json!("Multivac was silent.");
+        assert!(index.add_point(3, &[&payload]).is_err());
+        assert!(index.remove_point(0).is_err());
+        assert!(index.import_vocab(index.export_vocab()).is_err());
+        assert_eq!(index.count_indexed_points(), payloads.len());
+        let search_res: Vec<_> = index.filter(&filter_condition).unwrap().collect();
+        assert_eq!(search_res, vec![0, 2]);
+
+        assert!(index.clear().is_err());
+    }
+
+    #[test]
+    fn test_store_restore_key() {
+        let mut rng = rand::thread_rng();
+        let ids = [0, 1, PointOffsetType::MAX]
+            .into_iter()
+            .chain((0..100).map(|_| rng.gen()));
+        for id in ids {
+            let key = FullTextIndex::store_key(&id);
+            assert_eq!(key, bincode::serialize(&id).unwrap());
+            assert_eq!(FullTextIndex::restore_key(&key).unwrap(), id);
+        }
+
+        // Keys of any other length are rejected, instead of being truncated or padded
+        for len in (0..16).filter(|&len| len != size_of::<PointOffsetType>()) {
+            let key: Vec<u8> = (0..len).map(|_| rng.gen()).collect();
+            assert!(FullTextIndex::restore_key(&key).is_err(), "length {len}");
+        }
+    }
 }
@@ -159,6 +159,20 @@ impl GeoMapIndex {
         }
     }
 
+    pub fn get_values(
+        &self,
+        idx: PointOffsetType,
+    ) -> Option<Box<dyn Iterator<Item = &GeoPoint> + '_>> {
+        match self {
+            GeoMapIndex::Mutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values) as Box<dyn Iterator<Item = _>>),
+            GeoMapIndex::Immutable(index) => index
+                .get_values(idx)
+                .map(|values| Box::new(values) as Box<dyn Iterator<Item = _>>),
+        }
+    }
+
     pub fn values_count(&self, idx: PointOffsetType) -> usize {
         match self {
             GeoMapIndex::Mutable(index) => index.values_count(idx),
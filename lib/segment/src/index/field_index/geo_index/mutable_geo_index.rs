@@ -75,6 +75,12 @@ impl MutableGeoMapIndex {
             .unwrap_or(false)
     }
 
+    pub fn get_values(&self, idx: PointOffsetType) -> Option<impl Iterator<Item = &GeoPoint> + '_> {
+        self.point_to_values
+            .get(idx as usize)
+            .map(|values| values.iter())
+    }
+
     pub fn values_count(&self, idx: PointOffsetType) -> usize {
         self.point_to_values
             .get(idx as usize)
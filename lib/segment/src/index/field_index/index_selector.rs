@@ -46,7 +46,12 @@ impl<'a> IndexSelector<'a> {
         payload_schema: &PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndex>> {
         Ok(match payload_schema.expand().as_ref() {
-            PayloadSchemaParams::Keyword(_) => vec![FieldIndex::KeywordIndex(self.map_new(field)?)],
+            PayloadSchemaParams::Keyword(keyword_params) => {
+                vec![FieldIndex::KeywordIndex(
+                    self.map_new(field)?
+                        .with_case_insensitive(keyword_params.case_insensitive.unwrap_or(false)),
+                )]
+            }
             PayloadSchemaParams::Integer(integer_params) => itertools::chain(
                 integer_params
                     .lookup
@@ -96,12 +101,8 @@ impl<'a> IndexSelector<'a> {
         payload_schema: &PayloadFieldSchema,
     ) -> OperationResult<Vec<FieldIndexBuilder>> {
         Ok(match payload_schema.expand().as_ref() {
-            PayloadSchemaParams::Keyword(_) => {
-                vec![self.map_builder(
-                    field,
-                    FieldIndexBuilder::KeywordIndex,
-                    FieldIndexBuilder::KeywordMmapIndex,
-                )]
+            PayloadSchemaParams::Keyword(keyword_params) => {
+                vec![self.keyword_builder(field, keyword_params.case_insensitive.unwrap_or(false))]
             }
             PayloadSchemaParams::Integer(integer_params) => itertools::chain(
                 integer_params.lookup.unwrap_or(true).then(|| {
@@ -190,6 +191,23 @@ impl<'a> IndexSelector<'a> {
         }
     }
 
+    fn keyword_builder(&self, field: &JsonPath, case_insensitive: bool) -> FieldIndexBuilder {
+        match self {
+            IndexSelector::RocksDb(IndexSelectorRocksDb { db, .. }) => {
+                FieldIndexBuilder::KeywordIndex(
+                    MapIndex::builder(Arc::clone(db), &field.to_string())
+                        .with_case_insensitive(case_insensitive),
+                )
+            }
+            IndexSelector::OnDisk(IndexSelectorOnDisk { dir }) => {
+                FieldIndexBuilder::KeywordMmapIndex(
+                    MapIndex::mmap_builder(&map_dir(dir, field))
+                        .with_case_insensitive(case_insensitive),
+                )
+            }
+        }
+    }
+
     fn numeric_new<T: Encodable + Numericable + MmapValue + Default, P>(
         &self,
         field: &JsonPath,
@@ -23,6 +23,8 @@ pub struct ImmutableMapIndex<N: MapIndexKey + ?Sized> {
     indexed_points: usize,
     values_count: usize,
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    /// Keywords are lowercased at index and query time
+    pub(super) case_insensitive: bool,
 }
 
 impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
@@ -39,6 +41,7 @@ impl<N: MapIndexKey + ?Sized> ImmutableMapIndex<N> {
             indexed_points: 0,
             values_count: 0,
             db_wrapper,
+            case_insensitive: false,
         }
     }
 
@@ -32,6 +32,8 @@ pub struct MmapMapIndex<N: MapIndexKey + Key + ?Sized> {
     deleted: MmapBitSliceBufferedUpdateWrapper,
     deleted_count: usize,
     total_key_value_pairs: usize,
+    /// Keywords are lowercased at index and query time
+    pub(super) case_insensitive: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +63,7 @@ impl<N: MapIndexKey + Key + ?Sized> MmapMapIndex<N> {
             deleted: MmapBitSliceBufferedUpdateWrapper::new(deleted),
             deleted_count,
             total_key_value_pairs: config.total_key_value_pairs,
+            case_insensitive: false,
         })
     }
 
@@ -1,4 +1,4 @@
-use std::borrow::Borrow;
+use std::borrow::{Borrow, Cow};
 use std::fmt::{Debug, Display};
 use std::hash::{BuildHasher, Hash};
 use std::iter;
@@ -9,6 +9,7 @@ use std::sync::Arc;
 use ahash::HashMap;
 use common::mmap_hashmap::Key;
 use common::types::PointOffsetType;
+use fnv::FnvBuildHasher;
 use indexmap::IndexSet;
 use itertools::Itertools;
 use mmap_map_index::MmapMapIndex;
@@ -45,6 +46,11 @@ pub trait MapIndexKey: Key + MmapValue + Eq + Display + Debug {
     type Owned: Borrow<Self> + Hash + Eq + Clone + FromStr + Default;
 
     fn to_owned(&self) -> Self::Owned;
+
+    /// Normalize value for case-insensitive indexes. Values without case are returned as is.
+    fn to_lowercase(value: Self::Owned) -> Self::Owned {
+        value
+    }
 }
 
 impl MapIndexKey for str {
@@ -53,6 +59,10 @@ impl MapIndexKey for str {
     fn to_owned(&self) -> Self::Owned {
         SmolStr::from(self)
     }
+
+    fn to_lowercase(value: Self::Owned) -> Self::Owned {
+        SmolStr::from(value.to_lowercase())
+    }
 }
 
 impl MapIndexKey for IntPayloadType {
@@ -99,6 +109,7 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
             path: path.to_owned(),
             point_to_values: Default::default(),
             values_to_points: Default::default(),
+            case_insensitive: false,
         }
     }
 
@@ -396,23 +407,116 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
 
     fn except_set<'a, K, A>(
         &'a self,
-        excluded: &'a IndexSet<K, A>,
+        excluded: Cow<'a, IndexSet<K, A>>,
     ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a>
     where
-        A: BuildHasher,
-        K: Borrow<N> + Hash + Eq,
+        A: BuildHasher + Clone,
+        K: Borrow<N> + Hash + Eq + Clone,
     {
         Box::new(
             self.iter_values()
-                .filter(|key| !excluded.contains((*key).borrow()))
+                .filter(move |key| !excluded.contains((*key).borrow()))
                 .flat_map(|key| self.get_iterator(key.borrow()).copied())
                 .unique(),
         )
     }
 }
 
+impl MapIndex<str> {
+    /// Lowercase keywords at index and query time
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        match &mut self {
+            MapIndex::Mutable(index) => index.case_insensitive = case_insensitive,
+            MapIndex::Immutable(index) => index.case_insensitive = case_insensitive,
+            MapIndex::Mmap(index) => index.case_insensitive = case_insensitive,
+        }
+        self
+    }
+
+    pub fn is_case_insensitive(&self) -> bool {
+        match self {
+            MapIndex::Mutable(index) => index.case_insensitive,
+            MapIndex::Immutable(index) => index.case_insensitive,
+            MapIndex::Mmap(index) => index.case_insensitive,
+        }
+    }
+
+    /// Convert keyword into the form it is stored in the index
+    pub fn normalize_keyword<'a>(&self, keyword: &'a str) -> Cow<'a, str> {
+        if self.is_case_insensitive() {
+            Cow::Owned(keyword.to_lowercase())
+        } else {
+            Cow::Borrowed(keyword)
+        }
+    }
+
+    fn normalize_keywords<'a>(
+        &self,
+        keywords: &'a IndexSet<String, FnvBuildHasher>,
+    ) -> Cow<'a, IndexSet<String, FnvBuildHasher>> {
+        if self.is_case_insensitive() {
+            Cow::Owned(
+                keywords
+                    .iter()
+                    .map(|keyword| keyword.to_lowercase())
+                    .collect(),
+            )
+        } else {
+            Cow::Borrowed(keywords)
+        }
+    }
+
+    /// Convert keywords of the match condition into the form they are stored in the index
+    pub fn normalize_match(&self, r#match: Match) -> Match {
+        if !self.is_case_insensitive() {
+            return r#match;
+        }
+        match r#match {
+            Match::Value(MatchValue {
+                value: ValueVariants::String(keyword),
+            }) => Match::Value(MatchValue {
+                value: ValueVariants::String(keyword.to_lowercase()),
+            }),
+            Match::Any(MatchAny {
+                any: AnyVariants::Strings(keywords),
+            }) => Match::Any(MatchAny {
+                any: AnyVariants::Strings(self.normalize_keywords(&keywords).into_owned()),
+            }),
+            Match::Except(MatchExcept {
+                except: AnyVariants::Strings(keywords),
+            }) => Match::Except(MatchExcept {
+                except: AnyVariants::Strings(self.normalize_keywords(&keywords).into_owned()),
+            }),
+            other => other,
+        }
+    }
+
+    /// Lowercase string payload values, if the index is case-insensitive
+    pub fn normalize_payload_value<'a>(&self, value: &'a Value) -> Cow<'a, Value> {
+        if !self.is_case_insensitive() {
+            return Cow::Borrowed(value);
+        }
+        match value {
+            Value::String(keyword) => Cow::Owned(Value::String(keyword.to_lowercase())),
+            Value::Array(values) => Cow::Owned(Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.normalize_payload_value(value).into_owned())
+                    .collect(),
+            )),
+            _ => Cow::Borrowed(value),
+        }
+    }
+}
+
 pub struct MapIndexBuilder<N: MapIndexKey + ?Sized>(MapIndex<N>);
 
+impl MapIndexBuilder<str> {
+    pub fn with_case_insensitive(self, case_insensitive: bool) -> Self {
+        Self(self.0.with_case_insensitive(case_insensitive))
+    }
+}
+
 impl<N: MapIndexKey + ?Sized> FieldIndexBuilderTrait for MapIndexBuilder<N>
 where
     MapIndex<N>: PayloadFieldIndex + ValueIndexer,
@@ -440,6 +544,14 @@ pub struct MapIndexMmapBuilder<N: MapIndexKey + ?Sized> {
     path: PathBuf,
     point_to_values: Vec<Vec<N::Owned>>,
     values_to_points: HashMap<N::Owned, Vec<PointOffsetType>>,
+    case_insensitive: bool,
+}
+
+impl MapIndexMmapBuilder<str> {
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
 }
 
 impl<N: MapIndexKey + ?Sized> FieldIndexBuilderTrait for MapIndexMmapBuilder<N>
@@ -459,7 +571,17 @@ where
             let payload_values = <MapIndex<N> as ValueIndexer>::get_values(value);
             flatten_values.extend(payload_values);
         }
-        let flatten_values: Vec<N::Owned> = flatten_values.into_iter().map(Into::into).collect();
+        let flatten_values: Vec<N::Owned> = flatten_values
+            .into_iter()
+            .map(Into::into)
+            .map(|value| {
+                if self.case_insensitive {
+                    N::to_lowercase(value)
+                } else {
+                    value
+                }
+            })
+            .collect();
 
         if self.point_to_values.len() <= id as usize {
             self.point_to_values.resize_with(id as usize + 1, Vec::new);
@@ -473,11 +595,10 @@ where
     }
 
     fn finalize(self) -> OperationResult<Self::FieldIndexType> {
-        Ok(MapIndex::Mmap(Box::new(MmapMapIndex::build(
-            &self.path,
-            self.point_to_values,
-            self.values_to_points,
-        )?)))
+        let mut index =
+            MmapMapIndex::build(&self.path, self.point_to_values, self.values_to_points)?;
+        index.case_insensitive = self.case_insensitive;
+        Ok(MapIndex::Mmap(Box::new(index)))
     }
 }
 
@@ -508,9 +629,9 @@ impl PayloadFieldIndex for MapIndex<str> {
     ) -> Option<Box<dyn Iterator<Item = PointOffsetType> + 'a>> {
         match &condition.r#match {
             Some(Match::Value(MatchValue { value })) => match value {
-                ValueVariants::String(keyword) => {
-                    Some(Box::new(self.get_iterator(keyword.as_str()).copied()))
-                }
+                ValueVariants::String(keyword) => Some(Box::new(
+                    self.get_iterator(&self.normalize_keyword(keyword)).copied(),
+                )),
                 ValueVariants::Integer(_) => None,
                 ValueVariants::Bool(_) => None,
             },
@@ -518,7 +639,9 @@ impl PayloadFieldIndex for MapIndex<str> {
                 AnyVariants::Strings(keywords) => Some(Box::new(
                     keywords
                         .iter()
-                        .flat_map(|keyword| self.get_iterator(keyword.as_str()).copied())
+                        .flat_map(|keyword| {
+                            self.get_iterator(&self.normalize_keyword(keyword)).copied()
+                        })
                         .unique(),
                 )),
                 AnyVariants::Integers(integers) => {
@@ -530,7 +653,9 @@ impl PayloadFieldIndex for MapIndex<str> {
                 }
             },
             Some(Match::Except(MatchExcept { except })) => match except {
-                AnyVariants::Strings(keywords) => Some(self.except_set(keywords)),
+                AnyVariants::Strings(keywords) => {
+                    Some(self.except_set(self.normalize_keywords(keywords)))
+                }
                 AnyVariants::Integers(other) => {
                     if other.is_empty() {
                         Some(Box::new(iter::empty()))
@@ -547,7 +672,7 @@ impl PayloadFieldIndex for MapIndex<str> {
         match &condition.r#match {
             Some(Match::Value(MatchValue { value })) => match value {
                 ValueVariants::String(keyword) => {
-                    let mut estimation = self.match_cardinality(keyword.as_str());
+                    let mut estimation = self.match_cardinality(&self.normalize_keyword(keyword));
                     estimation
                         .primary_clauses
                         .push(PrimaryCondition::Condition(condition.clone()));
@@ -560,7 +685,7 @@ impl PayloadFieldIndex for MapIndex<str> {
                 AnyVariants::Strings(keywords) => {
                     let estimations = keywords
                         .iter()
-                        .map(|keyword| self.match_cardinality(keyword.as_str()))
+                        .map(|keyword| self.match_cardinality(&self.normalize_keyword(keyword)))
                         .collect::<Vec<_>>();
                     let estimation = if estimations.is_empty() {
                         CardinalityEstimation::exact(0)
@@ -587,6 +712,7 @@ impl PayloadFieldIndex for MapIndex<str> {
             Some(Match::Except(MatchExcept { except })) => {
                 match except {
                     AnyVariants::Strings(keywords) => {
+                        let keywords = self.normalize_keywords(keywords);
                         Some(self.except_cardinality(keywords.iter().map(|k| k.as_str())))
                     }
                     AnyVariants::Integers(others) => {
@@ -858,7 +984,7 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                         None
                     }
                 }
-                AnyVariants::Integers(integers) => Some(self.except_set(integers)),
+                AnyVariants::Integers(integers) => Some(self.except_set(Cow::Borrowed(integers))),
             },
             _ => None,
         }
@@ -945,6 +1071,10 @@ impl ValueIndexer for MapIndex<str> {
 
     fn add_many(&mut self, id: PointOffsetType, values: Vec<String>) -> OperationResult<()> {
         match self {
+            MapIndex::Mutable(index) if index.case_insensitive => {
+                let values: Vec<_> = values.iter().map(|value| value.to_lowercase()).collect();
+                index.add_many_to_map(id, values)
+            }
             MapIndex::Mutable(index) => index.add_many_to_map(id, values),
             MapIndex::Immutable(_) => Err(OperationError::service_error(
                 "Can't add values to immutable map index",
@@ -1036,6 +1166,8 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::index::field_index::FieldIndex;
+    use crate::json_path::JsonPath;
 
     const FIELD_NAME: &str = "test";
 
@@ -1213,4 +1345,78 @@ mod tests {
             .except_cardinality(vec![].into_iter())
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
+
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_case_insensitive_keyword_index(#[case] index_type: IndexType) {
+        let data = [
+            vec!["Hello", "WORLD"],
+            vec!["hello"],
+            vec!["HeLLo"],
+            vec!["other"],
+        ];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        let path = temp_dir.path();
+
+        let add_points = |builder: &mut dyn FnMut(PointOffsetType, &[&Value])| {
+            for (idx, values) in data.iter().enumerate() {
+                let values: Vec<Value> = values.iter().map(|v| Value::from(*v)).collect();
+                let values: Vec<_> = values.iter().collect();
+                builder(idx as PointOffsetType, &values);
+            }
+        };
+        match index_type {
+            IndexType::Mutable | IndexType::Immutable => {
+                let mut builder =
+                    MapIndex::<str>::builder(open_db_with_existing_cf(path).unwrap(), FIELD_NAME)
+                        .with_case_insensitive(true);
+                builder.init().unwrap();
+                add_points(&mut |idx, values| builder.add_point(idx, values).unwrap());
+                builder.finalize().unwrap();
+            }
+            IndexType::Mmap => {
+                let mut builder = MapIndex::<str>::mmap_builder(path).with_case_insensitive(true);
+                builder.init().unwrap();
+                add_points(&mut |idx, values| builder.add_point(idx, values).unwrap());
+                builder.finalize().unwrap();
+            }
+        }
+
+        let mut index = match index_type {
+            IndexType::Mutable => {
+                MapIndex::<str>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, true)
+            }
+            IndexType::Immutable => {
+                MapIndex::<str>::new(open_db_with_existing_cf(path).unwrap(), FIELD_NAME, false)
+            }
+            IndexType::Mmap => MapIndex::<str>::new_mmap(path).unwrap(),
+        }
+        .with_case_insensitive(true);
+        index.load_from_db().unwrap();
+
+        let key = JsonPath::new(FIELD_NAME);
+        for query in ["hello", "HELLO"] {
+            let condition = FieldCondition::new_match(key.clone(), query.to_string().into());
+            let points: Vec<_> = index.filter(&condition).unwrap().sorted().collect();
+            assert_eq!(points, vec![0, 1, 2]);
+
+            let estimation = index.estimate_cardinality(&condition).unwrap();
+            assert_eq!(estimation.exp, 3);
+        }
+
+        // Payload values are matched regardless of case too
+        let condition = FieldCondition::new_match(key, "world".to_string().into());
+        let index = FieldIndex::KeywordIndex(index);
+        assert_eq!(
+            index.check_condition(&condition, &Value::from("World")),
+            Some(true),
+        );
+        assert_eq!(
+            index.check_condition(&condition, &Value::from("other")),
+            Some(false),
+        );
+    }
 }
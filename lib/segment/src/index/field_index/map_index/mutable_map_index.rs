@@ -19,6 +19,8 @@ pub struct MutableMapIndex<N: MapIndexKey + ?Sized> {
     pub(super) indexed_points: usize,
     pub(super) values_count: usize,
     pub(super) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    /// Keywords are lowercased at index and query time
+    pub(super) case_insensitive: bool,
 }
 
 impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
@@ -34,6 +36,7 @@ impl<N: MapIndexKey + ?Sized> MutableMapIndex<N> {
             indexed_points: 0,
             values_count: 0,
             db_wrapper,
+            case_insensitive: false,
         }
     }
 
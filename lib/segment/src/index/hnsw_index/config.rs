@@ -29,6 +29,10 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Peak memory in bytes used while building the index, unknown for indices built by older
+    /// versions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_peak_memory_bytes: Option<usize>,
 }
 
 impl HnswGraphConfig {
@@ -50,6 +54,7 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            build_peak_memory_bytes: None,
         }
     }
 
@@ -1,5 +1,6 @@
 use std::cmp::max;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 use common::fixed_length_priority_queue::FixedLengthPriorityQueue;
 use common::types::{PointOffsetType, ScoredPointOffset};
@@ -24,6 +25,9 @@ pub type LayersContainer = Vec<LinkContainer>;
 pub const HNSW_GRAPH_FILE: &str = "graph.bin";
 pub const HNSW_LINKS_FILE: &str = "links.bin";
 
+/// How many candidates are expanded between checks of the search deadline
+const DEADLINE_CHECK_INTERVAL: usize = 32;
+
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GraphLayersBackwardCompatibility {
     pub(super) max_level: usize,
@@ -59,21 +63,34 @@ pub trait GraphLayersBase {
     fn get_m(&self, level: usize) -> usize;
 
     /// Greedy search for closest points within a single graph layer
+    ///
+    /// Returns `true` if the search was interrupted by the `deadline`,
+    /// the searcher then holds the closest points found so far.
     fn _search_on_level(
         &self,
         searcher: &mut SearchContext,
         level: usize,
         visited_list: &mut VisitedListHandle,
         points_scorer: &mut FilteredScorer,
-    ) {
+        deadline: Option<Instant>,
+    ) -> bool {
         let limit = self.get_m(level);
         let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
+        let mut expanded_candidates = 0;
 
         while let Some(candidate) = searcher.candidates.pop() {
             if candidate.score < searcher.lower_bound() {
                 break;
             }
 
+            if let Some(deadline) = deadline {
+                if expanded_candidates % DEADLINE_CHECK_INTERVAL == 0 && Instant::now() >= deadline
+                {
+                    return true;
+                }
+                expanded_candidates += 1;
+            }
+
             points_ids.clear();
             self.links_map(candidate.idx, level, |link| {
                 if !visited_list.check(link) {
@@ -87,6 +104,7 @@ pub trait GraphLayersBase {
                 visited_list.check_and_update_visited(score_point.idx);
             });
         }
+        false
     }
 
     fn search_on_level(
@@ -95,13 +113,20 @@ pub trait GraphLayersBase {
         level: usize,
         ef: usize,
         points_scorer: &mut FilteredScorer,
-    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        deadline: Option<Instant>,
+    ) -> (FixedLengthPriorityQueue<ScoredPointOffset>, bool) {
         let mut visited_list = self.get_visited_list_from_pool();
         visited_list.check_and_update_visited(level_entry.idx);
         let mut search_context = SearchContext::new(level_entry, ef);
 
-        self._search_on_level(&mut search_context, level, &mut visited_list, points_scorer);
-        search_context.nearest
+        let deadline_exceeded = self._search_on_level(
+            &mut search_context,
+            level,
+            &mut visited_list,
+            points_scorer,
+            deadline,
+        );
+        (search_context.nearest, deadline_exceeded)
     }
 
     /// Greedy searches for entry point of level `target_level`.
@@ -176,6 +201,16 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         self.links.point_level(point_id)
     }
 
+    /// Find the highest entry point which satisfies filtering condition of `checker`
+    ///
+    /// Useful to diagnose where a filtered search starts from.
+    pub fn filtered_entry_point<F>(&self, checker: F) -> Option<EntryPoint>
+    where
+        F: Fn(PointOffsetType) -> bool,
+    {
+        self.entry_points.get_entry_point(checker)
+    }
+
     fn get_entry_point(
         &self,
         points_scorer: &FilteredScorer,
@@ -204,11 +239,27 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         &self,
         top: usize,
         ef: usize,
-        mut points_scorer: FilteredScorer,
+        points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
     ) -> Vec<ScoredPointOffset> {
+        let (result, _) =
+            self.search_with_deadline(top, ef, points_scorer, custom_entry_points, None);
+        result
+    }
+
+    /// Search the graph, but stop expanding candidates once the `deadline` is reached
+    ///
+    /// Returns the closest points found and whether the search was interrupted by the deadline.
+    pub fn search_with_deadline(
+        &self,
+        top: usize,
+        ef: usize,
+        mut points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+        deadline: Option<Instant>,
+    ) -> (Vec<ScoredPointOffset>, bool) {
         let Some(entry_point) = self.get_entry_point(&points_scorer, custom_entry_points) else {
-            return Vec::default();
+            return (Vec::default(), false);
         };
 
         let zero_level_entry = self.search_entry(
@@ -217,8 +268,17 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             0,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
-        nearest.into_iter().take(top).collect_vec()
+        let (nearest, deadline_exceeded) = self.search_on_level(
+            zero_level_entry,
+            0,
+            max(top, ef),
+            &mut points_scorer,
+            deadline,
+        );
+        (
+            nearest.into_iter().take(top).collect_vec(),
+            deadline_exceeded,
+        )
     }
 
     pub fn get_path(path: &Path) -> PathBuf {
@@ -364,7 +424,7 @@ mod tests {
         let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
         let mut scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
 
-        let nearest_on_level = graph_layers.search_on_level(
+        let (nearest_on_level, deadline_exceeded) = graph_layers.search_on_level(
             ScoredPointOffset {
                 idx: 0,
                 score: scorer.score_point(0),
@@ -372,8 +432,11 @@ mod tests {
             0,
             32,
             &mut scorer,
+            None,
         );
 
+        assert!(!deadline_exceeded);
+
         assert_eq!(nearest_on_level.len(), graph_links[0][0].len() + 1);
 
         for nearest in &nearest_on_level {
@@ -469,6 +532,45 @@ mod tests {
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[test]
+    fn test_filtered_entry_point() {
+        let num_vectors = 1000;
+        let dim = 8;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let (_vector_holder, graph_layers) = create_graph_layer_fixture::<CosineMetric, _>(
+            num_vectors,
+            M,
+            dim,
+            false,
+            &mut rng,
+            None,
+        );
+
+        let main_entry = graph_layers
+            .filtered_entry_point(|_x| true)
+            .expect("Expect entry point to exists");
+        let max_level = (0..num_vectors)
+            .map(|i| graph_layers.point_level(i as PointOffsetType))
+            .max()
+            .unwrap();
+        assert_eq!(main_entry.level, max_level);
+
+        // Entry point for points other than the main entry
+        let filtered_entry = graph_layers
+            .filtered_entry_point(|point_id| point_id != main_entry.point_id)
+            .expect("Expect filtered entry point to exists");
+        assert_ne!(filtered_entry.point_id, main_entry.point_id);
+        assert_eq!(
+            filtered_entry.level,
+            graph_layers.point_level(filtered_entry.point_id),
+        );
+        assert!(filtered_entry.level <= main_entry.level);
+
+        assert!(graph_layers.filtered_entry_point(|_x| false).is_none());
+    }
+
     #[test]
     #[ignore]
     fn test_draw_hnsw_graph() {
@@ -1,5 +1,6 @@
 use std::cmp::{max, min};
 use std::collections::BinaryHeap;
+use std::mem::size_of;
 use std::path::Path;
 use std::sync::atomic::AtomicUsize;
 
@@ -9,9 +10,10 @@ use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 use parking_lot::{Mutex, MutexGuard, RwLock};
 use rand::distributions::Uniform;
 use rand::Rng;
+use serde::Serialize;
 
 use super::graph_links::GraphLinks;
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::index::hnsw_index::entry_points::EntryPoints;
 use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
@@ -22,6 +24,40 @@ use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 pub type LockedLinkContainer = RwLock<LinkContainer>;
 pub type LockedLayersContainer = Vec<LockedLinkContainer>;
 
+/// Defines how much memory is reserved for link containers before points are linked
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LinksReservation {
+    /// Do not reserve anything for level 0, containers grow on demand
+    None,
+    /// Reserve `m0` links on level 0 for initial points, upper levels reserve `m` links once
+    /// point levels are set
+    #[default]
+    Level0,
+    /// Reserve full capacity of every level once point levels are set, including level 0 of
+    /// points added after construction. No link container is reallocated during linking.
+    Full,
+}
+
+/// Connectivity of a single level of the graph
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct LevelConnectivityStats {
+    /// Number of points present on the level
+    pub num_points: usize,
+    /// Number of links of all points on the level, each direction of a link is counted
+    pub num_edges: usize,
+    pub avg_degree: f64,
+    pub min_degree: usize,
+    pub max_degree: usize,
+    /// Number of points on the level without any links
+    pub isolated_points: usize,
+}
+
+/// Connectivity of the graph, indexed by level
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ConnectivityStats {
+    pub levels: Vec<LevelConnectivityStats>,
+}
+
 /// Same as `GraphLayers`,  but allows to build in parallel
 /// Convertible to `GraphLayers`
 pub struct GraphLayersBuilder {
@@ -33,6 +69,7 @@ pub struct GraphLayersBuilder {
     level_factor: f64,
     // Exclude points according to "not closer than base" heuristic?
     use_heuristic: bool,
+    links_reservation: LinksReservation,
     links_layers: Vec<LockedLayersContainer>,
     entry_points: Mutex<EntryPoints>,
 
@@ -108,13 +145,40 @@ impl GraphLayersBuilder {
         ef_construct: usize,
         entry_points_num: usize, // Depends on number of points
         use_heuristic: bool,
-        reserve: bool,
+        links_reservation: LinksReservation,
+    ) -> Self {
+        Self::new_with_level_factor(
+            num_vectors,
+            m,
+            m0,
+            ef_construct,
+            entry_points_num,
+            use_heuristic,
+            links_reservation,
+            1.0 / (max(m, 2) as f64).ln(),
+        )
+    }
+
+    /// Same as [`GraphLayersBuilder::new_with_params`], but with an explicit level factor
+    /// instead of the default `1 / ln(m)`.
+    ///
+    /// Levels of points are drawn as `-ln(uniform(0, 1)) * level_factor`, so a higher factor
+    /// makes higher layers more populated.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_level_factor(
+        num_vectors: usize, // Initial number of points in index
+        m: usize,           // Expected M for non-first layer
+        m0: usize,          // Expected M for first layer
+        ef_construct: usize,
+        entry_points_num: usize, // Depends on number of points
+        use_heuristic: bool,
+        links_reservation: LinksReservation,
+        level_factor: f64,
     ) -> Self {
         let links_layers = std::iter::repeat_with(|| {
-            vec![RwLock::new(if reserve {
-                Vec::with_capacity(m0)
-            } else {
-                vec![]
+            vec![RwLock::new(match links_reservation {
+                LinksReservation::None => vec![],
+                LinksReservation::Level0 | LinksReservation::Full => Vec::with_capacity(m0),
             })]
         })
         .take(num_vectors)
@@ -127,8 +191,9 @@ impl GraphLayersBuilder {
             m,
             m0,
             ef_construct,
-            level_factor: 1.0 / (max(m, 2) as f64).ln(),
+            level_factor,
             use_heuristic,
+            links_reservation,
             links_layers,
             entry_points: Mutex::new(EntryPoints::new(entry_points_num)),
             visited_pool: VisitedPool::new(),
@@ -151,7 +216,7 @@ impl GraphLayersBuilder {
             ef_construct,
             entry_points_num,
             use_heuristic,
-            true,
+            LinksReservation::default(),
         )
     }
 
@@ -195,6 +260,33 @@ impl GraphLayersBuilder {
         self.links_layers.len()
     }
 
+    /// Memory allocated by the builder, including structures only used during construction
+    ///
+    /// Lists of visited points are only included while they are kept in the pool, i.e. not
+    /// while points are being linked.
+    pub fn memory_usage_bytes(&self) -> usize {
+        let links_bytes: usize = self
+            .links_layers
+            .iter()
+            .map(|layers| {
+                layers.capacity() * size_of::<LockedLinkContainer>()
+                    + layers
+                        .iter()
+                        .map(|links| links.read().capacity() * size_of::<PointOffsetType>())
+                        .sum::<usize>()
+            })
+            .sum();
+
+        self.links_layers.capacity() * size_of::<LockedLayersContainer>()
+            + links_bytes
+            + self
+                .ready_list
+                .read()
+                .capacity()
+                .div_ceil(u8::BITS as usize)
+            + self.visited_pool.memory_usage_bytes()
+    }
+
     /// Generate random level for a new point, according to geometric distribution
     pub fn get_random_layer<R>(&self, rng: &mut R) -> usize
     where
@@ -216,15 +308,44 @@ impl GraphLayersBuilder {
                 self.links_layers.push(vec![]);
             }
         }
+        let reserve_full = self.links_reservation == LinksReservation::Full;
+        let (m, m0) = (self.m, self.m0);
         let point_layers = &mut self.links_layers[point_id as usize];
+        if reserve_full {
+            point_layers.reserve_exact((level + 1).saturating_sub(point_layers.len()));
+        }
         while point_layers.len() <= level {
-            let links = Vec::with_capacity(self.m);
-            point_layers.push(RwLock::new(links));
+            let capacity = if reserve_full && point_layers.is_empty() {
+                m0
+            } else {
+                m
+            };
+            point_layers.push(RwLock::new(Vec::with_capacity(capacity)));
         }
         self.max_level
             .fetch_max(level, std::sync::atomic::Ordering::Relaxed);
     }
 
+    /// Set levels of all points at once, instead of picking them with `get_random_layer`
+    ///
+    /// Allows to build graphs with exactly the same layout of levels, independent of the RNG.
+    pub fn set_levels_from_slice(&mut self, levels: &[usize]) -> OperationResult<()> {
+        if levels.len() != self.num_points() {
+            return Err(OperationError::ValidationError {
+                description: format!(
+                    "Expected levels of {} points, got {}",
+                    self.num_points(),
+                    levels.len(),
+                ),
+            });
+        }
+
+        for (point_id, &level) in levels.iter().enumerate() {
+            self.set_levels(point_id as PointOffsetType, level);
+        }
+        Ok(())
+    }
+
     /// Connect new point to links, so that links contains only closest points
     fn connect_new_point<F>(
         links: &mut LinkContainer,
@@ -349,6 +470,7 @@ impl GraphLayersBuilder {
                         curr_level,
                         &mut visited_list,
                         &mut points_scorer,
+                        None,
                     );
 
                     if let Some(the_nearest) = search_context.nearest.iter().max() {
@@ -477,6 +599,41 @@ impl GraphLayersBuilder {
             sum as f32 / count as f32
         }
     }
+
+    /// Statistics of links on every level of the graph, to check health of the built graph
+    pub fn connectivity_stats(&self) -> ConnectivityStats {
+        let max_level = self.max_level.load(std::sync::atomic::Ordering::Relaxed);
+        let mut levels = vec![
+            LevelConnectivityStats {
+                min_degree: usize::MAX,
+                ..Default::default()
+            };
+            max_level + 1
+        ];
+
+        for point_layers in &self.links_layers {
+            for (stats, links) in levels.iter_mut().zip(point_layers) {
+                let degree = links.read().len();
+                stats.num_points += 1;
+                stats.num_edges += degree;
+                stats.min_degree = min(stats.min_degree, degree);
+                stats.max_degree = max(stats.max_degree, degree);
+                if degree == 0 {
+                    stats.isolated_points += 1;
+                }
+            }
+        }
+
+        for stats in &mut levels {
+            if stats.num_points == 0 {
+                stats.min_degree = 0;
+            } else {
+                stats.avg_degree = stats.num_edges as f64 / stats.num_points as f64;
+            }
+        }
+
+        ConnectivityStats { levels }
+    }
 }
 
 #[cfg(test)]
@@ -590,6 +747,71 @@ mod tests {
         (vector_holder, graph_layers)
     }
 
+    #[cfg(not(windows))] // https://github.com/qdrant/qdrant/issues/1452
+    #[test]
+    fn test_full_links_reservation_parallel_build() {
+        use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+        let initial_vectors = 500;
+        let num_vectors = 1000;
+        let dim = 8;
+        let m = M;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder = TestRawScorerProducer::<CosineMetric>::new(dim, num_vectors, &mut rng);
+
+        // Half of the points are added after construction, to also cover lazily created containers
+        let mut graph_layers = GraphLayersBuilder::new_with_params(
+            initial_vectors,
+            m,
+            m * 2,
+            16,
+            10,
+            true,
+            LinksReservation::Full,
+        );
+
+        for idx in 0..(num_vectors as PointOffsetType) {
+            let level = graph_layers.get_random_layer(&mut rng);
+            graph_layers.set_levels(idx, level);
+        }
+
+        let capacities = |graph_layers: &GraphLayersBuilder| -> Vec<Vec<usize>> {
+            graph_layers
+                .links_layers
+                .iter()
+                .map(|layers| layers.iter().map(|links| links.read().capacity()).collect())
+                .collect()
+        };
+        let reserved = capacities(&graph_layers);
+
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        pool.install(|| {
+            (0..(num_vectors as PointOffsetType))
+                .into_par_iter()
+                .for_each(|idx| {
+                    let fake_filter_context = FakeFilterContext {};
+                    let added_vector = vector_holder.vectors.get(idx as VectorOffsetType).to_vec();
+                    let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                    let scorer =
+                        FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                    graph_layers.link_new_point(idx, scorer);
+                });
+        });
+
+        // Capacity only changes on reallocation
+        assert_eq!(capacities(&graph_layers), reserved);
+
+        for layers in &graph_layers.links_layers {
+            for (level, links) in layers.iter().enumerate() {
+                assert!(links.read().len() <= graph_layers.get_m(level));
+            }
+        }
+    }
+
     #[cfg(not(windows))] // https://github.com/qdrant/qdrant/issues/1452
     #[test]
     fn test_parallel_graph_build() {
@@ -742,6 +964,168 @@ mod tests {
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[test]
+    fn test_custom_level_factor() {
+        const NUM_VECTORS: usize = 1_000;
+        const DIM: usize = 8;
+        const EF_CONSTRUCT: usize = 16;
+
+        let build_levels = |level_factor: f64| {
+            let mut rng = StdRng::seed_from_u64(42);
+            let vector_holder =
+                TestRawScorerProducer::<CosineMetric>::new(DIM, NUM_VECTORS, &mut rng);
+            let mut graph_layers_builder = GraphLayersBuilder::new_with_level_factor(
+                NUM_VECTORS,
+                M,
+                M * 2,
+                EF_CONSTRUCT,
+                10,
+                true,
+                LinksReservation::default(),
+                level_factor,
+            );
+            let fake_filter_context = FakeFilterContext {};
+            for idx in 0..(NUM_VECTORS as PointOffsetType) {
+                let added_vector = vector_holder.vectors.get(idx as VectorOffsetType).to_vec();
+                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                let level = graph_layers_builder.get_random_layer(&mut rng);
+                graph_layers_builder.set_levels(idx, level);
+                graph_layers_builder.link_new_point(idx, scorer);
+            }
+            let graph_layers = graph_layers_builder
+                .into_graph_layers::<GraphLinksRam>(None)
+                .unwrap();
+            (0..NUM_VECTORS)
+                .map(|i| graph_layers.links.point_level(i as PointOffsetType))
+                .collect_vec()
+        };
+
+        // Default factor for M, and a higher one
+        let default_levels = build_levels(1.0 / (M as f64).ln());
+        let high_levels = build_levels(1.0);
+
+        // Same random samples are scaled by the factor, so no point gets a lower level
+        assert!(default_levels
+            .iter()
+            .zip(&high_levels)
+            .all(|(default, high)| default <= high));
+
+        // Point is above the first layer with probability `exp(-0.5 / level_factor)`,
+        // which is ~0.35 for the default factor and ~0.61 for the high one
+        let above_first = |levels: &[usize]| levels.iter().filter(|&&level| level > 0).count();
+        let default_above = above_first(&default_levels);
+        let high_above = above_first(&high_levels);
+        assert!(
+            high_above > default_above + NUM_VECTORS / 10,
+            "{high_above} points above the first layer with high factor, {default_above} with default",
+        );
+
+        let max_level = |levels: &[usize]| levels.iter().copied().max().unwrap();
+        assert!(max_level(&high_levels) > max_level(&default_levels));
+    }
+
+    #[test]
+    fn test_set_levels_from_slice() {
+        const NUM_VECTORS: usize = 500;
+        const DIM: usize = 8;
+        const EF_CONSTRUCT: usize = 16;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let vector_holder = TestRawScorerProducer::<CosineMetric>::new(DIM, NUM_VECTORS, &mut rng);
+
+        let new_builder = || GraphLayersBuilder::new(NUM_VECTORS, M, M * 2, EF_CONSTRUCT, 10, true);
+
+        let levels = {
+            let builder = new_builder();
+            (0..NUM_VECTORS)
+                .map(|_| builder.get_random_layer(&mut rng))
+                .collect_vec()
+        };
+
+        let mut wrong_builder = new_builder();
+        assert!(wrong_builder
+            .set_levels_from_slice(&levels[..NUM_VECTORS - 1])
+            .is_err());
+
+        // Builders with the same levels produce the same graph, whatever the RNG state
+        let build_graph = || {
+            let mut graph_layers_builder = new_builder();
+            graph_layers_builder.set_levels_from_slice(&levels).unwrap();
+            assert_eq!(
+                graph_layers_builder
+                    .max_level
+                    .load(std::sync::atomic::Ordering::Relaxed),
+                levels.iter().copied().max().unwrap(),
+            );
+
+            let fake_filter_context = FakeFilterContext {};
+            for idx in 0..(NUM_VECTORS as PointOffsetType) {
+                let added_vector = vector_holder.vectors.get(idx as VectorOffsetType).to_vec();
+                let raw_scorer = vector_holder.get_raw_scorer(added_vector).unwrap();
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                graph_layers_builder.link_new_point(idx, scorer);
+            }
+            graph_layers_builder
+                .into_graph_layers::<GraphLinksRam>(None)
+                .unwrap()
+        };
+
+        let graph = build_graph();
+        let other_graph = build_graph();
+        for idx in 0..(NUM_VECTORS as PointOffsetType) {
+            let level = graph.links.point_level(idx);
+            assert_eq!(level, levels[idx as usize]);
+            assert_eq!(level, other_graph.links.point_level(idx));
+            for level in 0..=level {
+                assert_eq!(
+                    graph.links.links(idx, level),
+                    other_graph.links.links(idx, level),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_connectivity_stats() {
+        const NUM_VECTORS: usize = 1_000;
+        const DIM: usize = 16;
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let (_vector_holder, graph_layers_builder) =
+            create_graph_layer::<CosineMetric, _>(NUM_VECTORS, DIM, true, &mut rng);
+
+        let stats = graph_layers_builder.connectivity_stats();
+        assert_eq!(
+            stats.levels.len(),
+            graph_layers_builder
+                .max_level
+                .load(std::sync::atomic::Ordering::Relaxed)
+                + 1,
+        );
+
+        let level0 = &stats.levels[0];
+        assert_eq!(level0.num_points, NUM_VECTORS);
+        assert_eq!(level0.isolated_points, 0);
+        assert!(level0.avg_degree > M as f64, "{level0:?}");
+        assert_eq!(
+            level0.avg_degree,
+            level0.num_edges as f64 / NUM_VECTORS as f64,
+        );
+
+        for (level, stats) in stats.levels.iter().enumerate() {
+            let average = graph_layers_builder.get_average_connectivity_on_level(level);
+            assert!((stats.avg_degree as f32 - average).abs() < 1e-4);
+            assert!(stats.max_degree <= graph_layers_builder.get_m(level));
+        }
+
+        // Upper levels have fewer points
+        assert!(stats
+            .levels
+            .windows(2)
+            .all(|pair| pair[0].num_points >= pair[1].num_points));
+    }
+
     #[test]
     #[ignore]
     fn test_hnsw_graph_properties() {
@@ -30,7 +30,8 @@ use crate::id_tracker::IdTrackerSS;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
-use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::graph_layers_builder::{GraphLayersBuilder, LinksReservation};
+use crate::index::hnsw_index::peak_memory::PeakMemory;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
@@ -78,6 +79,8 @@ struct HNSWSearchesTelemetry {
     large_cardinality: Arc<Mutex<OperationDurationsAggregator>>,
     exact_filtered: Arc<Mutex<OperationDurationsAggregator>>,
     exact_unfiltered: Arc<Mutex<OperationDurationsAggregator>>,
+    /// Small cardinality searches, which could not use any payload index to select points
+    full_scan: Arc<Mutex<OperationDurationsAggregator>>,
 }
 
 pub struct HnswIndexOpenArgs<'a> {
@@ -183,6 +186,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 large_cardinality: OperationDurationsAggregator::new(),
                 exact_filtered: OperationDurationsAggregator::new(),
                 exact_unfiltered: OperationDurationsAggregator::new(),
+                full_scan: OperationDurationsAggregator::new(),
             },
         })
     }
@@ -220,6 +224,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             })
             .unwrap_or(1);
 
+        let use_heuristic = hnsw_config.use_heuristic.unwrap_or(HNSW_USE_HEURISTIC);
+
         let mut config = HnswGraphConfig::new(
             hnsw_config.m,
             hnsw_config.ef_construct,
@@ -247,7 +253,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                     .unwrap_or(0)
                     * 10,
             ),
-            HNSW_USE_HEURISTIC,
+            use_heuristic,
         );
 
         let pool = rayon::ThreadPoolBuilder::new()
@@ -283,6 +289,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
 
         let mut indexed_vectors = 0;
+        let peak_memory = PeakMemory::default();
 
         if config.m > 0 {
             let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
@@ -315,13 +322,20 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 Ok::<_, OperationError>(())
             };
 
-            for vector_id in first_few_ids {
-                insert_point(vector_id)?;
-            }
+            peak_memory.track(
+                || graph_layers_builder.memory_usage_bytes(),
+                || {
+                    for vector_id in first_few_ids {
+                        insert_point(vector_id)?;
+                    }
 
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
-            }
+                    if !ids.is_empty() {
+                        pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+                    }
+
+                    Ok::<_, OperationError>(())
+                },
+            )?;
 
             debug!("finish main graph");
         } else {
@@ -367,8 +381,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         config.payload_m0.unwrap_or(config.m0),
                         config.ef_construct,
                         1,
-                        HNSW_USE_HEURISTIC,
-                        false,
+                        use_heuristic,
+                        LinksReservation::None,
                     );
                     Self::build_filtered_graph(
                         id_tracker,
@@ -380,6 +394,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         &mut additional_graph,
                         payload_block.condition,
                         &mut block_filter_list,
+                        &peak_memory,
+                        graph_layers_builder.memory_usage_bytes(),
                     )?;
                     graph_layers_builder.merge_from_other(additional_graph);
                 }
@@ -396,6 +412,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         config.indexed_vector_count.replace(indexed_vectors);
 
+        peak_memory.record(
+            graph_layers_builder.memory_usage_bytes() + block_filter_list.memory_usage_bytes(),
+        );
+        let peak_memory_bytes = peak_memory.get();
+        debug!("peak memory of HNSW build: {peak_memory_bytes} bytes");
+        config.build_peak_memory_bytes.replace(peak_memory_bytes);
+
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
         let graph: GraphLayers<TGraphLinks> =
             graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
@@ -424,6 +447,8 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         graph_layers_builder: &mut GraphLayersBuilder,
         condition: FieldCondition,
         block_filter_list: &mut VisitedListHandle,
+        peak_memory: &PeakMemory,
+        main_graph_memory_bytes: usize,
     ) -> OperationResult<()> {
         block_filter_list.next_iteration();
 
@@ -476,22 +501,31 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             .len()
             .min(SINGLE_THREADED_HNSW_BUILD_THRESHOLD);
 
-        // First index points in single thread so ensure warm start for parallel indexing process
-        for point_id in points_to_index[..first_points].iter().copied() {
-            insert_points(point_id)?;
-        }
-        // Once initial structure is built, index remaining points in parallel
-        // So that each thread will insert points in different parts of the graph,
-        // it is less likely that they will compete for the same locks
-        if points_to_index.len() > first_points {
-            pool.install(|| {
-                points_to_index
-                    .into_par_iter()
-                    .skip(first_points)
-                    .try_for_each(insert_points)
-            })?;
-        }
-        Ok(())
+        // Main graph and block filter list are not changed while the block is linked
+        let base_memory_bytes = main_graph_memory_bytes + block_filter_list.memory_usage_bytes();
+
+        peak_memory.track(
+            || base_memory_bytes + graph_layers_builder.memory_usage_bytes(),
+            || {
+                // First index points in single thread so ensure warm start for parallel
+                // indexing process
+                for point_id in points_to_index[..first_points].iter().copied() {
+                    insert_points(point_id)?;
+                }
+                // Once initial structure is built, index remaining points in parallel
+                // So that each thread will insert points in different parts of the graph,
+                // it is less likely that they will compete for the same locks
+                if points_to_index.len() > first_points {
+                    pool.install(|| {
+                        points_to_index
+                            .into_par_iter()
+                            .skip(first_points)
+                            .try_for_each(insert_points)
+                    })?;
+                }
+                Ok::<_, OperationError>(())
+            },
+        )
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -532,9 +566,14 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let filter_context = filter.map(|f| payload_index.filter_context(f));
         let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
 
-        let search_result =
-            self.graph
-                .search(oversampled_top, ef, points_scorer, custom_entry_points);
+        let (search_result, deadline_exceeded) = self.graph.search_with_deadline(
+            oversampled_top,
+            ef,
+            points_scorer,
+            custom_entry_points,
+            vector_query_context.deadline(),
+        );
+        vector_query_context.check_deadline_exceeded(deadline_exceeded)?;
         self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
     }
 
@@ -770,7 +809,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
-        let exact = params.map(|params| params.exact).unwrap_or(false);
+        let exact = params.is_some_and(SearchParams::is_exact);
         match filter {
             None => {
                 let id_tracker = self.id_tracker.borrow();
@@ -852,10 +891,19 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     id_tracker.available_point_count(),
                 );
 
+                // Plain search iterates over all points, if no index can be used to select them
+                let full_scan_timer = || {
+                    query_cardinality
+                        .primary_clauses
+                        .is_empty()
+                        .then(|| ScopeDurationMeasurer::new(&self.searches_telemetry.full_scan))
+                };
+
                 if query_cardinality.max < self.config.full_scan_threshold {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    let _full_scan_timer = full_scan_timer();
                     return self.search_vectors_plain(
                         vectors,
                         query_filter,
@@ -896,6 +944,7 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
                     // if cardinality is small - use plain index
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.small_cardinality);
+                    let _full_scan_timer = full_scan_timer();
                     self.search_vectors_plain(vectors, query_filter, top, params, query_context)
                 }
             }
@@ -912,9 +961,11 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
             filtered_small_cardinality: tm.small_cardinality.lock().get_statistics(detail),
             filtered_large_cardinality: tm.large_cardinality.lock().get_statistics(detail),
             filtered_exact: tm.exact_filtered.lock().get_statistics(detail),
+            filtered_full_scan: tm.full_scan.lock().get_statistics(detail),
             filtered_sparse: Default::default(),
             unfiltered_exact: tm.exact_unfiltered.lock().get_statistics(detail),
             unfiltered_sparse: Default::default(),
+            build_peak_memory_bytes: self.config.build_peak_memory_bytes,
         }
     }
 
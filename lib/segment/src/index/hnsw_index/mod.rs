@@ -3,11 +3,12 @@ use common::defaults::thread_count_for_hnsw;
 mod build_cache;
 pub mod build_condition_checker;
 mod config;
-mod entry_points;
+pub mod entry_points;
 pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
+mod peak_memory;
 pub mod point_scorer;
 mod search_context;
 
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+/// How often memory usage is sampled while build threads run
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Peak memory usage of an index build
+///
+/// Build threads allocate and release memory while they run, e.g. visited lists taken from
+/// a pool. To capture it, memory usage is sampled from a separate thread during the build.
+#[derive(Debug, Default)]
+pub struct PeakMemory {
+    peak_bytes: AtomicUsize,
+}
+
+impl PeakMemory {
+    /// Run `build`, sampling `memory_usage_bytes` from a separate thread until it returns
+    pub fn track<T>(
+        &self,
+        memory_usage_bytes: impl Fn() -> usize + Sync,
+        build: impl FnOnce() -> T,
+    ) -> T {
+        let done = AtomicBool::new(false);
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    self.record(memory_usage_bytes());
+                    thread::sleep(SAMPLE_INTERVAL);
+                }
+            });
+
+            // Stop sampling even if the build panics, the scope would wait for the sampler forever
+            let _done = SetOnDrop(&done);
+            let result = build();
+            self.record(memory_usage_bytes());
+            result
+        })
+    }
+
+    /// Record memory usage measured outside of [`Self::track`]
+    pub fn record(&self, bytes: usize) {
+        self.peak_bytes.fetch_max(bytes, Ordering::Relaxed);
+    }
+
+    pub fn get(&self) -> usize {
+        self.peak_bytes.load(Ordering::Relaxed)
+    }
+}
+
+struct SetOnDrop<'a>(&'a AtomicBool);
+
+impl Drop for SetOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_track_memory_released_during_build() {
+        let usage = AtomicUsize::new(100);
+        let peak_memory = PeakMemory::default();
+
+        let result = peak_memory.track(
+            || usage.load(Ordering::Relaxed),
+            || {
+                // Temporary allocation, released before the build returns
+                usage.store(1_000, Ordering::Relaxed);
+                thread::sleep(SAMPLE_INTERVAL * 5);
+                usage.store(200, Ordering::Relaxed);
+                "built"
+            },
+        );
+
+        assert_eq!(result, "built");
+        assert_eq!(peak_memory.get(), 1_000);
+
+        peak_memory.record(500);
+        assert_eq!(peak_memory.get(), 1_000);
+    }
+}
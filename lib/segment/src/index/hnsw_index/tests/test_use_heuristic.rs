@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::PointOffsetType;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use crate::index::hnsw_index::num_rayon_threads;
+use crate::segment::Segment;
+use crate::segment_constructor::build_segment;
+use crate::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+
+const DIM: usize = 2;
+const M: usize = 8;
+const NUM_VECTORS: u64 = 1_000;
+
+/// Number of links of each point on level 0
+fn build_level_0_links_count(segment: &Segment, use_heuristic: Option<bool>) -> Vec<usize> {
+    let stopped = AtomicBool::new(false);
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let hnsw_config = HnswConfig {
+        m: M,
+        ef_construct: 64,
+        full_scan_threshold: 10_000,
+        max_indexing_threads: 1,
+        on_disk: Some(false),
+        payload_m: None,
+        use_heuristic,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config,
+        permit: Some(permit),
+        stopped: &stopped,
+    })
+    .unwrap();
+
+    (0..NUM_VECTORS)
+        .map(|point_id| {
+            hnsw_index
+                .graph()
+                .links
+                .links(point_id as PointOffsetType, 0)
+                .len()
+        })
+        .collect()
+}
+
+#[test]
+fn test_hnsw_without_heuristic() {
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Euclid,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(&mut rnd, DIM);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let heuristic_links = build_level_0_links_count(&segment, None);
+    let nearest_links = build_level_0_links_count(&segment, Some(false));
+
+    // Nearest linking fills up all `m0` slots of a point, there are always enough candidates
+    let m0 = M * 2;
+    assert!(nearest_links.iter().all(|&count| count == m0));
+
+    // The heuristic drops candidates, which are closer to already selected neighbours than to
+    // the point itself. In low dimensions it keeps noticeably less links.
+    let heuristic_total: usize = heuristic_links.iter().sum();
+    let nearest_total: usize = nearest_links.iter().sum();
+    assert!(heuristic_links.iter().all(|&count| count <= m0));
+    assert!(heuristic_total < nearest_total);
+}
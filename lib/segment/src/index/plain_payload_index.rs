@@ -279,7 +279,7 @@ impl VectorIndex for PlainIndex {
         params: Option<&SearchParams>,
         query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
-        let is_indexed_only = params.map(|p| p.indexed_only).unwrap_or(false);
+        let is_indexed_only = params.is_some_and(SearchParams::is_indexed_only);
         if is_indexed_only
             && !self.is_small_enough_for_unindexed_search(
                 query_context.search_optimized_threshold_kb(),
@@ -354,9 +354,11 @@ impl VectorIndex for PlainIndex {
             filtered_small_cardinality: OperationDurationStatistics::default(),
             filtered_large_cardinality: OperationDurationStatistics::default(),
             filtered_exact: OperationDurationStatistics::default(),
+            filtered_full_scan: OperationDurationStatistics::default(),
             filtered_sparse: Default::default(),
             unfiltered_exact: OperationDurationStatistics::default(),
             unfiltered_sparse: OperationDurationStatistics::default(),
+            build_peak_memory_bytes: None,
         }
     }
 
@@ -284,6 +284,7 @@ mod tests {
             geo_radius: None,
             values_count: None,
             geo_polygon: None,
+            array_match: None,
         })
     }
 
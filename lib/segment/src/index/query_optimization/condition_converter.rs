@@ -16,8 +16,9 @@ use crate::payload_storage::query_checker::{
     select_nested_indexes,
 };
 use crate::types::{
-    Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPolygon,
-    GeoRadius, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range, RangeInterface,
+    ArrayMatch, Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox,
+    GeoPolygon, GeoRadius, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range,
+    RangeInterface,
 };
 
 pub fn condition_converter<'a>(
@@ -139,6 +140,11 @@ pub fn field_condition_index<'a>(
     index: &'a FieldIndex,
     field_condition: &FieldCondition,
 ) -> Option<ConditionCheckerFn<'a>> {
+    // Index checkers match if any value of a point matches, check payload for other semantics
+    if field_condition.array_match == Some(ArrayMatch::All) {
+        return None;
+    }
+
     match field_condition {
         FieldCondition {
             r#match: Some(cond_match),
@@ -176,6 +182,7 @@ pub fn field_condition_index<'a>(
             //
             // TODO: Try to use the indices that actually support counting values.
             values_count: _,
+            array_match: _,
         } => None,
     }
 }
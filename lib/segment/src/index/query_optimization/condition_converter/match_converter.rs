@@ -10,9 +10,13 @@ use crate::types::{
 };
 
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
+    let cond_match = match index {
+        FieldIndex::KeywordIndex(keyword_index) => keyword_index.normalize_match(cond_match),
+        _ => cond_match,
+    };
     match cond_match {
         Match::Value(MatchValue { value }) => get_match_value_checker(value, index),
-        Match::Text(MatchText { text }) => get_match_text_checker(text, index),
+        Match::Text(text_match) => get_match_text_checker(text_match, index),
         Match::Any(MatchAny { any }) => get_match_any_checker(any, index),
         Match::Except(MatchExcept { except }) => get_match_except_checker(except, index),
     }
@@ -213,12 +217,12 @@ fn get_match_except_checker(except: AnyVariants, index: &FieldIndex) -> Option<C
     checker
 }
 
-fn get_match_text_checker(text: String, index: &FieldIndex) -> Option<ConditionCheckerFn> {
+fn get_match_text_checker(text_match: MatchText, index: &FieldIndex) -> Option<ConditionCheckerFn> {
     match index {
         FieldIndex::FullTextIndex(full_text_index) => {
-            let parsed_query = full_text_index.parse_query(&text);
+            let query = full_text_index.parse_match_text(&text_match);
             Some(Box::new(move |point_id: PointOffsetType| {
-                full_text_index.check_match(&parsed_query, point_id)
+                full_text_index.check_text_query_match(&query, point_id)
             }))
         }
         FieldIndex::BinaryIndex(_)
@@ -35,9 +35,11 @@ impl SparseSearchesTelemetry {
             filtered_small_cardinality: self.small_cardinality.lock().get_statistics(detail),
             filtered_large_cardinality: Default::default(),
             filtered_exact: Default::default(),
+            filtered_full_scan: Default::default(),
             filtered_sparse: self.filtered_sparse.lock().get_statistics(detail),
             unfiltered_sparse: self.unfiltered_sparse.lock().get_statistics(detail),
             unfiltered_exact: Default::default(),
+            build_peak_memory_bytes: None,
         }
     }
 }
@@ -351,8 +351,11 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             &self.inverted_index,
             memory_handle,
             &is_stopped,
-        );
-        Ok(search_context.plain_search(&ids))
+        )
+        .with_deadline(vector_query_context.deadline());
+        let search_result = search_context.plain_search(&ids);
+        vector_query_context.check_deadline_exceeded(search_context.is_deadline_exceeded())?;
+        Ok(search_result)
     }
 
     // search using sparse vector inverted index
@@ -362,7 +365,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         filter: Option<&Filter>,
         top: usize,
         vector_query_context: &VectorQueryContext,
-    ) -> Vec<ScoredPointOffset> {
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
         let vector_storage = self.vector_storage.borrow();
         let id_tracker = self.id_tracker.borrow();
         let deleted_point_bitslice = vector_query_context
@@ -384,9 +387,10 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             &self.inverted_index,
             memory_handle,
             &is_stopped,
-        );
+        )
+        .with_deadline(vector_query_context.deadline());
 
-        match filter {
+        let search_result = match filter {
             Some(filter) => {
                 let payload_index = self.payload_index.borrow();
                 let filter_context = payload_index.filter_context(filter);
@@ -396,7 +400,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 search_context.search(&matches_filter_condition)
             }
             None => search_context.search(&not_deleted_condition),
-        }
+        };
+        vector_query_context.check_deadline_exceeded(search_context.is_deadline_exceeded())?;
+        Ok(search_result)
     }
 
     fn search_nearest_query(
@@ -434,12 +440,12 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 } else {
                     let _timer =
                         ScopeDurationMeasurer::new(&self.searches_telemetry.filtered_sparse);
-                    Ok(self.search_sparse(&vector, Some(filter), top, vector_query_context))
+                    self.search_sparse(&vector, Some(filter), top, vector_query_context)
                 }
             }
             None => {
                 let _timer = ScopeDurationMeasurer::new(&self.searches_telemetry.unfiltered_sparse);
-                Ok(self.search_sparse(&vector, filter, top, vector_query_context))
+                self.search_sparse(&vector, filter, top, vector_query_context)
             }
         }
     }
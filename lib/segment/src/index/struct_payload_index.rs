@@ -36,7 +36,7 @@ use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::{FilterContext, PayloadStorage};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
+    infer_collection_value_type, infer_value_type, ArrayMatch, Condition, FieldCondition, Filter,
     IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
     PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
@@ -73,9 +73,20 @@ impl StructPayloadIndex {
                 ..condition.clone()
             };
 
-            indexes
+            let estimation = indexes
                 .iter()
-                .find_map(|index| index.estimate_cardinality(&full_path_condition))
+                .find_map(|index| index.estimate_cardinality(&full_path_condition))?;
+
+            // Index counts points with any matching value, which is an upper bound if all
+            // values have to match
+            if condition.array_match == Some(ArrayMatch::All) {
+                Some(CardinalityEstimation {
+                    min: 0,
+                    ..estimation
+                })
+            } else {
+                Some(estimation)
+            }
         })
     }
 
@@ -116,7 +116,7 @@ impl VectorIndex for VectorIndexEnum {
         params: Option<&SearchParams>,
         query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
-        match self {
+        let mut results = match self {
             VectorIndexEnum::Plain(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
@@ -153,7 +153,14 @@ impl VectorIndex for VectorIndexEnum {
             VectorIndexEnum::SparseCompressedMmapU8(index) => {
                 index.search(vectors, filter, top, params, query_context)
             }
+        }?;
+
+        // Drop sub-threshold points right away, so they are not processed any further
+        for result in &mut results {
+            query_context.apply_score_threshold(result);
         }
+
+        Ok(results)
     }
 
     fn get_telemetry_data(&self, detail: TelemetryDetail) -> VectorIndexSearchesTelemetry {
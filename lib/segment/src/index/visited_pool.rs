@@ -1,5 +1,7 @@
 //! Structures for fast and tread-safe way to check if some points were visited or not
 
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use common::defaults::POOL_KEEP_LIMIT;
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
@@ -9,6 +11,8 @@ use parking_lot::RwLock;
 pub struct VisitedListHandle<'a> {
     pool: &'a VisitedPool,
     visited_list: VisitedList,
+    /// Memory of the list counted as in use by the pool
+    tracked_bytes: usize,
 }
 
 /// Visited list reuses same memory to keep track of visited points ids among multiple consequent queries
@@ -37,10 +41,17 @@ impl VisitedList {
             visit_counters: vec![0; num_points],
         }
     }
+
+    fn memory_usage_bytes(&self) -> usize {
+        self.visit_counters.capacity() * std::mem::size_of::<usize>()
+    }
 }
 
 impl<'a> Drop for VisitedListHandle<'a> {
     fn drop(&mut self) {
+        self.pool
+            .in_use_bytes
+            .fetch_sub(self.tracked_bytes, Ordering::Relaxed);
         self.pool
             .return_back(std::mem::take(&mut self.visited_list));
     }
@@ -48,9 +59,13 @@ impl<'a> Drop for VisitedListHandle<'a> {
 
 impl<'a> VisitedListHandle<'a> {
     fn new(pool: &'a VisitedPool, data: VisitedList) -> Self {
+        let tracked_bytes = data.memory_usage_bytes();
+        pool.in_use_bytes
+            .fetch_add(tracked_bytes, Ordering::Relaxed);
         VisitedListHandle {
             pool,
             visited_list: data,
+            tracked_bytes,
         }
     }
 
@@ -90,6 +105,11 @@ impl<'a> VisitedListHandle<'a> {
     pub fn next_iteration(&mut self) {
         self.visited_list.current_iter += 1;
     }
+
+    /// Memory allocated by this list
+    pub fn memory_usage_bytes(&self) -> usize {
+        self.visited_list.memory_usage_bytes()
+    }
 }
 
 /// Keeps a list of `VisitedList` which could be requested and released from multiple threads
@@ -98,12 +118,15 @@ impl<'a> VisitedListHandle<'a> {
 #[derive(Debug)]
 pub struct VisitedPool {
     pool: RwLock<Vec<VisitedList>>,
+    /// Memory of the lists currently taken from the pool
+    in_use_bytes: AtomicUsize,
 }
 
 impl VisitedPool {
     pub fn new() -> Self {
         VisitedPool {
             pool: RwLock::new(Vec::with_capacity(*POOL_KEEP_LIMIT)),
+            in_use_bytes: AtomicUsize::new(0),
         }
     }
 
@@ -121,6 +144,17 @@ impl VisitedPool {
         }
     }
 
+    /// Memory allocated by the lists kept in the pool and the lists currently in use
+    pub fn memory_usage_bytes(&self) -> usize {
+        let pooled_bytes: usize = self
+            .pool
+            .read()
+            .iter()
+            .map(VisitedList::memory_usage_bytes)
+            .sum();
+        pooled_bytes + self.in_use_bytes.load(Ordering::Relaxed)
+    }
+
     fn return_back(&self, data: VisitedList) {
         let mut pool = self.pool.write();
         if pool.len() < *POOL_KEEP_LIMIT {
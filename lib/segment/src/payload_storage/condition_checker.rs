@@ -4,10 +4,11 @@ use std::str::FromStr;
 
 use serde_json::Value;
 
+use crate::index::field_index::full_text_index::text_index::FullTextIndex;
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
-    RangeInterface, ValueVariants, ValuesCount,
+    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchValue, Range, RangeInterface,
+    ValueVariants, ValuesCount,
 };
 
 /// Threshold representing the point to which iterating through an IndexSet is more efficient than using hashing.
@@ -42,6 +43,7 @@ impl ValueChecker for FieldCondition {
             geo_polygon,
             values_count,
             key: _,
+            array_match: _,
         } = self;
 
         r#match
@@ -90,8 +92,8 @@ impl ValueChecker for Match {
                 }
                 _ => false,
             },
-            Match::Text(MatchText { text }) => match payload {
-                Value::String(stored) => stored.contains(text),
+            Match::Text(text_match) => match payload {
+                Value::String(stored) => FullTextIndex::check_unindexed_match(text_match, stored),
                 _ => false,
             },
             Match::Any(MatchAny { any }) => match (payload, any) {
@@ -264,6 +266,30 @@ mod tests {
         assert!(!miss_geo_query.check(&berlin_and_moscow));
     }
 
+    #[test]
+    fn test_text_matching() {
+        let text = json!("Red laptop, blue tablet");
+        let check = |condition: Match| condition.check(&text);
+
+        // Plain text is matched as a substring
+        assert!(check(Match::new_text("laptop, blue")));
+        assert!(!check(Match::new_text("Laptop")));
+
+        // Prefix of a token, but not within a token
+        assert!(check(Match::new_text_prefix("Lap")));
+        assert!(!check(Match::new_text_prefix("top")));
+
+        // Boolean queries on tokens of the text
+        assert!(check(Match::new_text_boolean("laptop AND (red OR green)")));
+        assert!(check(Match::new_text_boolean("Tablet NOT phone")));
+        assert!(!check(Match::new_text_boolean("laptop NOT blue")));
+        assert!(!check(Match::new_text_boolean("lap OR phone")));
+
+        // Without operators, all tokens are required in any order
+        assert!(check(Match::new_text_boolean("tablet red")));
+        assert!(!check(Match::new_text_boolean("tablet green")));
+    }
+
     #[test]
     fn test_value_count() {
         let countries = json!([
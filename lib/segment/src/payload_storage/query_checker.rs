@@ -2,20 +2,23 @@
 
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::iter;
 use std::ops::Deref;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
+use itertools::Either;
+use serde_json::Value;
 
-use crate::common::utils::{check_is_empty, check_is_null, IndexesMap};
+use crate::common::utils::{check_is_empty, check_is_null, IndexesMap, MultiValue};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
+    ArrayMatch, Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
     OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
 };
 
@@ -179,6 +182,12 @@ where
     let field_values = payload.get_value(&field_condition.key);
     let field_indexes = field_indexes.get(&field_condition.key);
 
+    if field_condition.array_match == Some(ArrayMatch::All)
+        && field_condition.values_count.is_none()
+    {
+        return check_all_field_values(field_condition, field_values, field_indexes);
+    }
+
     // This covers a case, when a field index affects the result of the condition.
     if let Some(field_indexes) = field_indexes {
         for p in field_values {
@@ -210,6 +219,36 @@ where
     }
 }
 
+/// Check that the field has values, and that each of them, including each array element,
+/// satisfies the condition.
+fn check_all_field_values<R>(
+    field_condition: &FieldCondition,
+    field_values: MultiValue<&Value>,
+    field_indexes: Option<&R>,
+) -> bool
+where
+    R: AsRef<Vec<FieldIndex>>,
+{
+    let field_indexes = field_indexes.map_or(&[][..], |indexes| indexes.as_ref().as_slice());
+
+    let mut elements = field_values
+        .into_iter()
+        .flat_map(|value| match value {
+            Value::Array(array) => Either::Left(array.iter()),
+            value => Either::Right(iter::once(value)),
+        })
+        .peekable();
+
+    elements.peek().is_some()
+        && elements.all(|element| {
+            // Index affects the result of the condition, if it is able to check it
+            field_indexes
+                .iter()
+                .find_map(|index| index.check_condition(field_condition, element))
+                .unwrap_or_else(|| field_condition.check_match(element))
+        })
+}
+
 /// Only used for testing
 #[cfg(feature = "testing")]
 pub struct SimpleConditionChecker {
@@ -213,6 +213,7 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
         geo_radius,
         geo_polygon,
         values_count,
+        array_match: _,
     } = field_condition;
 
     let mut inferred = Vec::new();
@@ -27,9 +27,9 @@ use crate::segment::{
 };
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
-    Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
-    PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorDataInfo, WithPayload, WithVector,
+    Distance, Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType,
+    PayloadKeyTypeRef, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo,
+    SegmentType, SeqNumberType, VectorDataInfo, WithPayload, WithVector,
 };
 use crate::utils;
 use crate::vector_storage::VectorStorage;
@@ -60,8 +60,24 @@ impl SegmentEntry for Segment {
         query_context: SegmentQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPoint>>> {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
+
+        // No point can match the filter, don't search the vector index at all.
+        // Only the upper bound of the estimation is exact enough to rely on.
+        let nothing_matches = filter.is_some_and(|filter| {
+            self.payload_index.borrow().estimate_cardinality(filter).max == 0
+        });
+        if nothing_matches {
+            return Ok(vec![Vec::new(); query_vectors.len()]);
+        }
+
         let vector_data = &self.vector_data[vector_name];
-        let vector_query_context = query_context.get_vector_context(vector_name);
+        // Sparse vectors are always scored with dot product
+        let distance = self
+            .segment_config
+            .vector_data
+            .get(vector_name)
+            .map_or(Distance::Dot, |config| config.distance);
+        let vector_query_context = query_context.get_vector_context(vector_name, distance);
         let internal_results = vector_data.vector_index.borrow().search(
             query_vectors,
             filter,
@@ -283,6 +299,20 @@ impl SegmentEntry for Segment {
         Ok(result)
     }
 
+    fn prefetch_vectors(&self, vector_name: &str, point_ids: &[PointIdType]) -> usize {
+        let Some(vector_data) = self.vector_data.get(vector_name) else {
+            return 0;
+        };
+        let internal_ids: Vec<_> = {
+            let id_tracker = self.id_tracker.borrow();
+            point_ids
+                .iter()
+                .filter_map(|&point_id| id_tracker.internal_id(point_id))
+                .collect()
+        };
+        vector_data.vector_storage.borrow().prefetch(&internal_ids)
+    }
+
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload> {
         let internal_id = self.lookup_internal_id(point_id)?;
         self.payload_by_offset(internal_id)
@@ -322,6 +352,11 @@ impl SegmentEntry for Segment {
         order_by: &'a OrderBy,
         is_stopped: &AtomicBool,
     ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        if let Some(geo_from) = &order_by.geo_distance_from {
+            return self
+                .filtered_read_by_geo_distance(order_by, geo_from, limit, filter, is_stopped);
+        }
+
         match filter {
             None => self.filtered_read_by_value_stream(order_by, limit, None, is_stopped),
             Some(filter) => {
@@ -478,6 +513,8 @@ impl SegmentEntry for Segment {
             is_appendable: self.appendable_flag,
             index_schema: schema,
             vector_data: vector_data_info,
+            created_at: self.created_at,
+            last_optimized_at: self.last_optimized_at,
         }
     }
 
@@ -614,8 +651,14 @@ impl SegmentEntry for Segment {
     }
 
     fn drop_data(self) -> OperationResult<()> {
-        let current_path = self.current_path.clone();
+        let mut current_path = self.current_path.clone();
         drop(self);
+        // Segment data may be stored elsewhere and linked, remove the data and not only the link
+        if current_path.is_symlink() {
+            let target = fs::read_link(&current_path)?;
+            fs::remove_file(&current_path)?;
+            current_path = target;
+        }
         let mut deleted_path = current_path.clone();
         deleted_path.set_extension("deleted");
         fs::rename(&current_path, &deleted_path)?;
@@ -70,6 +70,10 @@ pub struct Segment {
     /// Shows what kind of indexes and storages are used in this segment
     pub segment_type: SegmentType,
     pub segment_config: SegmentConfig,
+    /// Time the segment was created, `None` if created by an older version
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time the segment was last built by an optimizer
+    pub last_optimized_at: Option<chrono::DateTime<chrono::Utc>>,
     /// Last unhandled error
     /// If not None, all update operations will be aborted until original operation is performed properly
     pub error_status: Option<SegmentFailedState>,
@@ -9,7 +9,7 @@ use crate::data_types::order_by::{Direction, OrderBy, OrderValue};
 use crate::index::field_index::numeric_index::StreamRange;
 use crate::index::PayloadIndex;
 use crate::spaces::tools::{peek_top_largest_iterable, peek_top_smallest_iterable};
-use crate::types::{Filter, PointIdType};
+use crate::types::{Filter, GeoPoint, PointIdType};
 
 impl Segment {
     pub fn filtered_read_by_index_ordered(
@@ -76,6 +76,83 @@ impl Segment {
         Ok(page)
     }
 
+    /// Read points ordered by distance from `geo_from` to their geo values of `order_by.key`
+    ///
+    /// Distances are computed for every point matching the filter, so this is a full scan of the
+    /// filtered points. A point is repeated for as many geo values as it has.
+    pub fn filtered_read_by_geo_distance(
+        &self,
+        order_by: &OrderBy,
+        geo_from: &GeoPoint,
+        limit: Option<usize>,
+        filter: Option<&Filter>,
+        is_stopped: &AtomicBool,
+    ) -> OperationResult<Vec<(OrderValue, PointIdType)>> {
+        let payload_index = self.payload_index.borrow();
+        let id_tracker = self.id_tracker.borrow();
+
+        let geo_index = payload_index
+            .field_indexes
+            .get(&order_by.key)
+            .and_then(|indexes| indexes.iter().find_map(|index| index.as_geo()))
+            .ok_or_else(|| OperationError::MissingGeoIndexForOrderBy {
+                key: order_by.key.to_string(),
+            })?;
+
+        let cardinality_estimation =
+            filter.map(|filter| (filter, payload_index.estimate_cardinality(filter)));
+
+        let internal_ids = match &cardinality_estimation {
+            None => Either::Left(id_tracker.iter_ids()),
+            Some((filter, cardinality_estimation)) => Either::Right(
+                payload_index.iter_filtered_points(filter, &*id_tracker, cardinality_estimation),
+            ),
+        };
+
+        let start_from = order_by.start_from();
+
+        let values_ids_iterator = internal_ids
+            .check_stop(|| is_stopped.load(Ordering::Relaxed))
+            .flat_map(|internal_id| {
+                geo_index
+                    .get_values(internal_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|point| OrderValue::Float(geo_from.haversine_distance(point)))
+                    .filter(|distance| match order_by.direction() {
+                        Direction::Asc => distance >= &start_from,
+                        Direction::Desc => distance <= &start_from,
+                    })
+                    .map(move |distance| (distance, internal_id))
+            })
+            .filter_map(|(distance, internal_id)| {
+                id_tracker
+                    .external_id(internal_id)
+                    .map(|external_id| (distance, external_id))
+            });
+
+        let page = match order_by.direction() {
+            Direction::Asc => {
+                let mut page = match limit {
+                    Some(limit) => peek_top_smallest_iterable(values_ids_iterator, limit),
+                    None => values_ids_iterator.collect(),
+                };
+                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_a.cmp(value_b));
+                page
+            }
+            Direction::Desc => {
+                let mut page = match limit {
+                    Some(limit) => peek_top_largest_iterable(values_ids_iterator, limit),
+                    None => values_ids_iterator.collect(),
+                };
+                page.sort_unstable_by(|(value_a, _), (value_b, _)| value_b.cmp(value_a));
+                page
+            }
+        };
+
+        Ok(page)
+    }
+
     pub fn filtered_read_by_value_stream(
         &self,
         order_by: &OrderBy,
@@ -2,6 +2,7 @@ use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self};
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
 use std::thread::{self, JoinHandle};
 
 use bitvec::prelude::BitVec;
@@ -23,6 +24,7 @@ use crate::data_types::vectors::Vector;
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::segment_constructor::load_segment;
 use crate::types::{
     Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
     SegmentState, SeqNumberType,
@@ -325,6 +327,8 @@ impl Segment {
         SegmentState {
             version: self.version,
             config: self.segment_config.clone(),
+            created_at: self.created_at,
+            last_optimized_at: self.last_optimized_at,
         }
     }
 
@@ -424,6 +428,80 @@ impl Segment {
         payload_index.infer_payload_type(key)
     }
 
+    /// Remove payload `keys` from all points of the segment, without changing its version.
+    ///
+    /// Intended for sanitizing copies of a segment, such as snapshots. Field indexes of the keys
+    /// are dropped as well, so that no indexed values are left behind.
+    ///
+    /// Returns number of points which had any of the keys.
+    pub fn strip_payload_keys(&mut self, keys: &[PayloadKeyType]) -> OperationResult<usize> {
+        let internal_ids: Vec<_> = self.id_tracker.borrow().iter_ids().collect();
+
+        let mut payload_index = self.payload_index.borrow_mut();
+        let mut stripped_points = 0;
+        for internal_id in internal_ids {
+            let mut stripped = false;
+            for key in keys {
+                stripped |= !payload_index.delete(internal_id, key)?.is_empty();
+            }
+            stripped_points += usize::from(stripped);
+        }
+
+        for key in keys {
+            payload_index.drop_index(key)?;
+        }
+        drop(payload_index);
+
+        // Version is not changed, force flush to persist stripped payload
+        self.flush(true, true)?;
+
+        Ok(stripped_points)
+    }
+
+    /// Remove payload `keys` from the segment snapshot archive at `snapshot_path`, in place.
+    ///
+    /// The archive is unpacked into `temp_path`, stripped with [`Self::strip_payload_keys`] and
+    /// archived again.
+    ///
+    /// Returns number of points which had any of the keys.
+    pub fn strip_payload_keys_from_snapshot(
+        snapshot_path: &Path,
+        temp_path: &Path,
+        keys: &[PayloadKeyType],
+    ) -> OperationResult<usize> {
+        let (Some(segment_id), Some(snapshot_dir)) = (
+            snapshot_path.file_stem().and_then(|name| name.to_str()),
+            snapshot_path.parent(),
+        ) else {
+            return Err(OperationError::service_error(format!(
+                "invalid segment snapshot path {snapshot_path:?}"
+            )));
+        };
+
+        let work_dir = tempfile::Builder::new()
+            .prefix(&format!("segment-{segment_id}-strip-"))
+            .tempdir_in(temp_path)?;
+
+        let work_archive_path = work_dir.path().join(format!("{segment_id}.tar"));
+        fs::copy(snapshot_path, &work_archive_path)?;
+        Self::restore_snapshot(&work_archive_path, segment_id)?;
+        fs::remove_file(&work_archive_path)?;
+
+        let segment_path = work_dir.path().join(segment_id);
+        let Some(mut segment) = load_segment(&segment_path, &AtomicBool::new(false))? else {
+            return Err(OperationError::service_error(format!(
+                "segment snapshot {snapshot_path:?} contains no segment"
+            )));
+        };
+
+        let stripped_points = segment.strip_payload_keys(keys)?;
+
+        // Overwrites the original archive
+        segment.take_snapshot(work_dir.path(), snapshot_dir)?;
+
+        Ok(stripped_points)
+    }
+
     pub fn restore_snapshot(snapshot_path: &Path, segment_id: &str) -> OperationResult<()> {
         let segment_path = snapshot_path.parent().unwrap().join(segment_id);
 
@@ -1,16 +1,19 @@
 use std::sync::atomic::AtomicBool;
 
+use common::types::TelemetryDetail;
 use tempfile::Builder;
 
 use super::*;
 use crate::common::operation_error::OperationError::PointIdError;
 use crate::common::{check_named_vectors, check_vector, check_vector_name};
 use crate::data_types::named_vectors::NamedVectors;
-use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use crate::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
 use crate::entry::entry_point::SegmentEntry;
+use crate::json_path::JsonPath;
 use crate::segment_constructor::{build_segment, load_segment};
 use crate::types::{
-    Distance, Filter, Indexes, Payload, SegmentConfig, VectorDataConfig, VectorStorageType,
+    Condition, Distance, FieldCondition, Filter, Indexes, Match, Payload, PayloadFieldSchema,
+    PayloadSchemaType, SegmentConfig, ValueVariants, VectorDataConfig, VectorStorageType,
     WithPayload, WithVector,
 };
 
@@ -808,3 +811,90 @@ fn test_handle_point_version() {
         .unwrap();
     assert!(applied);
 }
+
+#[test]
+fn test_search_with_impossible_filter() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let dim = 4;
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Dot,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let color = JsonPath::new("color");
+    segment
+        .create_field_index(
+            0,
+            &color,
+            Some(&PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword)),
+        )
+        .unwrap();
+
+    for id in 0..10u64 {
+        segment
+            .upsert_point(
+                id + 1,
+                id.into(),
+                only_default_vector(&[id as f32, 1.0, 0.0, 1.0]),
+            )
+            .unwrap();
+        let payload: Payload = serde_json::json!({ "color": "red" }).into();
+        segment
+            .set_full_payload(id + 1, id.into(), &payload)
+            .unwrap();
+    }
+
+    let filtered_searches = |segment: &Segment| {
+        segment
+            .get_telemetry_data(TelemetryDetail::default())
+            .vector_index_searches[0]
+            .filtered_plain
+            .count
+    };
+
+    let query_vectors: [QueryVector; 2] =
+        [[1.0, 1.0, 1.0, 1.0].into(), [0.0, 1.0, 0.0, 1.0].into()];
+    let search = |segment: &Segment, value: &str| {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            color.clone(),
+            Match::new_value(ValueVariants::String(value.to_string())),
+        )));
+        segment
+            .search_batch(
+                DEFAULT_VECTOR_NAME,
+                &[&query_vectors[0], &query_vectors[1]],
+                &WithPayload::default(),
+                &false.into(),
+                Some(&filter),
+                10,
+                None,
+                Default::default(),
+            )
+            .unwrap()
+    };
+
+    // Nothing matches, results are empty without searching the vector index
+    assert_eq!(segment.estimate_point_count(None).max, 10);
+    let results = search(&segment, "blue");
+    assert_eq!(results, vec![vec![], vec![]]);
+    assert_eq!(filtered_searches(&segment), 0);
+
+    // Filter with matching points is searched as usual
+    let results = search(&segment, "red");
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|result| result.len() == 10));
+    assert_eq!(filtered_searches(&segment), 1);
+}
@@ -42,6 +42,8 @@ use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 /// Structure for constructing segment out of several other segments
 pub struct SegmentBuilder {
     version: SeqNumberType,
+    /// Creation time of the oldest source segment
+    created_at: Option<chrono::DateTime<chrono::Utc>>,
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
     vector_storages: HashMap<String, VectorStorageEnum>,
@@ -108,6 +110,7 @@ impl SegmentBuilder {
 
         Ok(SegmentBuilder {
             version: Default::default(), // default version is 0
+            created_at: None,
             id_tracker,
             payload_storage,
             vector_storages,
@@ -283,6 +286,13 @@ impl SegmentBuilder {
         let src_segment_max_version = segments.iter().map(|i| i.version()).max().unwrap();
         self.version = cmp::max(self.version, src_segment_max_version);
 
+        let src_segment_min_created_at = segments.iter().filter_map(|i| i.created_at).min();
+        self.created_at = self
+            .created_at
+            .into_iter()
+            .chain(src_segment_min_created_at)
+            .min();
+
         let vector_storages: Vec<_> = segments.iter().map(|i| &i.vector_data).collect();
 
         let mut new_internal_range = None;
@@ -393,6 +403,7 @@ impl SegmentBuilder {
         let (temp_path, destination_path) = {
             let SegmentBuilder {
                 version,
+                created_at,
                 id_tracker,
                 payload_storage,
                 mut vector_storages,
@@ -514,10 +525,13 @@ impl SegmentBuilder {
             drop(permit);
 
             // Finalize the newly created segment by saving config and version
+            let optimized_at = chrono::Utc::now();
             Segment::save_state(
                 &SegmentState {
                     version: Some(version),
                     config: segment_config,
+                    created_at: Some(created_at.unwrap_or(optimized_at)),
+                    last_optimized_at: Some(optimized_at),
                 },
                 &temp_path,
             )?;
@@ -605,6 +605,8 @@ fn create_segment(
         appendable_flag,
         payload_index,
         segment_config: config.clone(),
+        created_at: None,
+        last_optimized_at: None,
         error_status: None,
         database,
         flush_thread: Mutex::new(None),
@@ -664,7 +666,9 @@ pub fn load_segment(path: &Path, stopped: &AtomicBool) -> OperationResult<Option
 
     let segment_state = Segment::load_state(path)?;
 
-    let segment = create_segment(segment_state.version, path, &segment_state.config, stopped)?;
+    let mut segment = create_segment(segment_state.version, path, &segment_state.config, stopped)?;
+    segment.created_at = segment_state.created_at;
+    segment.last_optimized_at = segment_state.last_optimized_at;
 
     Ok(Some(segment))
 }
@@ -694,7 +698,8 @@ pub fn build_segment(
 
     std::fs::create_dir_all(&segment_path)?;
 
-    let segment = create_segment(None, &segment_path, config, &AtomicBool::new(false))?;
+    let mut segment = create_segment(None, &segment_path, config, &AtomicBool::new(false))?;
+    segment.created_at = Some(chrono::Utc::now());
     segment.save_current_state()?;
 
     // Version is the last file to save, as it will be used to check if segment was built correctly.
@@ -764,6 +769,8 @@ fn load_segment_state_v3(segment_path: &Path) -> OperationResult<SegmentState> {
             SegmentState {
                 version: Some(state.version),
                 config: segment_config.into(),
+                created_at: None,
+                last_optimized_at: None,
             }
         })
         .map_err(|err| {
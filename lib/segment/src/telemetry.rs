@@ -59,11 +59,20 @@ pub struct VectorIndexSearchesTelemetry {
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub filtered_exact: OperationDurationStatistics,
 
+    /// Filtered searches, which fell back to a full scan over all points, because no payload
+    /// index could be used for the filter.
+    #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
+    pub filtered_full_scan: OperationDurationStatistics,
+
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub filtered_sparse: OperationDurationStatistics,
 
     #[serde(skip_serializing_if = "OperationDurationStatistics::is_empty")]
     pub unfiltered_exact: OperationDurationStatistics,
+
+    /// Peak memory in bytes used while building the index, if known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub build_peak_memory_bytes: Option<usize>,
 }
 
 impl Anonymize for SegmentTelemetry {
@@ -90,6 +99,8 @@ impl Anonymize for SegmentInfo {
             is_appendable: self.is_appendable,
             index_schema: self.index_schema.anonymize(),
             vector_data: self.vector_data.anonymize(),
+            created_at: self.created_at,
+            last_optimized_at: self.last_optimized_at,
         }
     }
 }
@@ -157,8 +168,10 @@ impl Anonymize for VectorIndexSearchesTelemetry {
             filtered_small_cardinality: self.filtered_small_cardinality.anonymize(),
             filtered_large_cardinality: self.filtered_large_cardinality.anonymize(),
             filtered_exact: self.filtered_exact.anonymize(),
+            filtered_full_scan: self.filtered_full_scan.anonymize(),
             filtered_sparse: self.filtered_sparse.anonymize(),
             unfiltered_exact: self.filtered_exact.anonymize(),
+            build_peak_memory_bytes: self.build_peak_memory_bytes,
         }
     }
 }
@@ -219,7 +219,7 @@ impl Distance {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Order {
     LargeBetter,
     SmallBetter,
@@ -332,6 +332,12 @@ pub struct SegmentInfo {
     pub is_appendable: bool,
     pub index_schema: HashMap<PayloadKeyType, PayloadIndexInfo>,
     pub vector_data: HashMap<String, VectorDataInfo>,
+    /// Time the segment was created, unknown for segments created by older versions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time the segment was last built by an optimizer, if ever
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_optimized_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Additional parameters of the search
@@ -360,6 +366,39 @@ pub struct QuantizationSearchParams {
     pub oversampling: Option<f64>,
 }
 
+impl Eq for QuantizationSearchParams {}
+
+impl Hash for QuantizationSearchParams {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            ignore,
+            rescore,
+            oversampling,
+        } = self;
+
+        ignore.hash(state);
+        rescore.hash(state);
+        oversampling.map(f64::to_le_bytes).hash(state);
+    }
+}
+
+impl QuantizationSearchParams {
+    /// Fill in parameters not specified in these params from `defaults`.
+    pub fn with_defaults(self, defaults: &QuantizationSearchParams) -> Self {
+        let QuantizationSearchParams {
+            ignore,
+            rescore,
+            oversampling,
+        } = self;
+
+        QuantizationSearchParams {
+            ignore: ignore || defaults.ignore,
+            rescore: rescore.or(defaults.rescore),
+            oversampling: oversampling.or(defaults.oversampling),
+        }
+    }
+}
+
 pub const fn default_quantization_ignore_value() -> bool {
     false
 }
@@ -378,7 +417,7 @@ pub struct SearchParams {
 
     /// Search without approximation. If set to true, search may run long but with exact results.
     #[serde(default)]
-    pub exact: bool,
+    pub exact: Option<bool>,
 
     /// Quantization params
     #[serde(default)]
@@ -389,7 +428,84 @@ pub struct SearchParams {
     /// Using this option prevents slow searches in case of delayed index, but does not
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
-    pub indexed_only: bool,
+    pub indexed_only: Option<bool>,
+
+    /// If set, scores of the results are rounded to this number of decimal places.
+    /// Scores which differ only slightly become equal, the order of the results is preserved.
+    #[serde(default)]
+    #[validate(range(max = 9))]
+    pub score_precision: Option<u32>,
+}
+
+impl Eq for SearchParams {}
+
+impl Hash for SearchParams {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            hnsw_ef,
+            exact,
+            quantization,
+            indexed_only,
+            score_precision,
+        } = self;
+
+        hnsw_ef.hash(state);
+        exact.hash(state);
+        quantization.hash(state);
+        indexed_only.hash(state);
+        score_precision.hash(state);
+    }
+}
+
+impl SearchParams {
+    /// Whether to search without approximation, disabled if not specified.
+    pub fn is_exact(&self) -> bool {
+        self.exact.unwrap_or_default()
+    }
+
+    /// Whether to search among indexed or small segments only, disabled if not specified.
+    pub fn is_indexed_only(&self) -> bool {
+        self.indexed_only.unwrap_or_default()
+    }
+
+    /// Fill in parameters not specified in these params from `defaults`.
+    ///
+    /// Flags explicitly disabled in these params stay disabled.
+    pub fn with_defaults(self, defaults: &SearchParams) -> Self {
+        let SearchParams {
+            hnsw_ef,
+            exact,
+            quantization,
+            indexed_only,
+            score_precision,
+        } = self;
+
+        let quantization = match (quantization, defaults.quantization) {
+            (Some(quantization), Some(defaults)) => Some(quantization.with_defaults(&defaults)),
+            (quantization, defaults) => quantization.or(defaults),
+        };
+
+        SearchParams {
+            hnsw_ef: hnsw_ef.or(defaults.hnsw_ef),
+            exact: exact.or(defaults.exact),
+            quantization,
+            indexed_only: indexed_only.or(defaults.indexed_only),
+            score_precision: score_precision.or(defaults.score_precision),
+        }
+    }
+
+    /// Round scores of the points to the configured `score_precision`, if any.
+    ///
+    /// Rounding is monotonic, so points sorted by score stay sorted.
+    pub fn round_scores(&self, points: &mut [ScoredPoint]) {
+        let Some(precision) = self.score_precision else {
+            return;
+        };
+        let factor = 10f64.powi(precision as i32);
+        for point in points {
+            point.score = ((f64::from(point.score) * factor).round() / factor) as ScoreType;
+        }
+    }
 }
 
 /// Collection default values
@@ -468,9 +584,10 @@ impl Indexes {
 #[serde(rename_all = "snake_case")]
 pub struct HnswConfig {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
+    #[validate(range(max = 1024))]
     pub m: usize,
     /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build index.
-    #[validate(range(min = 4))]
+    #[validate(range(min = 4, max = 10000))]
     pub ef_construct: usize,
     /// Minimal size (in KiloBytes) of vectors for additional payload-based indexing.
     /// If payload chunk is smaller than `full_scan_threshold_kb` additional indexing won't be used -
@@ -488,8 +605,14 @@ pub struct HnswConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub on_disk: Option<bool>,
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
+    #[validate(range(max = 1024))]
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Select neighbours of a node with the heuristic, which prefers diverse directions over the
+    /// nearest candidates. If set to false, nodes are simply linked to their nearest neighbours.
+    /// Default: true
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub use_heuristic: Option<bool>,
 }
 
 impl HnswConfig {
@@ -506,6 +629,7 @@ impl HnswConfig {
             || self.ef_construct != other.ef_construct
             || self.full_scan_threshold != other.full_scan_threshold
             || self.payload_m != other.payload_m
+            || self.use_heuristic.unwrap_or(true) != other.use_heuristic.unwrap_or(true)
             // Data on disk is the same, we have a unit test for that. We can eventually optimize
             // this to just reload the collection rather than optimizing it again as a whole just
             // to flip this flag
@@ -672,6 +796,7 @@ impl Default for HnswConfig {
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            use_heuristic: None,
         }
     }
 }
@@ -897,6 +1022,12 @@ pub const DEFAULT_SPARSE_FULL_SCAN_THRESHOLD: usize = 5_000;
 pub struct SegmentState {
     pub version: Option<SeqNumberType>,
     pub config: SegmentConfig,
+    /// Time the segment was created, for optimized segments the time its oldest source was created
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Time the segment was last built by an optimizer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_optimized_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 /// Geo point payload schema
@@ -1377,11 +1508,24 @@ pub struct MatchValue {
 #[serde(rename_all = "snake_case")]
 pub struct MatchText {
     pub text: String,
+    /// If true - match strings with any token starting with the text, e.g. `lapt` matches
+    /// `laptop`. The text is used as a single prefix, it is not split into tokens.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub prefix: bool,
+    /// If true - parse the text as a boolean expression of terms, combined with `AND`, `OR` and
+    /// `NOT` operators and grouped with parentheses, e.g. `laptop AND (red OR blue)`.
+    /// Otherwise, operators are matched as regular words. Ignored in prefix mode.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub boolean: bool,
 }
 
 impl From<String> for MatchText {
     fn from(text: String) -> Self {
-        MatchText { text }
+        MatchText {
+            text,
+            prefix: false,
+            boolean: false,
+        }
     }
 }
 
@@ -1425,7 +1569,27 @@ impl Match {
     }
 
     pub fn new_text(text: &str) -> Self {
-        Self::Text(MatchText { text: text.into() })
+        Self::Text(MatchText {
+            text: text.into(),
+            prefix: false,
+            boolean: false,
+        })
+    }
+
+    pub fn new_text_prefix(prefix: &str) -> Self {
+        Self::Text(MatchText {
+            text: prefix.into(),
+            prefix: true,
+            boolean: false,
+        })
+    }
+
+    pub fn new_text_boolean(text: &str) -> Self {
+        Self::Text(MatchText {
+            text: text.into(),
+            prefix: false,
+            boolean: true,
+        })
     }
 
     pub fn new_any(any: AnyVariants) -> Self {
@@ -1447,7 +1611,7 @@ impl From<MatchInterface> for Match {
     fn from(value: MatchInterface) -> Self {
         match value {
             MatchInterface::Value(value) => Self::Value(MatchValue { value: value.value }),
-            MatchInterface::Text(text) => Self::Text(MatchText { text: text.text }),
+            MatchInterface::Text(text) => Self::Text(text),
             MatchInterface::Any(any) => Self::Any(MatchAny { any: any.any }),
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
@@ -1678,6 +1842,13 @@ pub struct GeoRadius {
     pub radius: f64,
 }
 
+impl GeoPoint {
+    /// Haversine distance to the other point, in meters
+    pub fn haversine_distance(&self, other: &GeoPoint) -> f64 {
+        Point::new(self.lon, self.lat).haversine_distance(&Point::new(other.lon, other.lat))
+    }
+}
+
 impl GeoRadius {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
         let query_center = Point::new(self.center.lon, self.center.lat);
@@ -1792,6 +1963,17 @@ impl TryFrom<GeoPolygonShadow> for GeoPolygon {
     }
 }
 
+/// How a field condition is matched against arrays of values
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ArrayMatch {
+    /// Match if any value of the array satisfies the condition
+    #[default]
+    Any,
+    /// Match if the array is not empty, and all of its values satisfy the condition
+    All,
+}
+
 /// All possible payload filtering conditions
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[validate(schema(function = "validate_field_condition"))]
@@ -1811,6 +1993,9 @@ pub struct FieldCondition {
     pub geo_polygon: Option<GeoPolygon>,
     /// Check number of values of the field
     pub values_count: Option<ValuesCount>,
+    /// How to match arrays of values, default is `any`. Does not apply to `values_count`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub array_match: Option<ArrayMatch>,
 }
 
 impl FieldCondition {
@@ -1823,6 +2008,7 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: None,
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1835,6 +2021,7 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: None,
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1847,6 +2034,7 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: None,
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1859,6 +2047,7 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: None,
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1871,6 +2060,7 @@ impl FieldCondition {
             geo_radius: Some(geo_radius),
             geo_polygon: None,
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1883,6 +2073,7 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: Some(geo_polygon),
             values_count: None,
+            array_match: None,
         }
     }
 
@@ -1895,6 +2086,15 @@ impl FieldCondition {
             geo_radius: None,
             geo_polygon: None,
             values_count: Some(values_count),
+            array_match: None,
+        }
+    }
+
+    /// Set how the condition is matched against arrays of values.
+    pub fn with_array_match(self, array_match: ArrayMatch) -> Self {
+        Self {
+            array_match: Some(array_match),
+            ..self
         }
     }
 
@@ -1909,6 +2109,7 @@ impl FieldCondition {
                 geo_polygon: None,
                 values_count: None,
                 key: _,
+                array_match: _,
             }
         )
     }
@@ -1,6 +1,13 @@
 use std::cmp::Ordering;
 
-use crate::types::ScoredPoint;
+use ordered_float::OrderedFloat;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::data_types::order_by::Direction;
+use crate::json_path::JsonPath;
+use crate::types::{Order, PayloadContainer as _, ScoredPoint};
 
 // Newtype to provide alternative comparator for ScoredPoint which breaks ties by id
 pub struct ScoredPointTies<'a>(pub &'a ScoredPoint);
@@ -33,3 +40,84 @@ impl<'a> PartialEq for ScoredPointTies<'a> {
         self.0 == other.0
     }
 }
+
+/// Order of points with equal scores in search results
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TieBreak {
+    /// Points with equal scores are ordered by their id
+    Id(Direction),
+    /// Points with equal scores are ordered by the value of a payload key
+    Payload(PayloadTieBreak),
+}
+
+/// Order points with equal scores by the value of a payload key.
+///
+/// The key must be included in the payload returned with the points. Points without a value come
+/// last, points with equal values are ordered by id.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadTieBreak {
+    /// Payload key to order by, only the first value of arrays is used
+    pub key: JsonPath,
+    /// Direction of ordering, ascending by default
+    #[serde(default)]
+    pub direction: Direction,
+}
+
+impl TieBreak {
+    /// Compare points with equal scores, `Less` if `a` comes first
+    pub fn cmp_ties(&self, a: &ScoredPoint, b: &ScoredPoint) -> Ordering {
+        match self {
+            TieBreak::Id(direction) => direction.apply(a.id.cmp(&b.id)),
+            TieBreak::Payload(PayloadTieBreak { key, direction }) => {
+                match (first_value(a, key), first_value(b, key)) {
+                    (Some(a_value), Some(b_value)) => {
+                        direction.apply(cmp_json_values(a_value, b_value))
+                    }
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+                .then_with(|| a.id.cmp(&b.id))
+            }
+        }
+    }
+
+    /// Compare points by score in the given order, and by the tie break if scores are equal.
+    /// `Less` if `a` comes first.
+    pub fn cmp_scored(&self, a: &ScoredPoint, b: &ScoredPoint, order: Order) -> Ordering {
+        let by_score = match order {
+            Order::LargeBetter => b.cmp(a),
+            Order::SmallBetter => a.cmp(b),
+        };
+        by_score.then_with(|| self.cmp_ties(a, b))
+    }
+}
+
+fn first_value<'a>(point: &'a ScoredPoint, key: &JsonPath) -> Option<&'a Value> {
+    point
+        .payload
+        .as_ref()
+        .and_then(|payload| payload.get_value(key).first().copied())
+}
+
+/// Total order of payload values: numbers, then strings, then booleans, then other values
+fn cmp_json_values(a: &Value, b: &Value) -> Ordering {
+    fn type_rank(value: &Value) -> u8 {
+        match value {
+            Value::Number(_) => 0,
+            Value::String(_) => 1,
+            Value::Bool(_) => 2,
+            Value::Null | Value::Array(_) | Value::Object(_) => 3,
+        }
+    }
+
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => OrderedFloat(a.as_f64().unwrap_or_default())
+            .cmp(&OrderedFloat(b.as_f64().unwrap_or_default())),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        _ => type_rank(a).cmp(&type_rank(b)),
+    }
+}
@@ -169,6 +169,10 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
             .map(|vector| T::slice_to_float_cow(vector.into()).into())
     }
 
+    fn prefetch(&self, keys: &[PointOffsetType]) -> usize {
+        self.mmap_store.as_ref().unwrap().prefetch(keys)
+    }
+
     fn insert_vector(&mut self, _key: PointOffsetType, _vector: VectorRef) -> OperationResult<()> {
         panic!("Can't directly update vector in mmap storage")
     }
@@ -795,4 +799,43 @@ mod tests {
             assert!((orig - quant).abs() < 0.15);
         }
     }
+
+    #[test]
+    fn test_prefetch_ranges() {
+        // Vectors of 32 KiB, so gaps of up to two vectors are read ahead along
+        const DIM: usize = 8 * 1024;
+
+        let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+        let mut storage = open_memmap_vector_storage(dir.path(), DIM, Distance::Dot).unwrap();
+        let mut vectors = (0..10).map(|i| (CowVector::from(vec![i as f32; DIM]), false));
+        storage
+            .update_from(&mut vectors, &AtomicBool::new(false))
+            .unwrap();
+
+        // Adjacent vectors are read ahead at once, in any key order
+        assert_eq!(storage.prefetch(&[0, 1, 2, 3]), 1);
+        assert_eq!(storage.prefetch(&[3, 0, 2]), 1);
+        // Small gaps are read ahead as well, larger gaps split the ranges
+        assert_eq!(storage.prefetch(&[0, 3]), 1);
+        assert_eq!(storage.prefetch(&[0, 4, 8]), 3);
+        // Keys out of the storage are ignored
+        assert_eq!(storage.prefetch(&[9, 100]), 1);
+        assert_eq!(storage.prefetch(&[]), 0);
+
+        // Storages in RAM never read ahead
+        let db_dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(db_dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let mut storage_in_ram = open_simple_dense_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            DIM,
+            Distance::Dot,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
+        storage_in_ram
+            .insert_vector(0, vec![1.0; DIM].as_slice().into())
+            .unwrap();
+        assert_eq!(storage_in_ram.prefetch(&[0]), 0);
+    }
 }
@@ -24,6 +24,9 @@ const HEADER_SIZE: usize = 4;
 const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
 const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
 
+/// Prefetched vectors closer than this many bytes are read ahead as one range
+const PREFETCH_MERGE_GAP: usize = 64 * 1024;
+
 /// Mem-mapped file for dense vectors
 #[derive(Debug)]
 pub struct MmapDenseVectors<T: PrimitiveVectorElement> {
@@ -154,6 +157,47 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
         &self.deleted
     }
 
+    /// Advise the kernel to read ahead the vectors at the given keys.
+    ///
+    /// Vectors close to each other are merged into contiguous ranges, so reading many vectors in
+    /// key order results in a few large reads instead of a page fault per vector.
+    ///
+    /// Returns the number of ranges read ahead.
+    pub fn prefetch(&self, keys: &[PointOffsetType]) -> usize {
+        let mut offsets: Vec<_> = keys
+            .iter()
+            .filter_map(|&key| self.data_offset(key))
+            .collect();
+        offsets.sort_unstable();
+
+        let raw_size = self.raw_size();
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for offset in offsets {
+            match ranges.last_mut() {
+                Some((_, end)) if offset <= *end + PREFETCH_MERGE_GAP => {
+                    *end = (*end).max(offset + raw_size);
+                }
+                _ => ranges.push((offset, offset + raw_size)),
+            }
+        }
+
+        let num_ranges = ranges.len();
+
+        #[cfg(unix)]
+        for (start, end) in ranges {
+            if let Err(err) = self
+                .mmap
+                .advise_range(memmap2::Advice::WillNeed, start, end - start)
+            {
+                log::debug!("Failed to advise MADV_WILLNEED for vectors: {err}");
+            }
+        }
+        #[cfg(not(unix))]
+        log::debug!("Ignore prefetch of {num_ranges} vector ranges on this platform",);
+
+        num_ranges
+    }
+
     pub fn prefault_mmap_pages(&self, path: &Path) -> mmap_ops::PrefaultMmapPages {
         mmap_ops::PrefaultMmapPages::new(self.mmap.clone(), Some(path))
     }
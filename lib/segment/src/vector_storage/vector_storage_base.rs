@@ -102,6 +102,16 @@ pub trait VectorStorage {
     /// The size of this slice is not guaranteed. It may be smaller/larger than the number of
     /// vectors in this segment.
     fn deleted_vector_bitslice(&self) -> &BitSlice;
+
+    /// Hint that the vectors at the given keys are about to be read, in ascending key order
+    ///
+    /// Storages reading from disk may use this to read ahead. This is a no-op for storages
+    /// keeping vectors in RAM.
+    ///
+    /// Returns the number of ranges read ahead.
+    fn prefetch(&self, _keys: &[PointOffsetType]) -> usize {
+        0
+    }
 }
 
 pub trait DenseVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
@@ -736,4 +746,31 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.deleted_vector_bitslice(),
         }
     }
+
+    fn prefetch(&self, keys: &[PointOffsetType]) -> usize {
+        match self {
+            VectorStorageEnum::DenseSimple(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseSimpleByte(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseSimpleHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseMemmap(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseMemmapByte(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseMemmapHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableMemmap(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableMemmapByte(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableInRam(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableInRamByte(v) => v.prefetch(keys),
+            VectorStorageEnum::DenseAppendableInRamHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::SparseSimple(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseSimple(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseSimpleByte(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseSimpleHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableInRam(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.prefetch(keys),
+            VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.prefetch(keys),
+        }
+    }
 }
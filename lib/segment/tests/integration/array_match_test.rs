@@ -0,0 +1,108 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use atomic_refcell::AtomicRefCell;
+use common::types::PointOffsetType;
+use segment::fixtures::payload_context_fixture::FixtureIdTracker;
+use segment::index::struct_payload_index::StructPayloadIndex;
+use segment::index::PayloadIndex;
+use segment::json_path::JsonPath;
+use segment::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
+use segment::payload_storage::PayloadStorage;
+use segment::types::{
+    ArrayMatch, Condition, FieldCondition, Filter, Payload, PayloadSchemaType, Range,
+};
+use serde_json::json;
+use tempfile::Builder;
+
+fn array_payloads() -> Vec<Payload> {
+    [
+        json!({"tags": ["a", "a"], "nums": [1, 2]}),
+        json!({"tags": ["a", "b"], "nums": [1, 5]}),
+        json!({"tags": ["b"], "nums": [5]}),
+        json!({"tags": [], "nums": []}),
+        json!({"tags": "a", "nums": 2}),
+        // Values of other types never satisfy the condition, even if not indexed
+        json!({"tags": ["a", 1], "nums": [2, "x"]}),
+        json!({}),
+    ]
+    .into_iter()
+    .map(Payload::from)
+    .collect()
+}
+
+fn payload_index(path: &Path, with_field_indexes: bool) -> StructPayloadIndex {
+    let payloads = array_payloads();
+
+    let mut payload_storage = InMemoryPayloadStorage::default();
+    for (idx, payload) in payloads.iter().enumerate() {
+        payload_storage
+            .assign(idx as PointOffsetType, payload)
+            .unwrap();
+    }
+
+    let payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
+    let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(payloads.len())));
+
+    let mut index = StructPayloadIndex::open(payload_storage, id_tracker, path, true).unwrap();
+    if with_field_indexes {
+        index
+            .set_indexed(&JsonPath::new("tags"), PayloadSchemaType::Keyword)
+            .unwrap();
+        index
+            .set_indexed(&JsonPath::new("nums"), PayloadSchemaType::Integer)
+            .unwrap();
+    }
+    index
+}
+
+fn query(index: &StructPayloadIndex, condition: FieldCondition) -> Vec<PointOffsetType> {
+    let filter = Filter::new_must(Condition::Field(condition.clone()));
+
+    let mut points = index.query_points(&filter);
+    points.sort_unstable();
+
+    // Filter context has to agree with the query
+    let filter_context = index.filter_context(&filter);
+    let checked: Vec<_> = (0..array_payloads().len() as PointOffsetType)
+        .filter(|&point_id| filter_context.check(point_id))
+        .collect();
+    assert_eq!(points, checked, "condition: {condition:?}");
+
+    points
+}
+
+#[test]
+fn test_array_match_any_and_all() {
+    let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+
+    for with_field_indexes in [false, true] {
+        let path = dir.path().join(format!("index_{with_field_indexes}"));
+        let index = payload_index(&path, with_field_indexes);
+
+        let match_a = FieldCondition::new_match(JsonPath::new("tags"), "a".to_string().into());
+        assert_eq!(query(&index, match_a.clone()), vec![0, 1, 4, 5]);
+        assert_eq!(
+            query(&index, match_a.clone().with_array_match(ArrayMatch::Any)),
+            vec![0, 1, 4, 5],
+        );
+        assert_eq!(
+            query(&index, match_a.with_array_match(ArrayMatch::All)),
+            vec![0, 4],
+        );
+
+        let range = FieldCondition::new_range(
+            JsonPath::new("nums"),
+            Range {
+                gte: Some(1.0),
+                lte: Some(3.0),
+                ..Default::default()
+            },
+        );
+        assert_eq!(query(&index, range.clone()), vec![0, 1, 4, 5]);
+        assert_eq!(
+            query(&index, range.with_array_match(ArrayMatch::All)),
+            vec![0, 4],
+        );
+    }
+}
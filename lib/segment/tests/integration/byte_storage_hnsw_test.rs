@@ -207,6 +207,7 @@ fn test_byte_storage_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        use_heuristic: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
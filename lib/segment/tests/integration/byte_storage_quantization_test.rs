@@ -334,6 +334,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        use_heuristic: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -404,7 +405,7 @@ fn test_byte_storage_binary_quantization_hnsw(
                         ignore: true,
                         ..Default::default()
                     }),
-                    exact: true,
+                    exact: Some(true),
                     ..Default::default()
                 }),
                 &Default::default(),
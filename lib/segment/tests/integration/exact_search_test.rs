@@ -86,6 +86,7 @@ fn exact_search_test() {
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        use_heuristic: None,
     };
 
     payload_index_ptr
@@ -156,7 +157,7 @@ fn exact_search_test() {
                 top,
                 Some(&SearchParams {
                     hnsw_ef: Some(ef),
-                    exact: true,
+                    exact: Some(true),
                     ..Default::default()
                 }),
                 &Default::default(),
@@ -195,7 +196,7 @@ fn exact_search_test() {
                 top,
                 Some(&SearchParams {
                     hnsw_ef: Some(ef),
-                    exact: true,
+                    exact: Some(true),
                     ..Default::default()
                 }),
                 &Default::default(),
@@ -147,6 +147,7 @@ fn _test_filterable_hnsw(
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        use_heuristic: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -264,3 +265,121 @@ fn _test_filterable_hnsw(
     ); // Not more than X% failures
     eprintln!("hits = {hits:#?} out of {attempts}");
 }
+
+#[test]
+fn test_filtered_full_scan_telemetry() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 2_000;
+    let distance = Distance::Cosine;
+    let full_scan_threshold = 16; // KB
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    // Same values under both keys, but only one of them is indexed
+    let indexed_key = "indexed";
+    let unindexed_key = "unindexed";
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+
+        let int_payload = random_int_payload(&mut rnd, 2..=2);
+        let payload: Payload = json!({indexed_key: int_payload, unindexed_key: int_payload}).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+
+    segment
+        .payload_index
+        .borrow_mut()
+        .set_indexed(&JsonPath::new(indexed_key), PayloadSchemaType::Integer)
+        .unwrap();
+
+    let hnsw_config = HnswConfig {
+        m: 8,
+        ef_construct: 16,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        use_heuristic: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config,
+        permit: Some(permit),
+        stopped: &stopped,
+    })
+    .unwrap();
+
+    // Matches only a few points, so a plain search is used in both cases
+    let small_range_filter = |key: &str| {
+        Filter::new_must(Condition::Field(FieldCondition::new_range(
+            JsonPath::new(key),
+            Range {
+                lt: None,
+                gt: None,
+                gte: Some(0.0),
+                lte: Some(4.0),
+            },
+        )))
+    };
+
+    let search = |filter: &Filter| {
+        let query: QueryVector = random_vector(&mut StdRng::seed_from_u64(43), dim).into();
+        hnsw_index
+            .search(&[&query], Some(filter), 3, None, &Default::default())
+            .unwrap()
+    };
+
+    // Points are selected with the payload index
+    search(&small_range_filter(indexed_key));
+    let telemetry = hnsw_index.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(telemetry.filtered_small_cardinality.count, 1);
+    assert_eq!(telemetry.filtered_full_scan.count, 0);
+
+    // No index to select points with, all of them are checked against the filter
+    search(&small_range_filter(unindexed_key));
+    let telemetry = hnsw_index.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(telemetry.filtered_small_cardinality.count, 2);
+    assert_eq!(telemetry.filtered_full_scan.count, 1);
+}
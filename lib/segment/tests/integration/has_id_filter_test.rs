@@ -0,0 +1,139 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::query_context::QueryContext;
+use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::field_index::PrimaryCondition;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::{PayloadIndex, VectorIndex};
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Condition, Distance, Filter, HasIdCondition, HnswConfig, Indexes, PointIdType, SegmentConfig,
+    SeqNumberType, VectorDataConfig, VectorStorageType, WithPayload, WithVector,
+};
+use tempfile::Builder;
+
+const NUM_VECTORS: u64 = 1_000;
+const DIM: usize = 16;
+const TOP: usize = 10;
+
+#[test]
+fn test_search_with_has_id_filter() {
+    let stopped = AtomicBool::new(false);
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(&mut rnd, DIM);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    // Allowed ids, with one which is not in the segment
+    let allowed_ids: HashSet<PointIdType> = [3, 17, 256, 512, 999, 5_000]
+        .into_iter()
+        .map(PointIdType::from)
+        .collect();
+    let filter = Filter::new_must(Condition::HasId(HasIdCondition::from(allowed_ids.clone())));
+
+    // Allowed ids are used to select the points directly
+    let cardinality = segment.payload_index.borrow().estimate_cardinality(&filter);
+    assert_eq!(cardinality.exp, allowed_ids.len() - 1);
+    assert!(matches!(
+        cardinality.primary_clauses.as_slice(),
+        [PrimaryCondition::Ids(ids)] if ids.len() == allowed_ids.len() - 1,
+    ));
+
+    let query = random_vector(&mut rnd, DIM);
+
+    // Plain segment
+    let result = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &query.clone().into(),
+            &WithPayload::default(),
+            &WithVector::Bool(false),
+            Some(&filter),
+            TOP,
+            None,
+        )
+        .unwrap();
+    let result_ids: HashSet<_> = result.iter().map(|point| point.id).collect();
+    assert_eq!(result_ids.len(), allowed_ids.len() - 1);
+    assert!(result_ids.is_subset(&allowed_ids));
+
+    // HNSW index
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 1, // KB
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        use_heuristic: None,
+    };
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config,
+        permit: Some(permit),
+        stopped: &stopped,
+    })
+    .unwrap();
+
+    let query: QueryVector = query.into();
+    let query_context = QueryContext::default();
+    let segment_query_context = query_context.get_segment_query_context();
+    let vector_query_context =
+        segment_query_context.get_vector_context(DEFAULT_VECTOR_NAME, Distance::Cosine);
+    let result = hnsw_index
+        .search(&[&query], Some(&filter), TOP, None, &vector_query_context)
+        .unwrap();
+
+    let id_tracker = segment.id_tracker.borrow();
+    let result_ids: HashSet<_> = result[0]
+        .iter()
+        .map(|point| id_tracker.external_id(point.idx).unwrap())
+        .collect();
+    assert_eq!(result_ids.len(), allowed_ids.len() - 1);
+    assert!(result_ids.is_subset(&allowed_ids));
+}
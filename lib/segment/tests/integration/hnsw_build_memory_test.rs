@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+use common::cpu::CpuPermit;
+use common::types::TelemetryDetail;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use tempfile::Builder;
+
+#[test]
+fn test_hnsw_build_peak_memory() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 16;
+    let num_vectors: u64 = 2_000;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: dim,
+                distance: Distance::Cosine,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        sparse_vector_data: Default::default(),
+        payload_storage_type: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(&mut rnd, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 10,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        use_heuristic: None,
+    };
+
+    let open_args = |permit| HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config: hnsw_config.clone(),
+        permit,
+        stopped: &stopped,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(open_args(Some(permit))).unwrap();
+
+    let peak_memory_bytes = hnsw_index
+        .get_telemetry_data(TelemetryDetail::default())
+        .build_peak_memory_bytes
+        .expect("peak memory of the build is recorded");
+
+    // Links reserved on level 0, and the visited list each build thread takes while linking
+    // points, are alive at the same time during the build
+    let level_0_links_bytes = num_vectors as usize * 2 * hnsw_config.m * size_of::<u32>();
+    let visited_list_bytes = num_vectors as usize * size_of::<usize>();
+    let min_peak_memory_bytes = level_0_links_bytes + visited_list_bytes;
+    assert!(
+        peak_memory_bytes >= min_peak_memory_bytes,
+        "peak memory {peak_memory_bytes} is below {min_peak_memory_bytes}",
+    );
+
+    // Recorded peak is kept when the index is loaded again
+    drop(hnsw_index);
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(open_args(None)).unwrap();
+    assert_eq!(
+        hnsw_index
+            .get_telemetry_data(TelemetryDetail::default())
+            .build_peak_memory_bytes,
+        Some(peak_memory_bytes),
+    );
+}
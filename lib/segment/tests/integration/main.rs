@@ -1,3 +1,4 @@
+mod array_match_test;
 mod batch_search_test;
 mod byte_storage_hnsw_test;
 mod byte_storage_quantization_test;
@@ -7,6 +8,8 @@ mod fail_recovery_test;
 mod filtering_context_check;
 mod filtrable_hnsw_test;
 mod fixtures;
+mod has_id_filter_test;
+mod hnsw_build_memory_test;
 mod hnsw_discover_test;
 mod hnsw_quantized_search_test;
 mod multivector_filtrable_hnsw_test;
@@ -15,6 +18,7 @@ mod multivector_quantization_test;
 mod nested_filtering_test;
 mod payload_index_test;
 mod scroll_filtering_test;
+mod search_deadline_test;
 mod segment_builder_test;
 mod segment_on_disk_snapshot;
 mod segment_tests;
@@ -261,6 +261,7 @@ impl TestSegments {
                         r#type: KeywordIndexType::Keyword,
                         is_tenant: None,
                         on_disk: Some(true),
+                        case_insensitive: None,
                     },
                 ))),
             )
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use common::cpu::CpuPermit;
+use rand::prelude::StdRng;
+use rand::SeedableRng;
+use segment::common::operation_error::OperationError;
+use segment::data_types::query_context::{DeadlinePolicy, QueryContext};
+use segment::data_types::vectors::{only_default_vector, QueryVector, DEFAULT_VECTOR_NAME};
+use segment::entry::entry_point::SegmentEntry;
+use segment::fixtures::payload_fixtures::random_vector;
+use segment::fixtures::sparse_fixtures::fixture_sparse_index;
+use segment::index::hnsw_index::graph_links::GraphLinksRam;
+use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
+use segment::index::hnsw_index::num_rayon_threads;
+use segment::index::VectorIndex;
+use segment::segment_constructor::build_segment;
+use segment::types::{
+    Distance, HnswConfig, Indexes, SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig,
+    VectorStorageType,
+};
+use sparse::common::sparse_vector_fixture::random_full_sparse_vector;
+use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
+use tempfile::Builder;
+
+const NUM_VECTORS: u64 = 2_000;
+const DIM: usize = 16;
+const TOP: usize = 10;
+
+/// Searches interrupted by the deadline must not take much longer than the deadline itself
+const PROMPT_RESPONSE: Duration = Duration::from_millis(100);
+
+/// Query context with a deadline, which is already reached when the search starts
+fn tight_deadline_context(policy: DeadlinePolicy) -> QueryContext {
+    QueryContext::default().with_deadline(Instant::now(), policy)
+}
+
+/// Query context with a deadline, which is never reached by the search
+fn cooperative_deadline_context(policy: DeadlinePolicy) -> QueryContext {
+    QueryContext::default().with_deadline(Instant::now() + Duration::from_secs(3600), policy)
+}
+
+#[test]
+fn test_hnsw_search_deadline() {
+    let stopped = AtomicBool::new(false);
+    let distance = Distance::Cosine;
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let config = SegmentConfig {
+        vector_data: HashMap::from([(
+            DEFAULT_VECTOR_NAME.to_owned(),
+            VectorDataConfig {
+                size: DIM,
+                distance,
+                storage_type: VectorStorageType::Memory,
+                index: Indexes::Plain {},
+                quantization_config: None,
+                multivector_config: None,
+                datatype: None,
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        sparse_vector_data: Default::default(),
+    };
+
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    for n in 0..NUM_VECTORS {
+        let vector = random_vector(&mut rnd, DIM);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+
+    let hnsw_config = HnswConfig {
+        m: 16,
+        ef_construct: 64,
+        full_scan_threshold: 1, // KB, force graph search
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        use_heuristic: None,
+    };
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .quantized_vectors
+            .clone(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config,
+        permit: Some(permit),
+        stopped: &stopped,
+    })
+    .unwrap();
+
+    let params = SearchParams {
+        hnsw_ef: Some(256),
+        ..Default::default()
+    };
+    let query: QueryVector = random_vector(&mut rnd, DIM).into();
+
+    let search = |query_context: &QueryContext| {
+        let segment_query_context = query_context.get_segment_query_context();
+        let vector_query_context =
+            segment_query_context.get_vector_context(DEFAULT_VECTOR_NAME, distance);
+        hnsw_index.search(&[&query], None, TOP, Some(&params), &vector_query_context)
+    };
+
+    let expected = search(&QueryContext::default()).unwrap();
+    assert_eq!(expected[0].len(), TOP);
+
+    // Deadline which is never reached doesn't affect the results
+    for policy in [DeadlinePolicy::Partial, DeadlinePolicy::Error] {
+        let result = search(&cooperative_deadline_context(policy)).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    // Best results found so far are returned promptly
+    let timer = Instant::now();
+    let result = search(&tight_deadline_context(DeadlinePolicy::Partial)).unwrap();
+    assert!(timer.elapsed() < PROMPT_RESPONSE);
+    assert!(result[0].len() <= TOP);
+
+    let timer = Instant::now();
+    let result = search(&tight_deadline_context(DeadlinePolicy::Error));
+    assert!(timer.elapsed() < PROMPT_RESPONSE);
+    assert!(
+        matches!(result, Err(OperationError::Timeout { .. })),
+        "{result:?}",
+    );
+}
+
+#[test]
+fn test_sparse_search_deadline() {
+    let mut rnd = StdRng::seed_from_u64(42);
+    let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
+
+    let max_dim = 1_000;
+    let sparse_vector_index = fixture_sparse_index::<InvertedIndexCompressedImmutableRam<f32>, _>(
+        &mut rnd,
+        NUM_VECTORS as usize,
+        max_dim,
+        1, // force usage of the inverted index
+        data_dir.path(),
+    );
+
+    let query: QueryVector = random_full_sparse_vector(&mut rnd, max_dim).into();
+
+    let search = |query_context: &QueryContext| {
+        let segment_query_context = query_context.get_segment_query_context();
+        let vector_query_context =
+            segment_query_context.get_vector_context(DEFAULT_VECTOR_NAME, Distance::Dot);
+        sparse_vector_index.search(&[&query], None, TOP, None, &vector_query_context)
+    };
+
+    let expected = search(&QueryContext::default()).unwrap();
+    assert_eq!(expected[0].len(), TOP);
+
+    // Deadline which is never reached doesn't affect the results
+    for policy in [DeadlinePolicy::Partial, DeadlinePolicy::Error] {
+        let result = search(&cooperative_deadline_context(policy)).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    // Best results found so far are returned promptly
+    let timer = Instant::now();
+    let result = search(&tight_deadline_context(DeadlinePolicy::Partial)).unwrap();
+    assert!(timer.elapsed() < PROMPT_RESPONSE);
+    assert!(result[0].len() <= TOP);
+
+    let timer = Instant::now();
+    let result = search(&tight_deadline_context(DeadlinePolicy::Error));
+    assert!(timer.elapsed() < PROMPT_RESPONSE);
+    assert!(
+        matches!(result, Err(OperationError::Timeout { .. })),
+        "{result:?}",
+    );
+}
@@ -13,6 +13,7 @@ use segment::entry::entry_point::SegmentEntry;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::json_path::JsonPath;
 use segment::segment::Segment;
+use segment::segment_constructor::load_segment;
 use segment::segment_constructor::segment_builder::SegmentBuilder;
 use segment::types::{
     Indexes, PayloadContainer, PayloadKeyType, SegmentConfig, VectorDataConfig, VectorStorageType,
@@ -385,3 +386,42 @@ fn test_building_cancellation() {
         "time_early: {time_fast}, time_later: {time_long}, was_cancelled_later: {was_cancelled_later}",
     );
 }
+
+#[test]
+fn test_building_new_segment_timestamps() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+    let stopped = AtomicBool::new(false);
+
+    let segment1 = build_segment_1(dir.path());
+    let segment2 = build_segment_2(dir.path());
+
+    let info = segment1.info();
+    let created_at = info.created_at.unwrap();
+    assert_eq!(info.last_optimized_at, None);
+    assert!(segment2.info().created_at.unwrap() >= created_at);
+
+    let mut builder =
+        SegmentBuilder::new(dir.path(), temp_dir.path(), &segment1.segment_config).unwrap();
+    builder.update(&[&segment1, &segment2], &stopped).unwrap();
+
+    let permit_cpu_count = num_rayon_threads(0);
+    let permit = CpuPermit::dummy(permit_cpu_count as u32);
+    let merged_segment: Segment = builder.build(permit, &stopped).unwrap();
+
+    // Optimized segment keeps the creation time of its oldest source
+    let info = merged_segment.info();
+    assert_eq!(info.created_at, Some(created_at));
+    assert!(info.last_optimized_at.unwrap() >= created_at);
+
+    // Timestamps are persisted
+    let segment_path = merged_segment.current_path.clone();
+    drop(merged_segment);
+    let loaded_segment = load_segment(&segment_path, &stopped).unwrap().unwrap();
+    assert_eq!(loaded_segment.info().created_at, info.created_at);
+    assert_eq!(
+        loaded_segment.info().last_optimized_at,
+        info.last_optimized_at
+    );
+}
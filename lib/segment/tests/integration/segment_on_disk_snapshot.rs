@@ -72,6 +72,7 @@ fn test_on_disk_segment_snapshot() {
                     r#type: segment::data_types::index::KeywordIndexType::Keyword,
                     is_tenant: None,
                     on_disk: Some(true),
+                    case_insensitive: None,
                 }),
             )),
         )
@@ -106,6 +107,7 @@ fn test_on_disk_segment_snapshot() {
                     max_indexing_threads: 2,
                     on_disk: Some(true), // mmap index
                     payload_m: None,
+                    use_heuristic: None,
                 }),
                 quantization_config: None,
                 multivector_config: None,
@@ -258,9 +258,10 @@ fn test_update_named_vector() {
     // do exact search
     let search_params = SearchParams {
         hnsw_ef: None,
-        exact: true,
+        exact: Some(true),
         quantization: None,
-        indexed_only: false,
+        indexed_only: Some(false),
+        score_precision: None,
     };
     let nearest_upsert = segment
         .search(
@@ -1,6 +1,7 @@
 use std::cmp::{max, min, Ordering};
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
+use std::time::Instant;
 
 use common::top_k::TopK;
 use common::types::{PointOffsetType, ScoredPointOffset};
@@ -22,11 +23,16 @@ pub struct IndexedPostingListIterator<T: PostingListIter> {
 /// Making this larger makes the search faster but uses more (pooled) memory
 const ADVANCE_BATCH_SIZE: usize = 10_000;
 
+/// How many ids are scored by plain search between checks of the deadline
+const PLAIN_DEADLINE_CHECK_INTERVAL: usize = 1_000;
+
 pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
     postings_iterators: Vec<IndexedPostingListIterator<T>>,
     query: RemappedSparseVector,
     top: usize,
     is_stopped: &'a AtomicBool,
+    deadline: Option<Instant>,
+    deadline_exceeded: bool,
     top_results: TopK,
     min_record_id: Option<PointOffsetType>, // min_record_id ids across all posting lists
     max_record_id: PointOffsetType,         // max_record_id ids across all posting lists
@@ -81,6 +87,8 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             query,
             top,
             is_stopped,
+            deadline: None,
+            deadline_exceeded: false,
             top_results,
             min_record_id,
             max_record_id,
@@ -89,18 +97,41 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         }
     }
 
+    /// Stop the search once the `deadline` is reached, keeping the best results found so far
+    pub fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    /// Whether the last search was interrupted by the deadline
+    pub fn is_deadline_exceeded(&self) -> bool {
+        self.deadline_exceeded
+    }
+
+    fn check_deadline(&mut self) -> bool {
+        if let Some(deadline) = self.deadline {
+            self.deadline_exceeded = Instant::now() >= deadline;
+        }
+        self.deadline_exceeded
+    }
+
     /// Plain search against the given ids without any pruning
     pub fn plain_search(&mut self, ids: &[PointOffsetType]) -> Vec<ScoredPointOffset> {
         // sort ids to fully leverage posting list iterator traversal
         let mut sorted_ids = ids.to_vec();
         sorted_ids.sort_unstable();
 
-        for id in sorted_ids {
+        for (i, id) in sorted_ids.into_iter().enumerate() {
             // check for cancellation
             if self.is_stopped.load(Relaxed) {
                 break;
             }
 
+            // check for deadline (clock reads amortized by interval)
+            if i % PLAIN_DEADLINE_CHECK_INTERVAL == 0 && self.check_deadline() {
+                break;
+            }
+
             let mut indices = Vec::with_capacity(self.query.indices.len());
             let mut values = Vec::with_capacity(self.query.values.len());
             // collect indices and values for the current record id from the query's posting lists *only*
@@ -250,6 +281,10 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 break;
             }
 
+            if self.check_deadline() {
+                break;
+            }
+
             // prepare next iterator of batched ids
             let Some(start_batch_id) = self.min_record_id else {
                 break;
@@ -29,6 +29,8 @@ impl AliasMapping {
 /// Persists mapping between alias and collection name. The data is assumed to be relatively small.
 /// - Reads are served from memory.
 /// - Writes are durably saved.
+/// - Every write atomically replaces the whole file, so removed aliases leave nothing behind and
+///   the store never needs to be compacted.
 #[derive(Debug)]
 pub struct AliasPersistence {
     data_path: PathBuf,
@@ -138,3 +140,35 @@ impl AliasPersistence {
         self.alias_mapping.0.contains_key(alias)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_removed_aliases_do_not_grow_store() {
+        let dir = Builder::new().prefix("aliases").tempdir().unwrap();
+        let mut persistence = AliasPersistence::open(dir.path().to_path_buf()).unwrap();
+        let data_path = AliasPersistence::get_config_path(dir.path());
+        let empty_size = fs::metadata(&data_path).unwrap().len();
+
+        for i in 0..1000 {
+            persistence
+                .insert(format!("alias_{i}"), "collection".to_string())
+                .unwrap();
+            persistence
+                .rename_alias(&format!("alias_{i}"), format!("renamed_{i}"))
+                .unwrap();
+        }
+        assert!(fs::metadata(&data_path).unwrap().len() > empty_size);
+
+        persistence.remove_collection("collection").unwrap();
+        assert_eq!(fs::metadata(&data_path).unwrap().len(), empty_size);
+
+        // State survives reopening
+        let persistence = AliasPersistence::open(dir.path().to_path_buf()).unwrap();
+        assert_eq!(persistence.state(), &AliasMapping::default());
+    }
+}
@@ -5,6 +5,7 @@ use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     StrictModeConfig, WalConfigDiff,
 };
+use collection::operations::payload_schema::PayloadSchemaConfig;
 use collection::operations::types::{
     SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
 };
@@ -14,7 +15,10 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{replica_set, CollectionId};
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{
+    PayloadFieldSchema, PayloadKeyType, QuantizationConfig, SearchParams, ShardKey,
+};
+use segment::utils::scored_point_ties::TieBreak;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -133,6 +137,14 @@ pub struct CreateCollection {
     #[serde(default)]
     #[validate(range(min = 1))]
     pub replication_factor: Option<u32>,
+    /// Exact placement of the shards on peers, replicas of each shard ID are created on the listed
+    /// peers. Shard IDs must be consecutive, starting from 0, and all peers must be known.
+    /// Every shard must have `replication_factor` replicas, the replication factor is derived from
+    /// the placement if not specified.
+    /// Only for auto sharding.
+    /// If none - shards are distributed evenly across all peers.
+    #[serde(default)]
+    pub shard_placement: Option<BTreeMap<ShardId, Vec<PeerId>>>,
     /// Defines how many replicas should apply the operation for us to consider it successful.
     /// Increasing this number will make the collection more resilient to inconsistencies, but will
     /// also make it fail if not enough replicas are available.
@@ -170,6 +182,28 @@ pub struct CreateCollection {
     #[validate(nested)]
     #[schemars(skip)]
     pub strict_mode_config: Option<StrictModeConfig>,
+    /// Payload schema of the collection and how it is enforced on updates.
+    /// If none - payloads are not checked.
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchemaConfig>,
+    /// Search params used for parameters not specified in a search request.
+    /// If none - defaults of the service are used.
+    #[serde(default)]
+    #[validate(nested)]
+    pub default_search_params: Option<SearchParams>,
+    /// Order of points with equal scores in query results.
+    /// If none - points with equal scores are ordered by id.
+    #[serde(default)]
+    pub tie_break: Option<TieBreak>,
+    /// Timeout in seconds of read requests, which do not specify one.
+    /// If none - default timeout of the service is used.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub default_timeout: Option<usize>,
+    /// Create shard keys on the first write to them, instead of rejecting the write.
+    /// Only for custom sharding. Default: false
+    #[serde(default)]
+    pub auto_create_shard_keys: Option<bool>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -227,6 +261,23 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate(nested)]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Payload schema of the collection and how it is enforced on updates. Replaces the current
+    /// one, stored payloads are not checked against it. If none - it is left unchanged.
+    #[serde(default)]
+    pub payload_schema: Option<PayloadSchemaConfig>,
+    /// Search params used for parameters not specified in a search request. Replace the current
+    /// ones. If none - they are left unchanged.
+    #[serde(default)]
+    #[validate(nested)]
+    pub default_search_params: Option<SearchParams>,
+    /// Order of points with equal scores in query results. If none - it is left unchanged.
+    #[serde(default)]
+    pub tie_break: Option<TieBreak>,
+    /// Timeout in seconds of read requests, which do not specify one. If none - it is left
+    /// unchanged.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub default_timeout: Option<usize>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -249,6 +300,10 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                payload_schema: None,
+                default_search_params: None,
+                tie_break: None,
+                default_timeout: None,
             },
             shard_replica_changes: None,
         }
@@ -292,6 +347,8 @@ pub struct DeleteCollectionOperation(pub String);
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum ReshardingOperation {
     Start(ReshardKey),
+    /// Resume migrating points of a resharding, keeping the points that were already migrated
+    Resume(ReshardKey),
     CommitRead(ReshardKey),
     CommitWrite(ReshardKey),
     Finish(ReshardKey),
@@ -394,6 +451,7 @@ impl From<CollectionConfig> for CreateCollection {
             shard_number: Some(value.params.shard_number.get()),
             sharding_method: value.params.sharding_method,
             replication_factor: Some(value.params.replication_factor.get()),
+            shard_placement: None,
             write_consistency_factor: Some(value.params.write_consistency_factor.get()),
             on_disk_payload: Some(value.params.on_disk_payload),
             hnsw_config: Some(value.hnsw_config.into()),
@@ -403,6 +461,11 @@ impl From<CollectionConfig> for CreateCollection {
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
             strict_mode_config: value.strict_mode_config,
+            payload_schema: value.payload_schema,
+            default_search_params: value.default_search_params,
+            tie_break: value.tie_break,
+            default_timeout: value.default_timeout,
+            auto_create_shard_keys: Some(value.params.auto_create_shard_keys),
         }
     }
 }
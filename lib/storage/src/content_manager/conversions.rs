@@ -1,5 +1,8 @@
+use std::collections::BTreeMap;
+
 use collection::operations::conversions::sharding_method_from_proto;
 use collection::operations::types::SparseVectorsConfig;
+use collection::shards::shard::{PeerId, ShardId};
 use tonic::Status;
 
 use crate::content_manager::collection_meta_ops::{
@@ -23,6 +26,7 @@ impl From<StorageError> for tonic::Status {
             StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
             StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
             StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+            StorageError::Conflict { .. } => tonic::Code::Aborted,
         };
         tonic::Status::new(error_code, format!("{error}"))
     }
@@ -45,11 +49,12 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .map(|v| SparseVectorsConfig::try_from(v).map(|SparseVectorsConfig(x)| x))
                     .transpose()?,
                 hnsw_config: value.hnsw_config.map(|v| v.into()),
-                wal_config: value.wal_config.map(|v| v.into()),
+                wal_config: value.wal_config.map(TryInto::try_into).transpose()?,
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
                 replication_factor: value.replication_factor,
+                shard_placement: shard_placement_from_proto(value.shard_placement)?,
                 write_consistency_factor: value.write_consistency_factor,
                 init_from: value
                     .init_from_collection
@@ -63,11 +68,36 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .map(sharding_method_from_proto)
                     .transpose()?,
                 strict_mode_config: value.strict_mode_config.map(From::from),
+                payload_schema: value.payload_schema.map(TryInto::try_into).transpose()?,
+                default_search_params: value.default_search_params.map(From::from),
+                tie_break: value.tie_break.map(TryInto::try_into).transpose()?,
+                default_timeout: value.default_timeout.map(|timeout| timeout as usize),
+                auto_create_shard_keys: value.auto_create_shard_keys,
             },
         )))
     }
 }
 
+/// Explicit shard placement of a new collection, `None` if no shard is placed
+fn shard_placement_from_proto(
+    placement: Vec<api::grpc::qdrant::ShardPlacement>,
+) -> Result<Option<BTreeMap<ShardId, Vec<PeerId>>>, Status> {
+    if placement.is_empty() {
+        return Ok(None);
+    }
+
+    let mut shard_placement = BTreeMap::new();
+    for api::grpc::qdrant::ShardPlacement { shard_id, peer_ids } in placement {
+        if shard_placement.insert(shard_id, peer_ids).is_some() {
+            return Err(Status::invalid_argument(format!(
+                "Shard {shard_id} is placed more than once",
+            )));
+        }
+    }
+
+    Ok(Some(shard_placement))
+}
+
 impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
     type Error = Status;
 
@@ -91,6 +121,10 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                     .sparse_vectors_config
                     .map(TryInto::try_into)
                     .transpose()?,
+                payload_schema: value.payload_schema.map(TryInto::try_into).transpose()?,
+                default_search_params: value.default_search_params.map(From::from),
+                tie_break: value.tie_break.map(TryInto::try_into).transpose()?,
+                default_timeout: value.default_timeout.map(|timeout| timeout as usize),
             },
         )))
     }
@@ -34,6 +34,8 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Conflict: {description}")]
+    Conflict { description: String },
 }
 
 impl StorageError {
@@ -134,6 +136,9 @@ impl StorageError {
                 backtrace: None,
             },
             CollectionError::StrictMode { description } => StorageError::Forbidden { description },
+            CollectionError::Conflict { .. } => StorageError::Conflict {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -183,6 +188,7 @@ impl From<CollectionError> for StorageError {
                 backtrace: None,
             },
             CollectionError::StrictMode { description } => StorageError::Forbidden { description },
+            CollectionError::Conflict { description } => StorageError::Conflict { description },
         }
     }
 }
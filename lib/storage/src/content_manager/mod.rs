@@ -14,6 +14,7 @@ mod data_transfer;
 pub mod errors;
 pub mod shard_distribution;
 pub mod snapshots;
+pub mod streaming_delete;
 pub mod toc;
 
 pub mod consensus_ops {
@@ -134,6 +135,10 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    payload_schema: None,
+                    default_search_params: None,
+                    tie_break: None,
+                    default_timeout: None,
                 },
             );
             operation
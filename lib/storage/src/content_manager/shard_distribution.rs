@@ -1,5 +1,5 @@
 use std::cmp::{self, Reverse};
-use std::collections::BinaryHeap;
+use std::collections::{BTreeMap, BinaryHeap, HashSet};
 use std::iter::repeat_with;
 use std::num::NonZeroU32;
 
@@ -8,6 +8,8 @@ use collection::shards::shard::{PeerId, ShardId};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::content_manager::errors::StorageError;
+
 #[derive(PartialEq, Eq)]
 struct PeerShardCount {
     shard_count: usize,
@@ -100,6 +102,62 @@ impl ShardDistributionProposal {
 
         Self { distribution }
     }
+
+    /// Builds a proposal from an explicit placement of shards on peers.
+    /// Shard IDs must be consecutive, starting from 0. Every shard must be placed on at least one
+    /// known peer, and at most once on each peer.
+    pub fn from_placement(
+        placement: &BTreeMap<ShardId, Vec<PeerId>>,
+        known_peers: &[PeerId],
+    ) -> Result<Self, StorageError> {
+        if placement.is_empty() {
+            return Err(StorageError::bad_input("Shard placement cannot be empty"));
+        }
+
+        let mut distribution = Vec::with_capacity(placement.len());
+
+        for (expected_shard_id, (&shard_id, peers)) in (0..).zip(placement) {
+            if shard_id != expected_shard_id {
+                return Err(StorageError::bad_input(format!(
+                    "Shard placement must list consecutive shard IDs starting from 0, \
+                     but shard {expected_shard_id} is missing",
+                )));
+            }
+
+            if peers.is_empty() {
+                return Err(StorageError::bad_input(format!(
+                    "Shard {shard_id} must be placed on at least one peer",
+                )));
+            }
+
+            let mut unique_peers = HashSet::with_capacity(peers.len());
+            for peer_id in peers {
+                if !known_peers.contains(peer_id) {
+                    return Err(StorageError::bad_input(format!(
+                        "Shard {shard_id} cannot be placed on peer {peer_id}, peer does not exist",
+                    )));
+                }
+                if !unique_peers.insert(*peer_id) {
+                    return Err(StorageError::bad_input(format!(
+                        "Shard {shard_id} cannot be placed on peer {peer_id} more than once",
+                    )));
+                }
+            }
+
+            distribution.push((shard_id, peers.clone()));
+        }
+
+        Ok(Self { distribution })
+    }
+}
+
+/// Replication factor of an explicit placement of shards on peers
+///
+/// This is the number of replicas of the first shard, it matches all shards of a valid placement.
+pub fn placement_replication_factor(placement: &BTreeMap<ShardId, Vec<PeerId>>) -> Option<u32> {
+    placement
+        .first_key_value()
+        .map(|(_, peers)| peers.len() as u32)
 }
 
 impl From<ShardDistributionProposal> for CollectionShardDistribution {
@@ -116,7 +174,8 @@ impl From<ShardDistributionProposal> for CollectionShardDistribution {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
+    use collection::config::{CollectionParams, ShardKeyReplicationFactor};
+    use segment::types::ShardKey;
 
     use super::*;
 
@@ -187,4 +246,85 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_distribution_from_placement() {
+        let known_peers = vec![1, 2, 3];
+        let placement = BTreeMap::from([(0, vec![3]), (1, vec![1, 2]), (2, vec![2])]);
+
+        let proposal = ShardDistributionProposal::from_placement(&placement, &known_peers).unwrap();
+        assert_eq!(
+            proposal.distribution,
+            vec![(0, vec![3]), (1, vec![1, 2]), (2, vec![2])],
+        );
+
+        // Shards land exactly on the specified peers
+        let distribution = CollectionShardDistribution::from(proposal);
+        assert_eq!(distribution.shard_count(), 3);
+        for (shard_id, peers) in &placement {
+            assert_eq!(
+                distribution.shards[shard_id],
+                peers.iter().copied().collect::<HashSet<_>>(),
+            );
+        }
+    }
+
+    #[test]
+    fn test_distribution_from_invalid_placement() {
+        let known_peers = vec![1, 2, 3];
+
+        let invalid_placements = [
+            // Empty placement
+            BTreeMap::new(),
+            // Shard 1 is missing
+            BTreeMap::from([(0, vec![1]), (2, vec![2])]),
+            // Shard without replicas
+            BTreeMap::from([(0, vec![1]), (1, vec![])]),
+            // Unknown peer
+            BTreeMap::from([(0, vec![1]), (1, vec![4])]),
+            // Multiple replicas on the same peer
+            BTreeMap::from([(0, vec![1, 1])]),
+        ];
+
+        for placement in invalid_placements {
+            let result = ShardDistributionProposal::from_placement(&placement, &known_peers);
+            assert!(result.is_err(), "placement {placement:?} must be rejected");
+        }
+    }
+
+    #[test]
+    fn test_distribution_with_shard_key_replication_factor() {
+        let known_peers = vec![1, 2, 3, 4];
+        let critical = ShardKey::from("critical");
+        let regular = ShardKey::from("regular");
+
+        let params = CollectionParams {
+            shard_number: NonZeroU32::new(2).unwrap(),
+            replication_factor: NonZeroU32::new(1).unwrap(),
+            shard_key_replication_factors: vec![ShardKeyReplicationFactor {
+                shard_key: critical.clone(),
+                replication_factor: NonZeroU32::new(3).unwrap(),
+            }],
+            ..CollectionParams::empty()
+        };
+
+        // Only the shards of the critical shard key get extra replicas
+        for (shard_key, replicas) in [(critical, 3), (regular, 1)] {
+            let proposal = ShardDistributionProposal::new(
+                params.shard_number,
+                params.shard_key_replication_factor(Some(&shard_key)),
+                &known_peers,
+            );
+            assert_eq!(proposal.distribution.len(), 2);
+            for (_shard_id, peers) in &proposal.distribution {
+                assert_eq!(peers.iter().collect::<HashSet<_>>().len(), replicas);
+            }
+        }
+
+        // Shards without a shard key use the collection-wide replication factor
+        assert_eq!(
+            params.shard_key_replication_factor(None),
+            params.replication_factor,
+        );
+    }
 }
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use collection::shards::shard::ShardId;
+use parking_lot::Mutex;
+
+type ShardSnapshotKey = (String, ShardId);
+
+/// Tracks shard snapshots being created on this node, so that they can be cancelled.
+#[derive(Debug, Default)]
+pub struct ShardSnapshotsInProgress {
+    snapshots: Arc<Mutex<HashMap<ShardSnapshotKey, Vec<Arc<AtomicBool>>>>>,
+}
+
+/// Registration of a single shard snapshot creation, unregisters it on drop.
+#[must_use]
+#[derive(Debug)]
+pub struct ShardSnapshotHandle {
+    key: ShardSnapshotKey,
+    stopped: Arc<AtomicBool>,
+    snapshots: Arc<Mutex<HashMap<ShardSnapshotKey, Vec<Arc<AtomicBool>>>>>,
+}
+
+impl ShardSnapshotHandle {
+    /// Flag which is set once the snapshot creation is cancelled.
+    pub fn stopped(&self) -> Arc<AtomicBool> {
+        self.stopped.clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for ShardSnapshotHandle {
+    fn drop(&mut self) {
+        let mut snapshots = self.snapshots.lock();
+        if let Some(flags) = snapshots.get_mut(&self.key) {
+            flags.retain(|flag| !Arc::ptr_eq(flag, &self.stopped));
+            if flags.is_empty() {
+                snapshots.remove(&self.key);
+            }
+        }
+    }
+}
+
+impl ShardSnapshotsInProgress {
+    pub fn register(&self, collection_name: &str, shard_id: ShardId) -> ShardSnapshotHandle {
+        let key = (collection_name.to_string(), shard_id);
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        self.snapshots
+            .lock()
+            .entry(key.clone())
+            .or_default()
+            .push(stopped.clone());
+
+        ShardSnapshotHandle {
+            key,
+            stopped,
+            snapshots: self.snapshots.clone(),
+        }
+    }
+
+    /// Cancel all in-progress snapshot creations of the given shard.
+    ///
+    /// Returns number of cancelled snapshot creations.
+    pub fn cancel(&self, collection_name: &str, shard_id: ShardId) -> usize {
+        let snapshots = self.snapshots.lock();
+        let Some(flags) = snapshots.get(&(collection_name.to_string(), shard_id)) else {
+            return 0;
+        };
+
+        for flag in flags {
+            flag.store(true, Ordering::Relaxed);
+        }
+        flags.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel_shard_snapshots_in_progress() {
+        let in_progress = ShardSnapshotsInProgress::default();
+
+        let first = in_progress.register("test", 0);
+        let second = in_progress.register("test", 0);
+        let other_shard = in_progress.register("test", 1);
+
+        assert_eq!(in_progress.cancel("test", 0), 2);
+        assert!(first.is_cancelled());
+        assert!(second.is_cancelled());
+        assert!(!other_shard.is_cancelled());
+
+        drop(first);
+        drop(second);
+        assert_eq!(in_progress.cancel("test", 0), 0);
+        assert_eq!(in_progress.cancel("other", 1), 0);
+    }
+}
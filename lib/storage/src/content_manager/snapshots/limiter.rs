@@ -0,0 +1,187 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use schemars::JsonSchema;
+use segment::common::anonymize::Anonymize;
+use serde::Serialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Limits the number of shard snapshots being created concurrently on this node.
+///
+/// Requests exceeding the limit are queued until a running snapshot finishes.
+#[derive(Debug)]
+pub struct ShardSnapshotLimiter {
+    /// If not defined - no limit is applied.
+    semaphore: Option<Arc<Semaphore>>,
+    limit: Option<usize>,
+    counters: Arc<ShardSnapshotCounters>,
+}
+
+#[derive(Debug, Default)]
+struct ShardSnapshotCounters {
+    queued: AtomicUsize,
+    running: AtomicUsize,
+}
+
+/// Current state of shard snapshot creation on this node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ShardSnapshotLimiterStatus {
+    /// Maximum number of concurrently created shard snapshots, `None` if unlimited.
+    pub limit: Option<usize>,
+    /// Number of shard snapshot requests waiting for a free slot.
+    pub queued: usize,
+    /// Number of shard snapshots being created right now.
+    pub running: usize,
+}
+
+impl Anonymize for ShardSnapshotLimiterStatus {
+    fn anonymize(&self) -> Self {
+        *self
+    }
+}
+
+/// Grants the right to create a shard snapshot, releases the slot on drop.
+#[must_use]
+#[derive(Debug)]
+pub struct ShardSnapshotPermit {
+    _permit: Option<OwnedSemaphorePermit>,
+    counters: Arc<ShardSnapshotCounters>,
+}
+
+impl Drop for ShardSnapshotPermit {
+    fn drop(&mut self) {
+        self.counters.running.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Decrements the queued counter on drop, so a cancelled wait is accounted for.
+struct QueuedGuard<'a>(&'a ShardSnapshotCounters);
+
+impl Drop for QueuedGuard<'_> {
+    fn drop(&mut self) {
+        self.0.queued.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl ShardSnapshotLimiter {
+    pub fn new(limit: Option<usize>) -> Self {
+        Self {
+            semaphore: limit.map(|limit| Arc::new(Semaphore::new(limit.max(1)))),
+            limit: limit.map(|limit| limit.max(1)),
+            counters: Default::default(),
+        }
+    }
+
+    /// Wait for a free slot to create a shard snapshot.
+    ///
+    /// # Cancel safety
+    ///
+    /// This function is cancel safe.
+    pub async fn acquire(&self) -> ShardSnapshotPermit {
+        let permit = match &self.semaphore {
+            Some(semaphore) => {
+                let permit = match semaphore.clone().try_acquire_owned() {
+                    Ok(permit) => permit,
+                    Err(_) => {
+                        self.counters.queued.fetch_add(1, Ordering::SeqCst);
+                        let _queued = QueuedGuard(&self.counters);
+                        log::debug!(
+                            "Shard snapshot creation is queued, {} snapshots are already running",
+                            self.counters.running.load(Ordering::SeqCst),
+                        );
+                        semaphore
+                            .clone()
+                            .acquire_owned()
+                            .await
+                            .expect("shard snapshot semaphore is never closed")
+                    }
+                };
+                Some(permit)
+            }
+            None => None,
+        };
+
+        self.counters.running.fetch_add(1, Ordering::SeqCst);
+
+        ShardSnapshotPermit {
+            _permit: permit,
+            counters: self.counters.clone(),
+        }
+    }
+
+    pub fn status(&self) -> ShardSnapshotLimiterStatus {
+        ShardSnapshotLimiterStatus {
+            limit: self.limit,
+            queued: self.counters.queued.load(Ordering::SeqCst),
+            running: self.counters.running.load(Ordering::SeqCst),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shard_snapshot_limiter_bounds_concurrency() {
+        const LIMIT: usize = 2;
+        const REQUESTS: usize = 8;
+
+        let limiter = Arc::new(ShardSnapshotLimiter::new(Some(LIMIT)));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..REQUESTS)
+            .map(|_| {
+                let limiter = limiter.clone();
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+                tokio::spawn(async move {
+                    let _permit = limiter.acquire().await;
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(current, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(20)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+
+        // Let all tasks reach the limiter
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let status = limiter.status();
+        assert_eq!(status.limit, Some(LIMIT));
+        assert!(status.running <= LIMIT);
+        assert_eq!(status.running + status.queued, REQUESTS);
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert!(max_in_flight.load(Ordering::SeqCst) <= LIMIT);
+        assert_eq!(max_in_flight.load(Ordering::SeqCst), LIMIT);
+        assert_eq!(
+            limiter.status(),
+            ShardSnapshotLimiterStatus {
+                limit: Some(LIMIT),
+                queued: 0,
+                running: 0,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shard_snapshot_limiter_cancelled_wait() {
+        let limiter = ShardSnapshotLimiter::new(Some(1));
+        let permit = limiter.acquire().await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(waiting.is_err());
+        assert_eq!(limiter.status().queued, 0);
+        assert_eq!(limiter.status().running, 1);
+
+        drop(permit);
+        assert_eq!(limiter.status().running, 0);
+    }
+}
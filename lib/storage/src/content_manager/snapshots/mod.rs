@@ -1,4 +1,6 @@
 pub mod download;
+pub mod in_progress;
+pub mod limiter;
 pub mod recover;
 
 use std::collections::HashMap;
@@ -101,7 +103,7 @@ async fn _do_create_full_snapshot(
     let all_collections = toc.all_collections(&access).await;
     let mut created_snapshots: Vec<(&str, SnapshotDescription)> = vec![];
     for collection_pass in &all_collections {
-        let snapshot_details = toc.create_snapshot(collection_pass).await?;
+        let snapshot_details = toc.create_snapshot(collection_pass, &[]).await?;
         created_snapshots.push((collection_pass.name(), snapshot_details));
     }
     let current_time = chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S").to_string();
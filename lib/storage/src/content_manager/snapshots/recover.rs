@@ -1,7 +1,10 @@
+use std::collections::BTreeMap;
+
 use collection::collection::Collection;
 use collection::common::sha_256::{hash_file, hashes_equal};
-use collection::config::CollectionConfig;
+use collection::config::{CollectionConfig, CollectionParams};
 use collection::operations::snapshot_ops::{SnapshotPriority, SnapshotRecover};
+use collection::operations::types::{check_sparse_compatible, CollectionError};
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::shard_config::ShardType;
@@ -47,6 +50,61 @@ pub async fn activate_shard(
     Ok(())
 }
 
+/// Check that a collection snapshot can be recovered into an existing collection.
+///
+/// Vector names, dimensions and distances are compared first to report the exact mismatch,
+/// the rest of the vectors config must match exactly.
+pub fn check_snapshot_compatibility(
+    snapshot_params: &CollectionParams,
+    collection_params: &CollectionParams,
+) -> Result<(), StorageError> {
+    let incompatible = |err: CollectionError| {
+        StorageError::bad_input(format!(
+            "Snapshot is not compatible with existing collection: {err}"
+        ))
+    };
+
+    // Check dense vectors in both directions, so that vector names match exactly
+    collection_params
+        .vectors
+        .check_compatible(&snapshot_params.vectors)
+        .map_err(incompatible)?;
+    snapshot_params
+        .vectors
+        .check_compatible(&collection_params.vectors)
+        .map_err(incompatible)?;
+
+    // Check sparse vectors
+    let empty_sparse = BTreeMap::new();
+    let collection_sparse = collection_params
+        .sparse_vectors
+        .as_ref()
+        .unwrap_or(&empty_sparse);
+    let snapshot_sparse = snapshot_params
+        .sparse_vectors
+        .as_ref()
+        .unwrap_or(&empty_sparse);
+    check_sparse_compatible(collection_sparse, snapshot_sparse).map_err(incompatible)?;
+    check_sparse_compatible(snapshot_sparse, collection_sparse).map_err(incompatible)?;
+
+    // Check vectors config
+    if snapshot_params.vectors != collection_params.vectors {
+        return Err(StorageError::bad_input(format!(
+            "Snapshot is not compatible with existing collection: Collection vectors: {:?} Snapshot Vectors: {:?}",
+            collection_params.vectors, snapshot_params.vectors
+        )));
+    }
+    // Check shard number
+    if snapshot_params.shard_number != collection_params.shard_number {
+        return Err(StorageError::bad_input(format!(
+            "Snapshot is not compatible with existing collection: Collection shard number: {:?} Snapshot shard number: {:?}",
+            collection_params.shard_number, snapshot_params.shard_number
+        )));
+    }
+
+    Ok(())
+}
+
 pub async fn do_recover_from_snapshot(
     dispatcher: &Dispatcher,
     collection_name: &str,
@@ -156,21 +214,8 @@ async fn _do_recover_from_snapshot(
 
     let state = collection.state().await;
 
-    // Check config compatibility
-    // Check vectors config
-    if snapshot_config.params.vectors != state.config.params.vectors {
-        return Err(StorageError::bad_input(format!(
-            "Snapshot is not compatible with existing collection: Collection vectors: {:?} Snapshot Vectors: {:?}",
-            state.config.params.vectors, snapshot_config.params.vectors
-        )));
-    }
-    // Check shard number
-    if snapshot_config.params.shard_number != state.config.params.shard_number {
-        return Err(StorageError::bad_input(format!(
-            "Snapshot is not compatible with existing collection: Collection shard number: {:?} Snapshot shard number: {:?}",
-            state.config.params.shard_number, snapshot_config.params.shard_number
-        )));
-    }
+    // Check config compatibility before touching any of the existing data
+    check_snapshot_compatibility(&snapshot_config.params, &state.config.params)?;
 
     // Deactivate collection local shards during recovery
     for (shard_id, shard_info) in &state.shards {
@@ -328,3 +373,34 @@ async fn _do_recover_from_snapshot(
 
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use collection::operations::vector_params_builder::VectorParamsBuilder;
+    use segment::types::Distance;
+
+    use super::*;
+
+    fn params(dim: u64, distance: Distance) -> CollectionParams {
+        CollectionParams {
+            vectors: VectorParamsBuilder::new(dim, distance).build().into(),
+            ..CollectionParams::empty()
+        }
+    }
+
+    #[test]
+    fn test_snapshot_compatibility() {
+        let collection_params = params(8, Distance::Dot);
+
+        let err = check_snapshot_compatibility(&params(4, Distance::Dot), &collection_params)
+            .unwrap_err();
+        assert!(matches!(err, StorageError::BadInput { .. }));
+        assert!(err.to_string().contains("size"), "{err}");
+
+        let err = check_snapshot_compatibility(&params(8, Distance::Cosine), &collection_params)
+            .unwrap_err();
+        assert!(err.to_string().contains("distance"), "{err}");
+
+        check_snapshot_compatibility(&params(8, Distance::Dot), &collection_params).unwrap();
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Number of points deleted at once by a streaming delete, if not specified.
+pub const DEFAULT_STREAMING_DELETE_BATCH_SIZE: usize = 1000;
+
+/// Number of finished streaming deletes which are kept to report their final progress.
+const MAX_FINISHED_DELETES: usize = 64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StreamingDeleteStatus {
+    /// Points are being deleted
+    Running,
+    /// All points matching the filter are deleted
+    Completed,
+    /// Delete was cancelled, points deleted so far remain deleted
+    Cancelled,
+    /// Delete stopped because of an error
+    Failed,
+}
+
+/// Progress of a delete of points matching a filter, executed in batches in background.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct StreamingDeleteProgress {
+    /// Identifier of the delete on this node
+    pub id: u64,
+    pub status: StreamingDeleteStatus,
+    /// Number of points deleted so far
+    pub deleted: usize,
+    /// Number of points matching the filter when the delete was started.
+    /// Estimated from cardinality, so it may differ from the final number of deleted points.
+    pub estimated_total: usize,
+    /// Error which stopped the delete
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug)]
+struct StreamingDelete {
+    collection_name: String,
+    progress: Arc<Mutex<StreamingDeleteProgress>>,
+    stopped: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Default)]
+struct StreamingDeletesInner {
+    next_id: u64,
+    deletes: HashMap<u64, StreamingDelete>,
+    /// Finished deletes, oldest first
+    finished: VecDeque<u64>,
+}
+
+/// Tracks streaming deletes on this node, so that their progress can be reported and they can be
+/// cancelled.
+#[derive(Debug, Default)]
+pub struct StreamingDeletes {
+    inner: Arc<Mutex<StreamingDeletesInner>>,
+}
+
+/// Registration of a single streaming delete, used by the task executing it to report progress.
+///
+/// If dropped before the delete is finished, the delete is reported as cancelled.
+#[must_use]
+#[derive(Debug)]
+pub struct StreamingDeleteHandle {
+    id: u64,
+    progress: Arc<Mutex<StreamingDeleteProgress>>,
+    stopped: Arc<AtomicBool>,
+    inner: Arc<Mutex<StreamingDeletesInner>>,
+}
+
+impl StreamingDeleteHandle {
+    pub fn progress(&self) -> StreamingDeleteProgress {
+        self.progress.lock().clone()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    pub fn add_deleted(&self, count: usize) {
+        self.progress.lock().deleted += count;
+    }
+
+    /// Report the final status of the delete.
+    pub fn finish(self, status: StreamingDeleteStatus, error: Option<String>) {
+        self.finish_impl(status, error);
+    }
+
+    fn finish_impl(&self, status: StreamingDeleteStatus, error: Option<String>) {
+        {
+            let mut progress = self.progress.lock();
+            if progress.status != StreamingDeleteStatus::Running {
+                return;
+            }
+            progress.status = status;
+            progress.error = error;
+        }
+
+        let mut inner = self.inner.lock();
+        inner.finished.push_back(self.id);
+        while inner.finished.len() > MAX_FINISHED_DELETES {
+            if let Some(id) = inner.finished.pop_front() {
+                inner.deletes.remove(&id);
+            }
+        }
+    }
+}
+
+impl Drop for StreamingDeleteHandle {
+    fn drop(&mut self) {
+        self.finish_impl(StreamingDeleteStatus::Cancelled, None);
+    }
+}
+
+impl StreamingDeletes {
+    pub fn register(&self, collection_name: &str, estimated_total: usize) -> StreamingDeleteHandle {
+        let mut inner = self.inner.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+
+        let progress = Arc::new(Mutex::new(StreamingDeleteProgress {
+            id,
+            status: StreamingDeleteStatus::Running,
+            deleted: 0,
+            estimated_total,
+            error: None,
+        }));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        inner.deletes.insert(
+            id,
+            StreamingDelete {
+                collection_name: collection_name.to_string(),
+                progress: progress.clone(),
+                stopped: stopped.clone(),
+            },
+        );
+
+        StreamingDeleteHandle {
+            id,
+            progress,
+            stopped,
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Progress of a running or recently finished streaming delete in the given collection.
+    pub fn progress(&self, collection_name: &str, id: u64) -> Option<StreamingDeleteProgress> {
+        let inner = self.inner.lock();
+        inner
+            .deletes
+            .get(&id)
+            .filter(|delete| delete.collection_name == collection_name)
+            .map(|delete| delete.progress.lock().clone())
+    }
+
+    /// Request to stop a streaming delete, it stops after the batch being deleted right now.
+    ///
+    /// Returns the progress of the delete, or `None` if there is no such delete.
+    pub fn cancel(&self, collection_name: &str, id: u64) -> Option<StreamingDeleteProgress> {
+        let inner = self.inner.lock();
+        let delete = inner
+            .deletes
+            .get(&id)
+            .filter(|delete| delete.collection_name == collection_name)?;
+        delete.stopped.store(true, Ordering::Relaxed);
+        let progress = delete.progress.lock().clone();
+        Some(progress)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_streaming_deletes_progress() {
+        let deletes = StreamingDeletes::default();
+
+        let handle = deletes.register("test", 100);
+        let id = handle.progress().id;
+        handle.add_deleted(40);
+
+        let progress = deletes.progress("test", id).unwrap();
+        assert_eq!(progress.status, StreamingDeleteStatus::Running);
+        assert_eq!(progress.deleted, 40);
+        assert_eq!(progress.estimated_total, 100);
+        assert!(deletes.progress("other", id).is_none());
+
+        assert!(deletes.cancel("test", id).is_some());
+        assert!(handle.is_cancelled());
+        handle.finish(StreamingDeleteStatus::Cancelled, None);
+        assert_eq!(
+            deletes.progress("test", id).unwrap().status,
+            StreamingDeleteStatus::Cancelled,
+        );
+
+        // Dropped handle is reported as cancelled
+        let dropped = deletes.register("test", 10);
+        let dropped_id = dropped.progress().id;
+        drop(dropped);
+        assert_eq!(
+            deletes.progress("test", dropped_id).unwrap().status,
+            StreamingDeleteStatus::Cancelled,
+        );
+
+        // Only a limited number of finished deletes is kept
+        for _ in 0..MAX_FINISHED_DELETES {
+            deletes
+                .register("test", 0)
+                .finish(StreamingDeleteStatus::Completed, None);
+        }
+        assert!(deletes.progress("test", id).is_none());
+    }
+}
@@ -141,6 +141,7 @@ impl TableOfContent {
                     let snapshots_path = self.create_snapshots_path(id).await?;
                     let shard_distribution =
                         CollectionShardDistribution::from_shards_info(state.shards.clone());
+                    let management_permit = self.collection_management_limiter.acquire().await;
                     let collection = Collection::new(
                         id.to_string(),
                         self.this_peer_id,
@@ -172,6 +173,7 @@ impl TableOfContent {
                         self.storage_config.optimizers_overwrite.clone(),
                     )
                     .await?;
+                    drop(management_permit);
                     collections.validate_collection_not_exists(id).await?;
                     collections.insert(id.to_string(), collection);
                 }
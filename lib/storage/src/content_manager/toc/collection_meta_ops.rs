@@ -6,10 +6,13 @@ use collection::config::ShardingMethod;
 use collection::events::{CollectionDeletedEvent, IndexCreatedEvent};
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
 use collection::shards::replica_set::ReplicaState;
+use collection::shards::resharding::ReshardKey;
 use collection::shards::transfer::ShardTransfer;
 use collection::shards::{transfer, CollectionId};
+use futures::Future;
 use uuid::Uuid;
 
+use super::transfer::ShardTransferDispatcher;
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
 use crate::content_manager::collections_ops::Checker as _;
@@ -34,6 +37,10 @@ impl TableOfContent {
             CollectionMetaOperations::CreateCollection(mut operation) => {
                 log::info!("Creating collection {}", operation.collection_name);
                 let distribution = match operation.take_distribution() {
+                    None if operation.create_collection.shard_placement.is_some() => self
+                        .shard_placement_distribution(&operation)?
+                        .expect("shard placement is specified")
+                        .into(),
                     None => match operation
                         .create_collection
                         .sharding_method
@@ -126,6 +133,10 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            payload_schema,
+            default_search_params,
+            tie_break,
+            default_timeout,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -158,6 +169,20 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(payload_schema) = payload_schema {
+            collection.update_payload_schema(payload_schema).await?;
+        }
+        if let Some(default_search_params) = default_search_params {
+            collection
+                .update_default_search_params(default_search_params)
+                .await?;
+        }
+        if let Some(tie_break) = tie_break {
+            collection.update_tie_break(tie_break).await?;
+        }
+        if let Some(default_timeout) = default_timeout {
+            collection.update_default_timeout(default_timeout).await?;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -192,7 +217,10 @@ impl TableOfContent {
                 }
             }
 
+            // Stopping the collection releases all of its shards
+            let management_permit = self.collection_management_limiter.acquire().await;
             drop(removed);
+            drop(management_permit);
 
             // Move collection to ".deleted" folder to prevent accidental reuse
             let uuid = Uuid::new_v4().to_string();
@@ -212,8 +240,14 @@ impl TableOfContent {
             // At this point collection is removed from memory and moved to ".deleted" folder.
             // Next time we load service the collection will not appear in the list of collections.
             // We can take our time to delete the collection from disk.
+            let collection_management_limiter = self.collection_management_limiter.clone();
+            let segment_volumes = self.storage_config.segment_volumes();
             tokio::spawn(async move {
-                if let Err(error) = tokio::fs::remove_dir_all(&deleted_path).await {
+                let _management_permit = collection_management_limiter.acquire_owned().await;
+                if let Err(error) = segment_volumes
+                    .remove_dir_with_linked_segments(&deleted_path)
+                    .await
+                {
                     log::error!(
                         "Can't delete collection {} from disk. Error: {}",
                         deleted_path.display(),
@@ -231,7 +265,11 @@ impl TableOfContent {
                     "Collection {} is not loaded, but its directory still exists. Deleting it.",
                     collection_name
                 );
-                tokio::fs::remove_dir_all(path).await?;
+                let _management_permit = self.collection_management_limiter.acquire().await;
+                self.storage_config
+                    .segment_volumes()
+                    .remove_dir_with_linked_segments(&path)
+                    .await?;
             }
             Ok(false)
         }
@@ -289,49 +327,28 @@ impl TableOfContent {
         operation: ReshardingOperation,
     ) -> Result<(), StorageError> {
         let collection = self.get_collection_unchecked(&collection_id).await?;
-        let Some(proposal_sender) = self.consensus_proposal_sender.clone() else {
+        if self.consensus_proposal_sender.is_none() {
             return Err(StorageError::service_error(
                 "Can't handle resharding, this is a single node deployment",
             ));
-        };
+        }
 
         match operation {
             ReshardingOperation::Start(key) => {
-                let consensus = match self.shard_transfer_dispatcher.lock().as_ref() {
-                    Some(consensus) => Box::new(consensus.clone()),
-                    None => {
-                        return Err(StorageError::service_error(
-                            "Can't handle transfer, this is a single node deployment",
-                        ))
-                    }
-                };
+                let (consensus, on_finish, on_failure) =
+                    self.resharding_task_handles(collection_id, key.clone())?;
 
-                let on_finish = {
-                    let collection_id = collection_id.clone();
-                    let key = key.clone();
-                    let proposal_sender = proposal_sender.clone();
-                    async move {
-                        let operation = ConsensusOperations::finish_resharding(collection_id, key);
-                        if let Err(error) = proposal_sender.send(operation) {
-                            log::error!("Can't report resharding progress to consensus: {error}");
-                        };
-                    }
-                };
+                collection
+                    .start_resharding(key, consensus, on_finish, on_failure)
+                    .await?;
+            }
 
-                let on_failure = {
-                    let collection_id = collection_id.clone();
-                    let key = key.clone();
-                    async move {
-                        if let Err(error) = proposal_sender
-                            .send(ConsensusOperations::abort_resharding(collection_id, key))
-                        {
-                            log::error!("Can't report resharding progress to consensus: {error}");
-                        };
-                    }
-                };
+            ReshardingOperation::Resume(key) => {
+                let (consensus, on_finish, on_failure) =
+                    self.resharding_task_handles(collection_id, key.clone())?;
 
                 collection
-                    .start_resharding(key, consensus, on_finish, on_failure)
+                    .resume_resharding(key, consensus, on_finish, on_failure)
                     .await?;
             }
 
@@ -355,6 +372,60 @@ impl TableOfContent {
         Ok(())
     }
 
+    /// Consensus dispatcher and callbacks for driving a resharding task
+    ///
+    /// The callbacks propose to finish or to abort the resharding, once the task is done.
+    #[allow(clippy::type_complexity)]
+    fn resharding_task_handles(
+        &self,
+        collection_id: CollectionId,
+        key: ReshardKey,
+    ) -> Result<
+        (
+            Box<ShardTransferDispatcher>,
+            impl Future<Output = ()> + Send + 'static,
+            impl Future<Output = ()> + Send + 'static,
+        ),
+        StorageError,
+    > {
+        let Some(proposal_sender) = self.consensus_proposal_sender.clone() else {
+            return Err(StorageError::service_error(
+                "Can't handle resharding, this is a single node deployment",
+            ));
+        };
+
+        let consensus = match self.shard_transfer_dispatcher.lock().as_ref() {
+            Some(consensus) => Box::new(consensus.clone()),
+            None => {
+                return Err(StorageError::service_error(
+                    "Can't handle transfer, this is a single node deployment",
+                ))
+            }
+        };
+
+        let on_finish = {
+            let collection_id = collection_id.clone();
+            let key = key.clone();
+            let proposal_sender = proposal_sender.clone();
+            async move {
+                let operation = ConsensusOperations::finish_resharding(collection_id, key);
+                if let Err(error) = proposal_sender.send(operation) {
+                    log::error!("Can't report resharding progress to consensus: {error}");
+                };
+            }
+        };
+
+        let on_failure = async move {
+            if let Err(error) =
+                proposal_sender.send(ConsensusOperations::abort_resharding(collection_id, key))
+            {
+                log::error!("Can't report resharding progress to consensus: {error}");
+            };
+        };
+
+        Ok((consensus, on_finish, on_failure))
+    }
+
     /// Resume all resharding tasks
     ///
     /// Starts driving all registered resharding tasks to continue the resharding process.
@@ -383,11 +454,6 @@ impl TableOfContent {
         collection_id: CollectionId,
     ) -> Result<(), StorageError> {
         let collection = self.get_collection_unchecked(&collection_id).await?;
-        let Some(proposal_sender) = self.consensus_proposal_sender.clone() else {
-            return Err(StorageError::service_error(
-                "Can't handle resharding, this is a single node deployment",
-            ));
-        };
 
         // Get current resharding state and key, or return early if there is none
         let Some(resharding_state) = collection.resharding_state().await else {
@@ -395,38 +461,8 @@ impl TableOfContent {
         };
         let key = resharding_state.key();
 
-        let consensus = match self.shard_transfer_dispatcher.lock().as_ref() {
-            Some(consensus) => Box::new(consensus.clone()),
-            None => {
-                return Err(StorageError::service_error(
-                    "Can't handle transfer, this is a single node deployment",
-                ))
-            }
-        };
-
-        let on_finish = {
-            let collection_id = collection_id.clone();
-            let key = key.clone();
-            let proposal_sender = proposal_sender.clone();
-            async move {
-                let operation = ConsensusOperations::finish_resharding(collection_id, key);
-                if let Err(error) = proposal_sender.send(operation) {
-                    log::error!("Can't report resharding progress to consensus: {error}");
-                };
-            }
-        };
-
-        let on_failure = {
-            let collection_id = collection_id.clone();
-            let key = key.clone();
-            async move {
-                if let Err(error) =
-                    proposal_sender.send(ConsensusOperations::abort_resharding(collection_id, key))
-                {
-                    log::error!("Can't report resharding progress to consensus: {error}");
-                };
-            }
-        };
+        let (consensus, on_finish, on_failure) =
+            self.resharding_task_handles(collection_id, key)?;
 
         collection
             .resume_resharding_unchecked(consensus, on_finish, on_failure)
@@ -553,6 +589,17 @@ impl TableOfContent {
                     )));
                 }
 
+                let new_transfer = ShardTransfer {
+                    shard_id: transfer_restart.shard_id,
+                    to_shard_id: None,
+                    from: transfer_restart.from,
+                    to: transfer_restart.to,
+                    sync: old_transfer.sync, // Preserve sync flag from the old transfer
+                    method: Some(transfer_restart.method),
+                    filter: old_transfer.filter.clone(), // Preserve filter from the old transfer
+                };
+                new_transfer.check_filter_supported()?;
+
                 // Abort and start transfer
                 Box::pin(self.handle_transfer(
                     collection_id.clone(),
@@ -563,15 +610,6 @@ impl TableOfContent {
                 ))
                 .await?;
 
-                let new_transfer = ShardTransfer {
-                    shard_id: transfer_restart.shard_id,
-                    to_shard_id: None,
-                    from: transfer_restart.from,
-                    to: transfer_restart.to,
-                    sync: old_transfer.sync, // Preserve sync flag from the old transfer
-                    method: Some(transfer_restart.method),
-                };
-
                 Box::pin(
                     self.handle_transfer(
                         collection_id,
@@ -11,6 +11,7 @@ use collection::shards::collection_shard_distribution::CollectionShardDistributi
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
+use tokio::sync::MutexGuard;
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -18,6 +19,7 @@ use crate::content_manager::collections_ops::Checker as _;
 use crate::content_manager::consensus_ops::ConsensusOperations;
 use crate::content_manager::data_transfer;
 use crate::content_manager::errors::StorageError;
+use crate::content_manager::shard_distribution::placement_replication_factor;
 
 impl TableOfContent {
     pub(super) async fn create_collection(
@@ -40,38 +42,28 @@ impl TableOfContent {
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
             replication_factor,
+            shard_placement,
             write_consistency_factor,
             init_from,
             quantization_config,
             sparse_vectors,
             strict_mode_config,
+            payload_schema,
+            default_search_params,
+            tie_break,
+            default_timeout,
+            auto_create_shard_keys,
         } = operation;
 
-        self.collections
-            .read()
-            .await
-            .validate_collection_not_exists(collection_name)
+        self.check_collection_name_available(collection_name)
             .await?;
-
-        if self
-            .alias_persistence
-            .read()
-            .await
-            .check_alias_exists(collection_name)
-        {
-            return Err(StorageError::bad_input(format!(
-                "Can't create collection with name {collection_name}. Alias with the same name already exists",
-            )));
-        }
+        self.check_collections_limit().await?;
 
         if let Some(init_from) = &init_from {
             self.check_collections_compatibility(&vectors, &sparse_vectors, &init_from.collection)
                 .await?;
         }
 
-        let collection_path = self.create_collection_path(collection_name).await?;
-        let snapshots_path = self.create_snapshots_path(collection_name).await?;
-
         let collection_defaults_config = self.storage_config.collection.as_ref();
 
         let default_shard_number = collection_defaults_config
@@ -105,7 +97,20 @@ impl TableOfContent {
             }
         };
 
+        if auto_create_shard_keys == Some(true)
+            && sharding_method.unwrap_or_default() != ShardingMethod::Custom
+        {
+            return Err(StorageError::bad_input(
+                "Shard keys can only be created automatically with custom sharding method",
+            ));
+        }
+
         let replication_factor = replication_factor
+            .or_else(|| {
+                shard_placement
+                    .as_ref()
+                    .and_then(placement_replication_factor)
+            })
             .or_else(|| collection_defaults_config.map(|i| i.replication_factor))
             .unwrap_or_else(|| config::default_replication_factor().get());
 
@@ -139,12 +144,14 @@ impl TableOfContent {
                 description: "`shard_number` cannot be 0".to_string(),
             })?,
             sharding_method,
+            auto_create_shard_keys: auto_create_shard_keys.unwrap_or(false),
             on_disk_payload: on_disk_payload.unwrap_or(self.storage_config.on_disk_payload),
             replication_factor: NonZeroU32::new(replication_factor).ok_or(
                 StorageError::BadInput {
                     description: "`replication_factor` cannot be 0".to_string(),
                 },
             )?,
+            shard_key_replication_factors: Vec::new(),
             write_consistency_factor: NonZeroU32::new(write_consistency_factor).ok_or(
                 StorageError::BadInput {
                     description: "`write_consistency_factor` cannot be 0".to_string(),
@@ -176,11 +183,6 @@ impl TableOfContent {
             Some(diff) => Some(diff),
         };
 
-        let storage_config = self
-            .storage_config
-            .to_shared_storage_config(self.is_distributed())
-            .into();
-
         let collection_config = CollectionConfig {
             wal_config,
             params: collection_params,
@@ -188,13 +190,93 @@ impl TableOfContent {
             hnsw_config,
             quantization_config,
             strict_mode_config,
+            payload_schema,
+            default_search_params,
+            tie_break,
+            default_timeout,
         };
+
+        self.instantiate_collection(
+            collection_name,
+            &collection_config,
+            collection_shard_distribution,
+            collection_create_guard,
+        )
+        .await?;
+
+        if let Some(init_from) = init_from {
+            self.run_data_initialization(init_from.collection, collection_name.to_string())
+                .await;
+        }
+
+        Ok(true)
+    }
+
+    /// Check that one more collection fits into the configured maximum number of collections
+    async fn check_collections_limit(&self) -> Result<(), StorageError> {
+        let Some(max_collections) = self.storage_config.max_collections else {
+            return Ok(());
+        };
+
+        let collections_count = self.collections.read().await.len();
+        if collections_count >= max_collections {
+            return Err(StorageError::bad_input(format!(
+                "Can't create collection, the maximum number of {max_collections} collections is reached",
+            )));
+        }
+        Ok(())
+    }
+
+    async fn check_collection_name_available(
+        &self,
+        collection_name: &str,
+    ) -> Result<(), StorageError> {
+        self.collections
+            .read()
+            .await
+            .validate_collection_not_exists(collection_name)
+            .await?;
+
+        if self
+            .alias_persistence
+            .read()
+            .await
+            .check_alias_exists(collection_name)
+        {
+            return Err(StorageError::bad_input(format!(
+                "Can't create collection with name {collection_name}. Alias with the same name already exists",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Create collection files, register collection and activate its local shards
+    ///
+    /// Takes the collection creation guard, which is released as soon as the collection is
+    /// registered.
+    async fn instantiate_collection(
+        &self,
+        collection_name: &str,
+        collection_config: &CollectionConfig,
+        collection_shard_distribution: CollectionShardDistribution,
+        collection_create_guard: MutexGuard<'_, ()>,
+    ) -> Result<(), StorageError> {
+        let collection_path = self.create_collection_path(collection_name).await?;
+        let snapshots_path = self.create_snapshots_path(collection_name).await?;
+
+        let storage_config = self
+            .storage_config
+            .to_shared_storage_config(self.is_distributed())
+            .into();
+
+        let management_permit = self.collection_management_limiter.acquire().await;
         let collection = Collection::new(
             collection_name.to_string(),
             self.this_peer_id,
             &collection_path,
             &snapshots_path,
-            &collection_config,
+            collection_config,
             storage_config,
             collection_shard_distribution,
             self.channel_service.clone(),
@@ -218,6 +300,7 @@ impl TableOfContent {
             self.storage_config.optimizers_overwrite.clone(),
         )
         .await?;
+        drop(management_permit);
 
         let local_shards = collection.get_local_shards().await;
 
@@ -237,12 +320,7 @@ impl TableOfContent {
                 .await?;
         }
 
-        if let Some(init_from) = init_from {
-            self.run_data_initialization(init_from.collection, collection_name.to_string())
-                .await;
-        }
-
-        Ok(true)
+        Ok(())
     }
 
     async fn check_collections_compatibility(
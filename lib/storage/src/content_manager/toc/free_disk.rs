@@ -0,0 +1,76 @@
+use std::io;
+use std::path::Path;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+
+impl TableOfContent {
+    /// Check that the storage disk has at least the configured minimum of free space.
+    ///
+    /// Must be checked before operations which add data, so they are rejected before touching
+    /// the disk. If the available space can't be determined, the check is skipped.
+    pub fn check_free_disk_space(&self) -> Result<(), StorageError> {
+        let Some(min_free_disk_mb) = self.storage_config.min_free_disk_mb else {
+            return Ok(());
+        };
+
+        check_free_disk_space(
+            Path::new(&self.storage_config.storage_path),
+            min_free_disk_mb,
+            fs4::available_space,
+        )
+    }
+}
+
+fn check_free_disk_space(
+    storage_path: &Path,
+    min_free_disk_mb: u64,
+    available_space: impl FnOnce(&Path) -> io::Result<u64>,
+) -> Result<(), StorageError> {
+    let space_available = match available_space(storage_path) {
+        Ok(available) => available,
+        Err(err) => {
+            log::warn!(
+                "Could not estimate available storage space in `{}`, skipping free disk check: {err}",
+                storage_path.display(),
+            );
+            return Ok(());
+        }
+    };
+
+    let space_required = min_free_disk_mb.saturating_mul(1024 * 1024);
+    if space_available < space_required {
+        return Err(StorageError::PreconditionFailed {
+            description: format!(
+                "Not enough free disk space in `{}`: {} MB available, at least {min_free_disk_mb} MB \
+                 required. Only reads and deletes are allowed until space is freed",
+                storage_path.display(),
+                space_available / (1024 * 1024),
+            ),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn test_check_free_disk_space() {
+        let path = Path::new("/storage");
+
+        assert!(check_free_disk_space(path, 100, |_| Ok(200 * MB)).is_ok());
+        assert!(check_free_disk_space(path, 100, |_| Ok(100 * MB)).is_ok());
+        assert!(matches!(
+            check_free_disk_space(path, 100, |_| Ok(100 * MB - 1)),
+            Err(StorageError::PreconditionFailed { .. }),
+        ));
+
+        // Unknown free space does not block writes
+        assert!(check_free_disk_space(path, 100, |_| Err(io::Error::other("unknown"))).is_ok());
+    }
+}
@@ -0,0 +1,125 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use futures::future::join_all;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+
+/// Default number of collection management operations running concurrently.
+const DEFAULT_MAX_CONCURRENT_OPERATIONS: usize = 1;
+
+/// Bounds the number of heavy collection management operations, such as loading, creating and
+/// deleting collections, running concurrently on this node.
+///
+/// Each of these operations may allocate a lot of memory at once, running many of them in
+/// parallel (e.g. loading all collections on startup) may exhaust it.
+#[derive(Debug, Clone)]
+pub struct CollectionManagementLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl CollectionManagementLimiter {
+    /// If `limit` is not defined - operations run one at a time.
+    pub fn new(limit: Option<usize>) -> Self {
+        let limit = limit.unwrap_or(DEFAULT_MAX_CONCURRENT_OPERATIONS).max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+        }
+    }
+
+    /// Wait for a free slot to run a collection management operation.
+    /// The slot is released once the returned permit is dropped.
+    ///
+    /// # Cancel safety
+    ///
+    /// This function is cancel safe.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("collection management semaphore is never closed")
+    }
+
+    /// Same as [`Self::acquire`], but the permit can be moved into a spawned task.
+    ///
+    /// # Cancel safety
+    ///
+    /// This function is cancel safe.
+    pub async fn acquire_owned(&self) -> OwnedSemaphorePermit {
+        self.semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("collection management semaphore is never closed")
+    }
+
+    /// Run `operation` for all `items` concurrently, but not more than the limit at a time.
+    ///
+    /// Results are returned in the order of `items`.
+    pub async fn run_all<T, F, Fut>(
+        &self,
+        items: impl IntoIterator<Item = T>,
+        operation: F,
+    ) -> Vec<Fut::Output>
+    where
+        F: Fn(T) -> Fut,
+        Fut: Future,
+    {
+        let operation = &operation;
+        join_all(items.into_iter().map(|item| async move {
+            let _permit = self.acquire().await;
+            operation(item).await
+        }))
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_run_all_bounded() {
+        const NUM_COLLECTIONS: usize = 50;
+        const LIMIT: usize = 3;
+
+        let limiter = CollectionManagementLimiter::new(Some(LIMIT));
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let loaded = limiter
+            .run_all(0..NUM_COLLECTIONS, |collection| {
+                let running = &running;
+                let max_running = &max_running;
+                async move {
+                    let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now_running, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    format!("collection_{collection}")
+                }
+            })
+            .await;
+
+        let expected: Vec<_> = (0..NUM_COLLECTIONS)
+            .map(|collection| format!("collection_{collection}"))
+            .collect();
+        assert_eq!(loaded, expected);
+        assert_eq!(max_running.load(Ordering::SeqCst), LIMIT);
+        assert_eq!(running.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_default_limit_is_sequential() {
+        let limiter = CollectionManagementLimiter::new(None);
+        let permit = limiter.acquire().await;
+
+        let waiting = tokio::time::timeout(Duration::from_millis(10), limiter.acquire()).await;
+        assert!(waiting.is_err());
+
+        drop(permit);
+        let _permit = limiter.acquire().await;
+    }
+}
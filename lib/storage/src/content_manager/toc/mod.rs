@@ -2,10 +2,14 @@ mod collection_container;
 use common::types::TelemetryDetail;
 mod collection_meta_ops;
 mod create_collection;
+mod free_disk;
 mod locks;
+mod management_limiter;
 mod point_ops;
 mod point_ops_internal;
+mod recall_monitor;
 mod snapshots;
+mod streaming_delete;
 mod temp_directories;
 pub mod transfer;
 
@@ -18,7 +22,9 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use collection::collection::{Collection, RequestShardTransfer};
-use collection::config::{default_replication_factor, CollectionConfig};
+use collection::common::segment_volumes::SegmentVolumes;
+use collection::config::{default_replication_factor, CollectionConfig, ShardingMethod};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::replica_set;
@@ -29,13 +35,21 @@ use common::cpu::{get_num_cpus, CpuBudget};
 use tokio::runtime::Runtime;
 use tokio::sync::{Mutex, RwLock, RwLockReadGuard, Semaphore};
 
+use self::management_limiter::CollectionManagementLimiter;
 use self::transfer::ShardTransferDispatcher;
 use crate::content_manager::alias_mapping::AliasPersistence;
 use crate::content_manager::collection_meta_ops::CreateCollectionOperation;
 use crate::content_manager::collections_ops::{Checker, Collections};
 use crate::content_manager::consensus::operation_sender::OperationSender;
 use crate::content_manager::errors::StorageError;
-use crate::content_manager::shard_distribution::ShardDistributionProposal;
+use crate::content_manager::shard_distribution::{
+    placement_replication_factor, ShardDistributionProposal,
+};
+use crate::content_manager::snapshots::in_progress::ShardSnapshotsInProgress;
+use crate::content_manager::snapshots::limiter::{
+    ShardSnapshotLimiter, ShardSnapshotLimiterStatus, ShardSnapshotPermit,
+};
+use crate::content_manager::streaming_delete::StreamingDeletes;
 use crate::rbac::{Access, AccessRequirements, CollectionPass};
 use crate::types::StorageConfig;
 use crate::ConsensusOperations;
@@ -72,8 +86,16 @@ pub struct TableOfContent {
     /// A lock to prevent concurrent collection creation.
     /// Effectively, this lock ensures that `create_collection` is called sequentially.
     collection_create_lock: Mutex<()>,
+    /// Bounds the number of collections loaded, created or deleted concurrently on this node.
+    collection_management_limiter: CollectionManagementLimiter,
     /// Dispatcher for shard transfer to access consensus.
     shard_transfer_dispatcher: parking_lot::Mutex<Option<ShardTransferDispatcher>>,
+    /// Bounds the number of shard snapshots created concurrently on this node.
+    shard_snapshot_limiter: ShardSnapshotLimiter,
+    /// Shard snapshots being created on this node, allows to cancel them.
+    shard_snapshots_in_progress: ShardSnapshotsInProgress,
+    /// Deletes of points by filter running in background on this node.
+    streaming_deletes: StreamingDeletes,
 }
 
 impl TableOfContent {
@@ -97,10 +119,22 @@ impl TableOfContent {
             let temp_path = Path::new(path);
             create_dir_all(temp_path).expect("Can't create temporary files directory");
         }
+        for volume in &storage_config.segment_volumes {
+            let volume = Path::new(volume);
+            create_dir_all(SegmentVolumes::volume_segments_path(volume))
+                .expect("Can't create segments directory on storage volume");
+            create_dir_all(SegmentVolumes::volume_temp_path(volume))
+                .expect("Can't create temporary directory on storage volume");
+        }
         let collection_paths =
             read_dir(&collections_path).expect("Can't read Collections directory");
-        let mut collections: HashMap<String, Collection> = Default::default();
         let is_distributed = consensus_proposal_sender.is_some();
+        let collection_management_limiter = CollectionManagementLimiter::new(
+            storage_config
+                .performance
+                .max_concurrent_collection_operations,
+        );
+        let mut collections_to_load = Vec::new();
         for entry in collection_paths {
             let collection_path = entry
                 .expect("Can't access of one of the collection files")
@@ -125,38 +159,58 @@ impl TableOfContent {
             create_dir_all(&collection_snapshots_path).unwrap_or_else(|e| {
                 panic!("Can't create a directory for snapshot of {collection_name}: {e}")
             });
-            log::info!("Loading collection: {collection_name}");
-            let collection = general_runtime.block_on(Collection::load(
-                collection_name.clone(),
-                this_peer_id,
-                &collection_path,
-                &collection_snapshots_path,
-                storage_config
-                    .to_shared_storage_config(is_distributed)
-                    .into(),
-                channel_service.clone(),
-                Self::change_peer_state_callback(
+            collections_to_load.push((collection_name, collection_path, collection_snapshots_path));
+        }
+
+        let loaded_collections = general_runtime.block_on(collection_management_limiter.run_all(
+            collections_to_load,
+            |(collection_name, collection_path, collection_snapshots_path)| {
+                let shared_storage_config =
+                    Arc::new(storage_config.to_shared_storage_config(is_distributed));
+                let channel_service = channel_service.clone();
+                let on_replica_failure = Self::change_peer_state_callback(
                     consensus_proposal_sender.clone(),
                     collection_name.clone(),
                     ReplicaState::Dead,
                     None,
-                ),
-                Self::request_shard_transfer_callback(
+                );
+                let request_shard_transfer = Self::request_shard_transfer_callback(
                     consensus_proposal_sender.clone(),
                     collection_name.clone(),
-                ),
-                Self::abort_shard_transfer_callback(
+                );
+                let abort_shard_transfer = Self::abort_shard_transfer_callback(
                     consensus_proposal_sender.clone(),
                     collection_name.clone(),
-                ),
-                Some(search_runtime.handle().clone()),
-                Some(update_runtime.handle().clone()),
-                optimizer_cpu_budget.clone(),
-                storage_config.optimizers_overwrite.clone(),
-            ));
+                );
+                let search_runtime = search_runtime.handle().clone();
+                let update_runtime = update_runtime.handle().clone();
+                let optimizer_cpu_budget = optimizer_cpu_budget.clone();
+                let optimizers_overwrite = storage_config.optimizers_overwrite.clone();
+
+                async move {
+                    log::info!("Loading collection: {collection_name}");
+                    let collection = Collection::load(
+                        collection_name.clone(),
+                        this_peer_id,
+                        &collection_path,
+                        &collection_snapshots_path,
+                        shared_storage_config,
+                        channel_service,
+                        on_replica_failure,
+                        request_shard_transfer,
+                        abort_shard_transfer,
+                        Some(search_runtime),
+                        Some(update_runtime),
+                        optimizer_cpu_budget,
+                        optimizers_overwrite,
+                    )
+                    .await;
+                    (collection_name, collection)
+                }
+            },
+        ));
+        let collections: HashMap<String, Collection> = loaded_collections.into_iter().collect();
 
-            collections.insert(collection_name, collection);
-        }
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
@@ -193,10 +247,40 @@ impl TableOfContent {
             lock_error_message: parking_lot::Mutex::new(None),
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
+            collection_management_limiter,
             shard_transfer_dispatcher: Default::default(),
+            shard_snapshot_limiter: ShardSnapshotLimiter::new(
+                storage_config.performance.max_concurrent_shard_snapshots,
+            ),
+            shard_snapshots_in_progress: Default::default(),
+            streaming_deletes: Default::default(),
         }
     }
 
+    /// Wait for a free slot to create a shard snapshot.
+    /// The slot is released once the returned permit is dropped.
+    pub async fn acquire_shard_snapshot_permit(&self) -> ShardSnapshotPermit {
+        self.shard_snapshot_limiter.acquire().await
+    }
+
+    /// Number of queued and running shard snapshot creations on this node.
+    pub fn shard_snapshot_status(&self) -> ShardSnapshotLimiterStatus {
+        self.shard_snapshot_limiter.status()
+    }
+
+    /// List running shard transfers driven by this peer, in all accessible collections.
+    pub async fn running_shard_transfers(&self, access: &Access) -> Vec<ShardTransferProgressInfo> {
+        let mut transfers = Vec::new();
+        for collection_pass in self.all_collections(access).await {
+            // Collection may have been deleted in the meantime
+            let Ok(collection) = self.get_collection(&collection_pass).await else {
+                continue;
+            };
+            transfers.extend(collection.running_shard_transfers().await);
+        }
+        transfers
+    }
+
     /// Return `true` if service is working in distributed mode.
     pub fn is_distributed(&self) -> bool {
         self.consensus_proposal_sender.is_some()
@@ -257,6 +341,18 @@ impl TableOfContent {
         self.get_collection_unchecked(collection.name()).await
     }
 
+    /// Full config of the collection, which can be used to recreate the collection
+    /// with a [`CreateCollection`] operation converted from it
+    ///
+    /// [`CreateCollection`]: crate::content_manager::collection_meta_ops::CreateCollection
+    pub async fn export_config(
+        &self,
+        collection: &CollectionPass<'_>,
+    ) -> Result<CollectionConfig, StorageError> {
+        let collection = self.get_collection(collection).await?;
+        Ok(collection.state().await.config)
+    }
+
     async fn get_collection_opt(
         &self,
         collection_name: String,
@@ -332,6 +428,44 @@ impl TableOfContent {
         Ok(aliases)
     }
 
+    /// Info and aliases of all collections to which the user has access
+    ///
+    /// Collections and aliases are read under a single read lock, so the result is consistent
+    /// with concurrent creation, deletion and renaming of collections and aliases.
+    /// Collections are sorted by name.
+    pub async fn list_collections_with_info(
+        &self,
+        access: &Access,
+    ) -> Result<Vec<(String, CollectionInfo, Vec<String>)>, StorageError> {
+        let collections = self.collections.read().await;
+        let alias_persistence = self.alias_persistence.read().await;
+
+        let mut result = Vec::new();
+        for (name, collection) in collections.iter() {
+            if access
+                .check_collection_access(name, AccessRequirements::new())
+                .is_err()
+            {
+                continue;
+            }
+
+            let info = collection.info(&ShardSelectorInternal::All).await?;
+
+            let mut aliases = alias_persistence.collection_aliases(name);
+            aliases.retain(|alias| {
+                access
+                    .check_collection_access(alias, AccessRequirements::new())
+                    .is_ok()
+            });
+            aliases.sort_unstable();
+
+            result.push((name.clone(), info, aliases));
+        }
+        result.sort_unstable_by(|(a, ..), (b, ..)| a.cmp(b));
+
+        Ok(result)
+    }
+
     pub async fn suggest_shard_distribution(
         &self,
         op: &CreateCollectionOperation,
@@ -342,15 +476,7 @@ impl TableOfContent {
             .shard_number
             .and_then(NonZeroU32::new)
             .unwrap_or(suggested_shard_number);
-        let mut known_peers_set: HashSet<_> = self
-            .channel_service
-            .id_to_address
-            .read()
-            .keys()
-            .copied()
-            .collect();
-        known_peers_set.insert(self.this_peer_id());
-        let known_peers: Vec<_> = known_peers_set.into_iter().collect();
+        let known_peers = self.known_peers();
         let replication_factor = op
             .create_collection
             .replication_factor
@@ -370,6 +496,64 @@ impl TableOfContent {
         shard_distribution
     }
 
+    /// Shard distribution of the explicit shard placement of a new collection
+    ///
+    /// Returns `None` if the operation does not specify a shard placement.
+    pub fn shard_placement_distribution(
+        &self,
+        op: &CreateCollectionOperation,
+    ) -> Result<Option<ShardDistributionProposal>, StorageError> {
+        let create_collection = &op.create_collection;
+        let Some(placement) = &create_collection.shard_placement else {
+            return Ok(None);
+        };
+
+        if create_collection.sharding_method.unwrap_or_default() != ShardingMethod::Auto {
+            return Err(StorageError::bad_input(
+                "Shard placement can only be specified for auto sharding",
+            ));
+        }
+
+        if let Some(shard_number) = create_collection.shard_number {
+            if shard_number as usize != placement.len() {
+                return Err(StorageError::bad_input(format!(
+                    "Shard placement has {} shards, but shard number is {shard_number}",
+                    placement.len(),
+                )));
+            }
+        }
+
+        // Every shard must have as many replicas as the replication factor, which is derived from
+        // the placement if not specified
+        let replication_factor = create_collection
+            .replication_factor
+            .or_else(|| placement_replication_factor(placement));
+        for (shard_id, peers) in placement {
+            if Some(peers.len() as u32) != replication_factor {
+                return Err(StorageError::bad_input(format!(
+                    "Shard {shard_id} is placed on {} peers, but replication factor is {}",
+                    peers.len(),
+                    replication_factor.unwrap_or_default(),
+                )));
+            }
+        }
+
+        ShardDistributionProposal::from_placement(placement, &self.known_peers()).map(Some)
+    }
+
+    /// IDs of all peers in the cluster, including this one
+    fn known_peers(&self) -> Vec<PeerId> {
+        let mut known_peers_set: HashSet<_> = self
+            .channel_service
+            .id_to_address
+            .read()
+            .keys()
+            .copied()
+            .collect();
+        known_peers_set.insert(self.this_peer_id());
+        known_peers_set.into_iter().collect()
+    }
+
     /// Initiate receiving shard.
     ///
     /// Fails if the collection does not exist
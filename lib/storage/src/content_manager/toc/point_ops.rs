@@ -3,24 +3,27 @@ use std::time::Duration;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
+use collection::collection::query_explanation::ExplainedQueryResponse;
+use collection::collection::query_plan::QueryPlan;
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::point_ops::WriteOrdering;
+use collection::operations::point_ops::{PointOperations, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
 use collection::{discovery, recommendations};
+use common::types::ScoreType;
 use futures::stream::FuturesUnordered;
 use futures::TryStreamExt as _;
-use segment::data_types::facets::{FacetParams, FacetResponse};
-use segment::types::{ScoredPoint, ShardKey};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
+use segment::types::{ExtendedPointId, Filter, ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -133,6 +136,27 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Search for the ids and scores of the closest points only
+    ///
+    /// Payloads and vectors are neither fetched nor returned, regardless of the request.
+    pub async fn search_ids_only(
+        &self,
+        collection_name: &str,
+        mut request: CoreSearchRequest,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<Vec<(ExtendedPointId, ScoreType)>> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        collection
+            .search_ids_only(request, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
     /// Count points in the collection.
     ///
     /// # Arguments
@@ -331,6 +355,52 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Describe how a query is going to be executed, without executing it
+    pub async fn query_plan(
+        &self,
+        collection_name: &str,
+        mut request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<QueryPlan> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .query_plan(&request, read_consistency, &shard_selection, timeout)
+            .await
+            .map_err(|err| err.into())
+    }
+
+    /// Execute a query, explaining how the score of each returned point is composed
+    pub async fn query_explain(
+        &self,
+        collection_name: &str,
+        mut request: CollectionQueryRequest,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<ExplainedQueryResponse> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .query_explain(
+                request,
+                shard_selection,
+                |name| self.get_collection_opt(name),
+                read_consistency,
+                timeout,
+            )
+            .await
+            .map_err(|err| err.into())
+    }
+
     // Return unique values for a payload key, and a count of points for each value.
     pub async fn facet(
         &self,
@@ -351,6 +421,26 @@ impl TableOfContent {
             .map_err(StorageError::from)
     }
 
+    /// Return distinct values of a payload key, read from its payload index.
+    pub async fn distinct_values(
+        &self,
+        collection_name: &str,
+        mut request: DistinctValuesParams,
+        shard_selection: ShardSelectorInternal,
+        read_consistency: Option<ReadConsistency>,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<Vec<FacetValue>> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+
+        collection
+            .distinct_values(request, shard_selection, read_consistency, timeout)
+            .await
+            .map_err(StorageError::from)
+    }
+
     pub async fn search_points_matrix(
         &self,
         collection_name: &str,
@@ -385,6 +475,16 @@ impl TableOfContent {
     ) -> StorageResult<UpdateResult> {
         // `Collection::update_from_client` is cancel safe, so this method is cancel safe.
 
+        let is_conditional = matches!(
+            operation,
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsIfVersion(_)),
+        );
+        if is_conditional && shard_keys.len() > 1 {
+            return Err(StorageError::bad_input(
+                "Upsert with expected versions must only target a single shard key",
+            ));
+        }
+
         let updates: FuturesUnordered<_> = shard_keys
             .into_iter()
             .map(|shard_key| {
@@ -395,10 +495,18 @@ impl TableOfContent {
         // `Collection::update_from_client` is cancel safe, so it's safe to use `TryStreamExt::try_collect`
         let results: Vec<_> = updates.try_collect().await?;
 
-        results
+        // Points deduplicated in each shard add up
+        let deduplicated = results
+            .iter()
+            .filter_map(|result| result.deduplicated)
+            .reduce(|total, count| total + count);
+
+        let mut result = results
             .into_iter()
             .next()
-            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))
+            .ok_or_else(|| StorageError::bad_input("Empty shard keys selection"))?;
+        result.deduplicated = deduplicated;
+        Ok(result)
     }
 
     /// # Cancel safety
@@ -462,6 +570,7 @@ impl TableOfContent {
 
         if operation.operation.is_write_operation() {
             self.check_write_lock()?;
+            self.check_free_disk_space()?;
         }
 
         // TODO: `debug_assert(operation.clock_tag.is_none())` for `_update_shard_keys`/`update_from_client`!?
@@ -491,7 +600,8 @@ impl TableOfContent {
                 }
             }
 
-            ShardSelectorInternal::ShardKey(shard_key) => {
+            ShardSelectorInternal::ShardKey(shard_key)
+            | ShardSelectorInternal::TenantScope(shard_key) => {
                 collection
                     .update_from_client(operation.operation, wait, ordering, Some(shard_key))
                     .await?
@@ -517,4 +627,52 @@ impl TableOfContent {
 
         Ok(res)
     }
+
+    /// Delete all points of the collection in all shards
+    ///
+    /// Configuration, payload indexes and aliases of the collection are kept. Segments of every
+    /// replica are replaced with an empty segment, instead of deleting points one by one.
+    pub async fn clear_collection(
+        &self,
+        collection_name: &str,
+        wait: bool,
+        ordering: WriteOrdering,
+        access: Access,
+    ) -> StorageResult<UpdateResult> {
+        // Restricted access would only clear the points it can see
+        access
+            .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+
+        let operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(Filter::default()),
+        );
+
+        self.update(
+            collection_name,
+            OperationWithClockTag::from(operation),
+            wait,
+            ordering,
+            ShardSelectorInternal::All,
+            access,
+        )
+        .await
+    }
+
+    /// Dry run of an update operation
+    ///
+    /// Validates the operation like [`TableOfContent::update`] would, without applying it.
+    ///
+    /// # Result
+    ///
+    /// All errors the operation would fail with, empty if it is valid
+    pub async fn validate_update(
+        &self,
+        collection_name: &str,
+        mut operation: CollectionUpdateOperations,
+        access: Access,
+    ) -> StorageResult<Vec<UpdateValidationError>> {
+        let collection_pass = access.check_point_op(collection_name, &mut operation)?;
+        let collection = self.get_collection(&collection_pass).await?;
+        Ok(collection.validate_update(&operation).await?)
+    }
 }
@@ -4,7 +4,7 @@ use std::time::Duration;
 
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse, FacetValue};
 
 use super::TableOfContent;
 use crate::content_manager::errors::StorageResult;
@@ -41,4 +41,20 @@ impl TableOfContent {
 
         Ok(res)
     }
+
+    pub async fn distinct_values_internal(
+        &self,
+        collection_name: &str,
+        request: DistinctValuesParams,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> StorageResult<Vec<FacetValue>> {
+        let collection = self.get_collection_unchecked(collection_name).await?;
+
+        let res = collection
+            .distinct_values(request, shard_selection, None, timeout)
+            .await?;
+
+        Ok(res)
+    }
 }
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::collection::recall_monitor::RecallMonitorConfig;
+
+use super::TableOfContent;
+
+impl TableOfContent {
+    /// Periodically measure recall of approximate search in all collections
+    ///
+    /// Runs forever, intended to be spawned as a background task.
+    pub async fn run_recall_monitor(self: Arc<Self>, config: RecallMonitorConfig) {
+        let interval = Duration::from_secs(config.interval_sec);
+        loop {
+            tokio::time::sleep(interval).await;
+
+            let collection_names: Vec<_> = self.collections.read().await.keys().cloned().collect();
+            for collection_name in collection_names {
+                let Ok(collection) = self.get_collection_unchecked(&collection_name).await else {
+                    // Collection was removed in the meantime
+                    continue;
+                };
+                match collection.sample_recall(&config).await {
+                    Ok(Some(recall)) => {
+                        log::debug!("Sampled recall of collection {collection_name}: {recall:.3}");
+                    }
+                    Ok(None) => {}
+                    Err(err) => {
+                        log::warn!(
+                            "Failed to sample recall of collection {collection_name}: {err}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
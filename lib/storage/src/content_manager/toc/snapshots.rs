@@ -2,9 +2,11 @@ use std::path::{Path, PathBuf};
 
 use collection::common::snapshots_manager::SnapshotStorageManager;
 use collection::operations::snapshot_ops::SnapshotDescription;
+use collection::operations::types::CollectionError;
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::transfer::{ShardTransfer, ShardTransferMethod};
+use segment::json_path::JsonPath;
 
 use super::TableOfContent;
 use crate::content_manager::consensus::operation_sender::OperationSender;
@@ -52,19 +54,84 @@ impl TableOfContent {
         Ok(snapshots_path)
     }
 
+    /// Create a snapshot of all shards of the collection.
+    ///
+    /// Shards are snapshotted one by one, so the snapshot takes a single slot of the shard
+    /// snapshot limit, see [`Self::create_shard_snapshot`].
     pub async fn create_snapshot<'a>(
         &self,
         collection: &CollectionPass<'a>,
+        exclude_payload_keys: &[JsonPath],
     ) -> Result<SnapshotDescription, StorageError> {
+        self.check_free_disk_space()?;
+        // Wait for a free slot before locking the collection, queued requests must not block it
+        let _permit = self.acquire_shard_snapshot_permit().await;
         let collection = self.get_collection(collection).await?;
         // We want to use temp dir inside the temp_path (storage if not specified), because it is possible, that
         // snapshot directory is mounted as network share and multiple writes to it could be slow
         let temp_dir = self.optional_temp_or_storage_temp_path()?;
         Ok(collection
-            .create_snapshot(&temp_dir, self.this_peer_id)
+            .create_snapshot(&temp_dir, self.this_peer_id, exclude_payload_keys)
             .await?)
     }
 
+    /// Create a snapshot of a single shard.
+    ///
+    /// Waits for a free slot if too many shard snapshots are being created on this node.
+    /// Can be cancelled with [`Self::cancel_shard_snapshot`] while queued or running.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    pub async fn create_shard_snapshot(
+        &self,
+        collection: &CollectionPass<'_>,
+        shard_id: ShardId,
+    ) -> Result<SnapshotDescription, StorageError> {
+        self.check_free_disk_space()?;
+
+        let handle = self
+            .shard_snapshots_in_progress
+            .register(collection.name(), shard_id);
+
+        // Wait for a free slot before locking the collection, queued requests must not block it
+        let _permit = self.acquire_shard_snapshot_permit().await;
+
+        if handle.is_cancelled() {
+            return Err(CollectionError::Cancelled {
+                description: format!(
+                    "Snapshot creation of shard {shard_id} in collection {collection} was cancelled"
+                ),
+            }
+            .into());
+        }
+
+        let collection = self.get_collection(collection).await?;
+        let temp_dir = self.optional_temp_or_snapshot_temp_path()?;
+        Ok(collection
+            .create_shard_snapshot(shard_id, &temp_dir, handle.stopped())
+            .await?)
+    }
+
+    /// Cancel all queued and running snapshot creations of the given shard on this node.
+    ///
+    /// Returns `true` if any snapshot creation was cancelled.
+    pub fn cancel_shard_snapshot(
+        &self,
+        collection: &CollectionPass<'_>,
+        shard_id: ShardId,
+    ) -> bool {
+        let cancelled = self
+            .shard_snapshots_in_progress
+            .cancel(collection.name(), shard_id);
+        if cancelled > 0 {
+            log::info!(
+                "Cancelled {cancelled} snapshot creations of shard {shard_id} in collection {collection}"
+            );
+        }
+        cancelled > 0
+    }
+
     pub fn send_set_replica_state_proposal(
         &self,
         collection_name: String,
@@ -130,6 +197,7 @@ impl TableOfContent {
                 to: to_peer,
                 sync,
                 method,
+                filter: None,
             };
             let operation = ConsensusOperations::start_transfer(collection_name, transfer_request);
             proposal_sender.send(operation)?;
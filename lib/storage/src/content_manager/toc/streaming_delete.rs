@@ -0,0 +1,196 @@
+use api::rest::ShardKeySelector;
+use collection::operations::point_ops::{PointOperations, WriteOrdering};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::{CountRequestInternal, ScrollRequestInternal};
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use segment::types::{Condition, Filter, HasIdCondition, WithPayloadInterface, WithVector};
+
+use super::TableOfContent;
+use crate::content_manager::errors::{StorageError, StorageResult};
+use crate::content_manager::streaming_delete::{
+    StreamingDeleteHandle, StreamingDeleteProgress, StreamingDeleteStatus,
+};
+use crate::rbac::{Access, AccessRequirements};
+
+impl TableOfContent {
+    /// Register a streaming delete of points matching `filter`, and estimate how many points it
+    /// deletes.
+    ///
+    /// The delete itself is executed by [`TableOfContent::run_streaming_delete`] with the returned
+    /// handle, usually in background.
+    pub async fn register_streaming_delete(
+        &self,
+        collection_name: &str,
+        filter: &Filter,
+        shard_key: Option<ShardKeySelector>,
+        access: Access,
+    ) -> StorageResult<StreamingDeleteHandle> {
+        // Check early that the caller is allowed to delete by this filter
+        let mut operation = CollectionUpdateOperations::PointOperation(
+            PointOperations::DeletePointsByFilter(filter.clone()),
+        );
+        access.check_point_op(collection_name, &mut operation)?;
+
+        let estimate = self
+            .count(
+                collection_name,
+                CountRequestInternal {
+                    filter: Some(filter.clone()),
+                    exact: false,
+                },
+                None,
+                None,
+                read_shard_selection(shard_key),
+                access,
+            )
+            .await?;
+
+        Ok(self
+            .streaming_deletes
+            .register(collection_name, estimate.count))
+    }
+
+    /// Delete points matching `filter` in batches of `batch_size`, reporting progress to `handle`.
+    ///
+    /// Stops after the current batch once the delete is cancelled.
+    /// Only points still matching the filter at the time their batch is deleted are deleted.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn run_streaming_delete(
+        &self,
+        collection_name: &str,
+        filter: Filter,
+        shard_key: Option<ShardKeySelector>,
+        batch_size: usize,
+        ordering: WriteOrdering,
+        access: Access,
+        handle: StreamingDeleteHandle,
+    ) {
+        let result = self
+            .delete_in_batches(
+                collection_name,
+                filter,
+                shard_key,
+                batch_size,
+                ordering,
+                access,
+                &handle,
+            )
+            .await;
+
+        match result {
+            Ok(()) if handle.is_cancelled() => {
+                handle.finish(StreamingDeleteStatus::Cancelled, None)
+            }
+            Ok(()) => handle.finish(StreamingDeleteStatus::Completed, None),
+            Err(err) => {
+                log::error!("Streaming delete in collection {collection_name} failed: {err}");
+                handle.finish(StreamingDeleteStatus::Failed, Some(err.to_string()));
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn delete_in_batches(
+        &self,
+        collection_name: &str,
+        filter: Filter,
+        shard_key: Option<ShardKeySelector>,
+        batch_size: usize,
+        ordering: WriteOrdering,
+        access: Access,
+        handle: &StreamingDeleteHandle,
+    ) -> StorageResult<()> {
+        let mut offset = None;
+
+        while !handle.is_cancelled() {
+            let batch = self
+                .scroll(
+                    collection_name,
+                    ScrollRequestInternal {
+                        offset,
+                        limit: Some(batch_size),
+                        filter: Some(filter.clone()),
+                        with_payload: Some(WithPayloadInterface::Bool(false)),
+                        with_vector: WithVector::Bool(false),
+                        order_by: None,
+                    },
+                    None,
+                    None,
+                    read_shard_selection(shard_key.clone()),
+                    access.clone(),
+                )
+                .await?;
+
+            if batch.points.is_empty() {
+                break;
+            }
+
+            let deleted = batch.points.len();
+            let ids = batch.points.into_iter().map(|point| point.id).collect();
+            let batch_filter = Filter::new_must(Condition::HasId(HasIdCondition { has_id: ids }))
+                .merge_owned(filter.clone());
+            let operation = CollectionUpdateOperations::PointOperation(
+                PointOperations::DeletePointsByFilter(batch_filter),
+            );
+
+            self.update(
+                collection_name,
+                OperationWithClockTag::from(operation),
+                true,
+                ordering,
+                ShardSelectorInternal::from(shard_key.clone()),
+                access.clone(),
+            )
+            .await?;
+            handle.add_deleted(deleted);
+
+            match batch.next_page_offset {
+                Some(next_offset) => offset = Some(next_offset),
+                None => break,
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Progress of a running or recently finished streaming delete.
+    pub fn streaming_delete_progress(
+        &self,
+        collection_name: &str,
+        id: u64,
+        access: &Access,
+    ) -> StorageResult<StreamingDeleteProgress> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new())?;
+        self.streaming_deletes
+            .progress(collection_pass.name(), id)
+            .ok_or_else(|| streaming_delete_not_found(collection_name, id))
+    }
+
+    /// Cancel a streaming delete, points deleted so far remain deleted.
+    pub fn cancel_streaming_delete(
+        &self,
+        collection_name: &str,
+        id: u64,
+        access: &Access,
+    ) -> StorageResult<StreamingDeleteProgress> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().write())?;
+        self.streaming_deletes
+            .cancel(collection_pass.name(), id)
+            .ok_or_else(|| streaming_delete_not_found(collection_name, id))
+    }
+}
+
+fn read_shard_selection(shard_key: Option<ShardKeySelector>) -> ShardSelectorInternal {
+    match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    }
+}
+
+fn streaming_delete_not_found(collection_name: &str, id: u64) -> StorageError {
+    StorageError::not_found(format!(
+        "Streaming delete {id} not found in collection {collection_name}"
+    ))
+}
@@ -2,13 +2,14 @@ use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use api::rest::ShardKeySelector;
 use collection::config::ShardingMethod;
 use common::defaults::CONSENSUS_META_OP_WAIT;
 use segment::types::default_shard_number_per_node_const;
 
-use crate::content_manager::collection_meta_ops::AliasOperations;
+use crate::content_manager::collection_meta_ops::{AliasOperations, CreateShardKey};
 use crate::content_manager::shard_distribution::ShardDistributionProposal;
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 use crate::{
     ClusterStatus, CollectionMetaOperations, ConsensusOperations, ConsensusStateRef, StorageError,
     TableOfContent,
@@ -69,27 +70,34 @@ impl Dispatcher {
                     if !op.is_distribution_set() {
                         match op.create_collection.sharding_method.unwrap_or_default() {
                             ShardingMethod::Auto => {
-                                // Suggest even distribution of shards across nodes
-                                let number_of_peers = state.0.peer_count();
-
-                                let shard_nr_per_node = self
-                                    .toc
-                                    .storage_config
-                                    .collection
-                                    .as_ref()
-                                    .map(|i| i.shard_number_per_node)
-                                    .unwrap_or(default_shard_number_per_node_const());
-
-                                let suggested_shard_nr = number_of_peers as u32 * shard_nr_per_node;
-
-                                let shard_distribution = self
-                                    .toc
-                                    .suggest_shard_distribution(
-                                        &op,
-                                        NonZeroU32::new(suggested_shard_nr)
-                                            .expect("Peer count should be always >= 1"),
-                                    )
-                                    .await;
+                                // Use explicit placement if specified, otherwise suggest even
+                                // distribution of shards across nodes
+                                let shard_distribution =
+                                    match self.toc.shard_placement_distribution(&op)? {
+                                        Some(shard_distribution) => shard_distribution,
+                                        None => {
+                                            let number_of_peers = state.0.peer_count();
+
+                                            let shard_nr_per_node = self
+                                                .toc
+                                                .storage_config
+                                                .collection
+                                                .as_ref()
+                                                .map(|i| i.shard_number_per_node)
+                                                .unwrap_or(default_shard_number_per_node_const());
+
+                                            let suggested_shard_nr =
+                                                number_of_peers as u32 * shard_nr_per_node;
+
+                                            self.toc
+                                                .suggest_shard_distribution(
+                                                    &op,
+                                                    NonZeroU32::new(suggested_shard_nr)
+                                                        .expect("Peer count should be always >= 1"),
+                                                )
+                                                .await
+                                        }
+                                    };
 
                                 // Expect all replicas to become active eventually
                                 for (shard_id, peer_ids) in &shard_distribution.distribution {
@@ -107,6 +115,12 @@ impl Dispatcher {
                                 op.set_distribution(shard_distribution);
                             }
                             ShardingMethod::Custom => {
+                                if op.create_collection.shard_placement.is_some() {
+                                    return Err(StorageError::bad_input(
+                                        "Shard placement can only be specified for auto sharding",
+                                    ));
+                                }
+
                                 // If custom sharding is used - we don't create any shards in advance
                                 let empty_distribution = ShardDistributionProposal::empty();
                                 op.set_distribution(empty_distribution);
@@ -196,6 +210,99 @@ impl Dispatcher {
         }
     }
 
+    /// Create shard keys targeted by writes, which don't exist yet
+    ///
+    /// Only done for collections with `auto_create_shard_keys` enabled, otherwise the writes are
+    /// left to be rejected for unknown shard keys. Shards of the new keys are distributed evenly
+    /// across all peers. Creating shard keys requires the same access as creating them
+    /// explicitly.
+    pub async fn ensure_shard_keys<'a>(
+        &self,
+        collection_name: &str,
+        shard_key_selectors: impl IntoIterator<Item = &'a ShardKeySelector>,
+        access: &Access,
+        wait_timeout: Option<Duration>,
+    ) -> Result<(), StorageError> {
+        let mut shard_keys = Vec::new();
+        for shard_key_selector in shard_key_selectors {
+            let selected = match shard_key_selector {
+                ShardKeySelector::ShardKey(shard_key) => std::slice::from_ref(shard_key),
+                ShardKeySelector::ShardKeys(shard_keys) => shard_keys.as_slice(),
+                ShardKeySelector::TenantScope(scope) => std::slice::from_ref(&scope.tenant),
+            };
+            for shard_key in selected {
+                if !shard_keys.contains(&shard_key) {
+                    shard_keys.push(shard_key);
+                }
+            }
+        }
+        if shard_keys.is_empty() {
+            return Ok(());
+        }
+
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().write())?;
+        let collection = self.toc.get_collection(&collection_pass).await?;
+        let state = collection.state().await;
+        drop(collection);
+
+        let params = &state.config.params;
+        if !params.auto_create_shard_keys
+            || params.sharding_method.unwrap_or_default() != ShardingMethod::Custom
+        {
+            return Ok(());
+        }
+
+        let peers: Vec<_> = match &self.consensus_state {
+            Some(consensus_state) => consensus_state
+                .persistent
+                .read()
+                .peer_address_by_id
+                .read()
+                .keys()
+                .copied()
+                .collect(),
+            None => vec![self.toc.this_peer_id],
+        };
+
+        for shard_key in shard_keys {
+            if state.shards_key_mapping.contains_key(shard_key) {
+                continue;
+            }
+
+            let distribution = ShardDistributionProposal::new(
+                params.shard_number,
+                params.shard_key_replication_factor(Some(shard_key)),
+                &peers,
+            );
+            let placement = distribution
+                .distribution
+                .into_iter()
+                .map(|(_, peers)| peers)
+                .collect();
+
+            let operation = CollectionMetaOperations::CreateShardKey(CreateShardKey {
+                collection_name: collection_pass.name().to_string(),
+                shard_key: shard_key.clone(),
+                placement,
+            });
+
+            let result = self
+                .submit_collection_meta_op(operation, access.clone(), wait_timeout)
+                .await;
+
+            if let Err(err) = result {
+                // Shard key may have been created by a concurrent write
+                let collection = self.toc.get_collection(&collection_pass).await?;
+                if !collection.get_shard_keys().await.contains(shard_key) {
+                    return Err(err);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn cluster_status(&self) -> ClusterStatus {
         match self.consensus_state.as_ref() {
             Some(state) => state.cluster_status(),
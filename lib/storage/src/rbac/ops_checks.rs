@@ -17,7 +17,7 @@ use collection::operations::universal_query::collection_query::{
 };
 use collection::operations::vector_ops::VectorOperations;
 use collection::operations::CollectionUpdateOperations;
-use segment::data_types::facets::FacetParams;
+use segment::data_types::facets::{DistinctValuesParams, FacetParams};
 use segment::types::{Condition, ExtendedPointId, FieldCondition, Filter, Match, Payload};
 
 use super::{
@@ -386,6 +386,25 @@ impl CheckableCollectionOperation for FacetParams {
     }
 }
 
+impl CheckableCollectionOperation for DistinctValuesParams {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> StorageResult<()> {
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for CollectionSearchMatrixRequest {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -430,7 +449,10 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
     ) -> Result<(), StorageError> {
         match self {
             CollectionUpdateOperations::PointOperation(op) => match op {
-                PointOperations::UpsertPoints(_) => {
+                PointOperations::UpsertPoints(_)
+                | PointOperations::InsertPointsIfAbsent(_)
+                | PointOperations::UpsertPointsIfVersion(_)
+                | PointOperations::UpsertPointsDedup(_) => {
                     view.check_whole_access()?;
                 }
                 PointOperations::DeletePoints { ids } => {
@@ -659,11 +681,13 @@ mod tests_ops {
     };
     use collection::operations::payload_ops::PayloadOpsDiscriminants;
     use collection::operations::point_ops::{
-        Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        Batch, ConditionalUpsert, DedupUpsert, PointInsertOperationsInternal,
+        PointInsertOperationsInternalDiscriminants, PointOperationsDiscriminants, PointStruct,
+        PointSyncOperation, PointVersion,
     };
     use collection::operations::query_enum::QueryEnum;
     use collection::operations::types::UsingVector;
+    use collection::operations::vector_dedup::VectorDedup;
     use collection::operations::vector_ops::{
         PointVectors, UpdateVectorsOp, VectorOperationsDiscriminants,
     };
@@ -1159,6 +1183,51 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::InsertPointsIfAbsent => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::InsertPointsIfAbsent(
+                        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                        }]),
+                    ),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
+            PointOperationsDiscriminants::UpsertPointsIfVersion => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsIfVersion(ConditionalUpsert {
+                        points: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                        }]),
+                        expected_versions: vec![PointVersion {
+                            id: ExtendedPointId::NumId(12345),
+                            version: 1,
+                        }],
+                        version_key: "version".parse().unwrap(),
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
+            PointOperationsDiscriminants::UpsertPointsDedup => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsDedup(DedupUpsert {
+                        points: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                        }]),
+                        policy: VectorDedup::Skip,
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
@@ -1,8 +1,11 @@
 use std::collections::HashMap;
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::collection::recall_monitor::RecallMonitorConfig;
+use collection::common::segment_volumes::SegmentVolumes;
 use collection::common::snapshots_manager::SnapShotsConfig;
 use collection::config::WalConfig;
 use collection::operations::config_diff::OptimizersConfigDiff;
@@ -43,6 +46,38 @@ pub struct PerformanceConfig {
     pub incoming_shard_transfers_limit: Option<usize>,
     #[serde(default = "default_io_shard_transfers_limit")]
     pub outgoing_shard_transfers_limit: Option<usize>,
+    /// Maximum number of shard snapshots created concurrently on this node.
+    /// Excess requests are queued. If not defined - no limit is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_shard_snapshots: Option<usize>,
+    /// Maximum number of update operations per shard which are accepted, but not yet applied.
+    /// Further updates wait for a free slot. If not defined - no limit is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_in_flight_updates: Option<usize>,
+    /// Maximum number of concurrent single point upserts to a shard coalesced into one
+    /// operation. If not defined - upserts are not coalesced.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upsert_coalesce_max_batch: Option<usize>,
+    /// Maximum time in milliseconds a single point upsert waits for others to be coalesced with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upsert_coalesce_max_delay_ms: Option<u64>,
+    /// Maximum random delay in milliseconds before optimizations of a shard are started, to
+    /// stagger optimizations of many collections triggered at once. If not defined - no delay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub optimizer_jitter_ms: Option<u64>,
+    /// Maximum number of collections loaded, created or deleted concurrently on this node.
+    /// If not defined - these operations run one at a time.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_collection_operations: Option<usize>,
+    /// Maximum time in seconds to load a single local shard on startup. Shards not loaded in time
+    /// are replaced by a dummy shard if `handle_collection_load_errors` is enabled, otherwise
+    /// startup fails. If not defined - no limit is applied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_load_timeout_sec: Option<u64>,
+    /// Maximum number of points cached per local shard for repeated retrieval by id.
+    /// Cached points are invalidated when updates are applied. If not defined - retrieved points are not cached.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retrieve_cache_size: Option<usize>,
 }
 
 const fn default_io_shard_transfers_limit() -> Option<usize> {
@@ -62,6 +97,10 @@ pub struct StorageConfig {
     #[validate(length(min = 1))]
     #[serde(default)]
     pub temp_path: Option<String>,
+    /// Additional storage volumes, e.g. mount points of other disks, to spread segments across.
+    /// New segments are created on the volume with the most free space, `storage_path` included.
+    #[serde(default)]
+    pub segment_volumes: Vec<String>,
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
     #[validate(nested)]
@@ -82,6 +121,11 @@ pub struct StorageConfig {
     pub update_queue_size: Option<usize>,
     #[serde(default)]
     pub handle_collection_load_errors: bool,
+    /// If enabled, WAL operations which can't be read or applied when loading a shard are
+    /// quarantined: skipped and recorded in the shard directory, instead of failing the load. The
+    /// replica is then recovered from another replica, if there is one.
+    #[serde(default)]
+    pub lenient_wal_recovery: bool,
     #[serde(default)]
     pub async_scorer: bool,
     /// If provided - qdrant will start in recovery mode, which means that it will not accept any new data.
@@ -97,14 +141,43 @@ pub struct StorageConfig {
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// Fraction of search requests to each collection which are recorded for debugging, from 0 to 1.
+    /// Recently recorded requests are reported in collection telemetry. Disabled if not set.
+    #[serde(default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub search_log_sample_rate: Option<f64>,
+    /// Periodically measure recall of approximate search in each collection, by comparing it to
+    /// exact search on sampled queries. Measured recall is reported in collection telemetry.
+    /// Disabled if not set.
+    #[serde(default)]
+    #[validate(nested)]
+    pub recall_monitor: Option<RecallMonitorConfig>,
+    /// Minimum free space in megabytes on the storage disk. If free space drops below it,
+    /// operations which add data and snapshot creation are rejected, to not run out of space
+    /// in the middle of an operation. Reads and deletes are still allowed. Disabled if not set.
+    #[serde(default)]
+    pub min_free_disk_mb: Option<u64>,
+    /// Maximum number of collections in the storage. Creation of more collections is rejected.
+    /// Unlimited if not set.
+    #[serde(default)]
+    pub max_collections: Option<usize>,
 }
 
 impl StorageConfig {
     pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
+            self.performance.max_in_flight_updates,
+            self.performance.upsert_coalesce_max_batch,
+            self.performance
+                .upsert_coalesce_max_delay_ms
+                .map(Duration::from_millis),
+            self.performance
+                .optimizer_jitter_ms
+                .map(Duration::from_millis),
             self.node_type,
             self.handle_collection_load_errors,
+            self.lenient_wal_recovery,
             self.recovery_mode.clone(),
             self.performance
                 .search_timeout_sec
@@ -116,8 +189,18 @@ impl StorageConfig {
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.segment_volumes(),
+            self.search_log_sample_rate,
+            self.performance.retrieve_cache_size,
+            self.performance
+                .shard_load_timeout_sec
+                .map(Duration::from_secs),
         )
     }
+
+    pub fn segment_volumes(&self) -> SegmentVolumes {
+        SegmentVolumes::new(self.segment_volumes.iter().map(PathBuf::from).collect())
+    }
 }
 
 fn default_snapshots_path() -> String {
@@ -35,6 +35,7 @@ fn test_alias_operation() {
             .to_string(),
         snapshots_config: Default::default(),
         temp_path: None,
+        segment_volumes: vec![],
         on_disk_payload: false,
         optimizers: OptimizersConfig {
             deleted_threshold: 0.5,
@@ -43,6 +44,7 @@ fn test_alias_operation() {
             max_segment_size: None,
             memmap_threshold: Some(100),
             indexing_threshold: Some(100),
+            indexing_min_vector_number: None,
             flush_interval_sec: 2,
             max_optimization_threads: Some(2),
         },
@@ -56,18 +58,31 @@ fn test_alias_operation() {
             search_timeout_sec: None,
             incoming_shard_transfers_limit: Some(1),
             outgoing_shard_transfers_limit: Some(1),
+            max_concurrent_shard_snapshots: None,
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay_ms: None,
+            optimizer_jitter_ms: None,
+            max_concurrent_collection_operations: None,
+            shard_load_timeout_sec: None,
+            retrieve_cache_size: None,
         },
         hnsw_index: Default::default(),
         mmap_advice: madvise::Advice::Random,
         node_type: Default::default(),
         update_queue_size: Default::default(),
         handle_collection_load_errors: false,
+        lenient_wal_recovery: false,
         recovery_mode: None,
         async_scorer: false,
         update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
         // update_concurrency: None,
         shard_transfer_method: None,
         collection: None,
+        search_log_sample_rate: None,
+        recall_monitor: None,
+        min_free_disk_mb: None,
+        max_collections: None,
     };
 
     let search_runtime = Runtime::new().unwrap();
@@ -108,11 +123,17 @@ fn test_alias_operation() {
                         shard_number: Some(1),
                         on_disk_payload: None,
                         replication_factor: None,
+                        shard_placement: None,
                         write_consistency_factor: None,
                         init_from: None,
                         quantization_config: None,
                         sharding_method: None,
                         strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
                     },
                 )),
                 FULL_ACCESS.clone(),
@@ -0,0 +1,194 @@
+use std::sync::Arc;
+
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CountRequestInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{
+    CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
+};
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::json_path::JsonPath;
+use segment::types::{Distance, Payload, PayloadFieldSchema, PayloadSchemaType};
+use serde_json::json;
+use storage::content_manager::collection_meta_ops::{
+    ChangeAliasesOperation, CollectionMetaOperations, CreateAlias, CreateCollection,
+    CreateCollectionOperation,
+};
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+use crate::test_storage_config;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+const NUM_POINTS: u64 = 500;
+const NUM_SHARDS: u32 = 2;
+
+#[test]
+fn test_clear_collection() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = test_storage_config(storage_dir.path());
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(NUM_SHARDS),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    handle
+        .block_on(dispatcher.submit_collection_meta_op(
+            CollectionMetaOperations::ChangeAliases(ChangeAliasesOperation {
+                actions: vec![CreateAlias {
+                    collection_name: "test".to_string(),
+                    alias_name: "test_alias".to_string(),
+                }
+                .into()],
+            }),
+            FULL_ACCESS.clone(),
+            None,
+        ))
+        .unwrap();
+
+    let update = |operation: CollectionUpdateOperations| {
+        handle
+            .block_on(toc.update(
+                "test",
+                OperationWithClockTag::from(operation),
+                true,
+                WriteOrdering::Weak,
+                ShardSelectorInternal::All,
+                FULL_ACCESS.clone(),
+            ))
+            .unwrap();
+    };
+
+    update(CollectionUpdateOperations::FieldIndexOperation(
+        FieldIndexOperations::CreateIndex(CreateIndex {
+            field_name: JsonPath::new("group"),
+            field_schema: Some(PayloadFieldSchema::FieldType(PayloadSchemaType::Integer)),
+        }),
+    ));
+
+    let points = (0..NUM_POINTS)
+        .map(|id| {
+            let payload: Payload = serde_json::from_value(json!({"group": id % 4})).unwrap();
+            PointStruct {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![id as f32 + 1.0; 10]).into(),
+                payload: Some(payload),
+            }
+        })
+        .collect();
+    update(CollectionUpdateOperations::PointOperation(
+        PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+    ));
+
+    let count = || {
+        handle
+            .block_on(toc.count(
+                "test",
+                CountRequestInternal {
+                    filter: None,
+                    exact: true,
+                },
+                None,
+                None,
+                ShardSelectorInternal::All,
+                FULL_ACCESS.clone(),
+            ))
+            .unwrap()
+            .count
+    };
+    assert_eq!(count(), NUM_POINTS as usize);
+
+    let info = || {
+        handle.block_on(async {
+            let collection = toc
+                .get_collection(
+                    &FULL_ACCESS
+                        .check_collection_access("test_alias", AccessRequirements::new())
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+            collection.info(&ShardSelectorInternal::All).await.unwrap()
+        })
+    };
+    let info_before = info();
+
+    handle
+        .block_on(toc.clear_collection(
+            "test_alias",
+            true,
+            WriteOrdering::Weak,
+            FULL_ACCESS.clone(),
+        ))
+        .unwrap();
+
+    assert_eq!(count(), 0);
+
+    // Collection is still reachable by its alias, with the same config and payload index
+    let info_after = info();
+    assert_eq!(info_after.config, info_before.config);
+    assert_eq!(info_after.config.params.shard_number.get(), NUM_SHARDS);
+    assert!(info_after
+        .payload_schema
+        .contains_key(&JsonPath::new("group")));
+}
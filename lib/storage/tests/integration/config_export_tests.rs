@@ -0,0 +1,204 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+use collection::config::{CollectionConfig, WalConfig};
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use memory::madvise;
+use segment::types::{
+    Distance, HnswConfig, QuantizationConfig, ScalarQuantization, ScalarQuantizationConfig,
+    ScalarType,
+};
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::consensus::operation_sender::OperationSender;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_export_config_and_recreate() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        storage_path: storage_dir.path().to_str().unwrap().to_string(),
+        snapshots_path: storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        snapshots_config: Default::default(),
+        temp_path: None,
+        segment_volumes: vec![],
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: Some(100),
+            indexing_min_vector_number: None,
+            flush_interval_sec: 2,
+            max_optimization_threads: Some(2),
+        },
+        optimizers_overwrite: None,
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            optimizer_cpu_budget: 0,
+            update_rate_limit: None,
+            search_timeout_sec: None,
+            incoming_shard_transfers_limit: Some(1),
+            outgoing_shard_transfers_limit: Some(1),
+            max_concurrent_shard_snapshots: None,
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay_ms: None,
+            optimizer_jitter_ms: None,
+            max_concurrent_collection_operations: None,
+            shard_load_timeout_sec: None,
+            retrieve_cache_size: None,
+        },
+        hnsw_index: Default::default(),
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        handle_collection_load_errors: false,
+        lenient_wal_recovery: false,
+        recovery_mode: None,
+        async_scorer: false,
+        update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+        shard_transfer_method: None,
+        collection: None,
+        search_log_sample_rate: None,
+        recall_monitor: None,
+        min_free_disk_mb: None,
+        max_collections: None,
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
+    let propose_operation_sender = OperationSender::new(propose_sender);
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        Some(propose_operation_sender),
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    // Configure everything differently from the storage defaults
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "source".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Euclid)
+                            .with_on_disk(true)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: Some(
+                            HnswConfig {
+                                m: 32,
+                                ef_construct: 200,
+                                payload_m: Some(8),
+                                use_heuristic: None,
+                                ..Default::default()
+                            }
+                            .into(),
+                        ),
+                        wal_config: Some(
+                            WalConfig {
+                                wal_capacity_mb: 2,
+                                wal_segments_ahead: 1,
+                                fsync_policy: Default::default(),
+                                wal_disabled: false,
+                            }
+                            .into(),
+                        ),
+                        optimizers_config: Some(
+                            OptimizersConfig {
+                                indexing_threshold: Some(500),
+                                ..config.optimizers.clone()
+                            }
+                            .into(),
+                        ),
+                        shard_number: Some(2),
+                        on_disk_payload: Some(true),
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: Some(QuantizationConfig::Scalar(ScalarQuantization {
+                            scalar: ScalarQuantizationConfig {
+                                r#type: ScalarType::Int8,
+                                quantile: Some(0.99),
+                                always_ram: Some(true),
+                            },
+                        })),
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    let pass = |name: &'static str| {
+        FULL_ACCESS
+            .check_collection_access(name, AccessRequirements::new())
+            .unwrap()
+    };
+
+    let source_config = handle.block_on(toc.export_config(&pass("source"))).unwrap();
+
+    // Config must survive a round trip through JSON
+    let json = serde_json::to_string(&source_config).unwrap();
+    let imported_config: CollectionConfig = serde_json::from_str(&json).unwrap();
+
+    handle
+        .block_on(dispatcher.submit_collection_meta_op(
+            CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                "copy".to_string(),
+                imported_config.into(),
+            )),
+            FULL_ACCESS.clone(),
+            None,
+        ))
+        .unwrap();
+
+    let copy_config = handle.block_on(toc.export_config(&pass("copy"))).unwrap();
+
+    assert_eq!(copy_config, source_config);
+}
@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CountRequestInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use storage::types::StorageConfig;
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+use crate::test_storage_config;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_writes_rejected_on_low_free_disk() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        // More free space than any disk has
+        min_free_disk_mb: Some(u64::MAX / (1024 * 1024)),
+        ..test_storage_config(storage_dir.path())
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    let update = |operation: CollectionUpdateOperations| {
+        handle.block_on(toc.update(
+            "test",
+            OperationWithClockTag::from(operation),
+            true,
+            WriteOrdering::Weak,
+            ShardSelectorInternal::All,
+            FULL_ACCESS.clone(),
+        ))
+    };
+
+    let points = (0..10)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32 + 1.0; 10]).into(),
+            payload: None,
+        })
+        .collect();
+    let result = update(CollectionUpdateOperations::PointOperation(
+        PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+    ));
+    assert!(
+        matches!(result, Err(StorageError::PreconditionFailed { ref description }) if description.contains("free disk space")),
+        "{result:?}",
+    );
+
+    // Deletes are still allowed, to free some space
+    update(CollectionUpdateOperations::PointOperation(
+        PointOperations::DeletePoints {
+            ids: vec![1.into(), 2.into()],
+        },
+    ))
+    .unwrap();
+
+    // Reads are still allowed
+    let count = handle
+        .block_on(toc.count(
+            "test",
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+            },
+            None,
+            None,
+            ShardSelectorInternal::All,
+            FULL_ACCESS.clone(),
+        ))
+        .unwrap()
+        .count;
+    assert_eq!(count, 0);
+
+    // Snapshots would take space as well
+    let pass = FULL_ACCESS
+        .check_collection_access("test", AccessRequirements::new())
+        .unwrap();
+    let result = handle.block_on(toc.create_snapshot(&pass, &[]));
+    assert!(
+        matches!(result, Err(StorageError::PreconditionFailed { .. })),
+        "{result:?}",
+    );
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    ChangeAliasesOperation, CollectionMetaOperations, CreateAlias, CreateCollection,
+    CreateCollectionOperation,
+};
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+use crate::test_storage_config;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_list_collections_with_info() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = test_storage_config(storage_dir.path());
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    let submit = |operation: CollectionMetaOperations| {
+        handle
+            .block_on(dispatcher.submit_collection_meta_op(operation, FULL_ACCESS.clone(), None))
+            .unwrap();
+    };
+
+    for name in ["first", "second"] {
+        submit(CollectionMetaOperations::CreateCollection(
+            CreateCollectionOperation::new(
+                name.to_string(),
+                CreateCollection {
+                    vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                        .build()
+                        .into(),
+                    sparse_vectors: None,
+                    hnsw_config: None,
+                    wal_config: None,
+                    optimizers_config: None,
+                    shard_number: Some(1),
+                    on_disk_payload: None,
+                    replication_factor: None,
+                    shard_placement: None,
+                    write_consistency_factor: None,
+                    init_from: None,
+                    quantization_config: None,
+                    sharding_method: None,
+                    strict_mode_config: None,
+                    payload_schema: None,
+                    default_search_params: None,
+                    tie_break: None,
+                    default_timeout: None,
+                    auto_create_shard_keys: None,
+                },
+            ),
+        ));
+    }
+
+    submit(CollectionMetaOperations::ChangeAliases(
+        ChangeAliasesOperation {
+            actions: vec![
+                CreateAlias {
+                    collection_name: "first".to_string(),
+                    alias_name: "first_b".to_string(),
+                }
+                .into(),
+                CreateAlias {
+                    collection_name: "first".to_string(),
+                    alias_name: "first_a".to_string(),
+                }
+                .into(),
+            ],
+        },
+    ));
+
+    let points = (0..5)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32 + 1.0; 10]).into(),
+            payload: None,
+        })
+        .collect();
+    handle
+        .block_on(toc.update(
+            "second",
+            OperationWithClockTag::from(CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+            )),
+            true,
+            WriteOrdering::Weak,
+            ShardSelectorInternal::Empty,
+            FULL_ACCESS.clone(),
+        ))
+        .unwrap();
+
+    let collections = handle
+        .block_on(toc.list_collections_with_info(&FULL_ACCESS))
+        .unwrap();
+
+    let names: Vec<_> = collections.iter().map(|(name, ..)| name.as_str()).collect();
+    assert_eq!(names, ["first", "second"]);
+
+    let (_, first_info, first_aliases) = &collections[0];
+    assert_eq!(first_info.points_count, Some(0));
+    assert_eq!(first_aliases, &["first_a", "first_b"]);
+
+    let (_, second_info, second_aliases) = &collections[1];
+    assert_eq!(second_info.points_count, Some(5));
+    assert!(second_aliases.is_empty());
+}
@@ -1 +1,76 @@
 mod alias_tests;
+mod clear_collection_tests;
+mod config_export_tests;
+mod free_disk_tests;
+mod list_collections_tests;
+mod max_collections_tests;
+mod shard_load_timeout_tests;
+mod shard_snapshot_tests;
+mod streaming_delete_tests;
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use collection::optimizers_builder::OptimizersConfig;
+use memory::madvise;
+use storage::types::{PerformanceConfig, StorageConfig};
+
+/// Storage config with small optimizer and performance limits, stored in `storage_path`
+///
+/// Tests override the fields they need, e.g.
+/// `StorageConfig { max_collections: Some(2), ..test_storage_config(path) }`.
+fn test_storage_config(storage_path: &Path) -> StorageConfig {
+    StorageConfig {
+        storage_path: storage_path.to_str().unwrap().to_string(),
+        snapshots_path: storage_path.join("snapshots").to_str().unwrap().to_string(),
+        snapshots_config: Default::default(),
+        temp_path: None,
+        segment_volumes: vec![],
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: Some(100),
+            indexing_min_vector_number: None,
+            flush_interval_sec: 2,
+            max_optimization_threads: Some(2),
+        },
+        optimizers_overwrite: None,
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            optimizer_cpu_budget: 0,
+            update_rate_limit: None,
+            search_timeout_sec: None,
+            incoming_shard_transfers_limit: Some(1),
+            outgoing_shard_transfers_limit: Some(1),
+            max_concurrent_shard_snapshots: None,
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay_ms: None,
+            optimizer_jitter_ms: None,
+            max_concurrent_collection_operations: None,
+            shard_load_timeout_sec: None,
+            retrieve_cache_size: None,
+        },
+        hnsw_index: Default::default(),
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        handle_collection_load_errors: false,
+        lenient_wal_recovery: false,
+        recovery_mode: None,
+        async_scorer: false,
+        update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+        shard_transfer_method: None,
+        collection: None,
+        search_log_sample_rate: None,
+        recall_monitor: None,
+        min_free_disk_mb: None,
+        max_collections: None,
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+    DeleteCollectionOperation,
+};
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+use storage::types::StorageConfig;
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+use crate::test_storage_config;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_max_collections() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        max_collections: Some(2),
+        ..test_storage_config(storage_dir.path())
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    let create_collection = |collection_name: &str| {
+        handle.block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    collection_name.to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+    };
+
+    assert!(create_collection("first").unwrap());
+    assert!(create_collection("second").unwrap());
+
+    match create_collection("third") {
+        Err(StorageError::BadInput { description }) => {
+            assert!(description.contains("maximum number"), "{description}")
+        }
+        result => panic!("Over the limit collection must be rejected, got {result:?}"),
+    }
+    assert_eq!(handle.block_on(toc.all_collections(&FULL_ACCESS)).len(), 2);
+
+    // Deleting a collection makes room for another one
+    handle
+        .block_on(dispatcher.submit_collection_meta_op(
+            CollectionMetaOperations::DeleteCollection(DeleteCollectionOperation(
+                "first".to_string(),
+            )),
+            FULL_ACCESS.clone(),
+            None,
+        ))
+        .unwrap();
+    assert!(create_collection("third").unwrap());
+}
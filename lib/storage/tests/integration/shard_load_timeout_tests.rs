@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CountRequestInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::errors::StorageResult;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+use storage::types::StorageConfig;
+use tempfile::Builder;
+use tokio::runtime::{Handle, Runtime};
+
+use crate::test_storage_config;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+/// Start a node on the storage, like on startup of the service
+fn start_node(config: &StorageConfig) -> (Arc<TableOfContent>, Handle) {
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    (toc, handle)
+}
+
+fn create_collection(toc: &Arc<TableOfContent>, handle: &Handle, name: &str) {
+    let dispatcher = Dispatcher::new(toc.clone());
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    name.to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+}
+
+fn upsert_points(toc: &TableOfContent, handle: &Handle, name: &str, num_points: u64) {
+    let points = (0..num_points)
+        .map(|id| PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vec![id as f32 + 1.0; 10]).into(),
+            payload: None,
+        })
+        .collect();
+    handle
+        .block_on(toc.update(
+            name,
+            OperationWithClockTag::from(CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+            )),
+            true,
+            WriteOrdering::Weak,
+            ShardSelectorInternal::Empty,
+            FULL_ACCESS.clone(),
+        ))
+        .unwrap();
+}
+
+fn count_points(toc: &TableOfContent, handle: &Handle, name: &str) -> StorageResult<usize> {
+    let request = CountRequestInternal {
+        filter: None,
+        exact: true,
+    };
+    let count = handle.block_on(toc.count(
+        name,
+        request,
+        None,
+        None,
+        ShardSelectorInternal::All,
+        FULL_ACCESS.clone(),
+    ))?;
+    Ok(count.count)
+}
+
+#[test]
+fn test_shard_load_timeout_keeps_node_running() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    {
+        let config = test_storage_config(storage_dir.path());
+        let (toc, handle) = start_node(&config);
+        create_collection(&toc, &handle, "slow");
+        upsert_points(&toc, &handle, "slow", 1000);
+        assert_eq!(count_points(&toc, &handle, "slow").unwrap(), 1000);
+    }
+
+    // Restart the node with a timeout no shard can be loaded in
+    let mut config = test_storage_config(storage_dir.path());
+    config.handle_collection_load_errors = true;
+    config.performance.shard_load_timeout_sec = Some(0);
+    let (toc, handle) = start_node(&config);
+
+    // The node started, the collection is known, but its shard is not available
+    let collections = handle.block_on(toc.all_collections(&FULL_ACCESS));
+    assert_eq!(collections.len(), 1);
+    assert_eq!(collections[0].name(), "slow");
+    assert!(count_points(&toc, &handle, "slow").is_err());
+
+    // Other collections are still served
+    create_collection(&toc, &handle, "fresh");
+    upsert_points(&toc, &handle, "fresh", 5);
+    assert_eq!(count_points(&toc, &handle, "fresh").unwrap(), 5);
+}
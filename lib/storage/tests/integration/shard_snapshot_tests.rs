@@ -0,0 +1,225 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use memory::madvise;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::consensus::operation_sender::OperationSender;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::snapshots::limiter::ShardSnapshotLimiterStatus;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::{Access, AccessRequirements};
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_cancel_shard_snapshot() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("temp").tempdir().unwrap();
+
+    let config = StorageConfig {
+        storage_path: storage_dir.path().to_str().unwrap().to_string(),
+        snapshots_path: storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        snapshots_config: Default::default(),
+        temp_path: Some(temp_dir.path().to_str().unwrap().to_string()),
+        segment_volumes: vec![],
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: Some(100),
+            indexing_min_vector_number: None,
+            flush_interval_sec: 2,
+            max_optimization_threads: Some(2),
+        },
+        optimizers_overwrite: None,
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            optimizer_cpu_budget: 0,
+            update_rate_limit: None,
+            search_timeout_sec: None,
+            incoming_shard_transfers_limit: Some(1),
+            outgoing_shard_transfers_limit: Some(1),
+            max_concurrent_shard_snapshots: Some(1),
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay_ms: None,
+            optimizer_jitter_ms: None,
+            max_concurrent_collection_operations: None,
+            shard_load_timeout_sec: None,
+            retrieve_cache_size: None,
+        },
+        hnsw_index: Default::default(),
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        handle_collection_load_errors: false,
+        lenient_wal_recovery: false,
+        recovery_mode: None,
+        async_scorer: false,
+        update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+        shard_transfer_method: None,
+        collection: None,
+        search_log_sample_rate: None,
+        recall_monitor: None,
+        min_free_disk_mb: None,
+        max_collections: None,
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
+    let propose_operation_sender = OperationSender::new(propose_sender);
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        Some(propose_operation_sender),
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    let pass = FULL_ACCESS
+        .check_collection_access("test", AccessRequirements::new())
+        .unwrap();
+
+    // Occupy the only snapshot slot, so that the next snapshot creation is in progress but queued
+    let permit = handle.block_on(toc.acquire_shard_snapshot_permit());
+
+    let task = {
+        let toc = toc.clone();
+        let pass = FULL_ACCESS
+            .check_collection_access("test", AccessRequirements::new())
+            .unwrap();
+        handle.spawn(async move { toc.create_shard_snapshot(&pass, 0).await })
+    };
+
+    handle.block_on(async {
+        while toc.shard_snapshot_status().queued == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+
+    assert!(toc.cancel_shard_snapshot(&pass, 0));
+    drop(permit);
+
+    let result = handle.block_on(task).unwrap();
+    assert!(
+        matches!(result, Err(StorageError::ServiceError { ref description, .. }) if description.contains("cancelled")),
+        "{result:?}",
+    );
+
+    // Slot is released and nothing is left behind
+    assert_eq!(
+        toc.shard_snapshot_status(),
+        ShardSnapshotLimiterStatus {
+            limit: Some(1),
+            queued: 0,
+            running: 0,
+        },
+    );
+    assert!(!toc.cancel_shard_snapshot(&pass, 0));
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+    let collection = handle.block_on(toc.get_collection(&pass)).unwrap();
+    let snapshots = handle.block_on(collection.list_shard_snapshots(0)).unwrap();
+    assert!(snapshots.is_empty());
+    drop(collection);
+
+    // Snapshot creation works again
+    let snapshot = handle
+        .block_on(toc.create_shard_snapshot(&pass, 0))
+        .unwrap();
+    let collection = handle.block_on(toc.get_collection(&pass)).unwrap();
+    let snapshots = handle.block_on(collection.list_shard_snapshots(0)).unwrap();
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].name, snapshot.name);
+    drop(collection);
+
+    // Collection snapshots take a slot of the same limit
+    let permit = handle.block_on(toc.acquire_shard_snapshot_permit());
+
+    let task = {
+        let toc = toc.clone();
+        handle.spawn(async move {
+            let pass = FULL_ACCESS
+                .check_collection_access("test", AccessRequirements::new())
+                .unwrap();
+            toc.create_snapshot(&pass, &[]).await
+        })
+    };
+
+    handle.block_on(async {
+        while toc.shard_snapshot_status().queued == 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    });
+
+    drop(permit);
+    handle.block_on(task).unwrap().unwrap();
+}
@@ -0,0 +1,264 @@
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::CountRequestInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
+use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use memory::madvise;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{Condition, Distance, FieldCondition, Filter, Match, Payload};
+use serde_json::json;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::streaming_delete::StreamingDeleteStatus;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
+use storage::types::{PerformanceConfig, StorageConfig};
+use tempfile::Builder;
+use tokio::runtime::Runtime;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+const NUM_POINTS: u64 = 2000;
+const BATCH_SIZE: usize = 50;
+
+#[test]
+fn test_streaming_delete_by_filter() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let config = StorageConfig {
+        storage_path: storage_dir.path().to_str().unwrap().to_string(),
+        snapshots_path: storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string(),
+        snapshots_config: Default::default(),
+        temp_path: None,
+        segment_volumes: vec![],
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: Some(100),
+            indexing_min_vector_number: None,
+            flush_interval_sec: 2,
+            max_optimization_threads: Some(2),
+        },
+        optimizers_overwrite: None,
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            optimizer_cpu_budget: 0,
+            update_rate_limit: None,
+            search_timeout_sec: None,
+            incoming_shard_transfers_limit: Some(1),
+            outgoing_shard_transfers_limit: Some(1),
+            max_concurrent_shard_snapshots: None,
+            max_in_flight_updates: None,
+            upsert_coalesce_max_batch: None,
+            upsert_coalesce_max_delay_ms: None,
+            optimizer_jitter_ms: None,
+            max_concurrent_collection_operations: None,
+            shard_load_timeout_sec: None,
+            retrieve_cache_size: None,
+        },
+        hnsw_index: Default::default(),
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        handle_collection_load_errors: false,
+        lenient_wal_recovery: false,
+        recovery_mode: None,
+        async_scorer: false,
+        update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+        shard_transfer_method: None,
+        collection: None,
+        search_log_sample_rate: None,
+        recall_monitor: None,
+        min_free_disk_mb: None,
+        max_collections: None,
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        None,
+    ));
+    let dispatcher = Dispatcher::new(toc.clone());
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        replication_factor: None,
+                        shard_placement: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: None,
+                        payload_schema: None,
+                        default_search_params: None,
+                        tie_break: None,
+                        default_timeout: None,
+                        auto_create_shard_keys: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    let points = (0..NUM_POINTS)
+        .map(|id| {
+            let payload: Payload = serde_json::from_value(json!({"group": id % 4})).unwrap();
+            PointStruct {
+                id: id.into(),
+                vector: VectorStructInternal::from(vec![id as f32 + 1.0; 10]).into(),
+                payload: Some(payload),
+            }
+        })
+        .collect();
+    handle
+        .block_on(toc.update(
+            "test",
+            OperationWithClockTag::from(CollectionUpdateOperations::PointOperation(
+                PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points)),
+            )),
+            true,
+            WriteOrdering::Weak,
+            ShardSelectorInternal::Empty,
+            FULL_ACCESS.clone(),
+        ))
+        .unwrap();
+
+    // Delete every point not in group 0
+    let filter = Filter::new_must_not(Condition::Field(FieldCondition::new_match(
+        "group".parse().unwrap(),
+        Match::from(0_i64),
+    )));
+    let expected_deleted = NUM_POINTS as usize * 3 / 4;
+
+    let delete = handle
+        .block_on(toc.register_streaming_delete("test", &filter, None, FULL_ACCESS.clone()))
+        .unwrap();
+    let initial = delete.progress();
+    assert_eq!(initial.status, StreamingDeleteStatus::Running);
+    assert_eq!(initial.deleted, 0);
+    assert!(initial.estimated_total > 0);
+
+    let id = initial.id;
+    let task = {
+        let toc = toc.clone();
+        let filter = filter.clone();
+        handle.spawn(async move {
+            toc.run_streaming_delete(
+                "test",
+                filter,
+                None,
+                BATCH_SIZE,
+                WriteOrdering::Weak,
+                FULL_ACCESS.clone(),
+                delete,
+            )
+            .await
+        })
+    };
+
+    let mut observed = Vec::new();
+    let progress = handle.block_on(async {
+        loop {
+            let progress = toc
+                .streaming_delete_progress("test", id, &FULL_ACCESS)
+                .unwrap();
+            observed.push(progress.deleted);
+            if progress.status != StreamingDeleteStatus::Running {
+                break progress;
+            }
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    });
+    handle.block_on(task).unwrap();
+
+    assert_eq!(
+        progress.status,
+        StreamingDeleteStatus::Completed,
+        "{progress:?}"
+    );
+    assert_eq!(progress.deleted, expected_deleted);
+    assert!(observed.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(
+        observed
+            .iter()
+            .any(|&deleted| 0 < deleted && deleted < expected_deleted),
+        "no intermediate progress observed: {observed:?}",
+    );
+
+    let count = |filter: Option<Filter>| {
+        handle
+            .block_on(toc.count(
+                "test",
+                CountRequestInternal {
+                    filter,
+                    exact: true,
+                },
+                None,
+                None,
+                ShardSelectorInternal::All,
+                FULL_ACCESS.clone(),
+            ))
+            .unwrap()
+            .count
+    };
+    assert_eq!(count(Some(filter)), 0);
+    assert_eq!(count(None), NUM_POINTS as usize - expected_deleted);
+
+    // Finished delete can't be cancelled anymore, but is still reported
+    let cancelled = toc
+        .cancel_streaming_delete("test", id, &FULL_ACCESS)
+        .unwrap();
+    assert_eq!(cancelled.status, StreamingDeleteStatus::Completed);
+    assert!(toc
+        .streaming_delete_progress("other", id, &FULL_ACCESS)
+        .is_err());
+}
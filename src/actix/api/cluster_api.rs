@@ -32,6 +32,20 @@ fn cluster_status(
     })
 }
 
+#[get("/cluster/transfers")]
+fn list_shard_transfers(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let transfers = dispatcher
+            .toc(&access)
+            .running_shard_transfers(&access)
+            .await;
+        Ok(transfers)
+    })
+}
+
 #[post("/cluster/recover")]
 fn recover_current_peer(
     dispatcher: web::Data<Dispatcher>,
@@ -158,6 +172,7 @@ async fn delete_cluster_metadata_key(
 // Configure services
 pub fn config_cluster_api(cfg: &mut web::ServiceConfig) {
     cfg.service(cluster_status)
+        .service(list_shard_transfers)
         .service(remove_peer)
         .service(recover_current_peer)
         .service(get_cluster_metadata_keys)
@@ -204,6 +204,48 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[get("/collections/{name}/cluster/hash_ring")]
+async fn check_hash_ring_consistency(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(do_check_hash_ring_consistency(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+    ))
+    .await
+}
+
+#[post("/collections/{name}/reindex")]
+async fn reindex_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(do_reindex_collection(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+    ))
+    .await
+}
+
+#[post("/collections/{name}/reindex/cancel")]
+async fn cancel_reindex_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(do_cancel_reindex_collection(
+        dispatcher.toc(&access),
+        access,
+        &collection.name,
+    ))
+    .await
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -218,7 +260,10 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(check_hash_ring_consistency)
+        .service(reindex_collection)
+        .service(cancel_reindex_collection);
 }
 
 #[cfg(test)]
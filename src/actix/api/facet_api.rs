@@ -1,6 +1,8 @@
 use actix_web::{post, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{FacetRequest, FacetResponse};
+use api::rest::{
+    DistinctValuesRequest, DistinctValuesResponse, FacetRequest, FacetResponse, FacetValue,
+};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use storage::dispatcher::Dispatcher;
 use tokio::time::Instant;
@@ -48,6 +50,46 @@ async fn facet(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/distinct")]
+async fn distinct_values(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<DistinctValuesRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let DistinctValuesRequest {
+        distinct_request,
+        shard_key,
+    } = request.into_inner();
+
+    let distinct_params = From::from(distinct_request);
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let response = dispatcher
+        .toc(&access)
+        .distinct_values(
+            &collection.name,
+            distinct_params,
+            shard_selection,
+            params.consistency,
+            access,
+            params.timeout(),
+        )
+        .await
+        .map(|values| DistinctValuesResponse {
+            values: values.into_iter().map(FacetValue::from).collect(),
+        });
+
+    process_response(response, timing)
+}
+
 pub fn config_facet_api(cfg: &mut web::ServiceConfig) {
-    cfg.service(facet);
+    cfg.service(facet).service(distinct_values);
 }
@@ -1,12 +1,14 @@
 use std::sync::Arc;
 
-use actix_web::{post, web, Responder};
+use actix_web::{get, post, put, web, Responder};
 use collection::operations::point_ops::{PointIdsList, PointsSelector};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CountRequestInternal, PointRequestInternal, ScrollRequestInternal, UpdateResult, UpdateStatus,
 };
 use collection::shards::shard::ShardId;
+use segment::index::field_index::full_text_index::VocabEntry;
+use segment::json_path::JsonPath;
 use segment::types::{Condition, ExtendedPointId, Filter};
 use storage::content_manager::errors::{StorageError, StorageResult};
 use storage::dispatcher::Dispatcher;
@@ -23,7 +25,9 @@ pub fn config_local_shard_api(cfg: &mut web::ServiceConfig) {
     cfg.service(get_points)
         .service(scroll_points)
         .service(count_points)
-        .service(delete_points);
+        .service(delete_points)
+        .service(export_text_vocab)
+        .service(import_text_vocab);
 }
 
 #[post("/collections/{collection}/shards/{shard}/points")]
@@ -194,6 +198,7 @@ async fn delete_points(
                 operation_id: None,
                 status: UpdateStatus::Completed,
                 clock_tag: None,
+                deduplicated: None,
             });
         }
 
@@ -217,6 +222,48 @@ async fn delete_points(
     .await
 }
 
+/// Dump the vocabulary of the full-text index of a field, for each segment of the local shard
+#[get("/collections/{collection}/shards/{shard}/vocab/{field}")]
+async fn export_text_vocab(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+    path: web::Path<CollectionShardField>,
+) -> impl Responder {
+    helpers::time(async move {
+        let pass =
+            access.check_collection_access(&path.collection, AccessRequirements::new().whole())?;
+        let collection = dispatcher.toc(&access).get_collection(&pass).await?;
+
+        let vocab = collection
+            .export_full_text_vocab(path.shard, &path.field)
+            .await?;
+        Ok(vocab)
+    })
+    .await
+}
+
+/// Prewarm the vocabulary of the full-text index of a field in the local shard from a dump,
+/// returns the number of added tokens
+#[put("/collections/{collection}/shards/{shard}/vocab/{field}")]
+async fn import_text_vocab(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+    path: web::Path<CollectionShardField>,
+    vocab: web::Json<Vec<VocabEntry>>,
+) -> impl Responder {
+    helpers::time(async move {
+        let pass =
+            access.check_collection_access(&path.collection, AccessRequirements::new().manage())?;
+        let collection = dispatcher.toc(&access).get_collection(&pass).await?;
+
+        let added = collection
+            .import_full_text_vocab(path.shard, &path.field, &vocab)
+            .await?;
+        Ok(added)
+    })
+    .await
+}
+
 #[derive(serde::Deserialize, validator::Validate)]
 struct CollectionShard {
     #[validate(length(min = 1, max = 255))]
@@ -224,6 +271,13 @@ struct CollectionShard {
     shard: ShardId,
 }
 
+#[derive(serde::Deserialize)]
+struct CollectionShardField {
+    collection: String,
+    shard: ShardId,
+    field: JsonPath,
+}
+
 #[derive(Clone, Debug, serde::Deserialize)]
 struct WithFilter<T> {
     #[serde(flatten)]
@@ -145,8 +145,78 @@ async fn query_points_groups(
     .await
 }
 
+#[post("/collections/{name}/points/query/plan")]
+async fn query_points_plan(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let QueryRequest {
+            internal: query_request,
+            shard_key,
+        } = request.into_inner();
+
+        let shard_selection = match shard_key {
+            None => ShardSelectorInternal::All,
+            Some(shard_keys) => shard_keys.into(),
+        };
+
+        dispatcher
+            .toc(&access)
+            .query_plan(
+                &collection.name,
+                query_request.into(),
+                shard_selection,
+                params.consistency,
+                access,
+                params.timeout(),
+            )
+            .await
+    })
+    .await
+}
+
+#[post("/collections/{name}/points/query/explain")]
+async fn query_points_explain(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<QueryRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        let QueryRequest {
+            internal: query_request,
+            shard_key,
+        } = request.into_inner();
+
+        let shard_selection = match shard_key {
+            None => ShardSelectorInternal::All,
+            Some(shard_keys) => shard_keys.into(),
+        };
+
+        dispatcher
+            .toc(&access)
+            .query_explain(
+                &collection.name,
+                query_request.into(),
+                shard_selection,
+                params.consistency,
+                access,
+                params.timeout(),
+            )
+            .await
+    })
+    .await
+}
+
 pub fn config_query_api(cfg: &mut web::ServiceConfig) {
     cfg.service(query_points);
     cfg.service(query_points_batch);
     cfg.service(query_points_groups);
+    cfg.service(query_points_plan);
+    cfg.service(query_points_explain);
 }
@@ -64,6 +64,7 @@ mod test {
         test("all", from_type(ReadConsistencyType::All));
         test("majority", from_type(ReadConsistencyType::Majority));
         test("quorum", from_type(ReadConsistencyType::Quorum));
+        test("local_only", from_type(ReadConsistencyType::LocalOnly));
     }
 
     #[test]
@@ -1,10 +1,12 @@
 use std::time::Duration;
 
-use actix_web::{get, post, web, Responder};
+use actix_web::{get, post, web, HttpRequest, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
+use collection::operations::types::{
+    PointRequest, PointRequestInternal, Record, ScrollRequest, ScrollResult,
+};
 use futures::TryFutureExt;
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
@@ -13,6 +15,7 @@ use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
+use tokio::time::Instant;
 use validator::Validate;
 
 use super::read_params::ReadParams;
@@ -21,6 +24,9 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers;
 use crate::common::points::do_get_points;
 
+/// Header with the offset of the next page of a scroll streamed as newline delimited JSON
+const NEXT_PAGE_OFFSET_HEADER: &str = "next-page-offset";
+
 #[derive(Deserialize, Validate)]
 struct PointPath {
     #[validate(length(min = 1))]
@@ -134,6 +140,7 @@ async fn scroll_points(
     request: Json<ScrollRequest>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
+    http_request: HttpRequest,
 ) -> impl Responder {
     let ScrollRequest {
         scroll_request,
@@ -145,13 +152,32 @@ async fn scroll_points(
         Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
     };
 
-    helpers::time(dispatcher.toc(&access).scroll(
+    let result = dispatcher.toc(&access).scroll(
         &collection.name,
         scroll_request,
         params.consistency,
         params.timeout(),
         shard_selection,
         access,
-    ))
-    .await
+    );
+
+    if !helpers::accepts_ndjson(&http_request) {
+        return helpers::time(result).await;
+    }
+
+    // Offset of the next page can't be a line, as lines are points only
+    let instant = Instant::now();
+    match result.await {
+        Ok(ScrollResult {
+            points,
+            next_page_offset,
+        }) => {
+            let mut response = HttpResponse::Ok();
+            if let Some(offset) = next_page_offset {
+                response.insert_header((NEXT_PAGE_OFFSET_HEADER, offset.to_string()));
+            }
+            helpers::ndjson_response(response, points)
+        }
+        Err(err) => helpers::process_response_error(err, instant),
+    }
 }
@@ -1,6 +1,8 @@
-use actix_web::{post, web, HttpResponse, Responder};
+use actix_web::{post, web, HttpRequest, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
-use api::rest::{SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest};
+use api::rest::{
+    ScoredPointId, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest,
+};
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
@@ -16,7 +18,8 @@ use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response};
 use crate::common::points::{
-    do_core_search_points, do_search_batch_points, do_search_point_groups, do_search_points_matrix,
+    do_core_search_points, do_search_batch_points, do_search_point_groups,
+    do_search_points_ids_only, do_search_points_matrix,
 };
 
 #[post("/collections/{name}/points/search")]
@@ -26,6 +29,48 @@ async fn search_points(
     request: Json<SearchRequest>,
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
+    http_request: HttpRequest,
+) -> HttpResponse {
+    let SearchRequest {
+        search_request,
+        shard_key,
+    } = request.into_inner();
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    let result = do_core_search_points(
+        dispatcher.toc(&access),
+        &collection.name,
+        search_request.into(),
+        params.consistency,
+        shard_selection,
+        access,
+        params.timeout(),
+    )
+    .map_ok(|scored_points| {
+        scored_points
+            .into_iter()
+            .map(api::rest::ScoredPoint::from)
+            .collect_vec()
+    });
+
+    if helpers::accepts_ndjson(&http_request) {
+        helpers::time_ndjson(result).await
+    } else {
+        helpers::time(result).await
+    }
+}
+
+#[post("/collections/{name}/points/search/ids")]
+async fn search_points_ids(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<SearchRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
     let SearchRequest {
         search_request,
@@ -38,7 +83,7 @@ async fn search_points(
     };
 
     helpers::time(
-        do_core_search_points(
+        do_search_points_ids_only(
             dispatcher.toc(&access),
             &collection.name,
             search_request.into(),
@@ -47,10 +92,10 @@ async fn search_points(
             access,
             params.timeout(),
         )
-        .map_ok(|scored_points| {
-            scored_points
+        .map_ok(|scored_ids| {
+            scored_ids
                 .into_iter()
-                .map(api::rest::ScoredPoint::from)
+                .map(|(id, score)| ScoredPointId { id, score })
                 .collect_vec()
         }),
     )
@@ -211,6 +256,7 @@ async fn search_points_matrix_offsets(
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
+        .service(search_points_ids)
         .service(batch_search_points)
         .service(search_point_groups)
         .service(search_points_matrix_pairs)
@@ -8,7 +8,7 @@ use collection::common::file_utils::move_file;
 use collection::common::sha_256::{hash_file, hashes_equal};
 use collection::common::snapshot_stream::SnapshotStream;
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
+    CreateSnapshot, ShardSnapshotRecover, SnapshotPriority, SnapshotRecover,
 };
 use collection::shards::shard::ShardId;
 use futures::{FutureExt as _, TryFutureExt as _};
@@ -151,12 +151,23 @@ async fn create_snapshot(
     dispatcher: web::Data<Dispatcher>,
     path: web::Path<String>,
     params: valid::Query<SnapshottingParam>,
+    request: Option<valid::Json<CreateSnapshot>>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let collection_name = path.into_inner();
+    // Request body is optional, plain snapshot is created without it
+    let exclude_payload_keys = request
+        .and_then(|request| request.into_inner().exclude_payload_keys)
+        .unwrap_or_default();
 
     let future = async move {
-        do_create_snapshot(dispatcher.toc(&access).clone(), access, &collection_name).await
+        do_create_snapshot(
+            dispatcher.toc(&access).clone(),
+            access,
+            &collection_name,
+            exclude_payload_keys,
+        )
+        .await
     };
 
     helpers::time_or_accept(future, params.wait.unwrap_or(true)).await
@@ -357,6 +368,23 @@ async fn create_shard_snapshot(
     helpers::time_or_accept(future, query.wait.unwrap_or(true)).await
 }
 
+#[post("/collections/{collection}/shards/{shard}/snapshots/cancel")]
+async fn cancel_shard_snapshot(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, ShardId)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection, shard) = path.into_inner();
+    let future = common::snapshots::cancel_shard_snapshot(
+        dispatcher.toc(&access).clone(),
+        access,
+        collection,
+        shard,
+    );
+
+    helpers::time(future).await
+}
+
 // TODO: `PUT` (same as `recover_from_snapshot`) or `POST`!?
 #[put("/collections/{collection}/shards/{shard}/snapshots/recover")]
 async fn recover_shard_snapshot(
@@ -515,6 +543,7 @@ pub fn config_snapshots_api(cfg: &mut web::ServiceConfig) {
         .service(delete_collection_snapshot)
         .service(list_shard_snapshots)
         .service(create_shard_snapshot)
+        .service(cancel_shard_snapshot)
         .service(recover_shard_snapshot)
         .service(upload_shard_snapshot)
         .service(download_shard_snapshot)
@@ -1,11 +1,16 @@
 use actix_web::rt::time::Instant;
-use actix_web::{delete, post, put, web, Responder};
+use actix_web::{delete, get, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
+use api::rest::ShardKeySelector;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{
+    PointInsertOperations, PointsSelector, StreamingDeleteRequest, WriteOrdering,
+};
+use collection::operations::vector_dedup::VectorDedup;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
+use segment::types::ShardKey;
 use serde::{Deserialize, Serialize};
 use storage::dispatcher::Dispatcher;
 use validator::Validate;
@@ -15,9 +20,14 @@ use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response};
 use crate::common::points::{
     do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
-    do_upsert_points, CreateFieldIndex, UpdateOperations,
+    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload,
+    do_start_streaming_delete, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    UpdateOperation, UpdateOperations,
+};
+use crate::common::streaming_upsert::{
+    do_stream_upsert_points, DEFAULT_STREAMING_UPSERT_BATCH_SIZE, MAX_STREAMING_UPSERT_BATCH_SIZE,
 };
+use crate::settings::ServiceConfig;
 
 #[derive(Deserialize, Validate)]
 struct FieldPath {
@@ -43,16 +53,77 @@ async fn upsert_points(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
-    helpers::time(do_upsert_points(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operation,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    ))
+    helpers::time(async move {
+        dispatcher
+            .ensure_shard_keys(&collection.name, operation.shard_key(), &access, None)
+            .await?;
+
+        do_upsert_points(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            operation,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    })
+    .await
+}
+
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct StreamingUpsertParam {
+    /// Number of points applied at once
+    #[validate(range(min = 1, max = MAX_STREAMING_UPSERT_BATCH_SIZE))]
+    pub batch_size: Option<usize>,
+    pub ordering: Option<WriteOrdering>,
+    /// Keyword shard key to upsert points into
+    pub shard_key: Option<String>,
+    /// How to handle points with exactly the same vectors as another point of the batch or
+    /// a stored point
+    pub dedup: Option<VectorDedup>,
+}
+
+#[put("/collections/{name}/points/stream")]
+async fn stream_upsert_points(
+    dispatcher: web::Data<Dispatcher>,
+    service_config: web::Data<ServiceConfig>,
+    collection: Path<CollectionPath>,
+    body: web::Payload,
+    params: Query<StreamingUpsertParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let batch_size = params
+        .batch_size
+        .unwrap_or(DEFAULT_STREAMING_UPSERT_BATCH_SIZE);
+    let ordering = params.ordering.unwrap_or_default();
+    let max_point_size = service_config.max_request_size_mb * 1024 * 1024;
+    let StreamingUpsertParam {
+        shard_key, dedup, ..
+    } = params.into_inner();
+    let shard_key =
+        shard_key.map(|shard_key| ShardKeySelector::ShardKey(ShardKey::Keyword(shard_key)));
+
+    helpers::time(async move {
+        dispatcher
+            .ensure_shard_keys(&collection.name, shard_key.as_ref(), &access, None)
+            .await?;
+
+        do_stream_upsert_points(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            body,
+            batch_size,
+            max_point_size,
+            ordering,
+            shard_key,
+            dedup,
+            access,
+        )
+        .await
+    })
     .await
 }
 
@@ -81,31 +152,115 @@ async fn delete_points(
     .await
 }
 
-#[put("/collections/{name}/points/vectors")]
-async fn update_vectors(
+#[post("/collections/{name}/points/clear")]
+async fn clear_points(
     dispatcher: web::Data<Dispatcher>,
     collection: Path<CollectionPath>,
-    operation: Json<UpdateVectors>,
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
-    helpers::time(do_update_vectors(
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .clear_collection(&collection.name, wait, ordering, access)
+            .await
+    })
+    .await
+}
+
+#[post("/collections/{name}/points/delete/stream")]
+async fn start_streaming_delete(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<StreamingDeleteRequest>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let operation = operation.into_inner();
+    let ordering = params.ordering.unwrap_or_default();
+
+    helpers::time(do_start_streaming_delete(
         dispatcher.toc(&access).clone(),
         collection.into_inner().name,
         operation,
-        None,
-        None,
-        wait,
         ordering,
         access,
     ))
     .await
 }
 
+#[get("/collections/{name}/points/delete/stream/{id}")]
+async fn get_streaming_delete(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, u64)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection_name, id) = path.into_inner();
+
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .streaming_delete_progress(&collection_name, id, &access)
+    })
+    .await
+}
+
+#[post("/collections/{name}/points/delete/stream/{id}/cancel")]
+async fn cancel_streaming_delete(
+    dispatcher: web::Data<Dispatcher>,
+    path: web::Path<(String, u64)>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let (collection_name, id) = path.into_inner();
+
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .cancel_streaming_delete(&collection_name, id, &access)
+    })
+    .await
+}
+
+#[put("/collections/{name}/points/vectors")]
+async fn update_vectors(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<UpdateVectors>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let operation = operation.into_inner();
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+
+    helpers::time(async move {
+        dispatcher
+            .ensure_shard_keys(
+                &collection.name,
+                operation.shard_key.as_ref(),
+                &access,
+                None,
+            )
+            .await?;
+
+        do_update_vectors(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            operation,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    })
+    .await
+}
+
 #[post("/collections/{name}/points/vectors/delete")]
 async fn delete_vectors(
     dispatcher: web::Data<Dispatcher>,
@@ -145,16 +300,28 @@ async fn set_payload(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
-    helpers::time(do_set_payload(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operation,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    ))
+    helpers::time(async move {
+        dispatcher
+            .ensure_shard_keys(
+                &collection.name,
+                operation.shard_key.as_ref(),
+                &access,
+                None,
+            )
+            .await?;
+
+        do_set_payload(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            operation,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    })
     .await
 }
 
@@ -170,16 +337,28 @@ async fn overwrite_payload(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
-    helpers::time(do_overwrite_payload(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operation,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    ))
+    helpers::time(async move {
+        dispatcher
+            .ensure_shard_keys(
+                &collection.name,
+                operation.shard_key.as_ref(),
+                &access,
+                None,
+            )
+            .await?;
+
+        do_overwrite_payload(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            operation,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    })
     .await
 }
 
@@ -246,16 +425,31 @@ async fn update_batch(
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
 
-    let response = do_batch_update_points(
-        dispatcher.toc(&access).clone(),
-        collection.into_inner().name,
-        operations.operations,
-        None,
-        None,
-        wait,
-        ordering,
-        access,
-    )
+    let response = async {
+        dispatcher
+            .ensure_shard_keys(
+                &collection.name,
+                operations
+                    .operations
+                    .iter()
+                    .filter_map(UpdateOperation::written_shard_key),
+                &access,
+                None,
+            )
+            .await?;
+
+        do_batch_update_points(
+            dispatcher.toc(&access).clone(),
+            collection.into_inner().name,
+            operations.operations,
+            None,
+            None,
+            wait,
+            ordering,
+            access,
+        )
+        .await
+    }
     .await;
     process_response(response, timing)
 }
@@ -315,7 +509,12 @@ async fn delete_field_index(
 // Configure services
 pub fn config_update_api(cfg: &mut web::ServiceConfig) {
     cfg.service(upsert_points)
+        .service(stream_upsert_points)
         .service(delete_points)
+        .service(clear_points)
+        .service(start_streaming_delete)
+        .service(get_streaming_delete)
+        .service(cancel_streaming_delete)
         .service(update_vectors)
         .service(delete_vectors)
         .service(set_payload)
@@ -326,3 +525,155 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(delete_field_index)
         .service(update_batch);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use actix_web::{test, App};
+    use collection::shards::channel_service::ChannelService;
+    use common::cpu::CpuBudget;
+    use serde_json::json;
+    use storage::content_manager::collection_meta_ops::{
+        CollectionMetaOperations, CreateCollectionOperation,
+    };
+    use storage::content_manager::toc::TableOfContent;
+    use storage::rbac::{Access, AccessRequirements};
+    use tempfile::Builder;
+    use tokio::runtime::Runtime;
+
+    use super::*;
+    use crate::settings::Settings;
+
+    const FULL_ACCESS: Access = Access::full("For test");
+
+    #[test]
+    fn test_writes_create_shard_keys() {
+        let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+        let mut settings = Settings::new(None).unwrap();
+        settings.storage.storage_path = storage_dir.path().to_str().unwrap().to_string();
+        settings.storage.snapshots_path = storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        // Shard key placement is validated against known peers
+        let channel_service = ChannelService::new(6333, None);
+        channel_service
+            .id_to_address
+            .write()
+            .insert(0, "http://localhost:6335".parse().unwrap());
+
+        let toc = Arc::new(TableOfContent::new(
+            &settings.storage,
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            CpuBudget::default(),
+            channel_service,
+            0,
+            None,
+        ));
+        let dispatcher = Arc::new(Dispatcher::new(toc.clone()));
+
+        actix_web::rt::System::new().block_on(async {
+            for (name, auto_create_shard_keys) in [("auto", true), ("manual", false)] {
+                let create_collection = serde_json::from_value(json!({
+                    "vectors": { "size": 4, "distance": "Dot" },
+                    "sharding_method": "custom",
+                    "auto_create_shard_keys": auto_create_shard_keys,
+                }))
+                .unwrap();
+                dispatcher
+                    .submit_collection_meta_op(
+                        CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                            name.to_string(),
+                            create_collection,
+                        )),
+                        FULL_ACCESS.clone(),
+                        None,
+                    )
+                    .await
+                    .unwrap();
+            }
+
+            let app = test::init_service(
+                App::new()
+                    .app_data(web::Data::from(dispatcher.clone()))
+                    .configure(config_update_api),
+            )
+            .await;
+
+            let upsert = |collection_name: &str, shard_key: &str| {
+                test::TestRequest::put()
+                    .uri(&format!("/collections/{collection_name}/points?wait=true"))
+                    .set_json(json!({
+                        "points": [{ "id": 1, "vector": [1.0, 1.0, 1.0, 1.0] }],
+                        "shard_key": shard_key,
+                    }))
+                    .to_request()
+            };
+            let update_batch = |collection_name: &str, operations: serde_json::Value| {
+                test::TestRequest::post()
+                    .uri(&format!(
+                        "/collections/{collection_name}/points/batch?wait=true"
+                    ))
+                    .set_json(json!({ "operations": operations }))
+                    .to_request()
+            };
+            let writes = json!([
+                {
+                    "upsert": {
+                        "points": [{ "id": 1, "vector": [1.0, 1.0, 1.0, 1.0] }],
+                        "shard_key": "other",
+                    }
+                },
+                { "set_payload": { "payload": { "a": 1 }, "points": [1], "shard_key": "other" } },
+            ]);
+            let deletes = json!([{ "delete": { "points": [1], "shard_key": "deleted" } }]);
+            let shard_keys = |collection_name: &str| {
+                let pass = FULL_ACCESS
+                    .check_collection_access(collection_name, AccessRequirements::new())
+                    .unwrap();
+                let toc = toc.clone();
+                async move {
+                    let mut shard_keys = toc
+                        .get_collection(&pass)
+                        .await
+                        .unwrap()
+                        .get_shard_keys()
+                        .await;
+                    shard_keys.sort_by_key(ToString::to_string);
+                    shard_keys
+                }
+            };
+
+            // Shard key is created on the first write, and reused by the next ones
+            let response = test::call_service(&app, upsert("auto", "tenant")).await;
+            assert!(response.status().is_success(), "{:?}", response.status());
+            let response = test::call_service(&app, upsert("auto", "tenant")).await;
+            assert!(response.status().is_success(), "{:?}", response.status());
+            assert_eq!(shard_keys("auto").await, vec![ShardKey::from("tenant")]);
+
+            // Batches create the shard keys they write to, but not the ones they delete from
+            let response = test::call_service(&app, update_batch("auto", writes.clone())).await;
+            assert!(response.status().is_success(), "{:?}", response.status());
+            let response = test::call_service(&app, update_batch("auto", deletes)).await;
+            assert!(!response.status().is_success());
+            assert_eq!(
+                shard_keys("auto").await,
+                vec![ShardKey::from("other"), ShardKey::from("tenant")],
+            );
+
+            // Write to an unknown shard key is rejected, if the option is off
+            let response = test::call_service(&app, upsert("manual", "tenant")).await;
+            assert!(!response.status().is_success());
+            let response = test::call_service(&app, update_batch("manual", writes)).await;
+            assert!(!response.status().is_success());
+            assert!(shard_keys("manual").await.is_empty());
+        });
+    }
+}
@@ -2,9 +2,10 @@ use std::fmt::Debug;
 use std::future::Future;
 
 use actix_web::rt::time::Instant;
-use actix_web::{http, HttpResponse, ResponseError};
+use actix_web::{http, web, HttpRequest, HttpResponse, HttpResponseBuilder, ResponseError};
 use api::grpc::models::{ApiResponse, ApiStatus};
 use collection::operations::types::CollectionError;
+use futures::StreamExt;
 use serde::Serialize;
 use storage::content_manager::errors::StorageError;
 
@@ -43,6 +44,53 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
     })
 }
 
+/// Media type of newline delimited JSON, with one JSON value per line
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Whether the `Accept` header of the request asks for newline delimited JSON
+pub fn accepts_ndjson(req: &HttpRequest) -> bool {
+    req.headers()
+        .get_all(http::header::ACCEPT)
+        .filter_map(|accept| accept.to_str().ok())
+        .flat_map(|accept| accept.split(','))
+        .filter_map(|media_type| media_type.split(';').next())
+        .any(|media_type| media_type.trim() == NDJSON_CONTENT_TYPE)
+}
+
+/// Stream items as newline delimited JSON, instead of serializing all of them into one array.
+///
+/// Each item is only serialized when the client is ready to receive it.
+pub fn ndjson_response<T>(mut response: HttpResponseBuilder, items: Vec<T>) -> HttpResponse
+where
+    T: Serialize + 'static,
+{
+    let lines = futures::stream::iter(items).map(|item| {
+        let mut line = serde_json::to_vec(&item)?;
+        line.push(b'\n');
+        Ok::<_, serde_json::Error>(web::Bytes::from(line))
+    });
+
+    response.content_type(NDJSON_CONTENT_TYPE).streaming(lines)
+}
+
+/// Response wrapper for a `Future` returning a list of items, which are streamed as newline
+/// delimited JSON. Errors are responded the same way as in `time`.
+///
+/// # Cancel safety
+///
+/// Future must be cancel safe.
+pub async fn time_ndjson<T, Fut>(future: Fut) -> HttpResponse
+where
+    Fut: Future<Output = Result<Vec<T>, StorageError>>,
+    T: Serialize + 'static,
+{
+    let instant = Instant::now();
+    match future.await {
+        Ok(items) => ndjson_response(HttpResponse::Ok(), items),
+        Err(err) => process_response_error(err, instant),
+    }
+}
+
 /// Response wrapper for a `Future` returning `Result`.
 ///
 /// # Cancel safety
@@ -130,6 +178,7 @@ impl ResponseError for HttpError {
             StorageError::ChecksumMismatch { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::Forbidden { .. } => http::StatusCode::FORBIDDEN,
             StorageError::PreconditionFailed { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::Conflict { .. } => http::StatusCode::CONFLICT,
         }
     }
 }
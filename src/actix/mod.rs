@@ -80,6 +80,7 @@ pub fn init(
         let logger_handle_data = web::Data::new(logger_handle);
         let http_client = web::Data::new(HttpClient::from_settings(&settings)?);
         let health_checker = web::Data::new(health_checker);
+        let service_config = web::Data::new(settings.service.clone());
         let web_ui_available = web_ui_folder(&settings);
 
         let mut api_key_whitelist = vec![
@@ -132,6 +133,7 @@ pub fn init(
                 .app_data(http_client.clone())
                 .app_data(debugger_state.clone())
                 .app_data(health_checker.clone())
+                .app_data(service_config.clone())
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
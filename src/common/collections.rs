@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
 use api::grpc::qdrant::CollectionExists;
+use collection::collection::hash_ring_consistency::HashRingConsistency;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
     AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
@@ -14,14 +15,18 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
+    ReindexResult,
 };
 use collection::shards::replica_set;
 use collection::shards::resharding::ReshardKey;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
-use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
+use collection::shards::transfer::{
+    ShardTransfer, ShardTransferKey, ShardTransferMethod, ShardTransferRestart,
+};
 use itertools::Itertools;
 use rand::prelude::SliceRandom;
 use rand::seq::IteratorRandom;
+use segment::json_path::JsonPath;
 use storage::content_manager::collection_meta_ops::ShardTransferOperations::{Abort, Start};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreateShardKey, DropShardKey, ReshardingOperation,
@@ -169,12 +174,17 @@ pub async fn do_create_snapshot(
     toc: Arc<TableOfContent>,
     access: Access,
     collection_name: &str,
+    exclude_payload_keys: Vec<JsonPath>,
 ) -> Result<SnapshotDescription, StorageError> {
     let collection_pass = access
         .check_collection_access(collection_name, AccessRequirements::new().write().whole())?
         .into_static();
 
-    let result = tokio::spawn(async move { toc.create_snapshot(&collection_pass).await }).await??;
+    let result = tokio::spawn(async move {
+        toc.create_snapshot(&collection_pass, &exclude_payload_keys)
+            .await
+    })
+    .await??;
 
     Ok(result)
 }
@@ -190,6 +200,41 @@ pub async fn do_get_collection_cluster(
     Ok(collection.cluster_info(toc.this_peer_id).await?)
 }
 
+pub async fn do_check_hash_ring_consistency(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<HashRingConsistency, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().whole())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    Ok(collection.check_hash_ring_consistency().await)
+}
+
+pub async fn do_reindex_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<ReindexResult, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().manage())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    let scheduled_segments = collection.reindex().await?;
+    Ok(ReindexResult { scheduled_segments })
+}
+
+pub async fn do_cancel_reindex_collection(
+    toc: &TableOfContent,
+    access: Access,
+    name: &str,
+) -> Result<bool, StorageError> {
+    let collection_pass =
+        access.check_collection_access(name, AccessRequirements::new().write().manage())?;
+    let collection = toc.get_collection(&collection_pass).await?;
+    collection.cancel_reindex().await?;
+    Ok(true)
+}
+
 pub async fn do_update_collection_cluster(
     dispatcher: &Dispatcher,
     collection_name: String,
@@ -256,6 +301,15 @@ pub async fn do_update_collection_cluster(
             validate_peer_exists(move_shard.to_peer_id)?;
             validate_peer_exists(move_shard.from_peer_id)?;
 
+            let method = match move_shard.method {
+                Some(method) => method,
+                None => {
+                    collection
+                        .select_shard_transfer_method(move_shard.shard_id)
+                        .await
+                }
+            };
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
@@ -267,7 +321,8 @@ pub async fn do_update_collection_cluster(
                             to: move_shard.to_peer_id,
                             from: move_shard.from_peer_id,
                             sync: false,
-                            method: move_shard.method,
+                            method: Some(method),
+                            filter: None,
                         }),
                     ),
                     access,
@@ -292,20 +347,36 @@ pub async fn do_update_collection_cluster(
             // validate source peer exists
             validate_peer_exists(replicate_shard.from_peer_id)?;
 
+            if let Some(filter) = &replicate_shard.filter {
+                collection.check_transfer_filter(filter)?;
+            }
+
+            let method = match replicate_shard.method {
+                Some(method) => method,
+                // Only streaming records can transfer a subset of points
+                None if replicate_shard.filter.is_some() => ShardTransferMethod::StreamRecords,
+                None => {
+                    collection
+                        .select_shard_transfer_method(replicate_shard.shard_id)
+                        .await
+                }
+            };
+
+            let transfer = ShardTransfer {
+                shard_id: replicate_shard.shard_id,
+                to_shard_id: replicate_shard.to_shard_id,
+                to: replicate_shard.to_peer_id,
+                from: replicate_shard.from_peer_id,
+                sync: true,
+                method: Some(method),
+                filter: replicate_shard.filter,
+            };
+            transfer.check_filter_supported()?;
+
             // submit operation to consensus
             dispatcher
                 .submit_collection_meta_op(
-                    CollectionMetaOperations::TransferShard(
-                        collection_name,
-                        Start(ShardTransfer {
-                            shard_id: replicate_shard.shard_id,
-                            to_shard_id: replicate_shard.to_shard_id,
-                            to: replicate_shard.to_peer_id,
-                            from: replicate_shard.from_peer_id,
-                            sync: true,
-                            method: replicate_shard.method,
-                        }),
-                    ),
+                    CollectionMetaOperations::TransferShard(collection_name, Start(transfer)),
                     access,
                     wait_timeout,
                 )
@@ -396,7 +467,12 @@ pub async fn do_update_collection_cluster(
                 .get() as usize;
             let replication_factor = create_sharding_key
                 .replication_factor
-                .unwrap_or(state.config.params.replication_factor)
+                .unwrap_or_else(|| {
+                    state
+                        .config
+                        .params
+                        .shard_key_replication_factor(Some(&create_sharding_key.shard_key))
+                })
                 .get() as usize;
 
             let shard_keys_mapping = state.shards_key_mapping;
@@ -659,6 +735,26 @@ pub async fn do_update_collection_cluster(
                 )
                 .await
         }
+        ClusterOperations::ResumeResharding(_) => {
+            // TODO(resharding): Deduplicate resharding operations handling?
+
+            let Some(state) = collection.resharding_state().await else {
+                return Err(StorageError::bad_request(format!(
+                    "resharding is not in progress for collection {collection_name}"
+                )));
+            };
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::Resharding(
+                        collection_name.clone(),
+                        ReshardingOperation::Resume(state.key()),
+                    ),
+                    access,
+                    wait_timeout,
+                )
+                .await
+        }
         ClusterOperations::FinishResharding(_) => {
             // TODO(resharding): Deduplicate resharding operations handling?
 
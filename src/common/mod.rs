@@ -13,6 +13,7 @@ pub mod points;
 pub mod snapshots;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod stacktrace;
+pub mod streaming_upsert;
 #[allow(dead_code)] // May contain functions used in different binaries. Not actually dead
 pub mod telemetry;
 pub mod telemetry_ops;
@@ -12,8 +12,8 @@ use collection::operations::payload_ops::{
     DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    ConditionalUpsert, DedupUpsert, FilterSelector, PointIdsList, PointInsertOperations,
+    PointOperations, PointsSelector, StreamingDeleteRequest, UpsertMode, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
@@ -24,6 +24,7 @@ use collection::operations::types::{
 use collection::operations::universal_query::collection_query::{
     CollectionQueryGroupsRequest, CollectionQueryRequest,
 };
+use collection::operations::vector_dedup::dedup_vectors;
 use collection::operations::vector_ops::{
     DeleteVectors, UpdateVectors, UpdateVectorsOp, VectorOperations,
 };
@@ -31,14 +32,18 @@ use collection::operations::{
     ClockTag, CollectionUpdateOperations, CreateIndex, FieldIndexOperations, OperationWithClockTag,
 };
 use collection::shards::shard::ShardId;
+use common::types::ScoreType;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{ExtendedPointId, PayloadFieldSchema, PayloadKeyType, ScoredPoint};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
 };
 use storage::content_manager::errors::StorageError;
+use storage::content_manager::streaming_delete::{
+    StreamingDeleteProgress, DEFAULT_STREAMING_DELETE_BATCH_SIZE,
+};
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
@@ -133,6 +138,22 @@ impl Validate for UpdateOperation {
     }
 }
 
+impl UpdateOperation {
+    /// Shard key which this operation writes data to, missing shard keys may be created for it
+    pub fn written_shard_key(&self) -> Option<&ShardKeySelector> {
+        match self {
+            UpdateOperation::Upsert(op) => op.upsert.shard_key(),
+            UpdateOperation::SetPayload(op) => op.set_payload.shard_key.as_ref(),
+            UpdateOperation::OverwritePayload(op) => op.overwrite_payload.shard_key.as_ref(),
+            UpdateOperation::UpdateVectors(op) => op.update_vectors.shard_key.as_ref(),
+            UpdateOperation::Delete(_)
+            | UpdateOperation::DeletePayload(_)
+            | UpdateOperation::ClearPayload(_)
+            | UpdateOperation::DeleteVectors(_) => None,
+        }
+    }
+}
+
 /// Converts a pair of parameters into a shard selector
 /// suitable for update operations.
 ///
@@ -175,21 +196,76 @@ pub async fn do_upsert_points(
     ordering: WriteOrdering,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
-
+    let mode = operation.mode();
+    let expected_versions = operation.expected_versions().map(<[_]>::to_vec);
+    let version_key = operation.version_key().cloned();
+    let dedup = operation.dedup();
+    let (shard_key, mut operation) = operation.decompose();
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
-    toc.update(
-        &collection_name,
-        OperationWithClockTag::new(collection_operation, clock_tag),
-        wait,
-        ordering,
-        shard_selector,
-        access,
-    )
-    .await
+    // Duplicates within the upsert are removed here, stored duplicates by the shards
+    let duplicates = dedup.map(|policy| dedup_vectors(&mut operation, policy));
+
+    let point_operation = match (mode, expected_versions, dedup) {
+        (UpsertMode::Upsert, None, None) => PointOperations::UpsertPoints(operation),
+        (UpsertMode::Upsert, None, Some(policy)) => {
+            PointOperations::UpsertPointsDedup(DedupUpsert {
+                points: operation,
+                policy,
+            })
+        }
+        (_, Some(_), Some(_)) => {
+            return Err(StorageError::bad_input(
+                "`dedup` can't be used with expected versions",
+            ));
+        }
+        (UpsertMode::InsertIfAbsent, _, Some(_)) => {
+            return Err(StorageError::bad_input(
+                "`dedup` can't be used with `insert_if_absent` mode",
+            ));
+        }
+        (UpsertMode::Upsert, Some(expected_versions), None) => {
+            let Some(version_key) = version_key else {
+                return Err(StorageError::bad_input(
+                    "`version_key` is required with expected versions",
+                ));
+            };
+            let conditional_upsert = ConditionalUpsert {
+                points: operation,
+                expected_versions,
+                version_key,
+            };
+            conditional_upsert
+                .check_new_versions()
+                .map_err(StorageError::bad_input)?;
+            PointOperations::UpsertPointsIfVersion(conditional_upsert)
+        }
+        (UpsertMode::InsertIfAbsent, None, None) => {
+            PointOperations::InsertPointsIfAbsent(operation)
+        }
+        (UpsertMode::InsertIfAbsent, Some(_), None) => {
+            return Err(StorageError::bad_input(
+                "Expected versions can't be used with `insert_if_absent` mode",
+            ));
+        }
+    };
+    let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
+
+    let mut result = toc
+        .update(
+            &collection_name,
+            OperationWithClockTag::new(collection_operation, clock_tag),
+            wait,
+            ordering,
+            shard_selector,
+            access,
+        )
+        .await?;
+    if let Some(duplicates) = duplicates {
+        result.deduplicated = Some(duplicates + result.deduplicated.unwrap_or(0));
+    }
+
+    Ok(result)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -225,6 +301,43 @@ pub async fn do_delete_points(
     .await
 }
 
+/// Start deleting points matching a filter in batches, in background.
+///
+/// Returns immediately with the initial progress, which identifies the delete.
+pub async fn do_start_streaming_delete(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    request: StreamingDeleteRequest,
+    ordering: WriteOrdering,
+    access: Access,
+) -> Result<StreamingDeleteProgress, StorageError> {
+    let StreamingDeleteRequest {
+        filter,
+        shard_key,
+        batch_size,
+    } = request;
+
+    let handle = toc
+        .register_streaming_delete(&collection_name, &filter, shard_key.clone(), access.clone())
+        .await?;
+    let progress = handle.progress();
+
+    tokio::spawn(async move {
+        toc.run_streaming_delete(
+            &collection_name,
+            filter,
+            shard_key,
+            batch_size.unwrap_or(DEFAULT_STREAMING_DELETE_BATCH_SIZE),
+            ordering,
+            access,
+            handle,
+        )
+        .await
+    });
+
+    Ok(progress)
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_update_vectors(
     toc: Arc<TableOfContent>,
@@ -781,6 +894,26 @@ pub async fn do_core_search_points(
         .ok_or_else(|| StorageError::service_error("Empty search result"))
 }
 
+pub async fn do_search_points_ids_only(
+    toc: &TableOfContent,
+    collection_name: &str,
+    request: CoreSearchRequest,
+    read_consistency: Option<ReadConsistency>,
+    shard_selection: ShardSelectorInternal,
+    access: Access,
+    timeout: Option<Duration>,
+) -> Result<Vec<(ExtendedPointId, ScoreType)>, StorageError> {
+    toc.search_ids_only(
+        collection_name,
+        request,
+        read_consistency,
+        shard_selection,
+        access,
+        timeout,
+    )
+    .await
+}
+
 pub async fn do_search_batch_points(
     toc: &TableOfContent,
     collection_name: &str,
@@ -1043,3 +1176,158 @@ pub async fn do_search_points_matrix(
     )
     .await
 }
+
+#[cfg(test)]
+mod tests {
+    use collection::operations::point_ops::{PointStruct, PointsList};
+    use collection::operations::vector_dedup::VectorDedup;
+    use collection::shards::channel_service::ChannelService;
+    use common::cpu::CpuBudget;
+    use segment::data_types::vectors::VectorStructInternal;
+    use segment::types::{ExtendedPointId, Payload, WithPayloadInterface};
+    use serde_json::json;
+    use storage::content_manager::collection_meta_ops::CreateCollectionOperation;
+    use tempfile::Builder;
+    use tokio::runtime::Runtime;
+
+    use super::*;
+    use crate::settings::Settings;
+
+    const FULL_ACCESS: Access = Access::full("For test");
+
+    fn point(id: u64, vector: [f32; 4], payload: serde_json::Value) -> PointStruct {
+        PointStruct {
+            id: id.into(),
+            vector: VectorStructInternal::from(vector.to_vec()).into(),
+            payload: Some(payload.into()),
+        }
+    }
+
+    fn upsert(points: Vec<PointStruct>, dedup: Option<VectorDedup>) -> PointInsertOperations {
+        PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: None,
+            mode: None,
+            expected_versions: None,
+            version_key: None,
+            dedup,
+        })
+    }
+
+    #[test]
+    fn test_upsert_with_vector_dedup() {
+        let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+        let mut settings = Settings::new(None).unwrap();
+        settings.storage.storage_path = storage_dir.path().to_str().unwrap().to_string();
+        settings.storage.snapshots_path = storage_dir
+            .path()
+            .join("snapshots")
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let toc = Arc::new(TableOfContent::new(
+            &settings.storage,
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            Runtime::new().unwrap(),
+            CpuBudget::default(),
+            ChannelService::new(6333, None),
+            0,
+            None,
+        ));
+        let dispatcher = Dispatcher::new(toc.clone());
+
+        Runtime::new().unwrap().block_on(async {
+            // Cosine distance, so stored vectors are normalized and differ from upserted ones
+            let create_collection =
+                serde_json::from_value(json!({ "vectors": { "size": 4, "distance": "Cosine" } }))
+                    .unwrap();
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                        "dedup".to_string(),
+                        create_collection,
+                    )),
+                    FULL_ACCESS.clone(),
+                    None,
+                )
+                .await
+                .unwrap();
+
+            let do_upsert = |operation| {
+                do_upsert_points(
+                    toc.clone(),
+                    "dedup".to_string(),
+                    operation,
+                    None,
+                    None,
+                    true,
+                    WriteOrdering::default(),
+                    FULL_ACCESS.clone(),
+                )
+            };
+
+            let result = do_upsert(upsert(
+                vec![point(1, [1.0, 2.0, 3.0, 4.0], json!({ "a": 1 }))],
+                None,
+            ))
+            .await
+            .unwrap();
+            assert_eq!(result.deduplicated, None);
+
+            // Point 2 is a duplicate of the stored point 1, point 4 of point 3 of the same upsert
+            let result = do_upsert(upsert(
+                vec![
+                    point(2, [1.0, 2.0, 3.0, 4.0], json!({ "b": 2 })),
+                    point(3, [2.0, 0.0, 0.0, 0.0], json!({ "c": 3 })),
+                    point(4, [2.0, 0.0, 0.0, 0.0], json!({ "d": 4 })),
+                ],
+                Some(VectorDedup::Merge),
+            ))
+            .await
+            .unwrap();
+            assert_eq!(result.deduplicated, Some(2));
+
+            // Stored point with the same id is overwritten, not deduplicated
+            let result = do_upsert(upsert(
+                vec![
+                    point(1, [1.0, 2.0, 3.0, 4.0], json!({ "a": 1, "b": 2 })),
+                    point(5, [2.0, 0.0, 0.0, 0.0], json!({ "e": 5 })),
+                ],
+                Some(VectorDedup::Skip),
+            ))
+            .await
+            .unwrap();
+            assert_eq!(result.deduplicated, Some(1));
+
+            let mut records = do_get_points(
+                &toc,
+                "dedup",
+                PointRequestInternal {
+                    ids: (1..=5).map(ExtendedPointId::from).collect(),
+                    with_payload: Some(WithPayloadInterface::Bool(true)),
+                    with_vector: false.into(),
+                },
+                None,
+                None,
+                ShardSelectorInternal::All,
+                FULL_ACCESS.clone(),
+            )
+            .await
+            .unwrap();
+            records.sort_by_key(|record| record.id);
+
+            let ids: Vec<_> = records.iter().map(|record| record.id).collect();
+            assert_eq!(ids, vec![1.into(), 3.into()]);
+
+            let payloads: Vec<_> = records.into_iter().map(|record| record.payload).collect();
+            let expected: Vec<Option<Payload>> = vec![
+                Some(json!({ "a": 1, "b": 2 }).into()),
+                Some(json!({ "c": 3, "d": 4 }).into()),
+            ];
+            assert_eq!(payloads, expected);
+        });
+    }
+}
@@ -27,13 +27,25 @@ pub async fn create_shard_snapshot(
 ) -> Result<SnapshotDescription, StorageError> {
     let collection_pass = access
         .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
-    let collection = toc.get_collection(&collection_pass).await?;
 
-    let snapshot = collection
-        .create_shard_snapshot(shard_id, &toc.optional_temp_or_snapshot_temp_path()?)
-        .await?;
+    toc.create_shard_snapshot(&collection_pass, shard_id).await
+}
+
+/// Cancel in-progress snapshot creations of the shard on this node.
+///
+/// Returns `true` if any snapshot creation was cancelled.
+pub async fn cancel_shard_snapshot(
+    toc: Arc<TableOfContent>,
+    access: Access,
+    collection_name: String,
+    shard_id: ShardId,
+) -> Result<bool, StorageError> {
+    let collection_pass = access
+        .check_collection_access(&collection_name, AccessRequirements::new().write().whole())?;
+    // Check that collection exists
+    toc.get_collection(&collection_pass).await?;
 
-    Ok(snapshot)
+    Ok(toc.cancel_shard_snapshot(&collection_pass, shard_id))
 }
 
 /// # Cancel safety
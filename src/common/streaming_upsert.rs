@@ -0,0 +1,273 @@
+use std::fmt::Display;
+use std::future::Future;
+use std::mem;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use api::rest::ShardKeySelector;
+use collection::operations::point_ops::{
+    PointInsertOperations, PointStruct, PointsList, WriteOrdering,
+};
+use collection::operations::vector_dedup::VectorDedup;
+use futures::{Stream, StreamExt as _};
+use schemars::JsonSchema;
+use serde::Serialize;
+use storage::content_manager::errors::StorageError;
+use storage::content_manager::toc::TableOfContent;
+use storage::rbac::Access;
+use validator::Validate as _;
+
+use crate::common::points::do_upsert_points;
+
+/// Number of points applied at once by a streaming upsert, if not specified.
+pub const DEFAULT_STREAMING_UPSERT_BATCH_SIZE: usize = 1000;
+
+/// Maximum number of points applied at once by a streaming upsert.
+pub const MAX_STREAMING_UPSERT_BATCH_SIZE: usize = 10_000;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct StreamingUpsertResult {
+    /// Number of upserted points
+    pub upserted: usize,
+    /// Number of upserted points skipped as duplicates
+    /// Provided if deduplication of vectors is requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deduplicated: Option<usize>,
+}
+
+/// Upsert points received as newline delimited JSON, one point per line.
+///
+/// Points are applied in batches as soon as they are received. The next batch is read only after
+/// the previous one is applied, so at most one batch and one incomplete line are kept in memory.
+/// If the stream fails, points of the batches applied before remain upserted.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_stream_upsert_points<S, C, E>(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    body: S,
+    batch_size: usize,
+    max_point_size: usize,
+    ordering: WriteOrdering,
+    shard_key: Option<ShardKeySelector>,
+    dedup: Option<VectorDedup>,
+    access: Access,
+) -> Result<StreamingUpsertResult, StorageError>
+where
+    S: Stream<Item = Result<C, E>> + Unpin,
+    C: AsRef<[u8]>,
+    E: Display,
+{
+    let deduplicated = AtomicUsize::new(0);
+    let upserted = stream_upsert_points(body, batch_size, max_point_size, |points| {
+        let operation = PointInsertOperations::PointsList(PointsList {
+            points,
+            shard_key: shard_key.clone(),
+            mode: None,
+            expected_versions: None,
+            version_key: None,
+            dedup,
+        });
+        let upsert = do_upsert_points(
+            toc.clone(),
+            collection_name.clone(),
+            operation,
+            None,
+            None,
+            true,
+            ordering,
+            access.clone(),
+        );
+        let deduplicated = &deduplicated;
+        async move {
+            let result = upsert.await?;
+            deduplicated.fetch_add(result.deduplicated.unwrap_or(0), Ordering::Relaxed);
+            Ok(())
+        }
+    })
+    .await?;
+
+    Ok(StreamingUpsertResult {
+        upserted,
+        deduplicated: dedup.map(|_| deduplicated.into_inner()),
+    })
+}
+
+/// Split a stream of newline delimited JSON into batches of points, and apply each batch.
+///
+/// Returns the number of applied points.
+async fn stream_upsert_points<S, C, E, F, Fut>(
+    mut body: S,
+    batch_size: usize,
+    max_point_size: usize,
+    mut apply_batch: F,
+) -> Result<usize, StorageError>
+where
+    S: Stream<Item = Result<C, E>> + Unpin,
+    C: AsRef<[u8]>,
+    E: Display,
+    F: FnMut(Vec<PointStruct>) -> Fut,
+    Fut: Future<Output = Result<(), StorageError>>,
+{
+    let mut line = Vec::new();
+    let mut line_number = 0;
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut upserted = 0;
+
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|err| {
+            StorageError::bad_input(format!("Failed to read request body: {err}"))
+        })?;
+        let mut chunk = chunk.as_ref();
+
+        while let Some(line_end) = chunk.iter().position(|&byte| byte == b'\n') {
+            line.extend_from_slice(&chunk[..line_end]);
+            chunk = &chunk[line_end + 1..];
+
+            line_number += 1;
+            batch.extend(parse_point(&line, line_number)?);
+            line.clear();
+
+            if batch.len() >= batch_size {
+                let points = mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                upserted += points.len();
+                apply_batch(points).await?;
+            }
+        }
+
+        line.extend_from_slice(chunk);
+        if line.len() > max_point_size {
+            return Err(StorageError::bad_input(format!(
+                "Point at line {} exceeds the maximum size of {max_point_size} bytes",
+                line_number + 1,
+            )));
+        }
+    }
+
+    // Last line may not be terminated
+    batch.extend(parse_point(&line, line_number + 1)?);
+
+    if !batch.is_empty() {
+        upserted += batch.len();
+        apply_batch(batch).await?;
+    }
+
+    Ok(upserted)
+}
+
+/// Parse a single line of the stream, empty lines are skipped
+fn parse_point(line: &[u8], line_number: usize) -> Result<Option<PointStruct>, StorageError> {
+    if line.iter().all(u8::is_ascii_whitespace) {
+        return Ok(None);
+    }
+
+    let point: PointStruct = serde_json::from_slice(line).map_err(|err| {
+        StorageError::bad_input(format!("Invalid point at line {line_number}: {err}"))
+    })?;
+    point.validate().map_err(|err| {
+        StorageError::bad_input(format!("Invalid point at line {line_number}: {err}"))
+    })?;
+
+    Ok(Some(point))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    const NUM_POINTS: u64 = 5_000;
+    const BATCH_SIZE: usize = 128;
+    const CHUNK_SIZE: usize = 1_000;
+    const MAX_POINT_SIZE: usize = 1024;
+
+    fn ndjson_points(num_points: u64) -> Vec<u8> {
+        (0..num_points)
+            .map(|id| {
+                format!(
+                    r#"{{"id": {id}, "vector": [{id}.0, 1.0, 2.0, 3.0], "payload": {{"n": {id}}}}}"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .into_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_stream_upsert_points() {
+        let body = ndjson_points(NUM_POINTS);
+        let chunks: Vec<_> = body.chunks(CHUNK_SIZE).map(<[u8]>::to_vec).collect();
+        let num_chunks = chunks.len();
+
+        // Number of chunks read from the stream so far
+        let read_chunks = Arc::new(AtomicUsize::new(0));
+        let stream = futures::stream::iter(chunks).map({
+            let read_chunks = read_chunks.clone();
+            move |chunk| {
+                read_chunks.fetch_add(1, Ordering::Relaxed);
+                Ok::<_, Infallible>(chunk)
+            }
+        });
+
+        let upserted_ids = Arc::new(Mutex::new(HashSet::new()));
+        // Number of chunks read when each batch was applied
+        let applied_batches = Arc::new(Mutex::new(Vec::new()));
+
+        let upserted = stream_upsert_points(stream, BATCH_SIZE, MAX_POINT_SIZE, |points| {
+            assert!(!points.is_empty());
+            assert!(points.len() <= BATCH_SIZE);
+
+            upserted_ids
+                .lock()
+                .unwrap()
+                .extend(points.iter().map(|point| point.id));
+            applied_batches
+                .lock()
+                .unwrap()
+                .push(read_chunks.load(Ordering::Relaxed));
+            async { Ok(()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(upserted, NUM_POINTS as usize);
+        let upserted_ids = upserted_ids.lock().unwrap();
+        assert_eq!(upserted_ids.len(), NUM_POINTS as usize);
+        assert!((0..NUM_POINTS).all(|id| upserted_ids.contains(&ExtendedPointId::from(id))));
+
+        // Batches are applied while the stream is read, so memory is bounded by the batch size
+        let applied_batches = applied_batches.lock().unwrap();
+        assert_eq!(
+            applied_batches.len(),
+            (NUM_POINTS as usize).div_ceil(BATCH_SIZE),
+        );
+        assert!(applied_batches[0] < num_chunks / 10);
+        assert!(applied_batches.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[tokio::test]
+    async fn test_stream_upsert_invalid_points() {
+        let apply = |_| async { Ok(()) };
+
+        // Malformed line
+        let body = b"{\"id\": 1, \"vector\": [1.0]}\nnot a point\n".to_vec();
+        let stream = futures::stream::iter([Ok::<_, Infallible>(body)]);
+        let err = stream_upsert_points(stream, BATCH_SIZE, MAX_POINT_SIZE, apply)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("line 2"), "{err}");
+
+        // Point larger than the limit, even without a line end
+        let body = vec![b' '; MAX_POINT_SIZE + 1];
+        let stream = futures::stream::iter([Ok::<_, Infallible>(body)]);
+        let err = stream_upsert_points(stream, BATCH_SIZE, MAX_POINT_SIZE, apply)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("maximum size"), "{err}");
+    }
+}
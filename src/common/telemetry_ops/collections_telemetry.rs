@@ -5,6 +5,7 @@ use common::types::{DetailsLevel, TelemetryDetail};
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::Serialize;
+use storage::content_manager::snapshots::limiter::ShardSnapshotLimiterStatus;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
 
@@ -25,6 +26,8 @@ pub enum CollectionTelemetryEnum {
 #[derive(Serialize, Clone, Debug, JsonSchema)]
 pub struct CollectionsTelemetry {
     pub number_of_collections: usize,
+    /// Queued and running shard snapshot creations on this node
+    pub shard_snapshots: ShardSnapshotLimiterStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub collections: Option<Vec<CollectionTelemetryEnum>>,
 }
@@ -70,6 +73,7 @@ impl CollectionsTelemetry {
 
         CollectionsTelemetry {
             number_of_collections,
+            shard_snapshots: toc.shard_snapshot_status(),
             collections,
         }
     }
@@ -79,6 +83,7 @@ impl Anonymize for CollectionsTelemetry {
     fn anonymize(&self) -> Self {
         CollectionsTelemetry {
             number_of_collections: self.number_of_collections,
+            shard_snapshots: self.shard_snapshots.anonymize(),
             collections: self.collections.anonymize(),
         }
     }
@@ -1340,11 +1340,17 @@ mod tests {
                             shard_number: Some(2),
                             on_disk_payload: None,
                             replication_factor: None,
+                            shard_placement: None,
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
                             strict_mode_config: None,
+                            payload_schema: None,
+                            default_search_params: None,
+                            tie_break: None,
+                            default_timeout: None,
+                            auto_create_shard_keys: None,
                         },
                     )),
                     Access::full("For test"),
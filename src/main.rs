@@ -401,6 +401,12 @@ fn main() -> anyhow::Result<()> {
         log::info!("Telemetry reporting disabled");
     }
 
+    if let Some(recall_monitor_config) = settings.storage.recall_monitor.clone() {
+        log::info!("Recall monitoring enabled");
+
+        runtime_handle.spawn(toc_arc.clone().run_recall_monitor(recall_monitor_config));
+    }
+
     // Setup subscribers to listen for issue-able events
     issues_setup::setup_subscribers(&settings);
 
@@ -49,6 +49,7 @@ pub async fn handle_existing_collections(
                 shard_number: Some(shards_number),
                 sharding_method,
                 replication_factor: Some(collection_state.config.params.replication_factor.get()),
+                shard_placement: None,
                 write_consistency_factor: Some(
                     collection_state
                         .config
@@ -63,6 +64,11 @@ pub async fn handle_existing_collections(
                 init_from: None,
                 quantization_config: collection_state.config.quantization_config,
                 strict_mode_config: collection_state.config.strict_mode_config,
+                payload_schema: collection_state.config.payload_schema,
+                default_search_params: collection_state.config.default_search_params,
+                tie_break: collection_state.config.tie_break,
+                default_timeout: collection_state.config.default_timeout,
+                auto_create_shard_keys: Some(collection_state.config.params.auto_create_shard_keys),
             },
         );
 
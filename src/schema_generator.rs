@@ -1,22 +1,27 @@
 use api::grpc::models::{CollectionsResponse, VersionInfo};
 use api::rest::{
-    FacetRequest, FacetResponse, QueryGroupsRequest, QueryRequest, QueryRequestBatch,
-    QueryResponse, Record, ScoredPoint, SearchMatrixOffsetsResponse, SearchMatrixPairsResponse,
-    SearchMatrixRequest,
+    DistinctValuesRequest, DistinctValuesResponse, FacetRequest, FacetResponse, QueryGroupsRequest,
+    QueryRequest, QueryRequestBatch, QueryResponse, Record, ScoredPoint, ScoredPointId,
+    SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMatrixRequest,
 };
+use collection::collection::hash_ring_consistency::HashRingConsistency;
+use collection::collection::query_explanation::ExplainedQueryResponse;
+use collection::collection::query_plan::QueryPlan;
 use collection::operations::cluster_ops::ClusterOperations;
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::point_ops::{
+    PointInsertOperations, PointsSelector, StreamingDeleteRequest, WriteOrdering,
+};
 use collection::operations::snapshot_ops::{
-    ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
+    CreateSnapshot, ShardSnapshotRecover, SnapshotDescription, SnapshotRecover,
 };
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionExistence, CollectionInfo,
     CollectionsAliasesResponse, CountRequest, CountResult, DiscoverRequest, DiscoverRequestBatch,
     GroupsResult, PointGroup, PointRequest, RecommendGroupsRequest, RecommendRequest,
-    RecommendRequestBatch, ScrollRequest, ScrollResult, SearchGroupsRequest, SearchRequest,
-    SearchRequestBatch, UpdateResult,
+    RecommendRequestBatch, ReindexResult, ScrollRequest, ScrollResult, SearchGroupsRequest,
+    SearchRequest, SearchRequestBatch, ShardTransferProgressInfo, UpdateResult,
 };
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::gen::SchemaSettings;
@@ -25,10 +30,12 @@ use serde::Serialize;
 use storage::content_manager::collection_meta_ops::{
     ChangeAliasesOperation, CreateCollection, UpdateCollection,
 };
+use storage::content_manager::streaming_delete::StreamingDeleteProgress;
 use storage::types::ClusterStatus;
 
 use crate::common::helpers::LocksOption;
 use crate::common::points::{CreateFieldIndex, UpdateOperations};
+use crate::common::streaming_upsert::StreamingUpsertResult;
 use crate::common::telemetry::TelemetryData;
 
 mod actix;
@@ -94,6 +101,18 @@ struct AllDefinitions {
     bk: SearchMatrixPairsResponse,
     bl: FacetRequest,
     bm: FacetResponse,
+    bn: CreateSnapshot,
+    bo: ShardTransferProgressInfo,
+    bp: StreamingDeleteRequest,
+    bq: StreamingDeleteProgress,
+    br: DistinctValuesRequest,
+    bs: DistinctValuesResponse,
+    bt: ReindexResult,
+    bu: HashRingConsistency,
+    bv: QueryPlan,
+    bw: StreamingUpsertResult,
+    bx: ExplainedQueryResponse,
+    by: ScoredPointId,
 }
 
 fn save_schema<T: JsonSchema>() {
@@ -4,9 +4,11 @@ use std::time::{Duration, Instant};
 use api::grpc::qdrant::collections_internal_server::CollectionsInternal;
 use api::grpc::qdrant::{
     CollectionOperationResponse, GetCollectionInfoRequestInternal, GetCollectionInfoResponse,
-    GetShardRecoveryPointRequest, GetShardRecoveryPointResponse, InitiateShardTransferRequest,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    GetHashRingsRequest, GetHashRingsResponse, GetShardRecoveryPointRequest,
+    GetShardRecoveryPointResponse, InitiateShardTransferRequest, UpdateShardCutoffPointRequest,
+    WaitForShardStateRequest,
 };
+use collection::operations::conversions::hash_ring_state_to_grpc;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::{Access, AccessRequirements, CollectionPass};
 use tonic::{Request, Response, Status};
@@ -205,4 +207,37 @@ impl CollectionsInternal for CollectionsInternalService {
         };
         Ok(Response::new(response))
     }
+
+    async fn get_hash_rings(
+        &self,
+        request: Request<GetHashRingsRequest>,
+    ) -> Result<Response<GetHashRingsResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let GetHashRingsRequest { collection_name } = request.into_inner();
+
+        let collection_read = self
+            .toc
+            .get_collection(&full_access_pass(&collection_name)?)
+            .await
+            .map_err(|err| {
+                Status::not_found(format!(
+                    "Collection {collection_name} could not be found: {err}"
+                ))
+            })?;
+
+        let hash_rings = collection_read
+            .hash_rings()
+            .await
+            .into_iter()
+            .map(|(shard_key, state)| hash_ring_state_to_grpc(shard_key, state))
+            .collect();
+
+        let response = GetHashRingsResponse {
+            hash_rings,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
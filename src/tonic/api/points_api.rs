@@ -1,20 +1,25 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use api::grpc::qdrant as grpc;
 use api::grpc::qdrant::points_server::Points;
+use api::grpc::qdrant::points_update_operation::Operation;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
     DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
-    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
-    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
-    RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
-    RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
-    SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints, SearchResponse,
-    SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, PointsUpdateOperation,
+    QueryBatchPoints, QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints,
+    QueryResponse, RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse,
+    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
+    SearchBatchPoints, SearchBatchResponse, SearchGroupsResponse, SearchPointGroups, SearchPoints,
+    SearchResponse, SetPayloadPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors,
+    UpsertPoints,
 };
+use api::rest::ShardKeySelector;
 use collection::operations::types::CoreSearchRequest;
 use storage::dispatcher::Dispatcher;
+use storage::rbac::Access;
 use tonic::{Request, Response, Status};
 
 use super::points_common::{
@@ -37,6 +42,40 @@ impl PointsService {
     pub fn new(dispatcher: Arc<Dispatcher>) -> Self {
         Self { dispatcher }
     }
+
+    /// Create shard keys targeted by writes, which don't exist yet, if the collection allows it
+    async fn ensure_shard_keys<'a>(
+        &self,
+        collection_name: &str,
+        shard_key_selectors: impl IntoIterator<Item = &'a grpc::ShardKeySelector>,
+        access: &Access,
+    ) -> Result<(), Status> {
+        let shard_key_selectors: Vec<_> = shard_key_selectors
+            .into_iter()
+            .cloned()
+            .map(ShardKeySelector::from)
+            .collect();
+        self.dispatcher
+            .ensure_shard_keys(collection_name, &shard_key_selectors, access, None)
+            .await?;
+        Ok(())
+    }
+}
+
+/// Shard key which a batch operation writes data to, missing shard keys may be created for it
+fn written_shard_key(operation: &PointsUpdateOperation) -> Option<&grpc::ShardKeySelector> {
+    match operation.operation.as_ref()? {
+        Operation::Upsert(op) => op.shard_key_selector.as_ref(),
+        Operation::SetPayload(op) => op.shard_key_selector.as_ref(),
+        Operation::OverwritePayload(op) => op.shard_key_selector.as_ref(),
+        Operation::UpdateVectors(op) => op.shard_key_selector.as_ref(),
+        Operation::DeleteDeprecated(_)
+        | Operation::DeletePayload(_)
+        | Operation::ClearPayloadDeprecated(_)
+        | Operation::DeleteVectors(_)
+        | Operation::DeletePoints(_)
+        | Operation::ClearPayload(_) => None,
+    }
 }
 
 #[tonic::async_trait]
@@ -49,9 +88,17 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
 
+        let request = request.into_inner();
+        self.ensure_shard_keys(
+            &request.collection_name,
+            request.shard_key_selector.as_ref(),
+            &access,
+        )
+        .await?;
+
         upsert(
             self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+            request,
             None,
             None,
             access,
@@ -101,9 +148,17 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
 
+        let request = request.into_inner();
+        self.ensure_shard_keys(
+            &request.collection_name,
+            request.shard_key_selector.as_ref(),
+            &access,
+        )
+        .await?;
+
         update_vectors(
             self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+            request,
             None,
             None,
             access,
@@ -139,9 +194,17 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
 
+        let request = request.into_inner();
+        self.ensure_shard_keys(
+            &request.collection_name,
+            request.shard_key_selector.as_ref(),
+            &access,
+        )
+        .await?;
+
         set_payload(
             self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+            request,
             None,
             None,
             access,
@@ -158,9 +221,17 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
 
+        let request = request.into_inner();
+        self.ensure_shard_keys(
+            &request.collection_name,
+            request.shard_key_selector.as_ref(),
+            &access,
+        )
+        .await?;
+
         overwrite_payload(
             self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+            request,
             None,
             None,
             access,
@@ -215,9 +286,17 @@ impl Points for PointsService {
 
         let access = extract_access(&mut request);
 
+        let request = request.into_inner();
+        self.ensure_shard_keys(
+            &request.collection_name,
+            request.operations.iter().filter_map(written_shard_key),
+            &access,
+        )
+        .await?;
+
         update_batch(
             self.dispatcher.toc(&access).clone(),
-            request.into_inner(),
+            request,
             None,
             None,
             access,
@@ -19,7 +19,8 @@ use api::grpc::qdrant::{
 use api::rest::{OrderByInterface, ShardKeySelector};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    try_discover_request_from_grpc, try_points_selector_from_grpc, upsert_mode_from_proto,
+    vector_dedup_from_proto, write_ordering_from_proto,
 };
 use collection::operations::payload_ops::DeletePayload;
 use collection::operations::point_ops::{
@@ -113,14 +114,34 @@ pub async fn upsert(
         points,
         ordering,
         shard_key_selector,
+        mode,
+        expected_versions,
+        version_key,
+        dedup,
     } = upsert_points;
     let points = points
         .into_iter()
         .map(|point| point.try_into())
         .collect::<Result<_, _>>()?;
+    let expected_versions = if expected_versions.is_empty() {
+        None
+    } else {
+        Some(
+            expected_versions
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        )
+    };
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        mode: Some(upsert_mode_from_proto(mode)?),
+        expected_versions,
+        version_key: version_key
+            .map(|key| json_path_from_proto(&key))
+            .transpose()?,
+        dedup: vector_dedup_from_proto(dedup)?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -522,6 +543,8 @@ pub async fn update_batch(
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
                 shard_key_selector,
+                dedup,
+                mode,
             }) => {
                 upsert(
                     toc.clone(),
@@ -531,6 +554,10 @@ pub async fn update_batch(
                         points,
                         ordering,
                         shard_key_selector,
+                        mode,
+                        expected_versions: Vec::new(),
+                        version_key: None,
+                        dedup,
                     },
                     clock_tag,
                     shard_selection,
@@ -6,7 +6,8 @@ use api::grpc::qdrant::points_internal_server::PointsInternal;
 use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
-    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
+    DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal,
+    DistinctValuesInternal, DistinctValuesResponseInternal, FacetCountsInternal,
     FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
     PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
     QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
@@ -17,7 +18,7 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
-use segment::data_types::facets::{FacetParams, FacetResponse};
+use segment::data_types::facets::{DistinctValuesParams, FacetParams, FacetResponse};
 use segment::json_path::JsonPath;
 use segment::types::Filter;
 use storage::content_manager::toc::TableOfContent;
@@ -136,6 +137,47 @@ async fn facet_counts_internal(
     Ok(Response::new(response))
 }
 
+async fn distinct_values_internal(
+    toc: &TableOfContent,
+    request: DistinctValuesInternal,
+) -> Result<Response<DistinctValuesResponseInternal>, Status> {
+    let timing = Instant::now();
+
+    let DistinctValuesInternal {
+        collection_name,
+        key,
+        filter,
+        limit,
+        shard_id,
+        timeout,
+    } = request;
+
+    let shard_selection = ShardSelectorInternal::ShardId(shard_id);
+
+    let request = DistinctValuesParams {
+        key: JsonPath::from_str(&key)
+            .map_err(|_| Status::invalid_argument("Failed to parse distinct values key"))?,
+        limit: Some(limit as usize),
+        filter: filter.map(Filter::try_from).transpose()?,
+    };
+
+    let values = toc
+        .distinct_values_internal(
+            &collection_name,
+            request,
+            shard_selection,
+            timeout.map(Duration::from_secs),
+        )
+        .await?;
+
+    let response = DistinctValuesResponseInternal {
+        values: values.into_iter().map(From::from).collect_vec(),
+        time: timing.elapsed().as_secs_f64(),
+    };
+
+    Ok(Response::new(response))
+}
+
 #[tonic::async_trait]
 impl PointsInternal for PointsInternalService {
     async fn upsert(
@@ -562,4 +604,13 @@ impl PointsInternal for PointsInternalService {
 
         facet_counts_internal(self.toc.as_ref(), request.into_inner()).await
     }
+
+    async fn distinct_values(
+        &self,
+        request: Request<DistinctValuesInternal>,
+    ) -> Result<Response<DistinctValuesResponseInternal>, Status> {
+        validate_and_log(request.get_ref());
+
+        distinct_values_internal(self.toc.as_ref(), request.into_inner()).await
+    }
 }
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Instant;
 
+use api::grpc::conversions::json_path_from_proto;
 use api::grpc::qdrant::shard_snapshots_server::ShardSnapshots;
 use api::grpc::qdrant::snapshots_server::Snapshots;
 use api::grpc::qdrant::{
@@ -42,7 +43,14 @@ impl Snapshots for SnapshotsService {
     ) -> Result<Response<CreateSnapshotResponse>, Status> {
         validate(request.get_ref())?;
         let access = extract_access(&mut request);
-        let collection_name = request.into_inner().collection_name;
+        let CreateSnapshotRequest {
+            collection_name,
+            exclude_payload_keys,
+        } = request.into_inner();
+        let exclude_payload_keys = exclude_payload_keys
+            .iter()
+            .map(|key| json_path_from_proto(key))
+            .collect::<Result<Vec<_>, _>>()?;
         let timing = Instant::now();
         let dispatcher = self.dispatcher.clone();
 
@@ -50,6 +58,7 @@ impl Snapshots for SnapshotsService {
             Arc::clone(dispatcher.toc(&access)),
             access,
             &collection_name,
+            exclude_payload_keys,
         )
         .await?;
 
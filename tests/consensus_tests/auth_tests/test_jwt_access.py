@@ -130,6 +130,13 @@ ACTION_ACCESS = {
         "qdrant.Collections/CollectionClusterInfo",
         coll_rw_payload=False,
     ),
+    "check_hash_ring_consistency": EndpointAccess(
+        True,
+        True,
+        True,
+        "GET /collections/{collection_name}/cluster/hash_ring",
+        coll_rw_payload=False,
+    ),
     "collection_exists": EndpointAccess(
         True,
         True,
@@ -392,6 +399,13 @@ ACTION_ACCESS = {
         "qdrant.Points/Upsert",
         coll_rw_payload=False,
     ),
+    "stream_upsert_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "PUT /collections/{collection_name}/points/stream",
+        coll_rw_payload=False,
+    ),
     "update_points_batch": EndpointAccess(
         False,
         True,
@@ -407,6 +421,13 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/delete",
         "qdrant.Points/Delete",
     ),
+    "clear_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/clear",
+        coll_rw_payload=False,
+    ),
     "update_vectors": EndpointAccess(
         False,
         True,
@@ -468,6 +489,12 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/search",
         "qdrant.Points/Search",
     ),
+    "search_points_ids": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/search/ids",
+    ),
     "search_points_batch": EndpointAccess(
         True,
         True,
@@ -538,6 +565,12 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/query/groups",
         "qdrant.Points/QueryGroups",
     ),
+    "query_points_plan": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/query/plan"
+    ),
+    "query_points_explain": EndpointAccess(
+        True, True, True, "POST /collections/{collection_name}/points/query/explain"
+    ),
     "search_points_matrix_offsets": EndpointAccess(
         True,
         True,
@@ -966,6 +999,13 @@ def test_get_collection_cluster_info():
     )
 
 
+def test_check_hash_ring_consistency():
+    check_access(
+        "check_hash_ring_consistency",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_collection_exists():
     check_access(
         "collection_exists",
@@ -1437,6 +1477,16 @@ def test_upsert_points():
     )
 
 
+def test_stream_upsert_points():
+    check_access(
+        "stream_upsert_points",
+        path_params={"collection_name": COLL_NAME},
+        rest_req_kwargs={
+            "data": '{"id": 1, "vector": [1, 2, 3, 4]}\n{"id": 2, "vector": [1, 2, 3, 4]}\n',
+        },
+    )
+
+
 def test_update_points_batch():
     rest_operations = [
         {"upsert": {"shard_key": SHARD_KEY, "points": [{"id": 1, "vector": [1, 2, 3, 4]}]}},
@@ -1536,6 +1586,13 @@ def test_delete_points():
     )
 
 
+def test_clear_points():
+    check_access(
+        "clear_points",
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_update_vectors():
     check_access(
         "update_vectors",
@@ -1636,6 +1693,14 @@ def test_search_points():
     )
 
 
+def test_search_points_ids():
+    check_access(
+        "search_points_ids",
+        rest_request={"vector": [1, 2, 3, 4], "limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_search_points_batch():
     query = {"vector": [1, 2, 3, 4], "limit": 10}
     check_access(
@@ -1807,6 +1872,22 @@ def test_query_points_groups():
     )
 
 
+def test_query_points_plan():
+    check_access(
+        "query_points_plan",
+        path_params={"collection_name": COLL_NAME},
+        rest_request={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3},
+    )
+
+
+def test_query_points_explain():
+    check_access(
+        "query_points_explain",
+        path_params={"collection_name": COLL_NAME},
+        rest_request={"query": [0.1, 0.2, 0.3, 0.4], "limit": 3},
+    )
+
+
 def test_search_points_matrix_offsets():
     check_access(
         "search_points_matrix_offsets",
@@ -279,3 +279,81 @@ def test_shard_stream_transfer_fast_burst(tmp_path: pathlib.Path):
 
 
 
+
+
+# Replicate only points matching a filter from one node to another
+#
+# Test that the new replica holds exactly the matching points, and that it
+# becomes a listener which is not used for reads
+def test_shard_stream_transfer_filtered(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS, 20000)
+
+    # A single shard, so that counting the collection counts the transferred shard
+    create_collection(peer_api_uris[0], shard_number=1, replication_factor=1)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+
+    # Filtered transfers require an index for the filtered key
+    r = requests.put(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/index?wait=true", json={
+            "field_name": "city",
+            "field_schema": "keyword",
+        })
+    assert_http_ok(r)
+
+    upsert_random_points(peer_api_uris[0], 1000)
+
+    cluster_infos = [get_collection_cluster_info(uri, COLLECTION_NAME) for uri in peer_api_uris]
+    source = next(i for i, info in enumerate(cluster_infos) if len(info['local_shards']) == 1)
+    target = (source + 1) % N_PEERS
+
+    from_peer_id = cluster_infos[source]['peer_id']
+    to_peer_id = cluster_infos[target]['peer_id']
+
+    city_filter = {"must": [{"key": "city", "match": {"value": "London"}}]}
+
+    r = requests.post(
+        f"{peer_api_uris[source]}/collections/{COLLECTION_NAME}/points/count", json={
+            "exact": True,
+            "filter": city_filter,
+        })
+    assert_http_ok(r)
+    matching_count = r.json()["result"]['count']
+    assert 0 < matching_count < 1000
+
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "replicate_shard": {
+                "shard_id": 0,
+                "from_peer_id": from_peer_id,
+                "to_peer_id": to_peer_id,
+                "method": "stream_records",
+                "filter": city_filter,
+            }
+        })
+    assert_http_ok(r)
+
+    # Wait for end of shard transfer
+    wait_for_collection_shard_transfers_count(peer_api_uris[0], COLLECTION_NAME, 0)
+
+    # Target holds only the matching points, as a listener
+    target_info = get_collection_cluster_info(peer_api_uris[target], COLLECTION_NAME)
+    assert len(target_info['local_shards']) == 1
+    assert target_info['local_shards'][0]['state'] == "Listener"
+    assert target_info['local_shards'][0]['points_count'] == matching_count
+
+    # Source keeps all points, and reads are still served by it on every peer
+    source_info = get_collection_cluster_info(peer_api_uris[source], COLLECTION_NAME)
+    assert source_info['local_shards'][0]['state'] == "Active"
+    for uri in peer_api_uris:
+        r = requests.post(
+            f"{uri}/collections/{COLLECTION_NAME}/points/count", json={
+                "exact": True
+            }
+        )
+        assert_http_ok(r)
+        assert r.json()["result"]['count'] == 1000
@@ -0,0 +1,85 @@
+import json
+import os
+
+import pytest
+import requests
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+
+QDRANT_HOST = os.environ.get("QDRANT_HOST", "localhost:6333")
+collection_name = 'test_collection_ndjson'
+
+NDJSON = "application/x-ndjson"
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def parse_ndjson(response):
+    assert response.ok, response.text
+    assert response.headers["content-type"].startswith(NDJSON)
+    lines = response.text.splitlines()
+    assert response.text.endswith("\n")
+    return [json.loads(line) for line in lines]
+
+
+def test_scroll_ndjson():
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/{collection_name}/points/scroll",
+        headers={"Accept": NDJSON},
+        json={"limit": 100, "with_payload": True},
+    )
+    points = parse_ndjson(response)
+
+    assert sorted(point["id"] for point in points) == list(range(1, 9))
+    assert all("payload" in point for point in points)
+    assert "next-page-offset" not in response.headers
+
+
+def test_scroll_ndjson_next_page_offset():
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/{collection_name}/points/scroll",
+        headers={"Accept": NDJSON},
+        json={"limit": 5},
+    )
+    points = parse_ndjson(response)
+
+    assert [point["id"] for point in points] == [1, 2, 3, 4, 5]
+    assert response.headers["next-page-offset"] == "6"
+
+
+def test_search_ndjson():
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/{collection_name}/points/search",
+        headers={"Accept": f"text/plain, {NDJSON};q=0.9"},
+        json={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 10},
+    )
+    points = parse_ndjson(response)
+
+    assert sorted(point["id"] for point in points) == list(range(1, 9))
+    scores = [point["score"] for point in points]
+    assert scores == sorted(scores, reverse=True)
+
+
+def test_search_json_by_default():
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/{collection_name}/points/search",
+        json={"vector": [0.2, 0.1, 0.9, 0.7], "limit": 3},
+    )
+    assert response.ok, response.text
+    assert response.headers["content-type"].startswith("application/json")
+    assert len(response.json()["result"]) == 3
+
+
+def test_ndjson_error():
+    response = requests.post(
+        f"http://{QDRANT_HOST}/collections/missing_collection/points/scroll",
+        headers={"Accept": NDJSON},
+        json={"limit": 10},
+    )
+    assert response.status_code == 404
+    assert response.json()["status"]["error"]